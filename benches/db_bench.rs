@@ -0,0 +1,155 @@
+// ============================================================================
+// BENCHMARKS DE LA BASE DE DONNÉES
+// ============================================================================
+//
+// Références de performance des chemins chauds, sur une base en mémoire
+// (DatabaseManager::new_in_memory, schéma complet) pour des mesures
+// reproductibles:
+// - bench_insert_batch_1000: 1000 bougies en une transaction, comme
+//   CandleRetriever::insert_batch (insert_candle_records)
+// - bench_gap_fill_large: un trou de 5000 bougies comblé par interpolation
+// - bench_candle_query_by_range: lecture de 10000 bougies d'une plage
+//   (query_candle_records, même filtre et même tri que /api/candles)
+// - bench_indicator_recalc_batch_1000: RSI 14 + EMA 20 recalculés après un
+//   batch de 1000 bougies au milieu de 20000 (IndicatorRegistry, surcoût
+//   de chaque batch du backfill)
+//
+// Usage:
+//   cargo bench --bench db_bench
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use rust_candles_retriever::database::{
+    DatabaseManager, insert_candle_records, query_candle_records,
+};
+use rust_candles_retriever::gap_filler::{FillStrategy, GapFiller};
+use rust_candles_retriever::indicators::registry::IndicatorRegistry;
+use rust_candles_retriever::models::CandleRecord;
+use std::hint::black_box;
+
+const PROVIDER: &str = "binance";
+const SYMBOL: &str = "BTCUSDT";
+const BASE_TIME: i64 = 1704067200000; // 2024-01-01 00:00 UTC
+const INTERVAL: i64 = 300_000; // 5 minutes
+
+/// Bougie 5m réelle à la position `position` de la série
+fn candle(position: i64) -> CandleRecord {
+    let open_time = BASE_TIME + position * INTERVAL;
+    let close = 50_000.0 + (position % 100) as f64;
+    CandleRecord {
+        provider: PROVIDER.to_string(),
+        symbol: SYMBOL.to_string(),
+        timeframe: "5m".to_string(),
+        open_time,
+        open: close - 5.0,
+        high: close + 10.0,
+        low: close - 10.0,
+        close,
+        volume: 1.5,
+        close_time: open_time + INTERVAL - 1,
+        quote_asset_volume: 75_000.0,
+        number_of_trades: 42,
+        taker_buy_base_asset_volume: 0.7,
+        taker_buy_quote_asset_volume: 35_000.0,
+        interpolated: 0,
+        derived_from: None,
+    }
+}
+
+/// Base en mémoire contenant les bougies des positions données
+fn database_with(positions: impl Iterator<Item = i64>) -> DatabaseManager {
+    let mut db = DatabaseManager::new_in_memory().expect("base en mémoire");
+    let candles: Vec<CandleRecord> = positions.map(candle).collect();
+    db.insert_candles(&candles).expect("insertion");
+    db
+}
+
+fn bench_insert_batch_1000(c: &mut Criterion) {
+    let candles: Vec<CandleRecord> = (0..1000).map(candle).collect();
+
+    c.bench_function("insert_batch_1000", |b| {
+        b.iter_batched(
+            || DatabaseManager::new_in_memory().expect("base en mémoire"),
+            |mut db| {
+                let tx = db.connection_mut().transaction().unwrap();
+                let inserted = insert_candle_records(&tx, black_box(&candles)).unwrap();
+                tx.commit().unwrap();
+                assert_eq!(inserted, 1000);
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn bench_gap_fill_large(c: &mut Criterion) {
+    // Bornes aux positions 0 et 5001: un seul trou de 5000 bougies
+    c.bench_function("gap_fill_large", |b| {
+        b.iter_batched(
+            || database_with([0, 5001].into_iter()),
+            |mut db| {
+                let filled = GapFiller::fill_gaps_in_range(
+                    db.connection_mut(),
+                    PROVIDER,
+                    SYMBOL,
+                    "5m",
+                    BASE_TIME,
+                    BASE_TIME + 5001 * INTERVAL,
+                    FillStrategy::Linear,
+                )
+                .unwrap();
+                assert_eq!(filled, 5000);
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn bench_candle_query_by_range(c: &mut Criterion) {
+    let db = database_with(0..20_000);
+    let range = BASE_TIME + 5_000 * INTERVAL..=BASE_TIME + 14_999 * INTERVAL;
+
+    c.bench_function("candle_query_by_range", |b| {
+        b.iter(|| {
+            let candles = query_candle_records(
+                db.connection(),
+                PROVIDER,
+                SYMBOL,
+                "5m",
+                black_box(range.clone()),
+                None,
+            )
+            .unwrap();
+            assert_eq!(candles.len(), 10_000);
+        })
+    });
+}
+
+fn bench_indicator_recalc_batch_1000(c: &mut Criterion) {
+    let mut db = database_with(0..20_000);
+    let registry =
+        IndicatorRegistry::from_specs(&["rsi:14".parse().unwrap(), "ema:20".parse().unwrap()])
+            .unwrap();
+
+    c.bench_function("indicator_recalc_batch_1000", |b| {
+        b.iter(|| {
+            registry
+                .recalculate(
+                    db.connection_mut(),
+                    PROVIDER,
+                    SYMBOL,
+                    "5m",
+                    black_box(BASE_TIME + 10_000 * INTERVAL),
+                    BASE_TIME + 10_999 * INTERVAL,
+                )
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_insert_batch_1000,
+    bench_gap_fill_large,
+    bench_candle_query_by_range,
+    bench_indicator_recalc_batch_1000
+);
+criterion_main!(benches);
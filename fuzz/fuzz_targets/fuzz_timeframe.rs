@@ -0,0 +1,61 @@
+// ============================================================================
+// FUZZING DES TIMEFRAMES
+// ============================================================================
+//
+// Entrée: chaîne arbitraire utilisée comme timeframe (paramètre utilisateur
+// ?timeframe=..., --timeframes, fichiers importés)
+//
+// Invariants vérifiés (jamais de panique, ni de débordement en debug):
+// - parse_timeframe: Ok(intervalle > 0) pour ALL_TIMEFRAMES, Err sinon
+// - round_to_timeframe / next_candle_time: bornes de la bougie contenant le
+//   timestamp (début <= timestamp < suivante), ou Err pour un timestamp hors
+//   de la plage représentable (i64::MIN, i64::MAX...)
+//
+// Usage:
+//   cargo +nightly fuzz run fuzz_timeframe -- -max_total_time=60
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_candles_retriever::utils::{
+    ALL_TIMEFRAMES, is_valid_timeframe, next_candle_time, parse_timeframe, round_to_timeframe,
+};
+
+/// Timestamps testés pour chaque timeframe: extrêmes, epoch, 2024-01-01
+const TIMESTAMPS: [i64; 6] = [i64::MIN, -1, 0, 1704067200000, i64::MAX - 1, i64::MAX];
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(timeframe) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    match parse_timeframe(timeframe) {
+        Ok(interval) => {
+            assert!(interval > 0, "{:?}: intervalle {}", timeframe, interval);
+            assert!(ALL_TIMEFRAMES.contains(&timeframe));
+        }
+        Err(_) => assert!(!ALL_TIMEFRAMES.contains(&timeframe)),
+    }
+    assert_eq!(is_valid_timeframe(timeframe), parse_timeframe(timeframe).is_ok());
+
+    // Timestamp tiré de l'entrée elle-même: le fuzzer explore aussi les dates
+    let mut bytes = [0u8; 8];
+    let prefix = data.len().min(8);
+    bytes[..prefix].copy_from_slice(&data[..prefix]);
+    let derived = i64::from_le_bytes(bytes);
+
+    for timestamp in TIMESTAMPS.into_iter().chain([derived]) {
+        let start = round_to_timeframe(timestamp, timeframe);
+        let next = next_candle_time(timestamp, timeframe);
+        if let (Ok(start), Ok(next)) = (start, next) {
+            assert!(
+                start <= timestamp && timestamp < next,
+                "{:?} @ {}: [{}, {})",
+                timeframe,
+                timestamp,
+                start,
+                next
+            );
+        }
+    }
+});
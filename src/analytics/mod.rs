@@ -0,0 +1,4 @@
+/// Module des analyses de séries de prix (hors indicateurs techniques)
+///
+/// - returns: rendements simples (%), logarithmiques et cumulés du close
+pub mod returns;
@@ -0,0 +1,189 @@
+/// Module des rendements du close
+///
+/// - Simple (pct): (close[i] / close[i-1] - 1) × 100
+/// - Logarithmique (log): ln(close[i] / close[i-1]), additif: la somme des
+///   rendements logarithmiques d'une série vaut ln(dernier / premier)
+/// - Cumulé (cumulative): (close[i] / close[0] - 1) × 100, depuis le
+///   premier close de la série
+///
+/// SUBTILITÉ: pour une plage restreinte, le rendement simple ou
+/// logarithmique de la première bougie utilise le close de la bougie qui
+/// précède la plage; le rendement cumulé part du premier close de la plage
+/// (0 pour cette bougie)
+use crate::error::Result;
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+/// Type de rendement (paramètre `type` de /api/analytics/returns, colonne
+/// kind de la table returns)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReturnKind {
+    Pct,
+    Log,
+    Cumulative,
+}
+
+impl ReturnKind {
+    /// Nom stocké dans returns.kind (celui de l'API)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReturnKind::Pct => "pct",
+            ReturnKind::Log => "log",
+            ReturnKind::Cumulative => "cumulative",
+        }
+    }
+}
+
+/// Rendement d'une bougie
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ReturnValue {
+    pub open_time: i64,
+    pub value: f64,
+}
+
+/// Rendement logarithmique de chaque close d'une série triée
+///
+/// None pour le premier close (pas de close précédent)
+pub fn calculate_log_returns(closes: &[f64]) -> Vec<Option<f64>> {
+    std::iter::once(None)
+        .take(closes.len())
+        .chain(closes.windows(2).map(|w| Some((w[1] / w[0]).ln())))
+        .collect()
+}
+
+/// Rendement simple de chaque close d'une série triée, en pourcentage
+///
+/// None pour le premier close (pas de close précédent)
+///
+/// EXEMPLE: closes [100, 110, 99] → [None, 10, -10]
+pub fn calculate_pct_returns(closes: &[f64]) -> Vec<Option<f64>> {
+    std::iter::once(None)
+        .take(closes.len())
+        .chain(closes.windows(2).map(|w| Some((w[1] / w[0] - 1.0) * 100.0)))
+        .collect()
+}
+
+/// Rendement cumulé de chaque close d'une série triée depuis le premier,
+/// en pourcentage (0 pour le premier close)
+///
+/// EXEMPLE: closes [100, 110, 99] → [0, 10, -1]
+pub fn calculate_cumulative_returns(closes: &[f64]) -> Vec<Option<f64>> {
+    closes
+        .iter()
+        .map(|close| Some((close / closes[0] - 1.0) * 100.0))
+        .collect()
+}
+
+/// Rendements des bougies de `range` (open_time en ms), triés
+///
+/// pct / log: la bougie qui précède la plage fournit le close précédent de
+/// la première (première bougie de la série omise); cumulative: depuis le
+/// premier close de la plage
+pub fn returns_series(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    range: RangeInclusive<i64>,
+    kind: ReturnKind,
+) -> Result<Vec<ReturnValue>> {
+    let lookback: i64 = if kind == ReturnKind::Cumulative { 0 } else { 1 };
+    let mut stmt = conn.prepare_cached(
+        "SELECT open_time, close FROM candlesticks
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+           AND open_time >= COALESCE(
+               (SELECT MIN(open_time) FROM (
+                    SELECT open_time FROM candlesticks
+                    WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3 AND open_time < ?4
+                    ORDER BY open_time DESC LIMIT ?6)),
+               ?4)
+           AND open_time <= ?5
+         ORDER BY open_time ASC",
+    )?;
+    let candles = stmt
+        .query_map(
+            params![
+                provider,
+                symbol,
+                timeframe,
+                range.start(),
+                range.end(),
+                lookback
+            ],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, f64>(1)?)),
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let closes: Vec<f64> = candles.iter().map(|c| c.1).collect();
+    let values = match kind {
+        ReturnKind::Pct => calculate_pct_returns(&closes),
+        ReturnKind::Log => calculate_log_returns(&closes),
+        ReturnKind::Cumulative => calculate_cumulative_returns(&closes),
+    };
+
+    Ok(candles
+        .iter()
+        .zip(values)
+        .filter(|((open_time, _), _)| range.contains(open_time))
+        .filter_map(|((open_time, _), value)| {
+            value.map(|value| ReturnValue {
+                open_time: *open_time,
+                value,
+            })
+        })
+        .collect())
+}
+
+/// Calcule et enregistre les trois types de rendement de toute la série
+/// dans la table returns (valeurs existantes de la série remplacées), en
+/// une transaction
+///
+/// DESIGN: toute la série, jamais une plage: le rendement cumulé enregistré
+/// part toujours de la première bougie stockée
+///
+/// RETOUR: Nombre de valeurs enregistrées (tous types)
+pub fn store_returns(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+) -> Result<i64> {
+    let series = [ReturnKind::Pct, ReturnKind::Log, ReturnKind::Cumulative]
+        .into_iter()
+        .map(|kind| {
+            returns_series(conn, provider, symbol, timeframe, 0..=i64::MAX, kind)
+                .map(|values| (kind, values))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let tx = conn.transaction()?;
+    let mut stored = 0;
+    {
+        tx.execute(
+            "DELETE FROM returns WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3",
+            params![provider, symbol, timeframe],
+        )?;
+        let mut stmt = tx.prepare(
+            "INSERT INTO returns (provider, symbol, timeframe, kind, open_time, value)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        for (kind, values) in &series {
+            for value in values {
+                stmt.execute(params![
+                    provider,
+                    symbol,
+                    timeframe,
+                    kind.as_str(),
+                    value.open_time,
+                    value.value
+                ])?;
+                stored += 1;
+            }
+        }
+    }
+    tx.commit()?;
+
+    Ok(stored)
+}
@@ -0,0 +1,210 @@
+/// Module d'authentification par clé API pour le serveur web
+///
+/// ARCHITECTURE:
+/// - ApiKeys: clés valides chargées depuis API_KEYS (clair, séparées par des
+///   virgules) et/ou depuis api_keys.txt (un hash bcrypt par ligne)
+/// - ApiKeyMiddleware: middleware actix-web vérifiant le header X-API-Key,
+///   à appliquer uniquement aux endpoints d'écriture (les lectures restent publiques)
+/// - StreamToken: jeton optionnel du flux temps réel (STREAM_AUTH_TOKEN), passé
+///   en paramètre de requête car EventSource ne permet pas d'ajouter de header
+use actix_web::body::EitherBody;
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready};
+use actix_web::{Error, HttpResponse};
+use std::future::{Future, Ready, ready};
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Header HTTP portant la clé API
+pub const API_KEY_HEADER: &str = "X-API-Key";
+
+/// Fichier de hashes bcrypt chargé par défaut
+pub const DEFAULT_API_KEYS_FILE: &str = "api_keys.txt";
+
+/// Variable d'environnement portant le jeton du flux temps réel
+pub const STREAM_TOKEN_ENV: &str = "STREAM_AUTH_TOKEN";
+
+/// Ensemble des clés API acceptées
+///
+/// DESIGN: Sans aucune clé configurée, toute requête protégée est refusée
+#[derive(Debug, Default)]
+pub struct ApiKeys {
+    plain: Vec<String>,
+    hashes: Vec<String>,
+}
+
+impl ApiKeys {
+    /// Charge les clés depuis API_KEYS et depuis api_keys.txt (si présent)
+    pub fn load() -> Self {
+        let plain = std::env::var("API_KEYS")
+            .map(|v| Self::parse_env(&v))
+            .unwrap_or_default();
+        let hashes = std::fs::read_to_string(DEFAULT_API_KEYS_FILE)
+            .map(|content| Self::parse_hash_file(&content))
+            .unwrap_or_default();
+
+        ApiKeys { plain, hashes }
+    }
+
+    /// Nombre de clés configurées
+    pub fn len(&self) -> usize {
+        self.plain.len() + self.hashes.len()
+    }
+
+    /// true si aucune clé n'est configurée
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Vérifie une clé fournie par un client
+    ///
+    /// NOTE: bcrypt::verify est volontairement lent (coût 12), acceptable
+    /// car seuls les endpoints d'écriture sont protégés
+    pub fn verify(&self, key: &str) -> bool {
+        if key.is_empty() {
+            return false;
+        }
+
+        self.plain.iter().any(|k| k == key)
+            || self
+                .hashes
+                .iter()
+                .any(|hash| bcrypt::verify(key, hash).unwrap_or(false))
+    }
+
+    /// Parse "key1,key2" en ignorant les entrées vides
+    fn parse_env(value: &str) -> Vec<String> {
+        value
+            .split(',')
+            .map(|k| k.trim().to_string())
+            .filter(|k| !k.is_empty())
+            .collect()
+    }
+
+    /// Parse un hash bcrypt par ligne (lignes vides et # ignorées)
+    fn parse_hash_file(content: &str) -> Vec<String> {
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Jeton d'accès au flux temps réel (/api/stream/candles?token=...)
+///
+/// DESIGN: Sans jeton configuré, le flux reste public (compatibilité avec les
+/// clients existants). Avec STREAM_AUTH_TOKEN, seul le jeton exact est accepté
+///
+/// SÉCURITÉ:
+/// - Le jeton voyage dans l'URL: il apparaît dans les logs d'accès des proxys
+///   et dans l'historique du navigateur. Utiliser HTTPS et un jeton dédié,
+///   jamais une clé API d'écriture
+/// - Comparaison en temps constant (ring): la durée de la vérification ne
+///   révèle pas le nombre d'octets corrects. Seule la longueur peut fuiter
+#[derive(Debug, Clone, Default)]
+pub struct StreamToken {
+    expected: Option<String>,
+}
+
+impl StreamToken {
+    /// Jeton attendu, None pour un flux public (une valeur vide compte comme absente)
+    pub fn new(expected: Option<String>) -> Self {
+        StreamToken {
+            expected: expected.filter(|token| !token.is_empty()),
+        }
+    }
+
+    /// Charge le jeton depuis STREAM_AUTH_TOKEN
+    pub fn load() -> Self {
+        Self::new(std::env::var(STREAM_TOKEN_ENV).ok())
+    }
+
+    /// true si le flux exige un jeton
+    pub fn is_required(&self) -> bool {
+        self.expected.is_some()
+    }
+
+    /// Vérifie le jeton fourni par un client (toujours vrai sans jeton configuré)
+    ///
+    /// NOTE: ring marque constant_time comme déprécié sans remplaçant public,
+    /// la fonction reste la comparaison en temps constant disponible ici
+    #[allow(deprecated)]
+    pub fn verify(&self, provided: Option<&str>) -> bool {
+        match &self.expected {
+            None => true,
+            Some(expected) => provided.is_some_and(|token| {
+                ring::constant_time::verify_slices_are_equal(token.as_bytes(), expected.as_bytes())
+                    .is_ok()
+            }),
+        }
+    }
+}
+
+/// Middleware vérifiant le header X-API-Key
+///
+/// USAGE: web::scope("/api").wrap(ApiKeyMiddleware::new(keys)).service(...)
+pub struct ApiKeyMiddleware {
+    keys: Arc<ApiKeys>,
+}
+
+impl ApiKeyMiddleware {
+    pub fn new(keys: Arc<ApiKeys>) -> Self {
+        ApiKeyMiddleware { keys }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ApiKeyMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = ApiKeyMiddlewareService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ApiKeyMiddlewareService {
+            service,
+            keys: Arc::clone(&self.keys),
+        }))
+    }
+}
+
+/// Service produit par ApiKeyMiddleware (un par worker actix)
+pub struct ApiKeyMiddlewareService<S> {
+    service: S,
+    keys: Arc<ApiKeys>,
+}
+
+impl<S, B> Service<ServiceRequest> for ApiKeyMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let authorized = req
+            .headers()
+            .get(API_KEY_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|key| self.keys.verify(key));
+
+        if !authorized {
+            let response = HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "invalid or missing API key"
+            }));
+            return Box::pin(async move { Ok(req.into_response(response).map_into_right_body()) });
+        }
+
+        let fut = self.service.call(req);
+        Box::pin(async move { Ok(fut.await?.map_into_left_body()) })
+    }
+}
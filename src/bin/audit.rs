@@ -0,0 +1,268 @@
+// ============================================================================
+// BINAIRE D'AUDIT DES BASES
+// ============================================================================
+//
+// Audit de chaque fichier .db d'un répertoire (verify::audit_database):
+// intégrité SQLite, unicité des bougies, close_time, cohérence de
+// timeframe_status, valeurs impossibles et bougies identiques
+// (verify::verify_values), et avec --cross-check la cohérence entre timeframes
+// (verify::audit_cross_timeframe), et la comparaison des séries stockées
+// sous plusieurs providers (verify::audit_providers). Code de sortie 1 si
+// au moins un fichier échoue
+//
+// Usage:
+//   cargo run --bin audit
+//   cargo run --bin audit -- --db-dir data --json > audit.json
+//   cargo run --bin audit -- --cross-check 5m:1h,1h:4h
+//   cargo run --bin audit -- --provider-tolerance 0.005
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use rust_candles_retriever::database::open_configured;
+use rust_candles_retriever::verify::{
+    AUDIT_SAMPLE_LIMIT, AuditReport, CROSS_CHECK_TOLERANCE, DEFAULT_FLATLINE_RUN,
+    PROVIDER_TOLERANCE, audit_cross_timeframe, audit_database, audit_providers, parse_cross_check,
+};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Arguments CLI de l'audit
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Auditer les bases SQLite d'un répertoire", long_about = None)]
+struct Args {
+    /// Répertoire contenant les fichiers .db
+    #[arg(long, default_value = ".")]
+    db_dir: String,
+
+    /// Rapport JSON sur la sortie standard (au lieu du résumé lisible)
+    #[arg(long)]
+    json: bool,
+
+    /// Nombre de bougies identiques consécutives signalé comme anomalie
+    #[arg(long, default_value_t = DEFAULT_FLATLINE_RUN)]
+    flatline_run: usize,
+
+    /// Compare chaque timeframe supérieur à l'agrégation du timeframe
+    /// inférieur, pour chaque paire (inférieur:supérieur, ex: 5m:1h)
+    #[arg(long, value_delimiter = ',')]
+    cross_check: Vec<String>,
+
+    /// Écart relatif toléré par --cross-check
+    #[arg(long, default_value_t = CROSS_CHECK_TOLERANCE)]
+    tolerance: f64,
+
+    /// Écart relatif de close toléré entre deux providers d'une même série
+    #[arg(long, default_value_t = PROVIDER_TOLERANCE)]
+    provider_tolerance: f64,
+}
+
+/// Résultat d'un fichier dans la sortie JSON
+#[derive(Serialize)]
+struct FileAudit {
+    file: String,
+    healthy: bool,
+    /// Erreur empêchant l'audit (fichier illisible, table absente...)
+    error: Option<String>,
+    report: Option<AuditReport>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let cross_checks = args
+        .cross_check
+        .iter()
+        .map(|spec| parse_cross_check(spec))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut databases: Vec<PathBuf> = std::fs::read_dir(&args.db_dir)
+        .with_context(|| format!("Lecture de {} impossible", args.db_dir))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "db"))
+        .collect();
+    databases.sort();
+
+    let audits: Vec<FileAudit> = databases
+        .iter()
+        .map(|path| {
+            let file = path.display().to_string();
+            match open_configured(path)
+                .map_err(anyhow::Error::from)
+                .and_then(|conn| {
+                    let mut report = audit_database(&conn, args.flatline_run)?;
+                    for (lower_tf, higher_tf) in &cross_checks {
+                        report.cross_checks.push(audit_cross_timeframe(
+                            &conn,
+                            lower_tf,
+                            higher_tf,
+                            args.tolerance,
+                        )?);
+                    }
+                    report.provider_comparisons = audit_providers(&conn, args.provider_tolerance)?;
+                    Ok(report)
+                }) {
+                Ok(report) => FileAudit {
+                    file,
+                    healthy: report.is_healthy(),
+                    error: None,
+                    report: Some(report),
+                },
+                Err(e) => FileAudit {
+                    file,
+                    healthy: false,
+                    error: Some(e.to_string()),
+                    report: None,
+                },
+            }
+        })
+        .collect();
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&audits)?);
+    } else if audits.is_empty() {
+        println!("Aucun fichier .db dans {}", args.db_dir);
+    } else {
+        audits.iter().for_each(print_audit);
+    }
+
+    if audits.iter().any(|audit| !audit.healthy) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Résumé lisible d'un fichier: une ligne, puis le détail des anomalies
+fn print_audit(audit: &FileAudit) {
+    let Some(report) = &audit.report else {
+        println!("✗ {}: {}", audit.file, audit.error.as_deref().unwrap_or(""));
+        return;
+    };
+
+    println!(
+        "{} {}: {} bougies ({} interpolées)",
+        if audit.healthy { "✓" } else { "✗" },
+        audit.file,
+        report.total_rows,
+        report.interpolated_rows
+    );
+    for message in &report.integrity_errors {
+        println!("  integrity_check: {}", message);
+    }
+    if report.duplicate_keys > 0 {
+        println!("  {} clés en double", report.duplicate_keys);
+    }
+    if !report.unknown_timeframes.is_empty() {
+        println!(
+            "  timeframes inconnus: {}",
+            report.unknown_timeframes.join(", ")
+        );
+    }
+    if report.close_time_mismatches > 0 {
+        println!("  {} close_time incohérents", report.close_time_mismatches);
+        for sample in &report.close_time_samples {
+            println!(
+                "    {} {} {} open_time {}: close_time {} (attendu {})",
+                sample.provider,
+                sample.symbol,
+                sample.timeframe,
+                sample.open_time,
+                sample.close_time,
+                sample.expected_close_time
+            );
+        }
+    }
+    for status in &report.status_mismatches {
+        println!(
+            "  timeframe_status {} {} {}: bornes {:?}..{:?}, bougies {:?}..{:?}",
+            status.provider,
+            status.symbol,
+            status.timeframe,
+            status.status_oldest,
+            status.status_newest,
+            status.actual_oldest,
+            status.actual_newest
+        );
+    }
+    for anomalies in &report.value_anomalies {
+        println!(
+            "  valeurs {} {} {}: {} anomalies réelles, {} interpolées",
+            anomalies.provider,
+            anomalies.symbol,
+            anomalies.timeframe,
+            anomalies.real.total(),
+            anomalies.interpolated.total()
+        );
+        for (label, counts) in [
+            ("réelles", &anomalies.real),
+            ("interpolées", &anomalies.interpolated),
+        ] {
+            if counts.total() > 0 {
+                println!(
+                    "    {}: high<low {}, high<corps {}, low>corps {}, volume<0 {}, prix 0 {}, séries identiques {} ({} bougies)",
+                    label,
+                    counts.high_below_low,
+                    counts.high_below_body,
+                    counts.low_above_body,
+                    counts.negative_volume,
+                    counts.zero_price,
+                    counts.flatline_runs,
+                    counts.flatline_candles
+                );
+            }
+        }
+    }
+    for check in &report.cross_checks {
+        if check.mismatches.is_empty() {
+            continue;
+        }
+        println!(
+            "  {} désaccords {} → {}",
+            check.mismatches.len(),
+            check.lower_timeframe,
+            check.higher_timeframe
+        );
+        for mismatch in check.mismatches.iter().take(AUDIT_SAMPLE_LIMIT) {
+            println!(
+                "    {} {} open_time {} {}: agrégé {} / stocké {}",
+                mismatch.provider,
+                mismatch.symbol,
+                mismatch.open_time,
+                mismatch.field,
+                mismatch.resampled,
+                mismatch.stored
+            );
+        }
+    }
+    for comparison in &report.provider_comparisons {
+        let counts: Vec<String> = comparison
+            .providers
+            .iter()
+            .map(|count| format!("{} {}", count.provider, count.count))
+            .collect();
+        println!(
+            "  {} {}: plusieurs providers ({})",
+            comparison.symbol,
+            comparison.timeframe,
+            counts.join(", ")
+        );
+        for pair in &comparison.pairs {
+            println!(
+                "    {} / {}: {} bougies communes, écart moyen {:.6}, {} au-delà de {}",
+                pair.reference,
+                pair.other,
+                pair.common_candles,
+                pair.mean_abs_close_diff,
+                pair.divergent_candles,
+                comparison.tolerance
+            );
+            if let Some(max) = &pair.max_divergence {
+                println!(
+                    "      écart maximal open_time {}: {} / {} ({:.4} %)",
+                    max.open_time,
+                    max.reference_close,
+                    max.other_close,
+                    max.relative_diff * 100.0
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+// ============================================================================
+// BINAIRE DE SAUVEGARDE DES BASES
+// ============================================================================
+//
+// Sauvegarde cohérente (API de sauvegarde SQLite, voir backup_database) de
+// chaque paire d'une base dans un répertoire horodaté:
+//   <output-dir>/YYYYMMDD_HHMMSS/<SYMBOL>.db
+// Utilisable pendant qu'un backfill écrit dans la base
+//
+// Usage:
+//   cargo run --bin backup
+//   cargo run --bin backup -- --db-file candlesticks.db --output-dir backups --symbols BTCUSDT,ETHUSDT
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use rust_candles_retriever::database::{backup_database, open_configured, snapshot_dir};
+use std::path::Path;
+
+/// Arguments CLI de la sauvegarde
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Sauvegarder chaque paire d'une base SQLite", long_about = None)]
+struct Args {
+    /// Fichier de base de données
+    #[arg(long, default_value = "candlesticks.db")]
+    db_file: String,
+
+    /// Répertoire racine des sauvegardes
+    #[arg(long, default_value = "backups")]
+    output_dir: String,
+
+    /// Paires à sauvegarder, séparées par des virgules (par défaut: toutes)
+    #[arg(long, value_delimiter = ',')]
+    symbols: Option<Vec<String>>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    if !Path::new(&args.db_file).exists() {
+        eprintln!(
+            "Erreur: Le fichier de base de données '{}' n'existe pas",
+            args.db_file
+        );
+        std::process::exit(1);
+    }
+    // open_configured: pas de migration, la base sauvegardée n'est pas modifiée
+    let conn = open_configured(&args.db_file)?;
+
+    let symbols: Vec<String> = match &args.symbols {
+        Some(symbols) => symbols.iter().map(|s| s.to_uppercase()).collect(),
+        None => {
+            let mut stmt =
+                conn.prepare("SELECT DISTINCT symbol FROM candlesticks ORDER BY symbol")?;
+            stmt.query_map([], |row| row.get(0))?
+                .collect::<std::result::Result<_, _>>()?
+        }
+    };
+    if symbols.is_empty() {
+        println!("Aucune paire dans {}", args.db_file);
+        return Ok(());
+    }
+
+    let dir = snapshot_dir(&args.output_dir);
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Création de {} impossible", dir.display()))?;
+
+    let mut failed = 0;
+    for symbol in &symbols {
+        let dest = dir.join(format!("{}.db", symbol));
+        match backup_database(&conn, &dest, Some(symbol)) {
+            Ok(report) => println!(
+                "✓ {}: {} bougies, {:.1} Mo → {}",
+                symbol,
+                report.candles,
+                report.bytes as f64 / (1024.0 * 1024.0),
+                report.path
+            ),
+            Err(e) => {
+                println!("✗ {}: {}", symbol, e);
+                failed += 1;
+            }
+        }
+    }
+
+    println!(
+        "Sauvegarde de {} paires dans {}{}",
+        symbols.len() - failed,
+        dir.display(),
+        if failed > 0 {
+            format!(", {} en erreur", failed)
+        } else {
+            String::new()
+        }
+    );
+    if failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,124 @@
+// ============================================================================
+// BINAIRE D'EXPORT CSV
+// ============================================================================
+//
+// Exporte une série vers un fichier CSV (tableur, scripts rapides), avec
+// toutes les colonnes de la table candlesticks sous leur nom SQL.
+// Les lignes sont écrites par blocs, sans charger la série en mémoire
+//
+// Usage:
+//   cargo run --bin export_csv -- --symbol BTCUSDT --timeframe 1h --out btc_1h.csv
+//   cargo run --bin export_csv -- --symbol BTCUSDT --timeframe 5m --out btc_5m.csv \
+//       --start 2024-01-01 --end 2024-01-31 --include-interpolated false --human-time
+
+use anyhow::{Context, Result, bail};
+use clap::{ArgAction, Parser};
+use rust_candles_retriever::database::open_configured;
+use rust_candles_retriever::export::{ExportFormat, write_csv_filtered};
+use rust_candles_retriever::utils::parse_date_ms;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// Arguments CLI de l'export CSV
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Exporter une série de bougies au format CSV", long_about = None)]
+struct Args {
+    /// Fichier de base de données
+    #[arg(long, default_value = "candlesticks.db")]
+    db: String,
+
+    /// Le symbole/paire de trading (ex: BTCUSDT)
+    #[arg(short, long)]
+    symbol: String,
+
+    /// Le timeframe à exporter (ex: 1h)
+    #[arg(short, long)]
+    timeframe: String,
+
+    /// Fichier CSV produit
+    #[arg(short, long)]
+    out: String,
+
+    /// Le provider (par défaut: binance)
+    #[arg(short, long, default_value = "binance")]
+    provider: String,
+
+    /// Date de début au format YYYY-MM-DD (par défaut: toute la série)
+    #[arg(long)]
+    start: Option<String>,
+
+    /// Date de fin incluse au format YYYY-MM-DD (par défaut: toute la série)
+    #[arg(long)]
+    end: Option<String>,
+
+    /// Inclure les bougies interpolées (false: bougies réelles uniquement)
+    #[arg(long, default_value_t = true, action = ArgAction::Set)]
+    include_interpolated: bool,
+
+    /// open_time et close_time en ISO 8601 (au lieu des millisecondes)
+    #[arg(long)]
+    human_time: bool,
+
+    /// Table exportée: candles (rsi non disponible, voir main)
+    #[arg(long, default_value = "candles")]
+    table: String,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    // NOTE: aucune table rsi_values n'existe dans ce schéma (le RSI n'est
+    // pas stocké), seule la table candlesticks peut être exportée
+    match args.table.as_str() {
+        "candles" | "candlesticks" => {}
+        "rsi" => bail!("Table rsi_values absente: aucune valeur RSI n'est stockée dans la base"),
+        other => bail!("Table inconnue: {} (attendu: candles)", other),
+    }
+
+    let start_ms = args.start.as_deref().map(parse_date_ms).transpose()?;
+    // Fin incluse: jusqu'à la dernière milliseconde du jour
+    let end_ms = args
+        .end
+        .as_deref()
+        .map(parse_date_ms)
+        .transpose()?
+        .map(|day| day + 86_400_000 - 1);
+
+    let conn = open_configured(&args.db)?;
+    let file =
+        File::create(&args.out).with_context(|| format!("Création de {} impossible", args.out))?;
+    let mut output = BufWriter::new(file);
+
+    // SUBTILITÉ RUST: le sink retourne un bool, l'erreur d'écriture est
+    // conservée à part pour être remontée après l'arrêt de la lecture
+    let mut write_error = None;
+    let rows = write_csv_filtered(
+        &conn,
+        &args.provider,
+        &args.symbol,
+        &args.timeframe,
+        start_ms.unwrap_or(0)..=end_ms.unwrap_or(i64::MAX),
+        args.include_interpolated,
+        ExportFormat::Table {
+            human_time: args.human_time,
+        },
+        |chunk| match output.write_all(chunk.as_bytes()) {
+            Ok(()) => true,
+            Err(e) => {
+                write_error = Some(e);
+                false
+            }
+        },
+    )?;
+    if let Some(e) = write_error {
+        return Err(e).with_context(|| format!("Écriture de {} impossible", args.out));
+    }
+    output.flush()?;
+
+    println!(
+        "✓ {} bougies {} {} exportées dans {}",
+        rows, args.symbol, args.timeframe, args.out
+    );
+
+    Ok(())
+}
@@ -0,0 +1,155 @@
+// ============================================================================
+// BINAIRE D'EXPORT PARQUET
+// ============================================================================
+//
+// Exporte une série vers un fichier Parquet (compression snappy), plus compact
+// qu'un CSV pour l'analyse de plusieurs années de bougies 1m
+//
+// Usage:
+//   cargo run --bin export_parquet -- --symbol BTCUSDT --timeframe 1m --output btc_1m.parquet
+//   cargo run --bin export_parquet -- --db candlesticks.db --symbol BTCUSDT --timeframe 5m \
+//       --start 2024-01-01 --end 2024-12-31 --output btc_5m_2024.parquet
+//   cargo run --bin export_parquet -- --symbol BTCUSDT --timeframe 1m --output btc_1m.parquet \
+//       --split-by month    (btc_1m_2024-01.parquet, btc_1m_2024-02.parquet...)
+//
+// Nécessite la feature `parquet` (active par défaut)
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use rust_candles_retriever::database::{candle_stats, open_configured};
+use rust_candles_retriever::export::{SplitBy, month_ranges, write_parquet};
+use rust_candles_retriever::utils::parse_date_ms;
+use std::fs::File;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+/// Arguments CLI de l'export Parquet
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Exporter une série de bougies au format Parquet", long_about = None)]
+struct Args {
+    /// Fichier de base de données
+    #[arg(long, default_value = "candlesticks.db")]
+    db: String,
+
+    /// Le symbole/paire de trading (ex: BTCUSDT)
+    #[arg(short, long)]
+    symbol: String,
+
+    /// Le timeframe à exporter (ex: 5m)
+    #[arg(short, long)]
+    timeframe: String,
+
+    /// Fichier Parquet produit
+    #[arg(short, long)]
+    output: String,
+
+    /// Le provider (par défaut: binance)
+    #[arg(short, long, default_value = "binance")]
+    provider: String,
+
+    /// Date de début au format YYYY-MM-DD (par défaut: toute la série)
+    #[arg(long)]
+    start: Option<String>,
+
+    /// Date de fin incluse au format YYYY-MM-DD (par défaut: toute la série)
+    #[arg(long)]
+    end: Option<String>,
+
+    /// Un fichier par période (month): suffixe _YYYY-MM ajouté à --output
+    #[arg(long)]
+    split_by: Option<SplitBy>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let start_ms = args.start.as_deref().map(parse_date_ms).transpose()?;
+    // Fin incluse: jusqu'à la dernière milliseconde du jour
+    let end_ms = args
+        .end
+        .as_deref()
+        .map(parse_date_ms)
+        .transpose()?
+        .map(|day| day + 86_400_000 - 1);
+
+    let conn = open_configured(&args.db)?;
+    let range = start_ms.unwrap_or(0)..=end_ms.unwrap_or(i64::MAX);
+
+    let Some(SplitBy::Month) = args.split_by else {
+        let rows = export(&conn, &args, range, &args.output)?;
+        println!(
+            "✓ {} bougies {} {} exportées dans {} ({})",
+            rows,
+            args.symbol,
+            args.timeframe,
+            args.output,
+            format_size(&args.output)?
+        );
+        return Ok(());
+    };
+
+    // Découpage sur les bornes réelles de la série, pas sur la plage demandée
+    let stats = candle_stats(&conn, &args.provider, &args.symbol, &args.timeframe)?;
+    let (Some(first), Some(last)) = (stats.first_open_time, stats.last_open_time) else {
+        println!("Aucune bougie {} {}", args.symbol, args.timeframe);
+        return Ok(());
+    };
+    let bounds = first.max(*range.start())..=last.min(*range.end());
+
+    let mut total = 0;
+    for (month, month_range) in month_ranges(bounds) {
+        let output = split_path(&args.output, &month);
+        let rows = export(&conn, &args, month_range, &output)?;
+        // Mois sans bougie (série interrompue): pas de fichier vide
+        if rows == 0 {
+            std::fs::remove_file(&output)?;
+            continue;
+        }
+        println!("✓ {}: {} bougies ({})", output, rows, format_size(&output)?);
+        total += rows;
+    }
+    println!(
+        "✓ {} bougies {} {} exportées",
+        total, args.symbol, args.timeframe
+    );
+
+    Ok(())
+}
+
+/// Exporte une plage dans un fichier Parquet
+fn export(
+    conn: &rusqlite::Connection,
+    args: &Args,
+    range: RangeInclusive<i64>,
+    output: &str,
+) -> Result<usize> {
+    let file =
+        File::create(output).with_context(|| format!("Création de {} impossible", output))?;
+    Ok(write_parquet(
+        conn,
+        &args.provider,
+        &args.symbol,
+        &args.timeframe,
+        range,
+        file,
+    )?)
+}
+
+/// Chemin d'un fichier découpé: btc_1m.parquet → btc_1m_2024-01.parquet
+fn split_path(output: &str, label: &str) -> String {
+    let path = Path::new(output);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("candles");
+    let name = match path.extension().and_then(|e| e.to_str()) {
+        Some(extension) => format!("{}_{}.{}", stem, label, extension),
+        None => format!("{}_{}", stem, label),
+    };
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
+/// Taille lisible d'un fichier (Mo avec une décimale)
+fn format_size(path: &str) -> Result<String> {
+    let size = std::fs::metadata(path)?.len();
+    Ok(format!("{:.1} Mo", size as f64 / 1_048_576.0))
+}
@@ -0,0 +1,226 @@
+// ============================================================================
+// BINAIRE STANDALONE DE COMBLEMENT DES GAPS
+// ============================================================================
+//
+// Outil de maintenance (à côté de verify_data) pour combler les gaps d'une
+// base de production sans relancer la récupération depuis Binance
+//
+// Usage:
+//   cargo run --bin fill_gaps -- --symbol BTCUSDT --timeframes 5m,1h --dry-run
+//   cargo run --bin fill_gaps -- --symbol BTCUSDT --strategy ffill --max-gap 12
+
+use anyhow::Result;
+use clap::Parser;
+use rusqlite::Connection;
+use rust_candles_retriever::config::DEFAULT_BACKFILL_TIMEFRAMES;
+use rust_candles_retriever::database::open_configured;
+use rust_candles_retriever::gap_filler::{FillStrategy, GapFiller, GapRange};
+use rust_candles_retriever::utils::{format_timestamp_ms, parse_date_ms};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Délai d'attente du verrou d'écriture avant d'abandonner
+const BUSY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Arguments CLI du comblement des gaps
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Combler les gaps d'une base de chandeliers", long_about = None)]
+struct Args {
+    /// Le symbole/paire de trading à traiter (ex: BTCUSDT)
+    #[arg(short, long)]
+    symbol: String,
+
+    /// Le provider (par défaut: binance)
+    #[arg(short, long, default_value = "binance")]
+    provider: String,
+
+    /// Les timeframes à traiter (par défaut: tous)
+    #[arg(short, long, value_delimiter = ',')]
+    timeframes: Option<Vec<String>>,
+
+    /// Fichier de base de données
+    #[arg(short = 'f', long, default_value = "candlesticks.db")]
+    db_file: String,
+
+    /// Stratégie de remplissage: linear, ffill, zero-volume, none
+    #[arg(long, default_value = "linear")]
+    strategy: FillStrategy,
+
+    /// Taille maximale d'un gap comblé (en bougies), les gaps plus longs sont ignorés
+    #[arg(long)]
+    max_gap: Option<i64>,
+
+    /// Date de début au format YYYY-MM-DD (par défaut: toute la série)
+    #[arg(long)]
+    start: Option<String>,
+
+    /// Date de fin au format YYYY-MM-DD (par défaut: maintenant)
+    #[arg(long)]
+    end: Option<String>,
+
+    /// Liste les gaps sans rien écrire
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// Bilan du comblement pour un timeframe
+#[derive(Debug, Default)]
+struct FillReport {
+    gaps_found: usize,
+    gaps_skipped: usize,
+    missing_candles: i64,
+    candles_filled: i64,
+    downtime_gaps: usize,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    if !Path::new(&args.db_file).exists() {
+        eprintln!(
+            "Erreur: Le fichier de base de données '{}' n'existe pas",
+            args.db_file
+        );
+        std::process::exit(1);
+    }
+
+    let mut conn = open_configured(&args.db_file)?;
+    // Attente plus longue que le défaut: outil de maintenance, pas de client en attente
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+
+    // Un autre processus (récupération, serveur) écrit: refuser plutôt que bloquer
+    if !args.dry_run
+        && let Err(e) = conn.execute_batch("BEGIN IMMEDIATE; COMMIT;")
+    {
+        eprintln!(
+            "Erreur: verrou d'écriture indisponible après {:?} ({}). Un autre processus écrit-il dans '{}' ?",
+            BUSY_TIMEOUT, e, args.db_file
+        );
+        std::process::exit(1);
+    }
+
+    let start_time = args.start.as_deref().map(parse_date_ms).transpose()?;
+    let end_time = match args.end.as_deref() {
+        Some(date) => parse_date_ms(date)?,
+        None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as i64,
+    };
+
+    let timeframes = args.timeframes.clone().unwrap_or_else(|| {
+        DEFAULT_BACKFILL_TIMEFRAMES
+            .iter()
+            .map(|tf| tf.to_string())
+            .collect()
+    });
+
+    println!("========================================");
+    println!("COMBLEMENT DES GAPS");
+    println!("========================================");
+    println!("Provider: {}", args.provider);
+    println!("Symbol: {}", args.symbol);
+    println!("Timeframes: {:?}", timeframes);
+    println!("Stratégie: {:?}", args.strategy);
+    if args.dry_run {
+        println!("Mode: dry-run (aucune écriture)");
+    }
+    println!();
+
+    for tf in &timeframes {
+        match fill_timeframe(&mut conn, &args, tf, start_time.unwrap_or(0), end_time) {
+            Ok(report) => print_report(tf, &report, args.dry_run),
+            Err(e) => eprintln!("Erreur lors du comblement pour {}: {}", tf, e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Comble (ou liste en dry-run) les gaps d'un timeframe
+///
+/// ALGORITHME:
+/// - sans --max-gap: un seul fill_gaps_in_range sur toute la plage (par fenêtres)
+/// - avec --max-gap: fill_gaps_in_range restreint à chaque gap retenu
+/// - les gaps couverts par une maintenance connue (known_downtime) sont comptés à part
+fn fill_timeframe(
+    conn: &mut Connection,
+    args: &Args,
+    timeframe: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<FillReport> {
+    let gaps = GapFiller::list_gaps_in_range(
+        conn,
+        &args.provider,
+        &args.symbol,
+        timeframe,
+        start_time,
+        end_time,
+    )?;
+
+    let (kept, skipped): (Vec<GapRange>, Vec<GapRange>) = gaps
+        .into_iter()
+        .partition(|gap| args.max_gap.is_none_or(|max| gap.missing_candles <= max));
+
+    let mut report = FillReport {
+        gaps_found: kept.len() + skipped.len(),
+        gaps_skipped: skipped.len(),
+        missing_candles: kept.iter().map(|gap| gap.missing_candles).sum(),
+        ..FillReport::default()
+    };
+
+    if args.dry_run {
+        for gap in &kept {
+            println!(
+                "  {} | {} → {} | {} bougies",
+                timeframe,
+                format_timestamp_ms(gap.after_open_time),
+                format_timestamp_ms(gap.before_open_time),
+                gap.missing_candles
+            );
+        }
+        return Ok(report);
+    }
+
+    // Une seule plage sans --max-gap, sinon une plage par gap retenu
+    let ranges: Vec<(i64, i64)> = if skipped.is_empty() {
+        vec![(start_time, end_time)]
+    } else {
+        kept.iter()
+            .map(|gap| (gap.after_open_time, gap.before_open_time))
+            .collect()
+    };
+
+    for (range_start, range_end) in ranges {
+        let outcome = GapFiller::fill_gaps_in_range_detailed(
+            conn,
+            &args.provider,
+            &args.symbol,
+            timeframe,
+            range_start,
+            range_end,
+            args.strategy,
+        )?;
+        report.candles_filled += outcome.filled;
+        report.downtime_gaps += outcome.downtime_gaps.len();
+    }
+
+    Ok(report)
+}
+
+fn print_report(timeframe: &str, report: &FillReport, dry_run: bool) {
+    if dry_run {
+        println!(
+            "{}: {} gaps ({} ignorés > max-gap), {} bougies à combler\n",
+            timeframe, report.gaps_found, report.gaps_skipped, report.missing_candles
+        );
+    } else {
+        println!(
+            "✓ {}: {} gaps ({} ignorés > max-gap, {} maintenances), {} bougies comblées / {} manquantes",
+            timeframe,
+            report.gaps_found,
+            report.gaps_skipped,
+            report.downtime_gaps,
+            report.candles_filled,
+            report.missing_candles
+        );
+    }
+}
@@ -0,0 +1,46 @@
+/// Génère une nouvelle clé API et son hash bcrypt
+///
+/// La clé en clair est affichée une seule fois (à transmettre au client),
+/// le hash est ajouté à api_keys.txt (lu par le serveur web)
+use anyhow::Result;
+use clap::Parser;
+use rust_candles_retriever::auth::DEFAULT_API_KEYS_FILE;
+use std::io::Write;
+
+/// Arguments CLI du générateur de clés
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Générer une clé API pour le serveur web", long_about = None)]
+struct Args {
+    /// Fichier de hashes auquel ajouter la clé
+    #[arg(short, long, default_value = DEFAULT_API_KEYS_FILE)]
+    file: String,
+
+    /// Affiche seulement la clé et le hash sans modifier le fichier
+    #[arg(long)]
+    print_only: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    // 32 octets aléatoires encodés en hexadécimal (64 caractères)
+    let bytes: [u8; 32] = rand::random();
+    let key: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    let hash = bcrypt::hash(&key, bcrypt::DEFAULT_COST)?;
+
+    println!("Clé API (à conserver, elle ne sera plus affichée):");
+    println!("  {}", key);
+    println!("Hash bcrypt:");
+    println!("  {}", hash);
+
+    if !args.print_only {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&args.file)?;
+        writeln!(file, "{}", hash)?;
+        println!("\n✓ Hash ajouté à {}", args.file);
+    }
+
+    Ok(())
+}
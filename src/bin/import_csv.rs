@@ -0,0 +1,110 @@
+// ============================================================================
+// BINAIRE D'IMPORT CSV
+// ============================================================================
+//
+// Charge un historique CSV (TradingView, CryptoCompare, autre exchange...)
+// sans passer par l'API Binance. Format: voir src/import.rs
+//
+// Les lignes invalides sont écartées et listées avec leur numéro, les
+// bougies valides sont insérées par blocs (import_csv_lenient)
+//
+// Usage:
+//   cargo run --bin import_csv -- --file btc_5m.csv --symbol BTCUSDT --timeframe 5m
+//   cargo run --bin import_csv -- --file kraken.csv --symbol BTCUSD --timeframe 1h --provider kraken
+//   cargo run --bin import_csv -- --file vendor.csv --symbol BTCUSDT --timeframe 1h \
+//       --columns "timestamp=Date,volume=Volume BTC" --detect-gaps
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::gap_filler::GapFiller;
+use rust_candles_retriever::import::{ColumnMapping, IMPORT_PROVIDER, import_csv_lenient};
+use rust_candles_retriever::utils::format_timestamp_ms;
+
+/// Arguments CLI de l'import CSV
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Importer des bougies depuis un fichier CSV", long_about = None)]
+struct Args {
+    /// Fichier CSV à importer (timestamp_ms,open,high,low,close,volume)
+    #[arg(long)]
+    file: String,
+
+    /// Le symbole/paire de trading (ex: BTCUSDT)
+    #[arg(short, long)]
+    symbol: String,
+
+    /// Le timeframe des bougies du fichier (ex: 5m)
+    #[arg(short, long)]
+    timeframe: String,
+
+    /// Le provider enregistré avec les bougies
+    #[arg(short, long, default_value = IMPORT_PROVIDER)]
+    provider: String,
+
+    /// Fichier de base de données
+    #[arg(long, default_value = "candlesticks.db")]
+    db_file: String,
+
+    /// Noms des colonnes du fichier (ex: "timestamp=Date,open=Open"), en-tête requis
+    #[arg(long)]
+    columns: Option<ColumnMapping>,
+
+    /// Liste les gaps de la série sur la plage importée
+    #[arg(long)]
+    detect_gaps: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let content = std::fs::read_to_string(&args.file)
+        .with_context(|| format!("Lecture de {} impossible", args.file))?;
+
+    let mut db = DatabaseManager::new(&args.db_file)?;
+    let report = import_csv_lenient(
+        db.connection_mut(),
+        &content,
+        &args.provider,
+        &args.symbol,
+        &args.timeframe,
+        &args.columns.unwrap_or_default(),
+    )?;
+
+    for row in &report.invalid {
+        eprintln!("✗ Ligne {}: {}", row.line, row.reason);
+    }
+    println!(
+        "✓ {} bougies insérées, {} déjà présentes, {} lignes invalides pour {} {} [{}]",
+        report.summary.inserted,
+        report.skipped(),
+        report.invalid.len(),
+        args.symbol,
+        args.timeframe,
+        args.provider
+    );
+
+    if let (true, Some((start, end))) = (args.detect_gaps, report.range) {
+        let gaps = GapFiller::list_gaps_in_range(
+            db.connection(),
+            &args.provider,
+            &args.symbol,
+            &args.timeframe,
+            start,
+            end,
+        )?;
+        println!(
+            "{} gap(s) du {} au {}",
+            gaps.len(),
+            format_timestamp_ms(start),
+            format_timestamp_ms(end)
+        );
+        for gap in &gaps {
+            println!(
+                "  après {}: {} bougie(s) manquante(s)",
+                format_timestamp_ms(gap.after_open_time),
+                gap.missing_candles
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+// ============================================================================
+// BINAIRE DE GESTION DES MAINTENANCES CONNUES
+// ============================================================================
+//
+// Renseigne la table known_downtime: les gaps couverts par ces fenêtres ne
+// sont pas interpolés (fill_gaps_in_range) et sont signalés comme attendus
+// (verify_data)
+//
+// Usage:
+//   cargo run --bin known_downtime -- --seed-binance
+//   cargo run --bin known_downtime -- --start "2024-05-01 02:00" --end "2024-05-01 04:00" --note "Maintenance"
+
+use anyhow::Result;
+use clap::Parser;
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::downtime::KnownDowntime;
+use rust_candles_retriever::utils::{format_timestamp_ms, parse_datetime_ms};
+
+/// Arguments CLI de gestion des maintenances
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Gérer les fenêtres de maintenance de l'exchange", long_about = None)]
+struct Args {
+    /// Fichier de base de données
+    #[arg(short = 'f', long, default_value = "candlesticks.db")]
+    db_file: String,
+
+    /// Début de la fenêtre à ajouter (UTC, "YYYY-MM-DD HH:MM" ou "YYYY-MM-DD")
+    #[arg(long, requires = "end")]
+    start: Option<String>,
+
+    /// Fin de la fenêtre à ajouter (UTC, "YYYY-MM-DD HH:MM" ou "YYYY-MM-DD")
+    #[arg(long, requires = "start")]
+    end: Option<String>,
+
+    /// Description de la fenêtre ajoutée
+    #[arg(long)]
+    note: Option<String>,
+
+    /// Ajoute les maintenances historiques de Binance fournies avec le projet
+    #[arg(long)]
+    seed_binance: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let db = DatabaseManager::new(&args.db_file)?;
+
+    if args.seed_binance {
+        let added = KnownDowntime::seed_binance_history(db.connection())?;
+        println!("✓ {} maintenances historiques Binance ajoutées", added);
+    }
+
+    if let (Some(start), Some(end)) = (&args.start, &args.end) {
+        let (start_ms, end_ms) = (parse_datetime_ms(start)?, parse_datetime_ms(end)?);
+        if KnownDowntime::add(db.connection(), start_ms, end_ms, args.note.as_deref())? {
+            println!("✓ Fenêtre ajoutée");
+        } else {
+            println!("Fenêtre déjà présente");
+        }
+    }
+
+    let windows = KnownDowntime::list(db.connection())?;
+    println!("\n{} fenêtre(s) de maintenance connue(s):", windows.len());
+    for window in &windows {
+        println!(
+            "  {} → {}  {}",
+            format_timestamp_ms(window.start_ms),
+            format_timestamp_ms(window.end_ms),
+            window.note.as_deref().unwrap_or("")
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+// ============================================================================
+// BINAIRE DE MAINTENANCE DES BASES
+// ============================================================================
+//
+// ANALYZE + PRAGMA optimize (et VACUUM avec --vacuum) sur chaque fichier .db
+// d'un répertoire, après une purge (--prune) ou un recomblement massif
+//
+// Usage:
+//   cargo run --bin maintenance
+//   cargo run --bin maintenance -- --db-dir data --vacuum
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use rust_candles_retriever::database::{DbSize, open_configured, optimize_database};
+use std::path::PathBuf;
+
+/// Arguments CLI de la maintenance
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Optimiser les bases SQLite d'un répertoire", long_about = None)]
+struct Args {
+    /// Répertoire contenant les fichiers .db
+    #[arg(long, default_value = ".")]
+    db_dir: String,
+
+    /// Réécrit chaque fichier (VACUUM): rend l'espace libre, mais bloque les écritures
+    #[arg(long)]
+    vacuum: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let mut databases: Vec<PathBuf> = std::fs::read_dir(&args.db_dir)
+        .with_context(|| format!("Lecture de {} impossible", args.db_dir))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "db"))
+        .collect();
+    databases.sort();
+
+    if databases.is_empty() {
+        println!("Aucun fichier .db dans {}", args.db_dir);
+        return Ok(());
+    }
+
+    for path in databases {
+        // open_configured: pas de migration, un .db étranger reste intact
+        let conn = open_configured(&path)?;
+        match optimize_database(&conn, args.vacuum) {
+            Ok(report) => println!(
+                "✓ {}: {} → {} (pages {} → {}, libres {} → {}){}",
+                path.display(),
+                format_size(&report.before),
+                format_size(&report.after),
+                report.before.page_count,
+                report.after.page_count,
+                report.before.freelist_count,
+                report.after.freelist_count,
+                if report.vacuumed { ", VACUUM" } else { "" }
+            ),
+            Err(e) => println!("✗ {}: {}", path.display(), e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Taille lisible (Mo avec une décimale)
+fn format_size(size: &DbSize) -> String {
+    format!("{:.1} Mo", size.bytes() as f64 / (1024.0 * 1024.0))
+}
@@ -0,0 +1,101 @@
+// ============================================================================
+// BINAIRE DE FUSION DES BASES
+// ============================================================================
+//
+// Consolide tous les fichiers .db d'un répertoire dans une base unique
+// (bougies et timeframe_status). Les sources sont ouvertes en lecture seule;
+// relancer la commande après une interruption saute les paires déjà copiées
+//
+// Usage:
+//   cargo run --bin merge_databases -- --db-dir data --output candlesticks.db
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::merge::merge_database;
+use std::path::{Path, PathBuf};
+
+/// Arguments CLI de la fusion
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Fusionner les bases SQLite d'un répertoire", long_about = None)]
+struct Args {
+    /// Répertoire contenant les fichiers .db à fusionner
+    #[arg(long, default_value = ".")]
+    db_dir: String,
+
+    /// Base consolidée (créée si absente, complétée sinon)
+    #[arg(short, long)]
+    output: String,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let output = Path::new(&args.output);
+    let mut dest = DatabaseManager::new(&args.output)?;
+    // Après création: le chemin canonique de la sortie existe forcément
+    let output_canonical = output.canonicalize()?;
+
+    let mut sources: Vec<PathBuf> = std::fs::read_dir(&args.db_dir)
+        .with_context(|| format!("Lecture de {} impossible", args.db_dir))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "db"))
+        // La sortie peut se trouver dans le répertoire scanné
+        .filter(|path| path.canonicalize().ok().as_ref() != Some(&output_canonical))
+        .collect();
+    sources.sort();
+
+    if sources.is_empty() {
+        println!("Aucun fichier .db à fusionner dans {}", args.db_dir);
+        return Ok(());
+    }
+
+    let (mut total_inserted, mut total_conflicts, mut failed) = (0usize, 0i64, 0usize);
+    for path in &sources {
+        let reports = match merge_database(dest.connection_mut(), path) {
+            Ok(reports) => reports,
+            Err(e) => {
+                println!("✗ {}: {}", path.display(), e);
+                failed += 1;
+                continue;
+            }
+        };
+        for report in reports {
+            if report.skipped {
+                println!(
+                    "= {} {} [{}]: déjà fusionné ({} bougies)",
+                    path.display(),
+                    report.symbol,
+                    report.provider,
+                    report.source_rows
+                );
+            } else {
+                println!(
+                    "✓ {} {} [{}]: {} bougies, {} insérées, {} conflits",
+                    path.display(),
+                    report.symbol,
+                    report.provider,
+                    report.source_rows,
+                    report.inserted,
+                    report.conflicts
+                );
+                total_inserted += report.inserted;
+                total_conflicts += report.conflicts;
+            }
+        }
+    }
+
+    println!(
+        "Total: {} fichiers, {} bougies insérées, {} conflits{}",
+        sources.len(),
+        total_inserted,
+        total_conflicts,
+        if failed > 0 {
+            format!(", {} fichiers en erreur", failed)
+        } else {
+            String::new()
+        }
+    );
+
+    Ok(())
+}
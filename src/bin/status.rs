@@ -0,0 +1,69 @@
+// ============================================================================
+// BINAIRE D'ÉTAT DES BASES
+// ============================================================================
+//
+// Affiche, pour chaque paire, un tableau par timeframe: première et dernière
+// bougie, bougies stockées et interpolées, bougies manquantes, backfill
+// complet et dernière mise à jour (voir status_report)
+//
+// Les fichiers sont ouverts en lecture seule: utilisable pendant un backfill
+//
+// Usage:
+//   cargo run --bin status
+//   cargo run --bin status -- --db-file candlesticks.db --symbol BTCUSDT
+//   cargo run --bin status -- --db-dir data --json > status.json
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use rust_candles_retriever::status_report::{directory_status, file_status, render_table};
+use std::path::Path;
+
+/// Arguments CLI de l'état des bases
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Afficher la couverture de chaque paire et timeframe", long_about = None)]
+struct Args {
+    /// Fichier de base de données
+    #[arg(short = 'f', long, default_value = "candlesticks.db")]
+    db_file: String,
+
+    /// Répertoire de fichiers .db (remplace --db-file)
+    #[arg(long)]
+    db_dir: Option<String>,
+
+    /// Limite l'affichage à un symbole (ex: BTCUSDT)
+    #[arg(short, long)]
+    symbol: Option<String>,
+
+    /// Sortie JSON sur la sortie standard (au lieu des tableaux)
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let symbol = args.symbol.as_ref().map(|s| s.to_uppercase());
+
+    let files = match &args.db_dir {
+        Some(db_dir) => directory_status(Path::new(db_dir), symbol.as_deref())
+            .with_context(|| format!("Lecture de {} impossible", db_dir))?,
+        None => {
+            if !Path::new(&args.db_file).exists() {
+                eprintln!(
+                    "Erreur: Le fichier de base de données '{}' n'existe pas",
+                    args.db_file
+                );
+                std::process::exit(1);
+            }
+            vec![file_status(Path::new(&args.db_file), symbol.as_deref())]
+        }
+    };
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&files)?);
+    } else if files.is_empty() {
+        println!("Aucun fichier .db dans {}", args.db_dir.unwrap_or_default());
+    } else {
+        print!("{}", render_table(&files));
+    }
+    Ok(())
+}
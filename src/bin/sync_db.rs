@@ -0,0 +1,72 @@
+// ============================================================================
+// BINAIRE DE SYNCHRONISATION ENTRE DEUX BASES
+// ============================================================================
+//
+// Copie d'une base à l'autre les bougies plus récentes que la dernière bougie
+// de chaque série de la destination (un nœud récupère, un autre sert), sans
+// re-télécharger l'historique. La source est ouverte en lecture seule
+//
+// Usage:
+//   cargo run --bin sync_db -- --source fetcher.db --dest candlesticks.db
+//   cargo run --bin sync_db -- --source fetcher.db --dest candlesticks.db --symbol BTCUSDT
+
+use anyhow::{Result, bail};
+use clap::Parser;
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::merge::sync_database;
+use std::path::Path;
+
+/// Arguments CLI de la synchronisation
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Synchroniser les bougies d'une base SQLite vers une autre", long_about = None)]
+struct Args {
+    /// Base source (lecture seule)
+    #[arg(long)]
+    source: String,
+
+    /// Base de destination (créée si absente, complétée sinon)
+    #[arg(long)]
+    dest: String,
+
+    /// Synchroniser une seule paire (toutes les paires de la source sinon)
+    #[arg(long)]
+    symbol: Option<String>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let source = Path::new(&args.source);
+    if !source.exists() {
+        bail!("Base source introuvable: {}", args.source);
+    }
+    let symbol = args.symbol.map(|s| s.to_uppercase());
+
+    let mut dest = DatabaseManager::new(&args.dest)?;
+    let reports = sync_database(dest.connection_mut(), source, symbol.as_deref())?;
+
+    if reports.is_empty() {
+        println!("Aucune série à synchroniser dans {}", args.source);
+        return Ok(());
+    }
+
+    let mut total = 0;
+    for report in &reports {
+        println!(
+            "{} {} {} [{}]: {} bougies insérées",
+            if report.inserted > 0 { "✓" } else { "=" },
+            report.symbol,
+            report.timeframe,
+            report.provider,
+            report.inserted
+        );
+        total += report.inserted;
+    }
+    println!(
+        "Total: {} séries, {} bougies insérées",
+        reports.len(),
+        total
+    );
+
+    Ok(())
+}
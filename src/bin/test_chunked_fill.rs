@@ -0,0 +1,136 @@
+/// Test de performance du comblement par fenêtres sur 100k bougies
+///
+/// Série 1m synthétique avec des gaps dispersés (dont certains proches des
+/// frontières de fenêtres FILL_CHUNK_SIZE): le résultat doit être identique à
+/// l'ancien algorithme (chargement complet + interpolation en une passe)
+use anyhow::{Result, ensure};
+use rusqlite::Connection;
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::gap_filler::{FILL_CHUNK_SIZE, FillStrategy, GapFiller};
+use rust_candles_retriever::models::CandleRecord;
+use rust_candles_retriever::test_fixtures::{fixture_candle, insert_fixtures};
+use std::time::Instant;
+
+const BASE_TIME: i64 = 1700000000000;
+const INTERVAL: i64 = 60_000; // 1 minute
+const SERIES_LEN: i64 = 100_000;
+
+fn main() -> Result<()> {
+    println!("=== TEST DE COMBLEMENT PAR FENÊTRES (100k bougies) ===\n");
+
+    let mut db = DatabaseManager::new(":memory:")?;
+    let stored = seed_series(db.connection())?;
+    println!("✓ {} bougies réelles insérées", stored.len());
+
+    let expected = expected_interpolation(&stored);
+    println!("✓ {} bougies attendues (ancien algorithme)", expected.len());
+
+    let started = Instant::now();
+    let filled = GapFiller::fill_gaps_in_range(
+        db.connection_mut(),
+        "test_provider",
+        "TEST",
+        "1m",
+        BASE_TIME,
+        BASE_TIME + SERIES_LEN * INTERVAL,
+        FillStrategy::Linear,
+    )?;
+    println!(
+        "✓ {} bougies générées en {:.2?} (fenêtres de {})",
+        filled,
+        started.elapsed(),
+        FILL_CHUNK_SIZE
+    );
+
+    ensure!(
+        filled == expected.len() as i64,
+        "{} bougies attendues, {} générées",
+        expected.len(),
+        filled
+    );
+
+    let actual = read_interpolated(db.connection())?;
+    ensure!(actual.len() == expected.len(), "Nombre de lignes différent");
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        ensure!(a == e, "Bougie différente: {:?} != {:?}", a, e);
+    }
+    println!("✓ Résultat identique à l'ancien algorithme");
+
+    let remaining = GapFiller::count_gaps_in_range(
+        db.connection(),
+        "test_provider",
+        "TEST",
+        "1m",
+        BASE_TIME,
+        BASE_TIME + SERIES_LEN * INTERVAL,
+    )?;
+    ensure!(remaining == 0, "{} gaps restants", remaining);
+    println!("✓ Aucun gap restant");
+
+    println!("\n✓ Comblement par fenêtres correct");
+    Ok(())
+}
+
+/// Insère la série en omettant des bougies: gaps courts réguliers et gaps
+/// longs autour des frontières de fenêtres
+///
+/// RETOUR: (index, close, volume) des bougies stockées
+fn seed_series(conn: &Connection) -> Result<Vec<(i64, f64, f64)>> {
+    let chunk = FILL_CHUNK_SIZE as i64;
+    let is_missing = |i: i64| {
+        let short_gap = i % 997 == 0 || i % 1511 < 3;
+        let boundary_gap = (1..10).any(|k| (k * chunk - 5..k * chunk + 20).contains(&i));
+        i != 0 && i != SERIES_LEN - 1 && (short_gap || boundary_gap)
+    };
+
+    let mut stored = Vec::new();
+    let candles: Vec<CandleRecord> = (0..SERIES_LEN)
+        .filter(|i| !is_missing(*i))
+        .map(|i| {
+            let close = 100.0 + (i as f64 * 0.01).sin() * 10.0;
+            let volume = 1000.0 + (i % 50) as f64;
+            stored.push((i, close, volume));
+            CandleRecord {
+                volume,
+                ..fixture_candle(BASE_TIME + i * INTERVAL, INTERVAL, close)
+            }
+        })
+        .collect();
+    insert_fixtures(conn, "test_provider", "TEST", "1m", &candles)?;
+
+    Ok(stored)
+}
+
+/// Ancien algorithme: une seule passe sur toute la série en mémoire
+///
+/// RETOUR: (open_time, close, volume) des bougies interpolées
+fn expected_interpolation(stored: &[(i64, f64, f64)]) -> Vec<(i64, f64, f64)> {
+    let mut expected = Vec::new();
+    for pair in stored.windows(2) {
+        let (i0, close0, volume0) = pair[0];
+        let (i1, close1, volume1) = pair[1];
+        let missing = i1 - i0 - 1;
+
+        for j in 1..=missing {
+            let ratio = j as f64 / (missing + 1) as f64;
+            expected.push((
+                BASE_TIME + (i0 + j) * INTERVAL,
+                close0 + (close1 - close0) * ratio,
+                volume0 + (volume1 - volume0) * ratio,
+            ));
+        }
+    }
+    expected
+}
+
+fn read_interpolated(conn: &Connection) -> Result<Vec<(i64, f64, f64)>> {
+    let mut stmt = conn.prepare(
+        "SELECT open_time, close, volume FROM candlesticks
+         WHERE interpolated != 0
+         ORDER BY open_time ASC",
+    )?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
@@ -0,0 +1,173 @@
+/// Test du comblement de gaps par agrégation d'un timeframe inférieur
+///
+/// Un gap 1h entièrement couvert par des bougies 5m réelles doit être comblé
+/// par agrégation (interpolated = 0, derived_from = '5m'). Si la couverture
+/// est incomplète, la stratégie configurée (linear) prend le relais
+use anyhow::{Result, ensure};
+use rusqlite::{Connection, params};
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::gap_filler::{FillStrategy, GapFiller};
+use rust_candles_retriever::models::CandleRecord;
+use rust_candles_retriever::test_fixtures::{fixture_candle, insert_fixtures};
+
+const BASE_TIME: i64 = 1700002800000; // aligné sur l'heure
+const HOUR: i64 = 3_600_000;
+const FIVE_MIN: i64 = 300_000;
+
+fn main() -> Result<()> {
+    println!("=== TEST DU COMBLEMENT PAR TIMEFRAME INFÉRIEUR ===\n");
+
+    test_full_coverage_is_aggregated()?;
+    test_partial_coverage_falls_back()?;
+
+    println!("\n✓ Comblement par agrégation correct");
+    Ok(())
+}
+
+fn test_full_coverage_is_aggregated() -> Result<()> {
+    let mut db = DatabaseManager::new(":memory:")?;
+    insert_hour_candles(db.connection())?;
+
+    // 12 bougies 5m couvrant l'heure manquante: open = 200 + i, close = open + 1
+    for i in 0..12 {
+        let price = 200.0 + i as f64;
+        insert_candle(
+            db.connection(),
+            "5m",
+            BASE_TIME + HOUR + i * FIVE_MIN,
+            FIVE_MIN,
+            (
+                price,
+                price + 10.0,
+                price - 10.0,
+                price + 1.0,
+                10.0 + i as f64,
+            ),
+        )?;
+    }
+
+    let filled = fill(&mut db)?;
+    ensure!(filled == 1, "1 bougie dérivée attendue, {}", filled);
+
+    let (open, high, low, close, volume, trades, interpolated, derived_from) =
+        read_hour_candle(db.connection())?;
+
+    // Agrégation attendue: premier open, max high, min low, dernier close, sommes
+    let expected_volume: f64 = (0..12).map(|i| 10.0 + i as f64).sum();
+    ensure!(open == 200.0, "open {}", open);
+    ensure!(high == 221.0, "high {}", high);
+    ensure!(low == 190.0, "low {}", low);
+    ensure!(close == 212.0, "close {}", close);
+    ensure!(volume == expected_volume, "volume {}", volume);
+    ensure!(trades == 12 * 10, "trades {}", trades);
+    ensure!(interpolated == 0, "interpolated {}", interpolated);
+    ensure!(
+        derived_from.as_deref() == Some("5m"),
+        "derived_from {:?}",
+        derived_from
+    );
+
+    println!("✓ Couverture complète: bougie 1h agrégée depuis 5m (derived_from = 5m)");
+    Ok(())
+}
+
+fn test_partial_coverage_falls_back() -> Result<()> {
+    let mut db = DatabaseManager::new(":memory:")?;
+    insert_hour_candles(db.connection())?;
+
+    // Seulement 11 bougies 5m sur 12: couverture incomplète
+    for i in 0..11 {
+        insert_candle(
+            db.connection(),
+            "5m",
+            BASE_TIME + HOUR + i * FIVE_MIN,
+            FIVE_MIN,
+            (200.0, 210.0, 190.0, 201.0, 10.0),
+        )?;
+    }
+
+    let filled = fill(&mut db)?;
+    ensure!(filled == 1, "1 bougie interpolée attendue, {}", filled);
+
+    let (open, _, _, _, _, _, interpolated, derived_from) = read_hour_candle(db.connection())?;
+    ensure!(open == 150.0, "open interpolé attendu 150, {}", open);
+    ensure!(interpolated == 1, "interpolated {}", interpolated);
+    ensure!(derived_from.is_none(), "derived_from {:?}", derived_from);
+
+    println!("✓ Couverture incomplète: repli sur l'interpolation linéaire");
+    Ok(())
+}
+
+fn fill(db: &mut DatabaseManager) -> Result<i64> {
+    Ok(GapFiller::fill_gaps_in_range(
+        db.connection_mut(),
+        "test_provider",
+        "TEST",
+        "1h",
+        BASE_TIME,
+        BASE_TIME + 2 * HOUR,
+        FillStrategy::Linear,
+    )?)
+}
+
+/// Bougies 1h encadrant le gap: heure 0 (open 100) et heure 2 (open 200)
+fn insert_hour_candles(conn: &Connection) -> Result<()> {
+    insert_candle(
+        conn,
+        "1h",
+        BASE_TIME,
+        HOUR,
+        (100.0, 110.0, 90.0, 105.0, 100.0),
+    )?;
+    insert_candle(
+        conn,
+        "1h",
+        BASE_TIME + 2 * HOUR,
+        HOUR,
+        (200.0, 210.0, 190.0, 205.0, 200.0),
+    )?;
+    Ok(())
+}
+
+/// Bougie réelle aux valeurs OHLCV données, 10 trades
+fn insert_candle(
+    conn: &Connection,
+    timeframe: &str,
+    open_time: i64,
+    interval: i64,
+    (open, high, low, close, volume): (f64, f64, f64, f64, f64),
+) -> Result<()> {
+    let candle = CandleRecord {
+        open,
+        high,
+        low,
+        volume,
+        number_of_trades: 10,
+        ..fixture_candle(open_time, interval, close)
+    };
+    insert_fixtures(conn, "test_provider", "TEST", timeframe, &[candle])?;
+    Ok(())
+}
+
+type HourCandle = (f64, f64, f64, f64, f64, i64, i64, Option<String>);
+
+fn read_hour_candle(conn: &Connection) -> Result<HourCandle> {
+    Ok(conn.query_row(
+        "SELECT open, high, low, close, volume, number_of_trades, interpolated, derived_from
+         FROM candlesticks
+         WHERE timeframe = '1h' AND open_time = ?1",
+        params![BASE_TIME + HOUR],
+        |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+                row.get(6)?,
+                row.get(7)?,
+            ))
+        },
+    )?)
+}
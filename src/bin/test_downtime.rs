@@ -0,0 +1,124 @@
+/// Test des fenêtres de maintenance connues (known_downtime)
+///
+/// Un gap entièrement couvert par une maintenance n'est pas interpolé et est
+/// rapporté à part; un gap qui déborde de la fenêtre est comblé normalement
+use anyhow::{Result, ensure};
+use rusqlite::{Connection, params};
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::downtime::KnownDowntime;
+use rust_candles_retriever::gap_filler::{FillOutcome, FillStrategy, GapFiller};
+use rust_candles_retriever::test_fixtures::{candles_at, insert_fixtures};
+
+const BASE_TIME: i64 = 1700000000000;
+const INTERVAL: i64 = 300_000; // 5 minutes
+
+fn main() -> Result<()> {
+    println!("=== TEST DES MAINTENANCES CONNUES ===\n");
+
+    test_gap_inside_downtime()?;
+    test_gap_partially_overlapping_downtime()?;
+    test_invalid_window_rejected()?;
+
+    println!("\n✓ Maintenances correctement prises en compte");
+    Ok(())
+}
+
+fn test_gap_inside_downtime() -> Result<()> {
+    let mut db = DatabaseManager::new(":memory:")?;
+    // Gaps 0→4 (3 bougies) et 6→9 (2 bougies)
+    insert_candles(db.connection(), &[0, 4, 5, 6, 9])?;
+
+    // Maintenance couvrant les bougies 1 à 3, commencée en cours de bougie 1
+    KnownDowntime::add(
+        db.connection(),
+        time(1) + 60_000,
+        time(3) + 120_000,
+        Some("maintenance test"),
+    )?;
+
+    let outcome = fill(&mut db)?;
+    ensure!(
+        outcome.downtime_gaps.len() == 1
+            && outcome.downtime_gaps[0].after_open_time == time(0)
+            && outcome.downtime_gaps[0].missing_candles == 3,
+        "Gap 0→4 attendu comme maintenance: {:?}",
+        outcome.downtime_gaps
+    );
+    ensure!(outcome.filled == 2, "Seul le gap 6→9 doit être comblé");
+    ensure!(
+        count_interpolated(db.connection(), 1, 3)? == 0,
+        "Bougies inventées pendant la maintenance"
+    );
+
+    println!("✓ Gap dans une maintenance: non interpolé, rapporté à part");
+    Ok(())
+}
+
+fn test_gap_partially_overlapping_downtime() -> Result<()> {
+    let mut db = DatabaseManager::new(":memory:")?;
+    insert_candles(db.connection(), &[0, 6])?;
+
+    // La maintenance ne couvre que les bougies 1 à 3: 4 et 5 restent inexpliquées
+    KnownDowntime::add(db.connection(), time(1), time(3), None)?;
+
+    let outcome = fill(&mut db)?;
+    ensure!(
+        outcome.downtime_gaps.is_empty(),
+        "Gap débordant classé en maintenance"
+    );
+    ensure!(
+        outcome.filled == 5,
+        "5 bougies attendues, {}",
+        outcome.filled
+    );
+
+    println!("✓ Gap débordant de la maintenance: comblé normalement");
+    Ok(())
+}
+
+fn test_invalid_window_rejected() -> Result<()> {
+    let db = DatabaseManager::new(":memory:")?;
+    ensure!(
+        KnownDowntime::add(db.connection(), time(5), time(1), None).is_err(),
+        "Fenêtre inversée acceptée"
+    );
+    ensure!(KnownDowntime::seed_binance_history(db.connection())? > 0);
+    ensure!(
+        KnownDowntime::seed_binance_history(db.connection())? == 0,
+        "Doublons insérés"
+    );
+
+    println!("✓ Fenêtre inversée refusée, liste Binance idempotente");
+    Ok(())
+}
+
+fn fill(db: &mut DatabaseManager) -> Result<FillOutcome> {
+    Ok(GapFiller::fill_gaps_in_range_detailed(
+        db.connection_mut(),
+        "test_provider",
+        "TEST",
+        "5m",
+        time(0),
+        time(20),
+        FillStrategy::Linear,
+    )?)
+}
+
+fn count_interpolated(conn: &Connection, from_index: i64, to_index: i64) -> Result<i64> {
+    Ok(conn.query_row(
+        "SELECT COUNT(*) FROM candlesticks
+         WHERE interpolated != 0 AND open_time >= ?1 AND open_time <= ?2",
+        params![time(from_index), time(to_index)],
+        |row| row.get(0),
+    )?)
+}
+
+fn time(index: i64) -> i64 {
+    BASE_TIME + index * INTERVAL
+}
+
+fn insert_candles(conn: &Connection, indices: &[i64]) -> Result<()> {
+    let candles = candles_at(BASE_TIME, INTERVAL, indices, 100.0);
+    insert_fixtures(conn, "test_provider", "TEST", "5m", &candles)?;
+    Ok(())
+}
@@ -0,0 +1,197 @@
+/// Test des stratégies de remplissage des gaps
+///
+/// Ce test crée un gap connu de 3 bougies et vérifie les valeurs produites
+/// par chaque FillStrategy (linear, ffill, zero-volume, none)
+use anyhow::{Result, ensure};
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::gap_filler::{FillStrategy, GapFiller};
+use rust_candles_retriever::models::CandleRecord;
+use rust_candles_retriever::test_fixtures::{fixture_candle, insert_fixtures};
+
+const BASE_TIME: i64 = 1700000000000;
+const INTERVAL: i64 = 300_000; // 5 minutes
+
+/// Bougie relue depuis la base: (open_time, open, high, low, close, volume, trades, interpolated)
+type Row = (i64, f64, f64, f64, f64, f64, i64, i64);
+
+fn main() -> Result<()> {
+    println!("=== TEST DES STRATÉGIES DE REMPLISSAGE ===\n");
+
+    // Gap de 3 bougies entre l'indice 0 (close=100) et l'indice 4 (close=140)
+    // → ratios 0.25, 0.5, 0.75
+    test_linear()?;
+    test_forward_fill()?;
+    test_zero_volume_flat()?;
+    test_none()?;
+
+    println!("\n✓ Toutes les stratégies produisent les valeurs attendues");
+    Ok(())
+}
+
+fn test_linear() -> Result<()> {
+    let rows = run_strategy(FillStrategy::Linear)?;
+
+    ensure!(
+        rows.len() == 3,
+        "Linear: 3 bougies attendues, {}",
+        rows.len()
+    );
+    for (i, row) in rows.iter().enumerate() {
+        let ratio = (i + 1) as f64 / 4.0;
+        let (open_time, open, high, low, close, volume, trades, marker) = *row;
+
+        ensure!(
+            open_time == BASE_TIME + (i as i64 + 1) * INTERVAL,
+            "Linear: open_time"
+        );
+        ensure!(approx(open, 100.0 + 40.0 * ratio), "Linear: open {}", open);
+        ensure!(approx(high, 105.0 + 40.0 * ratio), "Linear: high {}", high);
+        ensure!(approx(low, 95.0 + 40.0 * ratio), "Linear: low {}", low);
+        ensure!(
+            approx(close, 100.0 + 40.0 * ratio),
+            "Linear: close {}",
+            close
+        );
+        ensure!(
+            approx(volume, 1000.0 + 1000.0 * ratio),
+            "Linear: volume {}",
+            volume
+        );
+        ensure!(
+            trades == (100.0 + 100.0 * ratio) as i64,
+            "Linear: trades {}",
+            trades
+        );
+        ensure!(marker == 1, "Linear: marqueur {}", marker);
+    }
+
+    println!("✓ Linear: interpolation A + (B-A) × ratio, marqueur 1");
+    Ok(())
+}
+
+fn test_forward_fill() -> Result<()> {
+    let rows = run_strategy(FillStrategy::ForwardFill)?;
+
+    ensure!(
+        rows.len() == 3,
+        "ForwardFill: 3 bougies attendues, {}",
+        rows.len()
+    );
+    for row in &rows {
+        let (_, open, high, low, close, volume, trades, marker) = *row;
+
+        ensure!(
+            open == 100.0 && high == 100.0 && low == 100.0 && close == 100.0,
+            "ForwardFill: OHLC doit valoir le close précédent"
+        );
+        ensure!(
+            volume == 0.0 && trades == 0,
+            "ForwardFill: volume nul attendu"
+        );
+        ensure!(marker == 2, "ForwardFill: marqueur {}", marker);
+    }
+
+    println!("✓ ForwardFill: bougies plates à 100.0, volume nul, marqueur 2");
+    Ok(())
+}
+
+fn test_zero_volume_flat() -> Result<()> {
+    let rows = run_strategy(FillStrategy::ZeroVolumeFlat)?;
+
+    ensure!(
+        rows.len() == 3,
+        "ZeroVolumeFlat: 3 bougies attendues, {}",
+        rows.len()
+    );
+    for (i, row) in rows.iter().enumerate() {
+        let price = 100.0 + 40.0 * (i + 1) as f64 / 4.0;
+        let (_, open, high, low, close, volume, trades, marker) = *row;
+
+        ensure!(
+            approx(open, price)
+                && approx(high, price)
+                && approx(low, price)
+                && approx(close, price),
+            "ZeroVolumeFlat: OHLC doit valoir {}",
+            price
+        );
+        ensure!(
+            volume == 0.0 && trades == 0,
+            "ZeroVolumeFlat: volume nul attendu"
+        );
+        ensure!(marker == 3, "ZeroVolumeFlat: marqueur {}", marker);
+    }
+
+    println!("✓ ZeroVolumeFlat: bougies plates à 110/120/130, volume nul, marqueur 3");
+    Ok(())
+}
+
+fn test_none() -> Result<()> {
+    let rows = run_strategy(FillStrategy::None)?;
+
+    ensure!(
+        rows.is_empty(),
+        "None: aucune bougie attendue, {}",
+        rows.len()
+    );
+
+    println!("✓ None: gap laissé intact");
+    Ok(())
+}
+
+/// Crée une base en mémoire avec un gap, applique la stratégie et relit les bougies générées
+fn run_strategy(strategy: FillStrategy) -> Result<Vec<Row>> {
+    let mut db = DatabaseManager::new(":memory:")?;
+    // Bougies encadrantes: volume et trades doublés sur la seconde
+    let candles = [
+        fixture_candle(BASE_TIME, INTERVAL, 100.0),
+        CandleRecord {
+            volume: 2000.0,
+            number_of_trades: 200,
+            ..fixture_candle(BASE_TIME + 4 * INTERVAL, INTERVAL, 140.0)
+        },
+    ];
+    insert_fixtures(db.connection(), "test_provider", "TEST", "5m", &candles)?;
+
+    let filled = GapFiller::fill_gaps_in_range(
+        db.connection_mut(),
+        "test_provider",
+        "TEST",
+        "5m",
+        BASE_TIME,
+        BASE_TIME + 4 * INTERVAL,
+        strategy,
+    )?;
+
+    let mut stmt = db.connection().prepare(
+        "SELECT open_time, open, high, low, close, volume, number_of_trades, interpolated
+         FROM candlesticks
+         WHERE interpolated > 0
+         ORDER BY open_time ASC",
+    )?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+                row.get(6)?,
+                row.get(7)?,
+            ))
+        })?
+        .collect::<Result<Vec<Row>, _>>()?;
+
+    ensure!(
+        filled == rows.len() as i64,
+        "Compteur de bougies générées incohérent"
+    );
+    Ok(rows)
+}
+
+fn approx(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
@@ -1,6 +1,8 @@
 /// Programme de test pour démontrer le comblement de trous avec interpolation
 use anyhow::Result;
 use rusqlite::{Connection, params};
+use rust_candles_retriever::database::SQL_CREATE_TABLE_CANDLESTICKS;
+use rust_candles_retriever::test_fixtures::{generate_candles_with_gaps, insert_fixtures};
 use std::path::Path;
 
 fn main() -> Result<()> {
@@ -17,7 +19,7 @@ fn main() -> Result<()> {
 
     // Insérer des données avec des trous intentionnels
     println!("Insertion de données avec trous intentionnels...");
-    insert_test_data_with_gaps(&mut conn)?;
+    insert_test_data_with_gaps(&conn)?;
     println!("✓ Données insérées\n");
 
     // Afficher l'état avant interpolation
@@ -43,89 +45,18 @@ fn setup_database(db_file: &str) -> Result<Connection> {
     let path = Path::new(db_file);
     let conn = Connection::open(path)?;
 
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS candlesticks (
-            provider TEXT NOT NULL,
-            symbol TEXT NOT NULL,
-            timeframe TEXT NOT NULL,
-            open_time INTEGER NOT NULL,
-            open REAL NOT NULL,
-            high REAL NOT NULL,
-            low REAL NOT NULL,
-            close REAL NOT NULL,
-            volume REAL NOT NULL,
-            close_time INTEGER NOT NULL,
-            quote_asset_volume REAL NOT NULL,
-            number_of_trades INTEGER NOT NULL,
-            taker_buy_base_asset_volume REAL NOT NULL,
-            taker_buy_quote_asset_volume REAL NOT NULL,
-            interpolated INTEGER NOT NULL DEFAULT 0,
-            UNIQUE(provider, symbol, timeframe, open_time)
-        )",
-        [],
-    )?;
+    conn.execute(SQL_CREATE_TABLE_CANDLESTICKS, [])?;
 
     Ok(conn)
 }
 
-fn insert_test_data_with_gaps(conn: &mut Connection) -> Result<()> {
+/// 19 bougies 5m en marche aléatoire: gap de 5 bougies (5-9) puis de 3 (13-15)
+fn insert_test_data_with_gaps(conn: &Connection) -> Result<()> {
     let base_time = 1700000000000i64; // Timestamp de référence
     let interval = 300_000i64; // 5 minutes
 
-    // Insérer des bougies avec des gaps intentionnels
-    let candles = vec![
-        // Groupe 1: bougies 0-4 (continues)
-        (0, 100.0, 105.0, 95.0, 102.0, 1000.0),
-        (1, 102.0, 107.0, 97.0, 104.0, 1100.0),
-        (2, 104.0, 109.0, 99.0, 106.0, 1200.0),
-        (3, 106.0, 111.0, 101.0, 108.0, 1300.0),
-        (4, 108.0, 113.0, 103.0, 110.0, 1400.0),
-        // GAP de 5 bougies ici (indices 5-9 manquants)
-        // Groupe 2: bougies 10-12 (continues)
-        (10, 130.0, 135.0, 125.0, 132.0, 2000.0),
-        (11, 132.0, 137.0, 127.0, 134.0, 2100.0),
-        (12, 134.0, 139.0, 129.0, 136.0, 2200.0),
-        // GAP de 3 bougies ici (indices 13-15 manquants)
-        // Groupe 3: bougies 16-18 (continues)
-        (16, 150.0, 155.0, 145.0, 152.0, 2600.0),
-        (17, 152.0, 157.0, 147.0, 154.0, 2700.0),
-        (18, 154.0, 159.0, 149.0, 156.0, 2800.0),
-    ];
-
-    let tx = conn.transaction()?;
-    {
-        let mut stmt = tx.prepare(
-            "INSERT INTO candlesticks (
-                provider, symbol, timeframe, open_time, open, high, low, close, volume,
-                close_time, quote_asset_volume, number_of_trades,
-                taker_buy_base_asset_volume, taker_buy_quote_asset_volume, interpolated
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
-        )?;
-
-        for (index, open, high, low, close, volume) in candles {
-            let open_time = base_time + (index * interval);
-            let close_time = open_time + interval - 1;
-
-            stmt.execute(params![
-                "test_provider",
-                "TEST",
-                "5m",
-                open_time,
-                open,
-                high,
-                low,
-                close,
-                volume,
-                close_time,
-                volume * 100.0,         // quote_asset_volume
-                (volume / 10.0) as i64, // number_of_trades
-                volume * 0.4,           // taker_buy_base
-                volume * 40.0,          // taker_buy_quote
-                0,                      // interpolated = 0 (données réelles)
-            ])?;
-        }
-    }
-    tx.commit()?;
+    let candles = generate_candles_with_gaps(base_time, 19, interval, &[5, 6, 7, 8, 9, 13, 14, 15]);
+    insert_fixtures(conn, "test_provider", "TEST", "5m", &candles)?;
 
     Ok(())
 }
@@ -0,0 +1,212 @@
+/// Test de GapFiller::list_gaps_in_range
+///
+/// Vérifie les cas limites de la détection des gaps:
+/// plage vide, bougie unique, gaps collés aux bornes de la plage,
+/// bougies hors plage ignorées, cohérence avec count_gaps_in_range,
+/// gaps de début et de fin relatifs aux bornes attendues
+use anyhow::{Result, ensure};
+use rusqlite::Connection;
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::gap_filler::{GapFiller, GapKind, GapRange};
+use rust_candles_retriever::test_fixtures::{candles_at, insert_fixtures};
+use rust_candles_retriever::timeframe_status::TimeframeStatus;
+
+const BASE_TIME: i64 = 1700000000000;
+const INTERVAL: i64 = 300_000; // 5 minutes
+
+fn main() -> Result<()> {
+    println!("=== TEST DE list_gaps_in_range ===\n");
+
+    test_zero_candles()?;
+    test_single_candle()?;
+    test_gaps_at_range_boundaries()?;
+    test_candles_outside_range_ignored()?;
+    test_trailing_gap_with_bounds()?;
+    test_leading_gap_with_bounds()?;
+    test_empty_series_with_bounds()?;
+
+    println!("\n✓ Tous les cas limites sont corrects");
+    Ok(())
+}
+
+fn test_zero_candles() -> Result<()> {
+    let db = DatabaseManager::new(":memory:")?;
+
+    let gaps = list(db.connection(), 0, 100)?;
+    ensure!(gaps.is_empty(), "Aucune bougie: aucun gap attendu");
+
+    println!("✓ Zéro bougie → aucun gap");
+    Ok(())
+}
+
+fn test_single_candle() -> Result<()> {
+    let db = DatabaseManager::new(":memory:")?;
+    insert_candles(db.connection(), &[5])?;
+
+    let gaps = list(db.connection(), 0, 100)?;
+    ensure!(gaps.is_empty(), "Bougie unique: aucun gap attendu");
+
+    println!("✓ Bougie unique → aucun gap");
+    Ok(())
+}
+
+fn test_gaps_at_range_boundaries() -> Result<()> {
+    let db = DatabaseManager::new(":memory:")?;
+    // Gap juste après la première bougie de la plage (1-2 manquantes)
+    // et juste avant la dernière (6-8 manquantes)
+    insert_candles(db.connection(), &[0, 3, 4, 5, 9])?;
+
+    let gaps = list(db.connection(), 0, 9)?;
+    ensure!(
+        gaps == vec![gap(0, 3, 2), gap(5, 9, 3)],
+        "Gaps aux bornes incorrects: {:?}",
+        gaps
+    );
+
+    let count = GapFiller::count_gaps_in_range(
+        db.connection(),
+        "test_provider",
+        "TEST",
+        "5m",
+        time(0),
+        time(9),
+    )?;
+    ensure!(count == 2, "count_gaps_in_range: 2 attendu, {}", count);
+
+    println!("✓ Gaps collés au début et à la fin de la plage détectés");
+    Ok(())
+}
+
+fn test_candles_outside_range_ignored() -> Result<()> {
+    let db = DatabaseManager::new(":memory:")?;
+    insert_candles(db.connection(), &[0, 10, 11, 12, 20])?;
+
+    // Les gaps 0→10 et 12→20 traversent les bornes: hors plage
+    let gaps = list(db.connection(), 10, 12)?;
+    ensure!(gaps.is_empty(), "Gaps hors plage détectés: {:?}", gaps);
+
+    let gaps = list(db.connection(), 0, 12)?;
+    ensure!(gaps == vec![gap(0, 10, 9)], "Gap 0→10 attendu: {:?}", gaps);
+
+    println!("✓ Bougies hors plage ignorées");
+    Ok(())
+}
+
+fn test_trailing_gap_with_bounds() -> Result<()> {
+    let db = DatabaseManager::new(":memory:")?;
+    // Données arrêtées à l'index 5, gap interne 2→4
+    insert_candles(db.connection(), &[0, 1, 2, 4, 5])?;
+
+    // Invisible pour list_gaps_in_range
+    let gaps = list(db.connection(), 0, 20)?;
+    ensure!(gaps == vec![gap(2, 4, 1)], "Seul le gap interne attendu");
+
+    // "Maintenant" = milieu de la bougie 21: dernière bougie clôturée = 20
+    let now = time(21) + INTERVAL / 2;
+    TimeframeStatus::update_progress(
+        db.connection(),
+        "test_provider",
+        "TEST",
+        "5m",
+        time(0),
+        time(5),
+    )?;
+    let (start, end) =
+        TimeframeStatus::expected_bounds(db.connection(), "test_provider", "TEST", "5m", now)
+            .expect("Statut enregistré");
+    ensure!(
+        start == time(0) && end == time(20) + INTERVAL / 2,
+        "Bornes incorrectes"
+    );
+
+    let gaps = list_with_bounds(db.connection(), start, end)?;
+    let trailing = GapRange {
+        after_open_time: time(5),
+        before_open_time: time(21),
+        missing_candles: 15,
+        kind: GapKind::Trailing,
+    };
+    ensure!(
+        gaps == vec![gap(2, 4, 1), trailing],
+        "Gap de fin incorrect: {:?}",
+        gaps
+    );
+
+    println!("✓ Gap de fin rapporté (15 bougies après la dernière stockée)");
+    Ok(())
+}
+
+fn test_leading_gap_with_bounds() -> Result<()> {
+    let db = DatabaseManager::new(":memory:")?;
+    insert_candles(db.connection(), &[4, 5, 6])?;
+
+    let gaps = list_with_bounds(db.connection(), time(0), time(6))?;
+    let leading = GapRange {
+        after_open_time: time(-1),
+        before_open_time: time(4),
+        missing_candles: 4,
+        kind: GapKind::Leading,
+    };
+    ensure!(gaps == vec![leading], "Gap de début incorrect: {:?}", gaps);
+
+    println!("✓ Gap de début rapporté (4 bougies avant la première stockée)");
+    Ok(())
+}
+
+fn test_empty_series_with_bounds() -> Result<()> {
+    let db = DatabaseManager::new(":memory:")?;
+
+    let gaps = list_with_bounds(db.connection(), time(0), time(9))?;
+    ensure!(
+        gaps.len() == 1 && gaps[0].missing_candles == 10,
+        "Plage vide: un gap de 10 bougies attendu: {:?}",
+        gaps
+    );
+
+    let gaps = list_with_bounds(db.connection(), time(9), time(0))?;
+    ensure!(gaps.is_empty(), "Bornes inversées: aucun gap attendu");
+
+    println!("✓ Série vide → un seul gap couvrant les bornes");
+    Ok(())
+}
+
+fn list_with_bounds(conn: &Connection, start: i64, end: i64) -> Result<Vec<GapRange>> {
+    Ok(GapFiller::list_gaps_with_bounds(
+        conn,
+        "test_provider",
+        "TEST",
+        "5m",
+        start,
+        end,
+    )?)
+}
+
+fn list(conn: &Connection, start_index: i64, end_index: i64) -> Result<Vec<GapRange>> {
+    Ok(GapFiller::list_gaps_in_range(
+        conn,
+        "test_provider",
+        "TEST",
+        "5m",
+        time(start_index),
+        time(end_index),
+    )?)
+}
+
+fn gap(after_index: i64, before_index: i64, missing_candles: i64) -> GapRange {
+    GapRange {
+        after_open_time: time(after_index),
+        before_open_time: time(before_index),
+        missing_candles,
+        kind: GapKind::Between,
+    }
+}
+
+fn time(index: i64) -> i64 {
+    BASE_TIME + index * INTERVAL
+}
+
+fn insert_candles(conn: &Connection, indices: &[i64]) -> Result<()> {
+    let candles = candles_at(BASE_TIME, INTERVAL, indices, 100.0);
+    insert_fixtures(conn, "test_provider", "TEST", "5m", &candles)?;
+    Ok(())
+}
@@ -0,0 +1,111 @@
+/// Test de la purge et du recomblement des bougies interpolées
+///
+/// Des bougies linéaires (anciennes exécutions) sont recalculées avec la
+/// stratégie ffill: les valeurs doivent changer, les comptes correspondre
+use anyhow::{Result, ensure};
+use rusqlite::Connection;
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::gap_filler::{FillStrategy, GapFiller};
+use rust_candles_retriever::test_fixtures::{fixture_candle, insert_fixtures};
+
+const BASE_TIME: i64 = 1700000000000;
+const INTERVAL: i64 = 300_000; // 5 minutes
+
+fn main() -> Result<()> {
+    println!("=== TEST DE PURGE ET RECOMBLEMENT ===\n");
+
+    let mut db = DatabaseManager::new(":memory:")?;
+
+    // Deux gaps: 3 bougies (1-3) et 2 bougies (5-6)
+    let candles: Vec<_> = [(0, 100.0), (4, 140.0), (7, 170.0)]
+        .into_iter()
+        .map(|(index, close)| fixture_candle(BASE_TIME + index * INTERVAL, INTERVAL, close))
+        .collect();
+    insert_fixtures(db.connection(), "test_provider", "TEST", "5m", &candles)?;
+
+    let seeded = fill(&mut db, FillStrategy::Linear)?;
+    ensure!(seeded == 5, "5 bougies linéaires attendues, {}", seeded);
+    let before = read_synthetic(db.connection())?;
+    ensure!(
+        before.iter().all(|(_, _, marker)| *marker == 1),
+        "Marqueur linéaire attendu"
+    );
+    println!("✓ {} bougies linéaires semées", seeded);
+
+    let (purged, refilled) = GapFiller::refill(
+        db.connection_mut(),
+        "test_provider",
+        "TEST",
+        "5m",
+        BASE_TIME,
+        BASE_TIME + 7 * INTERVAL,
+        FillStrategy::ForwardFill,
+    )?;
+    ensure!(purged == 5, "5 lignes purgées attendues, {}", purged);
+    ensure!(refilled == 5, "5 lignes regénérées attendues, {}", refilled);
+
+    let after = read_synthetic(db.connection())?;
+    ensure!(after.len() == before.len(), "Nombre de bougies modifié");
+    for ((time_before, close_before, _), (time_after, close_after, marker)) in
+        before.iter().zip(after.iter())
+    {
+        ensure!(time_before == time_after, "open_time modifié");
+        ensure!(
+            close_before != close_after,
+            "close inchangé à {}",
+            time_after
+        );
+        ensure!(*marker == 2, "Marqueur ffill attendu, {}", marker);
+    }
+    ensure!(
+        after[..3].iter().all(|(_, close, _)| *close == 100.0)
+            && after[3..].iter().all(|(_, close, _)| *close == 140.0),
+        "ffill doit reprendre le close précédent: {:?}",
+        after
+    );
+    println!("✓ {} purgées, {} regénérées en ffill", purged, refilled);
+
+    // Purge seule: plus aucune bougie synthétique
+    let purged = GapFiller::purge_interpolated(
+        db.connection(),
+        "test_provider",
+        "TEST",
+        "5m",
+        BASE_TIME,
+        BASE_TIME + 7 * INTERVAL,
+    )?;
+    ensure!(purged == 5, "5 lignes purgées attendues, {}", purged);
+    ensure!(
+        read_synthetic(db.connection())?.is_empty(),
+        "Bougies synthétiques restantes"
+    );
+    println!("✓ Purge seule: gaps rouverts");
+
+    println!("\n✓ Purge et recomblement corrects");
+    Ok(())
+}
+
+fn fill(db: &mut DatabaseManager, strategy: FillStrategy) -> Result<i64> {
+    Ok(GapFiller::fill_gaps_in_range(
+        db.connection_mut(),
+        "test_provider",
+        "TEST",
+        "5m",
+        BASE_TIME,
+        BASE_TIME + 7 * INTERVAL,
+        strategy,
+    )?)
+}
+
+/// Bougies synthétiques: (open_time, close, interpolated)
+fn read_synthetic(conn: &Connection) -> Result<Vec<(i64, f64, i64)>> {
+    let mut stmt = conn.prepare(
+        "SELECT open_time, close, interpolated FROM candlesticks
+         WHERE interpolated != 0
+         ORDER BY open_time ASC",
+    )?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
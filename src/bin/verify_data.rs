@@ -9,19 +9,40 @@
 // Les fichiers dans src/bin/ sont des binaires indépendants
 // Chacun a son propre main() et peut avoir ses propres dépendances
 // Compilé séparément: cargo build --bin verify_data
+//
+// Usage:
+//   cargo run --bin verify_data -- --symbol BTCUSDT --coverage
+//   cargo run --bin verify_data -- --db-dir data --symbol BTCUSDT --format json
+//   cargo run --bin verify_data -- --symbol BTCUSDT --fix --dry-run
+//   cargo run --bin verify_data -- --symbol BTCUSDT --cross-check 5m:1h,1h:4h
+//   cargo run --bin verify_data -- --symbol BTCUSDT --spot-check 20
+//   cargo run --bin verify_data -- --symbol BTCUSDT --record --keep-runs-days 30
+//   cargo run --bin verify_data -- --db-dir data --all --max-interpolated-pct 5
+//   cargo run --bin verify_data -- --db-dir data --all --jobs 8
 
 use anyhow::Result;
+use binance::api::Binance;
+use binance::market::Market;
 use clap::Parser;
 use rusqlite::Connection;
+use rust_candles_retriever::database::{candle_stats, open_configured};
+use rust_candles_retriever::rate_limiter::RateLimiter;
+use rust_candles_retriever::timeframe_status::{Coverage, CoverageReport, TimeframeStatus};
+use rust_candles_retriever::utils::{compare_timeframes, format_timestamp_ms, parse_date_ms};
+use rust_candles_retriever::verification_history::{
+    DEFAULT_RUN_RETENTION_DAYS, VerificationHistory,
+};
+use rust_candles_retriever::verify::{
+    self, AUDIT_SAMPLE_LIMIT, AnomalyCounts, CROSS_CHECK_TOLERANCE, CrossCheckReport,
+    DEFAULT_FLATLINE_RUN, Divergence, FileVerification, InterpolationReport, RepairReport,
+    ValueAnomalies, VerificationReport, VerificationSummary,
+};
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-// SUBTILITÉ RUST #23: include! macro
-// include!() copie-colle le contenu d'un fichier à la compilation
-// Ici utilisé pour réutiliser verify.rs sans le publier comme bibliothèque
-// Alternative plus propre: créer une lib.rs et utiliser `use crate::verify;`
-mod verify {
-    include!("../../src/verify.rs");
-}
+// SUBTILITÉ RUST #23: module de la bibliothèque
+// verify.rs est compilé une seule fois dans lib.rs (pub mod verify) et
+// partagé par verify_data et audit, au lieu d'être copié par include!()
 
 /// Arguments CLI pour le programme de vérification
 ///
@@ -32,8 +53,22 @@ mod verify {
 #[command(author, version, about = "Vérifier l'espacement des données de chandelier", long_about = None)]
 struct Args {
     /// Le symbole/paire de trading à vérifier (ex: BTCUSDT)
-    #[arg(short, long)]
-    symbol: String,
+    #[arg(short, long, required_unless_present = "all")]
+    symbol: Option<String>,
+
+    /// Vérifie chaque série de chaque .db de --db-dir (espacement et
+    /// valeurs), tableau récapitulatif symbole × timeframe
+    #[arg(
+        long,
+        requires = "db_dir",
+        conflicts_with_all = ["symbol", "timeframes", "fix", "coverage", "cross_check", "spot_check", "record"]
+    )]
+    all: bool,
+
+    /// Avec --all: nombre de fichiers vérifiés en parallèle (par défaut:
+    /// nombre de cœurs)
+    #[arg(long)]
+    jobs: Option<usize>,
 
     /// Le provider (par défaut: binance)
     #[arg(short, long, default_value = "binance")]
@@ -46,24 +81,117 @@ struct Args {
     /// Fichier de base de données
     #[arg(short = 'f', long, default_value = "candlesticks.db")]
     db_file: String,
+
+    /// Répertoire des bases par paire: `<db-dir>/<SYMBOL>.db`, comme le
+    /// serveur web (remplace --db-file)
+    #[arg(long)]
+    db_dir: Option<String>,
+
+    /// Supprime les lignes en trop des périodes couvertes plusieurs fois
+    /// (voir verify::repair_overlaps)
+    #[arg(long)]
+    fix: bool,
+
+    /// Avec --fix: liste les lignes à supprimer sans rien supprimer
+    #[arg(long, requires = "fix")]
+    dry_run: bool,
+
+    /// Format de sortie: text (rapport détaillé) ou json (VerificationSummary
+    /// sur la sortie standard, sans affichage décoratif)
+    #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+    format: String,
+
+    /// Nombre de bougies identiques consécutives signalé comme anomalie
+    /// (forward-fill défaillant, flux figé; < 2: non vérifié)
+    #[arg(long, default_value_t = DEFAULT_FLATLINE_RUN)]
+    flatline_run: usize,
+
+    /// Part maximale de bougies interpolées (en %): au-delà, la série est
+    /// signalée et le code de sortie vaut 1
+    #[arg(long, value_name = "PCT")]
+    max_interpolated_pct: Option<f64>,
+
+    /// Compare chaque timeframe supérieur à l'agrégation du timeframe
+    /// inférieur (inférieur:supérieur, ex: 5m:1h), sur la plage --start/--end
+    #[arg(long, value_delimiter = ',')]
+    cross_check: Vec<String>,
+
+    /// Compare N bougies réelles tirées au hasard par timeframe aux klines
+    /// renvoyées à nouveau par Binance (budget BINANCE_WEIGHT_LIMIT)
+    #[arg(long, value_name = "N")]
+    spot_check: Option<usize>,
+
+    /// Écart relatif toléré par --cross-check
+    #[arg(long, default_value_t = CROSS_CHECK_TOLERANCE)]
+    tolerance: f64,
+
+    /// Enregistre le résultat de chaque timeframe dans verification_runs
+    /// (historique, voir status)
+    #[arg(long)]
+    record: bool,
+
+    /// Avec --record: supprime les passages enregistrés depuis plus de N jours
+    #[arg(long, requires = "record", default_value_t = DEFAULT_RUN_RETENTION_DAYS)]
+    keep_runs_days: i64,
+
+    /// Affiche aussi le rapport de couverture (bougies présentes / attendues)
+    #[arg(long)]
+    coverage: bool,
+
+    /// Début de la plage de couverture YYYY-MM-DD (par défaut: première bougie)
+    #[arg(long)]
+    start: Option<String>,
+
+    /// Fin incluse de la plage de couverture YYYY-MM-DD (par défaut: dernière bougie)
+    #[arg(long)]
+    end: Option<String>,
 }
 
 /// Point d'entrée du binaire de vérification
 ///
 /// ALGORITHME:
 /// 1. Parse les arguments CLI
-/// 2. Vérifie que le fichier DB existe
+/// 2. Vérifie que le fichier DB existe (--db-dir: <db-dir>/<SYMBOL>.db)
 /// 3. Ouvre la connexion DB
-/// 4. Pour chaque timeframe demandé, lance verify_data_spacing()
+/// 4. Pour chaque timeframe demandé, lance verify_data_spacing() puis
+///    verify_values() (valeurs impossibles, bougies identiques) et
+///    verify_interpolation() (part de bougies interpolées)
+/// 5. Avec --coverage, affiche le rapport de couverture du timeframe
+/// 6. Avec --fix, supprime les lignes en double de chaque période
+///    (--dry-run: liste seulement)
+/// 7. Avec --spot-check N, compare N bougies par timeframe à l'API Binance
+/// 8. Avec --cross-check, compare les timeframes deux à deux
+/// 9. Avec --record, enregistre chaque timeframe dans verification_runs
+///    puis supprime les passages de plus de --keep-runs-days jours
+///
+/// --format json: mêmes vérifications (verify_spacing), un seul objet
+/// VerificationSummary sur la sortie standard
+///
+/// Code de sortie 1 si la part interpolée d'un timeframe dépasse
+/// --max-interpolated-pct
+///
+/// --all: voir verify_all (code de sortie 1 si une série échoue)
 fn main() -> Result<()> {
     let args = Args::parse();
+    if args.all {
+        return verify_all(&args);
+    }
+    // Présent sans --all (required_unless_present)
+    let symbol = args.symbol.as_deref().unwrap_or_default().to_uppercase();
 
     // Validation: le fichier DB doit exister
-    let path = Path::new(&args.db_file);
+    let db_file = match &args.db_dir {
+        Some(db_dir) => Path::new(db_dir)
+            .join(format!("{}.db", symbol))
+            .display()
+            .to_string(),
+        None => args.db_file.clone(),
+    };
+    let path = Path::new(&db_file);
     if !path.exists() {
         eprintln!(
             "Erreur: Le fichier de base de données '{}' n'existe pas",
-            args.db_file
+            db_file
         );
         // SUBTILITÉ RUST #25: std::process::exit()
         // exit(1) termine immédiatement le programme avec code d'erreur 1
@@ -71,9 +199,23 @@ fn main() -> Result<()> {
         std::process::exit(1);
     }
 
-    let conn = Connection::open(path)?;
+    let mut conn = open_configured(path)?;
+    let start_ms = args.start.as_deref().map(parse_date_ms).transpose()?;
+    // Fin incluse: jusqu'à la dernière milliseconde du jour
+    let end_ms = args
+        .end
+        .as_deref()
+        .map(parse_date_ms)
+        .transpose()?
+        .map(|day| day + 86_400_000 - 1);
+    let range = start_ms.unwrap_or(i64::MIN)..=end_ms.unwrap_or(i64::MAX);
 
-    let timeframes = args.timeframes.unwrap_or_else(|| {
+    // Client et budget de poids créés seulement avec --spot-check
+    let spot_source = args
+        .spot_check
+        .map(|_| (Market::new(None, None), RateLimiter::from_env()));
+
+    let timeframes = args.timeframes.clone().unwrap_or_else(|| {
         vec![
             "5m".to_string(),
             "15m".to_string(),
@@ -82,19 +224,652 @@ fn main() -> Result<()> {
         ]
     });
 
+    let run_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as i64;
+
+    if args.format == "json" {
+        let mut reports = Vec::new();
+        for tf in &timeframes {
+            let mut report = verify::verify_spacing(&conn, &args.provider, &symbol, tf)?;
+            report.values = Some(verify::verify_values(
+                &conn,
+                &args.provider,
+                &symbol,
+                tf,
+                args.flatline_run,
+            )?);
+            report.interpolation = Some(verify::verify_interpolation(
+                &conn,
+                &args.provider,
+                &symbol,
+                tf,
+            )?);
+            if args.coverage {
+                report.coverage = coverage(&conn, &args.provider, &symbol, tf, start_ms, end_ms)?
+                    .map(|(_, _, coverage)| coverage);
+            }
+            if args.fix {
+                report.repair = Some(verify::repair_overlaps(
+                    &mut conn,
+                    &args.provider,
+                    &symbol,
+                    tf,
+                    args.dry_run,
+                )?);
+            }
+            if let (Some(samples), Some((market, rate_limiter))) = (args.spot_check, &spot_source) {
+                report.spot_check = Some(verify::spot_check(
+                    &conn,
+                    market,
+                    rate_limiter,
+                    &args.provider,
+                    &symbol,
+                    tf,
+                    samples,
+                )?);
+            }
+            if args.record {
+                VerificationHistory::record(&conn, &args.provider, &symbol, &report, run_at)?;
+            }
+            reports.push(report);
+        }
+        let cross_checks = cross_checks(&conn, &args, &symbol, range)?;
+        if args.record {
+            prune_runs(&conn, &args, run_at)?;
+        }
+        let excessive = reports.iter().any(|report| {
+            report
+                .interpolation
+                .as_ref()
+                .is_some_and(|interpolation| interpolation_exceeded(&args, interpolation))
+        });
+        let summary = VerificationSummary {
+            provider: args.provider,
+            symbol,
+            db_file,
+            timeframes: reports,
+            cross_checks,
+        };
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        if excessive {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     println!("========================================");
     println!("VÉRIFICATION DE L'ESPACEMENT DES DONNÉES");
     println!("========================================");
     println!("Provider: {}", args.provider);
-    println!("Symbol: {}", args.symbol);
+    println!("Symbol: {}", symbol);
     println!("Timeframes: {:?}", timeframes);
     println!();
 
+    let mut excessive = false;
     for tf in &timeframes {
-        if let Err(e) = verify::verify_data_spacing(&conn, &args.provider, &args.symbol, tf) {
+        if let Err(e) = verify::verify_data_spacing(&conn, &args.provider, &symbol, tf) {
             eprintln!("Erreur lors de la vérification pour {}: {}", tf, e);
         }
+        let values =
+            match verify::verify_values(&conn, &args.provider, &symbol, tf, args.flatline_run) {
+                Ok(anomalies) => {
+                    print_values(&anomalies);
+                    Some(anomalies)
+                }
+                Err(e) => {
+                    eprintln!(
+                        "Erreur lors de la vérification des valeurs pour {}: {}",
+                        tf, e
+                    );
+                    None
+                }
+            };
+        let interpolation = match verify::verify_interpolation(&conn, &args.provider, &symbol, tf) {
+            Ok(interpolation) => {
+                print_interpolation(&interpolation, args.max_interpolated_pct);
+                excessive |= interpolation_exceeded(&args, &interpolation);
+                Some(interpolation)
+            }
+            Err(e) => {
+                eprintln!(
+                    "Erreur lors du comptage des bougies interpolées pour {}: {}",
+                    tf, e
+                );
+                None
+            }
+        };
+        if args.record {
+            match verify::verify_spacing(&conn, &args.provider, &symbol, tf) {
+                Ok(mut report) => {
+                    report.values = values;
+                    report.interpolation = interpolation;
+                    VerificationHistory::record(&conn, &args.provider, &symbol, &report, run_at)?;
+                }
+                Err(e) => eprintln!("Passage {} non enregistré: {}", tf, e),
+            }
+        }
+        if args.coverage {
+            print_coverage(&conn, &args.provider, &symbol, tf, start_ms, end_ms)?;
+        }
+        if args.fix {
+            match verify::repair_overlaps(&mut conn, &args.provider, &symbol, tf, args.dry_run) {
+                Ok(report) => print_repair(&report),
+                Err(e) => eprintln!("Erreur lors de la réparation pour {}: {}", tf, e),
+            }
+        }
+        if let (Some(samples), Some((market, rate_limiter))) = (args.spot_check, &spot_source) {
+            match verify::spot_check(
+                &conn,
+                market,
+                rate_limiter,
+                &args.provider,
+                &symbol,
+                tf,
+                samples,
+            ) {
+                Ok(divergences) => print_spot_check(tf, samples, &divergences),
+                Err(e) => eprintln!("Erreur lors du contrôle par sondage pour {}: {}", tf, e),
+            }
+        }
+    }
+
+    for check in cross_checks(&conn, &args, &symbol, range)? {
+        print_cross_check(&check);
+    }
+
+    if args.record {
+        let pruned = prune_runs(&conn, &args, run_at)?;
+        println!(
+            "Passages enregistrés dans verification_runs ({} passages de plus de {} jours supprimés)",
+            pruned, args.keep_runs_days
+        );
+    }
+
+    if excessive {
+        std::process::exit(1);
     }
+    Ok(())
+}
+
+/// Vrai si la part interpolée dépasse --max-interpolated-pct
+fn interpolation_exceeded(args: &Args, interpolation: &InterpolationReport) -> bool {
+    args.max_interpolated_pct
+        .is_some_and(|max| interpolation.exceeds(max))
+}
 
+/// Affiche la part de bougies interpolées et leur plus longue série
+fn print_interpolation(interpolation: &InterpolationReport, max_percentage: Option<f64>) {
+    let marker = match max_percentage {
+        Some(max) if interpolation.exceeds(max) => format!(" ✗ (maximum {} %)", max),
+        _ => String::new(),
+    };
+    println!(
+        "Interpolées {}: {} / {} ({:.2} %){}",
+        interpolation.timeframe,
+        interpolation.interpolated,
+        interpolation.total,
+        interpolation.percentage,
+        marker
+    );
+    if let Some(run) = &interpolation.longest_run {
+        println!(
+            "  Plus longue série: {} bougies, {} → {}",
+            run.length,
+            format_timestamp_ms(run.first_open_time),
+            format_timestamp_ms(run.last_open_time)
+        );
+    }
+    println!();
+}
+
+/// Supprime les passages plus anciens que --keep-runs-days
+fn prune_runs(conn: &Connection, args: &Args, run_at: i64) -> Result<usize> {
+    Ok(VerificationHistory::prune(
+        conn,
+        run_at - args.keep_runs_days * 86_400_000,
+    )?)
+}
+
+/// Affiche les anomalies de valeurs par type, réelles puis interpolées
+fn print_values(anomalies: &ValueAnomalies) {
+    println!(
+        "Valeurs {}: {} bougies vérifiées",
+        anomalies.timeframe, anomalies.checked_candles
+    );
+    if !anomalies.has_anomalies() {
+        println!("  ✓ Aucune valeur anormale");
+        println!();
+        return;
+    }
+    for (label, counts) in [
+        ("réelles", &anomalies.real),
+        ("interpolées", &anomalies.interpolated),
+    ] {
+        print_anomaly_counts(label, counts, anomalies.flatline_run);
+    }
+    for sample in &anomalies.samples {
+        println!(
+            "    {} {:?}{}{}",
+            format_timestamp_ms(sample.open_time),
+            sample.kind,
+            sample
+                .run_length
+                .map(|length| format!(" ({} bougies)", length))
+                .unwrap_or_default(),
+            if sample.interpolated {
+                " (interpolée)"
+            } else {
+                ""
+            }
+        );
+    }
+    println!();
+}
+
+/// Une ligne par type d'anomalie présent
+fn print_anomaly_counts(label: &str, counts: &AnomalyCounts, flatline_run: usize) {
+    if counts.total() == 0 {
+        return;
+    }
+    println!("  Bougies {}: {} anomalies", label, counts.total());
+    for (count, description) in [
+        (counts.high_below_low, "high < low".to_string()),
+        (counts.high_below_body, "high < open/close".to_string()),
+        (counts.low_above_body, "low > open/close".to_string()),
+        (counts.negative_volume, "volume négatif".to_string()),
+        (counts.zero_price, "prix à 0".to_string()),
+        (
+            counts.flatline_runs,
+            format!(
+                "séries de {}+ bougies identiques ({} bougies)",
+                flatline_run, counts.flatline_candles
+            ),
+        ),
+    ] {
+        if count > 0 {
+            println!("    {:>6} {}", count, description);
+        }
+    }
+}
+
+/// Affiche les champs différents de l'API sur les bougies tirées au hasard
+fn print_spot_check(timeframe: &str, samples: usize, divergences: &[Divergence]) {
+    println!(
+        "Sondage {}: {} bougies demandées, {} divergences",
+        timeframe,
+        samples,
+        divergences.len()
+    );
+    if divergences.is_empty() {
+        println!("  ✓ Bougies identiques à l'API (bougies plus servies ignorées)");
+    }
+    for divergence in divergences {
+        println!(
+            "  {} {}: stocké {} / API {}",
+            format_timestamp_ms(divergence.open_time),
+            divergence.field,
+            divergence.stored,
+            divergence.fresh
+        );
+    }
+    println!();
+}
+
+/// Comparaisons demandées par --cross-check, dans l'ordre des arguments
+fn cross_checks(
+    conn: &Connection,
+    args: &Args,
+    symbol: &str,
+    range: std::ops::RangeInclusive<i64>,
+) -> Result<Vec<CrossCheckReport>> {
+    args.cross_check
+        .iter()
+        .map(|spec| {
+            let (lower_tf, higher_tf) = verify::parse_cross_check(spec)?;
+            let mismatches = verify::verify_cross_timeframe(
+                conn,
+                &args.provider,
+                symbol,
+                &lower_tf,
+                &higher_tf,
+                range.clone(),
+                args.tolerance,
+            )?;
+            Ok(CrossCheckReport {
+                lower_timeframe: lower_tf,
+                higher_timeframe: higher_tf,
+                tolerance: args.tolerance,
+                mismatches,
+            })
+        })
+        .collect()
+}
+
+/// Affiche les désaccords d'une comparaison entre timeframes
+fn print_cross_check(check: &CrossCheckReport) {
+    println!(
+        "Cohérence {} → {}: {} désaccords (tolérance {})",
+        check.lower_timeframe,
+        check.higher_timeframe,
+        check.mismatches.len(),
+        check.tolerance
+    );
+    for mismatch in &check.mismatches {
+        println!(
+            "  {} {}: agrégé {} / stocké {}",
+            format_timestamp_ms(mismatch.open_time),
+            mismatch.field,
+            mismatch.resampled,
+            mismatch.stored
+        );
+    }
+    println!();
+}
+
+/// Mode --all: chaque série de chaque .db de --db-dir (verify::verify_directory)
+///
+/// Sortie texte: tableau symbole × timeframe (OK / N gaps / N overlaps /
+/// N valeurs), puis le détail des seules séries en échec et des séries
+/// au-delà de --max-interpolated-pct.
+/// --format json: la liste des FileVerification
+fn verify_all(args: &Args) -> Result<()> {
+    let db_dir = args.db_dir.as_deref().unwrap_or(".");
+    let jobs = args
+        .jobs
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()));
+    let files = verify::verify_directory(Path::new(db_dir), jobs, args.flatline_run)?;
+
+    if args.format == "json" {
+        println!("{}", serde_json::to_string_pretty(&files)?);
+    } else if files.is_empty() {
+        println!("Aucun fichier .db dans {}", db_dir);
+    } else {
+        print_matrix(&files);
+        for file in files.iter().filter(|file| !file.passed()) {
+            print_failures(file);
+        }
+    }
+
+    // Séries au-delà de --max-interpolated-pct (sans effet sur le tableau)
+    let excessive: Vec<&InterpolationReport> = files
+        .iter()
+        .flat_map(|file| &file.series)
+        .filter_map(|series| series.report.interpolation.as_ref())
+        .filter(|interpolation| interpolation_exceeded(args, interpolation))
+        .collect();
+    if args.format != "json" {
+        for interpolation in &excessive {
+            print!("✗ {} ", interpolation.symbol);
+            print_interpolation(interpolation, args.max_interpolated_pct);
+        }
+    }
+
+    if !excessive.is_empty() || files.iter().any(|file| !file.passed()) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Résumé d'une série dans le tableau de --all
+fn matrix_cell(report: &VerificationReport) -> String {
+    if report.passed() {
+        return "OK".to_string();
+    }
+    let real_values = report
+        .values
+        .as_ref()
+        .map_or(0, |values| values.real.total());
+    [
+        (report.gaps.len() as i64, "gaps"),
+        (report.overlaps.len() as i64, "overlaps"),
+        (real_values, "valeurs"),
+    ]
+    .iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, label)| format!("{} {}", count, label))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// Tableau symbole × timeframe de toutes les séries (une ligne par
+/// symbole de chaque fichier, "-" si le timeframe est absent)
+fn print_matrix(files: &[FileVerification]) {
+    let mut timeframes: Vec<&str> = files
+        .iter()
+        .flat_map(|file| &file.series)
+        .map(|series| series.report.timeframe.as_str())
+        .collect();
+    timeframes.sort_by(|a, b| compare_timeframes(a, b));
+    timeframes.dedup();
+
+    // (symbole, cellules) de chaque ligne
+    let mut rows: Vec<(String, Vec<String>)> = Vec::new();
+    for file in files {
+        if file.error.is_some() {
+            // Erreur détaillée après le tableau (print_failures)
+            rows.push((file.file.clone(), vec!["✗ illisible".to_string()]));
+            continue;
+        }
+        let mut symbols: Vec<&str> = file.series.iter().map(|s| s.symbol.as_str()).collect();
+        symbols.dedup();
+        for symbol in symbols {
+            let cells = timeframes
+                .iter()
+                .map(|tf| {
+                    file.series
+                        .iter()
+                        .find(|s| s.symbol == symbol && s.report.timeframe == *tf)
+                        .map_or_else(|| "-".to_string(), |s| matrix_cell(&s.report))
+                })
+                .collect();
+            rows.push((symbol.to_string(), cells));
+        }
+    }
+
+    let width = |column: usize| {
+        rows.iter()
+            .filter_map(|(_, cells)| cells.get(column))
+            .map(|cell| cell.chars().count())
+            .chain(std::iter::once(timeframes[column].len()))
+            .max()
+            .unwrap_or(0)
+    };
+    let widths: Vec<usize> = (0..timeframes.len()).map(width).collect();
+    let symbol_width = rows
+        .iter()
+        .map(|(symbol, _)| symbol.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Symbole".len());
+
+    let header: Vec<String> = timeframes
+        .iter()
+        .zip(&widths)
+        .map(|(tf, width)| format!("{:<width$}", tf, width = width))
+        .collect();
+    println!(
+        "{:<width$} | {}",
+        "Symbole",
+        header.join(" | "),
+        width = symbol_width
+    );
+    println!(
+        "{}",
+        "-".repeat(symbol_width + widths.iter().map(|w| w + 3).sum::<usize>())
+    );
+    for (symbol, cells) in &rows {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(widths.iter().chain(std::iter::repeat(&0)))
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        println!(
+            "{:<width$} | {}",
+            symbol,
+            cells.join(" | "),
+            width = symbol_width
+        );
+    }
+    println!();
+}
+
+/// Détail des séries en échec d'un fichier: gaps et overlaps (au plus
+/// AUDIT_SAMPLE_LIMIT de chaque), valeurs anormales
+fn print_failures(file: &FileVerification) {
+    if let Some(error) = &file.error {
+        println!("✗ {}: {}", file.file, error);
+        println!();
+        return;
+    }
+    for series in file.series.iter().filter(|series| !series.report.passed()) {
+        let report = &series.report;
+        println!(
+            "✗ {} — {} {} ({})",
+            file.file, series.symbol, report.timeframe, series.provider
+        );
+        if !report.gaps.is_empty() {
+            println!(
+                "  {} gaps ({} bougies manquantes)",
+                report.gaps.len(),
+                report
+                    .gaps
+                    .iter()
+                    .map(|gap| gap.missing_candles)
+                    .sum::<i64>()
+            );
+            for gap in report.gaps.iter().take(AUDIT_SAMPLE_LIMIT) {
+                println!(
+                    "    après {}: {} bougies manquantes",
+                    format_timestamp_ms(gap.open_time),
+                    gap.missing_candles
+                );
+            }
+        }
+        if !report.overlaps.is_empty() {
+            println!("  {} overlaps", report.overlaps.len());
+            for overlap in report.overlaps.iter().take(AUDIT_SAMPLE_LIMIT) {
+                println!(
+                    "    après {}: intervalle de {} ms",
+                    format_timestamp_ms(overlap.open_time),
+                    overlap.interval_ms
+                );
+            }
+        }
+        if let Some(values) = report.values.as_ref().filter(|v| v.real.total() > 0) {
+            print_values(values);
+        } else {
+            println!();
+        }
+    }
+}
+
+/// Affiche les lignes supprimées (ou à supprimer) par repair_overlaps
+fn print_repair(report: &RepairReport) {
+    let verb = if report.dry_run {
+        "à supprimer"
+    } else {
+        "supprimées"
+    };
+    println!(
+        "Réparation {}: {} périodes en double, {} lignes {}",
+        report.timeframe,
+        report.overlapping_periods,
+        report.removed.len(),
+        verb
+    );
+    for row in &report.removed {
+        println!(
+            "  {}{} → conservée: {}",
+            format_timestamp_ms(row.open_time),
+            if row.interpolated {
+                " (interpolée)"
+            } else {
+                ""
+            },
+            format_timestamp_ms(row.kept_open_time)
+        );
+    }
+    println!();
+}
+
+/// Rapport de couverture d'un timeframe, avec les bornes utilisées
+///
+/// Série entière (aucune borne): comptes de TimeframeStatus::coverage,
+/// source unique de /api/stats et /api/pairs/{symbol}/timeframes (mois
+/// calendaires pour 1M). Plage explicite: get_coverage, coverage() ne
+/// portant que sur la série entière
+///
+/// Bornes absentes: celles de la série stockée (None: aucune bougie)
+fn coverage(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    start_ms: Option<i64>,
+    end_ms: Option<i64>,
+) -> Result<Option<(i64, i64, CoverageReport)>> {
+    if start_ms.is_none() && end_ms.is_none() {
+        let Coverage::Series {
+            first,
+            last,
+            stored,
+            expected,
+            interpolated,
+            ..
+        } = TimeframeStatus::coverage(conn, provider, symbol, timeframe)?
+        else {
+            return Ok(None);
+        };
+        let report = CoverageReport {
+            actual: stored,
+            expected,
+            percentage: stored as f64 / expected as f64 * 100.0,
+            interpolated_count: interpolated,
+            real_count: stored - interpolated,
+        };
+        return Ok(Some((first, last, report)));
+    }
+
+    let stats = candle_stats(conn, provider, symbol, timeframe)?;
+    let (Some(start), Some(end)) = (
+        start_ms.or(stats.first_open_time),
+        end_ms.or(stats.last_open_time),
+    ) else {
+        return Ok(None);
+    };
+    let report = TimeframeStatus::get_coverage(conn, provider, symbol, timeframe, start, end)?;
+    Ok(Some((start, end, report)))
+}
+
+/// Affiche le rapport de couverture d'un timeframe (voir coverage)
+fn print_coverage(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    start_ms: Option<i64>,
+    end_ms: Option<i64>,
+) -> Result<()> {
+    let Some((start, end, report)) = coverage(conn, provider, symbol, timeframe, start_ms, end_ms)?
+    else {
+        println!("Couverture {}: aucune bougie", timeframe);
+        println!();
+        return Ok(());
+    };
+
+    println!(
+        "Couverture {} du {} au {}",
+        timeframe,
+        format_timestamp_ms(start),
+        format_timestamp_ms(end)
+    );
+    println!(
+        "  {:.2}% ({} / {} bougies attendues)",
+        report.percentage, report.actual, report.expected
+    );
+    println!(
+        "  Réelles: {}, interpolées: {}",
+        report.real_count, report.interpolated_count
+    );
+    println!();
     Ok(())
 }
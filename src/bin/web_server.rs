@@ -3,19 +3,117 @@
 /// ARCHITECTURE:
 /// - API REST avec actix-web
 /// - Sert les fichiers statiques (HTML/CSS/JS)
+/// - Configuration TOML (--config <path> ou ./config.toml) + variables d'environnement
 /// - Endpoints:
 ///   - GET /api/pairs → liste des paires disponibles
+///   - GET /api/pairs/{symbol}/timeframes → couverture de chaque timeframe
+///   - POST /api/timeframe/{symbol}/{timeframe}/reset → relance le backfill (clé API)
 ///   - GET /api/candles?symbol=X&timeframe=5m&limit=1000&offset=0
+///   - GET /api/candles/latest?symbol=X → bougie la plus récente de chaque timeframe
+///   - GET /api/candles/resample?symbol=X&source_tf=5m&target_tf=2h&start=T&end=T
+///   - GET /api/candles/export/csv?symbol=X&timeframe=5m&start=T&end=T → fichier CSV
+///   - GET /api/candles/export/parquet?symbol=X&timeframe=5m&start=T&end=T → fichier Parquet
+///   - POST /api/candles/import/csv?symbol=X&timeframe=5m → import CSV (clé API)
+///   - GET /api/stats?symbol=X → volume et couverture de chaque série
+///   - GET /api/status?symbol=X → progression du backfill de chaque timeframe
+///   - GET /api/statistics?symbol=X&timeframe=1h&start=T&end=T → statistiques OHLCV
+///   - POST /api/admin/maintenance?vacuum=true → ANALYZE/optimize/VACUUM (clé API)
+///   - POST /api/backup?symbol=X → sauvegarde de la paire côté serveur (clé API)
+///   - GET /api/config → configuration non sensible
+///   - GET /api/config/tls → état de l'écoute HTTPS
 use actix_cors::Cors;
 use actix_files::Files;
-use actix_web::{App, HttpResponse, HttpServer, Responder, get, web};
+use actix_multipart::Multipart;
+use actix_web::dev::{ServerHandle, Service};
+use actix_web::http::header::{
+    ContentDisposition, ContentEncoding, DispositionParam, DispositionType,
+};
+use actix_web::{App, HttpResponse, HttpServer, Responder, get, middleware, web};
+use clap::Parser;
+use futures_util::TryStreamExt;
 use rusqlite::{Connection, params};
+use rust_candles_retriever::analytics::returns::{ReturnKind, returns_series};
+use rust_candles_retriever::auth::{ApiKeyMiddleware, ApiKeys, StreamToken};
+use rust_candles_retriever::cache::{
+    CacheKey, ResponseCache, invalidate_series, new_response_cache,
+};
+use rust_candles_retriever::config::Config;
+use rust_candles_retriever::database::{
+    backup_database, check_integrity, descriptive_stats, optimize_database, query_candle_records,
+    snapshot_dir, symbol_candle_stats, timeframe_infos, vacuum_database,
+};
+use rust_candles_retriever::error::CandleError;
+#[cfg(feature = "parquet")]
+use rust_candles_retriever::export::write_parquet;
+use rust_candles_retriever::export::{ExportFormat, write_csv};
+use rust_candles_retriever::gap_filler::{FillStrategy, GapFiller};
+use rust_candles_retriever::import::{IMPORT_PROVIDER, import_csv};
+use rust_candles_retriever::indicators::atr::{Atr, DEFAULT_ATR_PERIOD};
+use rust_candles_retriever::indicators::bollinger::{
+    Bollinger, DEFAULT_BOLLINGER_K, DEFAULT_BOLLINGER_PERIOD,
+};
+use rust_candles_retriever::indicators::macd::{
+    DEFAULT_MACD_FAST, DEFAULT_MACD_SIGNAL, DEFAULT_MACD_SLOW, Macd,
+};
+use rust_candles_retriever::indicators::obv::obv_series;
+use rust_candles_retriever::indicators::parabolic_sar::{
+    DEFAULT_INITIAL_AF, DEFAULT_MAX_AF, DEFAULT_STEP_AF, sar_series,
+};
+use rust_candles_retriever::indicators::pivot_points::{DEFAULT_PIVOT_PERIOD, pivot_point_series};
+use rust_candles_retriever::indicators::rolling::{RollingKind, rolling_series};
+use rust_candles_retriever::indicators::stochastic::slow_stochastic_series;
+use rust_candles_retriever::indicators::values::{
+    component_series, indicator_by_name, indicator_series,
+};
+use rust_candles_retriever::indicators::volatility::{VolatilityKind, volatility_series};
+use rust_candles_retriever::indicators::vwap::{DEFAULT_VWAP_ANCHOR, Vwap, vwap_series};
+use rust_candles_retriever::logging::init_tracing;
+use rust_candles_retriever::metrics::{GaugeGuard, METRICS};
+use rust_candles_retriever::models::{CandleRecord, CandleStats};
+use rust_candles_retriever::patterns::{Pattern, pattern_series};
+use rust_candles_retriever::pool::ConnectionPool;
+use rust_candles_retriever::realtime::{CandleUpdate, RealtimeManager};
+use rust_candles_retriever::retention::RetentionPolicy;
+use rust_candles_retriever::timeframe_status::{TimeframeStatus, TimeframeStatusRow};
+use rust_candles_retriever::tls::load_server_config;
+use rust_candles_retriever::utils::{
+    compare_timeframes, format_timestamp_iso, is_valid_timeframe, parse_timeframe,
+    resample_candles, round_to_timeframe, timeframe_ordering,
+};
+use rust_candles_retriever::verification_history::{VerificationHistory, VerificationRun};
+use rust_candles_retriever::verify;
 use serde::{Deserialize, Serialize};
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, info, warn};
+
+/// Arguments CLI du serveur web
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Serveur web de visualisation des bougies", long_about = None)]
+struct Args {
+    /// Fichier de configuration TOML (par défaut: ./config.toml s'il existe)
+    #[arg(long)]
+    config: Option<String>,
+}
 
 /// État partagé de l'application
 struct AppState {
-    db_path: String,
+    config: Arc<Config>,
+    db_pool: Arc<ConnectionPool>,
+    realtime: Arc<RealtimeManager>,
+    /// Réponses JSON sérialisées (rééchantillonnages)
+    cache: ResponseCache<web::Bytes>,
+    /// Réponses de /api/candles/latest (TTL court: LATEST_CACHE_TTL)
+    latest_cache: ResponseCache<web::Bytes>,
+    /// Réponses de /api/statistics (STATISTICS_CACHE_TTL)
+    statistics_cache: ResponseCache<web::Bytes>,
+    /// Réponses de /api/pairs/{symbol}/timeframes (TIMEFRAMES_CACHE_TTL)
+    timeframes_cache: ResponseCache<web::Bytes>,
+    /// Jeton exigé par /api/stream/candles (voir StreamToken)
+    stream_token: StreamToken,
+    tls_active: bool,
 }
 
 /// Représentation d'une bougie pour l'API
@@ -29,6 +127,41 @@ struct Candle {
     volume: f64,
 }
 
+impl From<&CandleRecord> for Candle {
+    fn from(record: &CandleRecord) -> Self {
+        Candle {
+            time: record.open_time / 1000,
+            open: record.open,
+            high: record.high,
+            low: record.low,
+            close: record.close,
+            volume: record.volume,
+        }
+    }
+}
+
+impl From<&CandleUpdate> for Candle {
+    fn from(update: &CandleUpdate) -> Self {
+        Candle {
+            time: update.time,
+            open: update.open,
+            high: update.high,
+            low: update.low,
+            close: update.close,
+            volume: update.volume,
+        }
+    }
+}
+
+/// Bougie la plus récente d'un timeframe (/api/candles/latest)
+#[derive(Debug, Serialize)]
+struct LatestCandle {
+    #[serde(flatten)]
+    candle: Candle,
+    /// true: bougie en cours de formation (RealtimeManager), pas encore stockée
+    is_partial: bool,
+}
+
 /// Paire de trading disponible
 #[derive(Debug, Serialize)]
 struct TradingPair {
@@ -47,11 +180,374 @@ struct CandlesQuery {
     end: Option<i64>,   // Timestamp de fin en secondes
 }
 
+/// Paramètres de requête pour les dernières bougies
+#[derive(Debug, Deserialize)]
+struct LatestQuery {
+    symbol: String,
+}
+
+/// Paramètres de requête pour le rééchantillonnage
+#[derive(Debug, Deserialize)]
+struct ResampleQuery {
+    symbol: String,
+    source_tf: String,
+    target_tf: String,
+    start: Option<i64>, // Timestamp de début en secondes
+    end: Option<i64>,   // Timestamp de fin en secondes
+    limit: Option<usize>,
+}
+
+/// Paramètres de requête pour l'export CSV ou Parquet
+#[derive(Debug, Deserialize)]
+struct ExportQuery {
+    symbol: String,
+    timeframe: String,
+    start: Option<i64>,     // Timestamp de début en secondes
+    end: Option<i64>,       // Timestamp de fin en secondes
+    format: Option<String>, // CSV uniquement: "csv" (défaut) ou "tradingview"
+}
+
+impl ExportQuery {
+    /// Plage d'open_time en millisecondes (série complète par défaut)
+    fn range_ms(&self) -> std::ops::RangeInclusive<i64> {
+        self.start.map_or(0, |s| s * 1000)..=self.end.map_or(i64::MAX, |e| e * 1000)
+    }
+
+    /// Nom du fichier téléchargé (ex: BTCUSDT_5m_20240101_20240201.csv)
+    fn filename(&self, extension: &str) -> String {
+        format!(
+            "{}_{}_{}_{}.{}",
+            self.symbol,
+            self.timeframe,
+            self.start
+                .map_or("all".to_string(), |s| export_date(s * 1000)),
+            self.end
+                .map_or("now".to_string(), |e| export_date(e * 1000)),
+            extension
+        )
+    }
+}
+
+/// Paramètres de requête pour l'import CSV
+#[derive(Debug, Deserialize)]
+struct ImportQuery {
+    symbol: String,
+    timeframe: String,
+}
+
+/// Paramètres de requête pour les statistiques
+#[derive(Debug, Deserialize)]
+struct StatsQuery {
+    symbol: Option<String>, // toutes les paires si absent
+}
+
+/// Paramètres de requête pour la progression du backfill
+#[derive(Debug, Deserialize)]
+struct StatusQuery {
+    symbol: String,
+}
+
+/// Ligne de /api/status: TimeframeStatusRow, ses dates lisibles (ISO 8601)
+/// et le dernier passage de verify_data --record
+#[derive(Debug, Serialize)]
+struct TimeframeStatusView {
+    #[serde(flatten)]
+    row: TimeframeStatusRow,
+    oldest_candle_date: Option<String>,
+    newest_candle_date: Option<String>,
+    last_updated_date: String,
+    last_verification: Option<VerificationRun>,
+}
+
+impl TimeframeStatusView {
+    fn new(row: TimeframeStatusRow, last_verification: Option<VerificationRun>) -> Self {
+        TimeframeStatusView {
+            oldest_candle_date: row.oldest_candle_time.map(format_timestamp_iso),
+            newest_candle_date: row.newest_candle_time.map(format_timestamp_iso),
+            last_updated_date: format_timestamp_iso(row.last_updated),
+            last_verification,
+            row,
+        }
+    }
+}
+
+/// Paramètres de requête pour les statistiques descriptives
+#[derive(Debug, Deserialize)]
+struct StatisticsQuery {
+    symbol: String,
+    timeframe: String,
+    start: Option<i64>, // Timestamp de début en secondes
+    end: Option<i64>,   // Timestamp de fin en secondes
+}
+
+/// Paramètres de requête pour les indicateurs (/api/indicators/...)
+#[derive(Debug, Deserialize)]
+struct IndicatorQuery {
+    symbol: String,
+    timeframe: String,
+    start: Option<i64>, // Timestamp de début en secondes
+    end: Option<i64>,   // Timestamp de fin en secondes
+}
+
+/// Paramètres de requête de /api/indicators/{name}
+#[derive(Debug, Deserialize)]
+struct GenericIndicatorQuery {
+    symbol: String,
+    timeframe: String,
+    start: Option<i64>,    // Timestamp de début en secondes
+    end: Option<i64>,      // Timestamp de fin en secondes
+    period: Option<usize>, // Période (sma, ema; défaut: 20)
+}
+
+/// Paramètres de requête de /api/indicators/macd
+#[derive(Debug, Deserialize)]
+struct MacdQuery {
+    symbol: String,
+    timeframe: String,
+    start: Option<i64>,    // Timestamp de début en secondes
+    end: Option<i64>,      // Timestamp de fin en secondes
+    fast: Option<usize>,   // EMA rapide (défaut: 12)
+    slow: Option<usize>,   // EMA lente (défaut: 26)
+    signal: Option<usize>, // Ligne de signal (défaut: 9)
+}
+
+/// Paramètres de requête de /api/indicators/bollinger
+#[derive(Debug, Deserialize)]
+struct BollingerQuery {
+    symbol: String,
+    timeframe: String,
+    start: Option<i64>,    // Timestamp de début en secondes
+    end: Option<i64>,      // Timestamp de fin en secondes
+    period: Option<usize>, // Fenêtre en bougies (défaut: 20)
+    k: Option<f64>,        // Multiplicateur de l'écart-type (défaut: 2.0)
+}
+
+/// Paramètres de requête de /api/indicators/atr
+#[derive(Debug, Deserialize)]
+struct AtrQuery {
+    symbol: String,
+    timeframe: String,
+    start: Option<i64>,                 // Timestamp de début en secondes
+    end: Option<i64>,                   // Timestamp de fin en secondes
+    period: Option<usize>,              // Période de Wilder (défaut: 14)
+    exclude_interpolated: Option<bool>, // Bougies réelles seulement (défaut: false)
+}
+
+/// Paramètres de requête de /api/gaps et /api/gaps/interpolated
+#[derive(Debug, Deserialize)]
+struct GapsQuery {
+    symbol: String,
+    timeframe: String,
+    start: Option<i64>, // Timestamp de début en secondes
+    end: Option<i64>,   // Timestamp de fin en secondes
+}
+
+/// Paramètres de requête de /api/gaps/fill
+#[derive(Debug, Deserialize)]
+struct GapFillQuery {
+    symbol: String,
+    timeframe: String,
+    start: Option<i64>,     // Timestamp de début en secondes
+    end: Option<i64>,       // Timestamp de fin en secondes
+    method: Option<String>, // Stratégie de remplissage (défaut: linear)
+}
+
+/// Paramètres de requête de /api/indicators/vwap
+#[derive(Debug, Deserialize)]
+struct VwapQuery {
+    symbol: String,
+    timeframe: String,
+    start: Option<i64>,     // Timestamp de début en secondes
+    end: Option<i64>,       // Timestamp de fin en secondes
+    anchor: Option<String>, // Période des sessions (défaut: 1d)
+}
+
+/// Paramètres de requête de /api/indicators/stochastic
+#[derive(Debug, Deserialize)]
+struct StochasticQuery {
+    symbol: String,
+    timeframe: String,
+    start: Option<i64>,    // Timestamp de début en secondes
+    end: Option<i64>,      // Timestamp de fin en secondes
+    k: Option<usize>,      // Période de %K (défaut: 14)
+    smooth: Option<usize>, // Lissage de %K (défaut: 1, stochastique rapide)
+    d: Option<usize>,      // Période de %D (défaut: 3)
+}
+
+/// Point de l'oscillateur stochastique (%D absent tant que la fenêtre
+/// est incomplète)
+#[derive(Debug, Serialize)]
+struct StochasticPointJson {
+    time: i64, // timestamp en secondes
+    k: f64,
+    d: Option<f64>,
+}
+
+/// Paramètres de requête de /api/indicators/pivot-points
+#[derive(Debug, Deserialize)]
+struct PivotPointsQuery {
+    symbol: String,
+    timeframe: String,
+    start: Option<i64>,           // Timestamp de début en secondes
+    end: Option<i64>,             // Timestamp de fin en secondes
+    pivot_period: Option<String>, // Période pivot (défaut: 1d)
+}
+
+/// Niveaux pivots d'une période
+#[derive(Debug, Serialize)]
+struct PivotPointJson {
+    time: i64, // début de la période en secondes
+    pp: f64,
+    r1: f64,
+    r2: f64,
+    r3: f64,
+    s1: f64,
+    s2: f64,
+    s3: f64,
+}
+
+/// Paramètres de requête de /api/indicators/sar
+#[derive(Debug, Deserialize)]
+struct SarQuery {
+    symbol: String,
+    timeframe: String,
+    start: Option<i64>,      // Timestamp de début en secondes
+    end: Option<i64>,        // Timestamp de fin en secondes
+    initial_af: Option<f64>, // Facteur d'accélération initial (défaut: 0.02)
+    step: Option<f64>,       // Incrément du facteur (défaut: 0.02)
+    max: Option<f64>,        // Facteur maximal (défaut: 0.20)
+}
+
+/// SAR d'une bougie
+#[derive(Debug, Serialize)]
+struct SarPointJson {
+    time: i64, // timestamp en secondes
+    sar: f64,
+    is_bullish: bool,
+}
+
+/// Paramètres de requête de /api/indicators/volatility
+#[derive(Debug, Deserialize)]
+struct VolatilityQuery {
+    symbol: String,
+    timeframe: String,
+    start: Option<i64>, // Timestamp de début en secondes
+    end: Option<i64>,   // Timestamp de fin en secondes
+    #[serde(rename = "type")]
+    kind: VolatilityKind,
+    period: Option<usize>,   // Fenêtre en bougies (défaut: 20)
+    annualize: Option<bool>, // Historique seulement (défaut: false)
+}
+
+/// Paramètres de requête de /api/analytics/returns
+#[derive(Debug, Deserialize)]
+struct ReturnsQuery {
+    symbol: String,
+    timeframe: String,
+    start: Option<i64>, // Timestamp de début en secondes
+    end: Option<i64>,   // Timestamp de fin en secondes
+    #[serde(rename = "type")]
+    kind: ReturnKind,
+}
+
+/// Paramètres de requête de /api/indicators/rolling
+#[derive(Debug, Deserialize)]
+struct RollingQuery {
+    symbol: String,
+    timeframe: String,
+    start: Option<i64>, // Timestamp de début en secondes
+    end: Option<i64>,   // Timestamp de fin en secondes
+    #[serde(rename = "type")]
+    kind: RollingKind,
+    period: Option<usize>, // Fenêtre en bougies (défaut: 20)
+}
+
+/// Paramètres de requête de /api/compare
+#[derive(Debug, Deserialize)]
+struct CompareQuery {
+    symbol: String,
+    timeframe: String,
+    providers: Option<String>, // Liste séparée par des virgules (défaut: tous)
+    tolerance: Option<f64>,    // Écart relatif de close toléré (défaut: 0.001)
+}
+
+/// Paramètres de requête de /api/verify/{symbol}/outliers
+#[derive(Debug, Deserialize)]
+struct OutliersQuery {
+    timeframe: String,
+    z_threshold: Option<f64>, // Écarts-types (défaut: 3.0)
+}
+
+/// Point d'un indicateur (série en ligne de Lightweight Charts)
+#[derive(Debug, Serialize)]
+struct IndicatorPoint {
+    time: i64, // timestamp en secondes
+    value: f64,
+}
+
+/// Figure de chandeliers (marqueur de Lightweight Charts)
+#[derive(Debug, Serialize)]
+struct PatternPoint {
+    time: i64, // timestamp en secondes
+    pattern: Pattern,
+}
+
+/// Paramètres de requête pour la maintenance de la base
+#[derive(Debug, Deserialize)]
+struct MaintenanceQuery {
+    vacuum: Option<bool>, // VACUUM en plus d'ANALYZE/optimize (défaut: false)
+}
+
+/// Paramètres de requête pour la sauvegarde d'une paire
+#[derive(Debug, Deserialize)]
+struct BackupQuery {
+    symbol: String,
+}
+
+/// Paramètres de requête pour le flux SSE
+#[derive(Debug, Deserialize)]
+struct StreamQuery {
+    symbol: String,
+    timeframes: Option<String>, // ex: "5m,1h" (tous si absent)
+    token: Option<String>,      // exigé si STREAM_AUTH_TOKEN est défini
+    rsi: Option<usize>,         // période du RSI live (timeframes requis)
+}
+
+/// Délai de reconnexion automatique du navigateur (directive SSE retry)
+const SSE_RETRY_MS: u64 = 3000;
+
+/// Période de surveillance de la base pour les nouvelles bougies
+const REALTIME_POLL_PERIOD: Duration = Duration::from_secs(2);
+
+/// Durée de vie des réponses de /api/candles/latest
+const LATEST_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// Durée de vie des réponses de /api/statistics
+const STATISTICS_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Durée de vie des réponses de /api/pairs/{symbol}/timeframes
+const TIMEFRAMES_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Bougies source lues au maximum pour un rééchantillonnage
+const RESAMPLE_SOURCE_LIMIT: usize = 50_000;
+
+/// Blocs CSV en attente d'envoi avant que la lecture SQLite ne se bloque
+const EXPORT_CHANNEL_CAPACITY: usize = 4;
+
+/// Taille maximale d'un fichier CSV importé
+const MAX_IMPORT_BYTES: usize = 64 * 1024 * 1024;
+
+/// Délai laissé aux requêtes en cours après Ctrl-C
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Passe à true à la réception de Ctrl-C (arrêt en cours)
+static SHUTDOWN: AtomicBool = AtomicBool::new(false);
+
 /// GET /api/pairs - Récupère toutes les paires disponibles
 #[get("/api/pairs")]
 async fn get_pairs(data: web::Data<Mutex<AppState>>) -> impl Responder {
     let state = data.lock().unwrap();
-    let conn = match Connection::open(&state.db_path) {
+    let conn = match state.db_pool.get() {
         Ok(c) => c,
         Err(e) => {
             return HttpResponse::InternalServerError().json(serde_json::json!({
@@ -60,51 +556,1983 @@ async fn get_pairs(data: web::Data<Mutex<AppState>>) -> impl Responder {
         }
     };
 
-    // Récupérer toutes les paires distinctes avec leurs timeframes
-    let mut stmt = match conn.prepare(
-        "SELECT DISTINCT symbol, timeframe
-         FROM candlesticks
-         WHERE provider = 'binance'
-         ORDER BY symbol, timeframe",
-    ) {
-        Ok(s) => s,
+    // Récupérer toutes les paires distinctes avec leurs timeframes
+    let mut stmt = match conn.prepare(
+        "SELECT DISTINCT symbol, timeframe
+         FROM candlesticks
+         WHERE provider = 'binance'
+         ORDER BY symbol, timeframe",
+    ) {
+        Ok(s) => s,
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Query error: {}", e)
+            }));
+        }
+    };
+
+    let rows = match stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    }) {
+        Ok(r) => r,
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Query mapping error: {}", e)
+            }));
+        }
+    };
+
+    // Grouper par symbole
+    let mut pairs_map: std::collections::HashMap<String, Vec<String>> =
+        std::collections::HashMap::new();
+
+    for row in rows {
+        if let Ok((symbol, timeframe)) = row {
+            pairs_map
+                .entry(symbol)
+                .or_insert_with(Vec::new)
+                .push(timeframe);
+        }
+    }
+
+    // Timeframes du plus court au plus long (l'ordre SQL est alphabétique)
+    let pairs: Vec<TradingPair> = pairs_map
+        .into_iter()
+        .map(|(symbol, mut timeframes)| {
+            timeframes.sort_by(|a, b| compare_timeframes(a, b));
+            TradingPair { symbol, timeframes }
+        })
+        .collect();
+
+    HttpResponse::Ok().json(pairs)
+}
+
+/// Clé de cache de /api/pairs/{symbol}/timeframes (une entrée par symbole)
+fn timeframes_cache_key(symbol: &str) -> CacheKey {
+    CacheKey {
+        endpoint: "timeframes",
+        symbol: symbol.to_string(),
+        timeframe: String::new(),
+        source_tf: None,
+        start: None,
+        end: None,
+        limit: None,
+    }
+}
+
+/// GET /api/pairs/{symbol}/timeframes - Couverture de chaque timeframe d'un symbole
+///
+/// Nombre de bougies, bornes, trous, part interpolée et complétion du backfill
+/// (voir timeframe_infos): de quoi choisir les timeframes à afficher et
+/// décider d'un backfill. Cache par symbole de TIMEFRAMES_CACHE_TTL
+#[get("/api/pairs/{symbol}/timeframes")]
+async fn get_pair_timeframes(
+    data: web::Data<Mutex<AppState>>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let (db_pool, cache) = {
+        let state = data.lock().unwrap();
+        (Arc::clone(&state.db_pool), state.timeframes_cache.clone())
+    };
+    let symbol = path.into_inner();
+
+    let key = timeframes_cache_key(&symbol);
+    if let Some(body) = cache.get(&key) {
+        return HttpResponse::Ok()
+            .content_type("application/json")
+            .body(body);
+    }
+
+    let result = web::block(move || {
+        let conn = db_pool.get()?;
+        timeframe_infos(&conn, "binance", &symbol)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(infos)) => match serde_json::to_vec(&infos) {
+            Ok(json) => {
+                let body = web::Bytes::from(json);
+                cache.insert(key, body.clone());
+                HttpResponse::Ok()
+                    .content_type("application/json")
+                    .body(body)
+            }
+            Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            })),
+        },
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/candles/export/csv - Export CSV d'une série complète ou d'une plage
+///
+/// STREAMING: un thread bloquant lit SQLite ligne à ligne et envoie des blocs
+/// par un canal borné (contre-pression), la réponse est transmise en chunked.
+/// La mémoire reste bornée même pour plusieurs millions de lignes
+#[get("/api/candles/export/csv")]
+async fn export_candles_csv(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<ExportQuery>,
+) -> impl Responder {
+    let format: ExportFormat = match query.format.as_deref().map(str::parse).transpose() {
+        Ok(format) => format.unwrap_or_default(),
+        Err(e) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let symbol = query.symbol.clone();
+    let timeframe = query.timeframe.clone();
+    let range = query.range_ms();
+    let filename = query.filename("csv");
+
+    let (sender, mut receiver) = tokio::sync::mpsc::channel::<String>(EXPORT_CHANNEL_CAPACITY);
+    actix_web::rt::task::spawn_blocking(move || {
+        let conn = match db_pool.get() {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!(error = %e, "Export CSV impossible");
+                return;
+            }
+        };
+        // Client déconnecté: blocking_send échoue et la lecture s'arrête
+        let result = write_csv(
+            &conn,
+            "binance",
+            &symbol,
+            &timeframe,
+            range,
+            format,
+            |chunk| sender.blocking_send(chunk).is_ok(),
+        );
+        match result {
+            Ok(rows) => {
+                debug!(symbol = %symbol, timeframe = %timeframe, rows, "Export CSV terminé")
+            }
+            Err(e) => warn!(symbol = %symbol, error = %e, "Export CSV interrompu"),
+        }
+    });
+
+    let stream = async_stream::stream! {
+        while let Some(chunk) = receiver.recv().await {
+            yield Ok::<_, actix_web::Error>(web::Bytes::from(chunk));
+        }
+    };
+
+    HttpResponse::Ok()
+        .content_type("text/csv; charset=utf-8")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(filename)],
+        })
+        // Potentiellement très volumineux: jamais mis en cache
+        .insert_header(("Cache-Control", "no-store"))
+        .streaming(stream)
+}
+
+/// GET /api/candles/export/parquet - Export Parquet (snappy) d'une série
+///
+/// Même principe que l'export CSV: le fichier est écrit par un thread
+/// bloquant dans un ChannelWriter, la réponse est transmise en chunked
+///
+/// FEATURE: absent (404) sans la feature `parquet`
+#[cfg(feature = "parquet")]
+#[get("/api/candles/export/parquet")]
+async fn export_candles_parquet(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<ExportQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let symbol = query.symbol.clone();
+    let timeframe = query.timeframe.clone();
+    let range = query.range_ms();
+    let filename = query.filename("parquet");
+
+    let (sender, mut receiver) = tokio::sync::mpsc::channel::<web::Bytes>(EXPORT_CHANNEL_CAPACITY);
+    actix_web::rt::task::spawn_blocking(move || {
+        let conn = match db_pool.get() {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!(error = %e, "Export Parquet impossible");
+                return;
+            }
+        };
+        let output = ChannelWriter { sender };
+        match write_parquet(&conn, "binance", &symbol, &timeframe, range, output) {
+            Ok(rows) => {
+                debug!(symbol = %symbol, timeframe = %timeframe, rows, "Export Parquet terminé")
+            }
+            Err(e) => warn!(symbol = %symbol, error = %e, "Export Parquet interrompu"),
+        }
+    });
+
+    let stream = async_stream::stream! {
+        while let Some(chunk) = receiver.recv().await {
+            yield Ok::<_, actix_web::Error>(chunk);
+        }
+    };
+
+    HttpResponse::Ok()
+        .content_type("application/vnd.apache.parquet")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(filename)],
+        })
+        .insert_header(("Cache-Control", "no-store"))
+        .streaming(stream)
+}
+
+/// Routes d'export (Parquet uniquement avec la feature `parquet`)
+fn configure_exports(cfg: &mut web::ServiceConfig) {
+    cfg.service(export_candles_csv);
+    #[cfg(feature = "parquet")]
+    cfg.service(export_candles_parquet);
+}
+
+/// Sortie io::Write transmettant les octets écrits à la réponse HTTP
+///
+/// NOTE: write_parquet bufferise déjà ses écritures, chaque write() est un bloc
+#[cfg(feature = "parquet")]
+struct ChannelWriter {
+    sender: tokio::sync::mpsc::Sender<web::Bytes>,
+}
+
+#[cfg(feature = "parquet")]
+impl std::io::Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        // Client déconnecté: l'erreur arrête l'écriture du fichier
+        self.sender
+            .blocking_send(web::Bytes::copy_from_slice(buf))
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::BrokenPipe))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Date compacte pour le nom du fichier exporté (ex: 20240101)
+fn export_date(timestamp_ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(timestamp_ms)
+        .map(|d| d.format("%Y%m%d").to_string())
+        .unwrap_or_default()
+}
+
+/// POST /api/candles/import/csv - Import d'un fichier CSV (multipart/form-data)
+///
+/// Le fichier est lu dans le champ "file", les bougies sont insérées avec
+/// provider = "import" et interpolated = 0. Format et validation: voir import.rs
+///
+/// RETOUR: 400 si le fichier est invalide (rien n'est inséré), 413 au-delà
+/// de MAX_IMPORT_BYTES
+async fn import_candles_csv(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<ImportQuery>,
+    mut payload: Multipart,
+) -> impl Responder {
+    let mut content = None;
+    loop {
+        let mut field = match payload.try_next().await {
+            Ok(Some(field)) => field,
+            Ok(None) => break,
+            Err(e) => {
+                return HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": format!("Multipart invalide: {}", e)
+                }));
+            }
+        };
+        // Les autres champs sont ignorés (leur contenu est sauté par Multipart)
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let mut bytes = Vec::new();
+        loop {
+            match field.try_next().await {
+                Ok(Some(chunk)) => {
+                    if bytes.len() + chunk.len() > MAX_IMPORT_BYTES {
+                        return HttpResponse::PayloadTooLarge().json(serde_json::json!({
+                            "error": format!("Fichier limité à {} octets", MAX_IMPORT_BYTES)
+                        }));
+                    }
+                    bytes.extend_from_slice(&chunk);
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    return HttpResponse::BadRequest().json(serde_json::json!({
+                        "error": format!("Multipart invalide: {}", e)
+                    }));
+                }
+            }
+        }
+        content = Some(bytes);
+    }
+
+    let content = match content.map(String::from_utf8) {
+        Some(Ok(content)) => content,
+        Some(Err(_)) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Le fichier CSV doit être encodé en UTF-8"
+            }));
+        }
+        None => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Champ multipart \"file\" absent"
+            }));
+        }
+    };
+
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let symbol = query.symbol.clone();
+    let timeframe = query.timeframe.clone();
+    let result = web::block(move || {
+        let mut conn = db_pool.get()?;
+        import_csv(&mut conn, &content, IMPORT_PROVIDER, &symbol, &timeframe)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(summary)) => {
+            info!(
+                symbol = %query.symbol,
+                timeframe = %query.timeframe,
+                parsed = summary.parsed,
+                inserted = summary.inserted,
+                "Import CSV terminé"
+            );
+            HttpResponse::Ok().json(serde_json::json!({
+                "symbol": query.symbol,
+                "timeframe": query.timeframe,
+                "provider": IMPORT_PROVIDER,
+                "parsed": summary.parsed,
+                "inserted": summary.inserted
+            }))
+        }
+        Ok(Err(e @ (CandleError::ValidationError(_) | CandleError::ParseError(_)))) => {
+            HttpResponse::BadRequest().json(serde_json::json!({
+                "error": e.to_string()
+            }))
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// Ligne de /api/stats: CandleStats et la part de bougies interpolées
+/// (verify::verify_interpolation, plus longue série continue)
+#[derive(Debug, Serialize)]
+struct SeriesStatsView {
+    #[serde(flatten)]
+    stats: CandleStats,
+    interpolated_pct: f64,
+    longest_interpolated_run: Option<verify::InterpolatedRun>,
+}
+
+/// GET /api/stats - Volume et couverture de chaque série (DatabaseManager::stats_all)
+#[get("/api/stats")]
+async fn get_stats(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<StatsQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let symbol = query.into_inner().symbol;
+    // Agrégat sur toute la table: hors du thread de l'event loop
+    let result = web::block(move || {
+        let conn = db_pool.get()?;
+        symbol_candle_stats(&conn, symbol.as_deref())?
+            .into_iter()
+            .map(|stats| {
+                let interpolation = verify::verify_interpolation(
+                    &conn,
+                    &stats.provider,
+                    &stats.symbol,
+                    &stats.timeframe,
+                )?;
+                Ok(SeriesStatsView {
+                    stats,
+                    interpolated_pct: interpolation.percentage,
+                    longest_interpolated_run: interpolation.longest_run,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+    })
+    .await;
+
+    match result {
+        Ok(Ok(stats)) => HttpResponse::Ok().json(stats),
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/status - Progression du backfill de chaque timeframe d'un symbole
+///
+/// Lignes de timeframe_status (TimeframeStatus::list) avec le nombre de
+/// bougies stockées et interpolées: jusqu'où remontent les données et si
+/// le backfill est complet. Liste vide si le symbole n'a jamais été récupéré
+///
+/// last_verification: dernier passage enregistré (VerificationHistory::latest),
+/// null si la série n'a jamais été vérifiée avec --record
+#[get("/api/status")]
+async fn get_backfill_status(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<StatusQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let symbol = query.into_inner().symbol;
+
+    let result = {
+        let symbol = symbol.clone();
+        web::block(move || {
+            let conn = db_pool.get()?;
+            TimeframeStatus::list(&conn, "binance", &symbol)?
+                .into_iter()
+                .map(|row| {
+                    let last_verification =
+                        VerificationHistory::latest(&conn, "binance", &symbol, &row.timeframe)?;
+                    Ok(TimeframeStatusView::new(row, last_verification))
+                })
+                .collect::<Result<Vec<_>, CandleError>>()
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(timeframes)) => HttpResponse::Ok().json(serde_json::json!({
+            "symbol": symbol,
+            "timeframes": timeframes
+        })),
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/statistics - Statistiques descriptives OHLCV d'une plage
+/// (voir descriptive_stats), mises en cache STATISTICS_CACHE_TTL
+#[get("/api/statistics")]
+async fn get_statistics(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<StatisticsQuery>,
+) -> impl Responder {
+    let (db_pool, cache) = {
+        let state = data.lock().unwrap();
+        (Arc::clone(&state.db_pool), state.statistics_cache.clone())
+    };
+    let query = query.into_inner();
+
+    let key = CacheKey {
+        endpoint: "statistics",
+        symbol: query.symbol.clone(),
+        timeframe: query.timeframe.clone(),
+        source_tf: None,
+        start: query.start,
+        end: query.end,
+        limit: None,
+    };
+    if let Some(body) = cache.get(&key) {
+        return HttpResponse::Ok()
+            .content_type("application/json")
+            .body(body);
+    }
+
+    let result = web::block(move || {
+        let conn = db_pool.get()?;
+        descriptive_stats(
+            &conn,
+            "binance",
+            &query.symbol,
+            &query.timeframe,
+            query.start.map_or(0, |s| s * 1000)..=query.end.map_or(i64::MAX, |e| e * 1000),
+        )
+    })
+    .await;
+
+    match result {
+        Ok(Ok(Some(stats))) => match serde_json::to_vec(&stats) {
+            Ok(json) => {
+                let body = web::Bytes::from(json);
+                cache.insert(key, body.clone());
+                HttpResponse::Ok()
+                    .content_type("application/json")
+                    .body(body)
+            }
+            Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            })),
+        },
+        Ok(Ok(None)) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Aucune bougie dans la plage demandée"
+        })),
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/indicators/obv - On-Balance Volume d'une paire/timeframe
+///
+/// Calculé depuis la première bougie stockée (voir indicators::obv), puis
+/// limité à la plage start/end
+#[get("/api/indicators/obv")]
+async fn get_obv(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<IndicatorQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+
+    let result = {
+        let (symbol, timeframe) = (query.symbol.clone(), query.timeframe.clone());
+        web::block(move || {
+            let conn = db_pool.get()?;
+            obv_series(
+                &conn,
+                "binance",
+                &symbol,
+                &timeframe,
+                query.start.map_or(0, |s| s * 1000)..=query.end.map_or(i64::MAX, |e| e * 1000),
+            )
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(values)) => {
+            let values: Vec<IndicatorPoint> = values
+                .into_iter()
+                .map(|v| IndicatorPoint {
+                    time: v.open_time / 1000,
+                    value: v.obv,
+                })
+                .collect();
+            HttpResponse::Ok().json(serde_json::json!({
+                "symbol": query.symbol,
+                "timeframe": query.timeframe,
+                "values": values
+            }))
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/indicators/macd - MACD, ligne de signal et histogramme
+///
+/// Voir indicators::macd: une série de points par composante (trois lignes
+/// du graphique), bougies de chauffe lues avant la plage. 400 si les
+/// périodes ne vérifient pas 1 <= fast < slow, signal >= 1
+#[get("/api/indicators/macd")]
+async fn get_macd(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<MacdQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+    let (fast, slow, signal) = (
+        query.fast.unwrap_or(DEFAULT_MACD_FAST),
+        query.slow.unwrap_or(DEFAULT_MACD_SLOW),
+        query.signal.unwrap_or(DEFAULT_MACD_SIGNAL),
+    );
+    let macd = match Macd::new(fast, slow, signal) {
+        Ok(macd) => macd,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
+    let result = {
+        let (symbol, timeframe) = (query.symbol.clone(), query.timeframe.clone());
+        web::block(move || {
+            let conn = db_pool.get()?;
+            component_series(
+                &conn,
+                "binance",
+                &symbol,
+                &timeframe,
+                &macd,
+                query.start.map_or(0, |s| s * 1000)..=query.end.map_or(i64::MAX, |e| e * 1000),
+            )
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(series)) => {
+            let mut response = serde_json::json!({
+                "symbol": query.symbol,
+                "timeframe": query.timeframe,
+                "fast": fast,
+                "slow": slow,
+                "signal_period": signal,
+            });
+            for component in series {
+                let values: Vec<IndicatorPoint> = component
+                    .values
+                    .into_iter()
+                    .map(|v| IndicatorPoint {
+                        time: v.open_time / 1000,
+                        value: v.value,
+                    })
+                    .collect();
+                response[component.component] = serde_json::json!(values);
+            }
+            HttpResponse::Ok().json(response)
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/indicators/bollinger - Bandes de Bollinger (middle, upper,
+/// lower)
+///
+/// Voir indicators::bollinger: une série de points par bande, les period -
+/// 1 bougies qui précèdent la plage complètent la première fenêtre. 400 si
+/// period = 0 ou k < 0
+#[get("/api/indicators/bollinger")]
+async fn get_bollinger(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<BollingerQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+    let period = query.period.unwrap_or(DEFAULT_BOLLINGER_PERIOD);
+    let k = query.k.unwrap_or(DEFAULT_BOLLINGER_K);
+    let bollinger = match Bollinger::new(period, k) {
+        Ok(bollinger) => bollinger,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
+    let result = {
+        let (symbol, timeframe) = (query.symbol.clone(), query.timeframe.clone());
+        web::block(move || {
+            let conn = db_pool.get()?;
+            component_series(
+                &conn,
+                "binance",
+                &symbol,
+                &timeframe,
+                &bollinger,
+                query.start.map_or(0, |s| s * 1000)..=query.end.map_or(i64::MAX, |e| e * 1000),
+            )
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(series)) => {
+            let mut response = serde_json::json!({
+                "symbol": query.symbol,
+                "timeframe": query.timeframe,
+                "period": period,
+                "k": k,
+            });
+            for component in series {
+                let values: Vec<IndicatorPoint> = component
+                    .values
+                    .into_iter()
+                    .map(|v| IndicatorPoint {
+                        time: v.open_time / 1000,
+                        value: v.value,
+                    })
+                    .collect();
+                response[component.component] = serde_json::json!(values);
+            }
+            HttpResponse::Ok().json(response)
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/indicators/atr - Average True Range de Wilder
+///
+/// Voir indicators::atr: bougies de chauffe lues avant la plage;
+/// exclude_interpolated=true calcule sur les seules bougies réelles (les
+/// bougies interpolées sont omises). 400 si period = 0
+#[get("/api/indicators/atr")]
+async fn get_atr(data: web::Data<Mutex<AppState>>, query: web::Query<AtrQuery>) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+    let period = query.period.unwrap_or(DEFAULT_ATR_PERIOD);
+    let exclude_interpolated = query.exclude_interpolated.unwrap_or(false);
+    let atr = match Atr::new(period, exclude_interpolated) {
+        Ok(atr) => atr,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
+    let result = {
+        let (symbol, timeframe) = (query.symbol.clone(), query.timeframe.clone());
+        web::block(move || {
+            let conn = db_pool.get()?;
+            indicator_series(
+                &conn,
+                "binance",
+                &symbol,
+                &timeframe,
+                &atr,
+                query.start.map_or(0, |s| s * 1000)..=query.end.map_or(i64::MAX, |e| e * 1000),
+            )
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(values)) => {
+            let values: Vec<IndicatorPoint> = values
+                .into_iter()
+                .map(|v| IndicatorPoint {
+                    time: v.open_time / 1000,
+                    value: v.value,
+                })
+                .collect();
+            HttpResponse::Ok().json(serde_json::json!({
+                "symbol": query.symbol,
+                "timeframe": query.timeframe,
+                "period": period,
+                "exclude_interpolated": exclude_interpolated,
+                "values": values
+            }))
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/indicators/vwap - VWAP ancré par session
+///
+/// Voir indicators::vwap: remis à zéro à chaque session de `anchor`
+/// (minuit UTC par défaut), bougies lues depuis le début de session de la
+/// plage. 400 si anchor n'est pas un timeframe connu
+#[get("/api/indicators/vwap")]
+async fn get_vwap(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<VwapQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+    let vwap = match Vwap::new(query.anchor.as_deref().unwrap_or(DEFAULT_VWAP_ANCHOR)) {
+        Ok(vwap) => vwap,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+    let anchor = vwap.anchor().to_string();
+
+    let result = {
+        let (symbol, timeframe) = (query.symbol.clone(), query.timeframe.clone());
+        web::block(move || {
+            let conn = db_pool.get()?;
+            vwap_series(
+                &conn,
+                "binance",
+                &symbol,
+                &timeframe,
+                &vwap,
+                query.start.map_or(0, |s| s * 1000)..=query.end.map_or(i64::MAX, |e| e * 1000),
+            )
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(values)) => {
+            let values: Vec<IndicatorPoint> = values
+                .into_iter()
+                .map(|v| IndicatorPoint {
+                    time: v.open_time / 1000,
+                    value: v.value,
+                })
+                .collect();
+            HttpResponse::Ok().json(serde_json::json!({
+                "symbol": query.symbol,
+                "timeframe": query.timeframe,
+                "anchor": anchor,
+                "values": values
+            }))
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/indicators/{name} - Indicateur générique (obv, sma, ema)
+///
+/// Voir indicators::values::indicator_by_name: mêmes valeurs que celles
+/// enregistrées par recalculate_indicator_for_range, calculées à la volée.
+/// Enregistré après les indicateurs dédiés (/api/indicators/obv,
+/// stochastic, ...), qui restent prioritaires. 400 si l'indicateur est
+/// inconnu ou la période nulle
+#[get("/api/indicators/{name}")]
+async fn get_indicator(
+    data: web::Data<Mutex<AppState>>,
+    path: web::Path<String>,
+    query: web::Query<GenericIndicatorQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let name = path.into_inner();
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+    let indicator = match indicator_by_name(&name, query.period) {
+        Ok(indicator) => indicator,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+    let params = indicator.params();
+
+    let result = {
+        let (symbol, timeframe) = (query.symbol.clone(), query.timeframe.clone());
+        web::block(move || {
+            let conn = db_pool.get()?;
+            indicator_series(
+                &conn,
+                "binance",
+                &symbol,
+                &timeframe,
+                indicator.as_ref(),
+                query.start.map_or(0, |s| s * 1000)..=query.end.map_or(i64::MAX, |e| e * 1000),
+            )
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(values)) => {
+            let values: Vec<IndicatorPoint> = values
+                .into_iter()
+                .map(|v| IndicatorPoint {
+                    time: v.open_time / 1000,
+                    value: v.value,
+                })
+                .collect();
+            HttpResponse::Ok().json(serde_json::json!({
+                "symbol": query.symbol,
+                "timeframe": query.timeframe,
+                "indicator": name,
+                "params": params,
+                "values": values
+            }))
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/indicators/stochastic - Oscillateur stochastique %K / %D
+///
+/// Voir indicators::stochastic: les bougies sans %K (début de série) sont
+/// omises. smooth > 1: %K lissé (stochastique lent)
+#[get("/api/indicators/stochastic")]
+async fn get_stochastic(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<StochasticQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+    let (k_period, k_smoothing, d_period) = (
+        query.k.unwrap_or(14),
+        query.smooth.unwrap_or(1),
+        query.d.unwrap_or(3),
+    );
+    if k_period == 0 || k_smoothing == 0 || d_period == 0 {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "k, smooth and d must be at least 1"
+        }));
+    }
+
+    let result = {
+        let (symbol, timeframe) = (query.symbol.clone(), query.timeframe.clone());
+        web::block(move || {
+            let conn = db_pool.get()?;
+            slow_stochastic_series(
+                &conn,
+                "binance",
+                &symbol,
+                &timeframe,
+                query.start.map_or(0, |s| s * 1000)..=query.end.map_or(i64::MAX, |e| e * 1000),
+                k_period,
+                k_smoothing,
+                d_period,
+            )
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(points)) => {
+            let values: Vec<StochasticPointJson> = points
+                .into_iter()
+                .filter_map(|point| {
+                    point.k.map(|k| StochasticPointJson {
+                        time: point.open_time / 1000,
+                        k,
+                        d: point.d,
+                    })
+                })
+                .collect();
+            HttpResponse::Ok().json(serde_json::json!({
+                "symbol": query.symbol,
+                "timeframe": query.timeframe,
+                "k_period": k_period,
+                "k_smoothing": k_smoothing,
+                "d_period": d_period,
+                "values": values
+            }))
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/indicators/pivot-points - Points pivots classiques
+///
+/// Niveaux de chaque période pivot_period (défaut 1d) dont le début est
+/// dans la plage, calculés sur la période précédente (voir
+/// indicators::pivot_points). 400 si pivot_period n'est pas un multiple
+/// strict du timeframe
+#[get("/api/indicators/pivot-points")]
+async fn get_pivot_points(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<PivotPointsQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+    let pivot_period = query
+        .pivot_period
+        .clone()
+        .unwrap_or_else(|| DEFAULT_PIVOT_PERIOD.to_string());
+
+    let result = {
+        let (symbol, timeframe, pivot_period) = (
+            query.symbol.clone(),
+            query.timeframe.clone(),
+            pivot_period.clone(),
+        );
+        web::block(move || {
+            let conn = db_pool.get()?;
+            pivot_point_series(
+                &conn,
+                "binance",
+                &symbol,
+                &timeframe,
+                &pivot_period,
+                query.start.map_or(0, |s| s * 1000)..=query.end.map_or(i64::MAX, |e| e * 1000),
+            )
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(points)) => {
+            let values: Vec<PivotPointJson> = points
+                .into_iter()
+                .map(|point| PivotPointJson {
+                    time: point.date / 1000,
+                    pp: point.pp,
+                    r1: point.r1,
+                    r2: point.r2,
+                    r3: point.r3,
+                    s1: point.s1,
+                    s2: point.s2,
+                    s3: point.s3,
+                })
+                .collect();
+            HttpResponse::Ok().json(serde_json::json!({
+                "symbol": query.symbol,
+                "timeframe": query.timeframe,
+                "pivot_period": pivot_period,
+                "values": values
+            }))
+        }
+        Ok(Err(e @ CandleError::ValidationError(_))) => {
+            HttpResponse::BadRequest().json(serde_json::json!({
+                "error": e.to_string()
+            }))
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/indicators/sar - Parabolic SAR
+///
+/// Voir indicators::parabolic_sar: calculé depuis la première bougie de la
+/// série, la première bougie n'a pas de SAR. 400 si les facteurs
+/// d'accélération sont invalides
+#[get("/api/indicators/sar")]
+async fn get_sar(data: web::Data<Mutex<AppState>>, query: web::Query<SarQuery>) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+    let initial_af = query.initial_af.unwrap_or(DEFAULT_INITIAL_AF);
+    let step_af = query.step.unwrap_or(DEFAULT_STEP_AF);
+    let max_af = query.max.unwrap_or(DEFAULT_MAX_AF);
+
+    let result = {
+        let (symbol, timeframe) = (query.symbol.clone(), query.timeframe.clone());
+        web::block(move || {
+            let conn = db_pool.get()?;
+            sar_series(
+                &conn,
+                "binance",
+                &symbol,
+                &timeframe,
+                query.start.map_or(0, |s| s * 1000)..=query.end.map_or(i64::MAX, |e| e * 1000),
+                initial_af,
+                step_af,
+                max_af,
+            )
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(points)) => {
+            let values: Vec<SarPointJson> = points
+                .into_iter()
+                .map(|point| SarPointJson {
+                    time: point.open_time / 1000,
+                    sar: point.sar,
+                    is_bullish: point.is_bullish,
+                })
+                .collect();
+            HttpResponse::Ok().json(serde_json::json!({
+                "symbol": query.symbol,
+                "timeframe": query.timeframe,
+                "initial_af": initial_af,
+                "step": step_af,
+                "max": max_af,
+                "values": values
+            }))
+        }
+        Ok(Err(e @ CandleError::ValidationError(_))) => {
+            HttpResponse::BadRequest().json(serde_json::json!({
+                "error": e.to_string()
+            }))
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/indicators/volatility - Volatilité historique ou de Parkinson
+///
+/// Fenêtre glissante de `period` bougies (voir indicators::volatility): les
+/// bougies sans fenêtre complète sont omises
+#[get("/api/indicators/volatility")]
+async fn get_volatility(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<VolatilityQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+    let period = query.period.unwrap_or(20);
+    if period < 2 {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "period must be at least 2"
+        }));
+    }
+
+    let result = {
+        let (symbol, timeframe) = (query.symbol.clone(), query.timeframe.clone());
+        web::block(move || {
+            let conn = db_pool.get()?;
+            volatility_series(
+                &conn,
+                "binance",
+                &symbol,
+                &timeframe,
+                query.start.map_or(0, |s| s * 1000)..=query.end.map_or(i64::MAX, |e| e * 1000),
+                query.kind,
+                period,
+                query.annualize.unwrap_or(false),
+            )
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(values)) => {
+            let values: Vec<IndicatorPoint> = values
+                .into_iter()
+                .map(|v| IndicatorPoint {
+                    time: v.open_time / 1000,
+                    value: v.volatility,
+                })
+                .collect();
+            HttpResponse::Ok().json(serde_json::json!({
+                "symbol": query.symbol,
+                "timeframe": query.timeframe,
+                "type": query.kind,
+                "period": period,
+                "values": values
+            }))
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/indicators/rolling - Moyenne, écart-type, minimum ou maximum
+/// glissant du close
+///
+/// Fenêtre de `period` bougies (voir indicators::rolling): les bougies
+/// sans fenêtre complète sont omises
+#[get("/api/indicators/rolling")]
+async fn get_rolling(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<RollingQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+    let period = query.period.unwrap_or(20);
+    let min_period = if query.kind == RollingKind::Std { 2 } else { 1 };
+    if period < min_period {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("period must be at least {}", min_period)
+        }));
+    }
+
+    let result = {
+        let (symbol, timeframe) = (query.symbol.clone(), query.timeframe.clone());
+        web::block(move || {
+            let conn = db_pool.get()?;
+            rolling_series(
+                &conn,
+                "binance",
+                &symbol,
+                &timeframe,
+                query.start.map_or(0, |s| s * 1000)..=query.end.map_or(i64::MAX, |e| e * 1000),
+                query.kind,
+                period,
+            )
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(values)) => {
+            let values: Vec<IndicatorPoint> = values
+                .into_iter()
+                .map(|v| IndicatorPoint {
+                    time: v.open_time / 1000,
+                    value: v.value,
+                })
+                .collect();
+            HttpResponse::Ok().json(serde_json::json!({
+                "symbol": query.symbol,
+                "timeframe": query.timeframe,
+                "type": query.kind,
+                "period": period,
+                "values": values
+            }))
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/compare - Une série comparée entre providers
+///
+/// Bougies de chaque provider et, pour chaque paire, écart moyen des
+/// closes et plus forte divergence (voir verify::compare_providers): un
+/// resample ou un export ne doit pas mélanger deux sources qui divergent
+#[get("/api/compare")]
+async fn get_provider_comparison(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<CompareQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+    let tolerance = query.tolerance.unwrap_or(verify::PROVIDER_TOLERANCE);
+    if !(tolerance.is_finite() && tolerance >= 0.0) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "tolerance must be a non-negative number"
+        }));
+    }
+    let providers: Vec<String> = query
+        .providers
+        .iter()
+        .flat_map(|list| list.split(','))
+        .map(|provider| provider.trim().to_string())
+        .filter(|provider| !provider.is_empty())
+        .collect();
+
+    let result = web::block(move || {
+        let conn = db_pool.get()?;
+        verify::compare_providers(
+            &conn,
+            &query.symbol,
+            &query.timeframe,
+            &providers,
+            tolerance,
+        )
+    })
+    .await;
+
+    match result {
+        Ok(Ok(comparison)) => HttpResponse::Ok().json(comparison),
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/verify/{symbol}/outliers - Bougies au close aberrant
+///
+/// Close à plus de z_threshold écarts-types de la moyenne des
+/// OUTLIER_WINDOW bougies précédentes (voir verify::detect_price_outliers):
+/// flash crash réel ou donnée erronée, à confronter aux trous et aux
+/// bougies interpolées voisines
+#[get("/api/verify/{symbol}/outliers")]
+async fn get_price_outliers(
+    data: web::Data<Mutex<AppState>>,
+    path: web::Path<String>,
+    query: web::Query<OutliersQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let symbol = path.into_inner();
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+    let z_threshold = query.z_threshold.unwrap_or(3.0);
+    if !(z_threshold.is_finite() && z_threshold > 0.0) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "z_threshold must be a positive number"
+        }));
+    }
+
+    let result = {
+        let (symbol, timeframe) = (symbol.clone(), query.timeframe.clone());
+        web::block(move || {
+            let conn = db_pool.get()?;
+            verify::detect_price_outliers(&conn, "binance", &symbol, &timeframe, z_threshold)
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(outliers)) => HttpResponse::Ok().json(serde_json::json!({
+            "symbol": symbol,
+            "timeframe": query.timeframe,
+            "z_threshold": z_threshold,
+            "window": verify::OUTLIER_WINDOW,
+            "outliers": outliers
+        })),
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/analytics/returns - Rendements simples (%), logarithmiques ou
+/// cumulés (%) du close
+///
+/// Voir analytics::returns::returns_series: le rendement cumulé part du
+/// premier close de la plage
+#[get("/api/analytics/returns")]
+async fn get_returns(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<ReturnsQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+
+    let result = {
+        let (symbol, timeframe) = (query.symbol.clone(), query.timeframe.clone());
+        web::block(move || {
+            let conn = db_pool.get()?;
+            returns_series(
+                &conn,
+                "binance",
+                &symbol,
+                &timeframe,
+                query.start.map_or(0, |s| s * 1000)..=query.end.map_or(i64::MAX, |e| e * 1000),
+                query.kind,
+            )
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(values)) => {
+            let values: Vec<IndicatorPoint> = values
+                .into_iter()
+                .map(|v| IndicatorPoint {
+                    time: v.open_time / 1000,
+                    value: v.value,
+                })
+                .collect();
+            HttpResponse::Ok().json(serde_json::json!({
+                "symbol": query.symbol,
+                "timeframe": query.timeframe,
+                "type": query.kind,
+                "values": values
+            }))
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/patterns - Figures de chandeliers d'une paire/timeframe
+///
+/// Détectées à la volée (voir patterns::detect_patterns); un engulfing sur
+/// la première bougie de la plage est reconnu
+#[get("/api/patterns")]
+async fn get_patterns(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<IndicatorQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+
+    let result = {
+        let (symbol, timeframe) = (query.symbol.clone(), query.timeframe.clone());
+        web::block(move || {
+            let conn = db_pool.get()?;
+            pattern_series(
+                &conn,
+                "binance",
+                &symbol,
+                &timeframe,
+                query.start.map_or(0, |s| s * 1000)..=query.end.map_or(i64::MAX, |e| e * 1000),
+            )
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(events)) => {
+            let patterns: Vec<PatternPoint> = events
+                .into_iter()
+                .map(|event| PatternPoint {
+                    time: event.open_time / 1000,
+                    pattern: event.pattern,
+                })
+                .collect();
+            HttpResponse::Ok().json(serde_json::json!({
+                "symbol": query.symbol,
+                "timeframe": query.timeframe,
+                "patterns": patterns
+            }))
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// GET /api/db/integrity - Contrôle d'intégrité complet de la base
+///
+/// Voir check_integrity: lit toute la base, peut prendre plusieurs minutes
+/// sur une grosse base (thread bloquant, les autres requêtes continuent)
+#[get("/api/db/integrity")]
+async fn get_db_integrity(data: web::Data<Mutex<AppState>>) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+
+    let result = web::block(move || {
+        let conn = db_pool.get()?;
+        check_integrity(&conn)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(errors)) => {
+            if !errors.is_empty() {
+                warn!(errors = errors.len(), "Contrôle d'intégrité en échec");
+            }
+            HttpResponse::Ok().json(serde_json::json!({
+                "ok": errors.is_empty(),
+                "errors": errors
+            }))
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// POST /api/db/vacuum - Compacte la base du serveur (clé API)
+///
+/// Voir vacuum_database: réécrit tout le fichier (thread bloquant), échoue
+/// (500) si une autre connexion écrit ou si le disque manque d'espace
+async fn vacuum_db(data: web::Data<Mutex<AppState>>) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+
+    let result = web::block(move || {
+        let conn = db_pool.get()?;
+        vacuum_database(&conn)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(bytes_freed)) => HttpResponse::Ok().json(serde_json::json!({
+            "bytes_freed": bytes_freed
+        })),
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// POST /api/admin/maintenance - Maintenance de la base du serveur (clé API)
+///
+/// Voir optimize_database: le VACUUM échoue (500) si une autre connexion écrit
+async fn run_maintenance(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<MaintenanceQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let vacuum = query.vacuum.unwrap_or(false);
+
+    let result = web::block(move || {
+        let conn = db_pool.get()?;
+        optimize_database(&conn, vacuum)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(report)) => {
+            info!(
+                vacuum,
+                before_bytes = report.before.bytes(),
+                after_bytes = report.after.bytes(),
+                "Maintenance de la base terminée"
+            );
+            HttpResponse::Ok().json(report)
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// POST /api/backup?symbol=X - Sauvegarde d'une paire côté serveur (clé API)
+///
+/// Écrit `<backup_dir>/YYYYMMDD_HHMMSS/<SYMBOL>.db` (voir backup_database:
+/// copie par étapes, les écritures en cours ne sont pas bloquées) et
+/// retourne son chemin et sa taille
+async fn backup_pair(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<BackupQuery>,
+) -> impl Responder {
+    let (db_pool, backup_root) = {
+        let state = data.lock().unwrap();
+        (Arc::clone(&state.db_pool), state.config.backup_dir.clone())
+    };
+    let symbol = query.symbol.to_uppercase();
+
+    let result = {
+        let symbol = symbol.clone();
+        web::block(move || -> Result<_, CandleError> {
+            let conn = db_pool.get()?;
+            let exists: bool = conn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM candlesticks WHERE symbol = ?1)",
+                params![symbol],
+                |row| row.get(0),
+            )?;
+            if !exists {
+                return Ok(None);
+            }
+            let dir = snapshot_dir(&backup_root);
+            std::fs::create_dir_all(&dir)?;
+            backup_database(&conn, &dir.join(format!("{}.db", symbol)), Some(&symbol)).map(Some)
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(Some(report))) => {
+            info!(
+                symbol = %symbol,
+                path = %report.path,
+                bytes = report.bytes,
+                "Sauvegarde créée"
+            );
+            HttpResponse::Ok().json(report)
+        }
+        Ok(Ok(None)) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": format!("Aucune bougie pour {}", symbol)
+        })),
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// Caches dont les réponses dépendent des bougies stockées (purgés par
+/// invalidate_series après une écriture du serveur)
+fn candle_caches(state: &AppState) -> [ResponseCache<web::Bytes>; 4] {
+    [
+        state.cache.clone(),
+        state.latest_cache.clone(),
+        state.statistics_cache.clone(),
+        state.timeframes_cache.clone(),
+    ]
+}
+
+/// GET /api/gaps - Rapport des gaps d'une paire/timeframe
+///
+/// Voir GapFiller::gap_report: bougies manquantes de la plage, y compris
+/// avant la première et après la dernière bougie stockée (start_time /
+/// end_time en secondes: première et dernière bougie manquante), total
+/// manquant et couverture
+///
+/// Bornes par défaut: `start` = oldest_candle_time de timeframe_status (à
+/// défaut la première bougie stockée), `end` = dernière bougie clôturée.
+/// Série inconnue sans `start`: rapport vide
+#[get("/api/gaps")]
+async fn get_gaps(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<GapsQuery>,
+) -> impl Responder {
+    let db_pool = Arc::clone(&data.lock().unwrap().db_pool);
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+
+    let result = web::block(move || {
+        let conn = db_pool.get()?;
+        let end = match query.end {
+            Some(end) => end * 1000,
+            None => {
+                let now_ms = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as i64;
+                now_ms - parse_timeframe(&query.timeframe)?
+            }
+        };
+        let start = match query.start {
+            Some(start) => Some(start * 1000),
+            None => TimeframeStatus::get_last_candle_time(
+                &conn,
+                "binance",
+                &query.symbol,
+                &query.timeframe,
+            )
+            .or(conn.query_row(
+                "SELECT MIN(open_time) FROM candlesticks
+                 WHERE provider = 'binance' AND symbol = ?1 AND timeframe = ?2",
+                params![query.symbol, query.timeframe],
+                |row| row.get(0),
+            )?),
+        };
+        // Aucune borne de départ: plage vide (end < start)
+        GapFiller::gap_report(
+            &conn,
+            "binance",
+            &query.symbol,
+            &query.timeframe,
+            start.unwrap_or(end + 1),
+            end,
+        )
+    })
+    .await;
+
+    match result {
+        Ok(Ok(mut report)) => {
+            for gap in &mut report.gaps {
+                gap.start_time /= 1000;
+                gap.end_time /= 1000;
+            }
+            HttpResponse::Ok().json(report)
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// POST /api/gaps/fill - Comble les gaps d'une paire/timeframe (clé API)
+///
+/// Voir GapFiller::fill_gaps_in_range: agrégation d'un timeframe inférieur
+/// en priorité, puis `method` (défaut linear) en repli. Les réponses en
+/// cache de la série sont purgées. 400 si method est inconnue
+async fn fill_gaps(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<GapFillQuery>,
+) -> impl Responder {
+    let (db_pool, caches) = {
+        let state = data.lock().unwrap();
+        (Arc::clone(&state.db_pool), candle_caches(&state))
+    };
+    let query = query.into_inner();
+
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
+    let strategy = match query
+        .method
+        .as_deref()
+        .map(str::parse::<FillStrategy>)
+        .transpose()
+    {
+        Ok(strategy) => strategy.unwrap_or_default(),
         Err(e) => {
-            return HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": format!("Query error: {}", e)
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": e.to_string()
             }));
         }
     };
 
-    let rows = match stmt.query_map([], |row| {
-        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
-    }) {
-        Ok(r) => r,
-        Err(e) => {
-            return HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": format!("Query mapping error: {}", e)
-            }));
+    let result = {
+        let (symbol, timeframe) = (query.symbol.clone(), query.timeframe.clone());
+        web::block(move || {
+            let mut conn = db_pool.get()?;
+            GapFiller::fill_gaps_in_range(
+                &mut conn,
+                "binance",
+                &symbol,
+                &timeframe,
+                query.start.map_or(0, |s| s * 1000),
+                query.end.map_or(i64::MAX, |e| e * 1000),
+                strategy,
+            )
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(filled)) => {
+            info!(
+                symbol = %query.symbol,
+                timeframe = %query.timeframe,
+                filled,
+                method = strategy.as_str(),
+                "Gaps comblés"
+            );
+            if filled > 0 {
+                for cache in &caches {
+                    invalidate_series(cache, &query.symbol, &query.timeframe);
+                }
+            }
+            HttpResponse::Ok().json(serde_json::json!({
+                "filled": filled,
+                "method": strategy.as_str()
+            }))
         }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// DELETE /api/gaps/interpolated - Supprime les bougies synthétiques d'une
+/// paire/timeframe (clé API)
+///
+/// Voir GapFiller::purge_interpolated: rouvre les gaps comblés (par
+/// exemple avant une récupération de réparation). Les réponses en cache de
+/// la série sont purgées
+async fn delete_interpolated(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<GapsQuery>,
+) -> impl Responder {
+    let (db_pool, caches) = {
+        let state = data.lock().unwrap();
+        (Arc::clone(&state.db_pool), candle_caches(&state))
     };
+    let query = query.into_inner();
 
-    // Grouper par symbole
-    let mut pairs_map: std::collections::HashMap<String, Vec<String>> =
-        std::collections::HashMap::new();
+    if !is_valid_timeframe(&query.timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", query.timeframe)
+        }));
+    }
 
-    for row in rows {
-        if let Ok((symbol, timeframe)) = row {
-            pairs_map
-                .entry(symbol)
-                .or_insert_with(Vec::new)
-                .push(timeframe);
+    let result = {
+        let (symbol, timeframe) = (query.symbol.clone(), query.timeframe.clone());
+        web::block(move || {
+            let conn = db_pool.get()?;
+            GapFiller::purge_interpolated(
+                &conn,
+                "binance",
+                &symbol,
+                &timeframe,
+                query.start.map_or(0, |s| s * 1000),
+                query.end.map_or(i64::MAX, |e| e * 1000),
+            )
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(deleted)) => {
+            info!(
+                symbol = %query.symbol,
+                timeframe = %query.timeframe,
+                deleted,
+                "Bougies interpolées supprimées"
+            );
+            if deleted > 0 {
+                for cache in &caches {
+                    invalidate_series(cache, &query.symbol, &query.timeframe);
+                }
+            }
+            HttpResponse::Ok().json(serde_json::json!({ "deleted": deleted }))
         }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
     }
+}
 
-    let pairs: Vec<TradingPair> = pairs_map
-        .into_iter()
-        .map(|(symbol, timeframes)| TradingPair { symbol, timeframes })
-        .collect();
+/// POST /api/timeframe/{symbol}/{timeframe}/reset - Relance le backfill d'un timeframe (clé API)
+///
+/// Voir TimeframeStatus::reset: la prochaine exécution du CLI récupère de
+/// nouveau tout l'historique du timeframe, même marqué complet
+async fn reset_timeframe(
+    data: web::Data<Mutex<AppState>>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (db_pool, timeframes_cache) = {
+        let state = data.lock().unwrap();
+        (Arc::clone(&state.db_pool), state.timeframes_cache.clone())
+    };
+    let (symbol, timeframe) = path.into_inner();
 
-    HttpResponse::Ok().json(pairs)
+    if !is_valid_timeframe(&timeframe) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Unknown timeframe: {}", timeframe)
+        }));
+    }
+
+    let result = {
+        let (symbol, timeframe) = (symbol.clone(), timeframe.clone());
+        web::block(move || {
+            let conn = db_pool.get()?;
+            TimeframeStatus::reset(&conn, "binance", &symbol, &timeframe)
+        })
+        .await
+    };
+
+    match result {
+        Ok(Ok(())) => {
+            info!(symbol = %symbol, timeframe = %timeframe, "Progression du timeframe réinitialisée");
+            // is_backfill_complete a changé
+            timeframes_cache.invalidate(&timeframes_cache_key(&symbol));
+            HttpResponse::Ok().json(serde_json::json!({
+                "symbol": symbol,
+                "timeframe": timeframe,
+                "reset": true
+            }))
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
 }
 
 /// GET /api/candles - Récupère les candles pour une paire/timeframe
@@ -113,8 +2541,23 @@ async fn get_candles(
     data: web::Data<Mutex<AppState>>,
     query: web::Query<CandlesQuery>,
 ) -> impl Responder {
+    // Un start au milieu d'une bougie est recalé sur son open_time: sinon la
+    // bougie qui contient start (open_time < start) serait exclue
+    let start_ms = match query
+        .start
+        .map(|s| round_to_timeframe(s * 1000, &query.timeframe))
+        .transpose()
+    {
+        Ok(start_ms) => start_ms,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
     let state = data.lock().unwrap();
-    let conn = match Connection::open(&state.db_path) {
+    let conn = match state.db_pool.get() {
         Ok(c) => c,
         Err(e) => {
             return HttpResponse::InternalServerError().json(serde_json::json!({
@@ -134,8 +2577,8 @@ async fn get_candles(
 
     let mut param_index = 3;
 
-    // Ajouter filtre sur start (timestamp en secondes -> convertir en ms pour la DB)
-    if query.start.is_some() {
+    // Ajouter filtre sur start (déjà converti en ms et recalé)
+    if start_ms.is_some() {
         sql.push_str(&format!(" AND open_time >= ?{}", param_index));
         param_index += 1;
     }
@@ -171,8 +2614,8 @@ async fn get_candles(
         Box::new(query.timeframe.clone()),
     ];
 
-    if let Some(start) = query.start {
-        query_params.push(Box::new(start * 1000)); // Convertir secondes en ms
+    if let Some(start_ms) = start_ms {
+        query_params.push(Box::new(start_ms));
     }
 
     if let Some(end) = query.end {
@@ -212,20 +2655,23 @@ async fn get_candles(
     // Si aucune donnée, essayer le rééchantillonnage depuis une TF inférieure
     if candles.is_empty() {
         if let Some(smaller_tf) = find_smaller_timeframe(&conn, &query.symbol, &query.timeframe) {
-            println!(
-                "⚠️ Pas de données pour {} {}, rééchantillonnage depuis {}",
-                query.symbol, query.timeframe, smaller_tf
+            debug!(
+                symbol = %query.symbol,
+                timeframe = %query.timeframe,
+                source = %smaller_tf,
+                "Pas de données, rééchantillonnage"
             );
 
-            candles = resample_candles(
+            candles = load_resampled(
                 &conn,
                 &query.symbol,
                 &smaller_tf,
                 &query.timeframe,
-                query.start,
+                start_ms.map(|ms| ms / 1000),
                 query.end,
                 limit,
-            );
+            )
+            .unwrap_or_default();
         }
     }
 
@@ -234,15 +2680,9 @@ async fn get_candles(
 
 /// Trouve une timeframe plus petite disponible
 fn find_smaller_timeframe(conn: &Connection, symbol: &str, target_tf: &str) -> Option<String> {
-    let timeframes = vec![
-        "5m", "15m", "30m", "1h", "2h", "4h", "6h", "8h", "12h", "1d", "3d",
-    ];
-    let target_seconds = parse_timeframe_seconds(target_tf);
-
     // Chercher la plus grande TF qui est plus petite que target
-    for tf in timeframes.iter().rev() {
-        let tf_seconds = parse_timeframe_seconds(tf);
-        if tf_seconds < target_seconds {
+    for tf in timeframe_ordering().into_iter().rev() {
+        if compare_timeframes(tf, target_tf).is_lt() {
             // Vérifier si cette TF a des données
             let count: Result<i64, _> = conn.query_row(
                 "SELECT COUNT(*) FROM candlesticks WHERE provider = 'binance' AND symbol = ?1 AND timeframe = ?2",
@@ -261,21 +2701,138 @@ fn find_smaller_timeframe(conn: &Connection, symbol: &str, target_tf: &str) -> O
     None
 }
 
-/// Parse une timeframe en secondes
-fn parse_timeframe_seconds(tf: &str) -> i64 {
-    if let Some(stripped) = tf.strip_suffix('m') {
-        stripped.parse::<i64>().unwrap_or(0) * 60
-    } else if let Some(stripped) = tf.strip_suffix('h') {
-        stripped.parse::<i64>().unwrap_or(0) * 3600
-    } else if let Some(stripped) = tf.strip_suffix('d') {
-        stripped.parse::<i64>().unwrap_or(0) * 86400
-    } else {
-        0
+/// Bougie stockée la plus récente de chaque timeframe d'un symbole
+///
+/// SUBTILITÉ SQLITE: avec MAX() seul agrégat, les colonnes nues
+/// (open, high...) sont lues sur la ligne qui porte le maximum
+fn query_latest_candles(
+    conn: &Connection,
+    symbol: &str,
+) -> rusqlite::Result<std::collections::HashMap<String, Candle>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT timeframe, MAX(open_time), open, high, low, close, volume
+         FROM candlesticks
+         WHERE symbol = ?1 AND provider = 'binance'
+         GROUP BY timeframe",
+    )?;
+    let rows = stmt.query_map(params![symbol], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            Candle {
+                time: row.get::<_, i64>(1)? / 1000,
+                open: row.get(2)?,
+                high: row.get(3)?,
+                low: row.get(4)?,
+                close: row.get(5)?,
+                volume: row.get(6)?,
+            },
+        ))
+    })?;
+    rows.collect()
+}
+
+/// GET /api/candles/latest - Bougie la plus récente de chaque timeframe
+///
+/// Remplace un appel limit=1 par timeframe. Une bougie partielle du
+/// RealtimeManager plus récente que la bougie stockée la remplace
+/// (is_partial: true). Cache de LATEST_CACHE_TTL
+#[get("/api/candles/latest")]
+async fn get_latest_candles(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<LatestQuery>,
+) -> impl Responder {
+    let (db_pool, realtime, cache) = {
+        let state = data.lock().unwrap();
+        (
+            Arc::clone(&state.db_pool),
+            Arc::clone(&state.realtime),
+            state.latest_cache.clone(),
+        )
+    };
+    let symbol = query.into_inner().symbol;
+
+    let key = CacheKey {
+        endpoint: "latest",
+        symbol: symbol.clone(),
+        timeframe: String::new(),
+        source_tf: None,
+        start: None,
+        end: None,
+        limit: None,
+    };
+    if let Some(body) = cache.get(&key) {
+        return HttpResponse::Ok()
+            .content_type("application/json")
+            .body(body);
+    }
+
+    let stored = {
+        let symbol = symbol.clone();
+        web::block(move || -> Result<_, CandleError> {
+            let conn = db_pool.get()?;
+            Ok(query_latest_candles(&conn, &symbol)?)
+        })
+        .await
+    };
+    let stored = match stored {
+        Ok(Ok(stored)) => stored,
+        Ok(Err(e)) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
+    let mut latest: std::collections::HashMap<String, LatestCandle> = stored
+        .into_iter()
+        .map(|(timeframe, candle)| {
+            (
+                timeframe,
+                LatestCandle {
+                    candle,
+                    is_partial: false,
+                },
+            )
+        })
+        .collect();
+    for (timeframe, partial) in realtime.partials_for(&symbol) {
+        if latest
+            .get(&timeframe)
+            .is_none_or(|stored| partial.time > stored.candle.time)
+        {
+            latest.insert(
+                timeframe,
+                LatestCandle {
+                    candle: Candle::from(&partial),
+                    is_partial: true,
+                },
+            );
+        }
+    }
+
+    match serde_json::to_vec(&latest) {
+        Ok(json) => {
+            let body = web::Bytes::from(json);
+            cache.insert(key, body.clone());
+            HttpResponse::Ok()
+                .content_type("application/json")
+                .body(body)
+        }
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
     }
 }
 
-/// Rééchantillonne des candles depuis une TF inférieure
-fn resample_candles(
+/// Rééchantillonne les bougies de source_tf vers target_tf (utils::resample_candles)
+///
+/// start/end en secondes, au plus RESAMPLE_SOURCE_LIMIT bougies source lues
+fn load_resampled(
     conn: &Connection,
     symbol: &str,
     source_tf: &str,
@@ -283,156 +2840,600 @@ fn resample_candles(
     start: Option<i64>,
     end: Option<i64>,
     limit: usize,
-) -> Vec<Candle> {
-    // Récupérer toutes les candles source dans la plage
-    let mut sql = String::from(
-        "SELECT open_time, open, high, low, close, volume
-         FROM candlesticks
-         WHERE provider = 'binance'
-           AND symbol = ?1
-           AND timeframe = ?2",
-    );
-
-    let mut param_index = 3;
+) -> Result<Vec<Candle>, CandleError> {
+    let source = query_candle_records(
+        conn,
+        "binance",
+        symbol,
+        source_tf,
+        start.map_or(0, |s| s * 1000)..=end.map_or(i64::MAX, |e| e * 1000),
+        Some(RESAMPLE_SOURCE_LIMIT),
+    )?;
 
-    if start.is_some() {
-        sql.push_str(&format!(" AND open_time >= ?{}", param_index));
-        param_index += 1;
-    }
-
-    if end.is_some() {
-        sql.push_str(&format!(" AND open_time <= ?{}", param_index));
-    }
+    Ok(resample_candles(&source, source_tf, target_tf)?
+        .iter()
+        .take(limit)
+        .map(Candle::from)
+        .collect())
+}
 
-    sql.push_str(" ORDER BY open_time ASC LIMIT 50000");
+/// GET /api/candles/resample - Bougies d'un timeframe arbitraire agrégées
+/// depuis un timeframe source (target_tf multiple entier de source_tf)
+///
+/// Périodes alignées sur les frontières UTC (voir utils::resample_candles).
+/// Résultat mis en cache, la clé inclut source_tf
+#[get("/api/candles/resample")]
+async fn get_resampled_candles(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<ResampleQuery>,
+) -> impl Responder {
+    let (db_pool, cache) = {
+        let state = data.lock().unwrap();
+        (Arc::clone(&state.db_pool), state.cache.clone())
+    };
+    let query = query.into_inner();
+    let limit = query.limit.unwrap_or(2000);
 
-    let mut stmt = match conn.prepare(&sql) {
-        Ok(s) => s,
-        Err(_) => return vec![],
+    let key = CacheKey {
+        endpoint: "resample",
+        symbol: query.symbol.clone(),
+        timeframe: query.target_tf.clone(),
+        source_tf: Some(query.source_tf.clone()),
+        start: query.start,
+        end: query.end,
+        limit: Some(limit),
     };
+    if let Some(body) = cache.get(&key) {
+        return HttpResponse::Ok()
+            .content_type("application/json")
+            .body(body);
+    }
 
-    let mut query_params: Vec<Box<dyn rusqlite::ToSql>> = vec![
-        Box::new(symbol.to_string()),
-        Box::new(source_tf.to_string()),
-    ];
+    let result = web::block(move || {
+        let conn = db_pool.get()?;
+        load_resampled(
+            &conn,
+            &query.symbol,
+            &query.source_tf,
+            &query.target_tf,
+            query.start,
+            query.end,
+            limit,
+        )
+    })
+    .await;
 
-    if let Some(s) = start {
-        query_params.push(Box::new(s * 1000));
-    }
-    if let Some(e) = end {
-        query_params.push(Box::new(e * 1000));
+    match result {
+        Ok(Ok(candles)) => match serde_json::to_vec(&candles) {
+            Ok(json) => {
+                let body = web::Bytes::from(json);
+                cache.insert(key, body.clone());
+                HttpResponse::Ok()
+                    .content_type("application/json")
+                    .body(body)
+            }
+            Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            })),
+        },
+        Ok(Err(e @ CandleError::ValidationError(_))) => {
+            HttpResponse::BadRequest().json(serde_json::json!({
+                "error": e.to_string()
+            }))
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
     }
+}
 
-    let params_refs: Vec<&dyn rusqlite::ToSql> = query_params.iter().map(|p| p.as_ref()).collect();
-
-    let candles_iter = match stmt.query_map(params_refs.as_slice(), |row| {
-        Ok(Candle {
-            time: row.get::<_, i64>(0)? / 1000,
-            open: row.get(1)?,
-            high: row.get(2)?,
-            low: row.get(3)?,
-            close: row.get(4)?,
-            volume: row.get(5)?,
-        })
-    }) {
-        Ok(iter) => iter,
-        Err(_) => return vec![],
+/// GET /api/stream/candles - Flux Server-Sent Events des nouvelles bougies
+///
+/// Alternative au WebSocket pour les environnements qui le bloquent.
+/// Chaque événement: `event: candle_update` + `data: <json>`
+///
+/// AUTHENTIFICATION: si STREAM_AUTH_TOKEN est défini, `?token=<valeur>` est
+/// exigé (401 sinon), vérifié avant tout abonnement au broadcast.
+/// Le jeton est en clair dans l'URL: à réserver à HTTPS (voir StreamToken)
+///
+/// RSI LIVE: `?rsi=14` (avec `timeframes`) suit le RSI de chaque timeframe
+/// demandé, initialisé depuis la base à l'abonnement; chaque événement porte
+/// alors `indicators: {"rsi_14": ...}` (bougies partielles comprises)
+///
+/// SUBTILITÉ RUST: async_stream::stream! transforme la boucle de réception
+/// broadcast en Stream consommé par HttpResponse::streaming()
+#[get("/api/stream/candles")]
+async fn stream_candles(
+    data: web::Data<Mutex<AppState>>,
+    query: web::Query<StreamQuery>,
+) -> impl Responder {
+    let (mut receiver, mut shutdown, realtime, db_pool) = {
+        let state = data.lock().unwrap();
+        if !state.stream_token.verify(query.token.as_deref()) {
+            warn!(symbol = %query.symbol, "Flux SSE refusé: jeton absent ou invalide");
+            return HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "invalid or missing stream token"
+            }));
+        }
+        (
+            state.realtime.subscribe_updates(),
+            state.realtime.shutdown_signal(),
+            Arc::clone(&state.realtime),
+            Arc::clone(&state.db_pool),
+        )
     };
+    let symbol = query.symbol.clone();
+    let timeframes: Option<Vec<String>> = query
+        .timeframes
+        .as_ref()
+        .map(|tfs| tfs.split(',').map(|tf| tf.trim().to_string()).collect());
 
-    let source_candles: Vec<Candle> = candles_iter.filter_map(|r| r.ok()).collect();
-
-    if source_candles.is_empty() {
-        return vec![];
+    if let Some(period) = query.rsi {
+        let Some(tracked) = timeframes.clone() else {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "rsi requires timeframes"
+            }));
+        };
+        if period == 0 {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "rsi period must be positive"
+            }));
+        }
+        let rsi_symbol = symbol.clone();
+        let result = web::block(move || -> Result<(), CandleError> {
+            let conn = db_pool.get()?;
+            for timeframe in &tracked {
+                realtime.track_rsi_from_db(&conn, "binance", &rsi_symbol, timeframe, period)?;
+            }
+            Ok(())
+        })
+        .await;
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                return HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": format!("Database error: {}", e)
+                }));
+            }
+            Err(e) => {
+                return HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": e.to_string()
+                }));
+            }
+        }
     }
 
-    let target_seconds = parse_timeframe_seconds(target_tf);
+    let connection_guard = GaugeGuard::new(&METRICS.websocket_connections_active);
+    info!(symbol = %symbol, timeframes = ?timeframes, "Flux SSE ouvert");
 
-    // Grouper par période target
-    let mut resampled: Vec<Candle> = Vec::new();
-    let mut current_group: Vec<&Candle> = Vec::new();
-    let mut current_period_start = (source_candles[0].time / target_seconds) * target_seconds;
+    let stream = async_stream::stream! {
+        // Déplacé dans le flux: la jauge est décrémentée à la déconnexion
+        let _connection_guard = connection_guard;
+        yield Ok::<_, actix_web::Error>(web::Bytes::from(format!("retry: {}\n\n", SSE_RETRY_MS)));
 
-    for candle in &source_candles {
-        let period_start = (candle.time / target_seconds) * target_seconds;
-
-        if period_start != current_period_start {
-            // Agréger le groupe précédent
-            if !current_group.is_empty() {
-                resampled.push(aggregate_candles(&current_group, current_period_start));
-                current_group.clear();
+        loop {
+            // Arrêt du serveur: le flux se termine, le client se reconnectera
+            let received = tokio::select! {
+                received = receiver.recv() => received,
+                _ = shutdown.wait_for(|stop| *stop) => break,
+            };
+            match received {
+                Ok(update) => {
+                    let wanted = update.symbol == symbol
+                        && timeframes.as_ref().is_none_or(|tfs| tfs.contains(&update.timeframe));
+                    if !wanted {
+                        continue;
+                    }
+                    let json = serde_json::to_string(&update).unwrap_or_default();
+                    yield Ok(web::Bytes::from(format!("event: candle_update\ndata: {}\n\n", json)));
+                }
+                // Client trop lent: messages perdus, on continue avec les suivants
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(symbol = %symbol, skipped, "Client SSE trop lent, bougies perdues");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
             }
-            current_period_start = period_start;
         }
+    };
 
-        current_group.push(candle);
-    }
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        // Pas de compression: Compress bufferiserait les événements
+        .insert_header(ContentEncoding::Identity)
+        .streaming(stream)
+}
 
-    // Agréger le dernier groupe
-    if !current_group.is_empty() {
-        resampled.push(aggregate_candles(&current_group, current_period_start));
-    }
+/// GET /api/config - Configuration non sensible du serveur
+#[get("/api/config")]
+async fn get_config(data: web::Data<Mutex<AppState>>) -> impl Responder {
+    let state = data.lock().unwrap();
+    HttpResponse::Ok().json(state.config.public_view())
+}
 
-    // Limiter le nombre de résultats
-    resampled.truncate(limit);
-    resampled
+/// GET /api/config/tls - État de l'écoute HTTPS
+#[get("/api/config/tls")]
+async fn get_tls_config(data: web::Data<Mutex<AppState>>) -> impl Responder {
+    let state = data.lock().unwrap();
+    HttpResponse::Ok().json(serde_json::json!({
+        "enabled": state.tls_active,
+        "port": state.tls_active.then_some(state.config.tls_port)
+    }))
 }
 
-/// Agrège un groupe de candles en une seule
-fn aggregate_candles(candles: &[&Candle], period_start: i64) -> Candle {
-    let open = candles.first().unwrap().open;
-    let close = candles.last().unwrap().close;
-    let high = candles
-        .iter()
-        .map(|c| c.high)
-        .fold(f64::NEG_INFINITY, f64::max);
-    let low = candles.iter().map(|c| c.low).fold(f64::INFINITY, f64::min);
-    let volume = candles.iter().map(|c| c.volume).sum();
+/// GET /api/metrics - Métriques au format texte Prometheus
+#[get("/api/metrics")]
+async fn get_metrics(data: web::Data<Mutex<AppState>>) -> impl Responder {
+    let state = data.lock().unwrap();
 
-    Candle {
-        time: period_start,
-        open,
-        high,
-        low,
-        close,
-        volume,
+    match state.db_pool.get() {
+        Ok(conn) => {
+            if let Err(e) = METRICS.refresh_db_gauges(&conn, &state.config.db_path) {
+                warn!(error = %e, "Métriques de la base indisponibles");
+            }
+        }
+        Err(e) => warn!(error = %e, "Métriques de la base indisponibles"),
     }
+
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(METRICS.render())
 }
 
 /// GET /health - Health check
+///
+/// 503 pendant l'arrêt: le load balancer cesse d'envoyer du trafic
 #[get("/health")]
 async fn health() -> impl Responder {
+    if SHUTDOWN.load(Ordering::SeqCst) {
+        return HttpResponse::ServiceUnavailable().json(serde_json::json!({
+            "status": "shutting_down",
+            "version": env!("CARGO_PKG_VERSION")
+        }));
+    }
+
     HttpResponse::Ok().json(serde_json::json!({
         "status": "ok",
-        "version": env!("CARGO_PKG_VERSION")
+        "version": env!("CARGO_PKG_VERSION"),
+        "metrics_endpoint": "/api/metrics"
+    }))
+}
+
+/// Endpoint protégé: permet à un client de valider sa clé API
+///
+/// NOTE: les futurs endpoints d'écriture (fetch, backfill, realtime)
+/// s'enregistrent dans le même scope protégé que celui-ci
+async fn auth_check() -> impl Responder {
+    HttpResponse::Ok().json(serde_json::json!({
+        "status": "authorized"
     }))
 }
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    let db_path = std::env::var("DB_PATH").unwrap_or_else(|_| "candlesticks.db".to_string());
-    let port = std::env::var("PORT")
-        .unwrap_or_else(|_| "8080".to_string())
-        .parse::<u16>()
-        .unwrap_or(8080);
+    let args = Args::parse();
+    let config = Config::load(args.config.as_deref())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+    let port = config.port;
+    let static_dir = config.static_dir.clone();
+
+    // log_level de la configuration, surchargé par RUST_LOG
+    init_tracing(&config.log_level);
+
+    info!(
+        port,
+        db_path = %config.db_path,
+        static_dir = %static_dir,
+        "Démarrage du serveur web"
+    );
+
+    let api_keys = Arc::new(ApiKeys::load());
+    if api_keys.is_empty() {
+        info!("Aucune clé API configurée: endpoints d'écriture désactivés");
+    } else {
+        info!(count = api_keys.len(), "Clés API chargées");
+    }
+
+    let stream_token = StreamToken::load();
+    if stream_token.is_required() {
+        info!("Flux temps réel protégé par jeton (STREAM_AUTH_TOKEN)");
+    }
+
+    // HTTPS en plus de HTTP si certificat et clé sont présents
+    let tls_config =
+        match config.tls_files() {
+            Some((cert, key)) => Some(load_server_config(cert, key).map_err(|e| {
+                std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string())
+            })?),
+            None => {
+                if config.tls_cert_path.is_some() || config.tls_key_path.is_some() {
+                    warn!("Certificat ou clé TLS introuvable: HTTPS désactivé");
+                }
+                None
+            }
+        };
+    let tls_port = config.tls_port;
+
+    let retention = RetentionPolicy::from_config(&config.retention)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+
+    // Section [backfill]: une faute de frappe est refusée au démarrage
+    let backfill_timeframes = config
+        .backfill
+        .active_timeframes()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+    debug!(timeframes = ?backfill_timeframes, "Timeframes du backfill");
+
+    // Diffusion des bougies insérées par le CLI de récupération
+    let realtime = Arc::new(RealtimeManager::new(config.broadcast_capacity));
+    let watcher = realtime.start_db_watcher(config.db_path.clone(), REALTIME_POLL_PERIOD);
+
+    // Connexions de lecture réutilisées d'une requête à l'autre
+    let db_pool = ConnectionPool::new(
+        &config.db_path,
+        config.db_pool_size,
+        Duration::from_secs(config.db_pool_idle_secs),
+    );
+
+    // Séries à compléter, par priorité (TimeframeStatus::list_incomplete):
+    // le serveur ne récupère rien lui-même, le CLI de récupération les reprend
+    match db_pool
+        .get()
+        .and_then(|conn| TimeframeStatus::list_incomplete(&conn, "binance"))
+    {
+        Ok(incomplete) => {
+            for (symbol, timeframe, oldest) in incomplete
+                .iter()
+                .filter(|(_, tf, _)| backfill_timeframes.contains(&tf.as_str()))
+            {
+                info!(
+                    symbol = %symbol,
+                    timeframe = %timeframe,
+                    oldest = %oldest.map_or_else(|| "-".to_string(), format_timestamp_iso),
+                    "Backfill incomplet"
+                );
+            }
+        }
+        Err(e) => warn!(error = %e, "Statut des timeframes illisible"),
+    }
+
+    // Purge périodique selon [retention] (prune_interval_secs = 0: désactivée)
+    if config.prune_interval_secs > 0 && !retention.is_empty() {
+        info!(
+            interval_secs = config.prune_interval_secs,
+            "Purge de rétention planifiée"
+        );
+        actix_web::rt::spawn(prune_periodically(
+            Arc::clone(&db_pool),
+            retention,
+            Duration::from_secs(config.prune_interval_secs),
+        ));
+    }
+
+    let cache = new_response_cache(
+        config.cache_max_entries,
+        Duration::from_secs(config.cache_ttl_secs),
+    );
 
-    println!("🚀 Démarrage du serveur web sur http://127.0.0.1:{}", port);
-    println!("📊 Base de données: {}", db_path);
-    println!("📁 Fichiers statiques: ./web");
+    let latest_cache = new_response_cache(config.cache_max_entries, LATEST_CACHE_TTL);
+    let statistics_cache = new_response_cache(config.cache_max_entries, STATISTICS_CACHE_TTL);
+    let timeframes_cache = new_response_cache(config.cache_max_entries, TIMEFRAMES_CACHE_TTL);
 
-    let app_state = web::Data::new(Mutex::new(AppState { db_path }));
+    let app_state = web::Data::new(Mutex::new(AppState {
+        db_pool,
+        cache,
+        latest_cache,
+        statistics_cache,
+        timeframes_cache,
+        stream_token,
+        config: Arc::new(config),
+        realtime: Arc::clone(&realtime),
+        tls_active: tls_config.is_some(),
+    }));
 
-    HttpServer::new(move || {
+    let mut server = HttpServer::new(move || {
         let cors = Cors::permissive();
 
+        // Compress négocie gzip/brotli/zstd selon Accept-Encoding
+        // (≈ -75% sur une réponse de 2000 bougies, voir README)
         App::new()
+            .wrap(middleware::Compress::default())
             .wrap(cors)
+            // Comptage et durée des requêtes par route (pattern, pas l'URL brute)
+            .wrap_fn(|req, srv| {
+                let started = Instant::now();
+                let fut = srv.call(req);
+                async move {
+                    let res = fut.await?;
+                    let endpoint = res
+                        .request()
+                        .match_pattern()
+                        .unwrap_or_else(|| "static".to_string());
+                    METRICS
+                        .api_requests_total
+                        .with_label_values(&[endpoint.as_str(), res.status().as_str()])
+                        .inc();
+                    METRICS
+                        .api_request_duration_seconds
+                        .with_label_values(&[endpoint.as_str()])
+                        .observe(started.elapsed().as_secs_f64());
+                    Ok(res)
+                }
+            })
             .app_data(app_state.clone())
             .service(health)
             .service(get_pairs)
+            .service(get_pair_timeframes)
             .service(get_candles)
-            .service(Files::new("/", "./web").index_file("index.html"))
+            .service(get_latest_candles)
+            .service(get_resampled_candles)
+            .configure(configure_exports)
+            // Écriture: même protection par clé API que le scope /api/auth
+            .service(
+                web::resource("/api/candles/import/csv")
+                    .wrap(ApiKeyMiddleware::new(Arc::clone(&api_keys)))
+                    .route(web::post().to(import_candles_csv)),
+            )
+            .service(
+                web::resource("/api/timeframe/{symbol}/{timeframe}/reset")
+                    .wrap(ApiKeyMiddleware::new(Arc::clone(&api_keys)))
+                    .route(web::post().to(reset_timeframe)),
+            )
+            .service(
+                web::resource("/api/backup")
+                    .wrap(ApiKeyMiddleware::new(Arc::clone(&api_keys)))
+                    .route(web::post().to(backup_pair)),
+            )
+            .service(get_gaps)
+            .service(
+                web::resource("/api/gaps/fill")
+                    .wrap(ApiKeyMiddleware::new(Arc::clone(&api_keys)))
+                    .route(web::post().to(fill_gaps)),
+            )
+            .service(
+                web::resource("/api/gaps/interpolated")
+                    .wrap(ApiKeyMiddleware::new(Arc::clone(&api_keys)))
+                    .route(web::delete().to(delete_interpolated)),
+            )
+            .service(
+                web::resource("/api/db/vacuum")
+                    .wrap(ApiKeyMiddleware::new(Arc::clone(&api_keys)))
+                    .route(web::post().to(vacuum_db)),
+            )
+            .service(get_stats)
+            .service(get_backfill_status)
+            .service(get_statistics)
+            .service(get_obv)
+            .service(get_volatility)
+            .service(get_rolling)
+            .service(get_stochastic)
+            .service(get_pivot_points)
+            .service(get_sar)
+            .service(get_macd)
+            .service(get_bollinger)
+            .service(get_atr)
+            .service(get_vwap)
+            .service(get_indicator)
+            .service(get_returns)
+            .service(get_patterns)
+            .service(get_price_outliers)
+            .service(get_provider_comparison)
+            .service(get_db_integrity)
+            .service(get_config)
+            .service(get_tls_config)
+            .service(stream_candles)
+            .service(get_metrics)
+            .service(
+                web::scope("/api/admin")
+                    .wrap(ApiKeyMiddleware::new(Arc::clone(&api_keys)))
+                    .route("/maintenance", web::post().to(run_maintenance)),
+            )
+            .service(
+                web::scope("/api/auth")
+                    .wrap(ApiKeyMiddleware::new(Arc::clone(&api_keys)))
+                    .route("/check", web::post().to(auth_check)),
+            )
+            .service(Files::new("/", &static_dir).index_file("index.html"))
     })
-    .bind(("127.0.0.1", port))?
-    .run()
-    .await
+    .bind(("127.0.0.1", port))?;
+
+    if let Some(tls_config) = tls_config {
+        server = server.bind_rustls_0_23(("127.0.0.1", tls_port), tls_config)?;
+        info!(tls_port, "HTTPS actif");
+    }
+
+    let server = server
+        // Signaux gérés manuellement (drainage + code de sortie)
+        .disable_signals()
+        // Marge: le délai de shutdown_on_ctrl_c fait foi (détection du dépassement)
+        .shutdown_timeout(SHUTDOWN_TIMEOUT.as_secs() + 5)
+        .run();
+
+    let shutdown_task =
+        actix_web::rt::spawn(shutdown_on_ctrl_c(server.handle(), Arc::clone(&realtime)));
+
+    server.await?;
+    let timed_out = shutdown_task.await.unwrap_or(false);
+
+    // Le thread de surveillance ferme sa connexion à la base en sortant
+    let _ = actix_web::rt::task::spawn_blocking(move || watcher.join()).await;
+
+    if timed_out {
+        warn!(
+            timeout_secs = SHUTDOWN_TIMEOUT.as_secs(),
+            "Requêtes interrompues: délai d'arrêt dépassé"
+        );
+        std::process::exit(1);
+    }
+
+    info!("Serveur arrêté proprement");
+    Ok(())
+}
+
+/// Tâche de fond: applique la rétention à toutes les séries toutes les `period`
+/// (première purge au démarrage)
+async fn prune_periodically(
+    db_pool: Arc<ConnectionPool>,
+    retention: RetentionPolicy,
+    period: Duration,
+) {
+    let mut ticker = actix_web::rt::time::interval(period);
+    loop {
+        ticker.tick().await;
+
+        let db_pool = Arc::clone(&db_pool);
+        let retention = retention.clone();
+        let result = web::block(move || {
+            let mut conn = db_pool.get()?;
+            let now_ms = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as i64;
+            retention.apply(&mut conn, "binance", None, now_ms)
+        })
+        .await;
+
+        match result {
+            Ok(Ok(reports)) => {
+                let deleted: usize = reports.iter().map(|r| r.deleted).sum();
+                info!(
+                    deleted,
+                    series = reports.len(),
+                    "Purge de rétention terminée"
+                );
+            }
+            Ok(Err(e)) => warn!(error = %e, "Purge de rétention en échec"),
+            Err(e) => warn!(error = %e, "Purge de rétention interrompue"),
+        }
+    }
+}
+
+/// Attend Ctrl-C puis arrête le serveur
+///
+/// ALGORITHME:
+/// 1. SHUTDOWN = true (/health répond 503)
+/// 2. Fin des flux temps réel: un flux SSE ne se termine jamais de lui-même,
+///    il bloquerait le drainage jusqu'au délai
+/// 3. Arrêt gracieux: plus de nouvelles connexions, requêtes en cours terminées
+///    dans la limite de SHUTDOWN_TIMEOUT, sinon arrêt forcé
+///
+/// RETOUR: true si le délai a été dépassé
+async fn shutdown_on_ctrl_c(handle: ServerHandle, realtime: Arc<RealtimeManager>) -> bool {
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        warn!(error = %e, "Signal Ctrl-C indisponible, arrêt manuel impossible");
+        return false;
+    }
+
+    SHUTDOWN.store(true, Ordering::SeqCst);
+    info!("Arrêt demandé, fin des requêtes en cours");
+    realtime.shutdown();
+
+    match tokio::time::timeout(SHUTDOWN_TIMEOUT, handle.stop(true)).await {
+        Ok(()) => false,
+        Err(_) => {
+            handle.stop(false).await;
+            true
+        }
+    }
 }
@@ -0,0 +1,65 @@
+/// Module du cache des réponses de l'API
+///
+/// ARCHITECTURE:
+/// - moka::sync::Cache: éviction par taille (cache_max_entries) et par
+///   durée de vie (cache_ttl_secs), partageable entre workers (clone bon marché)
+/// - CacheKey: identifie une réponse par endpoint et paramètres de requête
+///
+/// NOTE: les nouvelles bougies ne purgent pas le cache, une réponse peut
+/// avoir jusqu'à cache_ttl_secs de retard sur la base. Seules les
+/// écritures du serveur lui-même (comblement des gaps) purgent les
+/// réponses concernées (invalidate_series)
+use moka::sync::Cache;
+use std::time::Duration;
+
+/// Clé d'une réponse en cache
+///
+/// source_tf distingue un rééchantillonnage 5m → 2h d'un 15m → 2h
+/// (même timeframe cible, résultats différents)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    pub endpoint: &'static str,
+    pub symbol: String,
+    pub timeframe: String,
+    /// Timeframe source d'un rééchantillonnage (None pour une lecture directe)
+    pub source_tf: Option<String>,
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+/// Cache de réponses (V: corps sérialisé)
+pub type ResponseCache<V> = Cache<CacheKey, V>;
+
+/// Crée un cache borné à `max_entries` réponses, chacune valable `ttl`
+pub fn new_response_cache<V>(max_entries: u64, ttl: Duration) -> ResponseCache<V>
+where
+    V: Clone + Send + Sync + 'static,
+{
+    Cache::builder()
+        .max_capacity(max_entries)
+        .time_to_live(ttl)
+        .build()
+}
+
+/// Supprime les réponses d'une série: celles du timeframe, celles
+/// rééchantillonnées depuis lui (source_tf) et celles propres au symbole
+/// (timeframe vide: dernières bougies, couverture par timeframe)
+pub fn invalidate_series<V>(cache: &ResponseCache<V>, symbol: &str, timeframe: &str)
+where
+    V: Clone + Send + Sync + 'static,
+{
+    let stale: Vec<_> = cache
+        .iter()
+        .map(|(key, _)| key)
+        .filter(|key| {
+            key.symbol == symbol
+                && (key.timeframe == timeframe
+                    || key.timeframe.is_empty()
+                    || key.source_tf.as_deref() == Some(timeframe))
+        })
+        .collect();
+    for key in stale {
+        cache.invalidate(&*key);
+    }
+}
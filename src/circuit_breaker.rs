@@ -0,0 +1,133 @@
+/// Module de disjoncteur (circuit breaker) pour les appels API Binance
+///
+/// Après plusieurs erreurs consécutives, le disjoncteur s'ouvre et refuse
+/// les requêtes pendant un délai, au lieu de brûler le budget de poids
+/// en réessayant immédiatement
+use crate::error::{CandleError, Result};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Nombre d'échecs consécutifs par défaut avant ouverture
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+/// Durée d'ouverture par défaut
+pub const DEFAULT_OPEN_DURATION: Duration = Duration::from_secs(60);
+
+/// État du disjoncteur
+///
+/// - Closed: requêtes autorisées
+/// - Open: requêtes refusées jusqu'à reopen_at
+/// - HalfOpen: une seule requête de test autorisée
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open { reopen_at: Instant },
+    HalfOpen,
+}
+
+/// État mutable protégé par le Mutex
+struct Inner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    probe_in_flight: bool,
+}
+
+/// Disjoncteur partagé entre les récupérateurs via Arc
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    inner: Mutex<Inner>,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(DEFAULT_FAILURE_THRESHOLD, DEFAULT_OPEN_DURATION)
+    }
+}
+
+impl CircuitBreaker {
+    /// Crée un disjoncteur fermé
+    pub fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        CircuitBreaker {
+            failure_threshold,
+            open_duration,
+            inner: Mutex::new(Inner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                probe_in_flight: false,
+            }),
+        }
+    }
+
+    /// État courant du disjoncteur
+    pub fn state(&self) -> CircuitState {
+        self.inner.lock().unwrap().state
+    }
+
+    /// Exécute la closure si le disjoncteur le permet
+    ///
+    /// ALGORITHME:
+    /// 1. Open et délai non écoulé → CandleError::CircuitBreakerOpen
+    /// 2. Open et délai écoulé → HalfOpen, la requête sert de sonde
+    /// 3. Succès → Closed, compteur remis à zéro
+    /// 4. Échec → compteur++, ouverture si seuil atteint ou sonde échouée
+    pub fn call<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce() -> Result<T>,
+    {
+        self.before_call()?;
+
+        let result = f();
+
+        let mut inner = self.inner.lock().unwrap();
+        inner.probe_in_flight = false;
+
+        match &result {
+            Ok(_) => {
+                inner.consecutive_failures = 0;
+                inner.state = CircuitState::Closed;
+            }
+            Err(_) => {
+                inner.consecutive_failures += 1;
+                if inner.state == CircuitState::HalfOpen
+                    || inner.consecutive_failures >= self.failure_threshold
+                {
+                    inner.state = CircuitState::Open {
+                        reopen_at: Instant::now() + self.open_duration,
+                    };
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Vérifie si une requête peut partir et gère la transition Open → HalfOpen
+    fn before_call(&self) -> Result<()> {
+        let now = Instant::now();
+        let mut inner = self.inner.lock().unwrap();
+
+        match inner.state {
+            CircuitState::Closed => Ok(()),
+            CircuitState::Open { reopen_at } if now < reopen_at => {
+                Err(CandleError::CircuitBreakerOpen {
+                    retry_after_ms: (reopen_at - now).as_millis() as u64,
+                })
+            }
+            CircuitState::Open { .. } => {
+                inner.state = CircuitState::HalfOpen;
+                inner.probe_in_flight = true;
+                Ok(())
+            }
+            CircuitState::HalfOpen if inner.probe_in_flight => {
+                Err(CandleError::CircuitBreakerOpen {
+                    retry_after_ms: self.open_duration.as_millis() as u64,
+                })
+            }
+            CircuitState::HalfOpen => {
+                inner.probe_in_flight = true;
+                Ok(())
+            }
+        }
+    }
+}
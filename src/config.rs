@@ -0,0 +1,288 @@
+/// Module de configuration du serveur web
+///
+/// ORDRE DE PRIORITÉ:
+/// 1. Valeurs par défaut (Config::default)
+/// 2. Fichier TOML (--config <path>, sinon ./config.toml s'il existe)
+/// 3. Variables d'environnement (DB_PATH, PORT, ...)
+use crate::error::{CandleError, Result};
+use crate::indicators::registry::{IndicatorRegistry, IndicatorSpec};
+use crate::utils::{ALL_TIMEFRAMES, compare_timeframes, parse_timeframe};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Fichier de configuration chargé quand --config n'est pas fourni
+pub const DEFAULT_CONFIG_FILE: &str = "config.toml";
+
+/// Timeframes récupérés par défaut par le backfill
+///
+/// NOTE: 1m et 3m sont exclus (volume de données), 1w et 1M aussi
+/// (dérivables des timeframes inférieurs)
+pub const DEFAULT_BACKFILL_TIMEFRAMES: &[&str] = &[
+    "5m", "15m", "30m", "1h", "2h", "4h", "6h", "8h", "12h", "1d", "3d",
+];
+
+/// Sélection des timeframes à récupérer
+///
+/// Partagée par le CLI (--timeframes, --exclude-timeframes) et la section
+/// [backfill] de la configuration du serveur
+///
+/// EXEMPLE (config.toml):
+/// [backfill]
+/// exclude_timeframes = ["5m", "15m"]
+/// indicators = [{ name = "rsi", period = 14 }, { name = "ema", period = 20 }]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackfillOptions {
+    /// Timeframes récupérés (vide: DEFAULT_BACKFILL_TIMEFRAMES)
+    pub timeframes: Vec<String>,
+    /// Timeframes retirés de la sélection
+    pub exclude_timeframes: Vec<String>,
+    /// Indicateurs recalculés après chaque batch (vide: aucun)
+    pub indicators: Vec<IndicatorSpec>,
+}
+
+impl BackfillOptions {
+    /// Vérifie chaque timeframe avec parse_timeframe, et chaque indicateur
+    ///
+    /// ERREURS: ValidationError (timeframe inconnu, sélection vide,
+    /// indicateur inconnu)
+    pub fn validate(&self) -> Result<()> {
+        self.active_timeframes()?;
+        self.indicator_registry().map(|_| ())
+    }
+
+    /// Registre des indicateurs à recalculer après chaque batch
+    ///
+    /// ERREURS: ValidationError (nom inconnu, période nulle)
+    pub fn indicator_registry(&self) -> Result<IndicatorRegistry> {
+        IndicatorRegistry::from_specs(&self.indicators)
+    }
+
+    /// Timeframes à récupérer, du plus court au plus long, sans doublon
+    ///
+    /// ALGORITHME: `timeframes` (ou la liste par défaut) moins `exclude_timeframes`
+    pub fn active_timeframes(&self) -> Result<Vec<&'static str>> {
+        let selected = if self.timeframes.is_empty() {
+            DEFAULT_BACKFILL_TIMEFRAMES.to_vec()
+        } else {
+            self.timeframes
+                .iter()
+                .map(|tf| canonical_timeframe(tf))
+                .collect::<Result<Vec<_>>>()?
+        };
+        let excluded = self
+            .exclude_timeframes
+            .iter()
+            .map(|tf| canonical_timeframe(tf))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut active: Vec<&'static str> = selected
+            .into_iter()
+            .filter(|tf| !excluded.contains(tf))
+            .collect();
+        active.sort_by(|a, b| compare_timeframes(a, b));
+        active.dedup();
+
+        if active.is_empty() {
+            return Err(CandleError::ValidationError(
+                "Aucun timeframe à récupérer après exclusion".to_string(),
+            ));
+        }
+        Ok(active)
+    }
+}
+
+/// Version 'static d'un timeframe validé (entrée de ALL_TIMEFRAMES)
+fn canonical_timeframe(timeframe: &str) -> Result<&'static str> {
+    parse_timeframe(timeframe)?;
+    Ok(ALL_TIMEFRAMES
+        .iter()
+        .copied()
+        .find(|tf| *tf == timeframe)
+        .unwrap_or_default())
+}
+
+/// Configuration du serveur web
+///
+/// SUBTILITÉ RUST: #[serde(default)] complète les champs absents du fichier
+/// avec les valeurs de Config::default()
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Fichier de base de données SQLite
+    pub db_path: String,
+    /// Port HTTP d'écoute
+    pub port: u16,
+    /// Répertoire des fichiers statiques (HTML/CSS/JS)
+    pub static_dir: String,
+    /// Nombre maximal d'entrées du cache de réponses
+    pub cache_max_entries: u64,
+    /// Durée de vie des entrées du cache en secondes
+    pub cache_ttl_secs: u64,
+    /// Capacité du canal de diffusion des mises à jour
+    pub broadcast_capacity: usize,
+    /// Nombre maximal de backfills simultanés
+    pub max_concurrent_backfill: usize,
+    /// Requêtes par minute autorisées par client
+    pub rate_limit_rpm: u32,
+    /// Niveau de log (error, warn, info, debug, trace)
+    pub log_level: String,
+    /// Connexions SQLite inactives conservées par le pool de lecture
+    pub db_pool_size: usize,
+    /// Durée après laquelle une connexion inactive du pool est fermée
+    pub db_pool_idle_secs: u64,
+    /// Port HTTPS d'écoute (utilisé seulement si certificat et clé sont fournis)
+    pub tls_port: u16,
+    /// Certificat PEM du serveur
+    pub tls_cert_path: Option<String>,
+    /// Clé privée PEM du serveur
+    pub tls_key_path: Option<String>,
+    /// Période de purge automatique selon `retention` (0: désactivée)
+    pub prune_interval_secs: u64,
+    /// Rétention par timeframe (ex: "1m" = "90d"), voir retention.rs
+    pub retention: BTreeMap<String, String>,
+    /// Timeframes du backfill automatique (section [backfill])
+    pub backfill: BackfillOptions,
+    /// Répertoire des sauvegardes créées par POST /api/backup
+    pub backup_dir: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            db_path: "candlesticks.db".to_string(),
+            port: 8080,
+            static_dir: "./web".to_string(),
+            cache_max_entries: 1000,
+            cache_ttl_secs: 60,
+            broadcast_capacity: 1024,
+            max_concurrent_backfill: 2,
+            rate_limit_rpm: 600,
+            log_level: "info".to_string(),
+            db_pool_size: 8,
+            db_pool_idle_secs: 300,
+            tls_port: 8443,
+            tls_cert_path: None,
+            tls_key_path: None,
+            prune_interval_secs: 0,
+            retention: BTreeMap::new(),
+            backfill: BackfillOptions::default(),
+            backup_dir: "backups".to_string(),
+        }
+    }
+}
+
+impl Config {
+    /// Charge la configuration: défauts → fichier TOML → variables d'environnement
+    ///
+    /// Un fichier passé explicitement doit exister, ./config.toml est optionnel
+    pub fn load(path: Option<&str>) -> Result<Self> {
+        let mut config = match path {
+            Some(p) => Self::from_file(p)?,
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => {
+                Self::from_file(DEFAULT_CONFIG_FILE)?
+            }
+            None => Config::default(),
+        };
+
+        config.apply_env_overrides()?;
+        Ok(config)
+    }
+
+    /// Parse un fichier TOML
+    pub fn from_file(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        toml::from_str(&content)
+            .map_err(|e| CandleError::ParseError(format!("Configuration {}: {}", path, e)))
+    }
+
+    /// Applique les variables d'environnement par-dessus la configuration
+    fn apply_env_overrides(&mut self) -> Result<()> {
+        if let Ok(v) = std::env::var("DB_PATH") {
+            self.db_path = v;
+        }
+        if let Ok(v) = std::env::var("STATIC_DIR") {
+            self.static_dir = v;
+        }
+        if let Ok(v) = std::env::var("LOG_LEVEL") {
+            self.log_level = v;
+        }
+        if let Ok(v) = std::env::var("BACKUP_DIR") {
+            self.backup_dir = v;
+        }
+        if let Ok(v) = std::env::var("TLS_CERT_PATH") {
+            self.tls_cert_path = Some(v);
+        }
+        if let Ok(v) = std::env::var("TLS_KEY_PATH") {
+            self.tls_key_path = Some(v);
+        }
+        override_parsed("PORT", &mut self.port)?;
+        override_parsed("CACHE_MAX_ENTRIES", &mut self.cache_max_entries)?;
+        override_parsed("CACHE_TTL_SECS", &mut self.cache_ttl_secs)?;
+        override_parsed("BROADCAST_CAPACITY", &mut self.broadcast_capacity)?;
+        override_parsed("MAX_CONCURRENT_BACKFILL", &mut self.max_concurrent_backfill)?;
+        override_parsed("RATE_LIMIT_RPM", &mut self.rate_limit_rpm)?;
+        override_parsed("TLS_PORT", &mut self.tls_port)?;
+        override_parsed("DB_POOL_SIZE", &mut self.db_pool_size)?;
+        override_parsed("DB_POOL_IDLE_SECS", &mut self.db_pool_idle_secs)?;
+        override_parsed("PRUNE_INTERVAL_SECS", &mut self.prune_interval_secs)?;
+        override_list("BACKFILL_TIMEFRAMES", &mut self.backfill.timeframes);
+        override_list(
+            "BACKFILL_EXCLUDE_TIMEFRAMES",
+            &mut self.backfill.exclude_timeframes,
+        );
+        Ok(())
+    }
+
+    /// Certificat et clé TLS, si les deux sont configurés et existent
+    pub fn tls_files(&self) -> Option<(&str, &str)> {
+        let cert = self.tls_cert_path.as_deref()?;
+        let key = self.tls_key_path.as_deref()?;
+        (Path::new(cert).exists() && Path::new(key).exists()).then_some((cert, key))
+    }
+
+    /// Champs exposables publiquement (sans chemins du système de fichiers)
+    pub fn public_view(&self) -> serde_json::Value {
+        serde_json::json!({
+            "port": self.port,
+            "cache_max_entries": self.cache_max_entries,
+            "cache_ttl_secs": self.cache_ttl_secs,
+            "broadcast_capacity": self.broadcast_capacity,
+            "max_concurrent_backfill": self.max_concurrent_backfill,
+            "rate_limit_rpm": self.rate_limit_rpm,
+            "log_level": self.log_level,
+            "db_pool_size": self.db_pool_size,
+            "db_pool_idle_secs": self.db_pool_idle_secs,
+            "prune_interval_secs": self.prune_interval_secs,
+            "retention": self.retention,
+            "backfill": self.backfill,
+        })
+    }
+}
+
+/// Remplace `target` par la variable d'environnement `name` si elle est définie
+///
+/// SUBTILITÉ RUST: générique sur FromStr pour parser u16, u32, u64, usize...
+fn override_parsed<T: std::str::FromStr>(name: &str, target: &mut T) -> Result<()> {
+    if let Ok(v) = std::env::var(name) {
+        *target = v
+            .parse()
+            .map_err(|_| CandleError::ParseError(format!("{} invalide: {}", name, v)))?;
+    }
+    Ok(())
+}
+
+/// Remplace `target` par la liste séparée par des virgules de la variable `name`
+///
+/// EXEMPLE: BACKFILL_TIMEFRAMES="1h,4h" → ["1h", "4h"]
+fn override_list(name: &str, target: &mut Vec<String>) {
+    if let Ok(v) = std::env::var(name) {
+        *target = v
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+}
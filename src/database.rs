@@ -2,9 +2,354 @@
 ///
 /// Ce module fournit une structure DatabaseManager pour encapsuler
 /// toutes les opérations liées à la base de données
-use anyhow::Result;
-use rusqlite::{Connection, Result as SqlResult};
-use std::path::Path;
+use crate::error::{CandleError, Result};
+use crate::migrations;
+use crate::models::{CandleRecord, CandleStats, DescriptiveStats, TimeframeInfo};
+use crate::timeframe_status::{Coverage, TimeframeStatus};
+use crate::utils::{compare_timeframes, parse_timeframe};
+use chrono::Utc;
+use rusqlite::backup::{Backup, StepResult};
+use rusqlite::{Connection, params};
+use serde::Serialize;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Attente maximale d'un verrou tenu par une autre connexion
+pub const BUSY_TIMEOUT: Duration = Duration::from_millis(5000);
+
+/// Schéma canonique de la table candlesticks
+///
+/// DESIGN: état du schéma après la dernière migration (migrations.rs).
+/// Les binaires qui créent une base de test sans DatabaseManager l'utilisent
+/// au lieu de leur propre CREATE TABLE
+pub const SQL_CREATE_TABLE_CANDLESTICKS: &str = "CREATE TABLE IF NOT EXISTS candlesticks (
+    provider TEXT NOT NULL,
+    symbol TEXT NOT NULL,
+    timeframe TEXT NOT NULL,
+    open_time INTEGER NOT NULL,
+    open REAL NOT NULL,
+    high REAL NOT NULL,
+    low REAL NOT NULL,
+    close REAL NOT NULL,
+    volume REAL NOT NULL,
+    close_time INTEGER NOT NULL,
+    quote_asset_volume REAL NOT NULL,
+    number_of_trades INTEGER NOT NULL,
+    taker_buy_base_asset_volume REAL NOT NULL,
+    taker_buy_quote_asset_volume REAL NOT NULL,
+    interpolated INTEGER NOT NULL DEFAULT 0,
+    derived_from TEXT,
+    UNIQUE(provider, symbol, timeframe, open_time)
+)";
+
+/// Schéma canonique de la table timeframe_status (voir TimeframeStatus)
+///
+/// - oldest_candle_time / newest_candle_time: bornes de la série récupérée
+/// - is_complete: 1 quand le backfill a atteint la limite (API ou date demandée)
+///
+/// NOTE: ordre des colonnes identique à une base migrée (ALTER TABLE ajoute
+/// newest_candle_time en fin de table)
+pub const SQL_CREATE_TABLE_TIMEFRAME_STATUS: &str = "CREATE TABLE IF NOT EXISTS timeframe_status (
+    provider TEXT NOT NULL,
+    symbol TEXT NOT NULL,
+    timeframe TEXT NOT NULL,
+    oldest_candle_time INTEGER,
+    is_complete INTEGER NOT NULL DEFAULT 0,
+    last_updated INTEGER NOT NULL,
+    newest_candle_time INTEGER,
+    PRIMARY KEY (provider, symbol, timeframe)
+)";
+
+/// Ouvre une connexion SQLite avec les pragmas communs à tous les binaires
+///
+/// PRAGMAS:
+/// - journal_mode=WAL: les lecteurs (API, temps réel) ne bloquent plus l'écrivain
+/// - synchronous=NORMAL: sûr en WAL, évite un fsync par transaction
+/// - busy_timeout: attend le verrou au lieu d'échouer avec "database is locked"
+/// - foreign_keys=ON
+///
+/// NOTE: le schéma n'est pas créé, voir DatabaseManager::new()
+pub fn open_configured<P: AsRef<Path>>(path: P) -> Result<Connection> {
+    let conn = Connection::open(path)?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    // journal_mode retourne le mode effectif ("memory" pour une base en mémoire)
+    conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))?;
+    conn.pragma_update(None, "synchronous", "NORMAL")?;
+    conn.pragma_update(None, "foreign_keys", "ON")?;
+    Ok(conn)
+}
+
+/// Insère des bougies sur la connexion (ou transaction) fournie
+///
+/// DESIGN: seule requête d'insertion dans candlesticks du projet.
+/// INSERT OR IGNORE: une bougie déjà présente (même open_time) est conservée
+///
+/// SUBTILITÉ RUST: prepare_cached réutilise le statement compilé d'un appel
+/// à l'autre via le cache de la connexion (pas de re-parsing SQL)
+///
+/// RETOUR: Nombre de bougies réellement insérées (pas les doublons)
+pub fn insert_candle_records(conn: &Connection, candles: &[CandleRecord]) -> Result<usize> {
+    let mut stmt = conn.prepare_cached(
+        "INSERT OR IGNORE INTO candlesticks (
+            provider, symbol, timeframe, open_time, open, high, low, close, volume,
+            close_time, quote_asset_volume, number_of_trades,
+            taker_buy_base_asset_volume, taker_buy_quote_asset_volume,
+            interpolated, derived_from
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+    )?;
+
+    let mut inserted = 0;
+    for candle in candles {
+        inserted += stmt.execute(params![
+            candle.provider,
+            candle.symbol,
+            candle.timeframe,
+            candle.open_time,
+            candle.open,
+            candle.high,
+            candle.low,
+            candle.close,
+            candle.volume,
+            candle.close_time,
+            candle.quote_asset_volume,
+            candle.number_of_trades,
+            candle.taker_buy_base_asset_volume,
+            candle.taker_buy_quote_asset_volume,
+            candle.interpolated,
+            candle.derived_from,
+        ])?;
+    }
+
+    Ok(inserted)
+}
+
+/// Lit les bougies d'une série dont l'open_time est dans `range`, triées
+///
+/// limit: nombre maximal de bougies (None: toute la plage)
+pub fn query_candle_records(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    range: RangeInclusive<i64>,
+    limit: Option<usize>,
+) -> Result<Vec<CandleRecord>> {
+    let mut stmt = conn.prepare_cached(&format!(
+        "SELECT {} FROM candlesticks
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+               AND open_time >= ?4 AND open_time <= ?5
+         ORDER BY open_time ASC
+         LIMIT ?6",
+        CandleRecord::COLUMNS
+    ))?;
+
+    // LIMIT négatif = pas de limite pour SQLite
+    let limit = limit.map_or(-1, |l| l as i64);
+    let candles = stmt
+        .query_map(
+            params![
+                provider,
+                symbol,
+                timeframe,
+                range.start(),
+                range.end(),
+                limit
+            ],
+            CandleRecord::from_row,
+        )?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(candles)
+}
+
+/// Statistiques d'une série (voir TimeframeStatus::coverage)
+///
+/// DESIGN: remplace les requêtes COUNT/MIN/MAX ad hoc des binaires
+///
+/// ERREURS: ValidationError (timeframe inconnu)
+pub fn candle_stats(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+) -> Result<CandleStats> {
+    let coverage = TimeframeStatus::coverage(conn, provider, symbol, timeframe)?;
+    Ok(CandleStats::from_coverage(
+        provider.to_string(),
+        symbol.to_string(),
+        timeframe.to_string(),
+        &coverage,
+    ))
+}
+
+/// Couverture de chaque timeframe stocké d'un symbole
+///
+/// RETOUR: trié par durée du timeframe (5m avant 1h), vide si symbole inconnu
+pub fn timeframe_infos(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+) -> Result<Vec<TimeframeInfo>> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT timeframe FROM candlesticks WHERE provider = ?1 AND symbol = ?2",
+    )?;
+    let timeframes = stmt
+        .query_map(params![provider, symbol], |row| row.get::<_, String>(0))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut infos = timeframes
+        .into_iter()
+        .map(|timeframe| {
+            let coverage = TimeframeStatus::coverage(conn, provider, symbol, &timeframe)?;
+            let (gap_count, missing_candles) = match coverage {
+                Coverage::Series { gaps, missing, .. } => (gaps, missing),
+                Coverage::Empty => (0, 0),
+            };
+            let stats = CandleStats::from_coverage(
+                provider.to_string(),
+                symbol.to_string(),
+                timeframe.clone(),
+                &coverage,
+            );
+            Ok(TimeframeInfo {
+                candle_count: stats.count,
+                oldest_candle: stats.first_open_time,
+                newest_candle: stats.last_open_time,
+                gap_count,
+                missing_candles,
+                interpolated_ratio: if stats.count > 0 {
+                    stats.interpolated_count as f64 / stats.count as f64
+                } else {
+                    0.0
+                },
+                coverage_percentage: stats.coverage_pct,
+                newest_candle_time: TimeframeStatus::get_newest_candle_time(
+                    conn, provider, symbol, &timeframe,
+                ),
+                is_backfill_complete: TimeframeStatus::is_complete(
+                    conn, provider, symbol, &timeframe,
+                )?,
+                timeframe,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    infos.sort_by(|a, b| compare_timeframes(&a.timeframe, &b.timeframe));
+    Ok(infos)
+}
+
+/// Statistiques descriptives OHLCV d'une plage d'open_time
+///
+/// ALGORITHME:
+/// 1. Une requête d'agrégats (COUNT, AVG, MIN, MAX, SUM) pour tout sauf l'écart-type
+/// 2. SQLite n'a pas de STDDEV: second passage sur les clôtures, écarts à la
+///    moyenne du premier passage (plus stable que sum(x²) - n·moyenne²)
+///
+/// RETOUR: None si aucune bougie dans la plage
+pub fn descriptive_stats(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    range: RangeInclusive<i64>,
+) -> Result<Option<DescriptiveStats>> {
+    let filter = "FROM candlesticks
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+               AND open_time >= ?4 AND open_time <= ?5";
+    let series = params![provider, symbol, timeframe, range.start(), range.end()];
+
+    let stats = conn.query_row(
+        &format!(
+            "SELECT COUNT(*), AVG(close), MIN(close), MAX(close), SUM(volume), AVG(volume),
+                    MAX(high), MIN(low), AVG(ABS(close - open)),
+                    AVG(high - low - ABS(close - open)),
+                    SUM(CASE WHEN ABS(close - open) > {ratio} * (high - low) AND close > open
+                             THEN 1 ELSE 0 END),
+                    SUM(CASE WHEN ABS(close - open) > {ratio} * (high - low) AND close < open
+                             THEN 1 ELSE 0 END),
+                    SUM(CASE WHEN ABS(close - open) <= {ratio} * (high - low)
+                             THEN 1 ELSE 0 END)
+             {filter}",
+            ratio = DescriptiveStats::DOJI_BODY_RATIO,
+            filter = filter
+        ),
+        series,
+        |row| {
+            let count: i64 = row.get(0)?;
+            if count == 0 {
+                return Ok(None);
+            }
+            Ok(Some(DescriptiveStats {
+                count,
+                mean_close: row.get(1)?,
+                std_close: 0.0,
+                min_close: row.get(2)?,
+                max_close: row.get(3)?,
+                total_volume: row.get(4)?,
+                mean_volume: row.get(5)?,
+                highest_high: row.get(6)?,
+                lowest_low: row.get(7)?,
+                avg_body_size: row.get(8)?,
+                avg_wick_size: row.get(9)?,
+                bullish_candles: row.get(10)?,
+                bearish_candles: row.get(11)?,
+                doji_candles: row.get(12)?,
+            }))
+        },
+    )?;
+
+    let Some(mut stats) = stats else {
+        return Ok(None);
+    };
+
+    if stats.count > 1 {
+        let mut stmt = conn.prepare_cached(&format!("SELECT close {}", filter))?;
+        let mut rows = stmt.query(series)?;
+        let mut squared_deviations = 0.0;
+        while let Some(row) = rows.next()? {
+            let deviation = row.get::<_, f64>(0)? - stats.mean_close;
+            squared_deviations += deviation * deviation;
+        }
+        stats.std_close = (squared_deviations / (stats.count - 1) as f64).sqrt();
+    }
+
+    Ok(Some(stats))
+}
+
+/// Statistiques de toutes les séries présentes dans la base
+///
+/// RETOUR: trié par provider, symbole puis durée du timeframe (5m avant 1h).
+/// Les timeframes inconnus sont ignorés (signalés par le binaire audit)
+pub fn all_candle_stats(conn: &Connection) -> Result<Vec<CandleStats>> {
+    symbol_candle_stats(conn, None)
+}
+
+/// Comme all_candle_stats, limité à un symbole si `symbol` est donné
+pub fn symbol_candle_stats(conn: &Connection, symbol: Option<&str>) -> Result<Vec<CandleStats>> {
+    let series: Vec<(String, String, String)> = {
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT provider, symbol, timeframe FROM candlesticks
+             WHERE ?1 IS NULL OR symbol = ?1",
+        )?;
+        stmt.query_map([symbol], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<std::result::Result<_, _>>()?
+    };
+
+    let mut stats = series
+        .iter()
+        .filter(|(_, _, timeframe)| parse_timeframe(timeframe).is_ok())
+        .map(|(provider, symbol, timeframe)| candle_stats(conn, provider, symbol, timeframe))
+        .collect::<Result<Vec<_>>>()?;
+
+    stats.sort_by(|a, b| {
+        (&a.provider, &a.symbol)
+            .cmp(&(&b.provider, &b.symbol))
+            .then_with(|| compare_timeframes(&a.timeframe, &b.timeframe))
+    });
+    Ok(stats)
+}
 
 /// Gestionnaire de la base de données SQLite
 ///
@@ -15,67 +360,480 @@ pub struct DatabaseManager {
     conn: Connection,
 }
 
+/// Supprime les bougies d'une série dont l'open_time précède keep_after_ms
+///
+/// ALGORITHME (transaction unique):
+/// 1. DELETE des bougies open_time < keep_after_ms (réelles et synthétiques)
+/// 2. Bornes de timeframe_status recalées sur les bougies restantes
+///    (TimeframeStatus::refresh_bounds, statut réinitialisé si tout est purgé)
+///
+/// Les bougies à partir de keep_after_ms (incluse) ne sont jamais touchées.
+/// NOTE: seules les bougies sont purgées; indicator_values est conservée
+/// (OBV cumulatif: un recalcul partirait de la nouvelle première bougie)
+///
+/// RETOUR: Nombre de bougies supprimées
+pub fn prune_candles(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    keep_after_ms: i64,
+) -> Result<usize> {
+    let tx = conn.transaction()?;
+    let deleted = tx.execute(
+        "DELETE FROM candlesticks
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3 AND open_time < ?4",
+        params![provider, symbol, timeframe, keep_after_ms],
+    )?;
+    if deleted > 0 {
+        TimeframeStatus::refresh_bounds(&tx, provider, symbol, timeframe)?;
+    }
+    tx.commit()?;
+    Ok(deleted)
+}
+
+/// Taille d'une base en pages SQLite (fichier principal, hors WAL)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DbSize {
+    pub page_count: i64,
+    /// Pages libérées (DELETE) non rendues au système sans VACUUM
+    pub freelist_count: i64,
+    pub page_size: i64,
+}
+
+impl DbSize {
+    /// Lit les pragmas de taille de la connexion
+    pub fn read(conn: &Connection) -> Result<Self> {
+        let pragma = |name: &str| conn.pragma_query_value(None, name, |row| row.get::<_, i64>(0));
+        Ok(DbSize {
+            page_count: pragma("page_count")?,
+            freelist_count: pragma("freelist_count")?,
+            page_size: pragma("page_size")?,
+        })
+    }
+
+    /// Taille du fichier principal en octets
+    pub fn bytes(&self) -> i64 {
+        self.page_count * self.page_size
+    }
+}
+
+/// Résultat d'une maintenance (voir optimize_database)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct OptimizeReport {
+    pub before: DbSize,
+    pub after: DbSize,
+    pub vacuumed: bool,
+}
+
+/// Maintenance de la base après purge ou remplacement massif de bougies
+///
+/// ALGORITHME:
+/// 1. VACUUM si demandé: réécrit tout le fichier et rend les pages libres
+///    (fichier plus petit, index défragmentés). Coûteux: optionnel
+/// 2. ANALYZE: statistiques des index à jour pour le planificateur
+/// 3. PRAGMA optimize: ajustements décidés par SQLite
+/// 4. Checkpoint WAL (TRUNCATE): le fichier -wal ne garde pas l'ancien contenu
+///
+/// NOTE: VACUUM exige qu'aucune autre connexion n'écrive; un backfill en
+/// cours fait échouer la maintenance (verrou SQLite, après BUSY_TIMEOUT).
+/// Pas encore de verrou de backfill par symbole pour le refuser en amont
+pub fn optimize_database(conn: &Connection, vacuum: bool) -> Result<OptimizeReport> {
+    let before = DbSize::read(conn)?;
+
+    if vacuum {
+        conn.execute_batch("VACUUM")?;
+    }
+    conn.execute_batch("ANALYZE; PRAGMA optimize;")?;
+    // En mémoire (pas de WAL) le pragma ne fait rien
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+
+    Ok(OptimizeReport {
+        before,
+        after: DbSize::read(conn)?,
+        vacuumed: vacuum,
+    })
+}
+
+/// Contrôle d'intégrité complet de la base (PRAGMA integrity_check)
+///
+/// Détecte une corruption (pages, index incohérents avec leur table,
+/// contraintes) qui ferait perdre des bougies sans erreur visible
+///
+/// PERFORMANCE: lit toute la base et vérifie chaque index, de quelques
+/// secondes à plusieurs minutes sur une grosse base; voir check_quick
+///
+/// RETOUR: Messages d'erreur de SQLite, vide si la base est saine
+pub fn check_integrity(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    let messages = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    // Base saine: une seule ligne "ok"
+    Ok(messages.into_iter().filter(|m| m != "ok").collect())
+}
+
+/// Contrôle rapide de la base (PRAGMA quick_check): comme check_integrity,
+/// sans vérifier le contenu des index par rapport à leur table
+///
+/// RETOUR: true si la base est saine
+pub fn check_quick(conn: &Connection) -> Result<bool> {
+    let first: String = conn.query_row("PRAGMA quick_check", [], |row| row.get(0))?;
+    Ok(first == "ok")
+}
+
+/// Variable d'environnement qui déclenche vacuum_database à l'ouverture
+/// (DatabaseManager::new) si elle vaut "true"
+pub const VACUUM_ON_STARTUP_ENV: &str = "VACUUM_ON_STARTUP";
+
+/// Compacte la base (VACUUM): réécrit tout le fichier sans les pages
+/// libérées par les DELETE et remplacements (purge, backfill massif)
+///
+/// SUBTILITÉ: VACUUM copie la base dans un fichier temporaire avant de la
+/// remplacer: il faut autant d'espace disque libre que la taille de la
+/// base. Comme optimize_database, il échoue si une autre connexion écrit
+///
+/// RETOUR: Octets libérés (taille du fichier principal avant - après)
+pub fn vacuum_database(conn: &Connection) -> Result<u64> {
+    let before = DbSize::read(conn)?;
+    info!(
+        bytes = before.bytes(),
+        free_pages = before.freelist_count,
+        "VACUUM démarré (espace disque libre nécessaire: taille de la base)"
+    );
+    let started = Instant::now();
+
+    conn.execute_batch("VACUUM")?;
+    // En WAL, le fichier principal ne rétrécit qu'au checkpoint
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+
+    let after = DbSize::read(conn)?;
+    let freed = (before.bytes() - after.bytes()).max(0) as u64;
+    info!(
+        bytes = after.bytes(),
+        freed,
+        duration_ms = started.elapsed().as_millis() as u64,
+        "VACUUM terminé"
+    );
+    Ok(freed)
+}
+
+/// Pages copiées par étape de sauvegarde: les écritures des autres
+/// connexions ne sont jamais retardées de plus d'une étape
+pub const BACKUP_PAGES_PER_STEP: i32 = 256;
+
+/// Pause entre deux étapes de sauvegarde (laisse passer les écrivains)
+pub const BACKUP_STEP_PAUSE: Duration = Duration::from_millis(5);
+
+/// Redémarrages tolérés avant de finir la copie en une seule étape
+const BACKUP_MAX_RESTARTS: usize = 3;
+
+/// Résultat d'une sauvegarde (voir backup_database)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BackupReport {
+    pub path: String,
+    /// Taille du fichier de sauvegarde
+    pub bytes: u64,
+    /// Paire conservée (None: base complète)
+    pub symbol: Option<String>,
+    /// Bougies présentes dans la sauvegarde
+    pub candles: i64,
+}
+
+/// Sauvegarde cohérente de la base de `conn` dans le fichier `dest`
+///
+/// Copier le fichier .db d'une base en WAL pendant qu'un processus écrit donne
+/// un instantané incohérent (pages du WAL absentes). L'API de sauvegarde de
+/// SQLite copie page à page depuis une transaction de lecture
+///
+/// ALGORITHME:
+/// 1. Copie dans `<dest>.partial` par étapes de BACKUP_PAGES_PER_STEP pages,
+///    BACKUP_STEP_PAUSE entre deux étapes
+/// 2. Une écriture d'une autre connexion entre deux étapes fait repartir la
+///    copie du début: après BACKUP_MAX_RESTARTS redémarrages, le reste est
+///    copié en une étape (transaction de lecture: en WAL, les écrivains ne
+///    sont pas bloqués, seul le checkpoint attend)
+/// 3. `symbol`: les autres paires sont supprimées de la copie puis VACUUM
+///    (sauvegarde d'une paire, au format `<SYMBOL>.db` de PairDatabases)
+/// 4. Journal DELETE: la sauvegarde est un fichier autonome, sans -wal
+/// 5. Renommage en `dest` (jamais de sauvegarde à moitié écrite sous ce nom)
+///
+/// ERREURS: `dest` existe déjà (ValidationError), erreurs SQLite ou disque
+/// (le fichier partiel est supprimé)
+pub fn backup_database(
+    conn: &Connection,
+    dest: &Path,
+    symbol: Option<&str>,
+) -> Result<BackupReport> {
+    if dest.exists() {
+        return Err(CandleError::ValidationError(format!(
+            "Sauvegarde déjà présente: {}",
+            dest.display()
+        )));
+    }
+    let mut partial = dest.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+    let _ = std::fs::remove_file(&partial);
+
+    let result = write_backup(conn, &partial, symbol).and_then(|candles| {
+        std::fs::rename(&partial, dest)?;
+        Ok(candles)
+    });
+    let candles = match result {
+        Ok(candles) => candles,
+        Err(e) => {
+            let _ = std::fs::remove_file(&partial);
+            return Err(e);
+        }
+    };
+
+    Ok(BackupReport {
+        path: dest.display().to_string(),
+        bytes: std::fs::metadata(dest)?.len(),
+        symbol: symbol.map(str::to_string),
+        candles,
+    })
+}
+
+/// Copie vers `path` puis restriction à `symbol` (voir backup_database)
+///
+/// RETOUR: nombre de bougies de la copie
+fn write_backup(conn: &Connection, path: &Path, symbol: Option<&str>) -> Result<i64> {
+    let mut dest = Connection::open(path)?;
+    {
+        let backup = Backup::new(conn, &mut dest)?;
+        let mut restarts = 0;
+        let mut last_remaining = i32::MAX;
+        loop {
+            let pages = if restarts > BACKUP_MAX_RESTARTS {
+                -1
+            } else {
+                BACKUP_PAGES_PER_STEP
+            };
+            match backup.step(pages)? {
+                StepResult::Done => break,
+                // More, ou Busy/Locked (étape à retenter)
+                _ => {
+                    // remaining remonte: la source a changé, la copie est repartie
+                    let remaining = backup.progress().remaining;
+                    if remaining > last_remaining {
+                        restarts += 1;
+                    }
+                    last_remaining = remaining;
+                    std::thread::sleep(BACKUP_STEP_PAUSE);
+                }
+            }
+        }
+    }
+
+    if let Some(symbol) = symbol {
+        dest.execute("DELETE FROM candlesticks WHERE symbol != ?1", [symbol])?;
+        dest.execute("DELETE FROM timeframe_status WHERE symbol != ?1", [symbol])?;
+    }
+    dest.pragma_update_and_check(None, "journal_mode", "DELETE", |row| {
+        row.get::<_, String>(0)
+    })?;
+    if symbol.is_some() {
+        dest.execute_batch("VACUUM")?;
+    }
+
+    let candles = dest.query_row("SELECT COUNT(*) FROM candlesticks", [], |row| row.get(0))?;
+    Ok(candles)
+}
+
+/// Répertoire horodaté d'une série de sauvegardes: `<root>/YYYYMMDD_HHMMSS`
+pub fn snapshot_dir<P: AsRef<Path>>(root: P) -> PathBuf {
+    root.as_ref()
+        .join(Utc::now().format("%Y%m%d_%H%M%S").to_string())
+}
+
+/// Nombre maximal de bases attachées à une connexion
+/// (SQLITE_MAX_ATTACHED, 10 dans la compilation par défaut de SQLite)
+pub const MAX_ATTACHED_DATABASES: usize = 10;
+
+/// URI SQLite en lecture seule d'un chemin
+///
+/// SUBTILITÉ: '?', '#' et '%' ont un sens dans une URI, ils sont encodés
+/// pour qu'un nom de fichier exotique ne soit pas pris pour un paramètre
+pub(crate) fn read_only_uri(path: &Path) -> String {
+    let mut encoded = String::new();
+    for c in path.to_string_lossy().chars() {
+        match c {
+            '%' => encoded.push_str("%25"),
+            '?' => encoded.push_str("%3f"),
+            '#' => encoded.push_str("%23"),
+            c => encoded.push(c),
+        }
+    }
+    format!("file:{}?mode=ro", encoded)
+}
+
+/// Bases de plusieurs paires réunies sur une seule connexion
+///
+/// Fichiers `<db_dir>/<SYMBOL>.db`: la base du premier symbole est ouverte
+/// (schéma `main`), les suivantes sont attachées en lecture seule sous un
+/// nom de schéma dérivé du symbole (voir attached_schema_name). Une requête
+/// peut alors lire plusieurs paires d'un coup:
+///
+/// EXEMPLE:
+/// SELECT ... FROM main.candlesticks UNION ALL SELECT ... FROM pair_ethusdt.candlesticks
+///
+/// DESIGN: les bases sont détachées dans Drop, y compris quand l'ouverture
+/// échoue à mi-chemin (la valeur partiellement construite est détruite)
+pub struct PairDatabases {
+    conn: Connection,
+    /// Schéma de chaque symbole, dans l'ordre de la demande
+    schemas: Vec<(String, String)>,
+}
+
+impl PairDatabases {
+    /// Ouvre la base du premier symbole et attache celles des suivants
+    ///
+    /// ERREURS (ValidationError): liste vide, plus de
+    /// 1 + MAX_ATTACHED_DATABASES symboles, symbole en double (après
+    /// normalisation du nom de schéma), fichier .db absent
+    pub fn open<P: AsRef<Path>>(db_dir: P, symbols: &[&str]) -> Result<Self> {
+        let Some((first, others)) = symbols.split_first() else {
+            return Err(CandleError::ValidationError(
+                "Aucun symbole à ouvrir".to_string(),
+            ));
+        };
+        if others.len() > MAX_ATTACHED_DATABASES {
+            return Err(CandleError::ValidationError(format!(
+                "Trop de symboles: {} (maximum {})",
+                symbols.len(),
+                MAX_ATTACHED_DATABASES + 1
+            )));
+        }
+
+        // Tous les fichiers sont vérifiés avant d'ouvrir quoi que ce soit
+        let paths = symbols
+            .iter()
+            .map(|symbol| {
+                let path = db_dir.as_ref().join(format!("{}.db", symbol));
+                if path.is_file() {
+                    Ok(path)
+                } else {
+                    Err(CandleError::ValidationError(format!(
+                        "Base introuvable pour {}: {}",
+                        symbol,
+                        path.display()
+                    )))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut combined = PairDatabases {
+            conn: open_configured(&paths[0])?,
+            schemas: vec![(first.to_string(), "main".to_string())],
+        };
+        for (symbol, path) in others.iter().zip(&paths[1..]) {
+            let schema = attached_schema_name(symbol);
+            if combined.schemas.iter().any(|(_, s)| *s == schema) {
+                return Err(CandleError::ValidationError(format!(
+                    "Symbole en double: {}",
+                    symbol
+                )));
+            }
+            // Le nom de schéma ne peut pas être un paramètre: il est
+            // restreint à [a-z0-9_] par attached_schema_name
+            combined.conn.execute(
+                &format!("ATTACH DATABASE ?1 AS {}", schema),
+                [read_only_uri(path)],
+            )?;
+            combined.schemas.push((symbol.to_string(), schema));
+        }
+
+        Ok(combined)
+    }
+
+    /// Schéma SQL d'un symbole ouvert (`main` pour le premier)
+    pub fn schema(&self, symbol: &str) -> Option<&str> {
+        self.schemas
+            .iter()
+            .find(|(s, _)| s == symbol)
+            .map(|(_, schema)| schema.as_str())
+    }
+
+    /// Exécute `f` sur la connexion combinée
+    ///
+    /// `f` reçoit les schémas dans l'ordre des symboles demandés
+    pub fn run<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&Connection, &[&str]) -> Result<T>,
+    {
+        let schemas: Vec<&str> = self.schemas.iter().map(|(_, s)| s.as_str()).collect();
+        f(&self.conn, &schemas)
+    }
+}
+
+impl Drop for PairDatabases {
+    fn drop(&mut self) {
+        for (_, schema) in self.schemas.iter().skip(1).rev() {
+            let _ = self
+                .conn
+                .execute(&format!("DETACH DATABASE {}", schema), []);
+        }
+    }
+}
+
+/// Nom de schéma d'une base attachée: `pair_` suivi du symbole en minuscules,
+/// tout caractère hors [a-z0-9] remplacé par `_`
+///
+/// EXEMPLE: "BTC-USDT" → "pair_btc_usdt"
+pub fn attached_schema_name(symbol: &str) -> String {
+    let sanitized: String = symbol
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("pair_{}", sanitized)
+}
+
 impl DatabaseManager {
     /// Crée et initialise une nouvelle connexion à la base de données
     ///
     /// ALGORITHME:
-    /// 1. Ouvre la connexion SQLite
-    /// 2. Crée la table candlesticks si elle n'existe pas
-    /// 3. Crée la table timeframe_status si elle n'existe pas
+    /// 1. Ouvre la connexion SQLite (open_configured: WAL, busy_timeout, ...)
+    /// 2. Applique les migrations manquantes (tables, colonnes, voir migrations.rs)
+    /// 3. Contrôle rapide (check_quick): une base corrompue est signalée par
+    ///    un warning, sans empêcher l'ouverture (données encore lisibles)
+    /// 4. VACUUM si VACUUM_ON_STARTUP=true (voir vacuum_database)
     ///
     /// SUBTILITÉ RUST: Pattern builder avec Self
     /// Self est un alias pour DatabaseManager dans ce contexte
     pub fn new(db_file: &str) -> Result<Self> {
-        let path = Path::new(db_file);
-        let conn = Connection::open(path)?;
+        let mut conn = open_configured(db_file)?;
 
-        // Initialiser le schéma
-        Self::init_schema(&conn)?;
+        // Créer ou mettre à jour le schéma
+        migrations::run(&mut conn)?;
 
-        Ok(DatabaseManager { conn })
+        let manager = DatabaseManager { conn };
+        if !manager.check_quick()? {
+            warn!(
+                db_file,
+                "Contrôle d'intégrité rapide en échec: base corrompue (voir check_integrity)"
+            );
+        }
+        if std::env::var(VACUUM_ON_STARTUP_ENV).is_ok_and(|v| v.eq_ignore_ascii_case("true")) {
+            manager.vacuum()?;
+        }
+
+        Ok(manager)
     }
 
-    /// Initialise le schéma de la base de données
+    /// Base en mémoire avec le schéma complet (tests, traitements jetables)
     ///
-    /// DESIGN: Méthode privée, appelée uniquement depuis new()
-    fn init_schema(conn: &Connection) -> SqlResult<()> {
-        // Table principale des bougies
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS candlesticks (
-                provider TEXT NOT NULL,
-                symbol TEXT NOT NULL,
-                timeframe TEXT NOT NULL,
-                open_time INTEGER NOT NULL,
-                open REAL NOT NULL,
-                high REAL NOT NULL,
-                low REAL NOT NULL,
-                close REAL NOT NULL,
-                volume REAL NOT NULL,
-                close_time INTEGER NOT NULL,
-                quote_asset_volume REAL NOT NULL,
-                number_of_trades INTEGER NOT NULL,
-                taker_buy_base_asset_volume REAL NOT NULL,
-                taker_buy_quote_asset_volume REAL NOT NULL,
-                interpolated INTEGER NOT NULL DEFAULT 0,
-                UNIQUE(provider, symbol, timeframe, open_time)
-            )",
-            [],
-        )?;
-
-        // Table de statut des timeframes (pour monitoring uniquement)
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS timeframe_status (
-                provider TEXT NOT NULL,
-                symbol TEXT NOT NULL,
-                timeframe TEXT NOT NULL,
-                oldest_candle_time INTEGER,
-                last_updated INTEGER NOT NULL,
-                PRIMARY KEY (provider, symbol, timeframe)
-            )",
-            [],
-        )?;
-
-        Ok(())
+    /// Chaque appel crée une base distincte, détruite avec le DatabaseManager
+    pub fn new_in_memory() -> Result<Self> {
+        Self::new(":memory:")
     }
 
     /// Retourne une référence à la connexion SQLite
@@ -93,4 +851,75 @@ impl DatabaseManager {
     pub fn connection_mut(&mut self) -> &mut Connection {
         &mut self.conn
     }
+
+    /// Insère des bougies dans une transaction unique
+    ///
+    /// RETOUR: Nombre de bougies réellement insérées (pas les doublons)
+    pub fn insert_candles(&mut self, candles: &[CandleRecord]) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let inserted = insert_candle_records(&tx, candles)?;
+        tx.commit()?;
+        Ok(inserted)
+    }
+
+    /// Lit les bougies d'une série (voir query_candle_records)
+    pub fn query_candles(
+        &self,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        range: RangeInclusive<i64>,
+        limit: Option<usize>,
+    ) -> Result<Vec<CandleRecord>> {
+        query_candle_records(&self.conn, provider, symbol, timeframe, range, limit)
+    }
+
+    /// Volume et couverture d'une série (voir candle_stats)
+    pub fn stats(&self, provider: &str, symbol: &str, timeframe: &str) -> Result<CandleStats> {
+        candle_stats(&self.conn, provider, symbol, timeframe)
+    }
+
+    /// Contrôle d'intégrité complet (voir check_integrity, lent sur une
+    /// grosse base)
+    pub fn check_integrity(&self) -> Result<Vec<String>> {
+        check_integrity(&self.conn)
+    }
+
+    /// Contrôle rapide (voir check_quick)
+    pub fn check_quick(&self) -> Result<bool> {
+        check_quick(&self.conn)
+    }
+
+    /// Compacte la base (voir vacuum_database)
+    ///
+    /// RETOUR: Octets libérés
+    pub fn vacuum(&self) -> Result<u64> {
+        vacuum_database(&self.conn)
+    }
+
+    /// Purge les bougies antérieures à keep_after_ms (voir prune_candles)
+    pub fn prune(
+        &mut self,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        keep_after_ms: i64,
+    ) -> Result<usize> {
+        prune_candles(&mut self.conn, provider, symbol, timeframe, keep_after_ms)
+    }
+
+    /// ANALYZE, PRAGMA optimize et VACUUM optionnel (voir optimize_database)
+    pub fn optimize(&self, vacuum: bool) -> Result<OptimizeReport> {
+        optimize_database(&self.conn, vacuum)
+    }
+
+    /// Sauvegarde cohérente de toute la base dans `path` (voir backup_database)
+    pub fn backup_to<P: AsRef<Path>>(&self, path: P) -> Result<BackupReport> {
+        backup_database(&self.conn, path.as_ref(), None)
+    }
+
+    /// Statistiques de chaque série présente dans la base (voir all_candle_stats)
+    pub fn stats_all(&self) -> Result<Vec<CandleStats>> {
+        all_candle_stats(&self.conn)
+    }
 }
@@ -0,0 +1,111 @@
+/// Module des fenêtres d'indisponibilité connues de l'exchange
+///
+/// Pendant une maintenance Binance, aucune bougie n'existe pour aucune paire:
+/// ces gaps sont attendus et ne doivent pas être interpolés (données inventées
+/// sur toutes les paires). La table known_downtime les recense
+use crate::error::{CandleError, Result};
+use rusqlite::{Connection, params};
+
+/// Maintenances historiques de Binance (spot), bornes en ms UTC
+///
+/// NOTE: bornes volontairement élargies. Une fenêtre trop large est sans
+/// risque: elle ne concerne que les périodes où aucune bougie n'est stockée
+pub const BINANCE_HISTORICAL_DOWNTIME: [(i64, i64, &str); 2] = [
+    (
+        1_518_048_000_000, // 2018-02-08 00:00
+        1_518_170_400_000, // 2018-02-09 10:00
+        "Binance: mise à jour système (février 2018)",
+    ),
+    (
+        1_679_659_200_000, // 2023-03-24 12:00
+        1_679_668_200_000, // 2023-03-24 14:30
+        "Binance: arrêt du trading spot (mars 2023)",
+    ),
+];
+
+/// Fenêtre d'indisponibilité [start_ms, end_ms]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DowntimeWindow {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub note: Option<String>,
+}
+
+impl DowntimeWindow {
+    /// true si chaque bougie manquante du gap chevauche la fenêtre
+    ///
+    /// ALGORITHME: les bougies manquantes sont contiguës, il suffit que la
+    /// première se termine après start_ms et que la dernière commence avant end_ms
+    ///
+    /// PARAMÈTRES: after_open_time / before_open_time = bornes exclusives du gap
+    pub fn covers_gap(&self, after_open_time: i64, before_open_time: i64, interval: i64) -> bool {
+        let first_missing_close = after_open_time + 2 * interval - 1;
+        let last_missing_open = before_open_time - interval;
+        first_missing_close >= self.start_ms && last_missing_open <= self.end_ms
+    }
+}
+
+/// Gestionnaire de la table known_downtime
+pub struct KnownDowntime;
+
+impl KnownDowntime {
+    /// Enregistre une fenêtre (ignorée si déjà présente)
+    ///
+    /// RETOUR: true si la fenêtre a été ajoutée
+    pub fn add(conn: &Connection, start_ms: i64, end_ms: i64, note: Option<&str>) -> Result<bool> {
+        if end_ms < start_ms {
+            return Err(CandleError::ValidationError(format!(
+                "Fenêtre invalide: fin {} avant début {}",
+                end_ms, start_ms
+            )));
+        }
+
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO known_downtime (start_ms, end_ms, note) VALUES (?1, ?2, ?3)",
+            params![start_ms, end_ms, note],
+        )?;
+
+        Ok(inserted > 0)
+    }
+
+    /// Enregistre la liste BINANCE_HISTORICAL_DOWNTIME
+    ///
+    /// RETOUR: Nombre de fenêtres ajoutées (0 si déjà présentes)
+    pub fn seed_binance_history(conn: &Connection) -> Result<usize> {
+        let mut added = 0;
+        for (start_ms, end_ms, note) in BINANCE_HISTORICAL_DOWNTIME {
+            if Self::add(conn, start_ms, end_ms, Some(note))? {
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    /// Liste les fenêtres triées par début
+    ///
+    /// Base créée sans DatabaseManager (table absente): liste vide
+    pub fn list(conn: &Connection) -> Result<Vec<DowntimeWindow>> {
+        let table_exists: bool = conn.query_row(
+            "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'known_downtime'",
+            [],
+            |row| row.get(0),
+        )?;
+        if !table_exists {
+            return Ok(Vec::new());
+        }
+
+        let mut stmt =
+            conn.prepare("SELECT start_ms, end_ms, note FROM known_downtime ORDER BY start_ms")?;
+        let windows = stmt
+            .query_map([], |row| {
+                Ok(DowntimeWindow {
+                    start_ms: row.get(0)?,
+                    end_ms: row.get(1)?,
+                    note: row.get(2)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(windows)
+    }
+}
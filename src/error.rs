@@ -0,0 +1,81 @@
+/// Module des erreurs de la bibliothèque
+///
+/// CandleError remplace anyhow dans l'API publique pour permettre aux
+/// appelants de distinguer les catégories d'erreurs par pattern matching.
+/// Les binaires continuent d'utiliser anyhow::Result (conversion via `?`)
+use thiserror::Error;
+
+/// Erreur de la bibliothèque Rust Candles Retriever
+///
+/// SUBTILITÉ RUST: thiserror génère Display, std::error::Error et les
+/// implémentations From<...> pour les variantes annotées #[from]
+#[derive(Debug, Error)]
+pub enum CandleError {
+    /// Erreur SQLite (ouverture, requête, transaction...)
+    #[error("Erreur base de données: {0}")]
+    DatabaseError(#[from] rusqlite::Error),
+
+    /// Erreur renvoyée par l'API Binance (code -1 si non fourni)
+    #[error("Erreur API Binance (code {code}): {message}")]
+    ApiError { code: i32, message: String },
+
+    /// Donnée impossible à parser (date, nombre, timeframe...)
+    #[error("Erreur de parsing: {0}")]
+    ParseError(String),
+
+    /// Limite de poids Binance dépassée, réessayer après le délai
+    #[error("Rate limit dépassé, réessayer dans {retry_after_ms} ms")]
+    RateLimitExceeded { retry_after_ms: u64 },
+
+    /// Disjoncteur ouvert après trop d'échecs API, ne pas réessayer immédiatement
+    #[error("Circuit breaker ouvert, réessayer dans {retry_after_ms} ms")]
+    CircuitBreakerOpen { retry_after_ms: u64 },
+
+    /// Paramètre ou état invalide
+    #[error("Erreur de validation: {0}")]
+    ValidationError(String),
+
+    /// Erreur d'entrée/sortie
+    #[error("Erreur d'E/S: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+/// Code Binance "Too many requests"
+const BINANCE_TOO_MANY_REQUESTS: i32 = -1003;
+
+impl From<binance::errors::Error> for CandleError {
+    /// Convertit une erreur binance-rs en conservant le code Binance quand il existe
+    fn from(e: binance::errors::Error) -> Self {
+        match e.0 {
+            binance::errors::ErrorKind::BinanceError(content) => {
+                let code = content.code as i32;
+                if code == BINANCE_TOO_MANY_REQUESTS {
+                    CandleError::RateLimitExceeded {
+                        retry_after_ms: 60_000,
+                    }
+                } else {
+                    CandleError::ApiError {
+                        code,
+                        message: content.msg,
+                    }
+                }
+            }
+            other => CandleError::ApiError {
+                code: -1,
+                message: format!("{:?}", other),
+            },
+        }
+    }
+}
+
+impl From<std::time::SystemTimeError> for CandleError {
+    fn from(e: std::time::SystemTimeError) -> Self {
+        CandleError::ValidationError(format!("Horloge système invalide: {}", e))
+    }
+}
+
+/// Alias de Result utilisé par les modules de la bibliothèque
+///
+/// SUBTILITÉ RUST: paramètre de type par défaut (E = CandleError), ce qui
+/// permet encore d'écrire Result<Vec<_>, _> dans les collect()
+pub type Result<T, E = CandleError> = std::result::Result<T, E>;
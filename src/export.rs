@@ -0,0 +1,469 @@
+/// Module d'export des bougies (CSV, Parquet)
+///
+/// DESIGN: les lignes sont lues une à une depuis SQLite et transmises par
+/// blocs à un `sink`, sans jamais charger toute la plage en mémoire
+/// (un export complet de 5m dépasse le million de lignes)
+///
+/// FEATURE: l'écriture Parquet n'est compilée qu'avec la feature `parquet`
+/// (active par défaut)
+use crate::error::{CandleError, Result};
+use crate::models::CandleRecord;
+use crate::utils::{format_timestamp_iso, format_timestamp_iso_ms};
+use chrono::{DateTime, Datelike, Months};
+#[cfg(feature = "parquet")]
+use parquet::{
+    basic::Compression, column::writer::ColumnWriter, data_type::ByteArray,
+    file::properties::WriterProperties, file::writer::SerializedFileWriter,
+    schema::parser::parse_message_type,
+};
+use rusqlite::{Connection, params};
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+#[cfg(feature = "parquet")]
+use std::sync::Arc;
+
+/// Nombre de lignes CSV par bloc transmis au sink
+pub const EXPORT_CHUNK_ROWS: usize = 1000;
+
+/// Nombre de bougies par row group Parquet (bougies gardées en mémoire)
+#[cfg(feature = "parquet")]
+pub const PARQUET_ROW_GROUP_ROWS: usize = 50_000;
+
+/// Format des colonnes du fichier CSV
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// Toutes les colonnes utiles, timestamps en ms et ISO 8601
+    #[default]
+    Standard,
+    /// Colonnes attendues par l'import de TradingView (time en secondes)
+    TradingView,
+    /// Toutes les colonnes de la table candlesticks, sous leur nom SQL
+    /// (human_time: open_time et close_time en ISO 8601 au lieu des ms)
+    Table { human_time: bool },
+}
+
+impl FromStr for ExportFormat {
+    type Err = CandleError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "csv" | "standard" => Ok(ExportFormat::Standard),
+            "tradingview" => Ok(ExportFormat::TradingView),
+            "table" => Ok(ExportFormat::Table { human_time: false }),
+            _ => Err(CandleError::ParseError(format!(
+                "Format d'export inconnu: {} (attendu: csv, tradingview, table)",
+                s
+            ))),
+        }
+    }
+}
+
+impl ExportFormat {
+    /// Ligne d'en-tête (avec saut de ligne)
+    pub fn header(&self) -> &'static str {
+        match self {
+            ExportFormat::Standard => {
+                "open_time_ms,open_time_iso,open,high,low,close,volume,quote_volume,trades,interpolated\n"
+            }
+            ExportFormat::TradingView => "time,open,high,low,close,Volume\n",
+            ExportFormat::Table { .. } => {
+                "provider,symbol,timeframe,open_time,open,high,low,close,volume,close_time,\
+                 quote_asset_volume,number_of_trades,taker_buy_base_asset_volume,\
+                 taker_buy_quote_asset_volume,interpolated,derived_from\n"
+            }
+        }
+    }
+
+    /// Ajoute la ligne CSV d'une bougie à `out`
+    ///
+    /// NOTE: Display de f64 produit la représentation la plus courte qui
+    /// relit exactement la même valeur: aucune perte de précision
+    pub fn write_row(&self, out: &mut String, candle: &CandleRecord) {
+        // SUBTILITÉ RUST: write! sur un String ne peut pas échouer
+        let _ = match self {
+            ExportFormat::Standard => writeln!(
+                out,
+                "{},{},{},{},{},{},{},{},{},{}",
+                candle.open_time,
+                format_timestamp_iso(candle.open_time),
+                candle.open,
+                candle.high,
+                candle.low,
+                candle.close,
+                candle.volume,
+                candle.quote_asset_volume,
+                candle.number_of_trades,
+                candle.interpolated
+            ),
+            ExportFormat::TradingView => writeln!(
+                out,
+                "{},{},{},{},{},{}",
+                candle.open_time / 1000,
+                candle.open,
+                candle.high,
+                candle.low,
+                candle.close,
+                candle.volume
+            ),
+            ExportFormat::Table { human_time } => {
+                let time = |timestamp_ms: i64| {
+                    if *human_time {
+                        format_timestamp_iso_ms(timestamp_ms)
+                    } else {
+                        timestamp_ms.to_string()
+                    }
+                };
+                writeln!(
+                    out,
+                    "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                    candle.provider,
+                    candle.symbol,
+                    candle.timeframe,
+                    time(candle.open_time),
+                    candle.open,
+                    candle.high,
+                    candle.low,
+                    candle.close,
+                    candle.volume,
+                    time(candle.close_time),
+                    candle.quote_asset_volume,
+                    candle.number_of_trades,
+                    candle.taker_buy_base_asset_volume,
+                    candle.taker_buy_quote_asset_volume,
+                    candle.interpolated,
+                    candle.derived_from.as_deref().unwrap_or("")
+                )
+            }
+        };
+    }
+}
+
+/// Découpage d'un export en plusieurs fichiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitBy {
+    /// Un fichier par mois calendaire UTC
+    Month,
+}
+
+impl FromStr for SplitBy {
+    type Err = CandleError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "month" => Ok(SplitBy::Month),
+            _ => Err(CandleError::ParseError(format!(
+                "Découpage inconnu: {} (attendu: month)",
+                s
+            ))),
+        }
+    }
+}
+
+/// Découpe une plage d'open_time en mois calendaires UTC
+///
+/// RETOUR: (étiquette "YYYY-MM", plage du mois bornée à `range`), dans l'ordre
+///
+/// NOTE: à appeler sur les bornes réelles d'une série (candle_stats), une
+/// plage ouverte (0..=i64::MAX) produirait des millions de mois
+pub fn month_ranges(range: RangeInclusive<i64>) -> Vec<(String, RangeInclusive<i64>)> {
+    let (start, end) = (*range.start(), *range.end());
+    let mut months = Vec::new();
+    let Some(mut month) =
+        DateTime::from_timestamp_millis(start).and_then(|first| first.date_naive().with_day(1))
+    else {
+        return months;
+    };
+
+    loop {
+        let month_start = month
+            .and_time(chrono::NaiveTime::MIN)
+            .and_utc()
+            .timestamp_millis();
+        if month_start > end {
+            break;
+        }
+        let Some(next) = month.checked_add_months(Months::new(1)) else {
+            break;
+        };
+        let next_start = next
+            .and_time(chrono::NaiveTime::MIN)
+            .and_utc()
+            .timestamp_millis();
+        months.push((
+            month.format("%Y-%m").to_string(),
+            month_start.max(start)..=(next_start - 1).min(end),
+        ));
+        month = next;
+    }
+    months
+}
+
+/// Requête de lecture d'une série dans l'ordre chronologique
+///
+/// include_interpolated = false: bougies réelles uniquement
+fn series_query(include_interpolated: bool) -> String {
+    format!(
+        "SELECT {} FROM candlesticks
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+               AND open_time >= ?4 AND open_time <= ?5{}
+         ORDER BY open_time ASC",
+        CandleRecord::COLUMNS,
+        if include_interpolated {
+            ""
+        } else {
+            " AND interpolated = 0"
+        }
+    )
+}
+
+/// Écrit le CSV d'une série par blocs de EXPORT_CHUNK_ROWS lignes
+///
+/// ALGORITHME:
+/// 1. En-tête envoyé seul (le client reçoit une réponse immédiatement)
+/// 2. Parcours des lignes SQLite sans collect(), un bloc envoyé toutes
+///    les EXPORT_CHUNK_ROWS lignes
+/// 3. `sink` retourne false si le client s'est déconnecté: arrêt de la lecture
+///
+/// RETOUR: Nombre de bougies exportées
+pub fn write_csv(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    range: RangeInclusive<i64>,
+    format: ExportFormat,
+    sink: impl FnMut(String) -> bool,
+) -> Result<usize> {
+    write_csv_filtered(conn, provider, symbol, timeframe, range, true, format, sink)
+}
+
+/// Comme write_csv, en excluant les bougies interpolées si
+/// `include_interpolated` est faux (binaire export_csv)
+#[allow(clippy::too_many_arguments)]
+pub fn write_csv_filtered(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    range: RangeInclusive<i64>,
+    include_interpolated: bool,
+    format: ExportFormat,
+    mut sink: impl FnMut(String) -> bool,
+) -> Result<usize> {
+    if !sink(format.header().to_string()) {
+        return Ok(0);
+    }
+
+    let mut stmt = conn.prepare(&series_query(include_interpolated))?;
+    let rows = stmt.query_map(
+        params![provider, symbol, timeframe, range.start(), range.end()],
+        CandleRecord::from_row,
+    )?;
+
+    let mut exported = 0;
+    let mut chunk = String::new();
+    for candle in rows {
+        format.write_row(&mut chunk, &candle?);
+        exported += 1;
+
+        if exported % EXPORT_CHUNK_ROWS == 0 && !sink(std::mem::take(&mut chunk)) {
+            return Ok(exported);
+        }
+    }
+
+    if !chunk.is_empty() {
+        sink(chunk);
+    }
+
+    Ok(exported)
+}
+
+/// Valeurs d'une colonne Parquet extraites d'une bougie
+#[cfg(feature = "parquet")]
+enum ParquetValues {
+    /// INT64 annoté TIMESTAMP(MILLIS, UTC)
+    Timestamp(fn(&CandleRecord) -> i64),
+    Int64(fn(&CandleRecord) -> i64),
+    Double(fn(&CandleRecord) -> f64),
+    Text(fn(&CandleRecord) -> &str),
+    OptionalText(fn(&CandleRecord) -> Option<&str>),
+}
+
+/// Colonnes du fichier Parquet, dans l'ordre de la table candlesticks
+///
+/// DESIGN: source unique du schéma (parquet_schema) et de l'écriture
+/// (write_row_group), les deux ne peuvent pas diverger
+///
+/// NOTE: interpolated reste un INT64 et non un booléen: la valeur porte le
+/// marqueur de FillStrategy (0 = bougie réelle), filtrer sur `interpolated != 0`
+#[cfg(feature = "parquet")]
+const PARQUET_COLUMNS: &[(&str, ParquetValues)] = &[
+    ("provider", ParquetValues::Text(|c| &c.provider)),
+    ("symbol", ParquetValues::Text(|c| &c.symbol)),
+    ("timeframe", ParquetValues::Text(|c| &c.timeframe)),
+    ("open_time", ParquetValues::Timestamp(|c| c.open_time)),
+    ("open", ParquetValues::Double(|c| c.open)),
+    ("high", ParquetValues::Double(|c| c.high)),
+    ("low", ParquetValues::Double(|c| c.low)),
+    ("close", ParquetValues::Double(|c| c.close)),
+    ("volume", ParquetValues::Double(|c| c.volume)),
+    ("close_time", ParquetValues::Timestamp(|c| c.close_time)),
+    (
+        "quote_asset_volume",
+        ParquetValues::Double(|c| c.quote_asset_volume),
+    ),
+    (
+        "number_of_trades",
+        ParquetValues::Int64(|c| c.number_of_trades),
+    ),
+    (
+        "taker_buy_base_asset_volume",
+        ParquetValues::Double(|c| c.taker_buy_base_asset_volume),
+    ),
+    (
+        "taker_buy_quote_asset_volume",
+        ParquetValues::Double(|c| c.taker_buy_quote_asset_volume),
+    ),
+    ("interpolated", ParquetValues::Int64(|c| c.interpolated)),
+    (
+        "derived_from",
+        ParquetValues::OptionalText(|c| c.derived_from.as_deref()),
+    ),
+];
+
+/// Schéma Parquet (format message) calqué sur la table candlesticks
+#[cfg(feature = "parquet")]
+pub fn parquet_schema() -> String {
+    let fields: String = PARQUET_COLUMNS
+        .iter()
+        .map(|(name, values)| {
+            let definition = match values {
+                ParquetValues::Timestamp(_) => "REQUIRED INT64 {} (TIMESTAMP(MILLIS,true))",
+                ParquetValues::Int64(_) => "REQUIRED INT64 {}",
+                ParquetValues::Double(_) => "REQUIRED DOUBLE {}",
+                ParquetValues::Text(_) => "REQUIRED BYTE_ARRAY {} (UTF8)",
+                ParquetValues::OptionalText(_) => "OPTIONAL BYTE_ARRAY {} (UTF8)",
+            };
+            format!("  {};\n", definition.replace("{}", name))
+        })
+        .collect();
+    format!("message candlesticks {{\n{}}}", fields)
+}
+
+/// Convertit une erreur Parquet en CandleError::IoError
+///
+/// Une erreur d'E/S de la sortie est restituée telle quelle (ex: BrokenPipe)
+#[cfg(feature = "parquet")]
+fn parquet_error(e: parquet::errors::ParquetError) -> CandleError {
+    match e {
+        parquet::errors::ParquetError::External(inner) => {
+            match inner.downcast::<std::io::Error>() {
+                Ok(io) => CandleError::IoError(*io),
+                Err(other) => CandleError::IoError(std::io::Error::other(other)),
+            }
+        }
+        other => CandleError::IoError(std::io::Error::other(other)),
+    }
+}
+
+/// Écrit une série au format Parquet (compression snappy) dans `output`
+///
+/// ALGORITHME:
+/// 1. Lecture SQLite ligne à ligne, accumulation de PARQUET_ROW_GROUP_ROWS bougies
+/// 2. Chaque lot devient un row group (colonnes écrites l'une après l'autre)
+/// 3. Pied de fichier (métadonnées) écrit à la fermeture
+///
+/// NOTE: une erreur d'écriture de `output` (client HTTP déconnecté) arrête
+/// la lecture et remonte en IoError
+///
+/// RETOUR: Nombre de bougies exportées
+#[cfg(feature = "parquet")]
+pub fn write_parquet<W: std::io::Write + Send>(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    range: RangeInclusive<i64>,
+    output: W,
+) -> Result<usize> {
+    let schema = Arc::new(parse_message_type(&parquet_schema()).map_err(parquet_error)?);
+    let properties = Arc::new(
+        WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build(),
+    );
+    let mut writer =
+        SerializedFileWriter::new(output, schema, properties).map_err(parquet_error)?;
+
+    let mut stmt = conn.prepare(&series_query(true))?;
+    let rows = stmt.query_map(
+        params![provider, symbol, timeframe, range.start(), range.end()],
+        CandleRecord::from_row,
+    )?;
+
+    let mut exported = 0;
+    let mut group = Vec::with_capacity(PARQUET_ROW_GROUP_ROWS);
+    for candle in rows {
+        group.push(candle?);
+        if group.len() == PARQUET_ROW_GROUP_ROWS {
+            write_row_group(&mut writer, &group).map_err(parquet_error)?;
+            exported += group.len();
+            group.clear();
+        }
+    }
+    if !group.is_empty() {
+        write_row_group(&mut writer, &group).map_err(parquet_error)?;
+        exported += group.len();
+    }
+
+    writer.close().map_err(parquet_error)?;
+    Ok(exported)
+}
+
+/// Écrit un row group: une colonne à la fois, dans l'ordre de PARQUET_COLUMNS
+#[cfg(feature = "parquet")]
+fn write_row_group<W: std::io::Write + Send>(
+    writer: &mut SerializedFileWriter<W>,
+    rows: &[CandleRecord],
+) -> parquet::errors::Result<()> {
+    let mut row_group = writer.next_row_group()?;
+
+    for (_, values) in PARQUET_COLUMNS {
+        let Some(mut column) = row_group.next_column()? else {
+            break;
+        };
+
+        match (column.untyped(), values) {
+            (ColumnWriter::Int64ColumnWriter(w), ParquetValues::Timestamp(get))
+            | (ColumnWriter::Int64ColumnWriter(w), ParquetValues::Int64(get)) => {
+                let batch: Vec<i64> = rows.iter().map(get).collect();
+                w.write_batch(&batch, None, None)?;
+            }
+            (ColumnWriter::DoubleColumnWriter(w), ParquetValues::Double(get)) => {
+                let batch: Vec<f64> = rows.iter().map(get).collect();
+                w.write_batch(&batch, None, None)?;
+            }
+            (ColumnWriter::ByteArrayColumnWriter(w), ParquetValues::Text(get)) => {
+                let batch: Vec<ByteArray> = rows.iter().map(|c| get(c).into()).collect();
+                w.write_batch(&batch, None, None)?;
+            }
+            (ColumnWriter::ByteArrayColumnWriter(w), ParquetValues::OptionalText(get)) => {
+                // Niveau de définition 0 = NULL (aucune valeur écrite)
+                let definition_levels: Vec<i16> =
+                    rows.iter().map(|c| i16::from(get(c).is_some())).collect();
+                let batch: Vec<ByteArray> = rows.iter().filter_map(get).map(Into::into).collect();
+                w.write_batch(&batch, Some(&definition_levels), None)?;
+            }
+            _ => {
+                return Err(parquet::errors::ParquetError::General(
+                    "Type de colonne Parquet inattendu".to_string(),
+                ));
+            }
+        }
+        column.close()?;
+    }
+
+    row_group.close()?;
+    Ok(())
+}
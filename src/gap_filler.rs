@@ -2,26 +2,132 @@
 ///
 /// Ce module détecte les gaps (intervalles manquants) et génère des bougies
 /// interpolées pour maintenir la continuité de la série temporelle
-use anyhow::Result;
+use crate::database::{insert_candle_records, query_candle_records};
+use crate::downtime::{DowntimeWindow, KnownDowntime};
+use crate::error::{CandleError, Result};
+use crate::metrics::METRICS;
+use crate::models::CandleRecord;
+use crate::timeframe_status::TimeframeStatus;
+use crate::utils::{aggregate_candles, parse_timeframe, timeframe_ordering};
 use rusqlite::{Connection, params};
+use serde::Serialize;
+use std::str::FromStr;
+use tracing::{debug, instrument};
 
-/// Structure pour stocker temporairement une bougie
+/// Stratégie de remplissage des gaps
 ///
-/// DESIGN: Struct simple sans méthodes, utilisée pour charger les données
-/// depuis la DB avant de calculer les interpolations
-#[derive(Debug)]
-struct Candle {
-    open_time: i64,
-    open: f64,
-    high: f64,
-    low: f64,
-    close: f64,
-    volume: f64,
-    close_time: i64,
-    quote_asset_volume: f64,
-    number_of_trades: i64,
-    taker_buy_base_asset_volume: f64,
-    taker_buy_quote_asset_volume: f64,
+/// Le marqueur de chaque stratégie est stocké dans la colonne `interpolated`
+/// (0 = donnée réelle), ce qui permet de savoir comment une bougie a été générée
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillStrategy {
+    /// Interpolation linéaire de tous les champs (marqueur 1)
+    #[default]
+    Linear,
+    /// Bougie plate au close précédent, volumes à zéro (marqueur 2)
+    ForwardFill,
+    /// Bougie plate au close interpolé linéairement, volumes à zéro (marqueur 3)
+    ZeroVolumeFlat,
+    /// Aucun remplissage, les gaps restent visibles
+    None,
+}
+
+impl FillStrategy {
+    /// Valeur stockée dans la colonne `interpolated`
+    pub fn marker(&self) -> i64 {
+        match self {
+            FillStrategy::Linear => 1,
+            FillStrategy::ForwardFill => 2,
+            FillStrategy::ZeroVolumeFlat => 3,
+            FillStrategy::None => 0,
+        }
+    }
+
+    /// Nom de la stratégie (forme canonique acceptée par from_str)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FillStrategy::Linear => "linear",
+            FillStrategy::ForwardFill => "ffill",
+            FillStrategy::ZeroVolumeFlat => "zero-volume",
+            FillStrategy::None => "none",
+        }
+    }
+}
+
+impl FromStr for FillStrategy {
+    type Err = CandleError;
+
+    /// Parse une stratégie depuis la configuration (ex: --fill-strategy ffill)
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "linear" => Ok(FillStrategy::Linear),
+            "ffill" | "forward-fill" => Ok(FillStrategy::ForwardFill),
+            "zero-volume" | "zero-volume-flat" => Ok(FillStrategy::ZeroVolumeFlat),
+            "none" => Ok(FillStrategy::None),
+            _ => Err(CandleError::ParseError(format!(
+                "Stratégie inconnue: {} (attendu: linear, ffill, zero-volume, none)",
+                s
+            ))),
+        }
+    }
+}
+
+/// Nombre de bougies chargées par fenêtre lors du comblement des gaps
+pub const FILL_CHUNK_SIZE: usize = 10_000;
+
+/// Position d'un gap par rapport aux bougies stockées
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GapKind {
+    /// Avant la première bougie stockée (début de couverture attendu non atteint)
+    Leading,
+    /// Entre deux bougies stockées
+    Between,
+    /// Après la dernière bougie stockée (ex: "les 3 derniers jours manquent")
+    Trailing,
+}
+
+/// Trou détecté dans une série de bougies
+///
+/// after_open_time / before_open_time: bornes exclusives du gap, alignées sur
+/// les bougies stockées. Pour un gap Leading (resp. Trailing), after_open_time
+/// (resp. before_open_time) est une position virtuelle sans bougie
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct GapRange {
+    pub after_open_time: i64,
+    pub before_open_time: i64,
+    pub missing_candles: i64,
+    pub kind: GapKind,
+}
+
+/// Trou d'un rapport de gaps (GapFiller::gap_report)
+///
+/// start_time / end_time: open_time (ms) de la première et de la dernière
+/// bougie manquante, bornes incluses (GapRange: bornes exclusives)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GapReportEntry {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub missing_count: i64,
+    pub timeframe: String,
+}
+
+/// Gaps d'une plage et couverture de la plage
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GapReport {
+    pub gaps: Vec<GapReportEntry>,
+    pub total_missing: i64,
+    /// Bougies stockées (réelles et interpolées) / bougies attendues dans
+    /// [start_time, end_time], en %. Plage sans bougie attendue: 100
+    pub coverage_pct: f64,
+}
+
+/// Résultat détaillé d'un comblement
+#[derive(Debug, Default)]
+pub struct FillOutcome {
+    /// Bougies générées (dérivées + synthétiques)
+    pub filled: i64,
+    /// Gaps laissés intacts car couverts par une maintenance connue
+    pub downtime_gaps: Vec<GapRange>,
 }
 
 /// Gestionnaire d'interpolation des gaps
@@ -34,18 +140,28 @@ impl GapFiller {
     /// Comble les gaps dans une plage de temps donnée
     ///
     /// ALGORITHME D'INTERPOLATION:
-    /// 1. Récupère toutes les bougies dans [start_time, end_time]
-    /// 2. Parcourt paire par paire (fenêtre glissante)
-    /// 3. Si intervalle > intervalle_attendu → GAP détecté
-    /// 4. Calcule nombre de bougies manquantes
-    /// 5. Pour chaque bougie manquante:
+    /// 1. Parcourt [start_time, end_time] par fenêtres de FILL_CHUNK_SIZE bougies
+    ///    (fetch_candle_window), la dernière bougie d'une fenêtre ouvrant la suivante
+    /// 2. Détecte les gaps via detect_gaps (fenêtre glissante):
+    ///    si intervalle > intervalle_attendu → GAP de (diff / intervalle) - 1 bougies
+    /// 3. Pour chaque bougie manquante:
     ///    - Calcule ratio de position: i / (n+1)
-    ///    - Interpole linéairement tous les champs
-    /// 6. Insère avec INSERT OR IGNORE
+    ///    - Génère la bougie selon la stratégie choisie
+    /// 4. Insère avec INSERT OR IGNORE, marqueur de stratégie dans `interpolated`
+    ///
+    /// PRIORITÉ: chaque gap est d'abord comblé par agrégation d'un timeframe
+    /// inférieur (fill_from_lower_timeframe), la stratégie n'est qu'un repli
+    ///
+    /// PERFORMANCE: une transaction par fenêtre, mémoire bornée et verrou
+    /// d'écriture relâché entre deux fenêtres (le serveur web reste réactif)
     ///
-    /// FORMULE: valeur = A + (B-A) × ratio
+    /// FORMULE (Linear): valeur = A + (B-A) × ratio
     ///
-    /// RETOUR: Nombre de bougies interpolées
+    /// MAINTENANCES: un gap entièrement couvert par une fenêtre known_downtime
+    /// n'est pas interpolé (voir fill_gaps_in_range_detailed)
+    ///
+    /// RETOUR: Nombre de bougies générées (dérivées + synthétiques)
+    #[instrument(skip(conn))]
     pub fn fill_gaps_in_range(
         conn: &mut Connection,
         provider: &str,
@@ -53,75 +169,468 @@ impl GapFiller {
         timeframe: &str,
         start_time: i64,
         end_time: i64,
+        strategy: FillStrategy,
     ) -> Result<i64> {
-        let interval = Self::timeframe_to_interval(timeframe);
+        let outcome = Self::fill_gaps_in_range_detailed(
+            conn, provider, symbol, timeframe, start_time, end_time, strategy,
+        )?;
+        Ok(outcome.filled)
+    }
+
+    /// Comme fill_gaps_in_range, en rapportant aussi les gaps de maintenance ignorés
+    pub fn fill_gaps_in_range_detailed(
+        conn: &mut Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        start_time: i64,
+        end_time: i64,
+        strategy: FillStrategy,
+    ) -> Result<FillOutcome> {
+        let interval = parse_timeframe(timeframe)?;
+        let downtime = KnownDowntime::list(conn)?;
+        let mut outcome = FillOutcome::default();
+        let mut window_start = start_time;
+
+        loop {
+            let candles = Self::fetch_candle_window(
+                conn,
+                provider,
+                symbol,
+                timeframe,
+                window_start,
+                end_time,
+                FILL_CHUNK_SIZE,
+            )?;
+
+            let gaps = Self::detect_gaps(&candles, interval);
+            if !gaps.is_empty() {
+                let tx = conn.transaction()?;
+                Self::fill_window(
+                    &tx,
+                    provider,
+                    symbol,
+                    timeframe,
+                    &candles,
+                    &gaps,
+                    strategy,
+                    interval,
+                    &downtime,
+                    &mut outcome,
+                )?;
+                tx.commit()?;
+            }
+
+            // Fenêtre incomplète: fin de la plage atteinte
+            if candles.len() < FILL_CHUNK_SIZE {
+                break;
+            }
 
-        // Récupérer toutes les bougies existantes dans la plage
-        let candles =
-            Self::fetch_candles_in_range(conn, provider, symbol, timeframe, start_time, end_time)?;
+            // Chevauchement d'une bougie pour détecter un gap à cheval sur deux fenêtres
+            window_start = candles[candles.len() - 1].open_time;
+        }
 
-        if candles.len() < 2 {
-            return Ok(0);
+        if outcome.filled > 0 || !outcome.downtime_gaps.is_empty() {
+            debug!(
+                filled = outcome.filled,
+                downtime_gaps = outcome.downtime_gaps.len(),
+                "Gaps comblés"
+            );
         }
 
-        let mut total_filled = 0i64;
-        let tx = conn.transaction()?;
+        METRICS
+            .gap_fill_candles_total
+            .with_label_values(&[symbol, timeframe])
+            .inc_by(outcome.filled as u64);
 
-        {
-            let mut insert_stmt = tx.prepare(
-                "INSERT OR IGNORE INTO candlesticks (
-                    provider, symbol, timeframe, open_time, open, high, low, close, volume,
-                    close_time, quote_asset_volume, number_of_trades,
-                    taker_buy_base_asset_volume, taker_buy_quote_asset_volume, interpolated
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
-            )?;
+        Ok(outcome)
+    }
+
+    /// Comble les gaps d'une fenêtre de bougies dans la transaction courante
+    #[allow(clippy::too_many_arguments)]
+    fn fill_window(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        candles: &[CandleRecord],
+        gaps: &[(usize, GapRange)],
+        strategy: FillStrategy,
+        interval: i64,
+        downtime: &[DowntimeWindow],
+        outcome: &mut FillOutcome,
+    ) -> Result<()> {
+        for (index, gap) in gaps {
+            // Données réelles d'un timeframe inférieur en priorité
+            let derived = Self::fill_from_lower_timeframe(conn, provider, symbol, timeframe, gap)?;
+            if derived > 0 {
+                outcome.filled += derived;
+                continue;
+            }
+
+            // Maintenance de l'exchange: gap attendu, rien à inventer
+            if downtime
+                .iter()
+                .any(|w| w.covers_gap(gap.after_open_time, gap.before_open_time, interval))
+            {
+                outcome.downtime_gaps.push(*gap);
+                continue;
+            }
 
-            // Fenêtre glissante: parcourir paires de bougies consécutives
-            for i in 0..candles.len() - 1 {
-                let current = &candles[i];
-                let next = &candles[i + 1];
+            if strategy == FillStrategy::None {
+                continue;
+            }
 
-                let time_diff = next.open_time - current.open_time;
+            let current = &candles[*index];
+            let next = &candles[*index + 1];
+            let missing_candles = gap.missing_candles;
 
-                if time_diff > interval {
-                    let missing_candles = (time_diff / interval) - 1;
-
-                    // Interpoler chaque bougie manquante
-                    for j in 1..=missing_candles {
-                        let ratio = j as f64 / (missing_candles + 1) as f64;
-                        let interpolated = Self::interpolate_candle(current, next, ratio, interval);
-
-                        insert_stmt.execute(params![
-                            provider,
-                            symbol,
-                            timeframe,
-                            interpolated.open_time,
-                            interpolated.open,
-                            interpolated.high,
-                            interpolated.low,
-                            interpolated.close,
-                            interpolated.volume,
-                            interpolated.close_time,
-                            interpolated.quote_asset_volume,
-                            interpolated.number_of_trades,
-                            interpolated.taker_buy_base_asset_volume,
-                            interpolated.taker_buy_quote_asset_volume,
-                            1, // interpolated = 1 (données synthétiques)
-                        ])?;
-
-                        total_filled += 1;
+            // Générer chaque bougie manquante
+            let synthetic: Vec<CandleRecord> = (1..=missing_candles)
+                .map(|j| {
+                    let ratio = j as f64 / (missing_candles + 1) as f64;
+                    let candle = match strategy {
+                        FillStrategy::ForwardFill => {
+                            Self::forward_fill_candle(current, next, ratio, interval)
+                        }
+                        FillStrategy::ZeroVolumeFlat => {
+                            Self::zero_volume_flat_candle(current, next, ratio, interval)
+                        }
+                        _ => Self::interpolate_candle(current, next, ratio, interval),
+                    };
+                    CandleRecord {
+                        interpolated: strategy.marker(), // > 0 = données synthétiques
+                        ..candle
                     }
+                })
+                .collect();
+
+            insert_candle_records(conn, &synthetic)?;
+            outcome.filled += synthetic.len() as i64;
+        }
+
+        Ok(())
+    }
+
+    /// Supprime les bougies synthétiques (interpolated != 0) d'une plage
+    ///
+    /// IMPORTANT: les insertions réelles utilisent INSERT OR IGNORE, une bougie
+    /// synthétique bloque donc la donnée réelle de même open_time. Purger avant
+    /// une récupération de réparation permet aux vraies données d'être stockées
+    ///
+    /// Les bornes de timeframe_status sont recalées (TimeframeStatus::refresh_bounds)
+    ///
+    /// RETOUR: Nombre de lignes supprimées
+    pub fn purge_interpolated(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<usize> {
+        let deleted = conn.execute(
+            "DELETE FROM candlesticks
+             WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+                   AND open_time >= ?4 AND open_time <= ?5
+                   AND interpolated != 0",
+            params![provider, symbol, timeframe, start_time, end_time],
+        )?;
+        if deleted > 0 {
+            TimeframeStatus::refresh_bounds(conn, provider, symbol, timeframe)?;
+        }
+
+        Ok(deleted)
+    }
+
+    /// Purge les bougies synthétiques d'une plage puis recomble les gaps
+    ///
+    /// Les gaps rouverts passent par fill_gaps_in_range: agrégation d'un timeframe
+    /// inférieur en priorité, puis la stratégie choisie en repli
+    ///
+    /// RETOUR: (lignes purgées, bougies regénérées)
+    pub fn refill(
+        conn: &mut Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        start_time: i64,
+        end_time: i64,
+        strategy: FillStrategy,
+    ) -> Result<(usize, i64)> {
+        let purged =
+            Self::purge_interpolated(conn, provider, symbol, timeframe, start_time, end_time)?;
+        let filled = Self::fill_gaps_in_range(
+            conn, provider, symbol, timeframe, start_time, end_time, strategy,
+        )?;
+
+        Ok((purged, filled))
+    }
+
+    /// Liste les gaps dans une plage de temps donnée
+    ///
+    /// ALGORITHME: même fenêtre glissante que fill_gaps_in_range (detect_gaps)
+    ///
+    /// RETOUR: Vec<GapRange> trié par open_time croissant
+    pub fn list_gaps_in_range(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<Vec<GapRange>> {
+        let interval = parse_timeframe(timeframe)?;
+        let candles = Self::fetch_candles_in_range(
+            conn, provider, symbol, timeframe, start_time, end_time, false,
+        )?;
+
+        Ok(Self::detect_gaps(&candles, interval)
+            .into_iter()
+            .map(|(_, gap)| gap)
+            .collect())
+    }
+
+    /// Rapport des gaps de [start_time, end_time]: trous entre bougies
+    /// stockées et bougies manquantes avant la première / après la dernière
+    /// (list_gaps_with_bounds), total manquant et couverture
+    ///
+    /// Bougies attendues: celles de la grille des bougies stockées comprises
+    /// dans [start_time, end_time] (bornes non alignées acceptées)
+    ///
+    /// EXEMPLE: bougies stockées jusqu'à J-3, end_time = J → gap Trailing des
+    /// trois derniers jours; plage sans aucune bougie → un seul gap couvrant
+    /// toute la plage, couverture 0
+    pub fn gap_report(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<GapReport> {
+        let interval = parse_timeframe(timeframe)?;
+        let gaps: Vec<GapReportEntry> =
+            Self::list_gaps_with_bounds(conn, provider, symbol, timeframe, start_time, end_time)?
+                .into_iter()
+                .map(|gap| GapReportEntry {
+                    start_time: gap.after_open_time + interval,
+                    end_time: gap.before_open_time - interval,
+                    missing_count: gap.missing_candles,
+                    timeframe: timeframe.to_string(),
+                })
+                .collect();
+        let total_missing: i64 = gaps.iter().map(|gap| gap.missing_count).sum();
+        let expected = if end_time < start_time {
+            0
+        } else {
+            (end_time - start_time) / interval + 1
+        };
+        let coverage_pct = if expected == 0 {
+            100.0
+        } else {
+            (expected - total_missing) as f64 * 100.0 / expected as f64
+        };
+
+        Ok(GapReport {
+            gaps,
+            total_missing,
+            coverage_pct,
+        })
+    }
+
+    /// Liste les gaps par rapport à des bornes de couverture attendues
+    ///
+    /// Contrairement à list_gaps_in_range (trous entre bougies stockées), les
+    /// bougies manquantes avant la première / après la dernière bougie stockée
+    /// sont rapportées comme gaps Leading / Trailing
+    ///
+    /// PARAMÈTRES:
+    /// - expected_start: open_time de la première bougie attendue
+    /// - expected_end: open_time de la dernière bougie attendue
+    ///   (voir TimeframeStatus::expected_bounds)
+    ///
+    /// CAS PARTICULIER: aucune bougie stockée → un seul gap Leading couvrant tout
+    pub fn list_gaps_with_bounds(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        expected_start: i64,
+        expected_end: i64,
+    ) -> Result<Vec<GapRange>> {
+        if expected_end < expected_start {
+            return Ok(Vec::new());
+        }
+
+        let interval = parse_timeframe(timeframe)?;
+        let candles = Self::fetch_candles_in_range(
+            conn,
+            provider,
+            symbol,
+            timeframe,
+            expected_start,
+            expected_end,
+            false,
+        )?;
+
+        let (Some(first), Some(last)) = (candles.first(), candles.last()) else {
+            let missing_candles = (expected_end - expected_start) / interval + 1;
+            return Ok(vec![GapRange {
+                after_open_time: expected_start - interval,
+                before_open_time: expected_start + missing_candles * interval,
+                missing_candles,
+                kind: GapKind::Leading,
+            }]);
+        };
+
+        let mut gaps = Vec::new();
+
+        // Division entière: seules les périodes complètes comptent
+        let leading = (first.open_time - expected_start) / interval;
+        if leading > 0 {
+            gaps.push(GapRange {
+                after_open_time: first.open_time - (leading + 1) * interval,
+                before_open_time: first.open_time,
+                missing_candles: leading,
+                kind: GapKind::Leading,
+            });
+        }
+
+        gaps.extend(
+            Self::detect_gaps(&candles, interval)
+                .into_iter()
+                .map(|(_, gap)| gap),
+        );
+
+        let trailing = (expected_end - last.open_time) / interval;
+        if trailing > 0 {
+            gaps.push(GapRange {
+                after_open_time: last.open_time,
+                before_open_time: last.open_time + (trailing + 1) * interval,
+                missing_candles: trailing,
+                kind: GapKind::Trailing,
+            });
+        }
+
+        Ok(gaps)
+    }
+
+    /// Compte les gaps dans une plage de temps donnée
+    pub fn count_gaps_in_range(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<i64> {
+        let gaps =
+            Self::list_gaps_in_range(conn, provider, symbol, timeframe, start_time, end_time)?;
+        Ok(gaps.len() as i64)
+    }
+
+    /// Détecte les gaps entre bougies consécutives (fenêtre glissante)
+    ///
+    /// RETOUR: (index de la bougie avant le gap, GapRange)
+    /// L'index permet à fill_gaps_in_range de retrouver les bougies encadrantes
+    fn detect_gaps(candles: &[CandleRecord], interval: i64) -> Vec<(usize, GapRange)> {
+        candles
+            .windows(2)
+            .enumerate()
+            .filter_map(|(i, pair)| {
+                let time_diff = pair[1].open_time - pair[0].open_time;
+                if time_diff > interval {
+                    Some((
+                        i,
+                        GapRange {
+                            after_open_time: pair[0].open_time,
+                            before_open_time: pair[1].open_time,
+                            missing_candles: (time_diff / interval) - 1,
+                            kind: GapKind::Between,
+                        },
+                    ))
+                } else {
+                    None
                 }
+            })
+            .collect()
+    }
+
+    /// Comble un gap en agrégeant les bougies réelles d'un timeframe inférieur
+    ///
+    /// ALGORITHME:
+    /// 1. Parcourt les timeframes inférieurs diviseurs du timeframe cible,
+    ///    du plus grand au plus petit (ex: 1h → 30m, 15m, 5m, 1m)
+    /// 2. Retient le premier dont les bougies réelles couvrent TOUT le gap
+    ///    (intervalle_cible / intervalle_source bougies par période manquante)
+    /// 3. Agrège chaque période: premier open, max high, min low, dernier close,
+    ///    volumes et nombre de trades sommés
+    /// 4. Insère avec interpolated = 0 et derived_from = timeframe source
+    ///
+    /// RETOUR: Nombre de bougies dérivées (0 si aucun timeframe ne couvre le gap)
+    pub fn fill_from_lower_timeframe(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        target_tf: &str,
+        gap_range: &GapRange,
+    ) -> Result<i64> {
+        let target_interval = parse_timeframe(target_tf)?;
+
+        // Du plus grand au plus petit: le moins de bougies source à agréger
+        for source_tf in timeframe_ordering().into_iter().rev() {
+            let source_interval = parse_timeframe(source_tf)?;
+            if source_interval >= target_interval || target_interval % source_interval != 0 {
+                continue;
+            }
+
+            let per_period = (target_interval / source_interval) as usize;
+            let source_candles = Self::fetch_candles_in_range(
+                conn,
+                provider,
+                symbol,
+                source_tf,
+                gap_range.after_open_time + target_interval,
+                gap_range.before_open_time - 1,
+                true,
+            )?;
+
+            // Regrouper par période cible manquante
+            let mut periods: Vec<Vec<&CandleRecord>> =
+                vec![Vec::new(); gap_range.missing_candles as usize];
+            for candle in &source_candles {
+                let k = (candle.open_time - gap_range.after_open_time) / target_interval - 1;
+                if let Some(period) = periods.get_mut(k as usize) {
+                    period.push(candle);
+                }
+            }
+
+            // Couverture complète exigée, sinon essayer un timeframe plus petit
+            if periods.is_empty() || periods.iter().any(|p| p.len() != per_period) {
+                continue;
             }
+
+            let aggregated: Vec<CandleRecord> = periods
+                .iter()
+                .enumerate()
+                .map(|(k, period)| {
+                    let open_time = gap_range.after_open_time + (k as i64 + 1) * target_interval;
+                    aggregate_candles(period, target_tf, open_time, target_interval)
+                })
+                .collect();
+
+            return Ok(insert_candle_records(conn, &aggregated)? as i64);
         }
 
-        tx.commit()?;
-        Ok(total_filled)
+        Ok(0)
     }
 
     /// Récupère les bougies dans une plage de temps
     ///
-    /// SUBTILITÉ RUST: Retourne un Vec<Candle>
+    /// real_only: exclut les bougies synthétiques (interpolated > 0)
+    ///
+    /// SUBTILITÉ RUST: Retourne un Vec<CandleRecord>
     /// Le Vec est alloué sur le heap et ownership est transféré à l'appelant
     fn fetch_candles_in_range(
         conn: &Connection,
@@ -130,61 +639,76 @@ impl GapFiller {
         timeframe: &str,
         start_time: i64,
         end_time: i64,
-    ) -> Result<Vec<Candle>> {
-        let mut stmt = conn.prepare(
-            "SELECT open_time, open, high, low, close, volume, close_time,
-                    quote_asset_volume, number_of_trades,
-                    taker_buy_base_asset_volume, taker_buy_quote_asset_volume
-             FROM candlesticks
-             WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
-                   AND open_time >= ?4 AND open_time <= ?5
-             ORDER BY open_time ASC",
+        real_only: bool,
+    ) -> Result<Vec<CandleRecord>> {
+        let mut candles = query_candle_records(
+            conn,
+            provider,
+            symbol,
+            timeframe,
+            start_time..=end_time,
+            None,
         )?;
-
-        let candles = stmt
-            .query_map(
-                params![provider, symbol, timeframe, start_time, end_time],
-                |row| {
-                    Ok(Candle {
-                        open_time: row.get(0)?,
-                        open: row.get(1)?,
-                        high: row.get(2)?,
-                        low: row.get(3)?,
-                        close: row.get(4)?,
-                        volume: row.get(5)?,
-                        close_time: row.get(6)?,
-                        quote_asset_volume: row.get(7)?,
-                        number_of_trades: row.get(8)?,
-                        taker_buy_base_asset_volume: row.get(9)?,
-                        taker_buy_quote_asset_volume: row.get(10)?,
-                    })
-                },
-            )?
-            .collect::<Result<Vec<_>, _>>()?;
-
+        if real_only {
+            candles.retain(|c| !c.is_synthetic());
+        }
         Ok(candles)
     }
 
+    /// Récupère au plus `limit` bougies à partir de start_time (inclus)
+    ///
+    /// DESIGN: LIMIT sur l'index (provider, symbol, timeframe, open_time),
+    /// la mémoire reste bornée quelle que soit la taille de la plage
+    fn fetch_candle_window(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        start_time: i64,
+        end_time: i64,
+        limit: usize,
+    ) -> Result<Vec<CandleRecord>> {
+        query_candle_records(
+            conn,
+            provider,
+            symbol,
+            timeframe,
+            start_time..=end_time,
+            Some(limit),
+        )
+    }
+
     /// Interpole une bougie entre deux bougies existantes
     ///
     /// ALGORITHME: Interpolation linéaire
     /// Pour chaque champ: valeur = A + (B-A) × ratio
+    /// puis high/low élargis à open et close: exact en théorie (combinaison
+    /// convexe de bougies valides), l'arrondi flottant peut sinon laisser
+    /// open ou close dépasser d'un ulp
     ///
     /// PARAMÈTRES:
     /// - current: bougie avant le gap
     /// - next: bougie après le gap
     /// - ratio: position relative (0.0 à 1.0)
     /// - interval: intervalle du timeframe en ms
-    fn interpolate_candle(current: &Candle, next: &Candle, ratio: f64, interval: i64) -> Candle {
-        let open_time =
-            current.open_time + ((next.open_time - current.open_time) as f64 * ratio) as i64;
+    fn interpolate_candle(
+        current: &CandleRecord,
+        next: &CandleRecord,
+        ratio: f64,
+        interval: i64,
+    ) -> CandleRecord {
+        let open_time = Self::gap_open_time(current, next, ratio);
+        let open = current.open + (next.open - current.open) * ratio;
+        let close = current.close + (next.close - current.close) * ratio;
+        let high = current.high + (next.high - current.high) * ratio;
+        let low = current.low + (next.low - current.low) * ratio;
 
-        Candle {
+        CandleRecord {
             open_time,
-            open: current.open + (next.open - current.open) * ratio,
-            high: current.high + (next.high - current.high) * ratio,
-            low: current.low + (next.low - current.low) * ratio,
-            close: current.close + (next.close - current.close) * ratio,
+            open,
+            high: high.max(open).max(close),
+            low: low.min(open).min(close),
+            close,
             volume: current.volume + (next.volume - current.volume) * ratio,
             close_time: open_time + interval - 1,
             quote_asset_volume: current.quote_asset_volume
@@ -197,30 +721,75 @@ impl GapFiller {
             taker_buy_quote_asset_volume: current.taker_buy_quote_asset_volume
                 + (next.taker_buy_quote_asset_volume - current.taker_buy_quote_asset_volume)
                     * ratio,
+            derived_from: None,
+            ..current.clone()
         }
     }
 
-    /// Convertit un timeframe en intervalle en millisecondes
-    ///
-    /// DESIGN: Fonction helper pour éviter la duplication de code
-    fn timeframe_to_interval(timeframe: &str) -> i64 {
-        match timeframe {
-            "1m" => 60_000,
-            "3m" => 180_000,
-            "5m" => 300_000,
-            "15m" => 900_000,
-            "30m" => 1_800_000,
-            "1h" => 3_600_000,
-            "2h" => 7_200_000,
-            "4h" => 14_400_000,
-            "6h" => 21_600_000,
-            "8h" => 28_800_000,
-            "12h" => 43_200_000,
-            "1d" => 86_400_000,
-            "3d" => 259_200_000,
-            "1w" => 604_800_000,
-            "1M" => 2_592_000_000,
-            _ => 300_000, // Par défaut: 5m
+    /// Génère une bougie plate au close de la bougie précédente
+    ///
+    /// DESIGN: Aucun mouvement de prix ni volume inventé, le marché est
+    /// considéré figé pendant le gap (évite de biaiser RSI & co)
+    fn forward_fill_candle(
+        current: &CandleRecord,
+        next: &CandleRecord,
+        ratio: f64,
+        interval: i64,
+    ) -> CandleRecord {
+        let open_time = Self::gap_open_time(current, next, ratio);
+
+        CandleRecord {
+            open_time,
+            open: current.close,
+            high: current.close,
+            low: current.close,
+            close: current.close,
+            volume: 0.0,
+            close_time: open_time + interval - 1,
+            quote_asset_volume: 0.0,
+            number_of_trades: 0,
+            taker_buy_base_asset_volume: 0.0,
+            taker_buy_quote_asset_volume: 0.0,
+            derived_from: None,
+            ..current.clone()
         }
     }
+
+    /// Génère une bougie plate au close interpolé linéairement, sans volume
+    ///
+    /// DESIGN: Conserve la trajectoire de prix de l'interpolation linéaire
+    /// mais sans mèches ni volume fabriqués
+    fn zero_volume_flat_candle(
+        current: &CandleRecord,
+        next: &CandleRecord,
+        ratio: f64,
+        interval: i64,
+    ) -> CandleRecord {
+        let open_time = Self::gap_open_time(current, next, ratio);
+        let price = current.close + (next.close - current.close) * ratio;
+
+        CandleRecord {
+            open_time,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: 0.0,
+            close_time: open_time + interval - 1,
+            quote_asset_volume: 0.0,
+            number_of_trades: 0,
+            taker_buy_base_asset_volume: 0.0,
+            taker_buy_quote_asset_volume: 0.0,
+            derived_from: None,
+            ..current.clone()
+        }
+    }
+
+    /// Calcule l'open_time de la bougie manquante à la position ratio
+    ///
+    /// IMPORTANT: round() et non troncature, sinon j/(n+1) × diff peut donner
+    /// 59999.99… et décaler la bougie d'1 ms (gap fantôme au prochain scan)
+    fn gap_open_time(current: &CandleRecord, next: &CandleRecord, ratio: f64) -> i64 {
+        current.open_time + ((next.open_time - current.open_time) as f64 * ratio).round() as i64
+    }
 }
@@ -0,0 +1,498 @@
+/// Module d'import CSV des bougies
+///
+/// Charge un historique exporté ailleurs (TradingView, CryptoCompare, autre
+/// exchange...) sans passer par l'API Binance
+///
+/// FORMAT ACCEPTÉ:
+/// - colonnes timestamp_ms,open,high,low,close,volume (colonnes en plus ignorées)
+/// - séparateur virgule ou point-virgule, détecté sur la première ligne
+/// - en-tête facultatif: s'il est présent, les colonnes sont repérées par leur
+///   nom (un export Standard de export.rs se ré-importe tel quel)
+/// - avec le point-virgule, la virgule décimale est acceptée ("42000,5")
+///
+/// - noms de colonne personnalisés via ColumnMapping (ex: "timestamp=Date")
+///
+/// VALIDATION de chaque ligne: open_time aligné sur la grille du timeframe,
+/// timestamps strictement croissants (les trous sont tolérés, voir fill_gaps),
+/// high >= low, volume >= 0
+///
+/// DEUX MODES:
+/// - import_csv (endpoint web): tout ou rien, la première ligne invalide
+///   annule l'import
+/// - import_csv_lenient (binaire import_csv): lignes invalides écartées et
+///   rapportées avec leur numéro, insertion par transactions de IMPORT_CHUNK_ROWS
+use crate::database::insert_candle_records;
+use crate::error::{CandleError, Result};
+use crate::models::CandleRecord;
+use crate::utils::{parse_timeframe, period_start};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Provider des bougies importées (par défaut)
+pub const IMPORT_PROVIDER: &str = "import";
+
+/// Bougies insérées par transaction (import tolérant)
+pub const IMPORT_CHUNK_ROWS: usize = 5_000;
+
+/// Noms de colonne reconnus dans un en-tête (comparés sans la casse)
+const TIMESTAMP_NAMES: &[&str] = &["timestamp_ms", "open_time_ms", "open_time", "timestamp"];
+
+/// Colonnes canoniques, dans l'ordre du format positionnel
+const CANONICAL_COLUMNS: [&str; 6] = ["timestamp", "open", "high", "low", "close", "volume"];
+
+/// Résultat d'un import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ImportSummary {
+    /// Lignes valides lues dans le fichier
+    pub parsed: usize,
+    /// Bougies réellement insérées (les doublons sont ignorés)
+    pub inserted: usize,
+}
+
+/// Ligne écartée par l'import tolérant
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InvalidRow {
+    /// Numéro de ligne dans le fichier (1 = première ligne)
+    pub line: usize,
+    pub reason: String,
+}
+
+/// Résultat d'un import tolérant
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportReport {
+    pub summary: ImportSummary,
+    /// Lignes écartées, dans l'ordre du fichier
+    pub invalid: Vec<InvalidRow>,
+    /// open_time de la première et de la dernière bougie valide
+    pub range: Option<(i64, i64)>,
+}
+
+impl ImportReport {
+    /// Bougies valides déjà présentes en base (INSERT OR IGNORE)
+    pub fn skipped(&self) -> usize {
+        self.summary.parsed - self.summary.inserted
+    }
+}
+
+/// Noms des colonnes du fichier, par colonne canonique
+///
+/// FORMAT: "timestamp=Date,open=Open,volume=Vol BTC" (une colonne absente
+/// garde ses noms par défaut). Nécessite un en-tête dans le fichier
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnMapping {
+    names: HashMap<String, String>,
+}
+
+impl ColumnMapping {
+    /// Nom de colonne choisi pour une colonne canonique
+    fn get(&self, canonical: &str) -> Option<&str> {
+        self.names.get(canonical).map(String::as_str)
+    }
+
+    /// true si aucune colonne n'est renommée
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+impl FromStr for ColumnMapping {
+    type Err = CandleError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut names = HashMap::new();
+        for pair in s.split(',').filter(|pair| !pair.trim().is_empty()) {
+            let Some((canonical, name)) = pair.split_once('=') else {
+                return Err(CandleError::ParseError(format!(
+                    "Correspondance invalide {:?} (attendu: colonne=nom)",
+                    pair
+                )));
+            };
+            let canonical = canonical.trim().to_lowercase();
+            if !CANONICAL_COLUMNS.contains(&canonical.as_str()) {
+                return Err(CandleError::ParseError(format!(
+                    "Colonne inconnue: {} (attendu: {})",
+                    canonical,
+                    CANONICAL_COLUMNS.join(", ")
+                )));
+            }
+            names.insert(canonical, name.trim().to_string());
+        }
+        Ok(ColumnMapping { names })
+    }
+}
+
+/// Position des colonnes utiles dans une ligne
+struct Columns {
+    timestamp: usize,
+    open: usize,
+    high: usize,
+    low: usize,
+    close: usize,
+    volume: usize,
+}
+
+impl Columns {
+    /// Sans en-tête: timestamp_ms,open,high,low,close,volume
+    const POSITIONAL: Columns = Columns {
+        timestamp: 0,
+        open: 1,
+        high: 2,
+        low: 3,
+        close: 4,
+        volume: 5,
+    };
+
+    /// Repère les colonnes par leur nom dans l'en-tête (noms de `mapping`
+    /// en priorité)
+    fn from_header(fields: &[&str], mapping: &ColumnMapping) -> Result<Self> {
+        let find = |canonical: &str, defaults: &[&str]| {
+            let mapped = mapping.get(canonical).map(|name| [name]);
+            let names = mapped.as_ref().map_or(defaults, |name| &name[..]);
+            fields
+                .iter()
+                .position(|field| names.iter().any(|name| field.eq_ignore_ascii_case(name)))
+                .ok_or_else(|| {
+                    CandleError::ValidationError(format!(
+                        "Colonne {} absente de l'en-tête",
+                        names[0]
+                    ))
+                })
+        };
+
+        Ok(Columns {
+            timestamp: find("timestamp", TIMESTAMP_NAMES)?,
+            open: find("open", &["open"])?,
+            high: find("high", &["high"])?,
+            low: find("low", &["low"])?,
+            close: find("close", &["close"])?,
+            volume: find("volume", &["volume"])?,
+        })
+    }
+}
+
+/// Séparateur du fichier: point-virgule s'il apparaît sur la première ligne
+///
+/// NOTE: un décompte virgules/points-virgules ne suffit pas, la virgule
+/// décimale des exports européens fausserait la comparaison
+pub fn detect_delimiter(content: &str) -> char {
+    let first_line = content.lines().find(|line| !line.trim().is_empty());
+    if first_line.is_some_and(|line| line.contains(';')) {
+        ';'
+    } else {
+        ','
+    }
+}
+
+/// Découpe une ligne en champs (espaces et guillemets retirés)
+fn split_fields(line: &str, delimiter: char) -> Vec<&str> {
+    line.split(delimiter)
+        .map(|field| field.trim().trim_matches('"'))
+        .collect()
+}
+
+/// Erreur d'une ligne, sans son numéro (ajouté par l'appelant)
+enum RowError {
+    /// Champ manquant ou illisible
+    Parse(String),
+    /// Valeurs lisibles mais incohérentes
+    Invalid(String),
+}
+
+impl RowError {
+    fn message(&self) -> &str {
+        match self {
+            RowError::Parse(message) | RowError::Invalid(message) => message,
+        }
+    }
+
+    /// Erreur de l'import tout ou rien
+    fn at_line(self, line_number: usize) -> CandleError {
+        match self {
+            RowError::Parse(message) => {
+                CandleError::ParseError(format!("Ligne {}: {}", line_number, message))
+            }
+            RowError::Invalid(message) => {
+                CandleError::ValidationError(format!("Ligne {}: {}", line_number, message))
+            }
+        }
+    }
+}
+
+/// Lecteur des lignes d'un fichier CSV pour une série
+struct CsvReader<'a> {
+    provider: &'a str,
+    symbol: &'a str,
+    timeframe: &'a str,
+    interval: i64,
+    delimiter: char,
+    columns: Columns,
+}
+
+impl<'a> CsvReader<'a> {
+    /// Prépare la lecture: intervalle, séparateur et colonnes
+    ///
+    /// ALGORITHME:
+    /// 1. Intervalle du timeframe (timeframe inconnu → erreur)
+    /// 2. Séparateur détecté, en-tête repéré si le premier champ n'est pas un entier
+    ///
+    /// RETOUR: le lecteur et les lignes de données (numéro 1-based, contenu)
+    fn open(
+        content: &'a str,
+        provider: &'a str,
+        symbol: &'a str,
+        timeframe: &'a str,
+        mapping: &ColumnMapping,
+    ) -> Result<(Self, Vec<(usize, &'a str)>)> {
+        let interval = parse_timeframe(timeframe)?;
+        let delimiter = detect_delimiter(content);
+
+        let mut lines: Vec<(usize, &str)> = content
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line))
+            .filter(|(_, line)| !line.trim().is_empty())
+            .collect();
+
+        let has_header = lines
+            .first()
+            .is_some_and(|&(_, line)| split_fields(line, delimiter)[0].parse::<i64>().is_err());
+        let columns = if has_header {
+            let (_, header) = lines.remove(0);
+            Columns::from_header(&split_fields(header, delimiter), mapping)?
+        } else if !mapping.is_empty() {
+            return Err(CandleError::ValidationError(
+                "Correspondance de colonnes sans en-tête dans le fichier".to_string(),
+            ));
+        } else {
+            Columns::POSITIONAL
+        };
+
+        let reader = CsvReader {
+            provider,
+            symbol,
+            timeframe,
+            interval,
+            delimiter,
+            columns,
+        };
+        Ok((reader, lines))
+    }
+
+    /// Parse et valide une ligne
+    ///
+    /// `previous`: open_time de la dernière bougie valide (ordre du fichier)
+    fn parse_row(
+        &self,
+        line: &str,
+        previous: Option<i64>,
+    ) -> std::result::Result<CandleRecord, RowError> {
+        let fields = split_fields(line, self.delimiter);
+        let field = |index: usize| {
+            fields.get(index).copied().ok_or_else(|| {
+                RowError::Parse(format!(
+                    "{} colonne(s), colonne {} attendue",
+                    fields.len(),
+                    index + 1
+                ))
+            })
+        };
+        let number = |index: usize| -> std::result::Result<f64, RowError> {
+            let raw = field(index)?;
+            // Avec le point-virgule, la virgule décimale est acceptée
+            let normalized = if self.delimiter == ';' {
+                raw.replace(',', ".")
+            } else {
+                raw.to_string()
+            };
+            normalized
+                .parse()
+                .map_err(|_| RowError::Parse(format!("nombre invalide {:?}", raw)))
+        };
+
+        let raw_timestamp = field(self.columns.timestamp)?;
+        let open_time: i64 = raw_timestamp
+            .parse()
+            .map_err(|_| RowError::Parse(format!("timestamp invalide {:?}", raw_timestamp)))?;
+
+        // 1M: mois de durée variable, pas de grille fixe
+        if self.timeframe != "1M" && period_start(open_time, self.interval) != open_time {
+            return Err(RowError::Invalid(format!(
+                "open_time {} non aligné sur la grille du timeframe {}",
+                open_time, self.timeframe
+            )));
+        }
+        if let Some(previous) = previous {
+            let diff = open_time - previous;
+            if diff <= 0 {
+                return Err(RowError::Invalid(format!(
+                    "timestamp {} non strictement croissant (précédent: {})",
+                    open_time, previous
+                )));
+            }
+            if diff % self.interval != 0 {
+                return Err(RowError::Invalid(format!(
+                    "écart de {} ms incompatible avec le timeframe {} ({} ms)",
+                    diff, self.timeframe, self.interval
+                )));
+            }
+        }
+
+        let (high, low, volume) = (
+            number(self.columns.high)?,
+            number(self.columns.low)?,
+            number(self.columns.volume)?,
+        );
+        if high < low {
+            return Err(RowError::Invalid(format!("high {} < low {}", high, low)));
+        }
+        if volume < 0.0 {
+            return Err(RowError::Invalid(format!("volume négatif {}", volume)));
+        }
+
+        Ok(CandleRecord {
+            provider: self.provider.to_string(),
+            symbol: self.symbol.to_string(),
+            timeframe: self.timeframe.to_string(),
+            open_time,
+            open: number(self.columns.open)?,
+            high,
+            low,
+            close: number(self.columns.close)?,
+            volume,
+            close_time: open_time + self.interval - 1,
+            // Colonnes absentes des exports courants
+            quote_asset_volume: 0.0,
+            number_of_trades: 0,
+            taker_buy_base_asset_volume: 0.0,
+            taker_buy_quote_asset_volume: 0.0,
+            interpolated: 0,
+            derived_from: None,
+        })
+    }
+}
+
+/// Parse les lignes d'un CSV en bougies réelles (interpolated = 0)
+///
+/// ERREURS: ValidationError ou ParseError avec le numéro de la première
+/// ligne fautive (tout ou rien)
+pub fn parse_csv(
+    content: &str,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+) -> Result<Vec<CandleRecord>> {
+    let (reader, lines) = CsvReader::open(
+        content,
+        provider,
+        symbol,
+        timeframe,
+        &ColumnMapping::default(),
+    )?;
+
+    let mut records: Vec<CandleRecord> = Vec::with_capacity(lines.len());
+    for (line_number, line) in lines {
+        let previous = records.last().map(|r| r.open_time);
+        let record = reader
+            .parse_row(line, previous)
+            .map_err(|e| e.at_line(line_number))?;
+        records.push(record);
+    }
+
+    if records.is_empty() {
+        return Err(CandleError::ValidationError(
+            "Aucune bougie dans le fichier".to_string(),
+        ));
+    }
+
+    Ok(records)
+}
+
+/// Comme parse_csv, mais les lignes invalides sont écartées et rapportées
+///
+/// ERREURS: uniquement pour le fichier entier (timeframe inconnu, en-tête
+/// incomplet); un fichier sans ligne valide n'est pas une erreur
+pub fn parse_csv_lenient(
+    content: &str,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    mapping: &ColumnMapping,
+) -> Result<(Vec<CandleRecord>, Vec<InvalidRow>)> {
+    let (reader, lines) = CsvReader::open(content, provider, symbol, timeframe, mapping)?;
+
+    let mut records: Vec<CandleRecord> = Vec::with_capacity(lines.len());
+    let mut invalid = Vec::new();
+    for (line_number, line) in lines {
+        let previous = records.last().map(|r| r.open_time);
+        match reader.parse_row(line, previous) {
+            Ok(record) => records.push(record),
+            Err(e) => invalid.push(InvalidRow {
+                line: line_number,
+                reason: e.message().to_string(),
+            }),
+        }
+    }
+
+    Ok((records, invalid))
+}
+
+/// Parse puis insère un CSV dans une transaction unique
+///
+/// DESIGN: tout ou rien, une ligne invalide n'insère aucune bougie
+pub fn import_csv(
+    conn: &mut Connection,
+    content: &str,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+) -> Result<ImportSummary> {
+    let records = parse_csv(content, provider, symbol, timeframe)?;
+
+    let tx = conn.transaction()?;
+    let inserted = insert_candle_records(&tx, &records)?;
+    tx.commit()?;
+
+    Ok(ImportSummary {
+        parsed: records.len(),
+        inserted,
+    })
+}
+
+/// Import tolérant: lignes invalides rapportées, bougies valides insérées
+/// par transactions de IMPORT_CHUNK_ROWS (INSERT OR IGNORE)
+///
+/// DESIGN: une transaction par bloc plutôt qu'une seule: un import de
+/// plusieurs millions de lignes ne bloque pas les écrivains concurrents
+/// pendant toute sa durée (les blocs déjà validés restent en base en cas
+/// d'erreur, un nouvel import les ignorera)
+pub fn import_csv_lenient(
+    conn: &mut Connection,
+    content: &str,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    mapping: &ColumnMapping,
+) -> Result<ImportReport> {
+    let (records, invalid) = parse_csv_lenient(content, provider, symbol, timeframe, mapping)?;
+
+    let mut inserted = 0;
+    for chunk in records.chunks(IMPORT_CHUNK_ROWS) {
+        let tx = conn.transaction()?;
+        inserted += insert_candle_records(&tx, chunk)?;
+        tx.commit()?;
+    }
+
+    Ok(ImportReport {
+        summary: ImportSummary {
+            parsed: records.len(),
+            inserted,
+        },
+        invalid,
+        range: records
+            .first()
+            .zip(records.last())
+            .map(|(first, last)| (first.open_time, last.open_time)),
+    })
+}
@@ -0,0 +1,207 @@
+/// Module de l'Average Directional Index (ADX, +DI, -DI)
+///
+/// ALGORITHME (Wilder), à partir de la bougie i et de la précédente:
+/// - TR = max(high - low, |high - close préc.|, |low - close préc.|)
+/// - +DM = high - high préc. si supérieur à low préc. - low et positif,
+///   sinon 0; -DM symétrique
+/// - TR, +DM, -DM lissés sur `period` bougies: somme des period premières
+///   valeurs, puis S = S - S / period + valeur
+/// - +DI = 100 × +DM lissé / TR lissé, -DI idem
+/// - DX = 100 × |+DI - -DI| / (+DI + -DI)
+/// - ADX = moyenne des period premiers DX, puis
+///   ADX = (ADX préc. × (period - 1) + DX) / period
+///
+/// Contrairement au RSI, le calcul a besoin du plus haut, du plus bas et de
+/// la clôture de chaque bougie
+///
+/// SUBTILITÉ: comme l'OBV, le lissage de Wilder dépend de toute la série
+/// qui précède. Le calcul part toujours de la plus ancienne bougie stockée,
+/// même pour une plage restreinte: les valeurs d'une plage ne changent pas
+/// selon la plage demandée
+use crate::error::Result;
+use rusqlite::{Connection, params};
+use serde::Serialize;
+use std::ops::RangeInclusive;
+
+/// ADX d'une bougie (None: période de chauffe)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct AdxPoint {
+    pub open_time: i64,
+    pub adx: Option<f64>,
+    pub plus_di: Option<f64>,
+    pub minus_di: Option<f64>,
+}
+
+/// ADX, +DI et -DI de chaque bougie d'une série triée
+///
+/// +DI / -DI: None pour les `period` premières bougies; ADX: None pour les
+/// 2 × period - 1 premières. Période nulle: toujours None. Longueurs
+/// différentes: la plus courte
+///
+/// SUBTILITÉ: TR lissé nul (prix figés) donne +DI = -DI = 0 et DX = 0,
+/// absence de tendance, plutôt qu'une division par zéro
+pub fn calculate_adx(
+    open_times: &[i64],
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    period: usize,
+) -> Vec<AdxPoint> {
+    let len = open_times
+        .len()
+        .min(highs.len())
+        .min(lows.len())
+        .min(closes.len());
+    let mut points: Vec<AdxPoint> = open_times[..len]
+        .iter()
+        .map(|&open_time| AdxPoint {
+            open_time,
+            adx: None,
+            plus_di: None,
+            minus_di: None,
+        })
+        .collect();
+    if period == 0 {
+        return points;
+    }
+
+    let weight = period as f64;
+    let (mut tr_sum, mut plus_sum, mut minus_sum) = (0.0, 0.0, 0.0);
+    let mut dx_sum = 0.0;
+    let mut adx: Option<f64> = None;
+
+    for i in 1..len {
+        let tr = (highs[i] - lows[i])
+            .max((highs[i] - closes[i - 1]).abs())
+            .max((lows[i] - closes[i - 1]).abs());
+        let up = highs[i] - highs[i - 1];
+        let down = lows[i - 1] - lows[i];
+        let plus_dm = if up > down && up > 0.0 { up } else { 0.0 };
+        let minus_dm = if down > up && down > 0.0 { down } else { 0.0 };
+
+        if i <= period {
+            tr_sum += tr;
+            plus_sum += plus_dm;
+            minus_sum += minus_dm;
+            if i < period {
+                continue;
+            }
+        } else {
+            tr_sum += tr - tr_sum / weight;
+            plus_sum += plus_dm - plus_sum / weight;
+            minus_sum += minus_dm - minus_sum / weight;
+        }
+
+        let (plus_di, minus_di) = if tr_sum > 0.0 {
+            (100.0 * plus_sum / tr_sum, 100.0 * minus_sum / tr_sum)
+        } else {
+            (0.0, 0.0)
+        };
+        let di_sum = plus_di + minus_di;
+        let dx = if di_sum > 0.0 {
+            100.0 * (plus_di - minus_di).abs() / di_sum
+        } else {
+            0.0
+        };
+
+        // Premier DX en i = period, premier ADX en i = 2 × period - 1
+        adx = match adx {
+            Some(previous) => Some((previous * (weight - 1.0) + dx) / weight),
+            None => {
+                dx_sum += dx;
+                (i + 1 == 2 * period).then(|| dx_sum / weight)
+            }
+        };
+
+        points[i].plus_di = Some(plus_di);
+        points[i].minus_di = Some(minus_di);
+        points[i].adx = adx;
+    }
+
+    points
+}
+
+/// ADX des bougies de `range` (open_time en ms), calculé depuis la première
+/// bougie de la série, bougies sans ADX omises
+pub fn adx_series(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    range: RangeInclusive<i64>,
+    period: usize,
+) -> Result<Vec<AdxPoint>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT open_time, high, low, close FROM candlesticks
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3 AND open_time <= ?4
+         ORDER BY open_time ASC",
+    )?;
+    let candles = stmt
+        .query_map(params![provider, symbol, timeframe, range.end()], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, f64>(1)?,
+                row.get::<_, f64>(2)?,
+                row.get::<_, f64>(3)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let open_times: Vec<i64> = candles.iter().map(|c| c.0).collect();
+    let highs: Vec<f64> = candles.iter().map(|c| c.1).collect();
+    let lows: Vec<f64> = candles.iter().map(|c| c.2).collect();
+    let closes: Vec<f64> = candles.iter().map(|c| c.3).collect();
+
+    Ok(calculate_adx(&open_times, &highs, &lows, &closes, period)
+        .into_iter()
+        .filter(|point| point.adx.is_some() && range.contains(&point.open_time))
+        .collect())
+}
+
+/// Calcule et enregistre l'ADX des bougies de [start_time, end_time] dans
+/// adx_values (valeurs existantes des mêmes périodes remplacées), en une
+/// transaction
+///
+/// RETOUR: Nombre de valeurs enregistrées
+pub fn store_adx(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    period: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<i64> {
+    let points = adx_series(
+        conn,
+        provider,
+        symbol,
+        timeframe,
+        start_time..=end_time,
+        period,
+    )?;
+
+    let tx = conn.transaction()?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT OR REPLACE INTO adx_values
+                 (provider, symbol, timeframe, period, open_time, adx, plus_di, minus_di)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )?;
+        for point in &points {
+            stmt.execute(params![
+                provider,
+                symbol,
+                timeframe,
+                period as i64,
+                point.open_time,
+                point.adx,
+                point.plus_di,
+                point.minus_di
+            ])?;
+        }
+    }
+    tx.commit()?;
+
+    Ok(points.len() as i64)
+}
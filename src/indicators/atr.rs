@@ -0,0 +1,155 @@
+/// Module de l'ATR (Average True Range de Wilder)
+///
+/// ALGORITHME, à partir de la bougie i et de la précédente:
+/// - TR = max(high - low, |high - close préc.|, |low - close préc.|): un
+///   gap d'ouverture compte dans l'amplitude
+/// - ATR = moyenne des `period` premiers TR, puis
+///   ATR = (ATR préc. × (period - 1) + TR) / period
+///
+/// Comme l'ADX, le calcul a besoin du plus haut, du plus bas et de la
+/// clôture: Indicator::compute reçoit les bougies complètes
+///
+/// SUBTILITÉ: une bougie interpolée (gap comblé) a une amplitude
+/// artificielle, nulle en ffill, et écrase l'ATR. Avec
+/// exclude_interpolated, l'ATR est calculé sur les seules bougies réelles
+/// (TR d'une bougie réelle à la précédente réelle), les bougies
+/// interpolées n'ont pas de valeur
+///
+/// Le lissage de Wilder dépend de toute la série, mais le poids de
+/// l'amorce décroît en (1 - 1/period)^n: ATR_WARMUP_FACTOR × period
+/// bougies lues avant la plage suffisent (poids sous 1e-11 pour 14)
+use crate::error::{CandleError, Result};
+use crate::indicators::values::{Indicator, recalculate_indicator_for_range};
+use crate::models::CandleRecord;
+use rusqlite::Connection;
+
+/// Période usuelle de l'ATR
+pub const DEFAULT_ATR_PERIOD: usize = 14;
+/// Bougies de chauffe lues avant la plage, par bougie de période
+pub const ATR_WARMUP_FACTOR: usize = 25;
+
+/// True Range de chaque bougie d'une série triée
+///
+/// None pour la première bougie (pas de close précédent). Longueurs
+/// différentes: la plus courte
+pub fn calculate_true_range(highs: &[f64], lows: &[f64], closes: &[f64]) -> Vec<Option<f64>> {
+    let len = highs.len().min(lows.len()).min(closes.len());
+    (0..len)
+        .map(|i| {
+            (i > 0).then(|| {
+                (highs[i] - lows[i])
+                    .max((highs[i] - closes[i - 1]).abs())
+                    .max((lows[i] - closes[i - 1]).abs())
+            })
+        })
+        .collect()
+}
+
+/// ATR de chaque bougie d'une série triée
+///
+/// None pour les `period` premières bougies (il faut period TR), période
+/// nulle: toujours None
+pub fn calculate_atr(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    period: usize,
+) -> Vec<Option<f64>> {
+    let true_ranges = calculate_true_range(highs, lows, closes);
+    let mut values = vec![None; true_ranges.len()];
+    if period == 0 || true_ranges.len() <= period {
+        return values;
+    }
+
+    let weight = period as f64;
+    let mut atr = true_ranges[1..=period].iter().flatten().sum::<f64>() / weight;
+    values[period] = Some(atr);
+    for (i, tr) in true_ranges.iter().enumerate().skip(period + 1) {
+        atr = (atr * (weight - 1.0) + tr.unwrap_or(0.0)) / weight;
+        values[i] = Some(atr);
+    }
+    values
+}
+
+/// ATR comme Indicator
+pub struct Atr {
+    period: usize,
+    exclude_interpolated: bool,
+}
+
+impl Atr {
+    /// ERREURS: ValidationError si period = 0
+    pub fn new(period: usize, exclude_interpolated: bool) -> Result<Self> {
+        if period == 0 {
+            return Err(CandleError::ValidationError(
+                "Période invalide (minimum 1)".to_string(),
+            ));
+        }
+        Ok(Atr {
+            period,
+            exclude_interpolated,
+        })
+    }
+}
+
+impl Default for Atr {
+    /// ATR(14), bougies interpolées comprises
+    fn default() -> Self {
+        Atr {
+            period: DEFAULT_ATR_PERIOD,
+            exclude_interpolated: false,
+        }
+    }
+}
+
+impl Indicator for Atr {
+    fn name(&self) -> &str {
+        "atr"
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::json!({
+            "period": self.period,
+            "exclude_interpolated": self.exclude_interpolated
+        })
+    }
+
+    fn compute(&self, candles: &[CandleRecord]) -> Vec<Option<f64>> {
+        // Indices des bougies retenues (toutes, ou les seules réelles)
+        let kept: Vec<usize> = (0..candles.len())
+            .filter(|&i| !self.exclude_interpolated || candles[i].interpolated == 0)
+            .collect();
+        let highs: Vec<f64> = kept.iter().map(|&i| candles[i].high).collect();
+        let lows: Vec<f64> = kept.iter().map(|&i| candles[i].low).collect();
+        let closes: Vec<f64> = kept.iter().map(|&i| candles[i].close).collect();
+
+        let mut values = vec![None; candles.len()];
+        for (i, atr) in kept
+            .into_iter()
+            .zip(calculate_atr(&highs, &lows, &closes, self.period))
+        {
+            values[i] = atr;
+        }
+        values
+    }
+
+    fn warmup(&self) -> Option<usize> {
+        Some(ATR_WARMUP_FACTOR * self.period)
+    }
+}
+
+/// Calcule et enregistre l'ATR des bougies de [start_time, end_time] dans
+/// indicator_values, avec les bougies de chauffe qui précèdent la plage
+///
+/// RETOUR: Nombre de valeurs enregistrées
+pub fn recalculate_atr_for_range(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    atr: &Atr,
+    start_time: i64,
+    end_time: i64,
+) -> Result<i64> {
+    recalculate_indicator_for_range(conn, provider, symbol, timeframe, atr, start_time, end_time)
+}
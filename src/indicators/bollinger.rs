@@ -0,0 +1,151 @@
+/// Module des bandes de Bollinger
+///
+/// ALGORITHME (fenêtre des `period` closes qui finissent en i):
+/// - middle = moyenne de la fenêtre (SMA)
+/// - upper / lower = middle ± k × écart-type de population (n) de la
+///   fenêtre
+///
+/// Indicateur à trois composantes (middle, upper, lower), une ligne par
+/// composante dans indicator_values, params = {"k": k, "period": n}
+///
+/// SUBTILITÉ: moyenne et écart-type recalculés en deux passes sur chaque
+/// fenêtre plutôt que par sommes glissantes (somme des carrés - carré de
+/// la somme): sur des prix élevés et peu dispersés, la soustraction de deux
+/// grands nombres voisins perdrait toute la précision. Des prix figés
+/// donnent un écart-type nul: les trois bandes se confondent
+///
+/// PERFORMANCE: O(n × period), négligeable pour les périodes usuelles
+use crate::error::{CandleError, Result};
+use crate::indicators::values::{Indicator, recalculate_indicator_for_range};
+use crate::models::CandleRecord;
+use rusqlite::Connection;
+use serde::Serialize;
+
+/// Période usuelle des bandes
+pub const DEFAULT_BOLLINGER_PERIOD: usize = 20;
+/// Multiplicateur usuel de l'écart-type
+pub const DEFAULT_BOLLINGER_K: f64 = 2.0;
+
+/// Bandes d'une bougie (None: période de chauffe)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct BollingerPoint {
+    pub middle: Option<f64>,
+    pub upper: Option<f64>,
+    pub lower: Option<f64>,
+}
+
+/// Bandes de chaque close d'une série triée
+///
+/// None pour les period - 1 premiers closes (série plus courte que la
+/// période: que des None, période nulle: idem)
+///
+/// EXEMPLE: closes [1, 2, 3], period 3, k 2 → middle 2, écart-type
+/// sqrt(2/3), upper 2 + 2 × sqrt(2/3)
+pub fn calculate_bollinger(closes: &[f64], period: usize, k: f64) -> Vec<BollingerPoint> {
+    (0..closes.len())
+        .map(|i| {
+            if period == 0 || i + 1 < period {
+                return BollingerPoint {
+                    middle: None,
+                    upper: None,
+                    lower: None,
+                };
+            }
+            let window = &closes[i + 1 - period..=i];
+            let mean = window.iter().sum::<f64>() / period as f64;
+            let variance = window
+                .iter()
+                .map(|close| (close - mean).powi(2))
+                .sum::<f64>()
+                / period as f64;
+            let width = k * variance.sqrt();
+            BollingerPoint {
+                middle: Some(mean),
+                upper: Some(mean + width),
+                lower: Some(mean - width),
+            }
+        })
+        .collect()
+}
+
+/// Bandes de Bollinger comme Indicator (composantes middle, upper, lower)
+pub struct Bollinger {
+    period: usize,
+    k: f64,
+}
+
+impl Bollinger {
+    /// ERREURS: ValidationError si period = 0 ou k n'est pas un réel
+    /// positif ou nul
+    pub fn new(period: usize, k: f64) -> Result<Self> {
+        if period == 0 || !k.is_finite() || k < 0.0 {
+            return Err(CandleError::ValidationError(format!(
+                "Paramètres de Bollinger invalides: période {}, k {} (période >= 1, k >= 0)",
+                period, k
+            )));
+        }
+        Ok(Bollinger { period, k })
+    }
+}
+
+impl Default for Bollinger {
+    /// Bandes (20, 2)
+    fn default() -> Self {
+        Bollinger {
+            period: DEFAULT_BOLLINGER_PERIOD,
+            k: DEFAULT_BOLLINGER_K,
+        }
+    }
+}
+
+impl Indicator for Bollinger {
+    fn name(&self) -> &str {
+        "bollinger"
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::json!({ "period": self.period, "k": self.k })
+    }
+
+    fn compute(&self, candles: &[CandleRecord]) -> Vec<Option<f64>> {
+        self.compute_components(candles).swap_remove(0)
+    }
+
+    fn components(&self) -> Vec<&'static str> {
+        vec!["middle", "upper", "lower"]
+    }
+
+    fn compute_components(&self, candles: &[CandleRecord]) -> Vec<Vec<Option<f64>>> {
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let points = calculate_bollinger(&closes, self.period, self.k);
+        vec![
+            points.iter().map(|p| p.middle).collect(),
+            points.iter().map(|p| p.upper).collect(),
+            points.iter().map(|p| p.lower).collect(),
+        ]
+    }
+
+    /// Fenêtre fixe: les period - 1 bougies qui précèdent la plage suffisent
+    fn warmup(&self) -> Option<usize> {
+        Some(self.period - 1)
+    }
+}
+
+/// Calcule et enregistre les trois bandes des bougies de [start_time,
+/// end_time] dans indicator_values, avec les period - 1 bougies qui
+/// précèdent la plage
+///
+/// RETOUR: Nombre de valeurs enregistrées (toutes composantes)
+pub fn recalculate_bollinger_for_range(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    bollinger: &Bollinger,
+    start_time: i64,
+    end_time: i64,
+) -> Result<i64> {
+    recalculate_indicator_for_range(
+        conn, provider, symbol, timeframe, bollinger, start_time, end_time,
+    )
+}
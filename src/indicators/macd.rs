@@ -0,0 +1,148 @@
+/// Module du MACD (Moving Average Convergence Divergence)
+///
+/// ALGORITHME (moving_average::ema):
+/// - MACD = EMA(close, fast) - EMA(close, slow)
+/// - signal = EMA(MACD, signal), amorcée sur les `signal` premières
+///   valeurs du MACD
+/// - histogramme = MACD - signal
+///
+/// Indicateur à trois composantes (macd, signal, histogram), une ligne par
+/// composante dans indicator_values
+///
+/// SUBTILITÉ: les EMA dépendent de toute la série qui précède, mais le
+/// poids de l'amorce décroît en (1 - k)^n. MACD_WARMUP_FACTOR × (slow +
+/// signal) bougies lues avant la plage suffisent: le poids de l'amorce y
+/// tombe sous 1e-11 pour 12/26/9, et un recalcul incrémental ne relit pas
+/// toute la série
+use crate::error::{CandleError, Result};
+use crate::indicators::moving_average::ema;
+use crate::indicators::values::{Indicator, recalculate_indicator_for_range};
+use crate::models::CandleRecord;
+use rusqlite::Connection;
+use serde::Serialize;
+
+/// Période usuelle de l'EMA rapide
+pub const DEFAULT_MACD_FAST: usize = 12;
+/// Période usuelle de l'EMA lente
+pub const DEFAULT_MACD_SLOW: usize = 26;
+/// Période usuelle de la ligne de signal
+pub const DEFAULT_MACD_SIGNAL: usize = 9;
+/// Bougies de chauffe lues avant la plage, par bougie de slow + signal
+pub const MACD_WARMUP_FACTOR: usize = 10;
+
+/// MACD d'une bougie (None: période de chauffe)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct MacdPoint {
+    pub macd: Option<f64>,
+    pub signal: Option<f64>,
+    pub histogram: Option<f64>,
+}
+
+/// MACD, signal et histogramme de chaque close d'une série triée
+///
+/// MACD: None pour les slow - 1 premiers closes (fast < slow); signal et
+/// histogramme: None pour les slow + signal - 2 premiers
+pub fn calculate_macd(closes: &[f64], fast: usize, slow: usize, signal: usize) -> Vec<MacdPoint> {
+    let macd: Vec<Option<f64>> = ema(closes, fast)
+        .into_iter()
+        .zip(ema(closes, slow))
+        .map(|(fast, slow)| Some(fast? - slow?))
+        .collect();
+
+    // Signal: EMA des valeurs du MACD, à partir de la première disponible
+    let first = macd.iter().position(Option::is_some).unwrap_or(macd.len());
+    let defined: Vec<f64> = macd[first..].iter().flatten().copied().collect();
+    let mut signal_line = vec![None; first];
+    signal_line.extend(ema(&defined, signal));
+
+    macd.into_iter()
+        .zip(signal_line)
+        .map(|(macd, signal)| MacdPoint {
+            macd,
+            signal,
+            histogram: macd.zip(signal).map(|(macd, signal)| macd - signal),
+        })
+        .collect()
+}
+
+/// MACD comme Indicator (composantes macd, signal, histogram)
+pub struct Macd {
+    fast: usize,
+    slow: usize,
+    signal: usize,
+}
+
+impl Macd {
+    /// ERREURS: ValidationError sauf si 1 <= fast < slow et signal >= 1
+    pub fn new(fast: usize, slow: usize, signal: usize) -> Result<Self> {
+        if fast == 0 || fast >= slow || signal == 0 {
+            return Err(CandleError::ValidationError(format!(
+                "Périodes MACD invalides: rapide {}, lente {}, signal {} (1 <= rapide < lente, signal >= 1)",
+                fast, slow, signal
+            )));
+        }
+        Ok(Macd { fast, slow, signal })
+    }
+}
+
+impl Default for Macd {
+    /// MACD(12, 26, 9)
+    fn default() -> Self {
+        Macd {
+            fast: DEFAULT_MACD_FAST,
+            slow: DEFAULT_MACD_SLOW,
+            signal: DEFAULT_MACD_SIGNAL,
+        }
+    }
+}
+
+impl Indicator for Macd {
+    fn name(&self) -> &str {
+        "macd"
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::json!({ "fast": self.fast, "slow": self.slow, "signal": self.signal })
+    }
+
+    fn compute(&self, candles: &[CandleRecord]) -> Vec<Option<f64>> {
+        self.compute_components(candles).swap_remove(0)
+    }
+
+    fn components(&self) -> Vec<&'static str> {
+        vec!["macd", "signal", "histogram"]
+    }
+
+    fn compute_components(&self, candles: &[CandleRecord]) -> Vec<Vec<Option<f64>>> {
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let points = calculate_macd(&closes, self.fast, self.slow, self.signal);
+        vec![
+            points.iter().map(|p| p.macd).collect(),
+            points.iter().map(|p| p.signal).collect(),
+            points.iter().map(|p| p.histogram).collect(),
+        ]
+    }
+
+    fn warmup(&self) -> Option<usize> {
+        Some(MACD_WARMUP_FACTOR * (self.slow + self.signal))
+    }
+}
+
+/// Calcule et enregistre les trois composantes du MACD des bougies de
+/// [start_time, end_time] dans indicator_values, avec les bougies de
+/// chauffe qui précèdent la plage
+///
+/// RETOUR: Nombre de valeurs enregistrées (toutes composantes)
+pub fn recalculate_macd_for_range(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    macd: &Macd,
+    start_time: i64,
+    end_time: i64,
+) -> Result<i64> {
+    recalculate_indicator_for_range(
+        conn, provider, symbol, timeframe, macd, start_time, end_time,
+    )
+}
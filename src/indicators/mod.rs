@@ -0,0 +1,45 @@
+/// Module des indicateurs techniques calculés à partir des bougies stockées
+///
+/// - adx: Average Directional Index, +DI / -DI (table adx_values)
+/// - atr: Average True Range de Wilder (indicator_values,
+///   /api/indicators/atr)
+/// - bollinger: bandes de Bollinger (indicator_values, trois composantes,
+///   /api/indicators/bollinger)
+/// - macd: MACD, signal et histogramme (indicator_values, trois
+///   composantes, /api/indicators/macd)
+/// - moving_average: moyennes mobiles simple et exponentielle
+///   (indicator_values, /api/indicators/sma et /api/indicators/ema)
+/// - obv: On-Balance Volume (indicator_values, /api/indicators/obv),
+///   recalcul repris depuis la dernière valeur enregistrée
+/// - parabolic_sar: Parabolic SAR (table sar_values, /api/indicators/sar)
+/// - pivot_points: points pivots classiques par période (table
+///   pivot_points, /api/indicators/pivot-points)
+/// - rsi: Relative Strength Index (indicator_values, /api/indicators/rsi),
+///   calcul incrémental RsiState pour le temps réel
+/// - registry: indicateurs recalculés après chaque batch du backfill et
+///   chaque lot des écrivains temps réel (IndicatorRegistry)
+/// - rolling: moyenne, écart-type, minimum et maximum glissants
+///   (/api/indicators/rolling, calculés à la volée)
+/// - stochastic: oscillateur stochastique %K / %D (indicator_values,
+///   /api/indicators/stochastic)
+/// - volatility: volatilité historique et de Parkinson (table
+///   historical_volatility, /api/indicators/volatility)
+/// - vwap: VWAP ancré par session, minuit UTC par défaut (indicator_values,
+///   /api/indicators/vwap)
+/// - values: trait Indicator et table générique indicator_values (une
+///   valeur par bougie, paramètres en JSON)
+pub mod adx;
+pub mod atr;
+pub mod bollinger;
+pub mod macd;
+pub mod moving_average;
+pub mod obv;
+pub mod parabolic_sar;
+pub mod pivot_points;
+pub mod registry;
+pub mod rolling;
+pub mod rsi;
+pub mod stochastic;
+pub mod values;
+pub mod volatility;
+pub mod vwap;
@@ -0,0 +1,147 @@
+/// Module des moyennes mobiles simple (SMA) et exponentielle (EMA)
+///
+/// ALGORITHME:
+/// - SMA[i] = moyenne des `period` closes qui finissent en i
+///   (rolling::rolling_mean)
+/// - EMA: amorcée par la SMA des `period` premiers closes, puis
+///   EMA[i] = EMA[i-1] + k × (close[i] - EMA[i-1]), k = 2 / (period + 1)
+///
+/// Sma et Ema implémentent Indicator: leurs valeurs sont enregistrées dans
+/// indicator_values (indicator = 'sma' / 'ema', params = {"period": n})
+///
+/// SUBTILITÉ: l'EMA dépend de toute la série depuis l'amorce, mais le poids
+/// de l'amorce décroît en (1 - k)^n: EMA_WARMUP_FACTOR × period bougies
+/// lues avant la plage suffisent (poids sous 1e-12 pour 20), un recalcul
+/// incrémental ne relit pas toute la série. La SMA n'a besoin que des
+/// period - 1 bougies qui précèdent
+use crate::error::Result;
+use crate::indicators::rolling::rolling_mean;
+use crate::indicators::values::{Indicator, recalculate_indicator_for_range};
+use crate::models::CandleRecord;
+use rusqlite::Connection;
+
+/// Bougies de chauffe de l'EMA lues avant la plage, par bougie de période
+pub const EMA_WARMUP_FACTOR: usize = 15;
+
+/// Moyenne mobile simple: None pour les period - 1 premières valeurs
+/// (série plus courte que la période: que des None, période nulle: idem)
+pub fn sma(closes: &[f64], period: usize) -> Vec<Option<f64>> {
+    rolling_mean(closes, period)
+}
+
+/// Moyenne mobile exponentielle: None pour les period - 1 premières
+/// valeurs, SMA des period premières en period - 1
+///
+/// EXEMPLE: closes [1, 2, 3, 4, 5], period 3 → [None, None, 2, 3, 4]
+/// (k = 0.5: 2 + 0.5 × (4 - 2) = 3, puis 3 + 0.5 × (5 - 3) = 4)
+pub fn ema(closes: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut values = vec![None; closes.len()];
+    if period == 0 || closes.len() < period {
+        return values;
+    }
+
+    let k = 2.0 / (period as f64 + 1.0);
+    let mut current = closes[..period].iter().sum::<f64>() / period as f64;
+    values[period - 1] = Some(current);
+    for (i, close) in closes.iter().enumerate().skip(period) {
+        current += k * (close - current);
+        values[i] = Some(current);
+    }
+    values
+}
+
+/// Moyenne mobile simple du close comme Indicator
+pub struct Sma {
+    pub period: usize,
+}
+
+impl Indicator for Sma {
+    fn name(&self) -> &str {
+        "sma"
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::json!({ "period": self.period })
+    }
+
+    fn compute(&self, candles: &[CandleRecord]) -> Vec<Option<f64>> {
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        sma(&closes, self.period)
+    }
+
+    fn warmup(&self) -> Option<usize> {
+        Some(self.period.saturating_sub(1))
+    }
+}
+
+/// Moyenne mobile exponentielle du close comme Indicator
+pub struct Ema {
+    pub period: usize,
+}
+
+impl Indicator for Ema {
+    fn name(&self) -> &str {
+        "ema"
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::json!({ "period": self.period })
+    }
+
+    fn compute(&self, candles: &[CandleRecord]) -> Vec<Option<f64>> {
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        ema(&closes, self.period)
+    }
+
+    fn warmup(&self) -> Option<usize> {
+        Some(EMA_WARMUP_FACTOR * self.period)
+    }
+}
+
+/// Calcule et enregistre la SMA des bougies de [start_time, end_time] dans
+/// indicator_values
+///
+/// RETOUR: Nombre de valeurs enregistrées
+pub fn recalculate_sma_for_range(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    period: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<i64> {
+    recalculate_indicator_for_range(
+        conn,
+        provider,
+        symbol,
+        timeframe,
+        &Sma { period },
+        start_time,
+        end_time,
+    )
+}
+
+/// Calcule et enregistre l'EMA des bougies de [start_time, end_time] dans
+/// indicator_values
+///
+/// RETOUR: Nombre de valeurs enregistrées
+pub fn recalculate_ema_for_range(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    period: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<i64> {
+    recalculate_indicator_for_range(
+        conn,
+        provider,
+        symbol,
+        timeframe,
+        &Ema { period },
+        start_time,
+        end_time,
+    )
+}
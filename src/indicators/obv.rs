@@ -0,0 +1,238 @@
+/// Module de l'On-Balance Volume (OBV)
+///
+/// ALGORITHME: volume cumulé, signé par le sens du close
+/// - close[i] > close[i-1]: OBV[i] = OBV[i-1] + volume[i]
+/// - close[i] < close[i-1]: OBV[i] = OBV[i-1] - volume[i]
+/// - close[i] = close[i-1]: OBV[i] = OBV[i-1]
+///
+/// SUBTILITÉ: OBV est cumulatif, sa valeur dépend de la première bougie
+/// prise en compte. Le calcul part toujours de la plus ancienne bougie
+/// stockée de la série, même pour une plage restreinte: les valeurs d'une
+/// plage ne changent pas selon la plage demandée. recalculate_obv_for_range
+/// évite de tout relire en reprenant la dernière valeur enregistrée avant
+/// la plage
+///
+/// Obv est la première implémentation de Indicator: ses valeurs sont
+/// enregistrées dans indicator_values (indicator = 'obv', params = '{}')
+use crate::database::query_candle_records;
+use crate::error::Result;
+use crate::indicators::values::{
+    ComponentSeries, Indicator, IndicatorValue, VALUE_COMPONENT, recalculate_indicator_for_range,
+    store_component_series,
+};
+use crate::models::CandleRecord;
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::Serialize;
+use std::ops::RangeInclusive;
+
+/// Valeur OBV d'une bougie
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ObvValue {
+    pub open_time: i64,
+    pub obv: f64,
+}
+
+/// On-Balance Volume comme Indicator (sans paramètre)
+pub struct Obv;
+
+impl Indicator for Obv {
+    fn name(&self) -> &str {
+        "obv"
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::json!({})
+    }
+
+    fn compute(&self, candles: &[CandleRecord]) -> Vec<Option<f64>> {
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let volumes: Vec<f64> = candles.iter().map(|c| c.volume).collect();
+        calculate_obv(&closes, &volumes)
+            .into_iter()
+            .map(Some)
+            .collect()
+    }
+
+    /// Reprise depuis la valeur enregistrée avant start_time
+    /// (recalculate_obv_for_range)
+    fn recalculate_appended(
+        &self,
+        conn: &mut Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        start_time: i64,
+        end_time: i64,
+    ) -> Option<Result<i64>> {
+        Some(recalculate_obv_for_range(
+            conn, provider, symbol, timeframe, start_time, end_time,
+        ))
+    }
+}
+
+/// OBV de chaque bougie d'une série triée, 0 pour la première
+///
+/// Une valeur par bougie dès la première (contrairement au RSI, pas de
+/// période de chauffe). Longueurs différentes: la plus courte des deux
+///
+/// EXEMPLE: closes [10, 11, 11, 9], volumes [5, 3, 4, 2] → [0, 3, 3, 1]
+pub fn calculate_obv(closes: &[f64], volumes: &[f64]) -> Vec<f64> {
+    let mut obv = 0.0;
+    let mut previous_close: Option<f64> = None;
+
+    closes
+        .iter()
+        .zip(volumes)
+        .map(|(&close, &volume)| {
+            match previous_close {
+                Some(previous) if close > previous => obv += volume,
+                Some(previous) if close < previous => obv -= volume,
+                _ => {}
+            }
+            previous_close = Some(close);
+            obv
+        })
+        .collect()
+}
+
+/// OBV des bougies de `range` (open_time en ms), calculé depuis la
+/// première bougie de la série (réelles et interpolées)
+pub fn obv_series(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    range: RangeInclusive<i64>,
+) -> Result<Vec<ObvValue>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT open_time, close, volume FROM candlesticks
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3 AND open_time <= ?4
+         ORDER BY open_time ASC",
+    )?;
+    let candles = stmt
+        .query_map(params![provider, symbol, timeframe, range.end()], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, f64>(1)?,
+                row.get::<_, f64>(2)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let closes: Vec<f64> = candles.iter().map(|(_, close, _)| *close).collect();
+    let volumes: Vec<f64> = candles.iter().map(|(_, _, volume)| *volume).collect();
+
+    Ok(candles
+        .iter()
+        .zip(calculate_obv(&closes, &volumes))
+        .filter(|((open_time, _, _), _)| range.contains(open_time))
+        .map(|((open_time, _, _), obv)| ObvValue {
+            open_time: *open_time,
+            obv,
+        })
+        .collect())
+}
+
+/// Calcule et enregistre l'OBV des bougies de [start_time, end_time]
+/// dans indicator_values (valeurs existantes remplacées), en une
+/// transaction
+///
+/// RETOUR: Nombre de valeurs enregistrées
+pub fn store_obv(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<i64> {
+    recalculate_indicator_for_range(
+        conn, provider, symbol, timeframe, &Obv, start_time, end_time,
+    )
+}
+
+/// Calcule et enregistre l'OBV des bougies de [start_time, end_time] en
+/// reprenant la dernière valeur enregistrée avant la plage
+///
+/// ALGORITHME:
+/// 1. Bougie qui précède start_time dans candlesticks, et son OBV
+///    enregistré dans indicator_values
+/// 2. Trouvé: OBV de chaque bougie de la plage = OBV enregistré + OBV
+///    calculé à partir de cette bougie (nul sur elle, voir calculate_obv)
+/// 3. Sinon (plage qui commence à la première bougie, OBV précédent jamais
+///    enregistré): calcul depuis la première bougie (store_obv)
+///
+/// SUBTILITÉ: la reprise suppose à jour les valeurs enregistrées avant la
+/// plage; après correction d'une bougie antérieure à start_time, recalculer
+/// toute la série (store_obv)
+///
+/// PERFORMANCE: lit la plage et une bougie, au lieu de toute la série:
+/// adapté à l'ajout des nouvelles bougies d'un backfill
+///
+/// RETOUR: Nombre de valeurs enregistrées
+pub fn recalculate_obv_for_range(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<i64> {
+    let prior: Option<(i64, f64)> = conn
+        .query_row(
+            "SELECT c.open_time, v.value FROM
+                 (SELECT open_time FROM candlesticks
+                  WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3 AND open_time < ?4
+                  ORDER BY open_time DESC LIMIT 1) c
+             JOIN indicator_values v
+               ON v.provider = ?1 AND v.symbol = ?2 AND v.timeframe = ?3
+              AND v.indicator = ?5 AND v.params = ?6 AND v.component = ?7
+              AND v.open_time = c.open_time",
+            params![
+                provider,
+                symbol,
+                timeframe,
+                start_time,
+                Obv.name(),
+                Obv.params().to_string(),
+                VALUE_COMPONENT
+            ],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+    let Some((prior_time, prior_obv)) = prior else {
+        return store_obv(conn, provider, symbol, timeframe, start_time, end_time);
+    };
+
+    let candles = query_candle_records(
+        conn,
+        provider,
+        symbol,
+        timeframe,
+        prior_time..=end_time,
+        None,
+    )?;
+    let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+    let volumes: Vec<f64> = candles.iter().map(|c| c.volume).collect();
+    let values = candles
+        .iter()
+        .zip(calculate_obv(&closes, &volumes))
+        .skip(1)
+        .map(|(candle, obv)| IndicatorValue {
+            open_time: candle.open_time,
+            value: prior_obv + obv,
+        })
+        .collect();
+
+    store_component_series(
+        conn,
+        provider,
+        symbol,
+        timeframe,
+        &Obv,
+        &[ComponentSeries {
+            component: VALUE_COMPONENT.to_string(),
+            values,
+        }],
+    )
+}
@@ -0,0 +1,215 @@
+/// Module du Parabolic SAR (Stop And Reverse de Wilder)
+///
+/// Stop suiveur sous le prix en tendance haussière, au-dessus en tendance
+/// baissière, qui bascule de côté quand le prix le franchit
+///
+/// ALGORITHME:
+/// - Initialisation sur les deux premières bougies: tendance haussière si
+///   le milieu (high + low) / 2 monte, SAR au plus bas (haussière) ou au
+///   plus haut (baissière) des deux, EP (point extrême) à l'opposé
+/// - Ensuite: SAR = SAR préc. + AF × (EP - SAR préc.), borné par les plus
+///   bas (haussière) ou plus hauts (baissière) des deux bougies précédentes
+/// - Nouvel extrême dans le sens de la tendance: EP mis à jour, AF augmenté
+///   de step_af jusqu'à max_af
+/// - Prix qui franchit le SAR: bascule, SAR = EP de la tendance finie, EP
+///   = extrême de la bougie, AF = initial_af
+///
+/// SUBTILITÉ: comme l'ADX, chaque valeur dépend de toute la série qui
+/// précède; le calcul part toujours de la plus ancienne bougie stockée
+use crate::error::{CandleError, Result};
+use rusqlite::{Connection, params};
+use serde::Serialize;
+use std::ops::RangeInclusive;
+
+/// Facteur d'accélération initial usuel
+pub const DEFAULT_INITIAL_AF: f64 = 0.02;
+/// Incrément usuel du facteur d'accélération
+pub const DEFAULT_STEP_AF: f64 = 0.02;
+/// Facteur d'accélération maximal usuel
+pub const DEFAULT_MAX_AF: f64 = 0.20;
+
+/// SAR d'une bougie
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SarPoint {
+    pub open_time: i64,
+    pub sar: f64,
+    /// Vrai si le SAR est sous le prix (tendance haussière)
+    pub is_bullish: bool,
+}
+
+/// SAR de chaque bougie d'une série triée
+///
+/// RETOUR: None pour la première bougie (l'initialisation a besoin de deux
+/// bougies). Longueurs différentes: la plus courte
+pub fn calculate_sar(
+    open_times: &[i64],
+    highs: &[f64],
+    lows: &[f64],
+    initial_af: f64,
+    step_af: f64,
+    max_af: f64,
+) -> Vec<Option<SarPoint>> {
+    let len = open_times.len().min(highs.len()).min(lows.len());
+    let mut points = vec![None; len];
+    if len < 2 {
+        return points;
+    }
+
+    let mut is_bullish = highs[1] + lows[1] >= highs[0] + lows[0];
+    let (mut sar, mut ep) = if is_bullish {
+        (lows[0].min(lows[1]), highs[0].max(highs[1]))
+    } else {
+        (highs[0].max(highs[1]), lows[0].min(lows[1]))
+    };
+    let mut af = initial_af;
+    points[1] = Some(SarPoint {
+        open_time: open_times[1],
+        sar,
+        is_bullish,
+    });
+
+    for i in 2..len {
+        sar += af * (ep - sar);
+        if is_bullish {
+            sar = sar.min(lows[i - 1]).min(lows[i - 2]);
+            if lows[i] < sar {
+                is_bullish = false;
+                sar = ep;
+                ep = lows[i];
+                af = initial_af;
+            } else if highs[i] > ep {
+                ep = highs[i];
+                af = (af + step_af).min(max_af);
+            }
+        } else {
+            sar = sar.max(highs[i - 1]).max(highs[i - 2]);
+            if highs[i] > sar {
+                is_bullish = true;
+                sar = ep;
+                ep = highs[i];
+                af = initial_af;
+            } else if lows[i] < ep {
+                ep = lows[i];
+                af = (af + step_af).min(max_af);
+            }
+        }
+        points[i] = Some(SarPoint {
+            open_time: open_times[i],
+            sar,
+            is_bullish,
+        });
+    }
+
+    points
+}
+
+/// SAR des bougies de `range` (open_time en ms), calculé depuis la première
+/// bougie de la série
+///
+/// ERREURS: ValidationError si les facteurs ne sont pas finis ou ne
+/// vérifient pas 0 < initial_af <= max_af et step_af >= 0
+#[allow(clippy::too_many_arguments)]
+pub fn sar_series(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    range: RangeInclusive<i64>,
+    initial_af: f64,
+    step_af: f64,
+    max_af: f64,
+) -> Result<Vec<SarPoint>> {
+    let valid = [initial_af, step_af, max_af]
+        .iter()
+        .all(|af| af.is_finite())
+        && initial_af > 0.0
+        && initial_af <= max_af
+        && step_af >= 0.0;
+    if !valid {
+        return Err(CandleError::ValidationError(format!(
+            "Facteurs d'accélération invalides: initial {}, pas {}, max {}",
+            initial_af, step_af, max_af
+        )));
+    }
+
+    let mut stmt = conn.prepare_cached(
+        "SELECT open_time, high, low FROM candlesticks
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3 AND open_time <= ?4
+         ORDER BY open_time ASC",
+    )?;
+    let candles = stmt
+        .query_map(params![provider, symbol, timeframe, range.end()], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, f64>(1)?,
+                row.get::<_, f64>(2)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let open_times: Vec<i64> = candles.iter().map(|c| c.0).collect();
+    let highs: Vec<f64> = candles.iter().map(|c| c.1).collect();
+    let lows: Vec<f64> = candles.iter().map(|c| c.2).collect();
+
+    Ok(
+        calculate_sar(&open_times, &highs, &lows, initial_af, step_af, max_af)
+            .into_iter()
+            .flatten()
+            .filter(|point| range.contains(&point.open_time))
+            .collect(),
+    )
+}
+
+/// Calcule et enregistre le SAR des bougies de [start_time, end_time] dans
+/// sar_values (valeurs existantes des mêmes bougies et facteurs
+/// remplacées), en une transaction
+///
+/// RETOUR: Nombre de valeurs enregistrées
+#[allow(clippy::too_many_arguments)]
+pub fn store_sar(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    initial_af: f64,
+    step_af: f64,
+    max_af: f64,
+    start_time: i64,
+    end_time: i64,
+) -> Result<i64> {
+    let points = sar_series(
+        conn,
+        provider,
+        symbol,
+        timeframe,
+        start_time..=end_time,
+        initial_af,
+        step_af,
+        max_af,
+    )?;
+
+    let tx = conn.transaction()?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT OR REPLACE INTO sar_values
+                 (provider, symbol, timeframe, initial_af, step_af, max_af, open_time, sar, is_bullish)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        )?;
+        for point in &points {
+            stmt.execute(params![
+                provider,
+                symbol,
+                timeframe,
+                initial_af,
+                step_af,
+                max_af,
+                point.open_time,
+                point.sar,
+                point.is_bullish
+            ])?;
+        }
+    }
+    tx.commit()?;
+
+    Ok(points.len() as i64)
+}
@@ -0,0 +1,166 @@
+/// Module des points pivots (supports / résistances de la période suivante)
+///
+/// ALGORITHME (pivots classiques), à partir du plus haut H, du plus bas L
+/// et de la clôture C de la période précédente:
+/// - PP = (H + L + C) / 3
+/// - R1 = 2 × PP - L, S1 = 2 × PP - H
+/// - R2 = PP + (H - L), S2 = PP - (H - L)
+/// - R3 = H + 2 × (PP - L), S3 = L - 2 × (H - PP)
+///
+/// Les bougies intrajournalières sont d'abord regroupées par période pivot
+/// (1d par défaut) avec resample_candles: périodes UTC, 1w aligné sur le lundi
+use crate::database::query_candle_records;
+use crate::error::Result;
+use crate::utils::{parse_timeframe, period_start, resample_candles};
+use rusqlite::{Connection, params};
+use serde::Serialize;
+use std::ops::RangeInclusive;
+
+/// Période pivot par défaut (/api/indicators/pivot-points)
+pub const DEFAULT_PIVOT_PERIOD: &str = "1d";
+
+/// Bougie d'une période pivot (jour par défaut), agrégée des bougies
+/// intrajournalières
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct DailyCandle {
+    /// Début de la période (ms)
+    pub date: i64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+/// Niveaux pivots d'une période
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PivotPoint {
+    /// Début de la période à laquelle les niveaux s'appliquent (ms)
+    pub date: i64,
+    pub pp: f64,
+    pub r1: f64,
+    pub r2: f64,
+    pub r3: f64,
+    pub s1: f64,
+    pub s2: f64,
+    pub s3: f64,
+}
+
+impl PivotPoint {
+    /// Niveaux calculés à partir d'une période, datés `date`
+    pub fn from_candle(date: i64, candle: &DailyCandle) -> Self {
+        let (high, low) = (candle.high, candle.low);
+        let pp = (high + low + candle.close) / 3.0;
+        PivotPoint {
+            date,
+            pp,
+            r1: 2.0 * pp - low,
+            r2: pp + (high - low),
+            r3: high + 2.0 * (pp - low),
+            s1: 2.0 * pp - high,
+            s2: pp - (high - low),
+            s3: low - 2.0 * (high - pp),
+        }
+    }
+}
+
+/// Niveaux de chaque période d'une série triée, calculés sur la période
+/// précédente
+///
+/// RETOUR: un point par période sauf la première (pas de période
+/// précédente). Période manquante: la suivante reprend la dernière période
+/// présente
+pub fn calculate_pivot_points(candles: &[DailyCandle]) -> Vec<PivotPoint> {
+    candles
+        .windows(2)
+        .map(|pair| PivotPoint::from_candle(pair[1].date, &pair[0]))
+        .collect()
+}
+
+/// Points pivots des périodes `pivot_period` dont le début est dans `range`
+/// (ms), à partir des bougies `timeframe`
+///
+/// La période qui précède la plage est lue aussi: la première période de
+/// la plage a ses niveaux
+///
+/// ERREURS: ValidationError si pivot_period n'est pas un multiple strict
+/// de timeframe (voir resample_candles)
+pub fn pivot_point_series(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    pivot_period: &str,
+    range: RangeInclusive<i64>,
+) -> Result<Vec<PivotPoint>> {
+    let interval = parse_timeframe(pivot_period)?;
+    // Période précédant la plage, et dernière période lue jusqu'à sa fin
+    let first = period_start(*range.start(), interval).saturating_sub(interval);
+    let last = period_start(*range.end(), interval).saturating_add(interval - 1);
+
+    let candles = query_candle_records(conn, provider, symbol, timeframe, first..=last, None)?;
+    let periods: Vec<DailyCandle> = resample_candles(&candles, timeframe, pivot_period)?
+        .iter()
+        .map(|candle| DailyCandle {
+            date: candle.open_time,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+        })
+        .collect();
+
+    Ok(calculate_pivot_points(&periods)
+        .into_iter()
+        .filter(|point| range.contains(&point.date))
+        .collect())
+}
+
+/// Calcule et enregistre les points pivots des périodes de
+/// [start_time, end_time] dans pivot_points (niveaux existants des mêmes
+/// périodes remplacés), en une transaction
+///
+/// RETOUR: Nombre de périodes enregistrées
+pub fn store_pivot_points(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    pivot_period: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<i64> {
+    let points = pivot_point_series(
+        conn,
+        provider,
+        symbol,
+        timeframe,
+        pivot_period,
+        start_time..=end_time,
+    )?;
+
+    let tx = conn.transaction()?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT OR REPLACE INTO pivot_points
+                 (provider, symbol, timeframe, pivot_period, date, pp, r1, r2, r3, s1, s2, s3)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        )?;
+        for point in &points {
+            stmt.execute(params![
+                provider,
+                symbol,
+                timeframe,
+                pivot_period,
+                point.date,
+                point.pp,
+                point.r1,
+                point.r2,
+                point.r3,
+                point.s1,
+                point.s2,
+                point.s3
+            ])?;
+        }
+    }
+    tx.commit()?;
+
+    Ok(points.len() as i64)
+}
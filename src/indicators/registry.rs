@@ -0,0 +1,215 @@
+/// Module du registre des indicateurs recalculés à chaque écriture de
+/// bougies (backfill, écrivains temps réel)
+///
+/// Sans registre, les valeurs de indicator_values ne bougent qu'à un
+/// recalcul explicite et deviennent obsolètes dès l'arrivée de nouvelles
+/// bougies. Le registre liste les indicateurs activés; recalculate est
+/// appelé après chaque batch inséré (CandleRetriever::fetch_one_batch) et
+/// chaque lot écrit par un écrivain temps réel
+///
+/// CONFIGURATION: IndicatorSpec ("rsi:14", ou { name = "rsi", period = 14 }
+/// dans la section [backfill] de config.toml), voir BackfillOptions
+///
+/// ALGORITHME (recalculate, par indicateur):
+/// 1. Plage du batch [start_time, end_time], étendue aux `warmup` bougies
+///    qui suivent end_time: leur valeur dépend des bougies du batch (un
+///    backfill remonte le temps, les bougies plus récentes sont déjà là)
+/// 2. recalculate_indicator_for_range: lit les `warmup` bougies avant la
+///    plage, calcule, enregistre
+///
+/// recalculate_appended (écrivains temps réel): les bougies écrites suivent
+/// les dernières calculées, un indicateur sans warmup (OBV) reprend sa
+/// dernière valeur enregistrée (Indicator::recalculate_appended)
+///
+/// PERFORMANCE: un indicateur à warmup borné (RSI, EMA, SMA, MACD...) lit
+/// au plus taille du batch + 2 × warmup bougies, quelle que soit la taille
+/// de la série. Sans warmup (OBV): après un batch du backfill, qui remonte
+/// le temps, toute la série est relue et recalculée jusqu'à la dernière
+/// bougie; après un lot temps réel, seule la bougie qui précède le lot est
+/// relue en plus du lot
+use crate::error::{CandleError, Result};
+use crate::indicators::values::{Indicator, indicator_by_name, recalculate_indicator_for_range};
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Instant;
+use tracing::debug;
+
+/// Indicateur activé dans la configuration: nom et période facultative
+/// (voir values::indicator_by_name: obv, sma, ema, rsi)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndicatorSpec {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period: Option<usize>,
+}
+
+impl FromStr for IndicatorSpec {
+    type Err = CandleError;
+
+    /// "rsi" (période par défaut) ou "rsi:14" (CLI: --indicators rsi:14,ema:20)
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, period) = match s.split_once(':') {
+            Some((name, period)) => {
+                let period = period.parse().map_err(|_| {
+                    CandleError::ParseError(format!("Période invalide: {} (attendu: rsi:14)", s))
+                })?;
+                (name, Some(period))
+            }
+            None => (s, None),
+        };
+        Ok(IndicatorSpec {
+            name: name.to_lowercase(),
+            period,
+        })
+    }
+}
+
+impl fmt::Display for IndicatorSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.period {
+            Some(period) => write!(f, "{}:{}", self.name, period),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// Indicateurs recalculés après chaque écriture de bougies
+///
+/// SUBTILITÉ RUST: Indicator: Send + Sync, un Arc<IndicatorRegistry> est
+/// partagé par les threads des écrivains temps réel
+#[derive(Default)]
+pub struct IndicatorRegistry {
+    indicators: Vec<Box<dyn Indicator>>,
+}
+
+impl IndicatorRegistry {
+    /// Registre vide (recalculate ne fait rien)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registre des indicateurs de la configuration
+    ///
+    /// ERREURS: ValidationError (nom inconnu, période nulle)
+    pub fn from_specs(specs: &[IndicatorSpec]) -> Result<Self> {
+        let indicators = specs
+            .iter()
+            .map(|spec| indicator_by_name(&spec.name, spec.period))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(IndicatorRegistry { indicators })
+    }
+
+    /// Ajoute un indicateur (MACD, ATR... absents de indicator_by_name)
+    pub fn with(mut self, indicator: impl Indicator + 'static) -> Self {
+        self.indicators.push(Box::new(indicator));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indicators.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.indicators.len()
+    }
+
+    /// Recalcule et enregistre chaque indicateur pour les bougies écrites
+    /// dans [start_time, end_time] et celles qui en dépendent (voir
+    /// l'ALGORITHME du module)
+    ///
+    /// RETOUR: Nombre de valeurs enregistrées, tous indicateurs
+    pub fn recalculate(
+        &self,
+        conn: &mut Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<i64> {
+        self.recalculate_with(
+            conn, provider, symbol, timeframe, start_time, end_time, false,
+        )
+    }
+
+    /// Comme recalculate, pour des bougies écrites après les dernières
+    /// calculées (lot d'un écrivain temps réel): les indicateurs qui le
+    /// permettent reprennent leur dernière valeur (voir le module)
+    pub fn recalculate_appended(
+        &self,
+        conn: &mut Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<i64> {
+        self.recalculate_with(
+            conn, provider, symbol, timeframe, start_time, end_time, true,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn recalculate_with(
+        &self,
+        conn: &mut Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        start_time: i64,
+        end_time: i64,
+        appended: bool,
+    ) -> Result<i64> {
+        let started = Instant::now();
+        let mut stored = 0;
+        for indicator in &self.indicators {
+            if appended {
+                // Fin de série: couvre aussi une bougie arrivée en retard
+                let resumed = indicator.recalculate_appended(
+                    conn,
+                    provider,
+                    symbol,
+                    timeframe,
+                    start_time,
+                    i64::MAX,
+                );
+                if let Some(resumed) = resumed {
+                    stored += resumed?;
+                    continue;
+                }
+            }
+            let affected_end = match indicator.warmup() {
+                Some(warmup) => conn.query_row(
+                    "SELECT COALESCE(MAX(open_time), ?4) FROM (
+                         SELECT open_time FROM candlesticks
+                         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3 AND open_time > ?4
+                         ORDER BY open_time ASC LIMIT ?5)",
+                    params![provider, symbol, timeframe, end_time, warmup as i64],
+                    |row| row.get(0),
+                )?,
+                None => i64::MAX,
+            };
+            stored += recalculate_indicator_for_range(
+                conn,
+                provider,
+                symbol,
+                timeframe,
+                indicator.as_ref(),
+                start_time,
+                affected_end,
+            )?;
+        }
+
+        debug!(
+            symbol,
+            timeframe,
+            indicators = self.indicators.len(),
+            stored,
+            duration_ms = started.elapsed().as_millis() as u64,
+            "Indicateurs recalculés"
+        );
+        Ok(stored)
+    }
+}
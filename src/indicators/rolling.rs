@@ -0,0 +1,179 @@
+/// Module des statistiques sur fenêtre glissante (moyenne, écart-type,
+/// minimum, maximum)
+///
+/// Fonctions génériques sur une série de valeurs, sans indicateur nommé:
+/// la valeur de l'indice i porte sur les `period` valeurs qui finissent en
+/// i, None pour les period - 1 premières (period = 0: toujours None)
+///
+/// PERFORMANCE: O(n) quelle que soit la période. Moyenne par somme
+/// glissante; minimum et maximum par file monotone (VecDeque d'indices):
+/// chaque indice entre et sort de la file une seule fois
+use crate::error::Result;
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::ops::RangeInclusive;
+
+/// Statistique glissante (paramètre `type` de /api/indicators/rolling)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RollingKind {
+    Mean,
+    Std,
+    Min,
+    Max,
+}
+
+/// Statistique glissante du close d'une bougie
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RollingValue {
+    pub open_time: i64,
+    pub value: f64,
+}
+
+/// Moyenne simple sur `period` valeurs
+pub fn rolling_mean(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut sum = 0.0;
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            sum += value;
+            if period > 0 && i >= period {
+                sum -= values[i - period];
+            }
+            (period > 0 && i + 1 >= period).then(|| sum / period as f64)
+        })
+        .collect()
+}
+
+/// Écart-type échantillon (n - 1) sur `period` valeurs (period < 2:
+/// toujours None)
+///
+/// SUBTILITÉ: écarts calculés à la moyenne de chaque fenêtre plutôt que par
+/// somme des carrés glissante, sans perte de précision sur des prix élevés
+/// et peu dispersés
+pub fn rolling_std(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    if period < 2 {
+        return vec![None; values.len()];
+    }
+    rolling_mean(values, period)
+        .into_iter()
+        .enumerate()
+        .map(|(i, mean)| {
+            mean.map(|mean| {
+                let variance = values[i + 1 - period..=i]
+                    .iter()
+                    .map(|value| (value - mean).powi(2))
+                    .sum::<f64>()
+                    / (period - 1) as f64;
+                variance.sqrt()
+            })
+        })
+        .collect()
+}
+
+/// Minimum sur `period` valeurs
+pub fn rolling_min(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    rolling_extreme(values, period, |candidate, kept| candidate <= kept)
+}
+
+/// Maximum sur `period` valeurs
+pub fn rolling_max(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    rolling_extreme(values, period, |candidate, kept| candidate >= kept)
+}
+
+/// Extrême glissant par file monotone
+///
+/// ALGORITHME: la file garde les indices de la fenêtre dont la valeur peut
+/// encore devenir l'extrême, l'extrême courant en tête. Une nouvelle valeur
+/// retire de la queue les indices qu'elle remplace (`replaces`), l'indice
+/// sorti de la fenêtre est retiré de la tête
+fn rolling_extreme(
+    values: &[f64],
+    period: usize,
+    replaces: fn(f64, f64) -> bool,
+) -> Vec<Option<f64>> {
+    if period == 0 {
+        return vec![None; values.len()];
+    }
+    let mut window: VecDeque<usize> = VecDeque::with_capacity(period);
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| {
+            while window
+                .back()
+                .is_some_and(|&kept| replaces(value, values[kept]))
+            {
+                window.pop_back();
+            }
+            window.push_back(i);
+            if window.front().is_some_and(|&first| first + period <= i) {
+                window.pop_front();
+            }
+            (i + 1 >= period).then(|| values[window[0]])
+        })
+        .collect()
+}
+
+/// Statistique glissante du close des bougies de `range` (open_time en ms),
+/// bougies sans fenêtre complète omises
+///
+/// Les period - 1 bougies qui précèdent la plage sont lues aussi: la
+/// valeur d'une bougie ne dépend pas de la plage demandée
+pub fn rolling_series(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    range: RangeInclusive<i64>,
+    kind: RollingKind,
+    period: usize,
+) -> Result<Vec<RollingValue>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT open_time, close FROM candlesticks
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+           AND open_time >= COALESCE(
+               (SELECT MIN(open_time) FROM (
+                    SELECT open_time FROM candlesticks
+                    WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3 AND open_time < ?4
+                    ORDER BY open_time DESC LIMIT ?6)),
+               ?4)
+           AND open_time <= ?5
+         ORDER BY open_time ASC",
+    )?;
+    let candles = stmt
+        .query_map(
+            params![
+                provider,
+                symbol,
+                timeframe,
+                range.start(),
+                range.end(),
+                period.saturating_sub(1) as i64
+            ],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, f64>(1)?)),
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let closes: Vec<f64> = candles.iter().map(|c| c.1).collect();
+    let values = match kind {
+        RollingKind::Mean => rolling_mean(&closes, period),
+        RollingKind::Std => rolling_std(&closes, period),
+        RollingKind::Min => rolling_min(&closes, period),
+        RollingKind::Max => rolling_max(&closes, period),
+    };
+
+    Ok(candles
+        .iter()
+        .zip(values)
+        .filter(|((open_time, _), _)| range.contains(open_time))
+        .filter_map(|((open_time, _), value)| {
+            value.map(|value| RollingValue {
+                open_time: *open_time,
+                value,
+            })
+        })
+        .collect())
+}
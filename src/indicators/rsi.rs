@@ -0,0 +1,140 @@
+/// Module du RSI (Relative Strength Index de Wilder)
+///
+/// ALGORITHME:
+/// - Variation de chaque close: hausse (gain) ou baisse (perte, positive)
+/// - Moyennes initiales: gains et pertes moyens des `period` premières
+///   variations
+/// - Ensuite (moyenne de Wilder): moyenne = (moyenne préc. × (period - 1)
+///   + variation) / period
+/// - RSI = 100 - 100 / (1 + gain moyen / perte moyenne)
+///
+/// SUBTILITÉ: perte moyenne nulle: RSI = 100 (que des hausses), ou
+/// FLAT_RSI si le gain moyen est nul aussi (prix figés), plutôt qu'une
+/// division par zéro
+///
+/// Comme pour l'ATR, le poids de l'amorce décroît en (1 - 1/period)^n:
+/// RSI_WARMUP_FACTOR × period bougies lues avant la plage suffisent (poids
+/// sous 1e-11 pour 14), un recalcul incrémental ne relit pas toute la série
+///
+/// - calculate_rsi: toute une série (Rsi: implémentation de Indicator,
+///   valeurs dans indicator_values)
+/// - RsiState: calcul incrémental, une étape de Wilder par nouvelle bougie
+///   (RealtimeManager, sans relire la base)
+use crate::indicators::values::Indicator;
+use crate::models::CandleRecord;
+
+/// Période usuelle du RSI
+pub const DEFAULT_RSI_PERIOD: usize = 14;
+
+/// Bougies de chauffe lues avant la plage, par bougie de période
+pub const RSI_WARMUP_FACTOR: usize = 25;
+
+/// RSI de prix figés (gain et perte moyens nuls): milieu de l'échelle
+pub const FLAT_RSI: f64 = 50.0;
+
+/// Moyennes de Wilder des gains et des pertes, de quoi calculer le RSI
+/// suivant en O(1)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RsiState {
+    pub avg_gain: f64,
+    pub avg_loss: f64,
+    pub period: usize,
+}
+
+impl RsiState {
+    /// État après le dernier close de `closes`
+    ///
+    /// RETOUR: None si la série n'a pas period + 1 closes (ou période nulle)
+    pub fn from_history(closes: &[f64], period: usize) -> Option<RsiState> {
+        if period == 0 || closes.len() <= period {
+            return None;
+        }
+        let (gains, losses) = closes[..=period]
+            .windows(2)
+            .map(|pair| split_change(pair[1] - pair[0]))
+            .fold((0.0, 0.0), |(g, l), (gain, loss)| (g + gain, l + loss));
+        let mut state = RsiState {
+            avg_gain: gains / period as f64,
+            avg_loss: losses / period as f64,
+            period,
+        };
+        for pair in closes[period..].windows(2) {
+            state.update(pair[1], pair[0]);
+        }
+        Some(state)
+    }
+
+    /// Applique une étape de Wilder pour un nouveau close
+    ///
+    /// RETOUR: RSI après ce close
+    pub fn update(&mut self, new_close: f64, prev_close: f64) -> f64 {
+        let (gain, loss) = split_change(new_close - prev_close);
+        let weight = self.period as f64;
+        self.avg_gain = (self.avg_gain * (weight - 1.0) + gain) / weight;
+        self.avg_loss = (self.avg_loss * (weight - 1.0) + loss) / weight;
+        self.value()
+    }
+
+    /// RSI de l'état courant
+    pub fn value(&self) -> f64 {
+        if self.avg_loss == 0.0 {
+            if self.avg_gain == 0.0 {
+                FLAT_RSI
+            } else {
+                100.0
+            }
+        } else {
+            100.0 - 100.0 / (1.0 + self.avg_gain / self.avg_loss)
+        }
+    }
+}
+
+/// Variation découpée en (gain, perte), chacun positif ou nul
+fn split_change(change: f64) -> (f64, f64) {
+    if change > 0.0 {
+        (change, 0.0)
+    } else {
+        (0.0, -change)
+    }
+}
+
+/// RSI de chaque close d'une série triée
+///
+/// None pour les `period` premiers closes (il faut period variations),
+/// période nulle: toujours None
+pub fn calculate_rsi(closes: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut values = vec![None; closes.len()];
+    let Some(mut state) = RsiState::from_history(&closes[..closes.len().min(period + 1)], period)
+    else {
+        return values;
+    };
+    values[period] = Some(state.value());
+    for i in period + 1..closes.len() {
+        values[i] = Some(state.update(closes[i], closes[i - 1]));
+    }
+    values
+}
+
+/// RSI du close comme Indicator
+pub struct Rsi {
+    pub period: usize,
+}
+
+impl Indicator for Rsi {
+    fn name(&self) -> &str {
+        "rsi"
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::json!({ "period": self.period })
+    }
+
+    fn compute(&self, candles: &[CandleRecord]) -> Vec<Option<f64>> {
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        calculate_rsi(&closes, self.period)
+    }
+
+    fn warmup(&self) -> Option<usize> {
+        Some(RSI_WARMUP_FACTOR * self.period)
+    }
+}
@@ -0,0 +1,324 @@
+/// Module de l'oscillateur stochastique (%K, %D)
+///
+/// ALGORITHME:
+/// - %K brut[i] = (close[i] - plus bas) / (plus haut - plus bas) × 100, sur
+///   les k_period bougies qui finissent en i
+/// - %K[i] = moyenne simple des k_smoothing derniers %K bruts (1: %K brut,
+///   stochastique rapide; 3: stochastique lent usuel)
+/// - %D[i] = moyenne simple des d_period derniers %K
+///
+/// SUBTILITÉ: plus haut = plus bas (prix figés sur la fenêtre): %K vaut 50,
+/// milieu de l'échelle, plutôt qu'une division par zéro
+///
+/// Pour une plage restreinte, les k_period + k_smoothing + d_period - 3
+/// bougies qui précèdent la plage sont lues aussi: la valeur d'une bougie
+/// ne dépend pas de la plage demandée
+///
+/// Stockage: indicator_values via Stochastic (composantes k et d), seul
+/// stockage depuis la v16 (reprise de l'ancienne table stochastic_values)
+use crate::error::{CandleError, Result};
+use crate::indicators::values::{Indicator, recalculate_indicator_for_range};
+use crate::models::CandleRecord;
+use rusqlite::{Connection, params};
+use serde::Serialize;
+use std::ops::RangeInclusive;
+
+/// %K d'une fenêtre sans amplitude (plus haut = plus bas)
+pub const FLAT_RANGE_K: f64 = 50.0;
+
+/// Oscillateur stochastique d'une bougie (None: fenêtre incomplète)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct StochasticPoint {
+    pub open_time: i64,
+    pub k: Option<f64>,
+    pub d: Option<f64>,
+}
+
+/// %K et %D de chaque bougie d'une série triée (stochastique rapide, voir
+/// calculate_slow_stochastic)
+///
+/// %K: None pour les k_period - 1 premières bougies; %D: None tant que
+/// d_period valeurs de %K ne sont pas disponibles. Période nulle: toujours
+/// None. Longueurs différentes: la plus courte
+pub fn calculate_stochastic(
+    open_times: &[i64],
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    k_period: usize,
+    d_period: usize,
+) -> Vec<StochasticPoint> {
+    calculate_slow_stochastic(open_times, highs, lows, closes, k_period, 1, d_period)
+}
+
+/// %K lissé sur k_smoothing valeurs et %D de chaque bougie d'une série
+/// triée
+///
+/// %K: None pour les k_period + k_smoothing - 2 premières bougies; %D: None
+/// tant que d_period valeurs de %K ne sont pas disponibles. Période nulle:
+/// toujours None. Longueurs différentes: la plus courte
+pub fn calculate_slow_stochastic(
+    open_times: &[i64],
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    k_period: usize,
+    k_smoothing: usize,
+    d_period: usize,
+) -> Vec<StochasticPoint> {
+    let len = open_times
+        .len()
+        .min(highs.len())
+        .min(lows.len())
+        .min(closes.len());
+
+    let raw_k: Vec<Option<f64>> = (0..len)
+        .map(|i| {
+            if k_period == 0 || i + 1 < k_period {
+                return None;
+            }
+            let window = i + 1 - k_period..=i;
+            let highest = highs[window.clone()]
+                .iter()
+                .fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+            let lowest = lows[window].iter().fold(f64::INFINITY, |a, &b| a.min(b));
+            if highest == lowest {
+                Some(FLAT_RANGE_K)
+            } else {
+                Some((closes[i] - lowest) / (highest - lowest) * 100.0)
+            }
+        })
+        .collect();
+    let k_values = trailing_mean(&raw_k, k_smoothing);
+    let d_values = trailing_mean(&k_values, d_period);
+
+    (0..len)
+        .map(|i| StochasticPoint {
+            open_time: open_times[i],
+            k: k_values[i],
+            d: d_values[i],
+        })
+        .collect()
+}
+
+/// Moyenne des `period` dernières valeurs, None si l'une d'elles manque
+/// (période nulle: toujours None)
+fn trailing_mean(values: &[Option<f64>], period: usize) -> Vec<Option<f64>> {
+    (0..values.len())
+        .map(|i| {
+            if period == 0 || i + 1 < period {
+                return None;
+            }
+            values[i + 1 - period..=i]
+                .iter()
+                .copied()
+                .sum::<Option<f64>>()
+                .map(|sum| sum / period as f64)
+        })
+        .collect()
+}
+
+/// Oscillateur des bougies de `range` (open_time en ms), bougies sans %K
+/// omises
+pub fn stochastic_series(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    range: RangeInclusive<i64>,
+    k_period: usize,
+    d_period: usize,
+) -> Result<Vec<StochasticPoint>> {
+    slow_stochastic_series(
+        conn, provider, symbol, timeframe, range, k_period, 1, d_period,
+    )
+}
+
+/// Oscillateur à %K lissé des bougies de `range` (open_time en ms),
+/// bougies sans %K omises
+#[allow(clippy::too_many_arguments)]
+pub fn slow_stochastic_series(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    range: RangeInclusive<i64>,
+    k_period: usize,
+    k_smoothing: usize,
+    d_period: usize,
+) -> Result<Vec<StochasticPoint>> {
+    // Bougies de la plage et celles des fenêtres de la première (%K brut,
+    // lissage puis %D)
+    let lookback = (k_period + k_smoothing + d_period).saturating_sub(3) as i64;
+    let mut stmt = conn.prepare_cached(
+        "SELECT open_time, high, low, close FROM candlesticks
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+           AND open_time >= COALESCE(
+               (SELECT MIN(open_time) FROM (
+                    SELECT open_time FROM candlesticks
+                    WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3 AND open_time < ?4
+                    ORDER BY open_time DESC LIMIT ?6)),
+               ?4)
+           AND open_time <= ?5
+         ORDER BY open_time ASC",
+    )?;
+    let candles = stmt
+        .query_map(
+            params![
+                provider,
+                symbol,
+                timeframe,
+                range.start(),
+                range.end(),
+                lookback
+            ],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, f64>(1)?,
+                    row.get::<_, f64>(2)?,
+                    row.get::<_, f64>(3)?,
+                ))
+            },
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let open_times: Vec<i64> = candles.iter().map(|c| c.0).collect();
+    let highs: Vec<f64> = candles.iter().map(|c| c.1).collect();
+    let lows: Vec<f64> = candles.iter().map(|c| c.2).collect();
+    let closes: Vec<f64> = candles.iter().map(|c| c.3).collect();
+
+    Ok(calculate_slow_stochastic(
+        &open_times,
+        &highs,
+        &lows,
+        &closes,
+        k_period,
+        k_smoothing,
+        d_period,
+    )
+    .into_iter()
+    .filter(|point| point.k.is_some() && range.contains(&point.open_time))
+    .collect())
+}
+
+/// Calcule et enregistre l'oscillateur rapide (k_smoothing = 1) des bougies
+/// de [start_time, end_time] dans indicator_values, comme
+/// recalculate_stochastic_for_range
+///
+/// RETOUR: Nombre de valeurs enregistrées, composantes k et d confondues
+/// (pas de ligne d tant que %D est incomplet)
+///
+/// ERREURS: ValidationError si une période est nulle
+#[allow(clippy::too_many_arguments)]
+pub fn store_stochastic(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    k_period: usize,
+    d_period: usize,
+    start_time: i64,
+    end_time: i64,
+) -> Result<i64> {
+    let stochastic = Stochastic::new(k_period, 1, d_period)?;
+    recalculate_stochastic_for_range(
+        conn,
+        provider,
+        symbol,
+        timeframe,
+        &stochastic,
+        start_time,
+        end_time,
+    )
+}
+
+/// Oscillateur stochastique comme Indicator (composantes k et d)
+pub struct Stochastic {
+    k_period: usize,
+    k_smoothing: usize,
+    d_period: usize,
+}
+
+impl Stochastic {
+    /// ERREURS: ValidationError si une période est nulle
+    pub fn new(k_period: usize, k_smoothing: usize, d_period: usize) -> Result<Self> {
+        if k_period == 0 || k_smoothing == 0 || d_period == 0 {
+            return Err(CandleError::ValidationError(format!(
+                "Périodes du stochastique invalides: k {}, lissage {}, d {} (toutes >= 1)",
+                k_period, k_smoothing, d_period
+            )));
+        }
+        Ok(Stochastic {
+            k_period,
+            k_smoothing,
+            d_period,
+        })
+    }
+}
+
+impl Indicator for Stochastic {
+    fn name(&self) -> &str {
+        "stochastic"
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::json!({
+            "k_period": self.k_period,
+            "k_smoothing": self.k_smoothing,
+            "d_period": self.d_period
+        })
+    }
+
+    fn compute(&self, candles: &[CandleRecord]) -> Vec<Option<f64>> {
+        self.compute_components(candles).swap_remove(0)
+    }
+
+    fn components(&self) -> Vec<&'static str> {
+        vec!["k", "d"]
+    }
+
+    fn compute_components(&self, candles: &[CandleRecord]) -> Vec<Vec<Option<f64>>> {
+        let open_times: Vec<i64> = candles.iter().map(|c| c.open_time).collect();
+        let highs: Vec<f64> = candles.iter().map(|c| c.high).collect();
+        let lows: Vec<f64> = candles.iter().map(|c| c.low).collect();
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let points = calculate_slow_stochastic(
+            &open_times,
+            &highs,
+            &lows,
+            &closes,
+            self.k_period,
+            self.k_smoothing,
+            self.d_period,
+        );
+        vec![
+            points.iter().map(|p| p.k).collect(),
+            points.iter().map(|p| p.d).collect(),
+        ]
+    }
+
+    /// Fenêtres fixes: %K brut, lissage puis %D
+    fn warmup(&self) -> Option<usize> {
+        Some(self.k_period + self.k_smoothing + self.d_period - 3)
+    }
+}
+
+/// Calcule et enregistre %K et %D des bougies de [start_time, end_time]
+/// dans indicator_values, avec les bougies des fenêtres qui précèdent la
+/// plage
+///
+/// RETOUR: Nombre de valeurs enregistrées (les deux composantes)
+pub fn recalculate_stochastic_for_range(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    stochastic: &Stochastic,
+    start_time: i64,
+    end_time: i64,
+) -> Result<i64> {
+    recalculate_indicator_for_range(
+        conn, provider, symbol, timeframe, stochastic, start_time, end_time,
+    )
+}
@@ -0,0 +1,348 @@
+/// Module de la table générique indicator_values
+///
+/// Une ligne par (série, indicateur, paramètres, composante, bougie): un
+/// nouvel indicateur n'a besoin ni de table ni de migration, seulement
+/// d'une implémentation de Indicator. Un indicateur à une valeur par bougie
+/// (OBV, SMA, RSI...) a une seule composante, VALUE_COMPONENT; un
+/// indicateur à plusieurs sorties (MACD: macd, signal, histogram) une ligne
+/// par composante
+///
+/// - Indicator: nom, paramètres, composantes et calcul d'un indicateur
+/// - indicator_by_name: indicateur d'après son nom (/api/indicators/{name})
+/// - indicator_series / component_series: calcule une plage sans
+///   l'enregistrer
+/// - recalculate_indicator_for_range: calcule et enregistre une plage
+/// - store_component_series: enregistre des valeurs calculées ailleurs
+/// - indicator_values / component_values: relit les valeurs enregistrées
+///
+/// DESIGN: les paramètres sont stockés en JSON (serde_json, clés triées):
+/// deux réglages d'un même indicateur (RSI 14 et RSI 7) cohabitent dans la
+/// table, et la même valeur de paramètres donne toujours le même texte
+use crate::database::query_candle_records;
+use crate::error::{CandleError, Result};
+use crate::indicators::moving_average::{Ema, Sma};
+use crate::indicators::obv::Obv;
+use crate::indicators::rsi::{DEFAULT_RSI_PERIOD, Rsi};
+use crate::models::CandleRecord;
+use rusqlite::{Connection, params};
+use serde::Serialize;
+use std::ops::RangeInclusive;
+
+/// Période par défaut des moyennes mobiles (sma, ema; rsi:
+/// rsi::DEFAULT_RSI_PERIOD)
+pub const DEFAULT_INDICATOR_PERIOD: usize = 20;
+
+/// Composante unique d'un indicateur à une valeur par bougie
+pub const VALUE_COMPONENT: &str = "value";
+
+/// Indicateur calculé sur les bougies d'une série
+///
+/// Les indicateurs à une valeur n'implémentent que name, params et
+/// compute; un indicateur à plusieurs sorties redéfinit aussi components
+/// et compute_components (compute: sa première composante)
+///
+/// Send + Sync: un indicateur (simples paramètres) passe dans le thread de
+/// web::block du serveur web, et un IndicatorRegistry est partagé par les
+/// écrivains temps réel
+pub trait Indicator: Send + Sync {
+    /// Nom stocké dans indicator_values.indicator (ex: "obv")
+    fn name(&self) -> &str;
+
+    /// Paramètres du calcul (objet JSON, vide si aucun)
+    fn params(&self) -> serde_json::Value;
+
+    /// Valeur de chaque bougie d'une série triée, None pendant la période
+    /// de chauffe (même longueur que `candles`)
+    fn compute(&self, candles: &[CandleRecord]) -> Vec<Option<f64>>;
+
+    /// Noms des composantes, dans l'ordre de compute_components
+    fn components(&self) -> Vec<&'static str> {
+        vec![VALUE_COMPONENT]
+    }
+
+    /// Valeurs de chaque composante (une liste par composante, chacune de
+    /// la longueur de `candles`)
+    fn compute_components(&self, candles: &[CandleRecord]) -> Vec<Vec<Option<f64>>> {
+        vec![self.compute(candles)]
+    }
+
+    /// Bougies lues avant la plage demandée pour stabiliser le calcul
+    ///
+    /// None (par défaut): calcul depuis la première bougie stockée, exact
+    /// pour un indicateur cumulatif (OBV) ou lissé (Wilder)
+    fn warmup(&self) -> Option<usize> {
+        None
+    }
+
+    /// Recalcule et enregistre [start_time, end_time] quand les bougies ne
+    /// font que s'ajouter après celles déjà calculées (écrivains temps réel,
+    /// voir IndicatorRegistry::recalculate_appended)
+    ///
+    /// None (par défaut): pas de reprise, recalcul habituel. Un indicateur
+    /// cumulatif sans warmup (OBV) reprend sa dernière valeur enregistrée au
+    /// lieu de relire toute la série
+    fn recalculate_appended(
+        &self,
+        _conn: &mut Connection,
+        _provider: &str,
+        _symbol: &str,
+        _timeframe: &str,
+        _start_time: i64,
+        _end_time: i64,
+    ) -> Option<Result<i64>> {
+        None
+    }
+}
+
+/// Valeur enregistrée d'un indicateur
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct IndicatorValue {
+    pub open_time: i64,
+    pub value: f64,
+}
+
+/// Valeurs d'une composante d'un indicateur
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ComponentSeries {
+    pub component: String,
+    pub values: Vec<IndicatorValue>,
+}
+
+/// Indicateur d'après son nom: "obv" (sans période), "sma", "ema" (period,
+/// défaut DEFAULT_INDICATOR_PERIOD) ou "rsi" (défaut DEFAULT_RSI_PERIOD)
+///
+/// Les indicateurs à plusieurs paramètres (MACD) ont leur propre route
+///
+/// ERREURS: ValidationError si le nom est inconnu ou la période nulle
+pub fn indicator_by_name(name: &str, period: Option<usize>) -> Result<Box<dyn Indicator>> {
+    let default_period = if name == "rsi" {
+        DEFAULT_RSI_PERIOD
+    } else {
+        DEFAULT_INDICATOR_PERIOD
+    };
+    let period = period.unwrap_or(default_period);
+    if period == 0 {
+        return Err(CandleError::ValidationError(
+            "Période invalide (minimum 1)".to_string(),
+        ));
+    }
+    match name {
+        "obv" => Ok(Box::new(Obv)),
+        "sma" => Ok(Box::new(Sma { period })),
+        "ema" => Ok(Box::new(Ema { period })),
+        "rsi" => Ok(Box::new(Rsi { period })),
+        _ => Err(CandleError::ValidationError(format!(
+            "Indicateur inconnu: {}",
+            name
+        ))),
+    }
+}
+
+/// Valeurs de chaque composante de `indicator` pour les bougies de `range`
+/// (open_time en ms), bougies en période de chauffe omises
+///
+/// Bougies lues: depuis la première de la série, ou `warmup` bougies avant
+/// la plage si l'indicateur en déclare
+pub fn component_series(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    indicator: &dyn Indicator,
+    range: RangeInclusive<i64>,
+) -> Result<Vec<ComponentSeries>> {
+    let first = match indicator.warmup() {
+        Some(warmup) => conn.query_row(
+            "SELECT COALESCE(
+                 (SELECT MIN(open_time) FROM (
+                      SELECT open_time FROM candlesticks
+                      WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3 AND open_time < ?4
+                      ORDER BY open_time DESC LIMIT ?5)),
+                 ?4)",
+            params![provider, symbol, timeframe, range.start(), warmup as i64],
+            |row| row.get(0),
+        )?,
+        None => 0,
+    };
+    let candles = query_candle_records(
+        conn,
+        provider,
+        symbol,
+        timeframe,
+        first..=*range.end(),
+        None,
+    )?;
+
+    Ok(indicator
+        .components()
+        .into_iter()
+        .zip(indicator.compute_components(&candles))
+        .map(|(component, values)| ComponentSeries {
+            component: component.to_string(),
+            values: candles
+                .iter()
+                .zip(values)
+                .filter(|(candle, _)| range.contains(&candle.open_time))
+                .filter_map(|(candle, value)| {
+                    value.map(|value| IndicatorValue {
+                        open_time: candle.open_time,
+                        value,
+                    })
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+/// Valeurs de la première composante de `indicator` pour les bougies de
+/// `range` (voir component_series)
+pub fn indicator_series(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    indicator: &dyn Indicator,
+    range: RangeInclusive<i64>,
+) -> Result<Vec<IndicatorValue>> {
+    Ok(
+        component_series(conn, provider, symbol, timeframe, indicator, range)?
+            .into_iter()
+            .next()
+            .map_or_else(Vec::new, |series| series.values),
+    )
+}
+
+/// Calcule `indicator` et enregistre les valeurs de toutes ses composantes
+/// pour les bougies de [start_time, end_time] dans indicator_values
+/// (valeurs existantes des mêmes bougies remplacées), en une transaction
+///
+/// SUBTILITÉ: sans warmup, le calcul part de la première bougie stockée de
+/// la série, même pour une plage restreinte: un indicateur cumulatif (OBV)
+/// ou lissé (moyenne de Wilder) a les mêmes valeurs quelle que soit la plage
+///
+/// RETOUR: Nombre de valeurs enregistrées, toutes composantes (bougies en
+/// période de chauffe exclues)
+pub fn recalculate_indicator_for_range(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    indicator: &dyn Indicator,
+    start_time: i64,
+    end_time: i64,
+) -> Result<i64> {
+    let series = component_series(
+        conn,
+        provider,
+        symbol,
+        timeframe,
+        indicator,
+        start_time..=end_time,
+    )?;
+    store_component_series(conn, provider, symbol, timeframe, indicator, &series)
+}
+
+/// Enregistre des valeurs déjà calculées de `indicator` dans
+/// indicator_values (valeurs existantes des mêmes bougies remplacées), en
+/// une transaction
+///
+/// Pour un indicateur qui lit lui-même ses bougies (reprise de l'OBV depuis
+/// la dernière valeur enregistrée, VWAP depuis le début de session)
+///
+/// RETOUR: Nombre de valeurs enregistrées, toutes composantes
+pub fn store_component_series(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    indicator: &dyn Indicator,
+    series: &[ComponentSeries],
+) -> Result<i64> {
+    let params_json = indicator.params().to_string();
+
+    let tx = conn.transaction()?;
+    let mut stored = 0;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT OR REPLACE INTO indicator_values
+                 (provider, symbol, timeframe, indicator, params, component, open_time, value)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )?;
+        for component in series {
+            for value in &component.values {
+                stmt.execute(params![
+                    provider,
+                    symbol,
+                    timeframe,
+                    indicator.name(),
+                    params_json,
+                    component.component,
+                    value.open_time,
+                    value.value
+                ])?;
+                stored += 1;
+            }
+        }
+    }
+    tx.commit()?;
+
+    Ok(stored)
+}
+
+/// Valeurs enregistrées de la composante `component` de `indicator` pour
+/// les bougies de `range` (open_time en ms), triées
+#[allow(clippy::too_many_arguments)]
+pub fn component_values(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    indicator: &dyn Indicator,
+    component: &str,
+    range: RangeInclusive<i64>,
+) -> Result<Vec<IndicatorValue>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT open_time, value FROM indicator_values
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+           AND indicator = ?4 AND params = ?5 AND component = ?6
+           AND open_time >= ?7 AND open_time <= ?8
+         ORDER BY open_time ASC",
+    )?;
+    let values = stmt
+        .query_map(
+            params![
+                provider,
+                symbol,
+                timeframe,
+                indicator.name(),
+                indicator.params().to_string(),
+                component,
+                range.start(),
+                range.end()
+            ],
+            |row| {
+                Ok(IndicatorValue {
+                    open_time: row.get(0)?,
+                    value: row.get(1)?,
+                })
+            },
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(values)
+}
+
+/// Valeurs enregistrées de la première composante de `indicator` pour les
+/// bougies de `range` (voir component_values)
+pub fn indicator_values(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    indicator: &dyn Indicator,
+    range: RangeInclusive<i64>,
+) -> Result<Vec<IndicatorValue>> {
+    let component = indicator.components()[0];
+    component_values(
+        conn, provider, symbol, timeframe, indicator, component, range,
+    )
+}
@@ -0,0 +1,227 @@
+/// Module des mesures de volatilité
+///
+/// - Historique: écart-type échantillon (n - 1) des rendements logarithmiques
+///   ln(close[i] / close[i-1]) sur `period` rendements, annualisable
+/// - Parkinson: sqrt(1 / (4 × period × ln 2) × Σ ln(high / low)²) sur
+///   `period` bougies, sans hypothèse sur l'ouverture ni la clôture
+///
+/// SUBTILITÉ: fenêtre glissante, les premières bougies d'une série n'ont pas
+/// de valeur (None). Pour une plage restreinte, les `period` bougies qui
+/// précèdent la plage sont lues aussi: la valeur d'une bougie ne dépend pas
+/// de la plage demandée
+use crate::error::Result;
+use crate::utils::parse_timeframe;
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+/// Jours de cotation par an de l'annualisation
+pub const TRADING_DAYS_PER_YEAR: f64 = 252.0;
+
+/// Estimateur de volatilité (paramètre `type` de /api/indicators/volatility)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VolatilityKind {
+    Historical,
+    Parkinson,
+}
+
+/// Volatilité d'une bougie
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct VolatilityValue {
+    pub open_time: i64,
+    pub volatility: f64,
+}
+
+/// Nombre de bougies du timeframe par jour (1M: 30 jours)
+///
+/// EXEMPLE: "1h" → 24, "1w" → 1/7
+pub fn candles_per_day(timeframe: &str) -> Result<f64> {
+    Ok(86_400_000.0 / parse_timeframe(timeframe)? as f64)
+}
+
+/// Volatilité historique de chaque bougie d'une série triée
+///
+/// Valeur de la bougie i: écart-type des `period` rendements qui finissent
+/// en i, None pour les `period` premières bougies (period < 2: toujours None)
+///
+/// annualize: multiplié par sqrt(TRADING_DAYS_PER_YEAR × candles_per_day)
+/// (voir candles_per_day)
+pub fn calculate_historical_volatility(
+    closes: &[f64],
+    period: usize,
+    annualize: bool,
+    candles_per_day: f64,
+) -> Vec<Option<f64>> {
+    let factor = if annualize {
+        (TRADING_DAYS_PER_YEAR * candles_per_day).sqrt()
+    } else {
+        1.0
+    };
+    let returns: Vec<f64> = closes.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+
+    (0..closes.len())
+        .map(|i| {
+            if period < 2 || i < period {
+                return None;
+            }
+            let window = &returns[i - period..i];
+            let mean = window.iter().sum::<f64>() / period as f64;
+            let variance =
+                window.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (period - 1) as f64;
+            Some(variance.sqrt() * factor)
+        })
+        .collect()
+}
+
+/// Volatilité de Parkinson de chaque bougie d'une série triée
+///
+/// Valeur de la bougie i: estimateur sur les `period` bougies qui finissent
+/// en i, None pour les `period - 1` premières (period = 0: toujours None).
+/// Longueurs différentes: la plus courte des deux
+pub fn calculate_parkinson_volatility(
+    highs: &[f64],
+    lows: &[f64],
+    period: usize,
+) -> Vec<Option<f64>> {
+    let squared: Vec<f64> = highs
+        .iter()
+        .zip(lows)
+        .map(|(high, low)| (high / low).ln().powi(2))
+        .collect();
+
+    (0..squared.len())
+        .map(|i| {
+            if period == 0 || i + 1 < period {
+                return None;
+            }
+            let sum: f64 = squared[i + 1 - period..=i].iter().sum();
+            Some((sum / (4.0 * period as f64 * std::f64::consts::LN_2)).sqrt())
+        })
+        .collect()
+}
+
+/// Volatilité des bougies de `range` (open_time en ms), bougies sans valeur
+/// omises
+///
+/// annualize: volatilité historique seulement (Parkinson jamais annualisée)
+#[allow(clippy::too_many_arguments)]
+pub fn volatility_series(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    range: RangeInclusive<i64>,
+    kind: VolatilityKind,
+    period: usize,
+    annualize: bool,
+) -> Result<Vec<VolatilityValue>> {
+    // Bougies de la plage et les `period` précédentes (fenêtre de la première)
+    let mut stmt = conn.prepare_cached(
+        "SELECT open_time, high, low, close FROM candlesticks
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+           AND open_time >= COALESCE(
+               (SELECT MIN(open_time) FROM (
+                    SELECT open_time FROM candlesticks
+                    WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3 AND open_time < ?4
+                    ORDER BY open_time DESC LIMIT ?6)),
+               ?4)
+           AND open_time <= ?5
+         ORDER BY open_time ASC",
+    )?;
+    let candles = stmt
+        .query_map(
+            params![
+                provider,
+                symbol,
+                timeframe,
+                range.start(),
+                range.end(),
+                period as i64
+            ],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, f64>(1)?,
+                    row.get::<_, f64>(2)?,
+                    row.get::<_, f64>(3)?,
+                ))
+            },
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let values = match kind {
+        VolatilityKind::Historical => {
+            let closes: Vec<f64> = candles.iter().map(|c| c.3).collect();
+            calculate_historical_volatility(&closes, period, annualize, candles_per_day(timeframe)?)
+        }
+        VolatilityKind::Parkinson => {
+            let highs: Vec<f64> = candles.iter().map(|c| c.1).collect();
+            let lows: Vec<f64> = candles.iter().map(|c| c.2).collect();
+            calculate_parkinson_volatility(&highs, &lows, period)
+        }
+    };
+
+    Ok(candles
+        .iter()
+        .zip(values)
+        .filter(|((open_time, ..), _)| range.contains(open_time))
+        .filter_map(|((open_time, ..), volatility)| {
+            volatility.map(|volatility| VolatilityValue {
+                open_time: *open_time,
+                volatility,
+            })
+        })
+        .collect())
+}
+
+/// Calcule et enregistre la volatilité historique des bougies de
+/// [start_time, end_time] dans historical_volatility (valeurs existantes
+/// de même période et même annualisation remplacées), en une transaction
+///
+/// RETOUR: Nombre de valeurs enregistrées
+#[allow(clippy::too_many_arguments)]
+pub fn store_historical_volatility(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    period: usize,
+    annualize: bool,
+    start_time: i64,
+    end_time: i64,
+) -> Result<i64> {
+    let values = volatility_series(
+        conn,
+        provider,
+        symbol,
+        timeframe,
+        start_time..=end_time,
+        VolatilityKind::Historical,
+        period,
+        annualize,
+    )?;
+
+    let tx = conn.transaction()?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT OR REPLACE INTO historical_volatility
+                 (provider, symbol, timeframe, open_time, period, annualized, volatility)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )?;
+        for value in &values {
+            stmt.execute(params![
+                provider,
+                symbol,
+                timeframe,
+                value.open_time,
+                period as i64,
+                annualize,
+                value.volatility
+            ])?;
+        }
+    }
+    tx.commit()?;
+
+    Ok(values.len() as i64)
+}
@@ -0,0 +1,176 @@
+/// Module du VWAP (Volume Weighted Average Price) ancré par session
+///
+/// ALGORITHME:
+/// - Prix typique de chaque bougie: (high + low + close) / 3
+/// - VWAP[i] = Σ prix typique × volume / Σ volume, sommes cumulées depuis
+///   le début de la session de la bougie i
+/// - Session: période `anchor` (1d par défaut: remise à zéro à minuit UTC),
+///   alignée comme les bougies (utils::round_to_timeframe: lundi pour 1w,
+///   1er du mois pour 1M)
+///
+/// SUBTILITÉ: volume cumulé nul (début de session sans échange, bougies
+/// interpolées): VWAP = prix typique de la bougie, plutôt qu'une division
+/// par zéro
+///
+/// Le VWAP d'une bougie ne dépend que des bougies de sa session: une plage
+/// est calculée depuis le début de session de sa première bougie
+/// (vwap_series), pas depuis la première bougie stockée
+use crate::database::query_candle_records;
+use crate::error::Result;
+use crate::indicators::values::{
+    ComponentSeries, Indicator, IndicatorValue, VALUE_COMPONENT, store_component_series,
+};
+use crate::models::CandleRecord;
+use crate::utils::{parse_timeframe, round_to_timeframe};
+use rusqlite::Connection;
+use std::ops::RangeInclusive;
+
+/// Session usuelle: journée UTC
+pub const DEFAULT_VWAP_ANCHOR: &str = "1d";
+
+/// VWAP de chaque bougie d'une série triée, remis à zéro à chaque session
+/// de `anchor` (un timeframe: "1d", "4h", "1w"...)
+///
+/// EXEMPLE: deux bougies 12h de la même journée, prix typiques 10 puis 20,
+/// volumes 1 et 3 → [10, 17.5]; la bougie suivante ouvre une nouvelle
+/// session et repart de son seul prix typique
+///
+/// ERREURS: ValidationError si `anchor` n'est pas un timeframe connu
+pub fn calculate_vwap(candles: &[CandleRecord], anchor: &str) -> Result<Vec<f64>> {
+    parse_timeframe(anchor)?;
+
+    let mut session = None;
+    let (mut price_volume, mut volume) = (0.0, 0.0);
+    candles
+        .iter()
+        .map(|candle| {
+            let start = round_to_timeframe(candle.open_time, anchor)?;
+            if session != Some(start) {
+                session = Some(start);
+                (price_volume, volume) = (0.0, 0.0);
+            }
+            let typical = (candle.high + candle.low + candle.close) / 3.0;
+            price_volume += typical * candle.volume;
+            volume += candle.volume;
+            Ok(if volume > 0.0 {
+                price_volume / volume
+            } else {
+                typical
+            })
+        })
+        .collect()
+}
+
+/// VWAP ancré comme Indicator (paramètre: anchor)
+pub struct Vwap {
+    anchor: String,
+}
+
+impl Vwap {
+    /// ERREURS: ValidationError si `anchor` n'est pas un timeframe connu
+    pub fn new(anchor: &str) -> Result<Self> {
+        parse_timeframe(anchor)?;
+        Ok(Vwap {
+            anchor: anchor.to_string(),
+        })
+    }
+
+    /// Période des sessions (timeframe)
+    pub fn anchor(&self) -> &str {
+        &self.anchor
+    }
+}
+
+impl Default for Vwap {
+    /// Sessions d'une journée UTC
+    fn default() -> Self {
+        Vwap {
+            anchor: DEFAULT_VWAP_ANCHOR.to_string(),
+        }
+    }
+}
+
+impl Indicator for Vwap {
+    fn name(&self) -> &str {
+        "vwap"
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::json!({ "anchor": self.anchor })
+    }
+
+    fn compute(&self, candles: &[CandleRecord]) -> Vec<Option<f64>> {
+        // anchor validé par new, open_time stockés représentables: pas
+        // d'erreur en pratique
+        calculate_vwap(candles, &self.anchor).map_or_else(
+            |_| vec![None; candles.len()],
+            |values| values.into_iter().map(Some).collect(),
+        )
+    }
+}
+
+/// VWAP des bougies de `range` (open_time en ms), calculé depuis le début
+/// de session de la première bougie de la plage
+pub fn vwap_series(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    vwap: &Vwap,
+    range: RangeInclusive<i64>,
+) -> Result<Vec<IndicatorValue>> {
+    let first = round_to_timeframe(*range.start(), &vwap.anchor)?;
+    let candles = query_candle_records(
+        conn,
+        provider,
+        symbol,
+        timeframe,
+        first..=*range.end(),
+        None,
+    )?;
+
+    Ok(candles
+        .iter()
+        .zip(calculate_vwap(&candles, &vwap.anchor)?)
+        .filter(|(candle, _)| range.contains(&candle.open_time))
+        .map(|(candle, value)| IndicatorValue {
+            open_time: candle.open_time,
+            value,
+        })
+        .collect())
+}
+
+/// Calcule et enregistre le VWAP des bougies de [start_time, end_time] dans
+/// indicator_values (valeurs existantes remplacées), en relisant seulement
+/// les bougies depuis le début de session de start_time
+///
+/// RETOUR: Nombre de valeurs enregistrées
+pub fn recalculate_vwap_for_range(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    vwap: &Vwap,
+    start_time: i64,
+    end_time: i64,
+) -> Result<i64> {
+    let values = vwap_series(
+        conn,
+        provider,
+        symbol,
+        timeframe,
+        vwap,
+        start_time..=end_time,
+    )?;
+    store_component_series(
+        conn,
+        provider,
+        symbol,
+        timeframe,
+        vwap,
+        &[ComponentSeries {
+            component: VALUE_COMPONENT.to_string(),
+            values,
+        }],
+    )
+}
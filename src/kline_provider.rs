@@ -0,0 +1,85 @@
+/// Module des sources de klines du récupérateur
+///
+/// ARCHITECTURE:
+/// - KlineProvider: un seul appel, GET /api/v3/klines (voir CandleRetriever)
+/// - Market (binance-rs): implémentation réelle
+/// - MockKlineProvider: batches préchargés, pour tester le backfill sans réseau
+use crate::error::{CandleError, Result};
+use binance::market::Market;
+use binance::model::{KlineSummaries, KlineSummary};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Source de klines (bougies brutes, prix en texte comme l'API Binance)
+///
+/// Mêmes paramètres que GET /api/v3/klines: limit, start et end en
+/// millisecondes, bougies triées de la plus ancienne à la plus récente
+pub trait KlineProvider {
+    fn get_klines(
+        &self,
+        symbol: &str,
+        interval: &str,
+        limit: Option<u16>,
+        start: Option<u64>,
+        end: Option<u64>,
+    ) -> Result<Vec<KlineSummary>>;
+}
+
+impl KlineProvider for Market {
+    fn get_klines(
+        &self,
+        symbol: &str,
+        interval: &str,
+        limit: Option<u16>,
+        start: Option<u64>,
+        end: Option<u64>,
+    ) -> Result<Vec<KlineSummary>> {
+        let klines = Market::get_klines(self, symbol, interval, limit, start, end)
+            .map_err(CandleError::from)?;
+        match klines {
+            KlineSummaries::AllKlineSummaries(klines) => Ok(klines),
+        }
+    }
+}
+
+/// Source de test: chaque appel renvoie le batch suivant, dans l'ordre de
+/// chargement, quels que soient les paramètres
+///
+/// File vide: batch vide, comme l'API au-delà de la limite historique
+#[derive(Debug, Default)]
+pub struct MockKlineProvider {
+    /// SUBTILITÉ RUST: Mutex car get_klines prend &self (le récupérateur
+    /// ne détient qu'une référence partagée)
+    batches: Mutex<VecDeque<Vec<KlineSummary>>>,
+}
+
+impl MockKlineProvider {
+    pub fn new(batches: impl IntoIterator<Item = Vec<KlineSummary>>) -> Self {
+        MockKlineProvider {
+            batches: Mutex::new(batches.into_iter().collect()),
+        }
+    }
+
+    /// Ajoute un batch en fin de file
+    pub fn push_batch(&self, batch: Vec<KlineSummary>) {
+        self.batches.lock().unwrap().push_back(batch);
+    }
+
+    /// Batches pas encore servis
+    pub fn remaining(&self) -> usize {
+        self.batches.lock().unwrap().len()
+    }
+}
+
+impl KlineProvider for MockKlineProvider {
+    fn get_klines(
+        &self,
+        _symbol: &str,
+        _interval: &str,
+        _limit: Option<u16>,
+        _start: Option<u64>,
+        _end: Option<u64>,
+    ) -> Result<Vec<KlineSummary>> {
+        Ok(self.batches.lock().unwrap().pop_front().unwrap_or_default())
+    }
+}
@@ -3,9 +3,35 @@
 /// Cette bibliothèque expose tous les modules nécessaires pour récupérer,
 /// stocker et interpoler des données de chandeliers depuis Binance
 // Déclaration des modules publics
+pub mod analytics;
+pub mod auth;
+pub mod cache;
+pub mod circuit_breaker;
+pub mod config;
 pub mod database;
+pub mod downtime;
+pub mod error;
+pub mod export;
 pub mod gap_filler;
+pub mod import;
+pub mod indicators;
+pub mod kline_provider;
+pub mod logging;
+pub mod merge;
+pub mod metrics;
+pub mod migrations;
+pub mod models;
+pub mod patterns;
+pub mod pool;
+pub mod rate_limiter;
+pub mod realtime;
+pub mod retention;
 pub mod retriever;
+pub mod status_report;
+#[cfg(feature = "test-fixtures")]
+pub mod test_fixtures;
 pub mod timeframe_status;
+pub mod tls;
 pub mod utils;
+pub mod verification_history;
 pub mod verify;
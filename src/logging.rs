@@ -0,0 +1,41 @@
+/// Module d'initialisation des logs structurés (tracing)
+///
+/// - Niveau: RUST_LOG (ex: "debug", "rust_candles_retriever=trace"),
+///   sinon le niveau par défaut fourni par le binaire
+/// - Format: texte lisible, ou JSON si LOG_FORMAT=json (agrégateurs de logs)
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt;
+use tracing_subscriber::prelude::*;
+
+/// Initialise le subscriber global (logs sur la sortie standard)
+///
+/// NOTE: try_init ignore un second appel (tests, binaires enchaînés)
+pub fn init_tracing(default_level: &str) {
+    init_with_writer(default_level, std::io::stdout);
+}
+
+/// Comme init_tracing, logs sur la sortie d'erreur: la sortie standard reste
+/// réservée à une sortie exploitable par un script (main --output-format json)
+pub fn init_tracing_stderr(default_level: &str) {
+    init_with_writer(default_level, std::io::stderr);
+}
+
+fn init_with_writer<W>(default_level: &str, writer: W)
+where
+    W: for<'w> fmt::MakeWriter<'w> + Send + Sync + 'static,
+{
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_level));
+    let json = std::env::var("LOG_FORMAT").is_ok_and(|v| v.eq_ignore_ascii_case("json"));
+
+    let registry = tracing_subscriber::registry().with(filter);
+    let _ = if json {
+        registry
+            .with(fmt::layer().json().with_writer(writer))
+            .try_init()
+    } else {
+        registry
+            .with(fmt::layer().with_target(false).with_writer(writer))
+            .try_init()
+    };
+}
@@ -5,12 +5,42 @@
 /// - Parcourt tous les timeframes simultanément
 /// - Retire dynamiquement les timeframes qui n'insèrent plus rien
 /// - Arrêt automatique quand tous les timeframes sont épuisés ou date limite atteinte
+/// - --dry-run: mêmes lectures de l'API, aucune écriture, bilan par timeframe
+/// - --output-format json: un objet JSON par ligne (BackfillEvent) sur la
+///   sortie standard, logs sur la sortie d'erreur
+/// - --store-returns: rendements de chaque timeframe sélectionné enregistrés
+///   dans la table returns en fin de récupération
 use anyhow::Result;
 use binance::api::*;
 use binance::market::*;
-use chrono::{DateTime, NaiveDateTime, Utc};
-use clap::Parser;
-use rust_candles_retriever::{database::DatabaseManager, retriever::CandleRetriever};
+use clap::{Parser, ValueEnum};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use rusqlite::{Connection, params};
+use rust_candles_retriever::{
+    analytics::returns::store_returns,
+    circuit_breaker::CircuitBreaker,
+    config::{BackfillOptions, Config},
+    database::DatabaseManager,
+    error::CandleError,
+    gap_filler::{FillStrategy, GapFiller, GapKind},
+    indicators::registry::IndicatorSpec,
+    logging::{init_tracing, init_tracing_stderr},
+    rate_limiter::RateLimiter,
+    retention::RetentionPolicy,
+    retriever::{CandleRetriever, DryRunReport},
+    timeframe_status::{Coverage, TimeframeStatus},
+    utils::{format_timestamp_ms, parse_date_ms, parse_timeframe},
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::IsTerminal;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, error, info, warn};
+
+/// Date d'ouverture de Binance (2017-08-17), utilisée comme début par défaut
+/// pour estimer le nombre total de bougies quand --start-date est absent
+const BINANCE_LAUNCH_MS: i64 = 1_502_928_000_000;
 
 /// Arguments CLI du programme
 #[derive(Parser, Debug)]
@@ -27,46 +57,367 @@ struct Args {
     /// Fichier de base de données
     #[arg(long, default_value = "candlesticks.db")]
     db_file: String,
+
+    /// Stratégie de remplissage des gaps (linear, ffill, zero-volume, none)
+    #[arg(long, default_value = "linear")]
+    fill_strategy: FillStrategy,
+
+    /// Purge les bougies interpolées puis recomble les gaps avec --fill-strategy
+    /// avant la récupération (agrégation d'un timeframe inférieur en priorité)
+    #[arg(long)]
+    refill_interpolated: bool,
+
+    /// Force l'affichage des barres de progression (même hors terminal)
+    #[arg(long, overrides_with = "no_progress")]
+    progress: bool,
+
+    /// Désactive les barres de progression
+    #[arg(long)]
+    no_progress: bool,
+
+    /// Purge les bougies hors rétention ([retention] de la configuration) puis quitte
+    #[arg(long)]
+    prune: bool,
+
+    /// Fichier de configuration TOML pour --prune (par défaut: ./config.toml s'il existe)
+    #[arg(long)]
+    config: Option<String>,
+
+    /// Réinitialise la progression d'un timeframe (ou `all`) avant la récupération:
+    /// un timeframe marqué complet est de nouveau récupéré depuis le début
+    #[arg(long)]
+    reset_timeframe: Option<String>,
+
+    /// Timeframes à récupérer, séparés par des virgules (par défaut: 5m à 3d)
+    #[arg(long, value_delimiter = ',')]
+    timeframes: Option<Vec<String>>,
+
+    /// Timeframes retirés de la sélection (ex: --exclude-timeframes 5m,15m)
+    #[arg(long, value_delimiter = ',')]
+    exclude_timeframes: Option<Vec<String>>,
+
+    /// Indicateurs recalculés après chaque batch inséré, nom ou nom:période
+    /// séparés par des virgules (ex: --indicators rsi:14,ema:20)
+    #[arg(long, value_delimiter = ',')]
+    indicators: Option<Vec<IndicatorSpec>>,
+
+    /// Parcourt l'API sans rien écrire puis affiche, par timeframe, les bougies
+    /// manquantes, la période couverte et le nombre d'appels nécessaires
+    #[arg(long, conflicts_with_all = ["prune", "reset_timeframe", "refill_interpolated"])]
+    dry_run: bool,
+
+    /// Format de sortie: text (messages lisibles) ou json (un événement JSON par ligne)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    output_format: OutputFormat,
+
+    /// Enregistre les rendements (pct, log, cumulative) de chaque timeframe
+    /// sélectionné dans la table returns, après la récupération
+    #[arg(long, conflicts_with = "dry_run")]
+    store_returns: bool,
+}
+
+/// Format de la sortie standard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// Événement de la sortie --output-format json
+///
+/// SUBTILITÉ SERDE: tag = "event" place le nom de la variante dans le champ
+/// "event" de l'objet: {"event": "batch_complete", "timeframe": "5m", ...}
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum BackfillEvent<'a> {
+    Iteration {
+        number: usize,
+        active_timeframes: &'a [&'a str],
+    },
+    BatchComplete {
+        timeframe: &'a str,
+        inserted: i64,
+        /// true: le timeframe est retiré du pool
+        is_exhausted: bool,
+    },
+    BatchError {
+        timeframe: &'a str,
+        error: String,
+    },
+    DryRun(&'a DryRunReport),
+    Pruned {
+        symbol: &'a str,
+        timeframe: &'a str,
+        deleted: usize,
+        cutoff: i64,
+    },
+    Done,
+}
+
+/// Écrit un événement sur une ligne de la sortie standard
+fn emit(event: &BackfillEvent) {
+    match serde_json::to_string(event) {
+        Ok(line) => println!("{}", line),
+        Err(e) => error!(error = %e, "Événement JSON impossible à sérialiser"),
+    }
+}
+
+/// Réinitialise la progression d'un timeframe (`all`: tous les timeframes)
+fn reset_timeframe(db: &DatabaseManager, symbol: &str, timeframe: &str) -> Result<()> {
+    if timeframe == "all" {
+        let reset = TimeframeStatus::reset_all(db.connection(), "binance", symbol)?;
+        info!(symbol = %symbol, reset, "Progression réinitialisée pour tous les timeframes");
+        return Ok(());
+    }
+
+    parse_timeframe(timeframe)?;
+    TimeframeStatus::reset(db.connection(), "binance", symbol, timeframe)?;
+    info!(symbol = %symbol, timeframe = %timeframe, "Progression réinitialisée");
+    Ok(())
+}
+
+/// Barres de progression par timeframe, groupées sous un MultiProgress
+///
+/// DESIGN: Quand les barres sont masquées (hors TTY ou --no-progress),
+/// les messages détaillés sont affichés en texte comme avant. En sortie JSON
+/// (quiet), ni barres ni messages: seuls les BackfillEvent sont écrits
+struct BackfillProgress {
+    multi: MultiProgress,
+    bars: HashMap<String, ProgressBar>,
+    start_ms: i64,
+    quiet: bool,
+}
+
+impl BackfillProgress {
+    /// Crée une barre par timeframe actif
+    fn new(enabled: bool, quiet: bool, timeframes: &[&str], start_ms: i64) -> Result<Self> {
+        let target = if enabled && !quiet {
+            ProgressDrawTarget::stdout()
+        } else {
+            ProgressDrawTarget::hidden()
+        };
+        let multi = MultiProgress::with_draw_target(target);
+        let style = ProgressStyle::with_template(
+            "[{bar:40}] {prefix}: {pos}/{len} candles ({percent}%) {msg}",
+        )?
+        .progress_chars("## ");
+
+        let mut bars = HashMap::new();
+        for tf in timeframes {
+            let bar = multi.add(ProgressBar::new(0));
+            bar.set_style(style.clone());
+            bar.set_prefix(tf.to_string());
+            bars.insert(tf.to_string(), bar);
+        }
+
+        Ok(BackfillProgress {
+            multi,
+            bars,
+            start_ms,
+            quiet,
+        })
+    }
+
+    /// Met à jour la barre d'un timeframe depuis le contenu de la base
+    ///
+    /// expected_total = (now_ms - start_ms) / intervalle
+    fn update(&self, conn: &Connection, symbol: &str, timeframe: &str) -> Result<()> {
+        if self.multi.is_hidden() {
+            return Ok(());
+        }
+        let Some(bar) = self.bars.get(timeframe) else {
+            return Ok(());
+        };
+
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as i64;
+        let interval = parse_timeframe(timeframe)?;
+        let expected_total = ((now_ms - self.start_ms) / interval).max(0) as u64;
+
+        let stored: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM candlesticks
+             WHERE provider = 'binance' AND symbol = ?1 AND timeframe = ?2
+                   AND open_time >= ?3",
+            params![symbol, timeframe, self.start_ms],
+            |row| row.get(0),
+        )?;
+
+        bar.set_length(expected_total);
+        bar.set_position((stored as u64).min(expected_total));
+        Ok(())
+    }
+
+    /// Termine la barre d'un timeframe épuisé
+    fn finish(&self, timeframe: &str) {
+        if let Some(bar) = self.bars.get(timeframe) {
+            bar.finish_with_message("✓");
+        }
+    }
+
+    /// Affiche un message important (au-dessus des barres si elles sont visibles)
+    fn println(&self, msg: &str) {
+        if self.quiet {
+            return;
+        }
+        if self.multi.is_hidden() {
+            info!("{}", msg);
+        } else {
+            let _ = self.multi.println(msg);
+        }
+    }
+
+    /// Message détaillé, niveau debug (RUST_LOG=debug), masqué sous les barres
+    fn verbose(&self, msg: &str) {
+        if self.multi.is_hidden() && !self.quiet {
+            debug!("{}", msg);
+        }
+    }
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
     let symbol = args.symbol.to_uppercase();
 
-    println!("Démarrage de la récupération pour le symbole: {}", symbol);
+    // Niveau par défaut info, surchargé par RUST_LOG (LOG_FORMAT=json possible)
+    let json = args.output_format == OutputFormat::Json;
+    if json {
+        init_tracing_stderr("info");
+    } else {
+        init_tracing("info");
+    }
+
+    info!(symbol = %symbol, "Démarrage de la récupération");
 
     // Initialiser la base de données
     let mut db = DatabaseManager::new(&args.db_file)?;
-    println!("Base de données initialisée.\n");
+    info!(db_file = %args.db_file, "Base de données initialisée");
 
-    // Timeframes supportés - liste dynamique
-    let mut active_timeframes: Vec<&str> = vec![
-        "5m", "15m", "30m", "1h", "2h", "4h", "6h", "8h", "12h", "1d", "3d",
-    ];
+    if args.prune {
+        return run_prune(&mut db, &symbol, args.config.as_deref(), json);
+    }
+
+    if let Some(timeframe) = args.reset_timeframe.as_deref() {
+        reset_timeframe(&db, &symbol, timeframe)?;
+    }
+
+    // Timeframes sélectionnés - liste dynamique (les timeframes épuisés sont retirés)
+    let backfill = BackfillOptions {
+        timeframes: args.timeframes.clone().unwrap_or_default(),
+        exclude_timeframes: args.exclude_timeframes.clone().unwrap_or_default(),
+        indicators: args.indicators.clone().unwrap_or_default(),
+    };
+    let mut active_timeframes: Vec<&str> = backfill.active_timeframes()?;
+    let indicators = backfill.indicator_registry()?;
 
     // Initialiser le client Binance
     let market: Market = Binance::new(None, None);
 
+    // Budget de poids partagé (BINANCE_WEIGHT_LIMIT, 1200 par défaut)
+    let rate_limiter = Arc::new(RateLimiter::from_env());
+
+    // Disjoncteur partagé: 5 échecs consécutifs → pause de 60s
+    let circuit_breaker = Arc::new(CircuitBreaker::default());
+
     // Parser la date de début si fournie
     let start_timestamp_ms = parse_start_date(args.start_date.as_deref())?;
 
+    // Recalcul des bougies synthétiques existantes (anciennes exécutions)
+    if args.refill_interpolated {
+        let end_ms = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as i64;
+        for tf in &active_timeframes {
+            let (purged, filled) = GapFiller::refill(
+                db.connection_mut(),
+                "binance",
+                &symbol,
+                tf,
+                start_timestamp_ms.unwrap_or(0),
+                end_ms,
+                args.fill_strategy,
+            )?;
+            info!(
+                timeframe = %tf,
+                purged, filled, "Bougies interpolées regénérées"
+            );
+
+            // Gaps aux extrémités (ex: dernières bougies jamais récupérées)
+            if let Some((expected_start, expected_end)) =
+                TimeframeStatus::expected_bounds(db.connection(), "binance", &symbol, tf, end_ms)
+            {
+                let gaps = GapFiller::list_gaps_with_bounds(
+                    db.connection(),
+                    "binance",
+                    &symbol,
+                    tf,
+                    expected_start.max(start_timestamp_ms.unwrap_or(0)),
+                    expected_end,
+                )?;
+                for gap in gaps.iter().filter(|g| g.kind != GapKind::Between) {
+                    warn!(
+                        timeframe = %tf,
+                        missing = gap.missing_candles,
+                        kind = ?gap.kind,
+                        from = %format_timestamp_ms(gap.after_open_time + parse_timeframe(tf)?),
+                        "Bougies manquantes hors des gaps internes"
+                    );
+                }
+            }
+        }
+    }
+
+    // Barres de progression: --progress / --no-progress, sinon seulement sur un TTY
+    // (jamais en dry-run: la base, d'où elles sont calculées, ne change pas)
+    let show_progress = if args.no_progress || args.dry_run {
+        false
+    } else {
+        args.progress || std::io::stdout().is_terminal()
+    };
+    let progress = BackfillProgress::new(
+        show_progress,
+        json,
+        &active_timeframes,
+        start_timestamp_ms.unwrap_or(BINANCE_LAUNCH_MS),
+    )?;
+
+    // Bilans du dry-run, un par timeframe sélectionné, avec la couverture
+    // déjà stockée
+    let mut dry_run_reports: Vec<DryRunReport> = if args.dry_run {
+        active_timeframes
+            .iter()
+            .map(|tf| {
+                Ok(DryRunReport {
+                    stored: TimeframeStatus::coverage(db.connection(), "binance", &symbol, tf)?,
+                    ..DryRunReport::new(tf)
+                })
+            })
+            .collect::<Result<_>>()?
+    } else {
+        Vec::new()
+    };
+
     // Boucle principale: traiter tous les timeframes simultanément
+    // (span: chaque événement de la boucle porte le symbole)
+    let _span = tracing::info_span!("run_backfill", symbol = %symbol).entered();
     let mut iteration = 0;
     loop {
         iteration += 1;
-        println!("═══ Itération #{} ═══", iteration);
-        println!("Timeframes actifs: {:?}\n", active_timeframes);
+        progress.verbose(&format!("═══ Itération #{} ═══", iteration));
+        progress.verbose(&format!("Timeframes actifs: {:?}", active_timeframes));
 
         if active_timeframes.is_empty() {
-            println!("✅ Tous les timeframes ont été traités complètement!");
+            progress.println("✅ Tous les timeframes ont été traités complètement!");
             break;
         }
+        if json {
+            emit(&BackfillEvent::Iteration {
+                number: iteration,
+                active_timeframes: &active_timeframes,
+            });
+        }
 
         let mut exhausted_timeframes = Vec::new();
 
         // Traiter chaque timeframe actif
         for tf in &active_timeframes {
-            println!("→ Traitement du timeframe {}...", tf);
+            progress.verbose(&format!("→ Traitement du timeframe {}...", tf));
 
             let mut retriever = CandleRetriever::new(
                 &market,
@@ -74,61 +425,198 @@ fn main() -> Result<()> {
                 &symbol,
                 tf,
                 start_timestamp_ms,
+                Arc::clone(&rate_limiter),
+                Arc::clone(&circuit_breaker),
+                args.fill_strategy,
             );
+            if let Some(report) = dry_run_reports.iter_mut().find(|r| r.timeframe == *tf) {
+                retriever = retriever.dry_run(report);
+            }
+            if !indicators.is_empty() {
+                retriever = retriever.indicators(&indicators);
+            }
 
             match retriever.fetch_one_batch() {
                 Ok((inserted, is_exhausted)) => {
                     if inserted > 0 {
-                        println!("  ✓ {} nouvelles bougies insérées", inserted);
+                        progress.verbose(&format!("  ✓ {} nouvelles bougies insérées", inserted));
                     }
 
                     // Retirer du pool si: date limite atteinte OU plus d'insertions
-                    if is_exhausted || inserted == 0 {
+                    // (dry-run: jamais d'insertion, seul is_exhausted compte)
+                    let removed = is_exhausted || (inserted == 0 && !args.dry_run);
+                    if json {
+                        emit(&BackfillEvent::BatchComplete {
+                            timeframe: tf,
+                            inserted,
+                            is_exhausted: removed,
+                        });
+                    }
+                    if removed {
                         if is_exhausted {
-                            println!("  🏁 Timeframe {} épuisé (date limite atteinte)", tf);
+                            progress.println(&format!(
+                                "  🏁 Timeframe {} épuisé (date limite atteinte)",
+                                tf
+                            ));
                         } else {
-                            println!("  🏁 Timeframe {} épuisé (plus de nouvelles données)", tf);
+                            progress.println(&format!(
+                                "  🏁 Timeframe {} épuisé (plus de nouvelles données)",
+                                tf
+                            ));
                         }
                         exhausted_timeframes.push(*tf);
                     }
                 }
+                Err(e @ CandleError::CircuitBreakerOpen { retry_after_ms }) => {
+                    if json {
+                        emit(&BackfillEvent::BatchError {
+                            timeframe: tf,
+                            error: e.to_string(),
+                        });
+                    }
+                    warn!(retry_after_ms, "API en pause (circuit breaker ouvert)");
+                    std::thread::sleep(std::time::Duration::from_millis(retry_after_ms));
+                }
                 Err(e) => {
-                    eprintln!("  ⚠  Erreur: {}", e);
+                    if json {
+                        emit(&BackfillEvent::BatchError {
+                            timeframe: tf,
+                            error: e.to_string(),
+                        });
+                    }
+                    error!(timeframe = %tf, error = %e, "Échec du batch");
                 }
             }
+
+            progress.update(db.connection(), &symbol, tf)?;
         }
 
         // Retirer les timeframes épuisés du pool actif
         active_timeframes.retain(|tf| !exhausted_timeframes.contains(tf));
 
         if !exhausted_timeframes.is_empty() {
-            println!(
-                "\n🗑  Timeframes retirés du pool: {:?}",
+            for tf in &exhausted_timeframes {
+                progress.finish(tf);
+            }
+            progress.verbose(&format!(
+                "🗑  Timeframes retirés du pool: {:?}",
                 exhausted_timeframes
-            );
+            ));
         }
 
-        println!();
-
         // Pause pour respecter les rate limits
         std::thread::sleep(std::time::Duration::from_millis(200));
     }
 
-    println!("Toutes les opérations sont terminées.");
+    if json {
+        for report in &dry_run_reports {
+            emit(&BackfillEvent::DryRun(report));
+        }
+        emit(&BackfillEvent::Done);
+    } else if args.dry_run {
+        print_dry_run(&symbol, &dry_run_reports);
+    }
+
+    // Rendements recalculés sur toute la série (cumulés depuis la première bougie)
+    if args.store_returns {
+        for tf in backfill.active_timeframes()? {
+            let stored = store_returns(db.connection_mut(), "binance", &symbol, tf)?;
+            info!(timeframe = %tf, stored, "Rendements enregistrés");
+        }
+    }
+
+    info!("Toutes les opérations sont terminées");
     Ok(())
 }
 
-/// Parse une date au format YYYY-MM-DD en timestamp millisecondes
-fn parse_start_date(date_str: Option<&str>) -> Result<Option<i64>> {
-    match date_str {
-        Some(date) => {
-            let naive_date = NaiveDateTime::parse_from_str(
-                &(date.to_string() + " 00:00:00"),
-                "%Y-%m-%d %H:%M:%S",
-            )?;
-            let datetime_utc = DateTime::<Utc>::from_naive_utc_and_offset(naive_date, Utc);
-            Ok(Some(datetime_utc.timestamp_millis()))
+/// Bilan du dry-run: une ligne par timeframe puis le total des appels
+fn print_dry_run(symbol: &str, reports: &[DryRunReport]) {
+    println!("Dry-run {} (aucune écriture):", symbol);
+    for report in reports {
+        if let Coverage::Series {
+            stored,
+            expected,
+            missing,
+            pct_complete,
+            ..
+        } = report.stored
+        {
+            println!(
+                "  {}: {}/{} bougies stockées ({:.1} %), {} dans des trous",
+                report.timeframe, stored, expected, pct_complete, missing
+            );
+        }
+        if report.candles_available == 0 {
+            println!(
+                "  {}: aucune bougie manquante ({} appels)",
+                report.timeframe, report.estimated_batches
+            );
+        } else {
+            println!(
+                "  {}: {} bougies manquantes du {} au {}, {} appels",
+                report.timeframe,
+                report.candles_available,
+                format_timestamp_ms(report.oldest_available_ms),
+                format_timestamp_ms(report.newest_available_ms),
+                report.estimated_batches
+            );
         }
-        None => Ok(None),
     }
+    println!(
+        "Total: {} appels klines",
+        reports.iter().map(|r| r.estimated_batches).sum::<usize>()
+    );
+}
+
+/// Mode --prune: supprime les bougies du symbole plus anciennes que la
+/// rétention de leur timeframe
+///
+/// DESIGN: pas de backfill dans la même exécution, il re-téléchargerait
+/// l'historique qui vient d'être purgé
+fn run_prune(
+    db: &mut DatabaseManager,
+    symbol: &str,
+    config: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let config = Config::load(config)?;
+    let policy = RetentionPolicy::from_config(&config.retention)?;
+    if policy.is_empty() {
+        if json {
+            emit(&BackfillEvent::Done);
+        } else {
+            println!("Aucune rétention configurée (section [retention] de la configuration)");
+        }
+        return Ok(());
+    }
+
+    let now_ms = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as i64;
+    for report in policy.apply(db.connection_mut(), "binance", Some(symbol), now_ms)? {
+        if json {
+            emit(&BackfillEvent::Pruned {
+                symbol: &report.symbol,
+                timeframe: &report.timeframe,
+                deleted: report.deleted,
+                cutoff: report.cutoff,
+            });
+            continue;
+        }
+        println!(
+            "🗑  {} {}: {} bougies antérieures au {} supprimées",
+            report.symbol,
+            report.timeframe,
+            report.deleted,
+            format_timestamp_ms(report.cutoff)
+        );
+    }
+    if json {
+        emit(&BackfillEvent::Done);
+    }
+
+    Ok(())
+}
+
+/// Parse une date au format YYYY-MM-DD en timestamp millisecondes
+fn parse_start_date(date_str: Option<&str>) -> Result<Option<i64>> {
+    Ok(date_str.map(parse_date_ms).transpose()?)
 }
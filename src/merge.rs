@@ -0,0 +1,309 @@
+/// Module de fusion de bases SQLite
+///
+/// Consolide plusieurs fichiers .db (une base par paire, copies de machines
+/// différentes...) dans une base unique, sans jamais écrire dans les sources
+///
+/// ALGORITHME (par fichier source):
+/// 1. ATTACH en lecture seule (URI mode=ro)
+/// 2. Vérification du schéma: toutes les colonnes de CandleRecord::COLUMNS
+/// 3. Par (provider, symbol): si la destination contient déjà autant de
+///    bougies que la source, la paire est sautée (reprise après interruption)
+/// 4. Sinon, une transaction: INSERT OR IGNORE des bougies puis fusion des
+///    lignes de timeframe_status
+/// 5. DETACH, y compris en cas d'erreur
+///
+/// SYNCHRONISATION (sync_database): variante incrémentale entre deux nœuds
+/// (l'un récupère, l'autre sert), par (provider, symbol, timeframe): seules
+/// les bougies plus récentes que la dernière de la destination sont copiées,
+/// sans recompter les paires ni re-télécharger l'historique
+///
+/// NOTE: seules candlesticks et timeframe_status sont copiées. Les tables
+/// d'indicateurs (indicator_values...) ne sont volontairement ni fusionnées
+/// ni synchronisées: valeurs dérivées des bougies, à recalculer sur la
+/// destination
+use crate::database::read_only_uri;
+use crate::error::{CandleError, Result};
+use crate::models::CandleRecord;
+use rusqlite::{Connection, params};
+use serde::Serialize;
+use std::path::Path;
+
+/// Alias de la base source attachée à la connexion de destination
+const SOURCE_ALIAS: &str = "merge_source";
+
+/// Résultat de la fusion d'une paire d'un fichier source
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MergeReport {
+    pub provider: String,
+    pub symbol: String,
+    /// Bougies de la paire dans la source
+    pub source_rows: i64,
+    /// Bougies effectivement ajoutées à la destination
+    pub inserted: usize,
+    /// Bougies déjà présentes dans la destination (même clé primaire)
+    pub conflicts: i64,
+    /// Paire sautée: la destination contient déjà autant de bougies
+    pub skipped: bool,
+}
+
+/// Résultat de la synchronisation d'une série d'un fichier source
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SyncReport {
+    pub provider: String,
+    pub symbol: String,
+    pub timeframe: String,
+    /// Bougies ajoutées à la destination (postérieures à sa dernière bougie)
+    pub inserted: usize,
+}
+
+/// Fusionne toutes les paires d'un fichier source dans `dest`
+///
+/// `dest` doit avoir le schéma complet (DatabaseManager::new). La source est
+/// ouverte en lecture seule: son contenu n'est jamais modifié
+///
+/// ERREURS: source illisible, schéma de candlesticks incomplet
+/// (ValidationError: ouvrir la source une fois avec le CLI pour la migrer)
+pub fn merge_database(dest: &mut Connection, source: &Path) -> Result<Vec<MergeReport>> {
+    with_attached_source(dest, source, merge_attached)
+}
+
+/// Copie dans `dest` les bougies de la source postérieures à la dernière
+/// bougie de chaque série de la destination (toutes les séries de la
+/// source, ou celles de `symbol`)
+///
+/// Une série absente de la destination est copiée en entier. La ligne
+/// timeframe_status de chaque série synchronisée est fusionnée comme par
+/// merge_database
+///
+/// SUBTILITÉ: les bougies manquantes *avant* la dernière bougie de la
+/// destination (trou comblé après coup dans la source) ne sont pas copiées:
+/// merge_database pour une copie complète
+///
+/// ERREURS: source illisible, schéma de candlesticks incomplet
+pub fn sync_database(
+    dest: &mut Connection,
+    source: &Path,
+    symbol: Option<&str>,
+) -> Result<Vec<SyncReport>> {
+    with_attached_source(dest, source, |dest| sync_attached(dest, symbol))
+}
+
+/// Attache la source en lecture seule le temps de `f`
+fn with_attached_source<T>(
+    dest: &mut Connection,
+    source: &Path,
+    f: impl FnOnce(&mut Connection) -> Result<T>,
+) -> Result<T> {
+    // ATTACH est interdit dans une transaction: la source est attachée une
+    // fois pour toutes, chaque paire a ensuite sa propre transaction
+    dest.execute(
+        &format!("ATTACH DATABASE ?1 AS {}", SOURCE_ALIAS),
+        [read_only_uri(source)],
+    )?;
+
+    let result = f(dest);
+
+    // Détacher même en cas d'erreur: la connexion reste réutilisable
+    let detached = dest.execute(&format!("DETACH DATABASE {}", SOURCE_ALIAS), []);
+    let value = result?;
+    detached?;
+    Ok(value)
+}
+
+/// Synchronisation de la source déjà attachée, série par série
+fn sync_attached(dest: &mut Connection, symbol: Option<&str>) -> Result<Vec<SyncReport>> {
+    check_source_schema(dest)?;
+
+    let series: Vec<(String, String, String)> = {
+        let mut stmt = dest.prepare(&format!(
+            "SELECT DISTINCT provider, symbol, timeframe FROM {}.candlesticks
+             WHERE ?1 IS NULL OR symbol = ?1
+             ORDER BY provider, symbol, timeframe",
+            SOURCE_ALIAS
+        ))?;
+        stmt.query_map([symbol], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<std::result::Result<_, _>>()?
+    };
+    let copy_status = source_has_status_table(dest)?;
+
+    let mut reports = Vec::with_capacity(series.len());
+    for (provider, symbol, timeframe) in series {
+        let tx = dest.transaction()?;
+        let inserted = tx.execute(
+            &format!(
+                "INSERT OR IGNORE INTO main.candlesticks ({columns})
+                 SELECT {columns} FROM {alias}.candlesticks
+                 WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+                   AND open_time > (
+                       SELECT COALESCE(MAX(open_time), -1) FROM main.candlesticks
+                       WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3)",
+                columns = CandleRecord::COLUMNS,
+                alias = SOURCE_ALIAS
+            ),
+            params![provider, symbol, timeframe],
+        )?;
+        if copy_status && inserted > 0 {
+            merge_status(&tx, &provider, &symbol, Some(&timeframe))?;
+        }
+        tx.commit()?;
+
+        reports.push(SyncReport {
+            provider,
+            symbol,
+            timeframe,
+            inserted,
+        });
+    }
+
+    Ok(reports)
+}
+
+/// Fusion de la source déjà attachée, paire par paire
+fn merge_attached(dest: &mut Connection) -> Result<Vec<MergeReport>> {
+    check_source_schema(dest)?;
+
+    let pairs: Vec<(String, String, i64)> = {
+        let mut stmt = dest.prepare(&format!(
+            "SELECT provider, symbol, COUNT(*) FROM {}.candlesticks
+             GROUP BY provider, symbol ORDER BY provider, symbol",
+            SOURCE_ALIAS
+        ))?;
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<std::result::Result<_, _>>()?
+    };
+    let copy_status = source_has_status_table(dest)?;
+
+    let mut reports = Vec::with_capacity(pairs.len());
+    for (provider, symbol, source_rows) in pairs {
+        let dest_rows: i64 = dest.query_row(
+            "SELECT COUNT(*) FROM main.candlesticks WHERE provider = ?1 AND symbol = ?2",
+            params![provider, symbol],
+            |row| row.get(0),
+        )?;
+        if dest_rows == source_rows {
+            reports.push(MergeReport {
+                provider,
+                symbol,
+                source_rows,
+                inserted: 0,
+                conflicts: 0,
+                skipped: true,
+            });
+            continue;
+        }
+
+        let tx = dest.transaction()?;
+        let inserted = tx.execute(
+            &format!(
+                "INSERT OR IGNORE INTO main.candlesticks ({columns})
+                 SELECT {columns} FROM {alias}.candlesticks
+                 WHERE provider = ?1 AND symbol = ?2",
+                columns = CandleRecord::COLUMNS,
+                alias = SOURCE_ALIAS
+            ),
+            params![provider, symbol],
+        )?;
+        if copy_status {
+            merge_status(&tx, &provider, &symbol, None)?;
+        }
+        tx.commit()?;
+
+        reports.push(MergeReport {
+            provider,
+            symbol,
+            source_rows,
+            inserted,
+            conflicts: source_rows - inserted as i64,
+            skipped: false,
+        });
+    }
+
+    Ok(reports)
+}
+
+/// Vérifie que la table candlesticks de la source a toutes les colonnes
+/// de CandleRecord::COLUMNS
+fn check_source_schema(dest: &Connection) -> Result<()> {
+    let columns = source_columns(dest, "candlesticks")?;
+    if columns.is_empty() {
+        return Err(CandleError::ValidationError(
+            "Table candlesticks absente de la source".to_string(),
+        ));
+    }
+    let missing: Vec<&str> = CandleRecord::COLUMNS
+        .split(',')
+        .map(str::trim)
+        .filter(|column| !columns.iter().any(|c| c == column))
+        .collect();
+    if !missing.is_empty() {
+        return Err(CandleError::ValidationError(format!(
+            "Schéma source à migrer, colonnes manquantes: {}",
+            missing.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// timeframe_status est copiée seulement si la source a le schéma courant
+fn source_has_status_table(dest: &Connection) -> Result<bool> {
+    let columns = source_columns(dest, "timeframe_status")?;
+    Ok([
+        "provider",
+        "symbol",
+        "timeframe",
+        "oldest_candle_time",
+        "newest_candle_time",
+        "is_complete",
+        "last_updated",
+    ]
+    .iter()
+    .all(|column| columns.iter().any(|c| c == column)))
+}
+
+/// Colonnes d'une table de la source (vide si la table n'existe pas)
+fn source_columns(dest: &Connection, table: &str) -> Result<Vec<String>> {
+    let mut stmt = dest.prepare(&format!("PRAGMA {}.table_info({})", SOURCE_ALIAS, table))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<std::result::Result<_, _>>()?;
+    Ok(columns)
+}
+
+/// Fusionne les lignes timeframe_status d'une paire (toutes, ou celle de
+/// `timeframe`)
+///
+/// Bornes élargies (MIN/MAX, NULL ignoré comme dans update_progress),
+/// is_complete: complet si l'une des deux bases l'est
+///
+/// SUBTILITÉ SQL: le WHERE est obligatoire avant ON CONFLICT dans un
+/// INSERT ... SELECT (sinon ambiguïté d'analyse avec une jointure)
+fn merge_status(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: Option<&str>,
+) -> Result<()> {
+    conn.execute(
+        &format!(
+            "INSERT INTO main.timeframe_status
+                 (provider, symbol, timeframe, oldest_candle_time, newest_candle_time,
+                  is_complete, last_updated)
+             SELECT provider, symbol, timeframe, oldest_candle_time, newest_candle_time,
+                    is_complete, last_updated
+             FROM {}.timeframe_status
+             WHERE provider = ?1 AND symbol = ?2 AND (?3 IS NULL OR timeframe = ?3)
+             ON CONFLICT (provider, symbol, timeframe) DO UPDATE SET
+                 oldest_candle_time = MIN(
+                     COALESCE(oldest_candle_time, excluded.oldest_candle_time),
+                     COALESCE(excluded.oldest_candle_time, oldest_candle_time)),
+                 newest_candle_time = MAX(
+                     COALESCE(newest_candle_time, excluded.newest_candle_time),
+                     COALESCE(excluded.newest_candle_time, newest_candle_time)),
+                 is_complete = MAX(is_complete, excluded.is_complete),
+                 last_updated = MAX(last_updated, excluded.last_updated)",
+            SOURCE_ALIAS
+        ),
+        params![provider, symbol, timeframe],
+    )?;
+    Ok(())
+}
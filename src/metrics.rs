@@ -0,0 +1,196 @@
+/// Module de métriques Prometheus
+///
+/// ARCHITECTURE:
+/// - METRICS: registre global initialisé au premier accès (LazyLock)
+/// - Les compteurs sont incrémentés dans les chemins de code concernés
+///   (retriever, gap_filler, middleware HTTP du serveur web)
+/// - Les jauges liées à la base (candles_total, db_size_bytes) sont
+///   recalculées à chaque lecture de /api/metrics
+///
+/// SUBTILITÉ: toutes les paires partagent un seul fichier SQLite, sans
+/// taille par paire mesurable (pas de table dbstat dans le SQLite embarqué).
+/// db_size_bytes{symbol} est la part du fichier au prorata des bougies de
+/// la paire (lignes de taille quasi fixe): une estimation, dont la somme
+/// sur les paires redonne la taille du fichier
+///
+/// NOTE: websocket_connections_active porte le nom demandé pour les
+/// tableaux de bord, mais compte les flux SSE (/api/stream/candles): le
+/// serveur n'a pas de WebSocket
+///
+/// NOTE: les compteurs sont propres au processus. Ceux du backfill ne sont
+/// visibles que si le backfill tourne dans le processus exposant /api/metrics
+use crate::error::Result;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Registre global des métriques
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+/// Ensemble des métriques exportées
+pub struct Metrics {
+    registry: Registry,
+    /// Bougies stockées par paire et timeframe
+    pub candles_total: IntGaugeVec,
+    /// Requêtes HTTP par endpoint et code de statut
+    pub api_requests_total: IntCounterVec,
+    /// Durée de traitement des requêtes HTTP
+    pub api_request_duration_seconds: HistogramVec,
+    /// Flux temps réel (SSE) ouverts
+    pub websocket_connections_active: IntGauge,
+    /// Bougies réelles insérées par le backfill
+    pub backfill_candles_inserted_total: IntCounterVec,
+    /// Bougies générées par le comblement des gaps
+    pub gap_fill_candles_total: IntCounterVec,
+    /// Taille du fichier de base de données, répartie par paire
+    pub db_size_bytes: IntGaugeVec,
+}
+
+impl Metrics {
+    /// Crée et enregistre toutes les métriques
+    ///
+    /// NOTE: expect() acceptable ici, l'enregistrement n'échoue que sur un
+    /// nom dupliqué, c'est-à-dire une erreur de programmation
+    fn new() -> Self {
+        let registry = Registry::new();
+
+        let candles_total = IntGaugeVec::new(
+            Opts::new("candles_total", "Bougies stockées"),
+            &["symbol", "timeframe"],
+        )
+        .expect("métrique candles_total");
+        let api_requests_total = IntCounterVec::new(
+            Opts::new("api_requests_total", "Requêtes HTTP traitées"),
+            &["endpoint", "status"],
+        )
+        .expect("métrique api_requests_total");
+        let api_request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "api_request_duration_seconds",
+                "Durée de traitement des requêtes HTTP",
+            ),
+            &["endpoint"],
+        )
+        .expect("métrique api_request_duration_seconds");
+        let websocket_connections_active = IntGauge::new(
+            "websocket_connections_active",
+            "Flux temps réel ouverts (SSE)",
+        )
+        .expect("métrique websocket_connections_active");
+        let backfill_candles_inserted_total = IntCounterVec::new(
+            Opts::new(
+                "backfill_candles_inserted_total",
+                "Bougies insérées par le backfill",
+            ),
+            &["symbol"],
+        )
+        .expect("métrique backfill_candles_inserted_total");
+        let gap_fill_candles_total = IntCounterVec::new(
+            Opts::new(
+                "gap_fill_candles_total",
+                "Bougies générées par le comblement",
+            ),
+            &["symbol", "timeframe"],
+        )
+        .expect("métrique gap_fill_candles_total");
+        let db_size_bytes = IntGaugeVec::new(
+            Opts::new(
+                "db_size_bytes",
+                "Taille de la base SQLite, au prorata des bougies de chaque paire",
+            ),
+            &["symbol"],
+        )
+        .expect("métrique db_size_bytes");
+
+        for collector in [
+            Box::new(candles_total.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(api_requests_total.clone()),
+            Box::new(api_request_duration_seconds.clone()),
+            Box::new(websocket_connections_active.clone()),
+            Box::new(backfill_candles_inserted_total.clone()),
+            Box::new(gap_fill_candles_total.clone()),
+            Box::new(db_size_bytes.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("enregistrement d'une métrique");
+        }
+
+        Metrics {
+            registry,
+            candles_total,
+            api_requests_total,
+            api_request_duration_seconds,
+            websocket_connections_active,
+            backfill_candles_inserted_total,
+            gap_fill_candles_total,
+            db_size_bytes,
+        }
+    }
+
+    /// Recalcule les jauges issues de la base (appelé avant chaque export)
+    pub fn refresh_db_gauges(&self, conn: &Connection, db_path: &str) -> Result<()> {
+        let mut stmt = conn.prepare(
+            "SELECT symbol, timeframe, COUNT(*) FROM candlesticks GROUP BY symbol, timeframe",
+        )?;
+        let counts = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        self.candles_total.reset();
+        let mut by_symbol: HashMap<&str, i64> = HashMap::new();
+        for (symbol, timeframe, count) in &counts {
+            self.candles_total
+                .with_label_values(&[symbol.as_str(), timeframe.as_str()])
+                .set(*count);
+            *by_symbol.entry(symbol.as_str()).or_default() += count;
+        }
+
+        let size = std::fs::metadata(db_path).map(|m| m.len()).unwrap_or(0) as i64;
+        let total: i64 = by_symbol.values().sum();
+        self.db_size_bytes.reset();
+        for (symbol, count) in by_symbol {
+            // i128: le produit taille × bougies peut dépasser i64
+            let share = (size as i128 * count as i128 / total as i128) as i64;
+            self.db_size_bytes.with_label_values(&[symbol]).set(share);
+        }
+
+        Ok(())
+    }
+
+    /// Exporte toutes les métriques au format texte Prometheus
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        // L'encodage texte n'échoue que sur une erreur d'écriture dans le Vec
+        let _ = TextEncoder::new().encode(&self.registry.gather(), &mut buffer);
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+/// Incrémente une jauge à la création, la décrémente à la destruction
+///
+/// USAGE: déplacé dans un flux SSE, la jauge suit la durée de vie de la connexion
+pub struct GaugeGuard(IntGauge);
+
+impl GaugeGuard {
+    pub fn new(gauge: &IntGauge) -> Self {
+        gauge.inc();
+        GaugeGuard(gauge.clone())
+    }
+}
+
+impl Drop for GaugeGuard {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
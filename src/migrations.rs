@@ -0,0 +1,589 @@
+/// Module des migrations versionnées du schéma SQLite
+///
+/// ARCHITECTURE:
+/// - MIGRATIONS: liste ordonnée, chaque version n'est appliquée qu'une fois
+/// - schema_migrations: versions déjà appliquées (avec date d'application)
+/// - run() est appelé par DatabaseManager::new() à chaque ouverture
+///
+/// RÈGLE: une migration publiée n'est jamais modifiée, toute évolution
+/// du schéma passe par une nouvelle version en fin de liste
+use crate::error::Result;
+use rusqlite::{Connection, Result as SqlResult, TransactionBehavior, params};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Migration numérotée
+struct Migration {
+    version: i64,
+    description: &'static str,
+    apply: fn(&Connection) -> SqlResult<()>,
+}
+
+/// Migrations dans l'ordre d'application
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "schéma initial (candlesticks, timeframe_status, known_downtime)",
+        apply: create_initial_schema,
+    },
+    Migration {
+        version: 2,
+        description: "candlesticks.derived_from",
+        apply: add_derived_from,
+    },
+    Migration {
+        version: 3,
+        description: "timeframe_status: colonnes unifiées + is_complete",
+        apply: reconcile_timeframe_status,
+    },
+    Migration {
+        version: 4,
+        description: "timeframe_status.newest_candle_time",
+        apply: add_newest_candle_time,
+    },
+    Migration {
+        version: 5,
+        description: "obv_values (On-Balance Volume)",
+        apply: create_obv_values,
+    },
+    Migration {
+        version: 6,
+        description: "pattern_events (figures de chandeliers)",
+        apply: create_pattern_events,
+    },
+    Migration {
+        version: 7,
+        description: "historical_volatility",
+        apply: create_historical_volatility,
+    },
+    Migration {
+        version: 8,
+        description: "stochastic_values (oscillateur stochastique)",
+        apply: create_stochastic_values,
+    },
+    Migration {
+        version: 9,
+        description: "verification_runs (historique des vérifications)",
+        apply: create_verification_runs,
+    },
+    Migration {
+        version: 10,
+        description: "pivot_points (points pivots)",
+        apply: create_pivot_points,
+    },
+    Migration {
+        version: 11,
+        description: "adx_values (Average Directional Index)",
+        apply: create_adx_values,
+    },
+    Migration {
+        version: 12,
+        description: "indicator_values (table générique, reprise de obv_values)",
+        apply: create_indicator_values,
+    },
+    Migration {
+        version: 13,
+        description: "sar_values (Parabolic SAR)",
+        apply: create_sar_values,
+    },
+    Migration {
+        version: 14,
+        description: "indicator_values.component (indicateurs à plusieurs sorties)",
+        apply: add_indicator_component,
+    },
+    Migration {
+        version: 15,
+        description: "returns (rendements simples, logarithmiques et cumulés)",
+        apply: create_returns,
+    },
+    Migration {
+        version: 16,
+        description: "reprise de stochastic_values dans indicator_values",
+        apply: move_stochastic_values,
+    },
+];
+
+/// Dernière version du schéma connue de ce binaire
+pub fn latest_version() -> i64 {
+    MIGRATIONS.last().map_or(0, |m| m.version)
+}
+
+/// Version actuelle de la base (0: aucune migration appliquée)
+pub fn current_version(conn: &Connection) -> Result<i64> {
+    create_migrations_table(conn)?;
+    let version = conn.query_row(
+        "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(version)
+}
+
+/// Applique les migrations manquantes
+///
+/// ALGORITHME:
+/// Pour chaque migration de version > version actuelle:
+/// 1. Transaction IMMEDIATE (un seul processus migre à la fois)
+/// 2. Re-vérification dans la transaction (un autre processus a pu migrer)
+/// 3. Application + enregistrement dans schema_migrations
+///
+/// RETOUR: Nombre de migrations appliquées
+pub fn run(conn: &mut Connection) -> Result<usize> {
+    let current = current_version(conn)?;
+    let mut applied = 0;
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+
+        let already_applied: bool = tx.query_row(
+            "SELECT COUNT(*) > 0 FROM schema_migrations WHERE version = ?1",
+            [migration.version],
+            |row| row.get(0),
+        )?;
+        if already_applied {
+            continue;
+        }
+
+        (migration.apply)(&tx)?;
+        tx.execute(
+            "INSERT INTO schema_migrations (version, description, applied_at)
+             VALUES (?1, ?2, ?3)",
+            params![migration.version, migration.description, now_ms()],
+        )?;
+        tx.commit()?;
+        applied += 1;
+    }
+
+    Ok(applied)
+}
+
+fn create_migrations_table(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            description TEXT NOT NULL,
+            applied_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Noms des colonnes d'une table (vide si la table n'existe pas)
+fn table_columns(conn: &Connection, table: &str) -> SqlResult<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    stmt.query_map([], |row| row.get::<_, String>(1))?.collect()
+}
+
+/// Ajoute une colonne à une table existante si elle est absente
+///
+/// DESIGN: CREATE TABLE IF NOT EXISTS ne modifie pas une table existante,
+/// les nouvelles colonnes doivent être ajoutées via ALTER TABLE
+fn ensure_column(conn: &Connection, table: &str, column: &str, definition: &str) -> SqlResult<()> {
+    if !table_columns(conn, table)?
+        .iter()
+        .any(|name| name == column)
+    {
+        conn.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// v1: schéma d'origine
+///
+/// IF NOT EXISTS: les bases antérieures aux migrations ont déjà ces tables
+fn create_initial_schema(conn: &Connection) -> SqlResult<()> {
+    // Table principale des bougies
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS candlesticks (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            open_time INTEGER NOT NULL,
+            open REAL NOT NULL,
+            high REAL NOT NULL,
+            low REAL NOT NULL,
+            close REAL NOT NULL,
+            volume REAL NOT NULL,
+            close_time INTEGER NOT NULL,
+            quote_asset_volume REAL NOT NULL,
+            number_of_trades INTEGER NOT NULL,
+            taker_buy_base_asset_volume REAL NOT NULL,
+            taker_buy_quote_asset_volume REAL NOT NULL,
+            interpolated INTEGER NOT NULL DEFAULT 0,
+            UNIQUE(provider, symbol, timeframe, open_time)
+        )",
+        [],
+    )?;
+
+    // Table de statut des timeframes (pour monitoring uniquement)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS timeframe_status (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            oldest_candle_time INTEGER,
+            last_updated INTEGER NOT NULL,
+            PRIMARY KEY (provider, symbol, timeframe)
+        )",
+        [],
+    )?;
+
+    // Fenêtres d'indisponibilité connues de l'exchange (gaps attendus)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS known_downtime (
+            start_ms INTEGER NOT NULL,
+            end_ms INTEGER NOT NULL,
+            note TEXT,
+            PRIMARY KEY (start_ms, end_ms)
+        )",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// v2: timeframe source d'une bougie agrégée
+fn add_derived_from(conn: &Connection) -> SqlResult<()> {
+    ensure_column(conn, "candlesticks", "derived_from", "TEXT")
+}
+
+/// v3: timeframe_status au format unique
+///
+/// Variantes rencontrées: oldest_time/newest_time (ancien outil de migration
+/// par paire), oldest_candle_time/last_updated (DatabaseManager), avec ou sans
+/// is_complete (documenté mais jamais créé)
+///
+/// ALGORITHME: reconstruction de la table (SQLite ne sait ni renommer ni
+/// retyper proprement une colonne), en reprenant les colonnes présentes
+fn reconcile_timeframe_status(conn: &Connection) -> SqlResult<()> {
+    let columns = table_columns(conn, "timeframe_status")?;
+    let has = |name: &str| columns.iter().any(|c| c == name);
+
+    // Table créée depuis SQL_CREATE_TABLE_TIMEFRAME_STATUS: rien à reconstruire
+    // (la reconstruction perdrait newest_candle_time)
+    if has("newest_candle_time") {
+        return Ok(());
+    }
+
+    let provider = if has("provider") {
+        "provider"
+    } else {
+        "'binance'"
+    };
+    let oldest = if has("oldest_candle_time") {
+        "oldest_candle_time"
+    } else if has("oldest_time") {
+        "oldest_time"
+    } else {
+        "NULL"
+    };
+    let is_complete = if has("is_complete") {
+        "COALESCE(is_complete, 0)"
+    } else {
+        "0"
+    };
+    let last_updated = if has("last_updated") {
+        "COALESCE(last_updated, 0)"
+    } else {
+        "0"
+    };
+
+    conn.execute(
+        "CREATE TABLE timeframe_status_new (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            oldest_candle_time INTEGER,
+            is_complete INTEGER NOT NULL DEFAULT 0,
+            last_updated INTEGER NOT NULL,
+            PRIMARY KEY (provider, symbol, timeframe)
+        )",
+        [],
+    )?;
+    conn.execute(
+        &format!(
+            "INSERT OR REPLACE INTO timeframe_status_new
+             (provider, symbol, timeframe, oldest_candle_time, is_complete, last_updated)
+             SELECT {}, symbol, timeframe, {}, {}, {} FROM timeframe_status",
+            provider, oldest, is_complete, last_updated
+        ),
+        [],
+    )?;
+    conn.execute("DROP TABLE timeframe_status", [])?;
+    conn.execute(
+        "ALTER TABLE timeframe_status_new RENAME TO timeframe_status",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// v4: borne récente de la série dans timeframe_status
+///
+/// Valeur initiale: dernière bougie réelle stockée (newest_time de l'ancien
+/// outil par paire n'a pas survécu à la v3)
+fn add_newest_candle_time(conn: &Connection) -> SqlResult<()> {
+    ensure_column(conn, "timeframe_status", "newest_candle_time", "INTEGER")?;
+    conn.execute(
+        "UPDATE timeframe_status
+         SET newest_candle_time = (
+             SELECT MAX(open_time) FROM candlesticks c
+             WHERE c.provider = timeframe_status.provider
+               AND c.symbol = timeframe_status.symbol
+               AND c.timeframe = timeframe_status.timeframe
+               AND c.interpolated = 0
+         )
+         WHERE newest_candle_time IS NULL",
+        [],
+    )?;
+    Ok(())
+}
+
+/// v5: valeurs OBV par bougie (indicators::obv::store_obv)
+fn create_obv_values(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS obv_values (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            open_time INTEGER NOT NULL,
+            obv REAL NOT NULL,
+            PRIMARY KEY (provider, symbol, timeframe, open_time)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// v6: figures de chandeliers reconnues (patterns::store_patterns)
+fn create_pattern_events(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS pattern_events (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            open_time INTEGER NOT NULL,
+            pattern TEXT NOT NULL,
+            PRIMARY KEY (provider, symbol, timeframe, open_time, pattern)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// v7: volatilité historique (indicators::volatility::store_historical_volatility)
+fn create_historical_volatility(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS historical_volatility (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            open_time INTEGER NOT NULL,
+            period INTEGER NOT NULL,
+            annualized INTEGER NOT NULL,
+            volatility REAL NOT NULL,
+            PRIMARY KEY (provider, symbol, timeframe, open_time, period, annualized)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// v8: oscillateur stochastique (indicators::stochastic::store_stochastic,
+/// table reprise dans indicator_values par la v16)
+fn create_stochastic_values(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS stochastic_values (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            k_period INTEGER NOT NULL,
+            d_period INTEGER NOT NULL,
+            open_time INTEGER NOT NULL,
+            k_value REAL NOT NULL,
+            d_value REAL,
+            PRIMARY KEY (provider, symbol, timeframe, k_period, d_period, open_time)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// v9: historique des vérifications (verification_history::VerificationHistory)
+fn create_verification_runs(conn: &Connection) -> SqlResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS verification_runs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            run_at INTEGER NOT NULL,
+            gaps INTEGER NOT NULL,
+            overlaps INTEGER NOT NULL,
+            value_anomalies INTEGER NOT NULL,
+            interpolated_count INTEGER NOT NULL,
+            total INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_verification_runs_series
+            ON verification_runs (symbol, timeframe, run_at);",
+    )
+}
+
+/// v10: points pivots (indicators::pivot_points::store_pivot_points)
+fn create_pivot_points(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS pivot_points (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            pivot_period TEXT NOT NULL,
+            date INTEGER NOT NULL,
+            pp REAL NOT NULL,
+            r1 REAL NOT NULL,
+            r2 REAL NOT NULL,
+            r3 REAL NOT NULL,
+            s1 REAL NOT NULL,
+            s2 REAL NOT NULL,
+            s3 REAL NOT NULL,
+            PRIMARY KEY (provider, symbol, timeframe, pivot_period, date)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// v11: Average Directional Index (indicators::adx::store_adx)
+fn create_adx_values(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS adx_values (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            period INTEGER NOT NULL,
+            open_time INTEGER NOT NULL,
+            adx REAL NOT NULL,
+            plus_di REAL NOT NULL,
+            minus_di REAL NOT NULL,
+            PRIMARY KEY (provider, symbol, timeframe, period, open_time)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// v12: table générique des indicateurs à une valeur par bougie
+/// (indicators::values), obv_values y est recopiée puis supprimée
+fn create_indicator_values(conn: &Connection) -> SqlResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS indicator_values (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            indicator TEXT NOT NULL,
+            params TEXT NOT NULL,
+            open_time INTEGER NOT NULL,
+            value REAL NOT NULL,
+            PRIMARY KEY (provider, symbol, timeframe, indicator, params, open_time)
+        );
+        INSERT OR IGNORE INTO indicator_values
+            (provider, symbol, timeframe, indicator, params, open_time, value)
+            SELECT provider, symbol, timeframe, 'obv', '{}', open_time, obv FROM obv_values;
+        DROP TABLE obv_values;",
+    )
+}
+
+/// v13: Parabolic SAR (indicators::parabolic_sar::store_sar)
+fn create_sar_values(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sar_values (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            initial_af REAL NOT NULL,
+            step_af REAL NOT NULL,
+            max_af REAL NOT NULL,
+            open_time INTEGER NOT NULL,
+            sar REAL NOT NULL,
+            is_bullish INTEGER NOT NULL,
+            PRIMARY KEY (provider, symbol, timeframe, initial_af, step_af, max_af, open_time)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// v14: composante dans indicator_values (MACD: macd, signal, histogram)
+///
+/// SQLite ne modifie pas une clé primaire: table reconstruite, lignes
+/// existantes en composante 'value'
+fn add_indicator_component(conn: &Connection) -> SqlResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE indicator_values_v14 (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            indicator TEXT NOT NULL,
+            params TEXT NOT NULL,
+            component TEXT NOT NULL DEFAULT 'value',
+            open_time INTEGER NOT NULL,
+            value REAL NOT NULL,
+            PRIMARY KEY (provider, symbol, timeframe, indicator, params, component, open_time)
+        );
+        INSERT INTO indicator_values_v14
+            (provider, symbol, timeframe, indicator, params, component, open_time, value)
+            SELECT provider, symbol, timeframe, indicator, params, 'value', open_time, value
+            FROM indicator_values;
+        DROP TABLE indicator_values;
+        ALTER TABLE indicator_values_v14 RENAME TO indicator_values;",
+    )
+}
+
+/// v15: rendements du close (analytics::returns::store_returns)
+fn create_returns(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS returns (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            open_time INTEGER NOT NULL,
+            value REAL NOT NULL,
+            PRIMARY KEY (provider, symbol, timeframe, kind, open_time)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// v16: stochastic_values reprise dans indicator_values, seul stockage du
+/// stochastique (indicators::stochastic::Stochastic)
+///
+/// Oscillateur rapide (k_smoothing = 1), composantes 'k' et 'd' (%D absent:
+/// pas de ligne). params identique à Stochastic::params (clés triées, JSON
+/// compact). Valeurs déjà présentes dans indicator_values conservées
+fn move_stochastic_values(conn: &Connection) -> SqlResult<()> {
+    let params = "'{\"d_period\":' || d_period || ',\"k_period\":' || k_period
+                  || ',\"k_smoothing\":1}'";
+    conn.execute_batch(&format!(
+        "INSERT OR IGNORE INTO indicator_values
+             (provider, symbol, timeframe, indicator, params, component, open_time, value)
+             SELECT provider, symbol, timeframe, 'stochastic', {params}, 'k', open_time, k_value
+             FROM stochastic_values;
+         INSERT OR IGNORE INTO indicator_values
+             (provider, symbol, timeframe, indicator, params, component, open_time, value)
+             SELECT provider, symbol, timeframe, 'stochastic', {params}, 'd', open_time, d_value
+             FROM stochastic_values WHERE d_value IS NOT NULL;
+         DROP TABLE stochastic_values;",
+        params = params
+    ))
+}
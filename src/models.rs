@@ -0,0 +1,195 @@
+/// Module des modèles de données partagés
+///
+/// CandleRecord reflète une ligne complète de la table candlesticks.
+/// L'insertion et la lecture passent par database.rs (requêtes SQL uniques)
+use crate::timeframe_status::Coverage;
+use crate::utils::parse_timeframe;
+use serde::Serialize;
+
+/// Millisecondes par jour
+const DAY_MS: f64 = 86_400_000.0;
+
+/// Bougie telle que stockée dans la table candlesticks
+///
+/// - interpolated: 0 = donnée réelle, sinon marqueur de FillStrategy
+/// - derived_from: timeframe source d'une bougie agrégée (None sinon)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CandleRecord {
+    pub provider: String,
+    pub symbol: String,
+    pub timeframe: String,
+    pub open_time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    pub close_time: i64,
+    pub quote_asset_volume: f64,
+    pub number_of_trades: i64,
+    pub taker_buy_base_asset_volume: f64,
+    pub taker_buy_quote_asset_volume: f64,
+    pub interpolated: i64,
+    pub derived_from: Option<String>,
+}
+
+impl CandleRecord {
+    /// Colonnes lues par from_row(), dans l'ordre attendu
+    pub const COLUMNS: &'static str = "provider, symbol, timeframe, open_time, open, high, low, \
+         close, volume, close_time, quote_asset_volume, number_of_trades, \
+         taker_buy_base_asset_volume, taker_buy_quote_asset_volume, interpolated, derived_from";
+
+    /// Convertit une ligne `SELECT {COLUMNS}` en CandleRecord
+    pub fn from_row(row: &rusqlite::Row) -> rusqlite::Result<Self> {
+        Ok(CandleRecord {
+            provider: row.get(0)?,
+            symbol: row.get(1)?,
+            timeframe: row.get(2)?,
+            open_time: row.get(3)?,
+            open: row.get(4)?,
+            high: row.get(5)?,
+            low: row.get(6)?,
+            close: row.get(7)?,
+            volume: row.get(8)?,
+            close_time: row.get(9)?,
+            quote_asset_volume: row.get(10)?,
+            number_of_trades: row.get(11)?,
+            taker_buy_base_asset_volume: row.get(12)?,
+            taker_buy_quote_asset_volume: row.get(13)?,
+            interpolated: row.get(14)?,
+            derived_from: row.get(15)?,
+        })
+    }
+
+    /// true pour une bougie synthétique (comblement d'un gap)
+    pub fn is_synthetic(&self) -> bool {
+        self.interpolated != 0
+    }
+}
+
+/// Volume et couverture d'une série (provider, symbol, timeframe)
+///
+/// - expected_count: bougies attendues entre la première et la dernière
+/// - missing_count: bougies absentes des trous entre bougies consécutives
+/// - coverage_pct: part des bougies attendues présentes en données réelles
+///   (les bougies interpolées comblent des gaps, elles ne comptent pas)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CandleStats {
+    pub provider: String,
+    pub symbol: String,
+    pub timeframe: String,
+    pub count: i64,
+    pub first_open_time: Option<i64>,
+    pub last_open_time: Option<i64>,
+    pub interpolated_count: i64,
+    pub span_days: f64,
+    pub expected_count: i64,
+    pub missing_count: i64,
+    pub coverage_pct: f64,
+}
+
+impl CandleStats {
+    /// Construit les statistiques depuis la couverture de la série
+    /// (TimeframeStatus::coverage, source unique du nombre attendu)
+    ///
+    /// Série vide: expected_count et coverage_pct à 0
+    pub fn from_coverage(
+        provider: String,
+        symbol: String,
+        timeframe: String,
+        coverage: &Coverage,
+    ) -> Self {
+        let mut stats = CandleStats {
+            provider,
+            symbol,
+            timeframe,
+            count: 0,
+            first_open_time: None,
+            last_open_time: None,
+            interpolated_count: 0,
+            span_days: 0.0,
+            expected_count: 0,
+            missing_count: 0,
+            coverage_pct: 0.0,
+        };
+        if let Coverage::Series {
+            first,
+            last,
+            stored,
+            expected,
+            missing,
+            interpolated,
+            pct_complete,
+            ..
+        } = *coverage
+        {
+            let interval = parse_timeframe(&stats.timeframe).unwrap_or(0);
+            stats.count = stored;
+            stats.first_open_time = Some(first);
+            stats.last_open_time = Some(last);
+            stats.interpolated_count = interpolated;
+            stats.span_days = (last - first + interval) as f64 / DAY_MS;
+            stats.expected_count = expected;
+            stats.missing_count = missing;
+            stats.coverage_pct = pct_complete;
+        }
+        stats
+    }
+}
+
+/// Statistiques descriptives des bougies d'une plage (écrans de backtest)
+///
+/// - std_close: écart-type échantillon (n - 1) des clôtures, 0 pour une bougie
+/// - avg_body_size: moyenne de |close - open|
+/// - avg_wick_size: moyenne des mèches hautes + basses ((high - low) - corps)
+/// - doji: corps <= DOJI_BODY_RATIO × (high - low); bullish / bearish
+///   comptent les autres bougies, les trois catégories somment à count
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DescriptiveStats {
+    pub count: i64,
+    pub mean_close: f64,
+    pub std_close: f64,
+    pub min_close: f64,
+    pub max_close: f64,
+    pub total_volume: f64,
+    pub mean_volume: f64,
+    pub highest_high: f64,
+    pub lowest_low: f64,
+    pub avg_body_size: f64,
+    pub avg_wick_size: f64,
+    pub bullish_candles: i64,
+    pub bearish_candles: i64,
+    pub doji_candles: i64,
+}
+
+impl DescriptiveStats {
+    /// Corps maximal d'un doji, en fraction de l'amplitude high - low
+    pub const DOJI_BODY_RATIO: f64 = 0.1;
+}
+
+/// Couverture d'un timeframe d'un symbole (/api/pairs/{symbol}/timeframes)
+///
+/// - oldest_candle / newest_candle: open_time en millisecondes
+/// - gap_count: trous restants entre bougies consécutives (non comblés)
+/// - missing_candles: bougies absentes de ces trous
+/// - interpolated_ratio: part des bougies synthétiques (0.0 à 1.0)
+/// - coverage_percentage: bougies réelles / attendues entre les bornes
+///
+/// gap_count, missing_candles et coverage_percentage viennent de
+/// TimeframeStatus::coverage, comme /api/stats
+/// - newest_candle_time: bougie la plus récente enregistrée par le backfill
+///   (timeframe_status), None si le timeframe n'a jamais été récupéré
+/// - is_backfill_complete: limite historique atteinte (timeframe_status)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimeframeInfo {
+    pub timeframe: String,
+    pub candle_count: i64,
+    pub oldest_candle: Option<i64>,
+    pub newest_candle: Option<i64>,
+    pub gap_count: i64,
+    pub missing_candles: i64,
+    pub interpolated_ratio: f64,
+    pub coverage_percentage: f64,
+    pub newest_candle_time: Option<i64>,
+    pub is_backfill_complete: bool,
+}
@@ -0,0 +1,219 @@
+/// Module de reconnaissance des figures de chandeliers
+///
+/// Figures d'une bougie (exclusives, dans cet ordre):
+/// - Doji: corps <= DOJI_BODY_RATIO × (high - low), même seuil que
+///   /api/statistics (DescriptiveStats::DOJI_BODY_RATIO)
+/// - Hammer: mèche basse > 2 × corps, mèche haute <= SMALL_WICK_RATIO × (high - low)
+/// - ShootingStar: mèche haute > 2 × corps, mèche basse <= SMALL_WICK_RATIO × (high - low)
+///
+/// Figures de deux bougies (la seconde porte l'événement):
+/// - BullishEngulfing: bougie baissière puis haussière dont le corps
+///   englobe strictement celui de la précédente
+/// - BearishEngulfing: symétrique
+///
+/// Une bougie peut porter une figure d'une bougie et un engulfing
+use crate::error::Result;
+use crate::models::DescriptiveStats;
+use rusqlite::{Connection, params};
+use serde::Serialize;
+use std::ops::RangeInclusive;
+
+/// Mèche "minimale" d'un hammer / shooting star, en fraction de high - low
+pub const SMALL_WICK_RATIO: f64 = 0.1;
+
+/// Bougie analysée (prix seulement)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PatternCandle {
+    pub open_time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+/// Figure reconnue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Pattern {
+    Doji,
+    Hammer,
+    ShootingStar,
+    BullishEngulfing,
+    BearishEngulfing,
+}
+
+impl Pattern {
+    /// Nom stocké dans pattern_events (identique à la sérialisation JSON)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Pattern::Doji => "doji",
+            Pattern::Hammer => "hammer",
+            Pattern::ShootingStar => "shooting_star",
+            Pattern::BullishEngulfing => "bullish_engulfing",
+            Pattern::BearishEngulfing => "bearish_engulfing",
+        }
+    }
+}
+
+/// Figure reconnue sur la bougie open_time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PatternEvent {
+    pub open_time: i64,
+    pub pattern: Pattern,
+}
+
+impl PatternCandle {
+    fn body(&self) -> f64 {
+        (self.close - self.open).abs()
+    }
+
+    fn range(&self) -> f64 {
+        self.high - self.low
+    }
+
+    fn upper_wick(&self) -> f64 {
+        self.high - self.open.max(self.close)
+    }
+
+    fn lower_wick(&self) -> f64 {
+        self.open.min(self.close) - self.low
+    }
+
+    /// Figure d'une bougie (Doji, Hammer ou ShootingStar)
+    fn single_pattern(&self) -> Option<Pattern> {
+        let (body, range) = (self.body(), self.range());
+        if body <= DescriptiveStats::DOJI_BODY_RATIO * range {
+            Some(Pattern::Doji)
+        } else if self.lower_wick() > 2.0 * body && self.upper_wick() <= SMALL_WICK_RATIO * range {
+            Some(Pattern::Hammer)
+        } else if self.upper_wick() > 2.0 * body && self.lower_wick() <= SMALL_WICK_RATIO * range {
+            Some(Pattern::ShootingStar)
+        } else {
+            None
+        }
+    }
+}
+
+/// Engulfing formé par `current` sur `previous`
+fn engulfing(previous: &PatternCandle, current: &PatternCandle) -> Option<Pattern> {
+    let bullish = |c: &PatternCandle| c.close > c.open;
+    let bearish = |c: &PatternCandle| c.close < c.open;
+    let engulfs = current.body() > previous.body()
+        && current.open.min(current.close) <= previous.open.min(previous.close)
+        && current.open.max(current.close) >= previous.open.max(previous.close);
+
+    if !engulfs {
+        None
+    } else if bearish(previous) && bullish(current) {
+        Some(Pattern::BullishEngulfing)
+    } else if bullish(previous) && bearish(current) {
+        Some(Pattern::BearishEngulfing)
+    } else {
+        None
+    }
+}
+
+/// Figures des bougies (triées par open_time), dans l'ordre des bougies
+///
+/// RETOUR: pour une même bougie, la figure d'une bougie avant l'engulfing
+pub fn detect_patterns(candles: &[PatternCandle]) -> Vec<PatternEvent> {
+    let mut events = Vec::new();
+    for (i, candle) in candles.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|p| &candles[p]);
+        let patterns = [
+            candle.single_pattern(),
+            previous.and_then(|previous| engulfing(previous, candle)),
+        ];
+        events.extend(patterns.into_iter().flatten().map(|pattern| PatternEvent {
+            open_time: candle.open_time,
+            pattern,
+        }));
+    }
+    events
+}
+
+/// Figures des bougies de `range` (open_time en ms)
+///
+/// La bougie précédant la plage est lue aussi: un engulfing sur la
+/// première bougie de la plage est détecté
+pub fn pattern_series(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    range: RangeInclusive<i64>,
+) -> Result<Vec<PatternEvent>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT open_time, open, high, low, close FROM candlesticks
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+           AND open_time >= COALESCE(
+               (SELECT MAX(open_time) FROM candlesticks
+                WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3 AND open_time < ?4),
+               ?4)
+           AND open_time <= ?5
+         ORDER BY open_time ASC",
+    )?;
+    let candles = stmt
+        .query_map(
+            params![provider, symbol, timeframe, range.start(), range.end()],
+            |row| {
+                Ok(PatternCandle {
+                    open_time: row.get(0)?,
+                    open: row.get(1)?,
+                    high: row.get(2)?,
+                    low: row.get(3)?,
+                    close: row.get(4)?,
+                })
+            },
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(detect_patterns(&candles)
+        .into_iter()
+        .filter(|event| range.contains(&event.open_time))
+        .collect())
+}
+
+/// Détecte et enregistre les figures de [start_time, end_time] dans
+/// pattern_events, en une transaction
+///
+/// Les figures déjà enregistrées sur la plage sont remplacées (une bougie
+/// corrigée peut perdre sa figure)
+///
+/// RETOUR: Nombre de figures enregistrées
+pub fn store_patterns(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<i64> {
+    let events = pattern_series(conn, provider, symbol, timeframe, start_time..=end_time)?;
+
+    let tx = conn.transaction()?;
+    tx.execute(
+        "DELETE FROM pattern_events
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+           AND open_time >= ?4 AND open_time <= ?5",
+        params![provider, symbol, timeframe, start_time, end_time],
+    )?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO pattern_events (provider, symbol, timeframe, open_time, pattern)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+        for event in &events {
+            stmt.execute(params![
+                provider,
+                symbol,
+                timeframe,
+                event.open_time,
+                event.pattern.as_str()
+            ])?;
+        }
+    }
+    tx.commit()?;
+
+    Ok(events.len() as i64)
+}
@@ -0,0 +1,131 @@
+/// Module de pool de connexions SQLite en lecture
+///
+/// Ouvrir une connexion à chaque requête coûte plusieurs millisecondes
+/// (ouverture du fichier, pragmas) et perd le cache de pages SQLite. Le pool
+/// conserve les connexions rendues et les redistribue aux requêtes suivantes
+///
+/// ARCHITECTURE:
+/// - get(): connexion inactive la plus récente (cache le plus chaud), sinon nouvelle
+/// - PooledConnection: rend la connexion au pool à sa destruction (RAII)
+/// - au plus max_idle connexions inactives conservées, fermées après idle_timeout
+///
+/// NOTE: une seule base (db_path) pour toutes les paires, donc un seul pool
+use crate::database::open_configured;
+use crate::error::Result;
+use rusqlite::Connection;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Connexion inactive et date de sa remise au pool
+struct IdleConnection {
+    conn: Connection,
+    since: Instant,
+}
+
+/// Pool de connexions vers une base SQLite
+///
+/// SUBTILITÉ RUST: Connection est Send mais pas Sync, le Mutex rend le pool
+/// partageable entre threads (Arc<ConnectionPool>)
+pub struct ConnectionPool {
+    db_path: String,
+    max_idle: usize,
+    idle_timeout: Duration,
+    idle: Mutex<Vec<IdleConnection>>,
+    opened: AtomicUsize,
+}
+
+impl ConnectionPool {
+    /// Crée un pool vide, les connexions sont ouvertes à la demande
+    pub fn new(db_path: &str, max_idle: usize, idle_timeout: Duration) -> Arc<Self> {
+        Arc::new(ConnectionPool {
+            db_path: db_path.to_string(),
+            max_idle,
+            idle_timeout,
+            idle: Mutex::new(Vec::new()),
+            opened: AtomicUsize::new(0),
+        })
+    }
+
+    /// Emprunte une connexion (réutilisée si possible)
+    pub fn get(self: &Arc<Self>) -> Result<PooledConnection> {
+        let reused = {
+            let mut idle = self.idle.lock().unwrap();
+            self.evict_expired(&mut idle);
+            idle.pop()
+        };
+
+        let conn = match reused {
+            Some(entry) => entry.conn,
+            None => {
+                let conn = open_configured(&self.db_path)?;
+                self.opened.fetch_add(1, Ordering::Relaxed);
+                conn
+            }
+        };
+
+        Ok(PooledConnection {
+            conn: Some(conn),
+            pool: Arc::clone(self),
+        })
+    }
+
+    /// Nombre total de connexions ouvertes depuis la création du pool
+    pub fn opened_connections(&self) -> usize {
+        self.opened.load(Ordering::Relaxed)
+    }
+
+    /// Nombre de connexions inactives actuellement conservées
+    pub fn idle_connections(&self) -> usize {
+        self.idle.lock().unwrap().len()
+    }
+
+    /// Remet une connexion dans le pool (fermée si le pool est plein)
+    fn release(&self, conn: Connection) {
+        let mut idle = self.idle.lock().unwrap();
+        self.evict_expired(&mut idle);
+        if idle.len() < self.max_idle {
+            idle.push(IdleConnection {
+                conn,
+                since: Instant::now(),
+            });
+        }
+    }
+
+    /// Ferme les connexions inactives depuis plus de idle_timeout
+    fn evict_expired(&self, idle: &mut Vec<IdleConnection>) {
+        idle.retain(|entry| entry.since.elapsed() < self.idle_timeout);
+    }
+}
+
+/// Connexion empruntée au pool, rendue automatiquement
+pub struct PooledConnection {
+    // Option: permet de sortir la connexion dans Drop
+    conn: Option<Connection>,
+    pool: Arc<ConnectionPool>,
+}
+
+impl Deref for PooledConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        // Toujours Some hors de Drop
+        self.conn.as_ref().expect("connexion du pool")
+    }
+}
+
+// Mutable pour les transactions (import CSV)
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn.as_mut().expect("connexion du pool")
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            self.pool.release(conn);
+        }
+    }
+}
@@ -0,0 +1,106 @@
+/// Module de suivi du budget de poids de l'API Binance
+///
+/// Binance bannit les adresses IP qui dépassent la limite de poids par minute.
+/// Ce module comptabilise le poids de chaque requête sur une fenêtre glissante
+/// de 60 secondes et indique combien de temps attendre avant la prochaine
+/// requête
+///
+/// NOTE: le poids est seulement décompté localement. Le client binance-rs ne
+/// renvoie que les réponses désérialisées, sans leurs headers: le poids
+/// consommé rapporté par Binance (X-MBX-USED-WEIGHT-1M) n'est pas lisible
+/// depuis le chemin de récupération
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Limite de poids par défaut de Binance (par minute et par IP)
+pub const DEFAULT_WEIGHT_LIMIT: u32 = 1200;
+
+/// Durée de la fenêtre glissante
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Limiteur de débit basé sur le poids des requêtes
+///
+/// ARCHITECTURE:
+/// Fenêtre glissante VecDeque<(Instant, poids)> protégée par un Mutex,
+/// ce qui permet de partager le limiteur via Arc entre plusieurs récupérateurs
+pub struct RateLimiter {
+    max_weight: u32,
+    window: Mutex<VecDeque<(Instant, u32)>>,
+}
+
+impl RateLimiter {
+    /// Crée un limiteur avec un budget maximal par minute
+    pub fn new(max_weight: u32) -> Self {
+        RateLimiter {
+            max_weight,
+            window: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Crée un limiteur configuré via la variable BINANCE_WEIGHT_LIMIT
+    ///
+    /// Si la variable est absente ou invalide, utilise DEFAULT_WEIGHT_LIMIT
+    pub fn from_env() -> Self {
+        let max_weight = std::env::var("BINANCE_WEIGHT_LIMIT")
+            .ok()
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(DEFAULT_WEIGHT_LIMIT);
+
+        Self::new(max_weight)
+    }
+
+    /// Budget maximal configuré
+    pub fn max_weight(&self) -> u32 {
+        self.max_weight
+    }
+
+    /// Vérifie le budget et enregistre la requête si elle est autorisée
+    ///
+    /// ALGORITHME:
+    /// 1. Purge les entrées sorties de la fenêtre de 60 secondes
+    /// 2. Si poids_utilisé + weight <= max → enregistre et retourne None
+    /// 3. Sinon, calcule quand assez de poids sera libéré et retourne Some(attente)
+    ///
+    /// RETOUR: None si la requête peut partir, Some(durée) si l'appelant doit dormir
+    /// (la requête n'est PAS enregistrée dans ce cas, il faut rappeler la méthode)
+    pub fn check_and_record(&self, weight: u32) -> Option<Duration> {
+        let now = Instant::now();
+        let mut window = self.window.lock().unwrap();
+
+        Self::purge(&mut window, now);
+
+        let used: u32 = window.iter().map(|(_, w)| w).sum();
+
+        // Une requête plus lourde que le budget passe dès que la fenêtre est vide
+        if used + weight <= self.max_weight || window.is_empty() {
+            window.push_back((now, weight));
+            return None;
+        }
+
+        // Chercher la plus ancienne entrée dont l'expiration libère assez de poids
+        let mut freed = 0u32;
+        for (timestamp, w) in window.iter() {
+            freed += w;
+            if used - freed + weight <= self.max_weight {
+                return Some((*timestamp + WINDOW).saturating_duration_since(now));
+            }
+        }
+
+        // Requête plus lourde que le budget: attendre que la fenêtre soit vide
+        window
+            .back()
+            .map(|(timestamp, _)| (*timestamp + WINDOW).saturating_duration_since(now))
+    }
+
+    /// Retire les entrées plus vieilles que la fenêtre
+    fn purge(window: &mut VecDeque<(Instant, u32)>, now: Instant) {
+        while let Some((timestamp, _)) = window.front() {
+            if now.duration_since(*timestamp) >= WINDOW {
+                window.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,742 @@
+/// Module de diffusion des nouvelles bougies en temps réel
+///
+/// ARCHITECTURE:
+/// - Le CLI de récupération écrit dans SQLite (processus séparé)
+/// - Un thread du serveur web surveille la table candlesticks (rowid croissant)
+/// - Chaque nouvelle bougie réelle est publiée sur un canal broadcast tokio,
+///   auquel s'abonnent les clients (SSE, ...)
+/// - shutdown() termine les flux abonnés et le thread de surveillance
+/// - Bougies partielles (en cours de formation): déposées par un flux live via
+///   set_partial, retirées dès que la bougie clôturée est stockée
+/// - Bougies clôturées d'un flux live: save_completed_candle les confie à un
+///   écrivain unique par symbole (file bornée, une transaction par lot),
+///   qui avance newest_candle_time / last_updated dans timeframe_status et
+///   recalcule les indicateurs du registre (enable_indicator_recalculation)
+/// - RSI en continu: track_rsi initialise un RsiState par (symbol,
+///   timeframe, period) depuis l'historique (track_rsi_from_db: lu en base à
+///   l'abonnement), chaque bougie clôturée diffusée l'avance d'une étape,
+///   sans relire la base
+/// - RSI live: chaque bougie partielle (set_partial, publish_partial) et
+///   chaque bougie clôturée diffusée porte le RSI des séries suivies dans
+///   CandleUpdate::indicators, la bougie partielle comptée comme dernier
+///   close. Approximation d'affichage jamais enregistrée: les valeurs de
+///   indicator_values viennent du recalcul canonique (IndicatorRegistry)
+use crate::database::{insert_candle_records, open_configured};
+use crate::error::{CandleError, Result};
+use crate::indicators::registry::IndicatorRegistry;
+use crate::indicators::rsi::{RSI_WARMUP_FACTOR, RsiState};
+use crate::models::CandleRecord;
+use crate::timeframe_status::TimeframeStatus;
+use rusqlite::{Connection, params};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, watch};
+use tracing::{debug, error, warn};
+
+/// Nombre maximal de bougies lues par cycle de surveillance
+const POLL_BATCH_SIZE: i64 = 1000;
+
+/// Tentatives d'écriture du dernier lot à la fermeture d'un écrivain
+const FINAL_FLUSH_ATTEMPTS: usize = 5;
+
+/// Bougie nouvellement stockée, diffusée aux abonnés
+#[derive(Debug, Clone, Serialize)]
+pub struct CandleUpdate {
+    pub provider: String,
+    pub symbol: String,
+    pub timeframe: String,
+    pub time: i64, // timestamp en secondes (pour Lightweight Charts)
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    /// RSI live des séries suivies ("rsi_14" → valeur), absent sinon
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indicators: Option<HashMap<String, f64>>,
+}
+
+/// Gestionnaire de diffusion des mises à jour
+///
+/// SUBTILITÉ RUST: broadcast::Sender est Clone + Sync, chaque abonné reçoit
+/// sa propre copie de chaque message (CandleUpdate: Clone requis)
+pub struct RealtimeManager {
+    sender: broadcast::Sender<CandleUpdate>,
+    shutdown: watch::Sender<bool>,
+    /// Dernière bougie partielle par (symbol, timeframe)
+    partials: Mutex<HashMap<(String, String), CandleUpdate>>,
+    /// Base et réglages des écrivains (None: save_completed_candle refusé)
+    writer_settings: Mutex<Option<(String, WriterConfig)>>,
+    /// Écrivain de bougies clôturées par symbole, créé au premier envoi
+    writers: Mutex<HashMap<String, CandleWriter>>,
+    /// Indicateurs recalculés après chaque lot écrit (None: aucun)
+    indicators: Mutex<Option<Arc<IndicatorRegistry>>>,
+    /// RSI suivis par (symbol, timeframe, period)
+    rsi: Mutex<HashMap<(String, String, usize), LiveRsi>>,
+}
+
+/// RSI suivi en continu: état de Wilder et dernière bougie prise en compte
+#[derive(Debug, Clone, Copy)]
+struct LiveRsi {
+    state: RsiState,
+    last_close: f64,
+    /// open_time en secondes (comme CandleUpdate::time)
+    last_time: i64,
+}
+
+/// Réglages d'un écrivain de bougies clôturées
+#[derive(Debug, Clone, Copy)]
+pub struct WriterConfig {
+    /// Délai maximal entre la réception d'une bougie et son écriture
+    pub flush_interval: Duration,
+    /// Un lot de cette taille est écrit sans attendre flush_interval
+    pub max_batch: usize,
+    /// Capacité de la file: au-delà, l'envoi attend (backpressure)
+    pub queue_capacity: usize,
+}
+
+impl Default for WriterConfig {
+    fn default() -> Self {
+        WriterConfig {
+            flush_interval: Duration::from_secs(1),
+            max_batch: 50,
+            queue_capacity: 1000,
+        }
+    }
+}
+
+/// Bilan d'un écrivain, retourné à sa fermeture
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WriterStats {
+    /// Bougies reçues par l'écrivain
+    pub received: usize,
+    /// Bougies insérées (hors doublons déjà présents)
+    pub inserted: usize,
+    /// Transactions validées
+    pub transactions: usize,
+    /// Écritures en échec (lot conservé puis retenté)
+    pub failed_flushes: usize,
+    /// Bougies jamais écrites (échec persistant à la fermeture)
+    pub dropped: usize,
+}
+
+/// Écrivain unique des bougies clôturées d'un symbole
+///
+/// DESIGN: une seule connexion par symbole, ouverte par le thread écrivain,
+/// au lieu d'une connexion et d'une transaction par bougie. Les bougies
+/// arrivent par une file std::sync::mpsc bornée (sync_channel) et sont écrites
+/// par lots, dans une transaction, toutes les flush_interval ou dès max_batch
+///
+/// ALGORITHME (thread écrivain):
+/// 1. Attente d'une bougie (sans délai si aucun lot en attente)
+/// 2. Écriture du lot à l'échéance ou quand il atteint max_batch
+/// 3. Échec: lot conservé, nouvelle tentative à l'échéance suivante. Tant que
+///    le lot dépasse queue_capacity, le thread ne lit plus la file: elle se
+///    remplit et les envois attendent
+/// 4. File fermée (close): dernier lot écrit, FINAL_FLUSH_ATTEMPTS tentatives
+///
+/// Avec un IndicatorRegistry, chaque lot écrit est suivi du recalcul des
+/// indicateurs de ses séries (échec journalisé, le lot reste écrit)
+pub struct CandleWriter {
+    symbol: String,
+    sender: SyncSender<CandleRecord>,
+    handle: JoinHandle<WriterStats>,
+}
+
+impl CandleWriter {
+    /// Lance le thread écrivain d'un symbole sur la base `db_path`
+    pub fn spawn(
+        symbol: &str,
+        db_path: String,
+        config: WriterConfig,
+        indicators: Option<Arc<IndicatorRegistry>>,
+    ) -> Self {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(config.queue_capacity.max(1));
+        let thread_symbol = symbol.to_string();
+        let handle = std::thread::spawn(move || {
+            run_writer(
+                &thread_symbol,
+                &db_path,
+                config,
+                indicators.as_deref(),
+                receiver,
+            )
+        });
+        CandleWriter {
+            symbol: symbol.to_string(),
+            sender,
+            handle,
+        }
+    }
+
+    /// Confie une bougie clôturée à l'écrivain
+    ///
+    /// File pleine: avertissement puis attente d'une place (backpressure sur
+    /// le flux producteur plutôt qu'une mémoire non bornée)
+    ///
+    /// ERREURS: thread écrivain arrêté
+    pub fn push(&self, candle: CandleRecord) -> Result<()> {
+        send_with_backpressure(&self.sender, &self.symbol, candle)
+    }
+
+    /// Ferme la file et attend l'écriture du dernier lot
+    pub fn close(self) -> WriterStats {
+        drop(self.sender);
+        self.handle.join().unwrap_or_else(|_| {
+            error!(symbol = %self.symbol, "Thread écrivain interrompu");
+            WriterStats::default()
+        })
+    }
+}
+
+/// Envoi bloquant si la file est pleine, avec avertissement
+fn send_with_backpressure(
+    sender: &SyncSender<CandleRecord>,
+    symbol: &str,
+    candle: CandleRecord,
+) -> Result<()> {
+    let stopped = || CandleError::ValidationError(format!("Écrivain {} arrêté", symbol));
+    match sender.try_send(candle) {
+        Ok(()) => Ok(()),
+        Err(TrySendError::Full(candle)) => {
+            warn!(symbol, "File d'écriture pleine, le flux attend l'écrivain");
+            sender.send(candle).map_err(|_| stopped())
+        }
+        Err(TrySendError::Disconnected(_)) => Err(stopped()),
+    }
+}
+
+/// Boucle du thread écrivain (voir CandleWriter)
+fn run_writer(
+    symbol: &str,
+    db_path: &str,
+    config: WriterConfig,
+    indicators: Option<&IndicatorRegistry>,
+    receiver: Receiver<CandleRecord>,
+) -> WriterStats {
+    let mut stats = WriterStats::default();
+    let mut conn: Option<Connection> = None;
+    let mut pending: Vec<CandleRecord> = Vec::new();
+    // Échéance du lot en attente (None: lot vide)
+    let mut deadline: Option<Instant> = None;
+    let max_batch = config.max_batch.max(1);
+
+    loop {
+        let backlogged = pending.len() >= config.queue_capacity.max(max_batch);
+        let closed = if backlogged {
+            // Échecs répétés: la file n'est plus lue, les envois attendent
+            std::thread::sleep(config.flush_interval);
+            false
+        } else {
+            let received = match deadline {
+                Some(at) => receiver.recv_timeout(at.saturating_duration_since(Instant::now())),
+                None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match received {
+                Ok(candle) => {
+                    stats.received += 1;
+                    pending.push(candle);
+                    deadline.get_or_insert_with(|| Instant::now() + config.flush_interval);
+                    if pending.len() < max_batch {
+                        continue;
+                    }
+                    false
+                }
+                Err(RecvTimeoutError::Timeout) => false,
+                Err(RecvTimeoutError::Disconnected) => true,
+            }
+        };
+
+        if closed {
+            for attempt in 1..=FINAL_FLUSH_ATTEMPTS {
+                if pending.is_empty()
+                    || flush_pending(
+                        symbol,
+                        db_path,
+                        indicators,
+                        &mut conn,
+                        &mut pending,
+                        &mut stats,
+                    )
+                {
+                    break;
+                }
+                if attempt < FINAL_FLUSH_ATTEMPTS {
+                    std::thread::sleep(config.flush_interval);
+                }
+            }
+            if !pending.is_empty() {
+                error!(
+                    symbol,
+                    dropped = pending.len(),
+                    "Bougies clôturées perdues à la fermeture de l'écrivain"
+                );
+                stats.dropped = pending.len();
+            }
+            debug!(symbol, ?stats, "Écrivain de bougies arrêté");
+            return stats;
+        }
+
+        if !pending.is_empty() {
+            deadline = if flush_pending(
+                symbol,
+                db_path,
+                indicators,
+                &mut conn,
+                &mut pending,
+                &mut stats,
+            ) {
+                None
+            } else {
+                Some(Instant::now() + config.flush_interval)
+            };
+        }
+    }
+}
+
+/// Écrit le lot en attente dans une transaction, avec la progression des
+/// séries (TimeframeStatus::update_newest) pour l'état des timeframes, puis
+/// recalcule les indicateurs de chaque série du lot
+///
+/// La connexion est ouverte au besoin et abandonnée après un échec (base
+/// déplacée, fichier verrouillé...): la tentative suivante la rouvre
+///
+/// RETOUR: true si le lot est écrit (et vidé), false s'il reste à retenter
+fn flush_pending(
+    symbol: &str,
+    db_path: &str,
+    indicators: Option<&IndicatorRegistry>,
+    conn: &mut Option<Connection>,
+    pending: &mut Vec<CandleRecord>,
+    stats: &mut WriterStats,
+) -> bool {
+    let result = (|| -> Result<usize> {
+        if conn.is_none() {
+            *conn = Some(open_configured(db_path)?);
+        }
+        let conn = conn.as_mut().expect("connexion ouverte ci-dessus");
+        let series = bounds_by_series(pending);
+        let tx = conn.transaction()?;
+        let inserted = insert_candle_records(&tx, pending)?;
+        for (&(provider, symbol, timeframe), &(_, newest)) in &series {
+            TimeframeStatus::update_newest(&tx, provider, symbol, timeframe, newest)?;
+        }
+        tx.commit()?;
+
+        // Lot écrit: un échec du recalcul ne doit pas le faire retenter
+        if let Some(registry) = indicators {
+            for (&(provider, symbol, timeframe), &(oldest, newest)) in &series {
+                let recalculated = registry
+                    .recalculate_appended(conn, provider, symbol, timeframe, oldest, newest);
+                if let Err(e) = recalculated {
+                    warn!(symbol, timeframe, error = %e, "Recalcul des indicateurs impossible");
+                }
+            }
+        }
+        Ok(inserted)
+    })();
+
+    match result {
+        Ok(inserted) => {
+            stats.inserted += inserted;
+            stats.transactions += 1;
+            pending.clear();
+            true
+        }
+        Err(e) => {
+            warn!(
+                symbol,
+                pending = pending.len(),
+                error = %e,
+                "Écriture des bougies clôturées en échec, nouvelle tentative"
+            );
+            stats.failed_flushes += 1;
+            *conn = None;
+            false
+        }
+    }
+}
+
+/// open_time minimal et maximal du lot par série (provider, symbol, timeframe)
+fn bounds_by_series(candles: &[CandleRecord]) -> HashMap<(&str, &str, &str), (i64, i64)> {
+    let mut bounds = HashMap::new();
+    for candle in candles {
+        let key = (
+            candle.provider.as_str(),
+            candle.symbol.as_str(),
+            candle.timeframe.as_str(),
+        );
+        let (oldest, newest) = bounds
+            .entry(key)
+            .or_insert((candle.open_time, candle.open_time));
+        *oldest = (*oldest).min(candle.open_time);
+        *newest = (*newest).max(candle.open_time);
+    }
+    bounds
+}
+
+impl RealtimeManager {
+    /// capacity: messages conservés pour un abonné lent avant qu'il ne décroche
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        let (shutdown, _) = watch::channel(false);
+        RealtimeManager {
+            sender,
+            shutdown,
+            partials: Mutex::new(HashMap::new()),
+            writer_settings: Mutex::new(None),
+            writers: Mutex::new(HashMap::new()),
+            indicators: Mutex::new(None),
+            rsi: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Suit le RSI `period` d'une série, initialisé depuis `history`
+    /// (bougies triées, les dernières stockées)
+    ///
+    /// Remplace un suivi existant de la même série et période
+    ///
+    /// RETOUR: false si l'historique est trop court (period + 1 bougies
+    /// nécessaires), la série n'est alors pas suivie
+    pub fn track_rsi(
+        &self,
+        symbol: &str,
+        timeframe: &str,
+        period: usize,
+        history: &[CandleRecord],
+    ) -> bool {
+        let closes: Vec<f64> = history.iter().map(|c| c.close).collect();
+        let (Some(state), Some(last)) = (RsiState::from_history(&closes, period), history.last())
+        else {
+            return false;
+        };
+        self.rsi.lock().unwrap().insert(
+            (symbol.to_string(), timeframe.to_string(), period),
+            LiveRsi {
+                state,
+                last_close: last.close,
+                last_time: last.open_time / 1000,
+            },
+        );
+        true
+    }
+
+    /// Suit le RSI `period` d'une série initialisé depuis les dernières
+    /// bougies réelles stockées (RSI_WARMUP_FACTOR × period + 1, la chauffe
+    /// du recalcul canonique), à appeler à l'abonnement d'un client
+    ///
+    /// RETOUR: false si la série a moins de period + 1 bougies
+    pub fn track_rsi_from_db(
+        &self,
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        period: usize,
+    ) -> Result<bool> {
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM candlesticks
+             WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3 AND interpolated = 0
+             ORDER BY open_time DESC
+             LIMIT ?4",
+            CandleRecord::COLUMNS
+        ))?;
+        let mut history = stmt
+            .query_map(
+                params![
+                    provider,
+                    symbol,
+                    timeframe,
+                    (RSI_WARMUP_FACTOR * period + 1) as i64
+                ],
+                CandleRecord::from_row,
+            )?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        history.reverse();
+        Ok(self.track_rsi(symbol, timeframe, period, &history))
+    }
+
+    /// RSI courant d'une série suivie (None: non suivie)
+    pub fn rsi(&self, symbol: &str, timeframe: &str, period: usize) -> Option<f64> {
+        self.rsi
+            .lock()
+            .unwrap()
+            .get(&(symbol.to_string(), timeframe.to_string(), period))
+            .map(|live| live.state.value())
+    }
+
+    /// Avance d'une étape les RSI suivis de la série d'une bougie clôturée
+    ///
+    /// SUBTILITÉ: une bougie plus ancienne que la dernière prise en compte
+    /// (rattrapage d'historique inséré après coup) est ignorée, elle
+    /// fausserait la moyenne de Wilder
+    fn update_rsi(&self, update: &CandleUpdate) {
+        let mut rsi = self.rsi.lock().unwrap();
+        for ((symbol, timeframe, _), live) in rsi.iter_mut() {
+            if *symbol != update.symbol
+                || *timeframe != update.timeframe
+                || update.time <= live.last_time
+            {
+                continue;
+            }
+            live.state.update(update.close, live.last_close);
+            live.last_close = update.close;
+            live.last_time = update.time;
+        }
+    }
+
+    /// RSI des séries suivies de la série d'une mise à jour, par
+    /// "rsi_{period}" (None: aucun RSI suivi)
+    ///
+    /// ALGORITHME: bougie déjà prise en compte (clôturée, diffusée) → RSI
+    /// courant; bougie plus récente (partielle) → une étape de Wilder sur une
+    /// copie de l'état, le close partiel comme dernier close. L'état n'avance
+    /// qu'à la clôture (update_rsi): le close partiel suivant repart de la
+    /// même base
+    fn live_indicators(&self, update: &CandleUpdate) -> Option<HashMap<String, f64>> {
+        let rsi = self.rsi.lock().unwrap();
+        let values: HashMap<String, f64> = rsi
+            .iter()
+            .filter(|((symbol, timeframe, _), live)| {
+                *symbol == update.symbol
+                    && *timeframe == update.timeframe
+                    && update.time >= live.last_time
+            })
+            .map(|((_, _, period), live)| {
+                let value = if update.time == live.last_time {
+                    live.state.value()
+                } else {
+                    // RsiState: Copy, l'état suivi reste intact
+                    let mut state = live.state;
+                    state.update(update.close, live.last_close)
+                };
+                (format!("rsi_{}", period), value)
+            })
+            .collect();
+        (!values.is_empty()).then_some(values)
+    }
+
+    /// Active save_completed_candle: les écrivains écriront dans `db_path`
+    pub fn enable_candle_writers(&self, db_path: String, config: WriterConfig) {
+        *self.writer_settings.lock().unwrap() = Some((db_path, config));
+    }
+
+    /// Recalcule les indicateurs de `registry` après chaque lot écrit par
+    /// les écrivains (voir IndicatorRegistry::recalculate)
+    ///
+    /// NOTE: comme enable_candle_writers, à appeler avant le premier
+    /// save_completed_candle: un écrivain déjà lancé garde ses réglages
+    pub fn enable_indicator_recalculation(&self, registry: Arc<IndicatorRegistry>) {
+        *self.indicators.lock().unwrap() = Some(registry);
+    }
+
+    /// Confie une bougie clôturée d'un flux live à l'écrivain de son symbole
+    ///
+    /// Remplace l'ouverture d'une connexion par bougie: l'écrivain du symbole
+    /// est créé au premier envoi, puis regroupe les écritures (CandleWriter).
+    /// Peut attendre si la file du symbole est pleine (backpressure): depuis
+    /// une tâche tokio, appeler via spawn_blocking
+    ///
+    /// ERREURS: enable_candle_writers non appelé, écrivain arrêté
+    pub fn save_completed_candle(&self, candle: CandleRecord) -> Result<()> {
+        // Envoi hors du verrou: une file pleine ne bloque pas les autres symboles
+        let sender = {
+            let mut writers = self.writers.lock().unwrap();
+            match writers.get(&candle.symbol) {
+                Some(writer) => writer.sender.clone(),
+                None => {
+                    let Some((db_path, config)) = self.writer_settings.lock().unwrap().clone()
+                    else {
+                        return Err(CandleError::ValidationError(
+                            "Écriture temps réel non activée (enable_candle_writers)".to_string(),
+                        ));
+                    };
+                    let indicators = self.indicators.lock().unwrap().clone();
+                    let writer = CandleWriter::spawn(&candle.symbol, db_path, config, indicators);
+                    let sender = writer.sender.clone();
+                    writers.insert(candle.symbol.clone(), writer);
+                    sender
+                }
+            }
+        };
+        let symbol = candle.symbol.clone();
+        send_with_backpressure(&sender, &symbol, candle)
+    }
+
+    /// Ferme les écrivains après écriture de leurs derniers lots
+    ///
+    /// RETOUR: bilan par symbole, trié par symbole
+    pub fn close_writers(&self) -> Vec<(String, WriterStats)> {
+        let writers: Vec<CandleWriter> = self
+            .writers
+            .lock()
+            .unwrap()
+            .drain()
+            .map(|(_, writer)| writer)
+            .collect();
+        let mut stats: Vec<(String, WriterStats)> = writers
+            .into_iter()
+            .map(|writer| (writer.symbol.clone(), writer.close()))
+            .collect();
+        stats.sort_by(|a, b| a.0.cmp(&b.0));
+        stats
+    }
+
+    /// Demande l'arrêt: les flux attendant shutdown_signal() se terminent,
+    /// le thread de surveillance s'arrête à son prochain cycle
+    pub fn shutdown(&self) {
+        self.shutdown.send_replace(true);
+    }
+
+    /// true après shutdown()
+    pub fn is_shut_down(&self) -> bool {
+        *self.shutdown.borrow()
+    }
+
+    /// Récepteur à surveiller par chaque flux (wait_for(|stop| *stop))
+    pub fn shutdown_signal(&self) -> watch::Receiver<bool> {
+        self.shutdown.subscribe()
+    }
+
+    /// Nouvel abonnement aux mises à jour
+    pub fn subscribe_updates(&self) -> broadcast::Receiver<CandleUpdate> {
+        self.sender.subscribe()
+    }
+
+    /// Publie une mise à jour, retourne le nombre d'abonnés l'ayant reçue
+    pub fn publish(&self, update: CandleUpdate) -> usize {
+        // Err = aucun abonné, pas une erreur pour nous
+        self.sender.send(update).unwrap_or(0)
+    }
+
+    /// Enregistre la bougie en cours de formation d'une série (remplace la
+    /// précédente), avec le RSI live des séries suivies
+    pub fn set_partial(&self, mut update: CandleUpdate) -> CandleUpdate {
+        update.indicators = self.live_indicators(&update);
+        let key = (update.symbol.clone(), update.timeframe.clone());
+        self.partials.lock().unwrap().insert(key, update.clone());
+        update
+    }
+
+    /// set_partial puis diffusion aux abonnés (mise à jour de la bougie en
+    /// cours sur le graphique, RSI live compris)
+    ///
+    /// RETOUR: Nombre d'abonnés l'ayant reçue
+    pub fn publish_partial(&self, update: CandleUpdate) -> usize {
+        let update = self.set_partial(update);
+        self.publish(update)
+    }
+
+    /// Bougies partielles d'un symbole, par timeframe
+    pub fn partials_for(&self, symbol: &str) -> HashMap<String, CandleUpdate> {
+        self.partials
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|((s, _), _)| s == symbol)
+            .map(|((_, timeframe), update)| (timeframe.clone(), update.clone()))
+            .collect()
+    }
+
+    /// Publie les bougies réelles insérées depuis `last_rowid` (et avance
+    /// les RSI suivis, dont la valeur de clôture accompagne la bougie)
+    ///
+    /// DESIGN: le rowid SQLite croît à chaque insertion, ce qui évite de
+    /// suivre un open_time par (symbol, timeframe)
+    ///
+    /// RETOUR: Nombre de bougies publiées (last_rowid est avancé)
+    pub fn poll_new_candles(&self, conn: &Connection, last_rowid: &mut i64) -> Result<usize> {
+        let mut stmt = conn.prepare_cached(
+            "SELECT rowid, provider, symbol, timeframe, open_time, open, high, low, close, volume
+             FROM candlesticks
+             WHERE rowid > ?1 AND interpolated = 0
+             ORDER BY rowid ASC
+             LIMIT ?2",
+        )?;
+
+        let rows = stmt
+            .query_map(params![*last_rowid, POLL_BATCH_SIZE], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    CandleUpdate {
+                        provider: row.get(1)?,
+                        symbol: row.get(2)?,
+                        timeframe: row.get(3)?,
+                        time: row.get::<_, i64>(4)? / 1000,
+                        open: row.get(5)?,
+                        high: row.get(6)?,
+                        low: row.get(7)?,
+                        close: row.get(8)?,
+                        volume: row.get(9)?,
+                        indicators: None,
+                    },
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let published = rows.len();
+        for (rowid, mut update) in rows {
+            *last_rowid = rowid;
+            self.clear_partial(&update);
+            self.update_rsi(&update);
+            update.indicators = self.live_indicators(&update);
+            self.publish(update);
+        }
+        if published > 0 {
+            debug!(
+                published,
+                last_rowid = *last_rowid,
+                "Nouvelles bougies diffusées"
+            );
+        }
+
+        Ok(published)
+    }
+
+    /// Retire la bougie partielle rattrapée par une bougie clôturée stockée
+    fn clear_partial(&self, stored: &CandleUpdate) {
+        let key = (stored.symbol.clone(), stored.timeframe.clone());
+        let mut partials = self.partials.lock().unwrap();
+        if partials.get(&key).is_some_and(|p| p.time <= stored.time) {
+            partials.remove(&key);
+        }
+    }
+
+    /// Lance un thread surveillant la base toutes les `period`
+    ///
+    /// Les bougies déjà présentes au démarrage ne sont pas rediffusées.
+    /// Le thread se termine (et ferme sa connexion) après shutdown()
+    pub fn start_db_watcher(self: &Arc<Self>, db_path: String, period: Duration) -> JoinHandle<()> {
+        let manager = Arc::clone(self);
+
+        std::thread::spawn(move || {
+            let conn = match open_configured(&db_path) {
+                Ok(conn) => conn,
+                Err(e) => {
+                    error!(error = %e, "Surveillance temps réel désactivée");
+                    return;
+                }
+            };
+
+            let mut last_rowid: i64 = conn
+                .query_row(
+                    "SELECT COALESCE(MAX(rowid), 0) FROM candlesticks",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap_or(0);
+
+            while !manager.is_shut_down() {
+                if let Err(e) = manager.poll_new_candles(&conn, &mut last_rowid) {
+                    warn!(error = %e, "Surveillance temps réel en échec");
+                }
+                std::thread::sleep(period);
+            }
+            debug!("Surveillance temps réel arrêtée");
+        })
+    }
+}
@@ -0,0 +1,134 @@
+/// Module de rétention des bougies
+///
+/// Les timeframes fins (1m, 3m, 5m) grossissent sans limite alors que seul
+/// l'historique récent est utile; les timeframes longs sont gardés indéfiniment
+///
+/// CONFIGURATION (section [retention] de config.toml):
+/// timeframe → durée conservée ("90d", "12w", "48h", "30m").
+/// Un timeframe absent de la table n'est jamais purgé
+use crate::database::prune_candles;
+use crate::error::{CandleError, Result};
+use crate::utils::is_valid_timeframe;
+use rusqlite::{Connection, params};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Durée de rétention parsée par timeframe
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// timeframe → durée conservée en millisecondes
+    rules: BTreeMap<String, i64>,
+}
+
+/// Résultat de la purge d'une série
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PruneReport {
+    pub symbol: String,
+    pub timeframe: String,
+    /// Les bougies d'open_time < cutoff sont supprimées
+    pub cutoff: i64,
+    pub deleted: usize,
+}
+
+/// Parse une durée de rétention: entier suivi de m, h, d ou w
+///
+/// EXEMPLE:
+/// "90d" → 7776000000
+pub fn parse_retention(duration: &str) -> Result<i64> {
+    let invalid = || CandleError::ParseError(format!("Durée de rétention invalide: {}", duration));
+
+    let unit_ms = match duration.chars().last().ok_or_else(invalid)? {
+        'm' => 60_000,
+        'h' => 3_600_000,
+        'd' => 86_400_000,
+        'w' => 604_800_000,
+        _ => return Err(invalid()),
+    };
+    let count: i64 = duration[..duration.len() - 1]
+        .parse()
+        .map_err(|_| invalid())?;
+    if count <= 0 {
+        return Err(invalid());
+    }
+    Ok(count * unit_ms)
+}
+
+impl RetentionPolicy {
+    /// Construit la politique depuis la table [retention] de la configuration
+    ///
+    /// ERREURS: timeframe inconnu (ValidationError), durée invalide (ParseError)
+    pub fn from_config(rules: &BTreeMap<String, String>) -> Result<Self> {
+        let mut parsed = BTreeMap::new();
+        for (timeframe, duration) in rules {
+            if !is_valid_timeframe(timeframe) {
+                return Err(CandleError::ValidationError(format!(
+                    "Rétention: timeframe inconnu {}",
+                    timeframe
+                )));
+            }
+            parsed.insert(timeframe.clone(), parse_retention(duration)?);
+        }
+        Ok(RetentionPolicy { rules: parsed })
+    }
+
+    /// true si aucun timeframe n'a de rétention
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Durée conservée pour un timeframe (None: conservé indéfiniment)
+    pub fn retention_ms(&self, timeframe: &str) -> Option<i64> {
+        self.rules.get(timeframe).copied()
+    }
+
+    /// Purge chaque timeframe configuré
+    ///
+    /// ALGORITHME:
+    /// 1. cutoff = now_ms - rétention du timeframe
+    /// 2. Séries concernées: `symbol`, ou tous les symboles du timeframe si None
+    /// 3. prune_candles pour chacune (une transaction par série)
+    ///
+    /// RETOUR: une entrée par série examinée (deleted = 0 si rien à purger)
+    pub fn apply(
+        &self,
+        conn: &mut Connection,
+        provider: &str,
+        symbol: Option<&str>,
+        now_ms: i64,
+    ) -> Result<Vec<PruneReport>> {
+        let mut reports = Vec::new();
+
+        for (timeframe, retention) in &self.rules {
+            let cutoff = now_ms - retention;
+            let symbols = match symbol {
+                Some(s) => vec![s.to_string()],
+                None => Self::symbols_of(conn, provider, timeframe)?,
+            };
+
+            for symbol in symbols {
+                let deleted = prune_candles(conn, provider, &symbol, timeframe, cutoff)?;
+                reports.push(PruneReport {
+                    symbol,
+                    timeframe: timeframe.clone(),
+                    cutoff,
+                    deleted,
+                });
+            }
+        }
+
+        Ok(reports)
+    }
+
+    /// Symboles ayant des bougies pour un timeframe
+    fn symbols_of(conn: &Connection, provider: &str, timeframe: &str) -> Result<Vec<String>> {
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT symbol FROM candlesticks
+             WHERE provider = ?1 AND timeframe = ?2
+             ORDER BY symbol",
+        )?;
+        let symbols = stmt
+            .query_map(params![provider, timeframe], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<String>, _>>()?;
+        Ok(symbols)
+    }
+}
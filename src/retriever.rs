@@ -4,35 +4,94 @@
 /// - Récupère UN batch à la fois
 /// - Retourne le nombre d'insertions réelles et si le timeframe est épuisé
 /// - Pas de boucle interne, la boucle est dans main.rs
-use crate::gap_filler::GapFiller;
-use crate::timeframe_status::TimeframeStatus;
-use anyhow::Result;
-use binance::market::*;
-use binance::model::KlineSummaries;
+/// - Mode dry-run (dry_run): lecture de l'API seulement, bilan dans un DryRunReport
+use crate::circuit_breaker::CircuitBreaker;
+use crate::database::insert_candle_records;
+use crate::error::{CandleError, Result};
+use crate::gap_filler::{FillStrategy, GapFiller};
+use crate::indicators::registry::IndicatorRegistry;
+use crate::kline_provider::KlineProvider;
+use crate::metrics::METRICS;
+use crate::models::CandleRecord;
+use crate::rate_limiter::RateLimiter;
+use crate::timeframe_status::{Coverage, TimeframeStatus};
+use binance::market::Market;
 use rusqlite::{Connection, params};
+use serde::Serialize;
+use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, instrument, warn};
 
 const BATCH_SIZE: usize = 1000;
 const PROVIDER: &str = "binance";
 
+/// Poids Binance d'un appel GET /api/v3/klines
+pub const KLINES_WEIGHT: u32 = 2;
+
+/// Bilan d'un timeframe en mode dry-run (rien n'est écrit)
+///
+/// DESIGN: conservé par l'appelant d'un batch à l'autre (le récupérateur est
+/// recréé à chaque itération), il sert aussi de curseur: le batch suivant
+/// repart juste avant oldest_available_ms
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DryRunReport {
+    pub timeframe: String,
+    /// Bougies renvoyées par l'API et absentes de la base
+    pub candles_available: usize,
+    /// open_time de la plus ancienne bougie lue (0: aucune)
+    pub oldest_available_ms: i64,
+    /// open_time de la plus récente bougie lue (0: aucune)
+    pub newest_available_ms: i64,
+    /// Appels klines nécessaires (un par batch lu)
+    pub estimated_batches: usize,
+    /// Couverture de la série déjà stockée, avant le backfill
+    pub stored: Coverage,
+}
+
+impl DryRunReport {
+    pub fn new(timeframe: &str) -> Self {
+        DryRunReport {
+            timeframe: timeframe.to_string(),
+            ..DryRunReport::default()
+        }
+    }
+}
+
 /// Récupérateur de bougies depuis Binance
-pub struct CandleRetriever<'a> {
-    market: &'a Market,
+///
+/// DESIGN: générique sur la source de klines (Market par défaut), un
+/// MockKlineProvider permet de tester le backfill sans réseau
+pub struct CandleRetriever<'a, P: KlineProvider = Market> {
+    market: &'a P,
     conn: &'a mut Connection,
     symbol: &'a str,
     timeframe: &'a str,
     start_timestamp_ms: Option<i64>,
+    rate_limiter: Arc<RateLimiter>,
+    circuit_breaker: Arc<CircuitBreaker>,
+    fill_strategy: FillStrategy,
+    /// Some: mode dry-run, aucune écriture (bilan accumulé ici)
+    dry_run: Option<&'a mut DryRunReport>,
+    /// Indicateurs recalculés après chaque batch inséré
+    indicators: Option<&'a IndicatorRegistry>,
 }
 
-impl<'a> CandleRetriever<'a> {
+impl<'a, P: KlineProvider> CandleRetriever<'a, P> {
     /// Crée un nouveau récupérateur
+    ///
+    /// DESIGN: rate_limiter et circuit_breaker sont partagés (Arc) entre
+    /// tous les récupérateurs car ils protègent la même API
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        market: &'a Market,
+        market: &'a P,
         conn: &'a mut Connection,
         symbol: &'a str,
         timeframe: &'a str,
         start_timestamp_ms: Option<i64>,
+        rate_limiter: Arc<RateLimiter>,
+        circuit_breaker: Arc<CircuitBreaker>,
+        fill_strategy: FillStrategy,
     ) -> Self {
         CandleRetriever {
             market,
@@ -40,22 +99,51 @@ impl<'a> CandleRetriever<'a> {
             symbol,
             timeframe,
             start_timestamp_ms,
+            rate_limiter,
+            circuit_breaker,
+            fill_strategy,
+            dry_run: None,
+            indicators: None,
         }
     }
 
+    /// Passe en mode dry-run: les batches sont lus mais jamais insérés,
+    /// le bilan est accumulé dans `report`
+    pub fn dry_run(mut self, report: &'a mut DryRunReport) -> Self {
+        self.dry_run = Some(report);
+        self
+    }
+
+    /// Recalcule les indicateurs de `registry` sur chaque batch inséré (et
+    /// les bougies comblées de sa plage), voir IndicatorRegistry::recalculate
+    pub fn indicators(mut self, registry: &'a IndicatorRegistry) -> Self {
+        self.indicators = Some(registry);
+        self
+    }
+
     /// Récupère et insère UN batch de bougies
     ///
     /// RETOUR: (nombre_insertions_reelles, is_exhausted)
     /// - nombre_insertions_reelles: nouvelles bougies insérées (pas les doublons)
     /// - is_exhausted: true si le timeframe est épuisé (toutes les bougies déjà en base)
+    ///
+    /// DRY-RUN: toujours 0 insertion, is_exhausted selon les mêmes règles
+    /// qu'une vraie exécution (voir record_dry_run)
+    #[instrument(skip(self), fields(symbol = self.symbol, timeframe = self.timeframe))]
     pub fn fetch_one_batch(&mut self) -> Result<(i64, bool)> {
-        // Déterminer le point de départ (dernière bougie stockée ou maintenant)
-        let end_time_ms = self.determine_start_point()?;
+        // Déterminer le point de départ (dernière bougie stockée ou maintenant,
+        // en dry-run juste avant la plus ancienne bougie déjà lue)
+        let end_time_ms = match self.dry_run.as_deref() {
+            Some(report) if report.estimated_batches > 0 => report.oldest_available_ms - 1,
+            _ => self.determine_start_point()?,
+        };
 
-        // Récupérer le batch depuis l'API (TOUJOURS en backward)
-        let klines = match self.fetch_batch(end_time_ms) {
+        // Récupérer le batch depuis l'API (TOUJOURS en backward), via le disjoncteur
+        let klines = match self.circuit_breaker.call(|| self.fetch_batch(end_time_ms)) {
             Ok(k) => k,
+            Err(e @ CandleError::CircuitBreakerOpen { .. }) => return Err(e),
             Err(e) => {
+                warn!(error = %e, "Échec de récupération, pause de 5s");
                 thread::sleep(Duration::from_secs(5));
                 return Err(e);
             }
@@ -63,33 +151,73 @@ impl<'a> CandleRetriever<'a> {
 
         // Vérifier si on a atteint la limite historique
         if klines.is_empty() {
+            // L'appel a coûté une requête, y compris en vraie exécution
+            if let Some(report) = self.dry_run.as_deref_mut() {
+                report.estimated_batches += 1;
+            }
             return Ok((0, true)); // Épuisé: API ne retourne plus rien
         }
 
         let oldest_kline_time = klines[0].open_time;
         let newest_kline_time = klines[klines.len() - 1].open_time;
 
+        if self.dry_run.is_some() {
+            return self.record_dry_run(&klines);
+        }
+
         // Insérer le batch
         let inserted = self.insert_batch(&klines)?;
+        METRICS
+            .backfill_candles_inserted_total
+            .with_label_values(&[self.symbol])
+            .inc_by(inserted as u64);
 
-        // Mettre à jour la progression pour monitoring
-        let _ = TimeframeStatus::update_progress(
+        debug!(
+            inserted,
+            received = klines.len(),
+            oldest_kline_time,
+            "Batch inséré"
+        );
+
+        // Mettre à jour la progression pour monitoring (non bloquant)
+        if let Err(e) = TimeframeStatus::update_progress(
             self.conn,
             PROVIDER,
             self.symbol,
             self.timeframe,
             oldest_kline_time,
-        );
+            newest_kline_time,
+        ) {
+            warn!(error = %e, "Mise à jour de timeframe_status impossible");
+        }
 
-        // Combler les gaps
-        let _ = GapFiller::fill_gaps_in_range(
+        // Combler les gaps selon la stratégie configurée (non bloquant)
+        if let Err(e) = GapFiller::fill_gaps_in_range(
             self.conn,
             PROVIDER,
             self.symbol,
             self.timeframe,
             oldest_kline_time,
             newest_kline_time,
-        );
+            self.fill_strategy,
+        ) {
+            warn!(error = %e, "Comblement des gaps impossible");
+        }
+
+        // Indicateurs à jour des bougies insérées et comblées (non bloquant)
+        if let Some(registry) = self.indicators.filter(|_| inserted > 0) {
+            let recalculated = registry.recalculate(
+                self.conn,
+                PROVIDER,
+                self.symbol,
+                self.timeframe,
+                oldest_kline_time,
+                newest_kline_time,
+            );
+            if let Err(e) = recalculated {
+                warn!(error = %e, "Recalcul des indicateurs impossible");
+            }
+        }
 
         // Épuisé si: aucune insertion (tout déjà en base) OU date limite atteinte
         let is_exhausted = inserted == 0 || self.is_date_limit_reached(oldest_kline_time);
@@ -97,6 +225,47 @@ impl<'a> CandleRetriever<'a> {
         Ok((inserted, is_exhausted))
     }
 
+    /// Ajoute un batch lu au bilan dry-run
+    ///
+    /// Seules les bougies absentes de la base comptent. Épuisé comme en vraie
+    /// exécution: plus aucune bougie nouvelle, ou date limite atteinte
+    fn record_dry_run(&mut self, klines: &[binance::model::KlineSummary]) -> Result<(i64, bool)> {
+        let oldest_kline_time = klines[0].open_time;
+        let newest_kline_time = klines[klines.len() - 1].open_time;
+        let stored: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM candlesticks
+             WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+                   AND open_time BETWEEN ?4 AND ?5",
+            params![
+                PROVIDER,
+                self.symbol,
+                self.timeframe,
+                oldest_kline_time,
+                newest_kline_time
+            ],
+            |row| row.get(0),
+        )?;
+        let missing = klines.len().saturating_sub(stored as usize);
+        let date_limit_reached = self.is_date_limit_reached(oldest_kline_time);
+
+        let report = self.dry_run.as_deref_mut().expect("mode dry-run");
+        if report.estimated_batches == 0 {
+            report.newest_available_ms = newest_kline_time;
+        }
+        report.estimated_batches += 1;
+        report.candles_available += missing;
+        report.oldest_available_ms = oldest_kline_time;
+
+        debug!(
+            missing,
+            received = klines.len(),
+            oldest_kline_time,
+            "Batch lu (dry-run)"
+        );
+
+        Ok((0, missing == 0 || date_limit_reached))
+    }
+
     /// Détermine le point de départ (dernière bougie stockée ou maintenant)
     fn determine_start_point(&self) -> Result<i64> {
         let last_stored =
@@ -118,20 +287,15 @@ impl<'a> CandleRetriever<'a> {
 
     /// Récupère un batch de bougies depuis l'API Binance (TOUJOURS en backward)
     fn fetch_batch(&self, end_time_ms: i64) -> Result<Vec<binance::model::KlineSummary>> {
-        let klines_data = self
-            .market
-            .get_klines(
-                self.symbol,
-                self.timeframe,
-                Some(BATCH_SIZE as u16),
-                None,
-                Some(end_time_ms as u64),
-            )
-            .map_err(|e| anyhow::anyhow!("Erreur API Binance: {:?}", e))?;
-
-        let mut klines = match klines_data {
-            KlineSummaries::AllKlineSummaries(vec) => vec,
-        };
+        self.wait_for_rate_limit();
+
+        let mut klines = self.market.get_klines(
+            self.symbol,
+            self.timeframe,
+            Some(BATCH_SIZE as u16),
+            None,
+            Some(end_time_ms as u64),
+        )?;
 
         // IMPORTANT: Filtrer les bougies incomplètes (en cours de formation)
         // Une bougie est complète si son close_time est dans le passé
@@ -142,55 +306,27 @@ impl<'a> CandleRetriever<'a> {
         Ok(klines)
     }
 
+    /// Attend que le budget de poids Binance permette une requête klines
+    fn wait_for_rate_limit(&self) {
+        wait_for_klines_budget(&self.rate_limiter);
+    }
+
     /// Insère un batch de bougies dans la base de données
     ///
     /// RETOUR: Nombre de bougies réellement insérées (pas les doublons)
     fn insert_batch(&mut self, klines: &[binance::model::KlineSummary]) -> Result<i64> {
-        let tx = self.conn.transaction()?;
-        let mut inserted = 0i64;
-
-        {
-            let mut stmt = tx.prepare(
-                "INSERT OR IGNORE INTO candlesticks (
-                    provider, symbol, timeframe, open_time, open, high, low, close, volume,
-                    close_time, quote_asset_volume, number_of_trades,
-                    taker_buy_base_asset_volume, taker_buy_quote_asset_volume, interpolated
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
-            )?;
-
-            for kline in klines {
-                let changes = stmt.execute(params![
-                    PROVIDER,
-                    self.symbol,
-                    self.timeframe,
-                    kline.open_time,
-                    kline.open.parse::<f64>().unwrap_or(0.0),
-                    kline.high.parse::<f64>().unwrap_or(0.0),
-                    kline.low.parse::<f64>().unwrap_or(0.0),
-                    kline.close.parse::<f64>().unwrap_or(0.0),
-                    kline.volume.parse::<f64>().unwrap_or(0.0),
-                    kline.close_time,
-                    kline.quote_asset_volume.parse::<f64>().unwrap_or(0.0),
-                    kline.number_of_trades,
-                    kline
-                        .taker_buy_base_asset_volume
-                        .parse::<f64>()
-                        .unwrap_or(0.0),
-                    kline
-                        .taker_buy_quote_asset_volume
-                        .parse::<f64>()
-                        .unwrap_or(0.0),
-                    0, // interpolated = 0 (données réelles)
-                ])?;
-
-                if changes > 0 {
-                    inserted += 1;
-                }
-            }
-        }
+        let candles: Vec<CandleRecord> = klines.iter().map(|k| self.to_record(k)).collect();
 
+        let tx = self.conn.transaction()?;
+        let inserted = insert_candle_records(&tx, &candles)?;
         tx.commit()?;
-        Ok(inserted)
+
+        Ok(inserted as i64)
+    }
+
+    /// Convertit une kline Binance (prix en texte) en bougie réelle
+    fn to_record(&self, kline: &binance::model::KlineSummary) -> CandleRecord {
+        kline_to_record(self.symbol, self.timeframe, kline)
     }
 
     /// Vérifie si la date limite utilisateur est atteinte
@@ -202,3 +338,48 @@ impl<'a> CandleRetriever<'a> {
         }
     }
 }
+
+/// Attend que le budget de poids Binance permette une requête klines
+///
+/// Partagé par CandleRetriever et verify::spot_check: avec le même
+/// RateLimiter, les deux consomment le même budget
+pub fn wait_for_klines_budget(rate_limiter: &RateLimiter) {
+    while let Some(wait) = rate_limiter.check_and_record(KLINES_WEIGHT) {
+        debug!(
+            wait_ms = wait.as_millis() as u64,
+            "Budget de poids épuisé, attente"
+        );
+        thread::sleep(wait);
+    }
+}
+
+/// Convertit une kline Binance (prix en texte) en bougie réelle du
+/// provider binance
+///
+/// SUBTILITÉ: un champ illisible vaut 0.0 (jamais d'erreur)
+pub fn kline_to_record(
+    symbol: &str,
+    timeframe: &str,
+    kline: &binance::model::KlineSummary,
+) -> CandleRecord {
+    let parse = |value: &str| value.parse::<f64>().unwrap_or(0.0);
+
+    CandleRecord {
+        provider: PROVIDER.to_string(),
+        symbol: symbol.to_string(),
+        timeframe: timeframe.to_string(),
+        open_time: kline.open_time,
+        open: parse(&kline.open),
+        high: parse(&kline.high),
+        low: parse(&kline.low),
+        close: parse(&kline.close),
+        volume: parse(&kline.volume),
+        close_time: kline.close_time,
+        quote_asset_volume: parse(&kline.quote_asset_volume),
+        number_of_trades: kline.number_of_trades,
+        taker_buy_base_asset_volume: parse(&kline.taker_buy_base_asset_volume),
+        taker_buy_quote_asset_volume: parse(&kline.taker_buy_quote_asset_volume),
+        interpolated: 0, // données réelles
+        derived_from: None,
+    }
+}
@@ -0,0 +1,220 @@
+/// Module de l'état des bases (binaire status)
+///
+/// Une ligne par série: bornes, bougies stockées et interpolées, bougies
+/// manquantes (TimeframeStatus::coverage via symbol_candle_stats), progression
+/// du backfill (timeframe_status), dernier passage de vérification
+/// (verification_runs, verify_data --record)
+///
+/// PERFORMANCE: les fichiers d'un répertoire sont ouverts en lecture seule;
+/// chaque série est lue une fois dans l'ordre de la clé primaire
+/// (provider, symbol, timeframe, open_time), sans chargement en mémoire
+use crate::database::{read_only_uri, symbol_candle_stats};
+use crate::error::Result;
+use crate::models::CandleStats;
+use crate::utils::format_timestamp_ms;
+use crate::verification_history::{VerificationHistory, VerificationRun};
+use rusqlite::{Connection, OpenFlags, OptionalExtension, params};
+use serde::Serialize;
+use std::fmt::Write;
+use std::path::Path;
+
+/// État d'une série: statistiques et progression enregistrée
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeriesStatus {
+    #[serde(flatten)]
+    pub stats: CandleStats,
+    /// false si le timeframe n'a pas de ligne de statut
+    pub is_complete: bool,
+    /// None si le timeframe n'a pas de ligne de statut
+    pub last_updated: Option<i64>,
+    /// Dernier passage de verify_data --record (None: jamais enregistré)
+    pub last_verification: Option<VerificationRun>,
+}
+
+/// État d'un fichier .db
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileStatus {
+    pub file: String,
+    /// Erreur empêchant la lecture (fichier illisible, table absente...)
+    pub error: Option<String>,
+    pub series: Vec<SeriesStatus>,
+}
+
+/// État de chaque série d'une base, limité à un symbole si `symbol` est donné
+///
+/// RETOUR: trié par provider, symbole puis durée du timeframe
+pub fn database_status(conn: &Connection, symbol: Option<&str>) -> Result<Vec<SeriesStatus>> {
+    let stats = symbol_candle_stats(conn, symbol)?;
+
+    // Base créée par une ancienne version: pas de progression enregistrée
+    let has_status: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'timeframe_status'",
+        [],
+        |row| row.get(0),
+    )?;
+    let mut status_stmt = if has_status {
+        Some(conn.prepare(
+            "SELECT is_complete, last_updated FROM timeframe_status
+             WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3",
+        )?)
+    } else {
+        None
+    };
+
+    stats
+        .into_iter()
+        .map(|stats| {
+            let progress = match status_stmt.as_mut() {
+                Some(stmt) => stmt
+                    .query_row(
+                        params![stats.provider, stats.symbol, stats.timeframe],
+                        |row| Ok((row.get::<_, i64>(0)? != 0, row.get(1)?)),
+                    )
+                    .optional()?,
+                None => None,
+            };
+            let last_verification = VerificationHistory::latest(
+                conn,
+                &stats.provider,
+                &stats.symbol,
+                &stats.timeframe,
+            )?;
+            Ok(SeriesStatus {
+                stats,
+                is_complete: progress.is_some_and(|(complete, _)| complete),
+                last_updated: progress.map(|(_, last_updated)| last_updated),
+                last_verification,
+            })
+        })
+        .collect()
+}
+
+/// État de chaque fichier .db d'un répertoire, triés par nom
+///
+/// Un fichier illisible n'interrompt pas le parcours (FileStatus::error)
+///
+/// ERREURS: répertoire illisible
+pub fn directory_status(db_dir: &Path, symbol: Option<&str>) -> Result<Vec<FileStatus>> {
+    let mut databases: Vec<_> = std::fs::read_dir(db_dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "db"))
+        .collect();
+    databases.sort();
+
+    Ok(databases
+        .iter()
+        .map(|path| file_status(path, symbol))
+        .collect())
+}
+
+/// État d'un fichier .db ouvert en lecture seule (jamais migré ni modifié)
+pub fn file_status(path: &Path, symbol: Option<&str>) -> FileStatus {
+    let file = path.display().to_string();
+    match Connection::open_with_flags(
+        read_only_uri(path),
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_URI,
+    )
+    .map_err(Into::into)
+    .and_then(|conn| database_status(&conn, symbol))
+    {
+        Ok(series) => FileStatus {
+            file,
+            error: None,
+            series,
+        },
+        Err(e) => FileStatus {
+            file,
+            error: Some(e.to_string()),
+            series: Vec::new(),
+        },
+    }
+}
+
+/// Tableau lisible: un bloc par fichier et par symbole, une ligne par timeframe
+pub fn render_table(files: &[FileStatus]) -> String {
+    let mut out = String::new();
+    for file in files {
+        if let Some(error) = &file.error {
+            let _ = writeln!(out, "✗ {}: {}", file.file, error);
+            continue;
+        }
+        if file.series.is_empty() {
+            let _ = writeln!(out, "{}: aucune bougie", file.file);
+            continue;
+        }
+
+        let mut previous: Option<(&str, &str)> = None;
+        for series in &file.series {
+            let stats = &series.stats;
+            let key = (stats.provider.as_str(), stats.symbol.as_str());
+            if previous != Some(key) {
+                if previous.is_some() {
+                    out.push('\n');
+                }
+                let _ = writeln!(out, "{} — {} ({})", file.file, stats.symbol, stats.provider);
+                let _ = writeln!(
+                    out,
+                    "{:<4} | {:<19} | {:<19} | {:>9} | {:>8} | {:>10} | {:<7} | {:<19} | Vérification",
+                    "TF",
+                    "Début",
+                    "Fin",
+                    "Bougies",
+                    "Interp.",
+                    "Manquantes",
+                    "Complet",
+                    "Mis à jour"
+                );
+                let _ = writeln!(out, "{}", "-".repeat(141));
+                previous = Some(key);
+            }
+            let _ = writeln!(
+                out,
+                "{:<4} | {:<19} | {:<19} | {:>9} | {:>8} | {:>10} | {:<7} | {:<19} | {}",
+                stats.timeframe,
+                stats
+                    .first_open_time
+                    .map(format_timestamp_ms)
+                    .unwrap_or_default(),
+                stats
+                    .last_open_time
+                    .map(format_timestamp_ms)
+                    .unwrap_or_default(),
+                stats.count,
+                stats.interpolated_count,
+                stats.missing_count,
+                if series.is_complete { "oui" } else { "non" },
+                series
+                    .last_updated
+                    .map(format_timestamp_ms)
+                    .unwrap_or_else(|| "-".to_string()),
+                series
+                    .last_verification
+                    .as_ref()
+                    .map(verification_cell)
+                    .unwrap_or_else(|| "-".to_string())
+            );
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Date du dernier passage et résultat: OK, ou le nombre de gaps,
+/// d'overlaps et de valeurs anormales
+fn verification_cell(run: &VerificationRun) -> String {
+    let result = if run.passed() {
+        "OK".to_string()
+    } else {
+        [
+            (run.gaps, "gaps"),
+            (run.overlaps, "overlaps"),
+            (run.value_anomalies, "valeurs"),
+        ]
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{} {}", count, label))
+        .collect::<Vec<_>>()
+        .join(", ")
+    };
+    format!("{} {}", format_timestamp_ms(run.run_at), result)
+}
@@ -0,0 +1,151 @@
+/// Module des données de test synthétiques (feature `test-fixtures`)
+///
+/// Générateurs de bougies pour les binaires src/bin/test_*.rs et les
+/// benchmarks, à la place des INSERT écrits à la main dans chaque binaire
+///
+/// - fixture_candle: bougie plate à valeurs connues (assertions exactes)
+/// - candles_at: bougies plates aux positions données d'une grille
+/// - generate_candles: marche aléatoire reproductible
+/// - generate_candles_with_gaps: idem, positions retirées
+/// - insert_fixtures: insertion groupée sous une série
+///
+/// DESIGN: les bougies générées n'ont ni provider, ni symbole, ni timeframe;
+/// insert_fixtures les attribue au moment de l'insertion
+use crate::database::insert_candle_records;
+use crate::error::Result;
+use crate::models::CandleRecord;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rusqlite::Connection;
+
+/// Prix de départ de generate_candles_with_gaps
+pub const FIXTURE_START_PRICE: f64 = 100.0;
+
+/// Variation maximale du close d'une bougie à l'autre (marche aléatoire)
+const MAX_STEP_RATIO: f64 = 0.005;
+
+/// Bougie réelle plate: open = close, high / low à ±5, volume 1000,
+/// 100 trades, volumes dérivés du volume (quote ×100, taker ×0.4 / ×40)
+///
+/// EXEMPLE: fixture_candle(t, 300_000, 100.0) → high 105, low 95,
+/// close_time t + 299_999
+pub fn fixture_candle(open_time: i64, interval: i64, close: f64) -> CandleRecord {
+    let volume = 1000.0;
+    CandleRecord {
+        provider: String::new(),
+        symbol: String::new(),
+        timeframe: String::new(),
+        open_time,
+        open: close,
+        high: close + 5.0,
+        low: close - 5.0,
+        close,
+        volume,
+        close_time: open_time + interval - 1,
+        quote_asset_volume: volume * 100.0,
+        number_of_trades: 100,
+        taker_buy_base_asset_volume: volume * 0.4,
+        taker_buy_quote_asset_volume: volume * 40.0,
+        interpolated: 0,
+        derived_from: None,
+    }
+}
+
+/// Bougies plates (fixture_candle) aux positions d'une grille
+/// start_ms + position × interval
+pub fn candles_at(
+    start_ms: i64,
+    interval: i64,
+    positions: &[i64],
+    close: f64,
+) -> Vec<CandleRecord> {
+    positions
+        .iter()
+        .map(|position| fixture_candle(start_ms + position * interval, interval, close))
+        .collect()
+}
+
+/// `count` bougies consécutives en marche aléatoire depuis `start_price`
+///
+/// ALGORITHME: open = close précédent, close = open × (1 ± 0.5 %),
+/// high / low au-delà du corps, volume entre 500 et 1500
+///
+/// SUBTILITÉ: générateur initialisé avec start_ms, la même série est
+/// produite à chaque appel (tests reproductibles)
+pub fn generate_candles(
+    start_ms: i64,
+    count: usize,
+    interval: i64,
+    start_price: f64,
+) -> Vec<CandleRecord> {
+    let mut rng = StdRng::seed_from_u64(start_ms as u64);
+    let mut price = start_price;
+
+    (0..count as i64)
+        .map(|position| {
+            let open = price;
+            let close = open * (1.0 + rng.random_range(-MAX_STEP_RATIO..MAX_STEP_RATIO));
+            let high = open.max(close) * (1.0 + rng.random_range(0.0..MAX_STEP_RATIO));
+            let low = open.min(close) * (1.0 - rng.random_range(0.0..MAX_STEP_RATIO));
+            let volume = rng.random_range(500.0..1500.0);
+            price = close;
+
+            CandleRecord {
+                open,
+                high,
+                low,
+                close,
+                volume,
+                quote_asset_volume: volume * close,
+                number_of_trades: (volume / 10.0) as i64,
+                taker_buy_base_asset_volume: volume * 0.4,
+                taker_buy_quote_asset_volume: volume * 0.4 * close,
+                ..fixture_candle(start_ms + position * interval, interval, close)
+            }
+        })
+        .collect()
+}
+
+/// generate_candles (FIXTURE_START_PRICE) sans les positions `gap_positions`
+///
+/// EXEMPLE: (t, 5, 300_000, &[2]) → 4 bougies, positions 0, 1, 3, 4
+pub fn generate_candles_with_gaps(
+    start_ms: i64,
+    count: usize,
+    interval: i64,
+    gap_positions: &[usize],
+) -> Vec<CandleRecord> {
+    generate_candles(start_ms, count, interval, FIXTURE_START_PRICE)
+        .into_iter()
+        .enumerate()
+        .filter(|(position, _)| !gap_positions.contains(position))
+        .map(|(_, candle)| candle)
+        .collect()
+}
+
+/// Insère les bougies sous (provider, symbol, timeframe), en une transaction
+///
+/// RETOUR: nombre de bougies insérées (doublons ignorés)
+pub fn insert_fixtures(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    candles: &[CandleRecord],
+) -> Result<usize> {
+    let candles: Vec<CandleRecord> = candles
+        .iter()
+        .map(|candle| CandleRecord {
+            provider: provider.to_string(),
+            symbol: symbol.to_string(),
+            timeframe: timeframe.to_string(),
+            ..candle.clone()
+        })
+        .collect();
+
+    // unchecked_transaction: &Connection suffit (pas de transaction en cours)
+    let tx = conn.unchecked_transaction()?;
+    let inserted = insert_candle_records(&tx, &candles)?;
+    tx.commit()?;
+    Ok(inserted)
+}
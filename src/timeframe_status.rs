@@ -1,38 +1,539 @@
 /// Module de monitoring de la progression des timeframes
 ///
 /// Ce module track la progression de chaque timeframe pour monitoring uniquement
-use anyhow::Result;
-use rusqlite::{Connection, params};
-use std::time::{SystemTime, UNIX_EPOCH};
+use crate::error::Result;
+use crate::utils::{compare_timeframes, parse_timeframe};
+use rusqlite::{Connection, OptionalExtension, params, params_from_iter};
+use serde::Serialize;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Gestionnaire du statut des timeframes
+///
+/// Schéma: SQL_CREATE_TABLE_TIMEFRAME_STATUS (database.rs)
 pub struct TimeframeStatus;
 
+/// Progression enregistrée d'un timeframe
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeframeProgress {
+    pub oldest_candle_time: Option<i64>,
+    pub newest_candle_time: Option<i64>,
+    pub is_complete: bool,
+    pub last_updated: i64,
+}
+
+/// Ligne de timeframe_status, avec le décompte des bougies stockées (voir
+/// TimeframeStatus::list et TimeframeStatus::list_all)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TimeframeStatusRow {
+    pub provider: String,
+    pub symbol: String,
+    pub timeframe: String,
+    pub oldest_candle_time: Option<i64>,
+    pub newest_candle_time: Option<i64>,
+    pub is_complete: bool,
+    pub last_updated: i64,
+    /// Bougies du timeframe dans candlesticks (réelles + interpolées)
+    pub total_rows: i64,
+    pub interpolated_rows: i64,
+}
+
+/// Couverture d'une plage d'open_time par les bougies stockées
+///
+/// - expected: (end - start) / intervalle + 1 bougies attendues
+/// - actual: bougies présentes (réelles + interpolées)
+/// - percentage: actual / expected * 100 (0 si rien n'est attendu)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CoverageReport {
+    pub actual: i64,
+    pub expected: i64,
+    pub percentage: f64,
+    pub interpolated_count: i64,
+    pub real_count: i64,
+}
+
+/// Couverture complète d'une série, de sa première à sa dernière bougie
+/// (voir TimeframeStatus::coverage)
+///
+/// - expected: bougies attendues entre first et last inclus, mois
+///   calendaires pour 1M
+/// - missing: bougies absentes des trous entre bougies consécutives
+/// - gaps: nombre de ces trous
+/// - interpolated: bougies synthétiques (comptées dans stored)
+/// - pct_complete: bougies réelles / attendues * 100 (une bougie seule: 100)
+///
+/// DESIGN: source unique de /api/stats, du binaire status et du dry-run;
+/// une série vide est un cas à part (Empty), pas un 0 % ambigu
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum Coverage {
+    #[default]
+    Empty,
+    Series {
+        first: i64,
+        last: i64,
+        stored: i64,
+        expected: i64,
+        missing: i64,
+        gaps: i64,
+        interpolated: i64,
+        pct_complete: f64,
+    },
+}
+
 impl TimeframeStatus {
     /// Met à jour la progression d'un timeframe
     ///
     /// ALGORITHME:
     /// Appelé après chaque batch pour tracker la progression
     /// Utile pour monitoring et debug
+    ///
+    /// - oldest_candle_time: remplacé (le backfill remonte dans le temps)
+    /// - newest_candle_time: conserve le maximum connu
+    /// - is_complete: conservé (UPSERT et non INSERT OR REPLACE, qui
+    ///   réinitialiserait les colonnes non fournies)
     pub fn update_progress(
         conn: &Connection,
         provider: &str,
         symbol: &str,
         timeframe: &str,
         oldest_candle_time: i64,
+        newest_candle_time: i64,
+    ) -> Result<()> {
+        let now = Self::current_timestamp_ms()?;
+
+        conn.execute(
+            "INSERT INTO timeframe_status
+             (provider, symbol, timeframe, oldest_candle_time, newest_candle_time, last_updated)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT (provider, symbol, timeframe) DO UPDATE SET
+                 oldest_candle_time = excluded.oldest_candle_time,
+                 newest_candle_time = MAX(COALESCE(newest_candle_time, excluded.newest_candle_time),
+                                          excluded.newest_candle_time),
+                 last_updated = excluded.last_updated",
+            params![
+                provider,
+                symbol,
+                timeframe,
+                oldest_candle_time,
+                newest_candle_time,
+                now
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Avance la bougie la plus récente d'un timeframe (flux temps réel)
+    ///
+    /// Même UPSERT que update_progress, sans toucher oldest_candle_time: la
+    /// reprise du backfill (get_last_candle_time) n'est pas déplacée par les
+    /// bougies live
+    ///
+    /// - newest_candle_time: conserve le maximum connu (bougie arrivée dans
+    ///   le désordre sans effet)
+    /// - is_complete: conservé, jamais positionné ici
+    pub fn update_newest(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        newest_candle_time: i64,
+    ) -> Result<()> {
+        let now = Self::current_timestamp_ms()?;
+
+        conn.execute(
+            "INSERT INTO timeframe_status
+             (provider, symbol, timeframe, newest_candle_time, last_updated)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT (provider, symbol, timeframe) DO UPDATE SET
+                 newest_candle_time = MAX(COALESCE(newest_candle_time, excluded.newest_candle_time),
+                                          excluded.newest_candle_time),
+                 last_updated = excluded.last_updated",
+            params![provider, symbol, timeframe, newest_candle_time, now],
+        )?;
+
+        Ok(())
+    }
+
+    /// Marque un timeframe comme complet (limite historique ou date demandée atteinte)
+    ///
+    /// Crée la ligne si le timeframe n'a encore aucune progression enregistrée
+    pub fn mark_complete(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+    ) -> Result<()> {
+        let now = Self::current_timestamp_ms()?;
+
+        conn.execute(
+            "INSERT INTO timeframe_status (provider, symbol, timeframe, is_complete, last_updated)
+             VALUES (?1, ?2, ?3, 1, ?4)
+             ON CONFLICT (provider, symbol, timeframe) DO UPDATE SET
+                 is_complete = 1,
+                 last_updated = excluded.last_updated",
+            params![provider, symbol, timeframe, now],
+        )?;
+
+        Ok(())
+    }
+
+    /// Force une nouvelle récupération complète d'un timeframe
+    ///
+    /// is_complete repasse à 0 et oldest_candle_time est effacé: le prochain
+    /// backfill repart de la bougie la plus récente (bougies déjà stockées
+    /// ignorées par INSERT OR IGNORE). Sans ligne de statut: aucun effet
+    pub fn reset(conn: &Connection, provider: &str, symbol: &str, timeframe: &str) -> Result<()> {
+        let now = Self::current_timestamp_ms()?;
+
+        conn.execute(
+            "UPDATE timeframe_status SET
+                 is_complete = 0,
+                 oldest_candle_time = NULL,
+                 last_updated = ?4
+             WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3",
+            params![provider, symbol, timeframe, now],
+        )?;
+
+        Ok(())
+    }
+
+    /// reset() sur tous les timeframes d'un symbole
+    ///
+    /// RETOUR: Nombre de timeframes réinitialisés
+    pub fn reset_all(conn: &Connection, provider: &str, symbol: &str) -> Result<usize> {
+        let now = Self::current_timestamp_ms()?;
+
+        let updated = conn.execute(
+            "UPDATE timeframe_status SET
+                 is_complete = 0,
+                 oldest_candle_time = NULL,
+                 last_updated = ?3
+             WHERE provider = ?1 AND symbol = ?2",
+            params![provider, symbol, now],
+        )?;
+
+        Ok(updated)
+    }
+
+    /// true si le timeframe a été marqué complet (false s'il est inconnu)
+    pub fn is_complete(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+    ) -> Result<bool> {
+        Ok(Self::get(conn, provider, symbol, timeframe)?.is_some_and(|p| p.is_complete))
+    }
+
+    /// Ligne complète de timeframe_status pour un timeframe
+    ///
+    /// RETOUR: None si le timeframe n'a jamais été récupéré
+    pub fn get(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+    ) -> Result<Option<TimeframeProgress>> {
+        let progress = conn
+            .query_row(
+                "SELECT oldest_candle_time, newest_candle_time, is_complete, last_updated
+                 FROM timeframe_status
+                 WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3",
+                params![provider, symbol, timeframe],
+                |row| {
+                    Ok(TimeframeProgress {
+                        oldest_candle_time: row.get(0)?,
+                        newest_candle_time: row.get(1)?,
+                        is_complete: row.get::<_, i64>(2)? != 0,
+                        last_updated: row.get(3)?,
+                    })
+                },
+            )
+            .optional()?;
+
+        Ok(progress)
+    }
+
+    /// Progression de tous les timeframes d'un symbole, du plus court au plus long
+    ///
+    /// Seuls les timeframes ayant une ligne de statut sont listés (vide si le
+    /// symbole n'a jamais été récupéré). Les décomptes viennent d'un seul
+    /// GROUP BY sur candlesticks, joint à timeframe_status
+    pub fn list(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+    ) -> Result<Vec<TimeframeStatusRow>> {
+        Self::list_rows(conn, Some((provider, symbol)))
+    }
+
+    /// Progression de toutes les séries de la base (export complet), triées
+    /// par provider, symbole puis timeframe du plus court au plus long
+    pub fn list_all(conn: &Connection) -> Result<Vec<TimeframeStatusRow>> {
+        Self::list_rows(conn, None)
+    }
+
+    /// Séries dont le backfill n'est pas terminé (is_complete = 0), tous
+    /// symboles confondus: (symbole, timeframe, oldest_candle_time)
+    ///
+    /// ORDRE (priorité de récupération):
+    /// 1. Séries jamais récupérées (oldest_candle_time NULL)
+    /// 2. Historique le plus court d'abord (oldest_candle_time le plus récent)
+    /// 3. Symbole, puis timeframe du plus court au plus long
+    pub fn list_incomplete(
+        conn: &Connection,
+        provider: &str,
+    ) -> Result<Vec<(String, String, Option<i64>)>> {
+        let mut stmt = conn.prepare(
+            "SELECT symbol, timeframe, oldest_candle_time FROM timeframe_status
+             WHERE provider = ?1 AND is_complete = 0",
+        )?;
+        let mut incomplete = stmt
+            .query_map([provider], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?
+            .collect::<std::result::Result<Vec<(String, String, Option<i64>)>, _>>()?;
+        incomplete.sort_by(|a, b| {
+            a.2.is_some()
+                .cmp(&b.2.is_some())
+                .then(b.2.cmp(&a.2))
+                .then_with(|| a.0.cmp(&b.0))
+                .then_with(|| compare_timeframes(&a.1, &b.1))
+        });
+
+        Ok(incomplete)
+    }
+
+    /// Lignes de statut d'un (provider, symbole), ou de toute la base (None)
+    ///
+    /// PERFORMANCE: filtre écrit dans la requête plutôt que `?1 IS NULL OR
+    /// provider = ?1`, qui empêcherait l'usage de l'index de candlesticks
+    fn list_rows(
+        conn: &Connection,
+        series: Option<(&str, &str)>,
+    ) -> Result<Vec<TimeframeStatusRow>> {
+        let (candle_filter, status_filter) = match series {
+            Some(_) => (
+                "WHERE provider = ?1 AND symbol = ?2",
+                "WHERE s.provider = ?1 AND s.symbol = ?2",
+            ),
+            None => ("", ""),
+        };
+        let mut stmt = conn.prepare(&format!(
+            "SELECT s.provider, s.symbol, s.timeframe, s.oldest_candle_time,
+                    s.newest_candle_time, s.is_complete, s.last_updated,
+                    COALESCE(c.total_rows, 0), COALESCE(c.interpolated_rows, 0)
+             FROM timeframe_status s
+             LEFT JOIN (
+                 SELECT provider, symbol, timeframe, COUNT(*) AS total_rows,
+                        SUM(interpolated != 0) AS interpolated_rows
+                 FROM candlesticks
+                 {}
+                 GROUP BY provider, symbol, timeframe
+             ) c ON c.provider = s.provider AND c.symbol = s.symbol AND c.timeframe = s.timeframe
+             {}",
+            candle_filter, status_filter
+        ))?;
+        let query_params = match series {
+            Some((provider, symbol)) => vec![provider, symbol],
+            None => Vec::new(),
+        };
+
+        let mut rows = stmt
+            .query_map(params_from_iter(query_params), |row| {
+                Ok(TimeframeStatusRow {
+                    provider: row.get(0)?,
+                    symbol: row.get(1)?,
+                    timeframe: row.get(2)?,
+                    oldest_candle_time: row.get(3)?,
+                    newest_candle_time: row.get(4)?,
+                    is_complete: row.get::<_, i64>(5)? != 0,
+                    last_updated: row.get(6)?,
+                    total_rows: row.get(7)?,
+                    interpolated_rows: row.get(8)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        rows.sort_by(|a, b| {
+            (&a.provider, &a.symbol)
+                .cmp(&(&b.provider, &b.symbol))
+                .then_with(|| compare_timeframes(&a.timeframe, &b.timeframe))
+        });
+
+        Ok(rows)
+    }
+
+    /// Recale les bornes sur les bougies restantes, après une suppression
+    /// (purge de rétention, purge des bougies interpolées)
+    ///
+    /// - oldest / newest_candle_time: MIN / MAX des open_time restants
+    /// - timeframe vidé: bornes à NULL et is_complete à 0, comme reset()
+    ///   (un timeframe sans bougie ne peut pas être marqué complet)
+    ///
+    /// DESIGN: une purge partielle conserve is_complete, l'historique retiré
+    /// l'a été volontairement (rétention)
+    ///
+    /// NOTE: oldest_candle_time est le point de reprise du backfill; un backfill
+    /// ultérieur sans --start-date re-téléchargera l'historique purgé
+    pub fn refresh_bounds(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
     ) -> Result<()> {
         let now = Self::current_timestamp_ms()?;
 
         conn.execute(
-            "INSERT OR REPLACE INTO timeframe_status
-             (provider, symbol, timeframe, oldest_candle_time, last_updated)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![provider, symbol, timeframe, oldest_candle_time, now],
+            "UPDATE timeframe_status SET
+                 oldest_candle_time = (SELECT MIN(open_time) FROM candlesticks
+                                       WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3),
+                 newest_candle_time = (SELECT MAX(open_time) FROM candlesticks
+                                       WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3),
+                 is_complete = is_complete AND EXISTS (
+                     SELECT 1 FROM candlesticks
+                     WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3),
+                 last_updated = ?4
+             WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3",
+            params![provider, symbol, timeframe, now],
         )?;
 
         Ok(())
     }
 
+    /// true si la progression n'a pas été mise à jour depuis plus de `max_age`
+    ///
+    /// Un timeframe jamais récupéré (sans ligne de statut) est considéré
+    /// périmé: il passe en priorité dans un rattrapage
+    pub fn is_stale(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        max_age: Duration,
+    ) -> Result<bool> {
+        let now = Self::current_timestamp_ms()?;
+        Ok(Self::get(conn, provider, symbol, timeframe)?
+            .is_none_or(|p| now - p.last_updated > max_age.as_millis() as i64))
+    }
+
+    /// Rapport de couverture de [start_ms, end_ms] (bornes incluses)
+    ///
+    /// NOTE: les bougies interpolées comptent dans actual (elles comblent des
+    /// gaps), real_count permet d'isoler les données réelles
+    pub fn get_coverage(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        start_ms: i64,
+        end_ms: i64,
+    ) -> Result<CoverageReport> {
+        let (actual, interpolated_count): (i64, i64) = conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(interpolated != 0), 0) FROM candlesticks
+             WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+                   AND open_time >= ?4 AND open_time <= ?5",
+            params![provider, symbol, timeframe, start_ms, end_ms],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+
+        let expected = if end_ms < start_ms {
+            0
+        } else {
+            (end_ms - start_ms) / parse_timeframe(timeframe)? + 1
+        };
+        let percentage = if expected > 0 {
+            actual as f64 / expected as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        Ok(CoverageReport {
+            actual,
+            expected,
+            percentage,
+            interpolated_count,
+            real_count: actual - interpolated_count,
+        })
+    }
+
+    /// Couverture d'une série entière (voir Coverage)
+    ///
+    /// ALGORITHME: une requête; chaque bougie reçoit un numéro de créneau
+    /// (open_time / intervalle, ou année * 12 + mois pour 1M), LAG donne
+    /// l'écart au créneau précédent: un écart de n créneaux est un trou de
+    /// n - 1 bougies. expected = dernier créneau - premier + 1
+    ///
+    /// SUBTILITÉ: les créneaux rendent le calcul indépendant de l'alignement
+    /// des open_time et de la durée variable des mois (28 à 31 jours)
+    ///
+    /// ERREURS: ValidationError (timeframe inconnu)
+    pub fn coverage(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+    ) -> Result<Coverage> {
+        let slot = if timeframe == "1M" {
+            "CAST(strftime('%Y', open_time / 1000, 'unixepoch') AS INTEGER) * 12
+             + CAST(strftime('%m', open_time / 1000, 'unixepoch') AS INTEGER)"
+                .to_string()
+        } else {
+            format!("open_time / {}", parse_timeframe(timeframe)?)
+        };
+
+        conn.query_row(
+            &format!(
+                "SELECT COUNT(*), MIN(open_time), MAX(open_time),
+                        COALESCE(SUM(interpolated != 0), 0), MIN(slot), MAX(slot),
+                        COALESCE(SUM(CASE WHEN step > 1 THEN step - 1 ELSE 0 END), 0),
+                        COALESCE(SUM(step > 1), 0)
+                 FROM (
+                     SELECT open_time, interpolated, slot,
+                            slot - LAG(slot) OVER (ORDER BY open_time) AS step
+                     FROM (
+                         SELECT open_time, interpolated, {} AS slot FROM candlesticks
+                         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+                     )
+                 )",
+                slot
+            ),
+            params![provider, symbol, timeframe],
+            |row| {
+                let bounds: [Option<i64>; 4] = [row.get(1)?, row.get(2)?, row.get(4)?, row.get(5)?];
+                let [Some(first), Some(last), Some(first_slot), Some(last_slot)] = bounds else {
+                    return Ok(Coverage::Empty);
+                };
+                let stored: i64 = row.get(0)?;
+                let interpolated: i64 = row.get(3)?;
+                let expected = last_slot - first_slot + 1;
+                Ok(Coverage::Series {
+                    first,
+                    last,
+                    stored,
+                    expected,
+                    missing: row.get(6)?,
+                    gaps: row.get(7)?,
+                    interpolated,
+                    pct_complete: (stored - interpolated) as f64 * 100.0 / expected as f64,
+                })
+            },
+        )
+        .map_err(Into::into)
+    }
+
+    /// Pourcentage de bougies présentes sur [start_ms, end_ms] (voir get_coverage)
+    pub fn get_coverage_percentage(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        start_ms: i64,
+        end_ms: i64,
+    ) -> Result<f64> {
+        Ok(Self::get_coverage(conn, provider, symbol, timeframe, start_ms, end_ms)?.percentage)
+    }
+
     /// Récupère le timestamp actuel en millisecondes
     fn current_timestamp_ms() -> Result<i64> {
         let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as i64;
@@ -59,4 +560,41 @@ impl TimeframeStatus {
         )
         .unwrap_or(None)
     }
+
+    /// Récupère le timestamp de la bougie la plus récente enregistrée par le backfill
+    ///
+    /// Fraîcheur des données sans MAX(open_time) sur candlesticks.
+    /// None si le timeframe n'a jamais été récupéré
+    pub fn get_newest_candle_time(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+    ) -> Option<i64> {
+        conn.query_row(
+            "SELECT newest_candle_time FROM timeframe_status
+             WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3",
+            params![provider, symbol, timeframe],
+            |row| row.get(0),
+        )
+        .unwrap_or(None)
+    }
+
+    /// Bornes de couverture attendues pour la détection des gaps aux extrémités
+    ///
+    /// - début: oldest_candle_time enregistré (jusqu'où le backfill est allé)
+    /// - fin: open_time de la dernière bougie clôturée à `now_ms`
+    ///
+    /// RETOUR: None si le timeframe n'a jamais été récupéré
+    pub fn expected_bounds(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+        now_ms: i64,
+    ) -> Option<(i64, i64)> {
+        let start = Self::get_last_candle_time(conn, provider, symbol, timeframe)?;
+        let end = now_ms - parse_timeframe(timeframe).ok()?;
+        Some((start, end))
+    }
 }
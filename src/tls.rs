@@ -0,0 +1,36 @@
+/// Module de chargement de la configuration TLS (rustls)
+///
+/// Le serveur web écoute en HTTPS en plus de HTTP quand un certificat et
+/// une clé privée PEM sont fournis (TLS_CERT_PATH / TLS_KEY_PATH)
+use crate::error::{CandleError, Result};
+use rustls::ServerConfig;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use std::sync::Arc;
+
+/// Construit la configuration rustls depuis des fichiers PEM
+///
+/// - cert_path: chaîne de certificats (certificat serveur en premier)
+/// - key_path: clé privée PKCS#8, PKCS#1 (RSA) ou SEC1 (EC)
+///
+/// DESIGN: fournisseur cryptographique ring explicite, indépendant des
+/// features activées par les autres dépendances
+pub fn load_server_config(cert_path: &str, key_path: &str) -> Result<ServerConfig> {
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .and_then(|certs| certs.collect::<std::result::Result<Vec<_>, _>>())
+        .map_err(|e| CandleError::ParseError(format!("Certificat {}: {}", cert_path, e)))?;
+    if certs.is_empty() {
+        return Err(CandleError::ValidationError(format!(
+            "Aucun certificat dans {}",
+            cert_path
+        )));
+    }
+
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .map_err(|e| CandleError::ParseError(format!("Clé privée {}: {}", key_path, e)))?;
+
+    ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .and_then(|builder| builder.with_no_client_auth().with_single_cert(certs, key))
+        .map_err(|e| CandleError::ValidationError(format!("Configuration TLS: {}", e)))
+}
@@ -1,5 +1,70 @@
 /// Module utilitaire pour les fonctions partagées
-use chrono::{DateTime, Utc};
+use crate::error::{CandleError, Result};
+use crate::models::CandleRecord;
+use chrono::{DateTime, Datelike, Months, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use std::cmp::Ordering;
+
+/// Décalage des semaines Binance: l'epoch (1970-01-01) est un jeudi,
+/// les bougies 1w commencent le lundi (1970-01-05)
+const WEEK_OFFSET_MS: i64 = 4 * 86_400_000;
+
+/// Timeframes supportés, du plus court au plus long (validation, autocomplétion)
+pub const ALL_TIMEFRAMES: &[&str] = &[
+    "1m", "3m", "5m", "15m", "30m", "1h", "2h", "4h", "6h", "8h", "12h", "1d", "3d", "1w", "1M",
+];
+
+/// Convertit un timeframe en intervalle en millisecondes
+///
+/// NOTE: 1M vaut 30 jours (approximation, un mois n'a pas de durée fixe)
+///
+/// ERREURS: ValidationError pour un timeframe inconnu ("25m", "1hour"...),
+/// plutôt qu'un intervalle par défaut qui masquerait la faute de frappe
+pub fn parse_timeframe(s: &str) -> Result<i64> {
+    let interval = match s {
+        "1m" => 60_000,
+        "3m" => 180_000,
+        "5m" => 300_000,
+        "15m" => 900_000,
+        "30m" => 1_800_000,
+        "1h" => 3_600_000,
+        "2h" => 7_200_000,
+        "4h" => 14_400_000,
+        "6h" => 21_600_000,
+        "8h" => 28_800_000,
+        "12h" => 43_200_000,
+        "1d" => 86_400_000,
+        "3d" => 259_200_000,
+        "1w" => 604_800_000,
+        "1M" => 2_592_000_000,
+        _ => {
+            return Err(CandleError::ValidationError(format!(
+                "Timeframe inconnu: {} (attendu: {})",
+                s,
+                ALL_TIMEFRAMES.join(", ")
+            )));
+        }
+    };
+    Ok(interval)
+}
+
+/// Vrai si `s` fait partie de ALL_TIMEFRAMES
+pub fn is_valid_timeframe(s: &str) -> bool {
+    parse_timeframe(s).is_ok()
+}
+
+/// Liste canonique des timeframes, du plus court ("1m") au plus long ("1M")
+pub fn timeframe_ordering() -> Vec<&'static str> {
+    ALL_TIMEFRAMES.to_vec()
+}
+
+/// Compare deux timeframes par durée (5m < 1h < 1d), pour sort_by
+///
+/// Un timeframe inconnu est classé après tous les timeframes connus
+/// (entre inconnus: ordre alphabétique, pour un ordre total)
+pub fn compare_timeframes(a: &str, b: &str) -> Ordering {
+    let duration = |tf: &str| parse_timeframe(tf).unwrap_or(i64::MAX);
+    duration(a).cmp(&duration(b)).then_with(|| a.cmp(b))
+}
 
 /// Formate un timestamp en millisecondes en format lisible
 ///
@@ -12,3 +77,218 @@ pub fn format_timestamp_ms(timestamp_ms: i64) -> String {
         "Invalid timestamp".to_string()
     }
 }
+
+/// Formate un timestamp en millisecondes en ISO 8601 UTC
+///
+/// EXEMPLE:
+/// 1700000000000 → "2023-11-14T22:13:20Z"
+pub fn format_timestamp_iso(timestamp_ms: i64) -> String {
+    DateTime::<Utc>::from_timestamp_millis(timestamp_ms)
+        .map(|datetime_utc| datetime_utc.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+        .unwrap_or_default()
+}
+
+/// Formate un timestamp en ISO 8601 UTC avec les millisecondes
+///
+/// EXEMPLE: 1704067499999 → "2024-01-01T00:04:59.999Z" (close_time sans perte)
+pub fn format_timestamp_iso_ms(timestamp_ms: i64) -> String {
+    DateTime::<Utc>::from_timestamp_millis(timestamp_ms)
+        .map(|datetime_utc| datetime_utc.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string())
+        .unwrap_or_default()
+}
+
+/// Parse une date au format YYYY-MM-DD en timestamp millisecondes (minuit UTC)
+///
+/// EXEMPLE:
+/// "2024-01-01" → 1704067200000
+pub fn parse_date_ms(date: &str) -> Result<i64> {
+    let naive_date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|e| CandleError::ParseError(format!("Date invalide {}: {}", date, e)))?;
+    let naive_datetime = naive_date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    Ok(DateTime::<Utc>::from_naive_utc_and_offset(naive_datetime, Utc).timestamp_millis())
+}
+
+/// Parse "YYYY-MM-DD HH:MM" (ou "YYYY-MM-DD", minuit) en timestamp millisecondes UTC
+///
+/// EXEMPLE:
+/// "2023-03-24 12:00" → 1679659200000
+pub fn parse_datetime_ms(datetime: &str) -> Result<i64> {
+    match NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M") {
+        Ok(naive_datetime) => {
+            Ok(DateTime::<Utc>::from_naive_utc_and_offset(naive_datetime, Utc).timestamp_millis())
+        }
+        Err(_) => parse_date_ms(datetime),
+    }
+}
+
+/// Début de la période de `interval` ms contenant `open_time`
+///
+/// Périodes alignées sur l'epoch UTC (2h → 00:00, 02:00, 04:00...),
+/// sauf 1w aligné sur le lundi 00:00 UTC comme Binance
+pub fn period_start(open_time: i64, interval: i64) -> i64 {
+    let offset = if interval == 604_800_000 {
+        WEEK_OFFSET_MS
+    } else {
+        0
+    };
+    (open_time - offset).div_euclid(interval) * interval + offset
+}
+
+/// open_time de la bougie de `timeframe` qui contient `timestamp_ms`
+///
+/// Sert à recaler un début de plage fourni par l'utilisateur (?start=...)
+/// qui tombe au milieu d'une bougie: sans recalage, la première bougie
+/// (open_time < start) serait exclue
+///
+/// Alignement comme Binance: epoch UTC (period_start), lundi pour 1w,
+/// 1er du mois 00:00 UTC pour 1M (durée variable, pas de calcul par intervalle)
+///
+/// EXEMPLE: (1704067499999, "5m") → 1704067200000
+///
+/// ERREURS: ValidationError (timeframe inconnu), ParseError (timestamp hors
+/// de la plage représentable)
+pub fn round_to_timeframe(timestamp_ms: i64, timeframe: &str) -> Result<i64> {
+    if timeframe == "1M" {
+        return month_start(timestamp_ms, 0);
+    }
+    let interval = parse_timeframe(timeframe)?;
+    Ok(period_start(representable(timestamp_ms)?, interval))
+}
+
+/// open_time de la bougie suivant celle qui contient `open_time_ms`
+/// (borne de clôture attendue: close_time = next_candle_time - 1)
+///
+/// EXEMPLE: (1704067200000, "1h") → 1704070800000
+///
+/// ERREURS: comme round_to_timeframe
+pub fn next_candle_time(open_time_ms: i64, timeframe: &str) -> Result<i64> {
+    if timeframe == "1M" {
+        return month_start(open_time_ms, 1);
+    }
+    let interval = parse_timeframe(timeframe)?;
+    Ok(period_start(representable(open_time_ms)?, interval) + interval)
+}
+
+/// `timestamp_ms` s'il est dans la plage de chrono (environ ±262 000 ans)
+///
+/// SUBTILITÉ: period_start (décalage 1w) et l'ajout d'un intervalle
+/// débordent près de i64::MIN / i64::MAX; la plage de chrono en est très
+/// loin, comme pour 1M (month_start)
+fn representable(timestamp_ms: i64) -> Result<i64> {
+    DateTime::<Utc>::from_timestamp_millis(timestamp_ms)
+        .map(|_| timestamp_ms)
+        .ok_or_else(|| CandleError::ParseError(format!("Timestamp invalide: {}", timestamp_ms)))
+}
+
+/// Début (00:00 UTC le 1er) du mois contenant `timestamp_ms`, décalé de
+/// `months_after` mois
+fn month_start(timestamp_ms: i64, months_after: u32) -> Result<i64> {
+    DateTime::from_timestamp_millis(timestamp_ms)
+        .and_then(|datetime_utc| datetime_utc.date_naive().with_day(1))
+        .and_then(|first_day| first_day.checked_add_months(Months::new(months_after)))
+        .map(|month| month.and_time(NaiveTime::MIN).and_utc().timestamp_millis())
+        .ok_or_else(|| CandleError::ParseError(format!("Timestamp invalide: {}", timestamp_ms)))
+}
+
+/// Agrège des bougies consécutives (triées) en une bougie du timeframe supérieur
+///
+/// ALGORITHME: premier open, max high, min low, dernier close,
+/// volumes et nombre de trades sommés
+///
+/// La bougie produite est synthétique si l'une des sources l'est
+/// (interpolated = max des sources), derived_from = timeframe source
+pub fn aggregate_candles(
+    candles: &[&CandleRecord],
+    target_tf: &str,
+    open_time: i64,
+    interval: i64,
+) -> CandleRecord {
+    CandleRecord {
+        provider: candles[0].provider.clone(),
+        symbol: candles[0].symbol.clone(),
+        timeframe: target_tf.to_string(),
+        open_time,
+        open: candles[0].open,
+        high: candles
+            .iter()
+            .map(|c| c.high)
+            .fold(f64::NEG_INFINITY, f64::max),
+        low: candles.iter().map(|c| c.low).fold(f64::INFINITY, f64::min),
+        close: candles[candles.len() - 1].close,
+        volume: candles.iter().map(|c| c.volume).sum(),
+        close_time: open_time + interval - 1,
+        quote_asset_volume: candles.iter().map(|c| c.quote_asset_volume).sum(),
+        number_of_trades: candles.iter().map(|c| c.number_of_trades).sum(),
+        taker_buy_base_asset_volume: candles.iter().map(|c| c.taker_buy_base_asset_volume).sum(),
+        taker_buy_quote_asset_volume: candles.iter().map(|c| c.taker_buy_quote_asset_volume).sum(),
+        interpolated: candles.iter().map(|c| c.interpolated).max().unwrap_or(0),
+        derived_from: Some(candles[0].timeframe.clone()),
+    }
+}
+
+/// Rééchantillonne des bougies (triées par open_time) vers un timeframe supérieur
+///
+/// VALIDATION: target_tf doit être un multiple entier strict de source_tf
+/// (5m → 2h ok, 5m → 5m ou 2h → 3h refusés). 1M est refusé: un mois n'a pas
+/// de durée fixe
+///
+/// ARRONDI: chaque bougie tombe dans la période UTC qui la contient
+/// (voir period_start): une bougie 2h commence à 00:00, 02:00, 04:00... UTC,
+/// jamais à l'heure de la première bougie source. Une période partiellement
+/// couverte (début/fin de plage, trou) est agrégée avec les bougies présentes
+///
+/// ERREURS: ValidationError (timeframe inconnu ou non multiple)
+pub fn resample_candles(
+    source: &[CandleRecord],
+    source_tf: &str,
+    target_tf: &str,
+) -> Result<Vec<CandleRecord>> {
+    let timeframe_interval = |tf: &str| {
+        if tf == "1M" {
+            return Err(CandleError::ValidationError(format!(
+                "Timeframe non supporté: {}",
+                tf
+            )));
+        }
+        parse_timeframe(tf)
+    };
+    let source_interval = timeframe_interval(source_tf)?;
+    let target_interval = timeframe_interval(target_tf)?;
+
+    if target_interval <= source_interval || target_interval % source_interval != 0 {
+        return Err(CandleError::ValidationError(format!(
+            "{} n'est pas un multiple entier de {}",
+            target_tf, source_tf
+        )));
+    }
+
+    let mut resampled = Vec::new();
+    let mut group: Vec<&CandleRecord> = Vec::new();
+    let mut group_start = 0;
+
+    for candle in source {
+        let start = period_start(candle.open_time, target_interval);
+        if !group.is_empty() && start != group_start {
+            resampled.push(aggregate_candles(
+                &group,
+                target_tf,
+                group_start,
+                target_interval,
+            ));
+            group.clear();
+        }
+        group_start = start;
+        group.push(candle);
+    }
+
+    if !group.is_empty() {
+        resampled.push(aggregate_candles(
+            &group,
+            target_tf,
+            group_start,
+            target_interval,
+        ));
+    }
+
+    Ok(resampled)
+}
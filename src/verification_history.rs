@@ -0,0 +1,186 @@
+/// Module de l'historique des vérifications (table verification_runs)
+///
+/// Une ligne par vérification d'une série (verify_data --record): gaps,
+/// overlaps, valeurs anormales, bougies interpolées et total au moment du
+/// passage. Sert à suivre l'évolution d'une série d'une nuit à l'autre
+/// (le nombre de gaps a-t-il baissé après la dernière réparation ?)
+///
+/// - VerificationHistory::record: enregistre un VerificationReport
+/// - VerificationHistory::recent / latest: derniers passages d'une série
+/// - VerificationHistory::prune: supprime les passages trop anciens
+use crate::error::Result;
+use crate::verify::VerificationReport;
+use rusqlite::{Connection, OptionalExtension, Row, params};
+use serde::Serialize;
+
+/// Durée de conservation par défaut des passages (verify_data --keep-runs-days)
+pub const DEFAULT_RUN_RETENTION_DAYS: i64 = 90;
+
+/// Passage de vérification d'une série
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VerificationRun {
+    pub id: i64,
+    pub provider: String,
+    pub symbol: String,
+    pub timeframe: String,
+    /// Date du passage (ms)
+    pub run_at: i64,
+    /// Gaps non couverts par une maintenance connue
+    pub gaps: i64,
+    pub overlaps: i64,
+    /// Valeurs anormales sur des bougies réelles (0 si non vérifiées)
+    pub value_anomalies: i64,
+    pub interpolated_count: i64,
+    pub total: i64,
+}
+
+impl VerificationRun {
+    /// Vrai sans gap, sans overlap et sans valeur anormale (comme
+    /// VerificationReport::passed)
+    pub fn passed(&self) -> bool {
+        self.gaps == 0 && self.overlaps == 0 && self.value_anomalies == 0
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(VerificationRun {
+            id: row.get(0)?,
+            provider: row.get(1)?,
+            symbol: row.get(2)?,
+            timeframe: row.get(3)?,
+            run_at: row.get(4)?,
+            gaps: row.get(5)?,
+            overlaps: row.get(6)?,
+            value_anomalies: row.get(7)?,
+            interpolated_count: row.get(8)?,
+            total: row.get(9)?,
+        })
+    }
+}
+
+const RUN_COLUMNS: &str = "id, provider, symbol, timeframe, run_at, gaps, overlaps,
+    value_anomalies, interpolated_count, total";
+
+/// Gestionnaire de la table verification_runs
+pub struct VerificationHistory;
+
+impl VerificationHistory {
+    /// Enregistre le résultat d'une vérification (verify::verify_spacing,
+    /// values renseigné si verify_values a été lancé)
+    ///
+    /// SUBTILITÉ: VerificationReport ne compte pas les bougies interpolées,
+    /// interpolated_count est lu dans candlesticks au moment de l'écriture
+    ///
+    /// RETOUR: id du passage
+    pub fn record(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        report: &VerificationReport,
+        run_at: i64,
+    ) -> Result<i64> {
+        let interpolated_count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM candlesticks
+             WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3 AND interpolated = 1",
+            params![provider, symbol, report.timeframe],
+            |row| row.get(0),
+        )?;
+        let value_anomalies = report
+            .values
+            .as_ref()
+            .map_or(0, |values| values.real.total());
+
+        conn.execute(
+            "INSERT INTO verification_runs (provider, symbol, timeframe, run_at, gaps,
+                 overlaps, value_anomalies, interpolated_count, total)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                provider,
+                symbol,
+                report.timeframe,
+                run_at,
+                report.gaps.len() as i64,
+                report.overlaps.len() as i64,
+                value_anomalies,
+                interpolated_count,
+                report.total_count
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Derniers passages d'une série, du plus récent au plus ancien
+    /// (tous providers confondus)
+    ///
+    /// Base sans historique (table absente, fichier en lecture seule d'une
+    /// ancienne version): liste vide
+    pub fn recent(
+        conn: &Connection,
+        symbol: &str,
+        timeframe: &str,
+        limit: usize,
+    ) -> Result<Vec<VerificationRun>> {
+        if !Self::table_exists(conn)? {
+            return Ok(Vec::new());
+        }
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM verification_runs
+             WHERE symbol = ?1 AND timeframe = ?2
+             ORDER BY run_at DESC, id DESC
+             LIMIT ?3",
+            RUN_COLUMNS
+        ))?;
+        let runs = stmt
+            .query_map(
+                params![symbol, timeframe, limit as i64],
+                VerificationRun::from_row,
+            )?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(runs)
+    }
+
+    /// Dernier passage d'une série (None: jamais vérifiée ou table absente)
+    pub fn latest(
+        conn: &Connection,
+        provider: &str,
+        symbol: &str,
+        timeframe: &str,
+    ) -> Result<Option<VerificationRun>> {
+        if !Self::table_exists(conn)? {
+            return Ok(None);
+        }
+        let run = conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM verification_runs
+                     WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+                     ORDER BY run_at DESC, id DESC
+                     LIMIT 1",
+                    RUN_COLUMNS
+                ),
+                params![provider, symbol, timeframe],
+                VerificationRun::from_row,
+            )
+            .optional()?;
+        Ok(run)
+    }
+
+    /// Supprime les passages antérieurs à `before_ms` (toutes séries)
+    ///
+    /// RETOUR: nombre de passages supprimés
+    pub fn prune(conn: &Connection, before_ms: i64) -> Result<usize> {
+        let deleted = conn.execute(
+            "DELETE FROM verification_runs WHERE run_at < ?1",
+            [before_ms],
+        )?;
+        Ok(deleted)
+    }
+
+    fn table_exists(conn: &Connection) -> Result<bool> {
+        let exists = conn.query_row(
+            "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'verification_runs'",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(exists)
+    }
+}
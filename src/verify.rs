@@ -5,12 +5,657 @@
 // Ce module vérifie que les données stockées sont continues et correctement espacées
 // Il détecte:
 // - Les GAPS (trous): intervalles trop grands entre les bougies
+//   (gaps "attendus" si couverts par une maintenance de la table known_downtime)
 // - Les OVERLAPS (chevauchements): intervalles trop petits ou négatifs
 // - Les statistiques globales: nombre total, plage temporelle, etc.
+// - L'audit d'une base entière (audit_database): intégrité SQLite, unicité,
+//   close_time, cohérence de timeframe_status
+// - La réparation des overlaps (repair_overlaps): une ligne par période
+// - La cohérence entre timeframes (verify_cross_timeframe): bougies
+//   stockées comparées à l'agrégation du timeframe inférieur
+// - Les valeurs impossibles et les séries de bougies identiques
+//   (verify_values), bougies réelles et interpolées comptées à part
+// - La vérification de toutes les séries d'un répertoire de bases
+//   (verify_directory), fichiers répartis sur un nombre borné de threads
+// - Les prix aberrants (detect_price_outliers): close éloigné de la
+//   moyenne glissante de plus de z_threshold écarts-types
+// - La comparaison d'une même série entre providers (compare_providers):
+//   bougies par provider, écart de close, plus forte divergence
+// - La part de bougies interpolées et leur plus longue série continue
+//   (verify_interpolation)
+// - Le contrôle par sondage (spot_check): bougies réelles tirées au hasard
+//   et comparées aux klines renvoyées à nouveau par l'API
 
-use anyhow::Result;
+use crate::database::{query_candle_records, read_only_uri};
+use crate::kline_provider::KlineProvider;
+use crate::models::CandleRecord;
+use crate::rate_limiter::RateLimiter;
+use crate::retriever::{kline_to_record, wait_for_klines_budget};
+use crate::timeframe_status::{CoverageReport, TimeframeStatus};
+use crate::utils::{
+    compare_timeframes, format_timestamp_iso, next_candle_time, parse_timeframe, period_start,
+    resample_candles, round_to_timeframe,
+};
+use anyhow::{Result, bail};
 use chrono::{DateTime, Utc};
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OpenFlags, params};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Nombre maximal de bougies fautives détaillées dans un AuditReport
+pub const AUDIT_SAMPLE_LIMIT: usize = 20;
+
+/// Longueur par défaut d'une série de bougies identiques signalée
+/// (forward-fill défaillant, flux figé)
+pub const DEFAULT_FLATLINE_RUN: usize = 10;
+
+/// Nombre de bougies précédentes de la moyenne glissante de
+/// detect_price_outliers
+pub const OUTLIER_WINDOW: usize = 20;
+
+/// Écart relatif toléré par défaut entre une bougie stockée et l'agrégation
+/// du timeframe inférieur (arrondis des sommes de volumes)
+pub const CROSS_CHECK_TOLERANCE: f64 = 1e-6;
+
+/// Écart relatif de close toléré par défaut entre deux providers d'une
+/// même série (prix d'exchanges différents, jamais identiques)
+pub const PROVIDER_TOLERANCE: f64 = 0.001;
+
+/// Bougie dont close_time ne correspond pas à son open_time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CloseTimeMismatch {
+    pub provider: String,
+    pub symbol: String,
+    pub timeframe: String,
+    pub open_time: i64,
+    pub close_time: i64,
+    pub expected_close_time: i64,
+}
+
+/// Ligne de timeframe_status dont les bornes diffèrent des bougies stockées
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StatusMismatch {
+    pub provider: String,
+    pub symbol: String,
+    pub timeframe: String,
+    pub status_oldest: Option<i64>,
+    pub status_newest: Option<i64>,
+    /// MIN(open_time) / MAX(open_time) de la série (None: aucune bougie)
+    pub actual_oldest: Option<i64>,
+    pub actual_newest: Option<i64>,
+}
+
+/// Rapport de vérification de plusieurs timeframes d'une paire
+/// (verify_data --format json)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerificationSummary {
+    pub provider: String,
+    pub symbol: String,
+    pub db_file: String,
+    pub timeframes: Vec<VerificationReport>,
+    /// Comparaisons entre timeframes (verify_data --cross-check)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub cross_checks: Vec<CrossCheckReport>,
+}
+
+/// Vérification de chaque série d'un fichier .db (voir verify_directory)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileVerification {
+    pub file: String,
+    /// Erreur empêchant la vérification (fichier illisible, table absente...)
+    pub error: Option<String>,
+    pub series: Vec<SeriesVerification>,
+}
+
+impl FileVerification {
+    /// Vrai si le fichier a été lu et que chaque série passe (voir
+    /// VerificationReport::passed)
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.series.iter().all(|series| series.report.passed())
+    }
+}
+
+/// Espacement et valeurs d'une série (provider, symbole, timeframe)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeriesVerification {
+    pub provider: String,
+    pub symbol: String,
+    pub report: VerificationReport,
+}
+
+/// Espacement des bougies d'un timeframe (voir verify_spacing)
+///
+/// Chaque timestamp est donné en millisecondes (`*_time`) et en
+/// ISO 8601 UTC (`*_date`), comme l'API du serveur web
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerificationReport {
+    pub timeframe: String,
+    pub expected_interval_ms: i64,
+    pub total_count: i64,
+    /// (dernière - première) / intervalle + 1, 0 sans bougie
+    pub expected_count: i64,
+    pub first_open_time: Option<i64>,
+    pub first_open_date: Option<String>,
+    pub last_open_time: Option<i64>,
+    pub last_open_date: Option<String>,
+    /// Gaps non couverts par une maintenance connue
+    pub gaps: Vec<SpacingGap>,
+    /// Gaps couverts par une maintenance de la table known_downtime
+    pub expected_gaps: Vec<SpacingGap>,
+    pub overlaps: Vec<SpacingOverlap>,
+    /// Rapport de couverture (verify_data --coverage)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<CoverageReport>,
+    /// Réparation des overlaps après vérification (verify_data --fix)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repair: Option<RepairReport>,
+    /// Valeurs impossibles et bougies identiques (voir verify_values)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<ValueAnomalies>,
+    /// Champs différents de l'API sur les bougies tirées au hasard
+    /// (verify_data --spot-check, voir spot_check)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spot_check: Option<Vec<Divergence>>,
+    /// Part de bougies interpolées (voir verify_interpolation)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interpolation: Option<InterpolationReport>,
+}
+
+impl VerificationReport {
+    /// Vrai sans gap inattendu, sans overlap et sans valeur anormale sur
+    /// une bougie réelle (valeurs non vérifiées: ignorées)
+    pub fn passed(&self) -> bool {
+        self.gaps.is_empty()
+            && self.overlaps.is_empty()
+            && self
+                .values
+                .as_ref()
+                .is_none_or(|values| values.real.total() == 0)
+    }
+}
+
+/// Intervalle trop grand après la bougie open_time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SpacingGap {
+    pub open_time: i64,
+    pub open_date: String,
+    pub interval_ms: i64,
+    pub missing_candles: i64,
+    /// Note de la maintenance connue couvrant le gap
+    pub note: Option<String>,
+}
+
+/// Intervalle trop petit après la bougie open_time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SpacingOverlap {
+    pub open_time: i64,
+    pub open_date: String,
+    pub interval_ms: i64,
+}
+
+/// Résultat de repair_overlaps pour un timeframe
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RepairReport {
+    pub timeframe: String,
+    /// true: lignes listées mais pas supprimées
+    pub dry_run: bool,
+    /// Périodes couvertes par plusieurs lignes
+    pub overlapping_periods: usize,
+    pub removed: Vec<RemovedRow>,
+}
+
+/// Ligne supprimée (ou à supprimer en dry-run) par repair_overlaps
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RemovedRow {
+    pub open_time: i64,
+    pub open_date: String,
+    pub close_time: i64,
+    pub interpolated: bool,
+    /// open_time de la ligne conservée pour la même période
+    pub kept_open_time: i64,
+}
+
+/// Bougie dont le close s'écarte anormalement de la moyenne glissante
+/// (voir detect_price_outliers)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct OutlierCandle {
+    pub open_time: i64,
+    pub close: f64,
+    pub rolling_mean: f64,
+    /// (close - moyenne) / écart-type, signé: négatif pour une chute
+    pub z_score: f64,
+}
+
+/// Type d'anomalie de valeurs (voir verify_values)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyKind {
+    HighBelowLow,
+    /// high < open ou high < close
+    HighBelowBody,
+    /// low > open ou low > close
+    LowAboveBody,
+    NegativeVolume,
+    /// open, high, low ou close exactement 0
+    ZeroPrice,
+    /// Série de bougies identiques (open, high, low, close, volume)
+    Flatline,
+}
+
+/// Nombre d'anomalies par type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct AnomalyCounts {
+    pub high_below_low: i64,
+    pub high_below_body: i64,
+    pub low_above_body: i64,
+    pub negative_volume: i64,
+    pub zero_price: i64,
+    /// Séries d'au moins flatline_run bougies identiques
+    pub flatline_runs: i64,
+    /// Bougies de ces séries
+    pub flatline_candles: i64,
+}
+
+impl AnomalyCounts {
+    /// Nombre d'anomalies (une série de bougies identiques compte pour une)
+    pub fn total(&self) -> i64 {
+        self.high_below_low
+            + self.high_below_body
+            + self.low_above_body
+            + self.negative_volume
+            + self.zero_price
+            + self.flatline_runs
+    }
+
+    fn add(&mut self, kind: AnomalyKind) {
+        match kind {
+            AnomalyKind::HighBelowLow => self.high_below_low += 1,
+            AnomalyKind::HighBelowBody => self.high_below_body += 1,
+            AnomalyKind::LowAboveBody => self.low_above_body += 1,
+            AnomalyKind::NegativeVolume => self.negative_volume += 1,
+            AnomalyKind::ZeroPrice => self.zero_price += 1,
+            AnomalyKind::Flatline => self.flatline_runs += 1,
+        }
+    }
+}
+
+/// Bougie anormale (Flatline: première bougie de la série)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValueAnomaly {
+    pub open_time: i64,
+    pub kind: AnomalyKind,
+    pub interpolated: bool,
+    /// Flatline: nombre de bougies identiques
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_length: Option<i64>,
+}
+
+/// Part de bougies interpolées d'une série (voir verify_interpolation)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InterpolationReport {
+    pub provider: String,
+    pub symbol: String,
+    pub timeframe: String,
+    pub total: i64,
+    pub interpolated: i64,
+    /// interpolated / total × 100 (0 sans bougie)
+    pub percentage: f64,
+    /// Plus longue série de bougies interpolées consécutives (None: aucune)
+    pub longest_run: Option<InterpolatedRun>,
+}
+
+impl InterpolationReport {
+    /// Vrai si la part interpolée dépasse `max_percentage`
+    pub fn exceeds(&self, max_percentage: f64) -> bool {
+        self.percentage > max_percentage
+    }
+}
+
+/// Série de bougies interpolées consécutives (dans l'ordre des open_time,
+/// sans bougie réelle entre elles)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct InterpolatedRun {
+    pub first_open_time: i64,
+    pub last_open_time: i64,
+    pub length: i64,
+}
+
+/// Valeurs anormales d'une série (voir verify_values)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValueAnomalies {
+    pub provider: String,
+    pub symbol: String,
+    pub timeframe: String,
+    /// Longueur minimale d'une série de bougies identiques signalée
+    pub flatline_run: usize,
+    pub checked_candles: i64,
+    /// Anomalies des bougies réelles
+    pub real: AnomalyCounts,
+    /// Anomalies des bougies interpolées (une série identique ne compte ici
+    /// que si toutes ses bougies sont interpolées)
+    pub interpolated: AnomalyCounts,
+    /// Au plus AUDIT_SAMPLE_LIMIT exemples, dans l'ordre des bougies
+    pub samples: Vec<ValueAnomaly>,
+}
+
+impl ValueAnomalies {
+    /// Vrai si une anomalie a été trouvée (réelle ou interpolée)
+    pub fn has_anomalies(&self) -> bool {
+        self.real.total() + self.interpolated.total() > 0
+    }
+
+    /// Compte une anomalie (réelle ou interpolée) et garde un exemple
+    fn record(&mut self, anomaly: ValueAnomaly) {
+        let counts = if anomaly.interpolated {
+            &mut self.interpolated
+        } else {
+            &mut self.real
+        };
+        counts.add(anomaly.kind);
+        counts.flatline_candles += anomaly.run_length.unwrap_or(0);
+        if self.samples.len() < AUDIT_SAMPLE_LIMIT {
+            self.samples.push(anomaly);
+        }
+    }
+}
+
+/// Comparaison d'un timeframe à l'agrégation d'un timeframe inférieur
+/// (voir verify_cross_timeframe)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CrossCheckReport {
+    pub lower_timeframe: String,
+    pub higher_timeframe: String,
+    pub tolerance: f64,
+    pub mismatches: Vec<CrossTimeframeMismatch>,
+}
+
+/// Valeur d'une bougie stockée qui diffère de l'agrégation du timeframe
+/// inférieur au-delà de la tolérance
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CrossTimeframeMismatch {
+    pub provider: String,
+    pub symbol: String,
+    /// open_time de la bougie du timeframe supérieur
+    pub open_time: i64,
+    /// open, high, low, close ou volume
+    pub field: String,
+    /// Valeur agrégée depuis le timeframe inférieur
+    pub resampled: f64,
+    /// Valeur de la bougie stockée
+    pub stored: f64,
+}
+
+/// Champ d'une bougie stockée qui diffère de la kline renvoyée à nouveau
+/// par l'API (voir spot_check)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Divergence {
+    pub provider: String,
+    pub symbol: String,
+    pub timeframe: String,
+    pub open_time: i64,
+    /// Nom de colonne de candlesticks (open, close_time, number_of_trades...)
+    pub field: String,
+    /// Valeur de la bougie stockée
+    pub stored: f64,
+    /// Valeur de la kline renvoyée par l'API
+    pub fresh: f64,
+}
+
+/// Comparaison d'une série (symbole, timeframe) entre providers (voir
+/// compare_providers)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProviderComparison {
+    pub symbol: String,
+    pub timeframe: String,
+    pub tolerance: f64,
+    /// Bougies de chaque provider, par nom
+    pub providers: Vec<ProviderCount>,
+    /// Chaque paire de providers, dans l'ordre de `providers`
+    pub pairs: Vec<ProviderPair>,
+}
+
+impl ProviderComparison {
+    /// Vrai si une paire a des closes qui divergent au-delà de la tolérance
+    pub fn has_divergences(&self) -> bool {
+        self.pairs.iter().any(|pair| pair.divergent_candles > 0)
+    }
+}
+
+/// Nombre de bougies d'un provider (réelles et interpolées)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProviderCount {
+    pub provider: String,
+    pub count: i64,
+}
+
+/// Comparaison des closes de deux providers sur leurs open_time communs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProviderPair {
+    pub reference: String,
+    pub other: String,
+    /// open_time réels chez les deux providers
+    pub common_candles: i64,
+    /// Moyenne de |close de référence - close de l'autre| (0 sans bougie commune)
+    pub mean_abs_close_diff: f64,
+    /// Bougies dont l'écart relatif dépasse la tolérance
+    pub divergent_candles: i64,
+    /// Bougie de plus fort écart relatif (None sans bougie commune)
+    pub max_divergence: Option<ProviderDivergence>,
+}
+
+/// Closes d'une même bougie chez deux providers
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ProviderDivergence {
+    pub open_time: i64,
+    pub reference_close: f64,
+    pub other_close: f64,
+    /// |écart| / max(|close|)
+    pub relative_diff: f64,
+}
+
+/// Résultat de l'audit d'une base (voir audit_database)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AuditReport {
+    /// Messages de PRAGMA integrity_check (vide si "ok")
+    pub integrity_errors: Vec<String>,
+    /// Clés (provider, symbol, timeframe, open_time) présentes plusieurs fois
+    pub duplicate_keys: i64,
+    pub total_rows: i64,
+    pub interpolated_rows: i64,
+    /// Timeframes stockés inconnus: leur close_time n'est pas vérifiable
+    pub unknown_timeframes: Vec<String>,
+    pub close_time_mismatches: i64,
+    /// Au plus AUDIT_SAMPLE_LIMIT exemples de close_time_mismatches
+    pub close_time_samples: Vec<CloseTimeMismatch>,
+    pub status_mismatches: Vec<StatusMismatch>,
+    /// Séries ayant des valeurs anormales (voir verify_values)
+    pub value_anomalies: Vec<ValueAnomalies>,
+    /// Comparaisons entre timeframes (audit --cross-check, vide sinon)
+    pub cross_checks: Vec<CrossCheckReport>,
+    /// Séries stockées sous plusieurs providers (voir audit_providers)
+    pub provider_comparisons: Vec<ProviderComparison>,
+}
+
+impl AuditReport {
+    /// Vrai si aucune vérification n'a échoué (les bougies interpolées ne
+    /// sont pas une anomalie, elles sont seulement comptées; leurs valeurs
+    /// anormales sont rapportées sans faire échouer l'audit)
+    pub fn is_healthy(&self) -> bool {
+        self.integrity_errors.is_empty()
+            && self.duplicate_keys == 0
+            && self.unknown_timeframes.is_empty()
+            && self.close_time_mismatches == 0
+            && self.status_mismatches.is_empty()
+            && self
+                .value_anomalies
+                .iter()
+                .all(|anomalies| anomalies.real.total() == 0)
+            && self
+                .cross_checks
+                .iter()
+                .all(|check| check.mismatches.is_empty())
+            && self
+                .provider_comparisons
+                .iter()
+                .all(|comparison| !comparison.has_divergences())
+    }
+}
+
+/// Audit complet d'une base: structure SQLite puis règles métier
+///
+/// VÉRIFICATIONS:
+/// 1. PRAGMA integrity_check (pages, index, contraintes NOT NULL)
+/// 2. Unicité de (provider, symbol, timeframe, open_time), recalculée par
+///    un parcours de la table (NOT INDEXED): un index corrompu ne peut pas
+///    masquer ses propres doublons
+/// 3. close_time = open_time + intervalle - 1 (1M: veille du mois suivant)
+/// 4. Nombre de bougies interpolées
+/// 5. Bornes de timeframe_status = MIN/MAX(open_time) de chaque série
+///    (bornes NULL, ex: après un reset, non comparées)
+/// 6. Valeurs impossibles et séries d'au moins `flatline_run` bougies
+///    identiques (verify_values), séries sans anomalie omises
+///
+/// NOTE: lecture seule, la base n'est jamais modifiée
+pub fn audit_database(conn: &Connection, flatline_run: usize) -> Result<AuditReport> {
+    let mut report = AuditReport {
+        integrity_errors: integrity_errors(conn)?,
+        ..AuditReport::default()
+    };
+
+    report.duplicate_keys = conn.query_row(
+        "SELECT COUNT(*) FROM (
+             SELECT 1 FROM candlesticks NOT INDEXED
+             GROUP BY provider, symbol, timeframe, open_time
+             HAVING COUNT(*) > 1
+         )",
+        [],
+        |row| row.get(0),
+    )?;
+    (report.total_rows, report.interpolated_rows) = conn.query_row(
+        "SELECT COUNT(*), COALESCE(SUM(interpolated > 0), 0) FROM candlesticks",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+
+    let timeframes: Vec<String> = conn
+        .prepare("SELECT DISTINCT timeframe FROM candlesticks ORDER BY timeframe")?
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+    for timeframe in timeframes {
+        if parse_timeframe(&timeframe).is_err() {
+            report.unknown_timeframes.push(timeframe);
+            continue;
+        }
+        audit_close_times(conn, &timeframe, &mut report)?;
+    }
+
+    report.status_mismatches = status_mismatches(conn)?;
+
+    let series: Vec<(String, String, String)> = conn
+        .prepare(
+            "SELECT DISTINCT provider, symbol, timeframe FROM candlesticks
+             ORDER BY provider, symbol, timeframe",
+        )?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    for (provider, symbol, timeframe) in series {
+        let anomalies = verify_values(conn, &provider, &symbol, &timeframe, flatline_run)?;
+        if anomalies.has_anomalies() {
+            report.value_anomalies.push(anomalies);
+        }
+    }
+    Ok(report)
+}
+
+/// Lignes de PRAGMA integrity_check, sauf le "ok" d'une base saine
+fn integrity_errors(conn: &Connection) -> Result<Vec<String>> {
+    let messages: Vec<String> = conn
+        .prepare("PRAGMA integrity_check")?
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(messages.into_iter().filter(|m| m != "ok").collect())
+}
+
+/// Compte les close_time incohérents d'un timeframe et garde des exemples
+///
+/// SUBTILITÉ: le filtre est fait en SQL pour les timeframes à durée fixe;
+/// 1M (durée variable) est vérifié ligne à ligne avec next_candle_time
+fn audit_close_times(conn: &Connection, timeframe: &str, report: &mut AuditReport) -> Result<()> {
+    let interval = parse_timeframe(timeframe)?;
+    let mut stmt = conn.prepare(
+        "SELECT provider, symbol, open_time, close_time FROM candlesticks
+         WHERE timeframe = ?1 AND (?2 = 1 OR close_time != open_time + ?3 - 1)
+         ORDER BY provider, symbol, open_time",
+    )?;
+    let mut rows = stmt.query(params![timeframe, timeframe == "1M", interval])?;
+
+    while let Some(row) = rows.next()? {
+        let (open_time, close_time): (i64, i64) = (row.get(2)?, row.get(3)?);
+        let expected_close_time = if timeframe == "1M" {
+            next_candle_time(open_time, timeframe)? - 1
+        } else {
+            open_time + interval - 1
+        };
+        if close_time == expected_close_time {
+            continue;
+        }
+        report.close_time_mismatches += 1;
+        if report.close_time_samples.len() < AUDIT_SAMPLE_LIMIT {
+            report.close_time_samples.push(CloseTimeMismatch {
+                provider: row.get(0)?,
+                symbol: row.get(1)?,
+                timeframe: timeframe.to_string(),
+                open_time,
+                close_time,
+                expected_close_time,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Séries dont timeframe_status ne correspond pas aux bougies stockées
+fn status_mismatches(conn: &Connection) -> Result<Vec<StatusMismatch>> {
+    let mut stmt = conn.prepare(
+        "SELECT s.provider, s.symbol, s.timeframe,
+                s.oldest_candle_time, s.newest_candle_time,
+                (SELECT MIN(open_time) FROM candlesticks c
+                 WHERE c.provider = s.provider AND c.symbol = s.symbol
+                       AND c.timeframe = s.timeframe),
+                (SELECT MAX(open_time) FROM candlesticks c
+                 WHERE c.provider = s.provider AND c.symbol = s.symbol
+                       AND c.timeframe = s.timeframe)
+         FROM timeframe_status s
+         ORDER BY s.provider, s.symbol, s.timeframe",
+    )?;
+    let statuses = stmt.query_map([], |row| {
+        Ok(StatusMismatch {
+            provider: row.get(0)?,
+            symbol: row.get(1)?,
+            timeframe: row.get(2)?,
+            status_oldest: row.get(3)?,
+            status_newest: row.get(4)?,
+            actual_oldest: row.get(5)?,
+            actual_newest: row.get(6)?,
+        })
+    })?;
+
+    let mut mismatches = Vec::new();
+    for status in statuses {
+        let status = status?;
+        let differs =
+            |stored: Option<i64>, actual: Option<i64>| stored.is_some() && stored != actual;
+        if differs(status.status_oldest, status.actual_oldest)
+            || differs(status.status_newest, status.actual_newest)
+        {
+            mismatches.push(status);
+        }
+    }
+    Ok(mismatches)
+}
 
 /// Vérifie que les dates dans la base de données sont espacées de façon homogène
 ///
@@ -20,49 +665,19 @@ use rusqlite::{Connection, params};
 /// 3. Compare chaque intervalle avec l'intervalle attendu
 /// 4. Classe les anomalies: gaps (intervalle trop grand) ou overlaps (trop petit)
 /// 5. Calcule des statistiques: nombre de bougies, période couverte, etc.
-/// 6. Affiche un rapport détaillé des anomalies trouvées
 ///
-/// SUBTILITÉ RUST #17: pub fn
-/// pub = fonction publique, accessible depuis d'autres modules
-/// Sans pub, la fonction serait privée au module (visibility par défaut)
-pub fn verify_data_spacing(
+/// RETOUR: rapport sérialisable (verify_data --format json); l'affichage
+/// détaillé est fait par verify_data_spacing
+///
+/// ERREURS: timeframe inconnu
+pub fn verify_spacing(
     conn: &Connection,
     provider: &str,
     symbol: &str,
     timeframe: &str,
-) -> Result<()> {
-    // Déterminer l'intervalle attendu en millisecondes selon le timeframe
-    let expected_interval_ms = match timeframe {
-        "1m" => 60_000,
-        "3m" => 180_000,
-        "5m" => 300_000,
-        "15m" => 900_000,
-        "30m" => 1_800_000,
-        "1h" => 3_600_000,
-        "2h" => 7_200_000,
-        "4h" => 14_400_000,
-        "6h" => 21_600_000,
-        "8h" => 28_800_000,
-        "12h" => 43_200_000,
-        "1d" => 86_400_000,
-        "3d" => 259_200_000,
-        "1w" => 604_800_000,
-        "1M" => 2_592_000_000, // 30 jours approximatif
-        _ => {
-            eprintln!("Timeframe inconnu: {}", timeframe);
-            return Ok(());
-        }
-    };
-
-    println!(
-        "\n=== Vérification de l'espacement pour {}/{}/{} ===",
-        provider, symbol, timeframe
-    );
-    println!(
-        "Intervalle attendu: {} ms ({} minutes)",
-        expected_interval_ms,
-        expected_interval_ms / 60_000
-    );
+) -> Result<VerificationReport> {
+    // Intervalle attendu en millisecondes (1M: 30 jours approximatif)
+    let expected_interval_ms = parse_timeframe(timeframe)?;
 
     // Récupérer toutes les bougies triées par date
     let mut stmt = conn.prepare(
@@ -78,10 +693,10 @@ pub fn verify_data_spacing(
     let mut previous_time: Option<i64> = None;
 
     // SUBTILITÉ RUST #19: Vec avec types tuples
-    // Vec<(i64, i64, i64)> = vecteur de tuples à 3 éléments
+    // Vec<(i64, i64)> = vecteur de tuples à 2 éléments
     // Plus simple qu'une struct quand on n'a besoin que de stocker temporairement
-    let mut gaps: Vec<(i64, i64, i64)> = Vec::new(); // (timestamp, interval, expected)
-    let mut overlaps: Vec<(i64, i64)> = Vec::new(); // (timestamp, interval)
+    let mut gaps: Vec<(i64, i64)> = Vec::new(); // (timestamp, interval)
+    let mut overlaps: Vec<SpacingOverlap> = Vec::new();
     let mut total_count = 0;
     let mut first_timestamp: Option<i64> = None;
     let mut last_timestamp: Option<i64> = None;
@@ -108,14 +723,14 @@ pub fn verify_data_spacing(
             // 1. interval == expected: OK
             // 2. interval > expected: GAP (données manquantes)
             // 3. interval < expected: OVERLAP (duplication ou erreur)
-            if interval != expected_interval_ms {
-                if interval > expected_interval_ms {
-                    // Gap détecté - stocker pour rapport
-                    gaps.push((prev, interval, expected_interval_ms));
-                } else if interval < expected_interval_ms {
-                    // Overlap détecté - stocker pour rapport
-                    overlaps.push((prev, interval));
-                }
+            if interval > expected_interval_ms {
+                gaps.push((prev, interval));
+            } else if interval < expected_interval_ms {
+                overlaps.push(SpacingOverlap {
+                    open_time: prev,
+                    open_date: format_timestamp_iso(prev),
+                    interval_ms: interval,
+                });
             }
         }
 
@@ -123,57 +738,235 @@ pub fn verify_data_spacing(
         total_count += 1;
     }
 
+    // Séparer les gaps attendus (maintenance connue de l'exchange)
+    let downtime = load_known_downtime(conn);
+    let (expected_gaps, gaps): (Vec<_>, Vec<_>) = gaps
+        .into_iter()
+        .map(|(timestamp, interval)| SpacingGap {
+            open_time: timestamp,
+            open_date: format_timestamp_iso(timestamp),
+            interval_ms: interval,
+            missing_candles: interval / expected_interval_ms - 1,
+            note: downtime_note(&downtime, timestamp, interval, expected_interval_ms),
+        })
+        .partition(|gap| gap.note.is_some());
+
+    let expected_count = match (first_timestamp, last_timestamp) {
+        (Some(first), Some(last)) => (last - first) / expected_interval_ms + 1,
+        _ => 0,
+    };
+
+    Ok(VerificationReport {
+        timeframe: timeframe.to_string(),
+        expected_interval_ms,
+        total_count,
+        expected_count,
+        first_open_time: first_timestamp,
+        first_open_date: first_timestamp.map(format_timestamp_iso),
+        last_open_time: last_timestamp,
+        last_open_date: last_timestamp.map(format_timestamp_iso),
+        gaps,
+        expected_gaps,
+        overlaps,
+        coverage: None,
+        repair: None,
+        values: None,
+        spot_check: None,
+        interpolation: None,
+    })
+}
+
+/// Recherche les valeurs impossibles et les bougies identiques d'une série
+///
+/// VÉRIFICATIONS (par bougie, plusieurs possibles):
+/// - high < low, high < open/close, low > open/close
+/// - volume négatif, prix (open, high, low, close) exactement 0
+/// - séries d'au moins `flatline_run` bougies consécutives identiques
+///   (open, high, low, close et volume): forward-fill défaillant ou flux
+///   figé. flatline_run < 2: non vérifié
+///
+/// Bougies réelles et interpolées comptées séparément: un comblement
+/// synthétique ne masque pas les anomalies des données réelles
+pub fn verify_values(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    flatline_run: usize,
+) -> Result<ValueAnomalies> {
+    let mut report = ValueAnomalies {
+        provider: provider.to_string(),
+        symbol: symbol.to_string(),
+        timeframe: timeframe.to_string(),
+        flatline_run,
+        checked_candles: 0,
+        real: AnomalyCounts::default(),
+        interpolated: AnomalyCounts::default(),
+        samples: Vec::new(),
+    };
+    let mut stmt = conn.prepare(
+        "SELECT open_time, open, high, low, close, volume, interpolated FROM candlesticks
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+         ORDER BY open_time ASC",
+    )?;
+    let mut rows = stmt.query(params![provider, symbol, timeframe])?;
+
+    // Série en cours: (valeurs, première bougie, longueur, toutes interpolées)
+    let mut run: Option<([f64; 5], i64, i64, bool)> = None;
+    let flush = |run: Option<([f64; 5], i64, i64, bool)>, report: &mut ValueAnomalies| {
+        if let Some((_, open_time, length, interpolated)) = run
+            && flatline_run >= 2
+            && length >= flatline_run as i64
+        {
+            report.record(ValueAnomaly {
+                open_time,
+                kind: AnomalyKind::Flatline,
+                interpolated,
+                run_length: Some(length),
+            });
+        }
+    };
+
+    while let Some(row) = rows.next()? {
+        let open_time: i64 = row.get(0)?;
+        let values: [f64; 5] = [
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+        ];
+        let interpolated = row.get::<_, i64>(6)? > 0;
+        let [open, high, low, close, volume] = values;
+        report.checked_candles += 1;
+
+        let checks = [
+            (high < low, AnomalyKind::HighBelowLow),
+            (high < open.max(close), AnomalyKind::HighBelowBody),
+            (low > open.min(close), AnomalyKind::LowAboveBody),
+            (volume < 0.0, AnomalyKind::NegativeVolume),
+            (
+                [open, high, low, close].contains(&0.0),
+                AnomalyKind::ZeroPrice,
+            ),
+        ];
+        for (_, kind) in checks.into_iter().filter(|(failed, _)| *failed) {
+            report.record(ValueAnomaly {
+                open_time,
+                kind,
+                interpolated,
+                run_length: None,
+            });
+        }
+
+        run = match run {
+            Some((previous, start, length, all_interpolated)) if previous == values => Some((
+                previous,
+                start,
+                length + 1,
+                all_interpolated && interpolated,
+            )),
+            previous => {
+                flush(previous, &mut report);
+                Some((values, open_time, 1, interpolated))
+            }
+        };
+    }
+    flush(run, &mut report);
+
+    Ok(report)
+}
+
+/// Vérifie l'espacement d'un timeframe et affiche un rapport détaillé
+/// des anomalies trouvées (voir verify_spacing)
+///
+/// SUBTILITÉ RUST #17: pub fn
+/// pub = fonction publique, accessible depuis d'autres modules
+/// Sans pub, la fonction serait privée au module (visibility par défaut)
+pub fn verify_data_spacing(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+) -> Result<()> {
+    if parse_timeframe(timeframe).is_err() {
+        eprintln!("Timeframe inconnu: {}", timeframe);
+        return Ok(());
+    }
+    let report = verify_spacing(conn, provider, symbol, timeframe)?;
+    let expected_interval_ms = report.expected_interval_ms;
+
+    println!(
+        "\n=== Vérification de l'espacement pour {}/{}/{} ===",
+        provider, symbol, timeframe
+    );
+    println!(
+        "Intervalle attendu: {} ms ({} minutes)",
+        expected_interval_ms,
+        expected_interval_ms / 60_000
+    );
+
     // Afficher les résultats
     println!("\n--- Statistiques ---");
-    println!("Nombre total de bougies: {}", total_count);
+    println!("Nombre total de bougies: {}", report.total_count);
 
-    if let (Some(first), Some(last)) = (first_timestamp, last_timestamp) {
+    if let (Some(first), Some(last)) = (report.first_open_time, report.last_open_time) {
         println!("Première bougie: {}", format_timestamp_ms(first));
         println!("Dernière bougie: {}", format_timestamp_ms(last));
+        println!("Nombre de bougies attendu: {}", report.expected_count);
+        println!("Différence: {}", report.total_count - report.expected_count);
+    }
 
-        let duration_ms = last - first;
-        let expected_count = (duration_ms / expected_interval_ms) + 1;
-        println!("Nombre de bougies attendu: {}", expected_count);
-        println!("Différence: {}", total_count as i64 - expected_count);
+    if !report.expected_gaps.is_empty() {
+        println!(
+            "\n--- GAPS ATTENDUS ({} gaps, maintenance connue) ---",
+            report.expected_gaps.len()
+        );
+        for gap in &report.expected_gaps {
+            println!(
+                "  Gap à {}: {} bougies manquantes (expected: {})",
+                format_timestamp_ms(gap.open_time),
+                gap.missing_candles,
+                gap.note.as_deref().unwrap_or_default()
+            );
+        }
     }
 
     // Afficher les gaps (trous)
-    if !gaps.is_empty() {
-        println!("\n--- GAPS DÉTECTÉS ({} gaps) ---", gaps.len());
-        for (i, (timestamp, interval, expected)) in gaps.iter().enumerate() {
-            if i < 10 {
-                // Limiter l'affichage aux 10 premiers
-                let missing_candles = (interval / expected) - 1;
-                println!(
-                    "  Gap à {}: intervalle de {} ms ({} bougies manquantes)",
-                    format_timestamp_ms(*timestamp),
-                    interval,
-                    missing_candles
-                );
-            }
+    if !report.gaps.is_empty() {
+        println!("\n--- GAPS DÉTECTÉS ({} gaps) ---", report.gaps.len());
+        // Limiter l'affichage aux 10 premiers
+        for gap in report.gaps.iter().take(10) {
+            println!(
+                "  Gap à {}: intervalle de {} ms ({} bougies manquantes)",
+                format_timestamp_ms(gap.open_time),
+                gap.interval_ms,
+                gap.missing_candles
+            );
         }
-        if gaps.len() > 10 {
-            println!("  ... et {} autres gaps", gaps.len() - 10);
+        if report.gaps.len() > 10 {
+            println!("  ... et {} autres gaps", report.gaps.len() - 10);
         }
     } else {
         println!("\n✓ Aucun gap détecté - les données sont continues!");
     }
 
     // Afficher les overlaps (chevauchements)
-    if !overlaps.is_empty() {
-        println!("\n--- OVERLAPS DÉTECTÉS ({} overlaps) ---", overlaps.len());
-        for (i, (timestamp, interval)) in overlaps.iter().enumerate() {
-            if i < 10 {
-                println!(
-                    "  Overlap à {}: intervalle de {} ms (attendu {} ms)",
-                    format_timestamp_ms(*timestamp),
-                    interval,
-                    expected_interval_ms
-                );
-            }
+    if !report.overlaps.is_empty() {
+        println!(
+            "\n--- OVERLAPS DÉTECTÉS ({} overlaps) ---",
+            report.overlaps.len()
+        );
+        for overlap in report.overlaps.iter().take(10) {
+            println!(
+                "  Overlap à {}: intervalle de {} ms (attendu {} ms)",
+                format_timestamp_ms(overlap.open_time),
+                overlap.interval_ms,
+                expected_interval_ms
+            );
         }
-        if overlaps.len() > 10 {
-            println!("  ... et {} autres overlaps", overlaps.len() - 10);
+        if report.overlaps.len() > 10 {
+            println!("  ... et {} autres overlaps", report.overlaps.len() - 10);
         }
     } else {
         println!("✓ Aucun overlap détecté - les espacements sont corrects!");
@@ -184,6 +977,144 @@ pub fn verify_data_spacing(
     Ok(())
 }
 
+/// Répare les overlaps d'un timeframe: une seule bougie par période
+///
+/// ALGORITHME (transaction unique):
+/// 1. Bougies regroupées par période (round_to_timeframe de open_time)
+/// 2. Période couverte par plusieurs lignes (doublon aligné, bougie hors
+///    grille): la ligne conservée est la première selon
+///    - réelle (interpolated = 0) avant interpolée
+///    - open_time aligné sur la grille avant hors grille
+///    - close_time le plus récent, puis la dernière insérée (rowid)
+/// 3. Les autres lignes de la période sont supprimées, puis les bornes de
+///    timeframe_status recalées (TimeframeStatus::refresh_bounds)
+///
+/// SUBTILITÉ: une période couverte par une seule ligne n'est jamais touchée,
+/// même hors grille (supprimer l'unique bougie créerait un gap)
+///
+/// dry_run: rapport seul, rien n'est supprimé (transaction annulée)
+///
+/// ERREURS: timeframe inconnu
+pub fn repair_overlaps(
+    conn: &mut Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    dry_run: bool,
+) -> Result<RepairReport> {
+    let tx = conn.transaction()?;
+
+    let rows = {
+        let mut stmt = tx.prepare(
+            "SELECT rowid, open_time, close_time, interpolated FROM candlesticks
+             WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+             ORDER BY open_time ASC, rowid ASC",
+        )?;
+        stmt.query_map(params![provider, symbol, timeframe], |row| {
+            Ok(RowKey {
+                rowid: row.get(0)?,
+                open_time: row.get(1)?,
+                close_time: row.get(2)?,
+                interpolated: row.get::<_, i64>(3)? != 0,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+
+    // Tri par open_time: les lignes d'une même période sont consécutives
+    let mut periods: Vec<(i64, Vec<RowKey>)> = Vec::new();
+    for row in rows {
+        let period = round_to_timeframe(row.open_time, timeframe)?;
+        match periods.last_mut() {
+            Some((last, group)) if *last == period => group.push(row),
+            _ => periods.push((period, vec![row])),
+        }
+    }
+
+    let mut report = RepairReport {
+        timeframe: timeframe.to_string(),
+        dry_run,
+        overlapping_periods: 0,
+        removed: Vec::new(),
+    };
+    for (period, group) in periods.iter().filter(|(_, group)| group.len() > 1) {
+        report.overlapping_periods += 1;
+        let kept = group
+            .iter()
+            .min_by_key(|row| {
+                (
+                    row.interpolated,
+                    row.open_time != *period,
+                    std::cmp::Reverse((row.close_time, row.rowid)),
+                )
+            })
+            .expect("période d'au moins deux lignes");
+
+        for row in group.iter().filter(|row| row.rowid != kept.rowid) {
+            if !dry_run {
+                tx.execute("DELETE FROM candlesticks WHERE rowid = ?1", [row.rowid])?;
+            }
+            report.removed.push(RemovedRow {
+                open_time: row.open_time,
+                open_date: format_timestamp_iso(row.open_time),
+                close_time: row.close_time,
+                interpolated: row.interpolated,
+                kept_open_time: kept.open_time,
+            });
+        }
+    }
+
+    if !dry_run {
+        if !report.removed.is_empty() {
+            TimeframeStatus::refresh_bounds(&tx, provider, symbol, timeframe)?;
+        }
+        tx.commit()?;
+    }
+
+    Ok(report)
+}
+
+/// Ligne examinée par repair_overlaps
+struct RowKey {
+    rowid: i64,
+    open_time: i64,
+    close_time: i64,
+    interpolated: bool,
+}
+
+/// Charge les fenêtres (start_ms, end_ms, note) de la table known_downtime
+///
+/// Base sans cette table (créée par une ancienne version): aucune fenêtre
+fn load_known_downtime(conn: &Connection) -> Vec<(i64, i64, String)> {
+    let Ok(mut stmt) =
+        conn.prepare("SELECT start_ms, end_ms, COALESCE(note, '') FROM known_downtime")
+    else {
+        return Vec::new();
+    };
+
+    stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .map(|rows| rows.filter_map(|r| r.ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Note de la maintenance couvrant un gap, si chaque bougie manquante la chevauche
+///
+/// PARAMÈTRES: timestamp = bougie avant le gap, interval = écart observé
+fn downtime_note(
+    downtime: &[(i64, i64, String)],
+    timestamp: i64,
+    interval: i64,
+    expected: i64,
+) -> Option<String> {
+    let first_missing_close = timestamp + 2 * expected - 1;
+    let last_missing_open = timestamp + interval - expected;
+
+    downtime
+        .iter()
+        .find(|(start, end, _)| first_missing_close >= *start && last_missing_open <= *end)
+        .map(|(_, _, note)| note.clone())
+}
+
 /// Fonction utilitaire pour afficher les timestamps
 fn format_timestamp_ms(timestamp_ms: i64) -> String {
     // Crée un DateTime à partir du timestamp Unix en millisecondes
@@ -194,3 +1125,583 @@ fn format_timestamp_ms(timestamp_ms: i64) -> String {
         "Invalid timestamp".to_string()
     }
 }
+
+/// Lit une comparaison `inférieur:supérieur` (ex: "5m:1h")
+///
+/// ERREURS: format invalide, timeframe inconnu
+pub fn parse_cross_check(spec: &str) -> Result<(String, String)> {
+    let Some((lower, higher)) = spec.split_once(':') else {
+        bail!("Comparaison invalide: {} (attendu: 5m:1h)", spec);
+    };
+    parse_timeframe(lower)?;
+    parse_timeframe(higher)?;
+    Ok((lower.to_string(), higher.to_string()))
+}
+
+/// Compare les bougies stockées de higher_tf (open_time dans `range`) à
+/// l'agrégation des bougies de lower_tf (utils::resample_candles)
+///
+/// ALGORITHME:
+/// 1. Lit les bougies de higher_tf de la plage, et celles de lower_tf
+///    jusqu'à la fin de la dernière période
+/// 2. Rééchantillonne lower_tf vers higher_tf
+/// 3. Compare open, high, low, close et volume de chaque période:
+///    |agrégé - stocké| > tolerance × max(|agrégé|, |stocké|) est signalé
+///
+/// Périodes ignorées (pas de comparaison possible):
+/// - bougie interpolée d'un côté ou de l'autre
+/// - période incomplète dans lower_tf (bougie absente: trou, pas désaccord)
+/// - bougie absente dans higher_tf
+///
+/// ERREURS: timeframe inconnu, higher_tf pas un multiple de lower_tf, 1M
+pub fn verify_cross_timeframe(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    lower_tf: &str,
+    higher_tf: &str,
+    range: RangeInclusive<i64>,
+    tolerance: f64,
+) -> Result<Vec<CrossTimeframeMismatch>> {
+    let lower_interval = parse_timeframe(lower_tf)?;
+    let higher_interval = parse_timeframe(higher_tf)?;
+
+    let stored = query_candle_records(conn, provider, symbol, higher_tf, range.clone(), None)?;
+    let lower = query_candle_records(
+        conn,
+        provider,
+        symbol,
+        lower_tf,
+        *range.start()..=range.end().saturating_add(higher_interval - 1),
+        None,
+    )?;
+
+    let mut lower_counts: HashMap<i64, i64> = HashMap::new();
+    for candle in &lower {
+        *lower_counts
+            .entry(period_start(candle.open_time, higher_interval))
+            .or_default() += 1;
+    }
+    let resampled: HashMap<i64, _> = resample_candles(&lower, lower_tf, higher_tf)?
+        .into_iter()
+        .map(|candle| (candle.open_time, candle))
+        .collect();
+
+    let differs = |resampled: f64, stored: f64| {
+        (resampled - stored).abs() > tolerance * resampled.abs().max(stored.abs())
+    };
+
+    let mut mismatches = Vec::new();
+    for candle in stored.iter().filter(|candle| candle.interpolated == 0) {
+        let Some(aggregate) = resampled.get(&candle.open_time) else {
+            continue;
+        };
+        if aggregate.interpolated > 0
+            || lower_counts[&candle.open_time] != higher_interval / lower_interval
+        {
+            continue;
+        }
+
+        for (field, resampled, stored) in [
+            ("open", aggregate.open, candle.open),
+            ("high", aggregate.high, candle.high),
+            ("low", aggregate.low, candle.low),
+            ("close", aggregate.close, candle.close),
+            ("volume", aggregate.volume, candle.volume),
+        ] {
+            if differs(resampled, stored) {
+                mismatches.push(CrossTimeframeMismatch {
+                    provider: provider.to_string(),
+                    symbol: symbol.to_string(),
+                    open_time: candle.open_time,
+                    field: field.to_string(),
+                    resampled,
+                    stored,
+                });
+            }
+        }
+    }
+    Ok(mismatches)
+}
+
+/// verify_cross_timeframe sur toutes les séries (provider, symbole) de la
+/// base qui ont les deux timeframes, sur toute leur plage (binaire audit)
+pub fn audit_cross_timeframe(
+    conn: &Connection,
+    lower_tf: &str,
+    higher_tf: &str,
+    tolerance: f64,
+) -> Result<CrossCheckReport> {
+    let series: Vec<(String, String)> = conn
+        .prepare(
+            "SELECT DISTINCT provider, symbol FROM candlesticks
+             WHERE timeframe = ?1
+             ORDER BY provider, symbol",
+        )?
+        .query_map(params![higher_tf], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    let mut mismatches = Vec::new();
+    for (provider, symbol) in series {
+        mismatches.extend(verify_cross_timeframe(
+            conn,
+            &provider,
+            &symbol,
+            lower_tf,
+            higher_tf,
+            i64::MIN..=i64::MAX,
+            tolerance,
+        )?);
+    }
+    Ok(CrossCheckReport {
+        lower_timeframe: lower_tf.to_string(),
+        higher_timeframe: higher_tf.to_string(),
+        tolerance,
+        mismatches,
+    })
+}
+
+/// Bougies dont le close s'écarte de plus de `z_threshold` écarts-types de
+/// la moyenne des OUTLIER_WINDOW closes précédents
+///
+/// ALGORITHME: fenêtre glissante des closes précédents (la bougie testée
+/// n'en fait pas partie: un pic n'augmente pas l'écart-type qui le juge),
+/// écart-type échantillon (n - 1). Une bougie aberrante entre ensuite dans
+/// la fenêtre: le retour au prix normal qui suit n'est pas signalé
+///
+/// SUBTILITÉ: les OUTLIER_WINDOW premières bougies (fenêtre incomplète) et
+/// les fenêtres de prix constants (écart-type nul) ne sont pas évaluées
+pub fn detect_price_outliers(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    z_threshold: f64,
+) -> Result<Vec<OutlierCandle>> {
+    let mut stmt = conn.prepare(
+        "SELECT open_time, close FROM candlesticks
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+         ORDER BY open_time ASC",
+    )?;
+    let mut rows = stmt.query(params![provider, symbol, timeframe])?;
+
+    let mut window: VecDeque<f64> = VecDeque::with_capacity(OUTLIER_WINDOW + 1);
+    let mut outliers = Vec::new();
+    while let Some(row) = rows.next()? {
+        let (open_time, close): (i64, f64) = (row.get(0)?, row.get(1)?);
+
+        if window.len() == OUTLIER_WINDOW {
+            let mean = window.iter().sum::<f64>() / OUTLIER_WINDOW as f64;
+            let variance = window.iter().map(|c| (c - mean).powi(2)).sum::<f64>()
+                / (OUTLIER_WINDOW - 1) as f64;
+            let std = variance.sqrt();
+            if std > 0.0 {
+                let z_score = (close - mean) / std;
+                if z_score.abs() > z_threshold {
+                    outliers.push(OutlierCandle {
+                        open_time,
+                        close,
+                        rolling_mean: mean,
+                        z_score,
+                    });
+                }
+            }
+            window.pop_front();
+        }
+        window.push_back(close);
+    }
+    Ok(outliers)
+}
+
+/// Compare les closes d'une série (symbole, timeframe) stockée sous
+/// plusieurs providers, deux à deux
+///
+/// `providers` vide: tous les providers de la série, par nom. Un provider
+/// demandé sans bougie est compté à 0
+///
+/// ALGORITHME: jointure sur open_time des bougies réelles des deux
+/// providers (une bougie interpolée n'a pas de prix à comparer); écart
+/// relatif |a - b| / max(|a|, |b|) comparé à `tolerance`
+pub fn compare_providers(
+    conn: &Connection,
+    symbol: &str,
+    timeframe: &str,
+    providers: &[String],
+    tolerance: f64,
+) -> Result<ProviderComparison> {
+    let providers: Vec<String> = if providers.is_empty() {
+        conn.prepare(
+            "SELECT DISTINCT provider FROM candlesticks
+             WHERE symbol = ?1 AND timeframe = ?2 ORDER BY provider",
+        )?
+        .query_map(params![symbol, timeframe], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?
+    } else {
+        providers.to_vec()
+    };
+
+    let counts = providers
+        .iter()
+        .map(|provider| {
+            let count = conn.query_row(
+                "SELECT COUNT(*) FROM candlesticks
+                 WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3",
+                params![provider, symbol, timeframe],
+                |row| row.get(0),
+            )?;
+            Ok(ProviderCount {
+                provider: provider.clone(),
+                count,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT a.open_time, a.close, b.close
+         FROM candlesticks a
+         JOIN candlesticks b
+           ON b.provider = ?2 AND b.symbol = a.symbol AND b.timeframe = a.timeframe
+              AND b.open_time = a.open_time AND b.interpolated = 0
+         WHERE a.provider = ?1 AND a.symbol = ?3 AND a.timeframe = ?4 AND a.interpolated = 0
+         ORDER BY a.open_time ASC",
+    )?;
+    let mut pairs = Vec::new();
+    for (i, reference) in providers.iter().enumerate() {
+        for other in &providers[i + 1..] {
+            let mut pair = ProviderPair {
+                reference: reference.clone(),
+                other: other.clone(),
+                common_candles: 0,
+                mean_abs_close_diff: 0.0,
+                divergent_candles: 0,
+                max_divergence: None,
+            };
+            let mut total_diff = 0.0;
+            let mut rows = stmt.query(params![reference, other, symbol, timeframe])?;
+            while let Some(row) = rows.next()? {
+                let (open_time, reference_close, other_close): (i64, f64, f64) =
+                    (row.get(0)?, row.get(1)?, row.get(2)?);
+                let diff = (reference_close - other_close).abs();
+                let scale = reference_close.abs().max(other_close.abs());
+                let relative_diff = if scale > 0.0 { diff / scale } else { 0.0 };
+
+                pair.common_candles += 1;
+                total_diff += diff;
+                if relative_diff > tolerance {
+                    pair.divergent_candles += 1;
+                }
+                if pair
+                    .max_divergence
+                    .is_none_or(|max| relative_diff > max.relative_diff)
+                {
+                    pair.max_divergence = Some(ProviderDivergence {
+                        open_time,
+                        reference_close,
+                        other_close,
+                        relative_diff,
+                    });
+                }
+            }
+            if pair.common_candles > 0 {
+                pair.mean_abs_close_diff = total_diff / pair.common_candles as f64;
+            }
+            pairs.push(pair);
+        }
+    }
+
+    Ok(ProviderComparison {
+        symbol: symbol.to_string(),
+        timeframe: timeframe.to_string(),
+        tolerance,
+        providers: counts,
+        pairs,
+    })
+}
+
+/// compare_providers sur chaque série (symbole, timeframe) de la base
+/// stockée sous plusieurs providers (binaire audit)
+pub fn audit_providers(conn: &Connection, tolerance: f64) -> Result<Vec<ProviderComparison>> {
+    let series: Vec<(String, String)> = conn
+        .prepare(
+            "SELECT symbol, timeframe FROM candlesticks
+             GROUP BY symbol, timeframe
+             HAVING COUNT(DISTINCT provider) > 1
+             ORDER BY symbol, timeframe",
+        )?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    series
+        .iter()
+        .map(|(symbol, timeframe)| compare_providers(conn, symbol, timeframe, &[], tolerance))
+        .collect()
+}
+
+/// Nombre et part de bougies interpolées d'une série, et leur plus longue
+/// série continue
+///
+/// ALGORITHME: un seul parcours dans l'ordre des open_time; une bougie
+/// réelle clôt la série en cours. À longueur égale, la première série est
+/// retenue
+pub fn verify_interpolation(
+    conn: &Connection,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+) -> Result<InterpolationReport> {
+    let mut stmt = conn.prepare(
+        "SELECT open_time, interpolated FROM candlesticks
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3
+         ORDER BY open_time ASC",
+    )?;
+    let mut rows = stmt.query(params![provider, symbol, timeframe])?;
+
+    let (mut total, mut interpolated) = (0, 0);
+    let mut current: Option<InterpolatedRun> = None;
+    let mut longest: Option<InterpolatedRun> = None;
+    while let Some(row) = rows.next()? {
+        let (open_time, is_interpolated): (i64, bool) = (row.get(0)?, row.get::<_, i64>(1)? != 0);
+        total += 1;
+        if !is_interpolated {
+            current = None;
+            continue;
+        }
+        interpolated += 1;
+        let run = current.get_or_insert(InterpolatedRun {
+            first_open_time: open_time,
+            last_open_time: open_time,
+            length: 0,
+        });
+        run.last_open_time = open_time;
+        run.length += 1;
+        if longest.is_none_or(|longest| run.length > longest.length) {
+            longest = Some(*run);
+        }
+    }
+
+    Ok(InterpolationReport {
+        provider: provider.to_string(),
+        symbol: symbol.to_string(),
+        timeframe: timeframe.to_string(),
+        total,
+        interpolated,
+        percentage: if total == 0 {
+            0.0
+        } else {
+            interpolated as f64 / total as f64 * 100.0
+        },
+        longest_run: longest,
+    })
+}
+
+/// Compare `samples` bougies réelles tirées au hasard aux klines renvoyées
+/// à nouveau par la source (contrôle par sondage)
+///
+/// ALGORITHME:
+/// 1. Tire les bougies non interpolées (ORDER BY RANDOM() LIMIT samples),
+///    triées ensuite par open_time
+/// 2. Pour chacune: attend le budget de poids (wait_for_klines_budget),
+///    demande une seule kline à partir de son open_time
+/// 3. Compare chaque champ (kline convertie comme par le récupérateur,
+///    kline_to_record): une Divergence par champ différent
+///
+/// SUBTILITÉ: une bougie que la source ne sert plus (batch vide, ou kline
+/// suivante renvoyée à la place) est ignorée, pas signalée: l'API Binance
+/// ne garde pas tout l'historique de tous les symboles
+///
+/// DESIGN: comparaison exacte, la bougie stockée et la kline passent par la
+/// même conversion du texte de l'API; le RateLimiter est celui du
+/// récupérateur s'il tourne dans le même processus
+pub fn spot_check<P: KlineProvider>(
+    conn: &Connection,
+    source: &P,
+    rate_limiter: &RateLimiter,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+    samples: usize,
+) -> Result<Vec<Divergence>> {
+    let mut stmt = conn.prepare(
+        "SELECT open_time FROM candlesticks
+         WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3 AND interpolated = 0
+         ORDER BY RANDOM() LIMIT ?4",
+    )?;
+    let mut open_times = stmt
+        .query_map(
+            params![provider, symbol, timeframe, samples as i64],
+            |row| row.get::<_, i64>(0),
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    open_times.sort_unstable();
+
+    let mut divergences = Vec::new();
+    for open_time in open_times {
+        let Some(stored) = query_candle_records(
+            conn,
+            provider,
+            symbol,
+            timeframe,
+            open_time..=open_time,
+            None,
+        )?
+        .pop() else {
+            continue;
+        };
+
+        wait_for_klines_budget(rate_limiter);
+        let klines = source.get_klines(symbol, timeframe, Some(1), Some(open_time as u64), None)?;
+        let Some(kline) = klines.first().filter(|k| k.open_time == open_time) else {
+            continue;
+        };
+        let fresh = kline_to_record(symbol, timeframe, kline);
+
+        for ((field, stored_value), (_, fresh_value)) in candle_fields(&stored)
+            .into_iter()
+            .zip(candle_fields(&fresh))
+        {
+            if stored_value != fresh_value {
+                divergences.push(Divergence {
+                    provider: provider.to_string(),
+                    symbol: symbol.to_string(),
+                    timeframe: timeframe.to_string(),
+                    open_time,
+                    field: field.to_string(),
+                    stored: stored_value,
+                    fresh: fresh_value,
+                });
+            }
+        }
+    }
+    Ok(divergences)
+}
+
+/// Champs comparés par spot_check (open_time exclu: clé de la comparaison)
+fn candle_fields(candle: &CandleRecord) -> [(&'static str, f64); 10] {
+    [
+        ("open", candle.open),
+        ("high", candle.high),
+        ("low", candle.low),
+        ("close", candle.close),
+        ("volume", candle.volume),
+        ("close_time", candle.close_time as f64),
+        ("quote_asset_volume", candle.quote_asset_volume),
+        ("number_of_trades", candle.number_of_trades as f64),
+        (
+            "taker_buy_base_asset_volume",
+            candle.taker_buy_base_asset_volume,
+        ),
+        (
+            "taker_buy_quote_asset_volume",
+            candle.taker_buy_quote_asset_volume,
+        ),
+    ]
+}
+
+/// Espacement, valeurs et part interpolée (verify_spacing, verify_values,
+/// verify_interpolation) de chaque série de chaque fichier .db d'un
+/// répertoire, triés par nom
+///
+/// PARALLÉLISME: au plus `jobs` threads (minimum 1), chacun prend le
+/// fichier suivant de la liste; chaque fichier est ouvert en lecture seule
+/// par le thread qui le vérifie. Un fichier illisible n'interrompt pas le
+/// parcours (FileVerification::error)
+///
+/// ERREURS: répertoire illisible
+pub fn verify_directory(
+    db_dir: &Path,
+    jobs: usize,
+    flatline_run: usize,
+) -> Result<Vec<FileVerification>> {
+    let mut databases: Vec<_> = std::fs::read_dir(db_dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "db"))
+        .collect();
+    databases.sort();
+
+    let next = AtomicUsize::new(0);
+    let mut verified: Vec<(usize, FileVerification)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.clamp(1, databases.len().max(1)))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(path) = databases.get(index) else {
+                            return done;
+                        };
+                        done.push((index, verify_file(path, flatline_run)));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("thread de vérification"))
+            .collect()
+    });
+    verified.sort_by_key(|(index, _)| *index);
+
+    Ok(verified.into_iter().map(|(_, file)| file).collect())
+}
+
+/// Vérification d'un fichier .db ouvert en lecture seule (voir
+/// verify_directory)
+fn verify_file(path: &Path, flatline_run: usize) -> FileVerification {
+    let file = path.display().to_string();
+    match Connection::open_with_flags(
+        read_only_uri(path),
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_URI,
+    )
+    .map_err(anyhow::Error::from)
+    .and_then(|conn| verify_all_series(&conn, flatline_run))
+    {
+        Ok(series) => FileVerification {
+            file,
+            error: None,
+            series,
+        },
+        Err(e) => FileVerification {
+            file,
+            error: Some(e.to_string()),
+            series: Vec::new(),
+        },
+    }
+}
+
+/// verify_spacing et verify_values de chaque série de timeframe connu,
+/// triées par provider, symbole puis durée du timeframe
+fn verify_all_series(conn: &Connection, flatline_run: usize) -> Result<Vec<SeriesVerification>> {
+    let mut series: Vec<(String, String, String)> = conn
+        .prepare("SELECT DISTINCT provider, symbol, timeframe FROM candlesticks")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<rusqlite::Result<Vec<(String, String, String)>>>()?
+        .into_iter()
+        .filter(|(_, _, timeframe)| parse_timeframe(timeframe).is_ok())
+        .collect();
+    series.sort_by(|a, b| {
+        (&a.0, &a.1)
+            .cmp(&(&b.0, &b.1))
+            .then_with(|| compare_timeframes(&a.2, &b.2))
+    });
+
+    series
+        .into_iter()
+        .map(|(provider, symbol, timeframe)| {
+            let mut report = verify_spacing(conn, &provider, &symbol, &timeframe)?;
+            report.values = Some(verify_values(
+                conn,
+                &provider,
+                &symbol,
+                &timeframe,
+                flatline_run,
+            )?);
+            report.interpolation =
+                Some(verify_interpolation(conn, &provider, &symbol, &timeframe)?);
+            Ok(SeriesVerification {
+                provider,
+                symbol,
+                report,
+            })
+        })
+        .collect()
+}
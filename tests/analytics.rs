@@ -0,0 +1,182 @@
+/// Tests des analyses de séries de prix (analytics::returns)
+///
+/// - Rendements simples, logarithmiques et cumulés calculés à la main,
+///   série vide
+/// - Somme des rendements logarithmiques = ln(dernier / premier)
+/// - returns_series: close précédant la plage pour pct / log, cumulé depuis
+///   le premier close de la plage
+/// - store_returns: trois types enregistrés, valeurs remplacées au
+///   recalcul
+mod testutil;
+
+use rust_candles_retriever::analytics::returns::{
+    ReturnKind, calculate_cumulative_returns, calculate_log_returns, calculate_pct_returns,
+    returns_series, store_returns,
+};
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::models::CandleRecord;
+use testutil::{BASE_TIME, PROVIDER, candle, memory_db};
+
+const INTERVAL: i64 = 86_400_000; // 1 jour
+
+/// Série 1d des closes donnés, à partir de BASE_TIME
+fn insert_daily(db: &mut DatabaseManager, closes: &[f64]) {
+    let candles: Vec<CandleRecord> = closes
+        .iter()
+        .enumerate()
+        .map(|(position, &close)| {
+            candle(
+                "BTCUSDT",
+                "1d",
+                BASE_TIME + position as i64 * INTERVAL,
+                close,
+            )
+        })
+        .collect();
+    db.insert_candles(&candles).unwrap();
+}
+
+fn assert_close(actual: Option<f64>, expected: f64) {
+    let actual = actual.expect("rendement attendu");
+    assert!(
+        (actual - expected).abs() < 1e-9,
+        "{} au lieu de {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn returns_computed_by_hand() {
+    let closes = [100.0, 110.0, 99.0];
+
+    let pct = calculate_pct_returns(&closes);
+    assert_eq!(pct[0], None);
+    assert_close(pct[1], 10.0);
+    assert_close(pct[2], -10.0);
+
+    let log = calculate_log_returns(&closes);
+    assert_eq!(log[0], None);
+    assert_close(log[1], (1.1f64).ln());
+    assert_close(log[2], (0.9f64).ln());
+
+    let cumulative = calculate_cumulative_returns(&closes);
+    assert_close(cumulative[0], 0.0);
+    assert_close(cumulative[1], 10.0);
+    assert_close(cumulative[2], -1.0);
+
+    assert!(calculate_pct_returns(&[]).is_empty());
+    assert!(calculate_log_returns(&[]).is_empty());
+    assert!(calculate_cumulative_returns(&[]).is_empty());
+    assert_eq!(calculate_log_returns(&[100.0]), vec![None]);
+}
+
+#[test]
+fn log_returns_sum_to_log_of_total_ratio() {
+    let closes = [
+        42_000.0, 42_850.5, 41_200.0, 43_999.9, 43_100.0, 45_020.0, 44_870.3, 46_500.0,
+    ];
+
+    let sum: f64 = calculate_log_returns(&closes).into_iter().flatten().sum();
+    let expected = (closes[closes.len() - 1] / closes[0]).ln();
+    assert!(
+        (sum - expected).abs() < 1e-12,
+        "{} au lieu de {}",
+        sum,
+        expected
+    );
+
+    // Cohérence avec le cumulé: exp(Σ log) - 1 = dernier cumulé / 100
+    let cumulative = calculate_cumulative_returns(&closes);
+    assert_close(
+        cumulative.last().copied().flatten().map(|c| c / 100.0),
+        sum.exp() - 1.0,
+    );
+}
+
+#[test]
+fn returns_series_uses_close_before_range() {
+    let mut db = memory_db();
+    insert_daily(&mut db, &[100.0, 200.0, 100.0, 150.0, 300.0]);
+    let range = BASE_TIME + 2 * INTERVAL..=BASE_TIME + 4 * INTERVAL;
+
+    // pct: la bougie 2 a un rendement (close précédent: bougie 1)
+    let pct = returns_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1d",
+        range.clone(),
+        ReturnKind::Pct,
+    )
+    .unwrap();
+    let values: Vec<f64> = pct.iter().map(|v| v.value).collect();
+    assert_eq!(values, vec![-50.0, 50.0, 100.0]);
+    assert_eq!(pct[0].open_time, BASE_TIME + 2 * INTERVAL);
+
+    // cumulative: depuis le premier close de la plage
+    let cumulative = returns_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1d",
+        range,
+        ReturnKind::Cumulative,
+    )
+    .unwrap();
+    let values: Vec<f64> = cumulative.iter().map(|v| v.value).collect();
+    assert_eq!(values, vec![0.0, 50.0, 200.0]);
+
+    // Toute la série: pas de rendement simple pour la première bougie
+    let log = returns_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1d",
+        0..=i64::MAX,
+        ReturnKind::Log,
+    )
+    .unwrap();
+    assert_eq!(log.len(), 4);
+    assert_eq!(log[0].open_time, BASE_TIME + INTERVAL);
+}
+
+#[test]
+fn store_returns_writes_every_kind() {
+    let mut db = memory_db();
+    insert_daily(&mut db, &[100.0, 110.0, 99.0]);
+
+    // 2 pct + 2 log + 3 cumulative
+    assert_eq!(
+        store_returns(db.connection_mut(), PROVIDER, "BTCUSDT", "1d").unwrap(),
+        7
+    );
+
+    // Nouvelle bougie: toute la série recalculée, sans doublon
+    insert_daily(&mut db, &[100.0, 110.0, 99.0, 120.0]);
+    assert_eq!(
+        store_returns(db.connection_mut(), PROVIDER, "BTCUSDT", "1d").unwrap(),
+        10
+    );
+
+    let count = |kind: &str| -> i64 {
+        db.connection()
+            .query_row(
+                "SELECT COUNT(*) FROM returns WHERE symbol = 'BTCUSDT' AND timeframe = '1d' AND kind = ?1",
+                [kind],
+                |row| row.get(0),
+            )
+            .unwrap()
+    };
+    assert_eq!((count("pct"), count("log"), count("cumulative")), (3, 3, 4));
+
+    let last_cumulative: f64 = db
+        .connection()
+        .query_row(
+            "SELECT value FROM returns WHERE kind = 'cumulative' ORDER BY open_time DESC LIMIT 1",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert!((last_cumulative - 20.0).abs() < 1e-9);
+}
@@ -0,0 +1,31 @@
+/// Tests du jeton du flux temps réel (StreamToken)
+use rust_candles_retriever::auth::StreamToken;
+
+#[test]
+fn stream_is_public_without_configured_token() {
+    for token in [
+        StreamToken::new(None),
+        StreamToken::new(Some(String::new())),
+    ] {
+        assert!(!token.is_required());
+        assert!(token.verify(None));
+        assert!(token.verify(Some("anything")));
+    }
+}
+
+#[test]
+fn stream_requires_the_exact_token() {
+    let token = StreamToken::new(Some("s3cret".to_string()));
+
+    assert!(token.is_required());
+    assert!(token.verify(Some("s3cret")));
+    for wrong in [
+        None,
+        Some(""),
+        Some("s3cre"),
+        Some("s3cret "),
+        Some("S3CRET"),
+    ] {
+        assert!(!token.verify(wrong), "{:?}", wrong);
+    }
+}
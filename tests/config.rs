@@ -0,0 +1,59 @@
+/// Tests de la sélection des timeframes du backfill (config::BackfillOptions)
+///
+/// - Liste par défaut, sous-ensemble explicite, exclusions
+/// - Timeframe inconnu ou sélection vide refusés
+use rust_candles_retriever::config::{BackfillOptions, DEFAULT_BACKFILL_TIMEFRAMES};
+use rust_candles_retriever::error::CandleError;
+
+fn options(timeframes: &[&str], exclude_timeframes: &[&str]) -> BackfillOptions {
+    BackfillOptions {
+        timeframes: timeframes.iter().map(|tf| tf.to_string()).collect(),
+        exclude_timeframes: exclude_timeframes.iter().map(|tf| tf.to_string()).collect(),
+        ..BackfillOptions::default()
+    }
+}
+
+#[test]
+fn default_selection_is_the_default_list() {
+    assert_eq!(
+        options(&[], &[]).active_timeframes().unwrap(),
+        DEFAULT_BACKFILL_TIMEFRAMES
+    );
+}
+
+#[test]
+fn explicit_subset_is_sorted_and_deduplicated() {
+    assert_eq!(
+        options(&["4h", "1h", "4h", "1m"], &[])
+            .active_timeframes()
+            .unwrap(),
+        vec!["1m", "1h", "4h"]
+    );
+}
+
+#[test]
+fn exclusions_apply_to_default_and_subset() {
+    let active = options(&[], &["5m", "15m"]).active_timeframes().unwrap();
+    assert_eq!(active.first(), Some(&"30m"));
+    assert_eq!(active.len(), DEFAULT_BACKFILL_TIMEFRAMES.len() - 2);
+
+    assert_eq!(
+        options(&["1h", "4h"], &["4h"]).active_timeframes().unwrap(),
+        vec!["1h"]
+    );
+}
+
+#[test]
+fn invalid_selections_are_rejected() {
+    for invalid in [
+        options(&["1h", "1hour"], &[]),
+        options(&[], &["25m"]),
+        options(&["1h"], &["1h"]),
+    ] {
+        assert!(
+            matches!(invalid.validate(), Err(CandleError::ValidationError(_))),
+            "{:?}",
+            invalid
+        );
+    }
+}
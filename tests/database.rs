@@ -0,0 +1,1006 @@
+/// Tests d'intégration de la couche base de données
+///
+/// - Pragmas de connexion (WAL, busy_timeout): un écrivain insère des batches
+///   pendant que plusieurs lecteurs interrogent la même base fichier, aucune
+///   erreur "database is locked" ne doit remonter
+/// - Migrations: une base à l'ancien schéma est mise à jour sans perte, et le
+///   schéma migré est identique aux constantes SQL_CREATE_TABLE_*;
+///   obv_values et stochastic_values reprises dans indicator_values
+/// - insert_candles / query_candles sur une base en mémoire
+/// - Maintenance: VACUUM rend les pages libérées par une purge; vacuum
+///   retourne les octets libérés, mesurés sur le fichier
+/// - Statistiques descriptives comparées à un calcul à la main
+/// - Couverture par timeframe (trous, part interpolée, complétion)
+/// - Bases de plusieurs paires attachées à une connexion (PairDatabases)
+/// - Sauvegarde pendant des écritures: instantané cohérent (integrity_check)
+/// - check_integrity / check_quick: base saine, contrainte violée
+use rusqlite::Connection;
+use rust_candles_retriever::database::{
+    DatabaseManager, MAX_ATTACHED_DATABASES, PairDatabases, SQL_CREATE_TABLE_CANDLESTICKS,
+    SQL_CREATE_TABLE_TIMEFRAME_STATUS, backup_database, descriptive_stats, open_configured,
+    timeframe_infos,
+};
+use rust_candles_retriever::indicators::stochastic::Stochastic;
+use rust_candles_retriever::indicators::values::Indicator;
+use rust_candles_retriever::migrations;
+use rust_candles_retriever::models::CandleRecord;
+use rust_candles_retriever::timeframe_status::TimeframeStatus;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+const BATCHES: i64 = 50;
+const BATCH_SIZE: i64 = 200;
+const READERS: usize = 4;
+const INTERVAL: i64 = 300_000; // 5 minutes
+
+/// Fichier temporaire supprimé (avec -wal et -shm) en fin de test
+struct TempDb(PathBuf);
+
+impl TempDb {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("{}_{}.db", name, std::process::id()));
+        let db = TempDb(path);
+        db.cleanup();
+        db
+    }
+
+    fn path(&self) -> &str {
+        self.0.to_str().expect("chemin UTF-8")
+    }
+
+    fn cleanup(&self) {
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", self.path(), suffix));
+        }
+    }
+}
+
+impl Drop for TempDb {
+    fn drop(&mut self) {
+        self.cleanup();
+    }
+}
+
+#[test]
+fn pragmas_are_applied() {
+    let temp = TempDb::new("candles_pragmas");
+    let db = DatabaseManager::new(temp.path()).expect("base fichier");
+    let conn = db.connection();
+
+    let journal_mode: String = conn
+        .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+        .unwrap();
+    let synchronous: i64 = conn
+        .query_row("PRAGMA synchronous", [], |row| row.get(0))
+        .unwrap();
+    let busy_timeout: i64 = conn
+        .query_row("PRAGMA busy_timeout", [], |row| row.get(0))
+        .unwrap();
+    let foreign_keys: i64 = conn
+        .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+        .unwrap();
+
+    assert_eq!(journal_mode, "wal");
+    assert_eq!(synchronous, 1); // NORMAL
+    assert_eq!(busy_timeout, 5000);
+    assert_eq!(foreign_keys, 1);
+}
+
+#[test]
+fn concurrent_writer_and_readers_do_not_lock() {
+    let temp = TempDb::new("candles_concurrency");
+    // Schéma créé avant le démarrage des threads
+    drop(DatabaseManager::new(temp.path()).expect("base fichier"));
+
+    let done = Arc::new(AtomicBool::new(false));
+
+    let readers: Vec<_> = (0..READERS)
+        .map(|_| {
+            let path = temp.path().to_string();
+            let done = Arc::clone(&done);
+            thread::spawn(move || -> rusqlite::Result<usize> {
+                let conn = open_configured(&path).expect("connexion lecteur");
+                let mut queries = 0;
+                // Au moins une requête, même si l'écrivain a déjà fini
+                loop {
+                    conn.query_row(
+                        "SELECT COUNT(*), MAX(open_time) FROM candlesticks
+                         WHERE symbol = 'TEST' AND timeframe = '5m'",
+                        [],
+                        |row| row.get::<_, i64>(0),
+                    )?;
+                    queries += 1;
+                    if done.load(Ordering::Relaxed) {
+                        break;
+                    }
+                }
+                Ok(queries)
+            })
+        })
+        .collect();
+
+    let writer = {
+        let path = temp.path().to_string();
+        thread::spawn(move || -> rust_candles_retriever::error::Result<()> {
+            let mut db = DatabaseManager::new(&path).expect("connexion écrivain");
+            for batch in 0..BATCHES {
+                let candles: Vec<_> = (0..BATCH_SIZE)
+                    .map(|i| record((batch * BATCH_SIZE + i) * INTERVAL, 1.0))
+                    .collect();
+                db.insert_candles(&candles)?;
+            }
+            Ok(())
+        })
+    };
+
+    let write_result = writer.join().expect("thread écrivain");
+    done.store(true, Ordering::Relaxed);
+    let read_results: Vec<_> = readers
+        .into_iter()
+        .map(|reader| reader.join().expect("thread lecteur"))
+        .collect();
+
+    write_result.expect("écriture sans verrou bloquant");
+    for result in read_results {
+        let queries = result.expect("lecture sans verrou bloquant");
+        assert!(queries > 0);
+    }
+
+    let conn = open_configured(temp.path()).unwrap();
+    let count: i64 = conn
+        .query_row("SELECT COUNT(*) FROM candlesticks", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(count, BATCHES * BATCH_SIZE);
+}
+
+/// Base créée avant les migrations: pas de derived_from, de known_downtime,
+/// ni de schema_migrations
+fn create_legacy_database(path: &str) {
+    let conn = Connection::open(path).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE candlesticks (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            open_time INTEGER NOT NULL,
+            open REAL NOT NULL,
+            high REAL NOT NULL,
+            low REAL NOT NULL,
+            close REAL NOT NULL,
+            volume REAL NOT NULL,
+            close_time INTEGER NOT NULL,
+            quote_asset_volume REAL NOT NULL,
+            number_of_trades INTEGER NOT NULL,
+            taker_buy_base_asset_volume REAL NOT NULL,
+            taker_buy_quote_asset_volume REAL NOT NULL,
+            interpolated INTEGER NOT NULL DEFAULT 0,
+            UNIQUE(provider, symbol, timeframe, open_time)
+        );
+        CREATE TABLE timeframe_status (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            oldest_candle_time INTEGER,
+            last_updated INTEGER NOT NULL,
+            PRIMARY KEY (provider, symbol, timeframe)
+        );
+        INSERT INTO candlesticks VALUES ('binance', 'BTCUSDT', '5m', 1700000000000,
+            100.0, 105.0, 95.0, 102.0, 10.0, 1700000299999, 1000.0, 42, 6.0, 600.0, 0);
+        INSERT INTO timeframe_status VALUES ('binance', 'BTCUSDT', '5m', 1700000000000, 1700000500000);",
+    )
+    .unwrap();
+}
+
+#[test]
+fn legacy_database_is_upgraded() {
+    let temp = TempDb::new("candles_legacy");
+    create_legacy_database(temp.path());
+
+    let db = DatabaseManager::new(temp.path()).expect("migration");
+    let conn = db.connection();
+
+    assert_eq!(
+        migrations::current_version(conn).unwrap(),
+        migrations::latest_version()
+    );
+
+    // Données conservées, nouvelles colonnes avec leur valeur par défaut
+    let (close, trades, derived_from): (f64, i64, Option<String>) = conn
+        .query_row(
+            "SELECT close, number_of_trades, derived_from FROM candlesticks",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(close, 102.0);
+    assert_eq!(trades, 42);
+    assert_eq!(derived_from, None);
+
+    let (oldest, newest, is_complete, last_updated): (i64, i64, i64, i64) = conn
+        .query_row(
+            "SELECT oldest_candle_time, newest_candle_time, is_complete, last_updated
+             FROM timeframe_status
+             WHERE provider = 'binance' AND symbol = 'BTCUSDT' AND timeframe = '5m'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(oldest, 1700000000000);
+    // Déduit de la dernière bougie stockée
+    assert_eq!(newest, 1700000000000);
+    assert_eq!(is_complete, 0);
+    assert_eq!(last_updated, 1700000500000);
+
+    let downtime_windows: i64 = conn
+        .query_row("SELECT COUNT(*) FROM known_downtime", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(downtime_windows, 0);
+    drop(db);
+
+    // Réouverture: rien à appliquer
+    let mut conn = open_configured(temp.path()).unwrap();
+    assert_eq!(migrations::run(&mut conn).unwrap(), 0);
+}
+
+#[test]
+fn obv_values_are_moved_to_indicator_values() {
+    let temp = TempDb::new("candles_obv_values");
+    create_legacy_database(temp.path());
+    Connection::open(temp.path())
+        .unwrap()
+        .execute_batch(
+            "CREATE TABLE obv_values (
+                provider TEXT NOT NULL,
+                symbol TEXT NOT NULL,
+                timeframe TEXT NOT NULL,
+                open_time INTEGER NOT NULL,
+                obv REAL NOT NULL,
+                PRIMARY KEY (provider, symbol, timeframe, open_time)
+            );
+            INSERT INTO obv_values VALUES ('binance', 'BTCUSDT', '5m', 1700000000000, 12.5);",
+        )
+        .unwrap();
+
+    let db = DatabaseManager::new(temp.path()).expect("migration");
+    let conn = db.connection();
+    let row: (String, String, String, i64, f64) = conn
+        .query_row(
+            "SELECT indicator, params, component, open_time, value FROM indicator_values",
+            [],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        row,
+        (
+            "obv".to_string(),
+            "{}".to_string(),
+            "value".to_string(),
+            1700000000000,
+            12.5
+        )
+    );
+    let obv_table: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE name = 'obv_values'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(obv_table, 0);
+}
+
+#[test]
+fn stochastic_values_are_moved_to_indicator_values() {
+    let temp = TempDb::new("candles_stochastic_values");
+    create_legacy_database(temp.path());
+    Connection::open(temp.path())
+        .unwrap()
+        .execute_batch(
+            "CREATE TABLE stochastic_values (
+                provider TEXT NOT NULL,
+                symbol TEXT NOT NULL,
+                timeframe TEXT NOT NULL,
+                k_period INTEGER NOT NULL,
+                d_period INTEGER NOT NULL,
+                open_time INTEGER NOT NULL,
+                k_value REAL NOT NULL,
+                d_value REAL,
+                PRIMARY KEY (provider, symbol, timeframe, k_period, d_period, open_time)
+            );
+            INSERT INTO stochastic_values VALUES
+                ('binance', 'BTCUSDT', '5m', 14, 3, 1700000000000, 40.0, NULL),
+                ('binance', 'BTCUSDT', '5m', 14, 3, 1700000300000, 60.0, 50.0);",
+        )
+        .unwrap();
+
+    let db = DatabaseManager::new(temp.path()).expect("migration");
+    let conn = db.connection();
+    let mut stmt = conn
+        .prepare(
+            "SELECT indicator, params, component, open_time, value FROM indicator_values
+             ORDER BY component DESC, open_time",
+        )
+        .unwrap();
+    let rows: Vec<(String, String, String, i64, f64)> = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+            ))
+        })
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let params = Stochastic::new(14, 1, 3).unwrap().params().to_string();
+    let row = |component: &str, open_time: i64, value: f64| {
+        (
+            "stochastic".to_string(),
+            params.clone(),
+            component.to_string(),
+            open_time,
+            value,
+        )
+    };
+    assert_eq!(
+        rows,
+        vec![
+            row("k", 1700000000000, 40.0),
+            row("k", 1700000300000, 60.0),
+            row("d", 1700000300000, 50.0),
+        ]
+    );
+    let stochastic_table: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE name = 'stochastic_values'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(stochastic_table, 0);
+}
+
+#[test]
+fn per_pair_timeframe_status_is_reconciled() {
+    let temp = TempDb::new("candles_per_pair");
+    {
+        let conn = Connection::open(temp.path()).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE timeframe_status (
+                symbol TEXT NOT NULL,
+                timeframe TEXT NOT NULL,
+                oldest_time INTEGER,
+                newest_time INTEGER,
+                PRIMARY KEY (symbol, timeframe)
+            );
+            INSERT INTO timeframe_status VALUES ('ETHUSDT', '1h', 1600000000000, 1700000000000);",
+        )
+        .unwrap();
+    }
+
+    let db = DatabaseManager::new(temp.path()).expect("migration");
+    let (provider, oldest, is_complete): (String, i64, i64) = db
+        .connection()
+        .query_row(
+            "SELECT provider, oldest_candle_time, is_complete FROM timeframe_status
+             WHERE symbol = 'ETHUSDT' AND timeframe = '1h'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(provider, "binance");
+    assert_eq!(oldest, 1600000000000);
+    assert_eq!(is_complete, 0);
+}
+
+/// (nom, type, notnull, défaut, pk) de chaque colonne d'une table
+fn table_info(conn: &Connection, table: &str) -> Vec<(String, String, bool, Option<String>, i64)> {
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA table_info({})", table))
+        .unwrap();
+    stmt.query_map([], |row| {
+        Ok((
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+        ))
+    })
+    .unwrap()
+    .collect::<Result<_, _>>()
+    .unwrap()
+}
+
+#[test]
+fn migrated_schema_matches_canonical_definitions() {
+    let canonical = Connection::open_in_memory().unwrap();
+    canonical
+        .execute(SQL_CREATE_TABLE_CANDLESTICKS, [])
+        .unwrap();
+    canonical
+        .execute(SQL_CREATE_TABLE_TIMEFRAME_STATUS, [])
+        .unwrap();
+
+    // Base neuve et base à l'ancien schéma: mêmes colonnes après migration
+    let temp = TempDb::new("candles_canonical");
+    create_legacy_database(temp.path());
+    let legacy = DatabaseManager::new(temp.path()).unwrap();
+    let fresh = DatabaseManager::new(":memory:").unwrap();
+
+    for table in ["candlesticks", "timeframe_status"] {
+        let expected = table_info(&canonical, table);
+        assert_eq!(table_info(fresh.connection(), table), expected, "{}", table);
+        assert_eq!(
+            table_info(legacy.connection(), table),
+            expected,
+            "{}",
+            table
+        );
+    }
+}
+
+#[test]
+fn canonical_database_keeps_its_status_when_migrated() {
+    let temp = TempDb::new("candles_canonical_status");
+    {
+        let conn = Connection::open(temp.path()).unwrap();
+        conn.execute(SQL_CREATE_TABLE_CANDLESTICKS, []).unwrap();
+        conn.execute(SQL_CREATE_TABLE_TIMEFRAME_STATUS, []).unwrap();
+        conn.execute(
+            "INSERT INTO timeframe_status VALUES ('binance', 'BTCUSDT', '5m', 1, 1, 3, 2)",
+            [],
+        )
+        .unwrap();
+    }
+
+    let db = DatabaseManager::new(temp.path()).expect("migration");
+    let row: (i64, i64, i64, i64) = db
+        .connection()
+        .query_row(
+            "SELECT oldest_candle_time, newest_candle_time, is_complete, last_updated
+             FROM timeframe_status",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(row, (1, 2, 1, 3));
+}
+
+fn record(open_time: i64, close: f64) -> CandleRecord {
+    CandleRecord {
+        provider: "test_provider".to_string(),
+        symbol: "TEST".to_string(),
+        timeframe: "5m".to_string(),
+        open_time,
+        open: close - 1.0,
+        high: close + 2.0,
+        low: close - 3.0,
+        close,
+        volume: 10.0,
+        close_time: open_time + INTERVAL - 1,
+        quote_asset_volume: 1000.0,
+        number_of_trades: 7,
+        taker_buy_base_asset_volume: 6.0,
+        taker_buy_quote_asset_volume: 600.0,
+        interpolated: 0,
+        derived_from: None,
+    }
+}
+
+#[test]
+fn insert_candles_ignores_duplicates() {
+    let mut db = DatabaseManager::new(":memory:").unwrap();
+    let candles: Vec<_> = (0..5)
+        .map(|i| record(i * INTERVAL, 100.0 + i as f64))
+        .collect();
+
+    assert_eq!(db.insert_candles(&candles).unwrap(), 5);
+
+    // Même open_time: la bougie existante est conservée
+    let duplicate = CandleRecord {
+        close: 999.0,
+        ..candles[2].clone()
+    };
+    let derived = CandleRecord {
+        derived_from: Some("1m".to_string()),
+        ..record(5 * INTERVAL, 105.0)
+    };
+    assert_eq!(db.insert_candles(&[duplicate, derived]).unwrap(), 1);
+
+    let stored = db
+        .query_candles("test_provider", "TEST", "5m", 0..=i64::MAX, None)
+        .unwrap();
+    assert_eq!(stored.len(), 6);
+    assert_eq!(stored[..5], candles[..]);
+    assert_eq!(stored[5].derived_from.as_deref(), Some("1m"));
+}
+
+#[test]
+fn query_candles_filters_range_series_and_limit() {
+    let mut db = DatabaseManager::new(":memory:").unwrap();
+    let mut candles: Vec<_> = (0..10).map(|i| record(i * INTERVAL, 100.0)).collect();
+    candles.push(CandleRecord {
+        timeframe: "1h".to_string(),
+        ..record(0, 100.0)
+    });
+    candles.push(CandleRecord {
+        symbol: "OTHER".to_string(),
+        ..record(INTERVAL, 100.0)
+    });
+    db.insert_candles(&candles).unwrap();
+
+    let in_range = db
+        .query_candles(
+            "test_provider",
+            "TEST",
+            "5m",
+            2 * INTERVAL..=5 * INTERVAL,
+            None,
+        )
+        .unwrap();
+    let open_times: Vec<i64> = in_range.iter().map(|c| c.open_time / INTERVAL).collect();
+    assert_eq!(open_times, vec![2, 3, 4, 5]);
+
+    let limited = db
+        .query_candles("test_provider", "TEST", "5m", 0..=i64::MAX, Some(3))
+        .unwrap();
+    assert_eq!(limited.len(), 3);
+    assert_eq!(limited[2].open_time, 2 * INTERVAL);
+
+    let empty = db
+        .query_candles(
+            "test_provider",
+            "TEST",
+            "5m",
+            20 * INTERVAL..=30 * INTERVAL,
+            None,
+        )
+        .unwrap();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn stats_report_coverage_with_gaps() {
+    let mut db = DatabaseManager::new(":memory:").unwrap();
+
+    // 10 bougies attendues: 4 absente, 3 interpolée (gap comblé)
+    let mut candles: Vec<_> = [0, 1, 2, 5, 6, 7, 8, 9]
+        .iter()
+        .map(|&i| record(i * INTERVAL, 100.0))
+        .collect();
+    candles.push(CandleRecord {
+        interpolated: 1,
+        ..record(3 * INTERVAL, 100.0)
+    });
+    db.insert_candles(&candles).unwrap();
+
+    let stats = db.stats("test_provider", "TEST", "5m").unwrap();
+    assert_eq!(stats.count, 9);
+    assert_eq!(stats.interpolated_count, 1);
+    assert_eq!(stats.first_open_time, Some(0));
+    assert_eq!(stats.last_open_time, Some(9 * INTERVAL));
+    assert_eq!(stats.expected_count, 10);
+    assert!((stats.coverage_pct - 80.0).abs() < 1e-9);
+    assert!((stats.span_days - 50.0 / 1440.0).abs() < 1e-9);
+
+    // Série absente: aucune bougie attendue
+    let empty = db.stats("test_provider", "TEST", "1h").unwrap();
+    assert_eq!(empty.count, 0);
+    assert_eq!(empty.first_open_time, None);
+    assert_eq!(empty.expected_count, 0);
+    assert_eq!(empty.coverage_pct, 0.0);
+}
+
+#[test]
+fn stats_all_lists_series_by_timeframe_duration() {
+    let mut db = DatabaseManager::new(":memory:").unwrap();
+    let series = |timeframe: &str, count: i64| -> Vec<CandleRecord> {
+        let interval = rust_candles_retriever::utils::parse_timeframe(timeframe).unwrap();
+        (0..count)
+            .map(|i| CandleRecord {
+                timeframe: timeframe.to_string(),
+                open_time: i * interval,
+                close_time: (i + 1) * interval - 1,
+                ..record(0, 100.0)
+            })
+            .collect()
+    };
+    for (timeframe, count) in [("1d", 2), ("15m", 4), ("5m", 3)] {
+        db.insert_candles(&series(timeframe, count)).unwrap();
+    }
+
+    let stats = db.stats_all().unwrap();
+    let summary: Vec<_> = stats
+        .iter()
+        .map(|s| (s.timeframe.as_str(), s.count, s.coverage_pct))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![("5m", 3, 100.0), ("15m", 4, 100.0), ("1d", 2, 100.0)]
+    );
+    assert_eq!(stats[0], db.stats("test_provider", "TEST", "5m").unwrap());
+}
+
+#[test]
+fn vacuum_reclaims_pages_freed_by_prune() {
+    let temp = TempDb::new("candles_optimize");
+    let mut db = DatabaseManager::new(temp.path()).unwrap();
+    let candles: Vec<CandleRecord> = (0..5000)
+        .map(|i| record(i * INTERVAL, 100.0 + i as f64))
+        .collect();
+    db.insert_candles(&candles).unwrap();
+    db.prune("test_provider", "TEST", "5m", 4900 * INTERVAL)
+        .unwrap();
+
+    // Sans VACUUM, les pages libérées restent dans le fichier
+    let report = db.optimize(false).unwrap();
+    assert!(!report.vacuumed);
+    assert!(report.after.freelist_count > 0);
+    assert_eq!(report.after.page_count, report.before.page_count);
+
+    let report = db.optimize(true).unwrap();
+    assert_eq!(report.after.freelist_count, 0);
+    assert!(report.after.bytes() < report.before.bytes());
+    assert_eq!(
+        std::fs::metadata(temp.path()).unwrap().len() as i64,
+        report.after.bytes()
+    );
+    assert_eq!(
+        db.query_candles("test_provider", "TEST", "5m", 0..=i64::MAX, None)
+            .unwrap()
+            .len(),
+        100
+    );
+}
+
+#[test]
+fn vacuum_returns_bytes_freed() {
+    let temp = TempDb::new("candles_vacuum");
+    let mut db = DatabaseManager::new(temp.path()).unwrap();
+    let candles: Vec<CandleRecord> = (0..5000)
+        .map(|i| record(i * INTERVAL, 100.0 + i as f64))
+        .collect();
+    db.insert_candles(&candles).unwrap();
+    db.prune("test_provider", "TEST", "5m", 4900 * INTERVAL)
+        .unwrap();
+    // Checkpoint: le fichier principal contient les pages libérées
+    db.connection()
+        .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+        .unwrap();
+    let before = std::fs::metadata(temp.path()).unwrap().len();
+
+    let freed = db.vacuum().unwrap();
+    let after = std::fs::metadata(temp.path()).unwrap().len();
+    assert!(freed > 0);
+    assert_eq!(before - after, freed);
+    // Rien à rendre au second passage
+    assert_eq!(db.vacuum().unwrap(), 0);
+}
+
+#[test]
+fn descriptive_stats_match_hand_computed_values() {
+    let mut db = DatabaseManager::new(":memory:").unwrap();
+    // (open, high, low, close, volume): haussière, baissière, doji
+    let ohlcv = [
+        (10.0, 14.0, 9.0, 13.0, 1.0),
+        (13.0, 13.5, 10.0, 11.0, 2.0),
+        (11.0, 12.0, 10.0, 11.05, 3.0),
+    ];
+    let candles: Vec<CandleRecord> = ohlcv
+        .iter()
+        .enumerate()
+        .map(|(i, &(open, high, low, close, volume))| CandleRecord {
+            open,
+            high,
+            low,
+            close,
+            volume,
+            ..record(i as i64 * INTERVAL, close)
+        })
+        .collect();
+    db.insert_candles(&candles).unwrap();
+
+    let stats = descriptive_stats(db.connection(), "test_provider", "TEST", "5m", 0..=i64::MAX)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(stats.count, 3);
+    assert!((stats.mean_close - 35.05 / 3.0).abs() < 1e-9);
+    // Écart-type échantillon de [13, 11, 11.05]
+    let mean = 35.05 / 3.0;
+    let variance = [13.0, 11.0, 11.05_f64]
+        .iter()
+        .map(|c| (c - mean).powi(2))
+        .sum::<f64>()
+        / 2.0;
+    assert!((stats.std_close - variance.sqrt()).abs() < 1e-9);
+    assert_eq!((stats.min_close, stats.max_close), (11.0, 13.0));
+    assert_eq!((stats.total_volume, stats.mean_volume), (6.0, 2.0));
+    assert_eq!((stats.highest_high, stats.lowest_low), (14.0, 9.0));
+    assert!((stats.avg_body_size - (3.0 + 2.0 + 0.05) / 3.0).abs() < 1e-9);
+    assert!((stats.avg_wick_size - (2.0 + 1.5 + 1.95) / 3.0).abs() < 1e-9);
+    assert_eq!(
+        (
+            stats.bullish_candles,
+            stats.bearish_candles,
+            stats.doji_candles
+        ),
+        (1, 1, 1)
+    );
+
+    // Plage restreinte à la première bougie, puis plage vide
+    let first = descriptive_stats(db.connection(), "test_provider", "TEST", "5m", 0..=0)
+        .unwrap()
+        .unwrap();
+    assert_eq!((first.count, first.std_close), (1, 0.0));
+    assert!(
+        descriptive_stats(db.connection(), "test_provider", "TEST", "1h", 0..=i64::MAX)
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn timeframe_infos_report_gaps_and_interpolation() {
+    let mut db = DatabaseManager::new(":memory:").unwrap();
+    // 5m: positions 0..10 sans 2, 5 et 6 (deux trous), position 9 interpolée
+    let candles: Vec<CandleRecord> = (0..10)
+        .filter(|i| ![2, 5, 6].contains(i))
+        .map(|i| CandleRecord {
+            interpolated: (i == 9) as i64,
+            ..record(i * INTERVAL, 100.0)
+        })
+        .collect();
+    db.insert_candles(&candles).unwrap();
+    db.insert_candles(&[CandleRecord {
+        timeframe: "1h".to_string(),
+        ..record(0, 100.0)
+    }])
+    .unwrap();
+    TimeframeStatus::mark_complete(db.connection(), "test_provider", "TEST", "1h").unwrap();
+    TimeframeStatus::update_progress(db.connection(), "test_provider", "TEST", "1h", 0, 0).unwrap();
+
+    let infos = timeframe_infos(db.connection(), "test_provider", "TEST").unwrap();
+
+    assert_eq!(infos.len(), 2);
+    let (five, hour) = (&infos[0], &infos[1]);
+    assert_eq!(five.timeframe, "5m");
+    assert_eq!(five.candle_count, 7);
+    assert_eq!(
+        (five.oldest_candle, five.newest_candle),
+        (Some(0), Some(9 * INTERVAL))
+    );
+    assert_eq!((five.gap_count, five.missing_candles), (2, 3));
+    assert!((five.interpolated_ratio - 1.0 / 7.0).abs() < 1e-9);
+    // Bougies réelles / attendues, comme /api/stats
+    assert!((five.coverage_percentage - 60.0).abs() < 1e-9);
+    assert_eq!(five.newest_candle_time, None);
+    assert!(!five.is_backfill_complete);
+
+    assert_eq!(hour.timeframe, "1h");
+    assert_eq!((hour.candle_count, hour.gap_count), (1, 0));
+    assert_eq!(hour.interpolated_ratio, 0.0);
+    assert_eq!(hour.coverage_percentage, 100.0);
+    assert_eq!(hour.newest_candle_time, Some(0));
+    assert!(hour.is_backfill_complete);
+
+    assert!(
+        timeframe_infos(db.connection(), "test_provider", "UNKNOWN")
+            .unwrap()
+            .is_empty()
+    );
+}
+
+/// Répertoire `<SYMBOL>.db` par paire, `count` bougies 5m par symbole
+fn pair_dir(name: &str, pairs: &[(&str, i64)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    for &(symbol, count) in pairs {
+        let path = dir.join(format!("{}.db", symbol));
+        let mut db = DatabaseManager::new(path.to_str().unwrap()).unwrap();
+        let candles: Vec<CandleRecord> = (0..count)
+            .map(|i| CandleRecord {
+                symbol: symbol.to_string(),
+                ..record(i * INTERVAL, 100.0)
+            })
+            .collect();
+        db.insert_candles(&candles).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn pair_databases_union_across_files() {
+    let dir = pair_dir("candles_pairs_union", &[("BTCUSDT", 5), ("ETHUSDT", 3)]);
+
+    let pairs = PairDatabases::open(&dir, &["BTCUSDT", "ETHUSDT"]).unwrap();
+    assert_eq!(pairs.schema("BTCUSDT"), Some("main"));
+    assert_eq!(pairs.schema("ETHUSDT"), Some("pair_ethusdt"));
+
+    let counts = pairs
+        .run(|conn, schemas| {
+            let union = schemas
+                .iter()
+                .map(|schema| {
+                    format!(
+                        "SELECT symbol, COUNT(*) AS n FROM {}.candlesticks GROUP BY symbol",
+                        schema
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" UNION ALL ");
+            let mut stmt = conn.prepare(&format!("{} ORDER BY symbol", union))?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(rows)
+        })
+        .unwrap();
+    assert_eq!(
+        counts,
+        vec![("BTCUSDT".to_string(), 5), ("ETHUSDT".to_string(), 3)]
+    );
+
+    // Les bases attachées sont en lecture seule
+    assert!(
+        pairs
+            .run(|conn, _| Ok(conn.execute("DELETE FROM pair_ethusdt.candlesticks", [])?))
+            .is_err()
+    );
+
+    drop(pairs);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn pair_databases_validate_requested_symbols() {
+    let dir = pair_dir("candles_pairs_validate", &[("BTCUSDT", 1), ("ETHUSDT", 1)]);
+
+    assert!(PairDatabases::open(&dir, &[]).is_err());
+    assert!(PairDatabases::open(&dir, &["BTCUSDT", "SOLUSDT"]).is_err());
+    assert!(PairDatabases::open(&dir, &["BTCUSDT", "ETHUSDT", "ETHUSDT"]).is_err());
+
+    let too_many = vec!["BTCUSDT"; MAX_ATTACHED_DATABASES + 2];
+    assert!(PairDatabases::open(&dir, &too_many).is_err());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn backup_during_writes_is_consistent() {
+    let temp = TempDb::new("candles_backup_source");
+    let snapshot = TempDb::new("candles_backup_snapshot");
+    let initial = BATCHES * BATCH_SIZE;
+    {
+        let mut db = DatabaseManager::new(temp.path()).unwrap();
+        let candles: Vec<_> = (0..initial).map(|i| record(i * INTERVAL, 1.0)).collect();
+        db.insert_candles(&candles).unwrap();
+    }
+
+    // Écrivain continu pendant toute la sauvegarde
+    let done = Arc::new(AtomicBool::new(false));
+    let writer = {
+        let path = temp.path().to_string();
+        let done = Arc::clone(&done);
+        thread::spawn(move || -> i64 {
+            let mut db = DatabaseManager::new(&path).expect("connexion écrivain");
+            let mut next = initial;
+            while !done.load(Ordering::Relaxed) {
+                let candles: Vec<_> = (next..next + 20)
+                    .map(|i| record(i * INTERVAL, 2.0))
+                    .collect();
+                db.insert_candles(&candles)
+                    .expect("écriture pendant la sauvegarde");
+                next += 20;
+            }
+            next
+        })
+    };
+
+    let db = DatabaseManager::new(temp.path()).unwrap();
+    let report = db.backup_to(snapshot.path()).expect("sauvegarde");
+    done.store(true, Ordering::Relaxed);
+    let written = writer.join().expect("thread écrivain");
+
+    let copy = Connection::open(snapshot.path()).unwrap();
+    let integrity: String = copy
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .unwrap();
+    let journal_mode: String = copy
+        .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(integrity, "ok");
+    assert_eq!(journal_mode, "delete");
+    assert!((initial..=written).contains(&report.candles));
+    assert!(!std::path::Path::new(&format!("{}.partial", snapshot.path())).exists());
+
+    // Une sauvegarde existante n'est jamais écrasée
+    assert!(db.backup_to(snapshot.path()).is_err());
+}
+
+#[test]
+fn pair_backup_keeps_only_the_symbol() {
+    let temp = TempDb::new("candles_backup_pair_source");
+    let snapshot = TempDb::new("candles_backup_pair_snapshot");
+    let mut db = DatabaseManager::new(temp.path()).unwrap();
+    for (symbol, count) in [("BTCUSDT", 30), ("ETHUSDT", 10)] {
+        let candles: Vec<_> = (0..count)
+            .map(|i| CandleRecord {
+                symbol: symbol.to_string(),
+                ..record(i * INTERVAL, 1.0)
+            })
+            .collect();
+        db.insert_candles(&candles).unwrap();
+        TimeframeStatus::update_progress(
+            db.connection(),
+            "binance",
+            symbol,
+            "5m",
+            0,
+            (count - 1) * INTERVAL,
+        )
+        .unwrap();
+    }
+
+    let report = backup_database(
+        db.connection(),
+        std::path::Path::new(snapshot.path()),
+        Some("ETHUSDT"),
+    )
+    .unwrap();
+
+    assert_eq!(report.candles, 10);
+    assert_eq!(report.symbol.as_deref(), Some("ETHUSDT"));
+    let copy = DatabaseManager::new(snapshot.path()).unwrap();
+    let symbols: Vec<String> = copy
+        .stats_all()
+        .unwrap()
+        .into_iter()
+        .map(|stats| stats.symbol)
+        .collect();
+    assert_eq!(symbols, vec!["ETHUSDT".to_string()]);
+    assert_eq!(
+        TimeframeStatus::get_last_candle_time(copy.connection(), "binance", "BTCUSDT", "5m"),
+        None
+    );
+}
+
+#[test]
+fn integrity_check_reports_violations() {
+    let db = DatabaseManager::new_in_memory().unwrap();
+    assert_eq!(db.check_integrity().unwrap(), Vec::<String>::new());
+    assert!(db.check_quick().unwrap());
+
+    // Ligne qui viole une contrainte, insérée en désactivant les CHECK
+    // (corruption logique que seul le contrôle d'intégrité détecte)
+    db.connection()
+        .execute_batch(
+            "CREATE TABLE guarded (price REAL CHECK (price > 0));
+             PRAGMA ignore_check_constraints = ON;
+             INSERT INTO guarded VALUES (-1.0);
+             PRAGMA ignore_check_constraints = OFF;",
+        )
+        .unwrap();
+
+    let errors = db.check_integrity().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("guarded"), "{:?}", errors);
+    assert!(!db.check_quick().unwrap());
+}
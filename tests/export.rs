@@ -0,0 +1,388 @@
+/// Tests d'intégration de l'export CSV et Parquet sur une base en mémoire
+///
+/// Les tests Parquet (module parquet) nécessitent la feature `parquet`
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::export::{
+    EXPORT_CHUNK_ROWS, ExportFormat, month_ranges, write_csv, write_csv_filtered,
+};
+use rust_candles_retriever::models::CandleRecord;
+
+const BASE_TIME: i64 = 1704067200000; // 2024-01-01 00:00 UTC
+const INTERVAL: i64 = 300_000; // 5 minutes
+const DAY: i64 = 86_400_000;
+
+fn setup(count: i64) -> DatabaseManager {
+    let mut db = DatabaseManager::new(":memory:").unwrap();
+    let candles: Vec<_> = (0..count)
+        .map(|i| CandleRecord {
+            provider: "binance".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            timeframe: "5m".to_string(),
+            open_time: BASE_TIME + i * INTERVAL,
+            open: 100.0,
+            high: 110.0,
+            low: 90.0,
+            close: 105.5,
+            volume: 12.0,
+            close_time: BASE_TIME + (i + 1) * INTERVAL - 1,
+            quote_asset_volume: 1266.0,
+            number_of_trades: 30,
+            taker_buy_base_asset_volume: 6.0,
+            taker_buy_quote_asset_volume: 633.0,
+            interpolated: i % 2,
+            derived_from: None,
+        })
+        .collect();
+    db.insert_candles(&candles).unwrap();
+    db
+}
+
+fn export(db: &DatabaseManager, format: ExportFormat) -> (usize, Vec<String>) {
+    let mut chunks = Vec::new();
+    let rows = write_csv(
+        db.connection(),
+        "binance",
+        "BTCUSDT",
+        "5m",
+        0..=i64::MAX,
+        format,
+        |chunk| {
+            chunks.push(chunk);
+            true
+        },
+    )
+    .unwrap();
+    (rows, chunks)
+}
+
+#[test]
+fn standard_format_rows() {
+    let db = setup(2);
+    let (rows, chunks) = export(&db, ExportFormat::Standard);
+
+    assert_eq!(rows, 2);
+    assert_eq!(
+        chunks.concat(),
+        "open_time_ms,open_time_iso,open,high,low,close,volume,quote_volume,trades,interpolated\n\
+         1704067200000,2024-01-01T00:00:00Z,100,110,90,105.5,12,1266,30,0\n\
+         1704067500000,2024-01-01T00:05:00Z,100,110,90,105.5,12,1266,30,1\n"
+    );
+}
+
+#[test]
+fn tradingview_format_rows() {
+    let db = setup(1);
+    let (_, chunks) = export(&db, ExportFormat::TradingView);
+
+    assert_eq!(
+        chunks.concat(),
+        "time,open,high,low,close,Volume\n1704067200,100,110,90,105.5,12\n"
+    );
+}
+
+#[test]
+fn rows_are_sent_in_bounded_chunks() {
+    let total = EXPORT_CHUNK_ROWS as i64 * 2 + 10;
+    let db = setup(total);
+    let (rows, chunks) = export(&db, ExportFormat::Standard);
+
+    assert_eq!(rows as i64, total);
+    // En-tête + 2 blocs pleins + le reste
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(chunks[1].lines().count(), EXPORT_CHUNK_ROWS);
+    assert_eq!(chunks[3].lines().count(), 10);
+}
+
+#[test]
+fn disconnected_sink_stops_reading() {
+    let db = setup(EXPORT_CHUNK_ROWS as i64 * 3);
+    let mut calls = 0;
+    let rows = write_csv(
+        db.connection(),
+        "binance",
+        "BTCUSDT",
+        "5m",
+        0..=i64::MAX,
+        ExportFormat::Standard,
+        |_| {
+            calls += 1;
+            calls < 2 // le client se déconnecte après l'en-tête
+        },
+    )
+    .unwrap();
+
+    assert_eq!(rows, EXPORT_CHUNK_ROWS);
+    assert_eq!(calls, 2);
+}
+
+/// Export au format Table (binaire export_csv), concaténé
+fn export_table(db: &DatabaseManager, include_interpolated: bool, human_time: bool) -> String {
+    let mut csv = String::new();
+    write_csv_filtered(
+        db.connection(),
+        "binance",
+        "BTCUSDT",
+        "5m",
+        0..=i64::MAX,
+        include_interpolated,
+        ExportFormat::Table { human_time },
+        |chunk| {
+            csv.push_str(&chunk);
+            true
+        },
+    )
+    .unwrap();
+    csv
+}
+
+#[test]
+fn table_format_matches_database_rows() {
+    let total = EXPORT_CHUNK_ROWS as i64 + 5;
+    let db = setup(total);
+    let csv = export_table(&db, true, false);
+    let lines: Vec<&str> = csv.lines().collect();
+
+    // En-tête = colonnes de CandleRecord::COLUMNS, puis une ligne par bougie
+    assert_eq!(lines[0], CandleRecord::COLUMNS.replace(", ", ","));
+    assert_eq!(lines.len() as i64, total + 1);
+
+    let stored = db
+        .query_candles("binance", "BTCUSDT", "5m", 0..=i64::MAX, None)
+        .unwrap();
+    for index in [0, 1, 500, total as usize - 1] {
+        let c = &stored[index];
+        let fields: Vec<&str> = lines[index + 1].split(',').collect();
+        assert_eq!(fields.len(), 16);
+        assert_eq!(fields[3].parse::<i64>().unwrap(), c.open_time);
+        assert_eq!(fields[7].parse::<f64>().unwrap(), c.close);
+        assert_eq!(fields[9].parse::<i64>().unwrap(), c.close_time);
+        assert_eq!(fields[14].parse::<i64>().unwrap(), c.interpolated);
+        assert_eq!(fields[15], "");
+    }
+}
+
+#[test]
+fn table_format_filters_interpolated_and_keeps_precision() {
+    let mut db = setup(4);
+    let precise = CandleRecord {
+        open_time: BASE_TIME + 4 * INTERVAL,
+        close_time: BASE_TIME + 5 * INTERVAL - 1,
+        close: 0.1 + 0.2,
+        interpolated: 0,
+        ..db.query_candles("binance", "BTCUSDT", "5m", 0..=i64::MAX, Some(1))
+            .unwrap()
+            .remove(0)
+    };
+    db.insert_candles(&[precise]).unwrap();
+
+    // Positions 1 et 3 interpolées (setup): 3 bougies réelles sur 5
+    let real_only = export_table(&db, false, false);
+    assert_eq!(real_only.lines().count(), 1 + 3);
+    assert!(
+        real_only
+            .lines()
+            .skip(1)
+            .all(|l| l.split(',').nth(14) == Some("0"))
+    );
+
+    let last = real_only.lines().last().unwrap();
+    let close: f64 = last.split(',').nth(7).unwrap().parse().unwrap();
+    assert_eq!(close, 0.1 + 0.2);
+
+    let human = export_table(&db, true, true);
+    let first: Vec<&str> = human.lines().nth(1).unwrap().split(',').collect();
+    assert_eq!(first[3], "2024-01-01T00:00:00.000Z");
+    assert_eq!(first[9], "2024-01-01T00:04:59.999Z");
+}
+
+#[test]
+fn month_ranges_follow_calendar_months() {
+    // 2024-01-15 00:00 → 2024-03-10 00:00 UTC (2024 est bissextile)
+    let start = BASE_TIME + 14 * DAY;
+    let end = BASE_TIME + 69 * DAY;
+    let months = month_ranges(start..=end);
+
+    let labels: Vec<&str> = months.iter().map(|(label, _)| label.as_str()).collect();
+    assert_eq!(labels, ["2024-01", "2024-02", "2024-03"]);
+    assert_eq!(months[0].1, start..=BASE_TIME + 31 * DAY - 1);
+    assert_eq!(months[1].1, BASE_TIME + 31 * DAY..=BASE_TIME + 60 * DAY - 1);
+    assert_eq!(months[2].1, BASE_TIME + 60 * DAY..=end);
+
+    // Plage contenue dans un seul mois
+    assert_eq!(
+        month_ranges(start..=start + 1),
+        vec![("2024-01".to_string(), start..=start + 1)]
+    );
+}
+
+#[cfg(feature = "parquet")]
+mod parquet_export {
+    use super::*;
+    use parquet::basic::{Compression, Type as PhysicalType};
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::RowAccessor;
+    use rust_candles_retriever::export::write_parquet;
+
+    /// Exporte une plage en Parquet dans un fichier temporaire et le relit
+    fn parquet_round_trip(
+        db: &DatabaseManager,
+        name: &str,
+        range: std::ops::RangeInclusive<i64>,
+    ) -> (usize, SerializedFileReader<std::fs::File>) {
+        let path = std::env::temp_dir().join(format!("{}_{}.parquet", name, std::process::id()));
+        let file = std::fs::File::create(&path).unwrap();
+        let rows = write_parquet(db.connection(), "binance", "BTCUSDT", "5m", range, file).unwrap();
+
+        let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+        // Le descripteur reste ouvert, le fichier peut être supprimé (Unix)
+        let _ = std::fs::remove_file(&path);
+        (rows, reader)
+    }
+
+    #[test]
+    fn parquet_round_trip_preserves_values() {
+        let mut db = setup(10);
+        // Bougie agrégée: derived_from renseigné (colonne OPTIONAL)
+        let derived = CandleRecord {
+            open_time: BASE_TIME + 10 * INTERVAL,
+            close_time: BASE_TIME + 11 * INTERVAL - 1,
+            close: 107.25,
+            derived_from: Some("1m".to_string()),
+            ..db.query_candles("binance", "BTCUSDT", "5m", 0..=i64::MAX, Some(1))
+                .unwrap()
+                .remove(0)
+        };
+        db.insert_candles(&[derived]).unwrap();
+
+        // Sous-ensemble: bougies 4 à 10 incluses
+        let range = BASE_TIME + 4 * INTERVAL..=BASE_TIME + 10 * INTERVAL;
+        let expected = db
+            .query_candles("binance", "BTCUSDT", "5m", range.clone(), None)
+            .unwrap();
+        let (rows, reader) = parquet_round_trip(&db, "candles_parquet_values", range);
+        assert_eq!(rows, 7);
+
+        let actual: Vec<CandleRecord> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| {
+                let row = row.unwrap();
+                CandleRecord {
+                    provider: row.get_string(0).unwrap().clone(),
+                    symbol: row.get_string(1).unwrap().clone(),
+                    timeframe: row.get_string(2).unwrap().clone(),
+                    open_time: row.get_timestamp_millis(3).unwrap(),
+                    open: row.get_double(4).unwrap(),
+                    high: row.get_double(5).unwrap(),
+                    low: row.get_double(6).unwrap(),
+                    close: row.get_double(7).unwrap(),
+                    volume: row.get_double(8).unwrap(),
+                    close_time: row.get_timestamp_millis(9).unwrap(),
+                    quote_asset_volume: row.get_double(10).unwrap(),
+                    number_of_trades: row.get_long(11).unwrap(),
+                    taker_buy_base_asset_volume: row.get_double(12).unwrap(),
+                    taker_buy_quote_asset_volume: row.get_double(13).unwrap(),
+                    interpolated: row.get_long(14).unwrap(),
+                    derived_from: row.get_string(15).ok().cloned(),
+                }
+            })
+            .collect();
+
+        assert_eq!(actual, expected);
+        assert_eq!(actual[6].derived_from.as_deref(), Some("1m"));
+    }
+
+    #[test]
+    fn parquet_schema_and_compression() {
+        let db = setup(3);
+        let (_, reader) = parquet_round_trip(&db, "candles_parquet_schema", 0..=i64::MAX);
+        let metadata = reader.metadata();
+
+        let schema = metadata.file_metadata().schema_descr();
+        let physical = |name: &str| {
+            schema
+                .columns()
+                .iter()
+                .find(|c| c.name() == name)
+                .map(|c| c.physical_type())
+        };
+        assert_eq!(schema.num_columns(), 16);
+        assert_eq!(physical("open_time"), Some(PhysicalType::INT64));
+        assert_eq!(physical("close_time"), Some(PhysicalType::INT64));
+        for column in ["open", "high", "low", "close", "volume"] {
+            assert_eq!(physical(column), Some(PhysicalType::DOUBLE), "{}", column);
+        }
+
+        let row_group = metadata.row_group(0);
+        assert_eq!(row_group.num_rows(), 3);
+        assert!(
+            row_group
+                .columns()
+                .iter()
+                .all(|c| c.compression() == Compression::SNAPPY)
+        );
+    }
+
+    #[test]
+    fn monthly_files_round_trip_every_row() {
+        // Une bougie 1d par jour du 2024-01-15 au 2024-03-10
+        let mut db = DatabaseManager::new(":memory:").unwrap();
+        let template = setup(1)
+            .query_candles("binance", "BTCUSDT", "5m", 0..=i64::MAX, None)
+            .unwrap()
+            .remove(0);
+        let candles: Vec<CandleRecord> = (14..70)
+            .map(|day| CandleRecord {
+                timeframe: "1d".to_string(),
+                open_time: BASE_TIME + day * DAY,
+                close_time: BASE_TIME + (day + 1) * DAY - 1,
+                close: 100.0 + day as f64,
+                interpolated: (day % 7 == 0) as i64,
+                ..template.clone()
+            })
+            .collect();
+        db.insert_candles(&candles).unwrap();
+
+        let months = month_ranges(candles[0].open_time..=candles[candles.len() - 1].open_time);
+        let (mut exported, mut counts) = (Vec::new(), Vec::new());
+        for (month, range) in &months {
+            let path = std::env::temp_dir().join(format!(
+                "candles_split_{}_{}.parquet",
+                month,
+                std::process::id()
+            ));
+            let rows = write_parquet(
+                db.connection(),
+                "binance",
+                "BTCUSDT",
+                "1d",
+                range.clone(),
+                std::fs::File::create(&path).unwrap(),
+            )
+            .unwrap();
+
+            let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+            let _ = std::fs::remove_file(&path);
+            let open_times: Vec<i64> = reader
+                .get_row_iter(None)
+                .unwrap()
+                .map(|row| row.unwrap().get_timestamp_millis(3).unwrap())
+                .collect();
+            let expected: Vec<i64> = db
+                .query_candles("binance", "BTCUSDT", "1d", range.clone(), None)
+                .unwrap()
+                .iter()
+                .map(|c| c.open_time)
+                .collect();
+            assert_eq!(open_times, expected, "{}", month);
+            assert_eq!(rows, expected.len());
+            exported.extend(open_times);
+            counts.push(rows);
+        }
+
+        // 17 + 29 + 10 jours, sans doublon ni perte entre fichiers
+        assert_eq!(counts, [17, 29, 10]);
+        let all: Vec<i64> = candles.iter().map(|c| c.open_time).collect();
+        assert_eq!(exported, all);
+    }
+}
@@ -0,0 +1,81 @@
+"""Valeurs de référence des golden files linear_fill_*.csv (tests/linear_fill_golden.rs)
+
+Implémentation indépendante de FillStrategy::Linear: pour un trou de n bougies
+entre A (position a) et B (position b = a + n + 1), la bougie j (1..n) vaut
+A + (B - A) * j / (n + 1), champ par champ.
+
+Colonnes: position, real (1: bougie insérée, 0: valeur attendue après
+comblement), open, high, low, close, volume
+
+Usage: python3 tests/fixtures/generate_linear_fill.py
+"""
+import os
+import random
+
+FIELDS = ("open", "high", "low", "close", "volume")
+HERE = os.path.dirname(os.path.abspath(__file__))
+
+
+def real_candle(close, previous_close, volume):
+    open_ = previous_close
+    return {
+        "open": open_,
+        "high": round(max(open_, close) + 2.5, 2),
+        "low": round(min(open_, close) - 2.5, 2),
+        "close": close,
+        "volume": volume,
+    }
+
+
+def fill(count, candles):
+    """candles: {position: bougie réelle} -> liste complète (position, real, bougie)"""
+    rows = []
+    positions = sorted(candles)
+    for a, b in zip(positions, positions[1:]):
+        rows.append((a, 1, candles[a]))
+        for j in range(1, b - a):
+            ratio = j / (b - a)
+            rows.append((a + j, 0, {f: candles[a][f] + (candles[b][f] - candles[a][f]) * ratio for f in FIELDS}))
+    rows.append((positions[-1], 1, candles[positions[-1]]))
+    assert len(rows) == count
+    return rows
+
+
+def write(name, rows):
+    with open(os.path.join(HERE, name), "w") as out:
+        out.write("position,real," + ",".join(FIELDS) + "\n")
+        for position, real, c in rows:
+            out.write("%d,%d,%s\n" % (position, real, ",".join("%.10f" % c[f] for f in FIELDS)))
+
+
+def series(count, gaps, closes, volumes):
+    candles = {}
+    for position in range(count):
+        if position in gaps:
+            continue
+        previous = closes[position - 1] if position > 0 else closes[0]
+        candles[position] = real_candle(closes[position], previous, volumes[position])
+    return fill(count, candles)
+
+
+def main():
+    rng = random.Random(42)
+
+    # Marche aléatoire de 100 bougies, trous de 5, 1, 20 et 8 bougies
+    closes, price = [], 42000.0
+    for _ in range(100):
+        price = round(price * (1 + rng.uniform(-0.01, 0.01)), 2)
+        closes.append(price)
+    volumes = [round(rng.uniform(10, 500), 3) for _ in range(100)]
+    gaps = set(range(10, 15)) | {30} | set(range(50, 70)) | set(range(90, 98))
+    write("linear_fill_random_walk.csv", series(100, gaps, closes, volumes))
+
+    # Cas limites: 20 bougies, trou de 10 (positions 5 à 14)
+    gaps = set(range(5, 15))
+    write("linear_fill_flat.csv", series(20, gaps, [100.0] * 20, [50.0] * 20))
+    write("linear_fill_increasing.csv", series(20, gaps, [100.0 + 10 * p for p in range(20)], [50.0] * 20))
+    write("linear_fill_decreasing.csv", series(20, gaps, [300.0 - 10 * p for p in range(20)], [50.0] * 20))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,449 @@
+/// Tests d'intégration du GapFiller sur une base en mémoire
+///
+/// - Scénario de test_gap_fill (3 groupes, 2 gaps) avec valeurs vérifiées
+/// - gap_report: bornes incluses des gaps, total manquant, couverture,
+///   rapport vide après comblement, bougies manquantes après la dernière
+///   bougie stockée, plage sans bougie
+/// - Propriétés (proptest) sur des motifs de gaps générés aléatoirement
+/// - Propriétés (proptest) de l'interpolation entre deux bougies générées:
+///   bornes, milieu d'un gap d'une bougie, monotonie, cohérence OHLC
+use proptest::prelude::*;
+use rusqlite::{Connection, params};
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::gap_filler::{FillStrategy, GapFiller, GapRange, GapReportEntry};
+
+const BASE_TIME: i64 = 1700000000000;
+const INTERVAL: i64 = 300_000; // 5 minutes
+
+/// Bougie lue depuis la base pour les assertions
+#[derive(Debug, Clone, Copy)]
+struct Row {
+    open_time: i64,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+    close_time: i64,
+    number_of_trades: i64,
+    interpolated: i64,
+}
+
+/// Bougie réelle à insérer: (index sur la grille, close, volume, trades)
+type Anchor = (i64, f64, f64, i64);
+
+fn setup(anchors: &[Anchor]) -> DatabaseManager {
+    let db = DatabaseManager::new(":memory:").expect("base en mémoire");
+    for (index, close, volume, trades) in anchors {
+        let open_time = BASE_TIME + index * INTERVAL;
+        db.connection()
+            .execute(
+                "INSERT INTO candlesticks (
+                    provider, symbol, timeframe, open_time, open, high, low, close, volume,
+                    close_time, quote_asset_volume, number_of_trades,
+                    taker_buy_base_asset_volume, taker_buy_quote_asset_volume, interpolated
+                ) VALUES ('test_provider', 'TEST', '5m', ?1, ?2, ?3, ?4, ?5, ?6, ?7,
+                          ?8, ?9, ?10, ?11, 0)",
+                params![
+                    open_time,
+                    close - 2.0,
+                    close + 3.0,
+                    close - 5.0,
+                    close,
+                    volume,
+                    open_time + INTERVAL - 1,
+                    volume * 100.0,
+                    trades,
+                    volume * 0.6,
+                    volume * 60.0
+                ],
+            )
+            .expect("insertion");
+    }
+    db
+}
+
+fn fill(db: &mut DatabaseManager, last_index: i64, strategy: FillStrategy) -> i64 {
+    GapFiller::fill_gaps_in_range(
+        db.connection_mut(),
+        "test_provider",
+        "TEST",
+        "5m",
+        BASE_TIME,
+        BASE_TIME + last_index * INTERVAL,
+        strategy,
+    )
+    .expect("comblement")
+}
+
+fn gaps(conn: &Connection, last_index: i64) -> Vec<GapRange> {
+    GapFiller::list_gaps_in_range(
+        conn,
+        "test_provider",
+        "TEST",
+        "5m",
+        BASE_TIME,
+        BASE_TIME + last_index * INTERVAL,
+    )
+    .expect("liste des gaps")
+}
+
+fn read_rows(conn: &Connection) -> Vec<Row> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT open_time, open, high, low, close, volume, close_time,
+                    number_of_trades, interpolated
+             FROM candlesticks ORDER BY open_time ASC",
+        )
+        .expect("requête");
+    stmt.query_map([], |row| {
+        Ok(Row {
+            open_time: row.get(0)?,
+            open: row.get(1)?,
+            high: row.get(2)?,
+            low: row.get(3)?,
+            close: row.get(4)?,
+            volume: row.get(5)?,
+            close_time: row.get(6)?,
+            number_of_trades: row.get(7)?,
+            interpolated: row.get(8)?,
+        })
+    })
+    .expect("lecture")
+    .collect::<Result<Vec<_>, _>>()
+    .expect("lignes")
+}
+
+/// true si `value` est entre a et b (bornes incluses, quel que soit l'ordre)
+fn between(value: f64, a: f64, b: f64) -> bool {
+    let eps = 1e-9 * (1.0 + a.abs().max(b.abs()));
+    value >= a.min(b) - eps && value <= a.max(b) + eps
+}
+
+#[test]
+fn scenario_three_groups_two_gaps() {
+    // Groupes 0-4, 10-12, 16-18: gaps de 5 et 3 bougies
+    let anchors: Vec<Anchor> = [0, 1, 2, 3, 4, 10, 11, 12, 16, 17, 18]
+        .iter()
+        .map(|&i| (i, 100.0 + 2.0 * i as f64, 1000.0 + 100.0 * i as f64, 10 * i))
+        .collect();
+    let mut db = setup(&anchors);
+
+    assert_eq!(gaps(db.connection(), 18).len(), 2);
+    assert_eq!(fill(&mut db, 18, FillStrategy::Linear), 8);
+    assert!(gaps(db.connection(), 18).is_empty());
+
+    let rows = read_rows(db.connection());
+    assert_eq!(rows.len(), 19);
+
+    // Bougie 5: premier sixième entre 4 (close 108) et 10 (close 120)
+    let row = rows[5];
+    assert_eq!(row.open_time, BASE_TIME + 5 * INTERVAL);
+    assert_eq!(row.close_time, row.open_time + INTERVAL - 1);
+    assert_eq!(row.interpolated, FillStrategy::Linear.marker());
+    assert!((row.close - (108.0 + 12.0 / 6.0)).abs() < 1e-9);
+    assert!((row.volume - (1400.0 + 600.0 / 6.0)).abs() < 1e-9);
+    // Trades: 40 + (100 - 40) / 6 = 50
+    assert_eq!(row.number_of_trades, 50);
+
+    // Gap 13-15: milieu exact pour la bougie 14
+    let row = rows[14];
+    assert!((row.close - (124.0 + 132.0) / 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn gap_report_lists_missing_candles() {
+    // Bougies 0-2, 5, 6 et 10: gaps 3-4 et 7-9
+    let mut db = setup(&[
+        (0, 100.0, 10.0, 5),
+        (1, 101.0, 10.0, 5),
+        (2, 102.0, 10.0, 5),
+        (5, 105.0, 10.0, 5),
+        (6, 106.0, 10.0, 5),
+        (10, 110.0, 10.0, 5),
+    ]);
+    let report = |db: &DatabaseManager| {
+        GapFiller::gap_report(
+            db.connection(),
+            "test_provider",
+            "TEST",
+            "5m",
+            BASE_TIME,
+            BASE_TIME + 10 * INTERVAL,
+        )
+        .expect("rapport")
+    };
+
+    let before = report(&db);
+    assert_eq!(
+        before.gaps,
+        vec![
+            GapReportEntry {
+                start_time: BASE_TIME + 3 * INTERVAL,
+                end_time: BASE_TIME + 4 * INTERVAL,
+                missing_count: 2,
+                timeframe: "5m".to_string(),
+            },
+            GapReportEntry {
+                start_time: BASE_TIME + 7 * INTERVAL,
+                end_time: BASE_TIME + 9 * INTERVAL,
+                missing_count: 3,
+                timeframe: "5m".to_string(),
+            },
+        ]
+    );
+    assert_eq!(before.total_missing, 5);
+    assert!((before.coverage_pct - 600.0 / 11.0).abs() < 1e-9);
+
+    assert_eq!(fill(&mut db, 10, FillStrategy::Linear), 5);
+    let after = report(&db);
+    assert!(after.gaps.is_empty());
+    assert_eq!(after.total_missing, 0);
+    assert_eq!(after.coverage_pct, 100.0);
+
+    // Plage sans bougie: un seul gap couvrant toute la plage
+    let empty = GapFiller::gap_report(
+        db.connection(),
+        "test_provider",
+        "TEST",
+        "5m",
+        BASE_TIME - 4 * INTERVAL,
+        BASE_TIME - INTERVAL,
+    )
+    .expect("rapport");
+    assert_eq!(empty.total_missing, 4);
+    assert_eq!(empty.gaps.len(), 1);
+    assert_eq!(empty.coverage_pct, 0.0);
+}
+
+#[test]
+fn gap_report_counts_candles_missing_after_the_data() {
+    // Données jusqu'à la bougie 5, plage demandée jusqu'à la bougie 9
+    let db = setup(&[
+        (0, 100.0, 10.0, 5),
+        (1, 101.0, 10.0, 5),
+        (3, 103.0, 10.0, 5),
+        (4, 104.0, 10.0, 5),
+        (5, 105.0, 10.0, 5),
+    ]);
+    let report = GapFiller::gap_report(
+        db.connection(),
+        "test_provider",
+        "TEST",
+        "5m",
+        BASE_TIME,
+        BASE_TIME + 9 * INTERVAL,
+    )
+    .expect("rapport");
+
+    assert_eq!(
+        report.gaps,
+        vec![
+            GapReportEntry {
+                start_time: BASE_TIME + 2 * INTERVAL,
+                end_time: BASE_TIME + 2 * INTERVAL,
+                missing_count: 1,
+                timeframe: "5m".to_string(),
+            },
+            GapReportEntry {
+                start_time: BASE_TIME + 6 * INTERVAL,
+                end_time: BASE_TIME + 9 * INTERVAL,
+                missing_count: 4,
+                timeframe: "5m".to_string(),
+            },
+        ]
+    );
+    assert_eq!(report.total_missing, 5);
+    assert_eq!(report.coverage_pct, 50.0);
+}
+
+#[test]
+fn strategy_none_leaves_gaps() {
+    let mut db = setup(&[(0, 100.0, 1.0, 1), (4, 110.0, 1.0, 1)]);
+    assert_eq!(fill(&mut db, 4, FillStrategy::None), 0);
+    assert_eq!(gaps(db.connection(), 4).len(), 1);
+}
+
+/// Motif de présence: première et dernière bougies toujours présentes
+fn anchors_strategy() -> impl Strategy<Value = Vec<Anchor>> {
+    (3usize..80)
+        .prop_flat_map(|len| {
+            (
+                prop::collection::vec(any::<bool>(), len - 2),
+                prop::collection::vec((1.0f64..100_000.0, 0.0f64..1e6, 0i64..100_000), len),
+            )
+        })
+        .prop_map(|(mask, values)| {
+            let len = values.len();
+            values
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| *i == 0 || *i == len - 1 || mask[i - 1])
+                .map(|(i, (close, volume, trades))| (i as i64, close, volume, trades))
+                .collect()
+        })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn linear_fill_invariants(anchors in anchors_strategy()) {
+        let last_index = anchors[anchors.len() - 1].0;
+        let mut db = setup(&anchors);
+
+        let before = gaps(db.connection(), last_index);
+        let expected: i64 = before.iter().map(|g| g.missing_candles).sum();
+
+        // Autant de lignes insérées que de bougies manquantes
+        let filled = fill(&mut db, last_index, FillStrategy::Linear);
+        prop_assert_eq!(filled, expected);
+        prop_assert!(gaps(db.connection(), last_index).is_empty());
+
+        let rows = read_rows(db.connection());
+        prop_assert_eq!(rows.len() as i64, last_index + 1);
+
+        for gap in &before {
+            let a = rows.iter().find(|r| r.open_time == gap.after_open_time).unwrap();
+            let b = rows.iter().find(|r| r.open_time == gap.before_open_time).unwrap();
+            let synthetic: Vec<&Row> = rows
+                .iter()
+                .filter(|r| r.open_time > a.open_time && r.open_time < b.open_time)
+                .collect();
+            prop_assert_eq!(synthetic.len() as i64, gap.missing_candles);
+
+            let mut previous = a;
+            for row in synthetic {
+                // Strictement entre les bornes, aligné sur la grille
+                prop_assert!(row.open_time > a.open_time && row.open_time < b.open_time);
+                prop_assert_eq!((row.open_time - BASE_TIME) % INTERVAL, 0);
+                prop_assert_eq!(row.close_time, row.open_time + INTERVAL - 1);
+                prop_assert_eq!(row.interpolated, FillStrategy::Linear.marker());
+
+                // Valeurs entre les ancres et monotones de a vers b
+                for (value, prev, from, to) in [
+                    (row.open, previous.open, a.open, b.open),
+                    (row.high, previous.high, a.high, b.high),
+                    (row.low, previous.low, a.low, b.low),
+                    (row.close, previous.close, a.close, b.close),
+                    (row.volume, previous.volume, a.volume, b.volume),
+                ] {
+                    prop_assert!(between(value, from, to));
+                    prop_assert!(between(value, prev, to));
+                }
+                prop_assert!(
+                    row.number_of_trades >= a.number_of_trades.min(b.number_of_trades)
+                        && row.number_of_trades <= a.number_of_trades.max(b.number_of_trades)
+                );
+
+                previous = row;
+            }
+        }
+    }
+}
+
+/// Bougie de bornage générée: (open, high, low, close, volume), OHLC cohérent
+type Boundary = (f64, f64, f64, f64, f64);
+
+/// low ≤ open, close ≤ high, prix strictement positifs
+fn boundary_strategy() -> impl Strategy<Value = Boundary> {
+    (
+        1.0f64..100_000.0,
+        1.0f64..100_000.0,
+        0.0f64..1.0,
+        0.0f64..1.0,
+        0.0f64..1e6,
+    )
+        .prop_map(|(open, close, wick_up, wick_down, volume)| {
+            let high = open.max(close) * (1.0 + wick_up * 0.1);
+            let low = open.min(close) * (1.0 - wick_down * 0.1);
+            (open, high, low, close, volume)
+        })
+}
+
+/// Base avec deux bougies réelles, aux index 0 et gap_size + 1
+fn setup_boundaries(a: Boundary, b: Boundary, gap_size: i64) -> DatabaseManager {
+    let db = DatabaseManager::new(":memory:").expect("base en mémoire");
+    for (index, (open, high, low, close, volume)) in [(0, a), (gap_size + 1, b)] {
+        let open_time = BASE_TIME + index * INTERVAL;
+        db.connection()
+            .execute(
+                "INSERT INTO candlesticks (
+                    provider, symbol, timeframe, open_time, open, high, low, close, volume,
+                    close_time, quote_asset_volume, number_of_trades,
+                    taker_buy_base_asset_volume, taker_buy_quote_asset_volume, interpolated
+                ) VALUES ('test_provider', 'TEST', '5m', ?1, ?2, ?3, ?4, ?5, ?6, ?7,
+                          0, 0, 0, 0, 0)",
+                params![
+                    open_time,
+                    open,
+                    high,
+                    low,
+                    close,
+                    volume,
+                    open_time + INTERVAL - 1
+                ],
+            )
+            .expect("insertion");
+    }
+    db
+}
+
+/// Champs comparés d'une ligne, dans l'ordre de Boundary
+fn fields(row: &Row) -> [f64; 5] {
+    [row.open, row.high, row.low, row.close, row.volume]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(128))]
+
+    #[test]
+    fn interpolation_between_two_boundaries(
+        a in boundary_strategy(),
+        b in boundary_strategy(),
+        gap_size in 1i64..50,
+    ) {
+        let mut db = setup_boundaries(a, b, gap_size);
+        prop_assert_eq!(fill(&mut db, gap_size + 1, FillStrategy::Linear), gap_size);
+
+        let rows = read_rows(db.connection());
+        let (first, last) = (rows[0], rows[rows.len() - 1]);
+        let synthetic = &rows[1..rows.len() - 1];
+        prop_assert_eq!(synthetic.len() as i64, gap_size);
+
+        // Propriété 1: open_time strictement entre les bornes
+        for row in synthetic {
+            prop_assert!(row.open_time > first.open_time && row.open_time < last.open_time);
+        }
+
+        // Propriété 2: une seule bougie manquante = milieu exact
+        if gap_size == 1 {
+            for (value, (from, to)) in fields(&synthetic[0])
+                .into_iter()
+                .zip(fields(&first).into_iter().zip(fields(&last)))
+            {
+                let midpoint = (from + to) / 2.0;
+                prop_assert!((value - midpoint).abs() <= 1e-9 * (1.0 + midpoint.abs()));
+            }
+        }
+
+        // Propriété 3: chaque champ varie de façon monotone de a vers b
+        let mut previous = first;
+        for row in synthetic.iter().chain([&last]) {
+            for ((value, prev), to) in fields(row)
+                .into_iter()
+                .zip(fields(&previous))
+                .zip(fields(&last))
+            {
+                prop_assert!(between(value, prev, to));
+            }
+            previous = *row;
+        }
+
+        // Propriété 4: OHLC cohérent, sans tolérance (high/low élargis)
+        for row in synthetic {
+            prop_assert!(row.low <= row.open && row.low <= row.close);
+            prop_assert!(row.open <= row.high && row.close <= row.high);
+        }
+    }
+}
@@ -0,0 +1,254 @@
+/// Tests d'intégration de l'import CSV sur une base en mémoire
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::error::CandleError;
+use rust_candles_retriever::export::{ExportFormat, write_csv};
+use rust_candles_retriever::import::{
+    ColumnMapping, IMPORT_CHUNK_ROWS, IMPORT_PROVIDER, InvalidRow, detect_delimiter, import_csv,
+    import_csv_lenient, parse_csv,
+};
+
+const BASE_TIME: i64 = 1704067200000; // 2024-01-01 00:00 UTC
+
+fn import(db: &mut DatabaseManager, content: &str) -> rust_candles_retriever::error::Result<usize> {
+    import_csv(
+        db.connection_mut(),
+        content,
+        IMPORT_PROVIDER,
+        "BTCUSDT",
+        "5m",
+    )
+    .map(|summary| summary.inserted)
+}
+
+fn stored(db: &DatabaseManager) -> usize {
+    db.query_candles(IMPORT_PROVIDER, "BTCUSDT", "5m", 0..=i64::MAX, None)
+        .unwrap()
+        .len()
+}
+
+#[test]
+fn comma_file_with_header() {
+    let content = "timestamp_ms,open,high,low,close,volume\n\
+                   1704067200000,100,110,90,105.5,12\n\
+                   1704067500000,105.5,112,101,111,8\n";
+    let candles = parse_csv(content, IMPORT_PROVIDER, "BTCUSDT", "5m").unwrap();
+
+    assert_eq!(candles.len(), 2);
+    assert_eq!(candles[0].open_time, BASE_TIME);
+    assert_eq!(candles[0].close_time, BASE_TIME + 300_000 - 1);
+    assert_eq!(candles[1].close, 111.0);
+    assert!(candles.iter().all(|c| c.interpolated == 0));
+    assert!(candles.iter().all(|c| c.provider == "import"));
+}
+
+#[test]
+fn semicolon_file_without_header_and_decimal_comma() {
+    let content = "1704067200000;42000,5;42100;41900,25;42050;3,5\n";
+    assert_eq!(detect_delimiter(content), ';');
+
+    let candles = parse_csv(content, IMPORT_PROVIDER, "BTCUSDT", "5m").unwrap();
+    assert_eq!(candles[0].open, 42000.5);
+    assert_eq!(candles[0].low, 41900.25);
+    assert_eq!(candles[0].volume, 3.5);
+}
+
+#[test]
+fn gaps_are_accepted_when_aligned_on_interval() {
+    let content = "1704067200000,1,1,1,1,1\n1704068100000,1,1,1,1,1\n";
+    assert_eq!(
+        parse_csv(content, IMPORT_PROVIDER, "BTCUSDT", "5m")
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn invalid_files_insert_nothing() {
+    let mut db = DatabaseManager::new(":memory:").unwrap();
+
+    let not_increasing = "1704067500000,1,1,1,1,1\n1704067200000,1,1,1,1,1\n";
+    let wrong_interval = "1704067200000,1,1,1,1,1\n1704067260000,1,1,1,1,1\n";
+    let bad_number = "1704067200000,1,1,1,1,1\n1704067500000,1,abc,1,1,1\n";
+    let high_below_low = "1704067200000,1,1,1,1,1\n1704067500000,1,1,2,1,1\n";
+    let off_grid = "1704067260000,1,1,1,1,1\n";
+
+    for content in [not_increasing, wrong_interval, high_below_low, off_grid] {
+        assert!(matches!(
+            import(&mut db, content),
+            Err(CandleError::ValidationError(_))
+        ));
+    }
+    assert!(matches!(
+        import(&mut db, bad_number),
+        Err(CandleError::ParseError(_))
+    ));
+    assert_eq!(stored(&db), 0);
+}
+
+#[test]
+fn unknown_timeframe_is_rejected() {
+    let result = parse_csv(
+        "1704067200000,1,1,1,1,1\n",
+        IMPORT_PROVIDER,
+        "BTCUSDT",
+        "7m",
+    );
+    assert!(matches!(result, Err(CandleError::ValidationError(_))));
+}
+
+#[test]
+fn reimport_ignores_duplicates() {
+    let mut db = DatabaseManager::new(":memory:").unwrap();
+    let content = "1704067200000,1,1,1,1,1\n1704067500000,1,1,1,1,1\n";
+
+    assert_eq!(import(&mut db, content).unwrap(), 2);
+    assert_eq!(import(&mut db, content).unwrap(), 0);
+    assert_eq!(stored(&db), 2);
+}
+
+#[test]
+fn standard_export_can_be_reimported() {
+    let mut db = DatabaseManager::new(":memory:").unwrap();
+    import(
+        &mut db,
+        "1704067200000,100,110,90,105.5,12\n1704067500000,105.5,112,101,111,8\n",
+    )
+    .unwrap();
+
+    let mut exported = String::new();
+    write_csv(
+        db.connection(),
+        IMPORT_PROVIDER,
+        "BTCUSDT",
+        "5m",
+        0..=i64::MAX,
+        ExportFormat::Standard,
+        |chunk| {
+            exported.push_str(&chunk);
+            true
+        },
+    )
+    .unwrap();
+
+    let candles = parse_csv(&exported, IMPORT_PROVIDER, "BTCUSDT", "5m").unwrap();
+    assert_eq!(
+        candles,
+        db.query_candles(IMPORT_PROVIDER, "BTCUSDT", "5m", 0..=i64::MAX, None)
+            .unwrap()
+    );
+}
+
+#[test]
+fn lenient_import_reports_invalid_rows_with_line_numbers() {
+    let mut db = DatabaseManager::new(":memory:").unwrap();
+    let content = "timestamp_ms,open,high,low,close,volume\n\
+                   1704067200000,1,2,0.5,1.5,10\n\
+                   1704067500000,1,0.4,0.5,1.5,10\n\
+                   1704067560000,1,2,0.5,1.5,10\n\
+                   \n\
+                   1704068100000,1,2,0.5,1.5,-1\n\
+                   1704067200000,1,2,0.5,1.5,1\n\
+                   1704068400000,1,2,0.5,1.5,3\n\
+                   1704068700000,1,2\n";
+
+    let report = import_csv_lenient(
+        db.connection_mut(),
+        content,
+        IMPORT_PROVIDER,
+        "BTCUSDT",
+        "5m",
+        &ColumnMapping::default(),
+    )
+    .unwrap();
+
+    let lines: Vec<usize> = report.invalid.iter().map(|row| row.line).collect();
+    assert_eq!(lines, [3, 4, 6, 7, 9]);
+    assert_eq!(
+        report.invalid[0],
+        InvalidRow {
+            line: 3,
+            reason: "high 0.4 < low 0.5".to_string()
+        }
+    );
+    assert_eq!((report.summary.parsed, report.summary.inserted), (2, 2));
+    assert_eq!(report.range, Some((BASE_TIME, BASE_TIME + 4 * 300_000)));
+    assert_eq!(stored(&db), 2);
+
+    // Réimport: bougies valides ignorées, lignes invalides toujours rapportées
+    let again = import_csv_lenient(
+        db.connection_mut(),
+        content,
+        IMPORT_PROVIDER,
+        "BTCUSDT",
+        "5m",
+        &ColumnMapping::default(),
+    )
+    .unwrap();
+    assert_eq!((again.summary.inserted, again.skipped()), (0, 2));
+    assert_eq!(again.invalid.len(), 5);
+}
+
+#[test]
+fn column_mapping_renames_header_columns() {
+    let mut db = DatabaseManager::new(":memory:").unwrap();
+    let mapping: ColumnMapping = "timestamp=Date, volume=Vol BTC".parse().unwrap();
+    let content = "Date;Open;High;Low;Close;Vol BTC\n1704067200000;1;2;0,5;1,5;3,25\n";
+
+    let report = import_csv_lenient(
+        db.connection_mut(),
+        content,
+        IMPORT_PROVIDER,
+        "BTCUSDT",
+        "5m",
+        &mapping,
+    )
+    .unwrap();
+    assert!(report.invalid.is_empty());
+    let candle = db
+        .query_candles(IMPORT_PROVIDER, "BTCUSDT", "5m", 0..=i64::MAX, None)
+        .unwrap()
+        .remove(0);
+    assert_eq!((candle.low, candle.volume), (0.5, 3.25));
+
+    // Mapping sans en-tête, colonne inconnue, syntaxe invalide
+    assert!(matches!(
+        import_csv_lenient(
+            db.connection_mut(),
+            "1704067200000,1,2,0.5,1.5,3\n",
+            IMPORT_PROVIDER,
+            "BTCUSDT",
+            "5m",
+            &mapping
+        ),
+        Err(CandleError::ValidationError(_))
+    ));
+    for invalid in ["date=Date", "timestamp"] {
+        assert!(matches!(
+            invalid.parse::<ColumnMapping>(),
+            Err(CandleError::ParseError(_))
+        ));
+    }
+}
+
+#[test]
+fn lenient_import_inserts_in_chunks() {
+    let mut db = DatabaseManager::new(":memory:").unwrap();
+    let count = IMPORT_CHUNK_ROWS as i64 * 2 + 7;
+    let content: String = (0..count)
+        .map(|i| format!("{},1,2,0.5,1.5,3\n", BASE_TIME + i * 300_000))
+        .collect();
+
+    let report = import_csv_lenient(
+        db.connection_mut(),
+        &content,
+        IMPORT_PROVIDER,
+        "BTCUSDT",
+        "5m",
+        &ColumnMapping::default(),
+    )
+    .unwrap();
+
+    assert_eq!(report.summary.inserted as i64, count);
+    assert_eq!(stored(&db) as i64, count);
+}
@@ -0,0 +1,1960 @@
+/// Tests des indicateurs (indicators::adx, indicators::atr,
+/// indicators::bollinger,
+/// indicators::macd,
+/// indicators::moving_average,
+/// indicators::obv, indicators::rsi,
+/// indicators::parabolic_sar, indicators::pivot_points,
+/// indicators::rolling, indicators::stochastic, indicators::volatility,
+/// indicators::vwap)
+///
+/// - calculate_obv: hausse, baisse, égalité, première bougie à 0
+/// - store_obv: calcul depuis la première bougie même pour une plage
+///   restreinte, valeurs remplacées au recalcul
+/// - recalculate_obv_for_range: reprise depuis l'OBV enregistré de la
+///   bougie qui précède la plage, calcul complet sans valeur enregistrée
+/// - Indicator: indicateur défini dans le test, chauffe non enregistrée,
+///   deux réglages distincts dans indicator_values
+/// - Volatilité historique et de Parkinson: valeurs calculées à la main,
+///   période de chauffe, annualisation
+/// - volatility_series: fenêtre complétée par les bougies avant la plage
+/// - Stochastique: %K / %D calculés à la main, fenêtre sans amplitude
+///   (plus haut = plus bas), plage restreinte et enregistrement; %K lissé
+///   calculé à la main, lissage 1 identique au stochastique rapide, k et d
+///   enregistrés dans indicator_values puis relus
+/// - Points pivots: R3 > R2 > R1 > PP > S1 > S2 > S3 pour une bougie
+///   ordinaire, regroupement des bougies 1h par jour, période précédant la
+///   plage, période pivot invalide
+/// - Statistiques glissantes: valeurs calculées à la main, minimum et
+///   maximum comparés au calcul naïf, plage restreinte
+/// - ADX: période de chauffe, tendance haussière nette (ADX > 25, -DI nul),
+///   prix figés, plage restreinte calculée depuis la première bougie et
+///   enregistrement
+/// - SMA / EMA: valeurs calculées à la main, EMA de référence (exemple
+///   StockCharts, 10 périodes), série plus courte que la période,
+///   enregistrement dans indicator_values et indicator_by_name
+/// - MACD: périodes de chauffe, comparaison avec une implémentation de
+///   référence, trois composantes enregistrées, plage restreinte avec
+///   bougies de chauffe, périodes invalides
+/// - ATR: TR et ATR calculés à la main avec un gap haussier, bougies
+///   interpolées exclues, plage restreinte avec bougies de chauffe
+/// - Bandes de Bollinger: valeurs calculées à la main, comparaison avec
+///   une implémentation de référence sur des prix élevés, prix figés
+///   (bandes confondues), série plus courte que la période,
+///   enregistrement d'une plage restreinte, paramètres invalides
+/// - RSI: valeurs calculées à la main, prix figés, RsiState incrémental
+///   identique au calcul complet
+/// - IndicatorRegistry: batch de 1000 bougies au milieu d'une longue série
+///   (RSI + EMA), bougies suivantes recalculées sur la seule chauffe, durée
+///   bornée, valeurs identiques au calcul complet; spécifications invalides
+/// - IndicatorRegistry (OBV): un lot temps réel reprend la dernière valeur
+///   enregistrée, un batch du backfill recalcule depuis la première bougie
+/// - VWAP: remise à zéro à minuit UTC, bougie sans volume, plage
+///   calculée depuis le début de session et enregistrement, session
+///   invalide
+/// - Parabolic SAR: première bougie sans SAR, SAR sous le prix en phase
+///   haussière, bascule au-dessus au retournement, facteurs invalides,
+///   enregistrement
+mod testutil;
+
+use rusqlite::params;
+use rust_candles_retriever::indicators::adx::{AdxPoint, adx_series, calculate_adx, store_adx};
+use rust_candles_retriever::indicators::atr::{
+    Atr, calculate_atr, calculate_true_range, recalculate_atr_for_range,
+};
+use rust_candles_retriever::indicators::bollinger::{
+    Bollinger, calculate_bollinger, recalculate_bollinger_for_range,
+};
+use rust_candles_retriever::indicators::macd::{
+    Macd, MacdPoint, calculate_macd, recalculate_macd_for_range,
+};
+use rust_candles_retriever::indicators::moving_average::{
+    EMA_WARMUP_FACTOR, Ema, ema, recalculate_ema_for_range, recalculate_sma_for_range, sma,
+};
+use rust_candles_retriever::indicators::obv::{
+    ObvValue, calculate_obv, obv_series, recalculate_obv_for_range, store_obv,
+};
+use rust_candles_retriever::indicators::parabolic_sar::{
+    DEFAULT_INITIAL_AF, DEFAULT_MAX_AF, DEFAULT_STEP_AF, calculate_sar, sar_series, store_sar,
+};
+use rust_candles_retriever::indicators::pivot_points::{
+    DailyCandle, PivotPoint, calculate_pivot_points, pivot_point_series, store_pivot_points,
+};
+use rust_candles_retriever::indicators::registry::{IndicatorRegistry, IndicatorSpec};
+use rust_candles_retriever::indicators::rolling::{
+    RollingKind, rolling_max, rolling_mean, rolling_min, rolling_series, rolling_std,
+};
+use rust_candles_retriever::indicators::rsi::{
+    FLAT_RSI, RSI_WARMUP_FACTOR, Rsi, RsiState, calculate_rsi,
+};
+use rust_candles_retriever::indicators::stochastic::{
+    FLAT_RANGE_K, Stochastic, StochasticPoint, calculate_slow_stochastic, calculate_stochastic,
+    recalculate_stochastic_for_range, slow_stochastic_series, stochastic_series, store_stochastic,
+};
+use rust_candles_retriever::indicators::values::{
+    Indicator, IndicatorValue, component_series, component_values, indicator_by_name,
+    indicator_series, indicator_values, recalculate_indicator_for_range,
+};
+use rust_candles_retriever::indicators::volatility::{
+    VolatilityKind, calculate_historical_volatility, calculate_parkinson_volatility,
+    candles_per_day, store_historical_volatility, volatility_series,
+};
+use rust_candles_retriever::indicators::vwap::{
+    Vwap, calculate_vwap, recalculate_vwap_for_range, vwap_series,
+};
+use rust_candles_retriever::models::CandleRecord;
+use testutil::{BASE_TIME, PROVIDER, candle, memory_db};
+
+const INTERVAL: i64 = 3_600_000; // 1 heure
+
+#[test]
+fn obv_follows_close_direction() {
+    let closes = [10.0, 11.0, 11.0, 9.0, 12.0];
+    let volumes = [5.0, 3.0, 4.0, 2.0, 7.0];
+
+    assert_eq!(
+        calculate_obv(&closes, &volumes),
+        vec![0.0, 3.0, 3.0, 1.0, 8.0]
+    );
+    assert!(calculate_obv(&[], &[]).is_empty());
+    // Longueurs différentes: la plus courte
+    assert_eq!(calculate_obv(&closes, &volumes[..2]).len(), 2);
+}
+
+/// Série 1h: closes et volumes donnés, à partir de BASE_TIME
+fn insert_hourly(
+    db: &mut rust_candles_retriever::database::DatabaseManager,
+    points: &[(f64, f64)],
+) {
+    let candles: Vec<CandleRecord> = points
+        .iter()
+        .enumerate()
+        .map(|(position, &(close, volume))| CandleRecord {
+            volume,
+            ..candle(
+                "BTCUSDT",
+                "1h",
+                BASE_TIME + position as i64 * INTERVAL,
+                close,
+            )
+        })
+        .collect();
+    db.insert_candles(&candles).unwrap();
+}
+
+#[test]
+fn stored_obv_starts_from_the_first_candle() {
+    let mut db = memory_db();
+    insert_hourly(
+        &mut db,
+        &[
+            (100.0, 10.0),
+            (101.0, 20.0),
+            (99.0, 5.0),
+            (99.0, 8.0),
+            (105.0, 1.0),
+        ],
+    );
+
+    // Plage restreinte aux deux dernières bougies: cumul depuis la première
+    let stored = store_obv(
+        db.connection_mut(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        BASE_TIME + 3 * INTERVAL,
+        BASE_TIME + 4 * INTERVAL,
+    )
+    .unwrap();
+    assert_eq!(stored, 2);
+
+    let rows: Vec<(i64, f64)> = db
+        .connection()
+        .prepare("SELECT open_time, value FROM indicator_values ORDER BY open_time")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<rusqlite::Result<_>>()
+        .unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            (BASE_TIME + 3 * INTERVAL, 15.0),
+            (BASE_TIME + 4 * INTERVAL, 16.0)
+        ]
+    );
+
+    // Toute la série, puis recalcul après correction d'une bougie
+    store_obv(db.connection_mut(), PROVIDER, "BTCUSDT", "1h", 0, i64::MAX).unwrap();
+    db.connection()
+        .execute(
+            "UPDATE candlesticks SET volume = 30.0 WHERE open_time = ?1",
+            params![BASE_TIME + INTERVAL],
+        )
+        .unwrap();
+    assert_eq!(
+        store_obv(db.connection_mut(), PROVIDER, "BTCUSDT", "1h", 0, i64::MAX).unwrap(),
+        5
+    );
+    let count: i64 = db
+        .connection()
+        .query_row("SELECT COUNT(*) FROM indicator_values", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(count, 5);
+    assert_eq!(
+        obv_series(db.connection(), PROVIDER, "BTCUSDT", "1h", 0..=i64::MAX)
+            .unwrap()
+            .last(),
+        Some(&ObvValue {
+            open_time: BASE_TIME + 4 * INTERVAL,
+            obv: 26.0
+        })
+    );
+}
+
+#[test]
+fn obv_range_resumes_from_the_stored_value() {
+    let mut db = memory_db();
+    insert_hourly(
+        &mut db,
+        &[
+            (100.0, 10.0),
+            (101.0, 20.0),
+            (99.0, 5.0),
+            (99.0, 8.0),
+            (105.0, 1.0),
+        ],
+    );
+    let stored_obv = |db: &rust_candles_retriever::database::DatabaseManager| -> Vec<(i64, f64)> {
+        db.connection()
+            .prepare("SELECT open_time, value FROM indicator_values ORDER BY open_time")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap()
+    };
+
+    // Sans valeur enregistrée avant la plage: calcul depuis la première bougie
+    assert_eq!(
+        recalculate_obv_for_range(
+            db.connection_mut(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            BASE_TIME + 2 * INTERVAL,
+            BASE_TIME + 2 * INTERVAL,
+        )
+        .unwrap(),
+        1
+    );
+    assert_eq!(stored_obv(&db), vec![(BASE_TIME + 2 * INTERVAL, 15.0)]);
+
+    // Valeur enregistrée décalée: la reprise part d'elle, sans relire le début
+    db.connection()
+        .execute("UPDATE indicator_values SET value = 115.0", [])
+        .unwrap();
+    let stored = recalculate_obv_for_range(
+        db.connection_mut(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        BASE_TIME + 3 * INTERVAL,
+        i64::MAX,
+    )
+    .unwrap();
+    assert_eq!(stored, 2);
+    assert_eq!(
+        stored_obv(&db),
+        vec![
+            (BASE_TIME + 2 * INTERVAL, 115.0),
+            (BASE_TIME + 3 * INTERVAL, 115.0),
+            (BASE_TIME + 4 * INTERVAL, 116.0)
+        ]
+    );
+
+    // Valeurs exactes: reprise identique au calcul complet
+    store_obv(db.connection_mut(), PROVIDER, "BTCUSDT", "1h", 0, i64::MAX).unwrap();
+    recalculate_obv_for_range(
+        db.connection_mut(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        BASE_TIME + 3 * INTERVAL,
+        i64::MAX,
+    )
+    .unwrap();
+    let expected: Vec<(i64, f64)> =
+        obv_series(db.connection(), PROVIDER, "BTCUSDT", "1h", 0..=i64::MAX)
+            .unwrap()
+            .into_iter()
+            .map(|v| (v.open_time, v.obv))
+            .collect();
+    assert_eq!(stored_obv(&db), expected);
+}
+
+const EPSILON: f64 = 1e-12;
+
+#[test]
+fn historical_volatility_of_log_returns() {
+    // Rendements ln(1.1), ln(0.9), ln(1.1): écart-type de deux rendements
+    // |a - b| / sqrt(2)
+    let closes = [100.0, 110.0, 99.0, 108.9];
+    let expected = (1.1f64.ln() - 0.9f64.ln()).abs() / 2f64.sqrt();
+
+    let volatility = calculate_historical_volatility(&closes, 2, false, 24.0);
+    assert_eq!(volatility.len(), 4);
+    assert_eq!(volatility[..2], [None, None]);
+    for value in &volatility[2..] {
+        assert!((value.unwrap() - expected).abs() < EPSILON);
+    }
+
+    // Annualisation 1h: × sqrt(252 × 24)
+    assert_eq!(candles_per_day("1h").unwrap(), 24.0);
+    let annualized = calculate_historical_volatility(&closes, 2, true, 24.0);
+    assert!((annualized[3].unwrap() - expected * (252.0f64 * 24.0).sqrt()).abs() < 1e-9);
+
+    // Prix constants: volatilité nulle; période < 2: aucune valeur
+    assert_eq!(
+        calculate_historical_volatility(&[50.0; 4], 3, false, 24.0)[3],
+        Some(0.0)
+    );
+    assert!(
+        calculate_historical_volatility(&closes, 1, false, 24.0)
+            .iter()
+            .all(Option::is_none)
+    );
+}
+
+#[test]
+fn parkinson_volatility_of_high_low_ranges() {
+    // ln(high / low) = ln(1.1) sur chaque bougie:
+    // sqrt(2 × ln(1.1)² / (4 × 2 × ln 2)) = ln(1.1) / (2 × sqrt(ln 2))
+    let highs = [110.0, 121.0, 121.0];
+    let lows = [100.0, 110.0, 110.0];
+    let expected = 1.1f64.ln() / (2.0 * std::f64::consts::LN_2.sqrt());
+
+    let volatility = calculate_parkinson_volatility(&highs, &lows, 2);
+    assert_eq!(volatility[0], None);
+    for value in &volatility[1..] {
+        assert!((value.unwrap() - expected).abs() < EPSILON);
+    }
+    assert!(
+        calculate_parkinson_volatility(&highs, &lows, 0)
+            .iter()
+            .all(Option::is_none)
+    );
+}
+
+#[test]
+fn restricted_range_uses_candles_before_it() {
+    let mut db = memory_db();
+    let closes = [1000.0, 1020.0, 990.0, 1010.0, 1040.0, 1030.0];
+    insert_hourly(
+        &mut db,
+        &closes.iter().map(|&close| (close, 1.0)).collect::<Vec<_>>(),
+    );
+
+    for kind in [VolatilityKind::Historical, VolatilityKind::Parkinson] {
+        let full = volatility_series(
+            db.connection(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            0..=i64::MAX,
+            kind,
+            3,
+            true,
+        )
+        .unwrap();
+        let last_two = volatility_series(
+            db.connection(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            BASE_TIME + 4 * INTERVAL..=i64::MAX,
+            kind,
+            3,
+            true,
+        )
+        .unwrap();
+        assert_eq!(last_two, full[full.len() - 2..], "{kind:?}");
+    }
+
+    // Historique: pas de valeur pour les 3 premières bougies
+    let stored = store_historical_volatility(
+        db.connection_mut(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        3,
+        false,
+        0,
+        i64::MAX,
+    )
+    .unwrap();
+    assert_eq!(stored, 3);
+    let first: i64 = db
+        .connection()
+        .query_row(
+            "SELECT MIN(open_time) FROM historical_volatility WHERE period = 3 AND annualized = 0",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(first, BASE_TIME + 3 * INTERVAL);
+}
+
+#[test]
+fn stochastic_k_and_d_by_hand() {
+    let open_times = [0, 1, 2, 3, 4];
+    let highs = [10.0, 12.0, 11.0, 13.0, 12.0];
+    let lows = [8.0, 9.0, 7.0, 10.0, 11.0];
+    let closes = [9.0, 11.0, 10.0, 13.0, 11.0];
+
+    let points = calculate_stochastic(&open_times, &highs, &lows, &closes, 3, 2);
+
+    // %K: fenêtres [7, 12], [7, 13], [7, 13]; %D: moyenne de 2 %K
+    let k: Vec<Option<f64>> = points.iter().map(|p| p.k).collect();
+    let d: Vec<Option<f64>> = points.iter().map(|p| p.d).collect();
+    assert_eq!(k[..3], [None, None, Some(60.0)]);
+    assert_eq!(k[3], Some(100.0));
+    assert!((k[4].unwrap() - 400.0 / 6.0).abs() < EPSILON);
+    assert_eq!(d[..3], [None, None, None]);
+    assert_eq!(d[3], Some(80.0));
+    assert!((d[4].unwrap() - (100.0 + 400.0 / 6.0) / 2.0).abs() < EPSILON);
+    assert_eq!(points[4].open_time, 4);
+}
+
+#[test]
+fn flat_window_does_not_divide_by_zero() {
+    let flat = [5.0; 4];
+    let points = calculate_stochastic(&[0, 1, 2, 3], &flat, &flat, &flat, 2, 2);
+
+    assert_eq!(
+        points[3],
+        StochasticPoint {
+            open_time: 3,
+            k: Some(FLAT_RANGE_K),
+            d: Some(FLAT_RANGE_K),
+        }
+    );
+    assert!(points.iter().all(|p| p.k.is_none_or(f64::is_finite)));
+
+    // Période nulle: aucune valeur
+    assert!(
+        calculate_stochastic(&[0, 1], &flat[..2], &flat[..2], &flat[..2], 0, 0)
+            .iter()
+            .all(|p| p.k.is_none() && p.d.is_none())
+    );
+}
+
+#[test]
+fn stochastic_range_and_storage() {
+    let mut db = memory_db();
+    let closes = [1000.0, 1020.0, 990.0, 1010.0, 1040.0, 1030.0, 1005.0];
+    insert_hourly(
+        &mut db,
+        &closes.iter().map(|&close| (close, 1.0)).collect::<Vec<_>>(),
+    );
+
+    let full = stochastic_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        0..=i64::MAX,
+        3,
+        2,
+    )
+    .unwrap();
+    // %K dès la 3e bougie
+    assert_eq!(full.len(), 5);
+    let tail = stochastic_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        BASE_TIME + 5 * INTERVAL..=i64::MAX,
+        3,
+        2,
+    )
+    .unwrap();
+    assert_eq!(tail, full[3..]);
+    assert!(tail.iter().all(|point| point.d.is_some()));
+
+    let stored = store_stochastic(
+        db.connection_mut(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        3,
+        2,
+        0,
+        i64::MAX,
+    )
+    .unwrap();
+    assert_eq!(stored, 9);
+    let with_d: i64 = db
+        .connection()
+        .query_row(
+            "SELECT COUNT(*) FROM indicator_values
+             WHERE indicator = 'stochastic' AND component = 'd'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(with_d, 4);
+}
+
+#[test]
+fn slow_stochastic_smooths_k_by_hand() {
+    let open_times = [0, 1, 2, 3, 4, 5];
+    let highs = [10.0, 12.0, 11.0, 13.0, 12.0, 12.0];
+    let lows = [8.0, 9.0, 7.0, 10.0, 11.0, 11.0];
+    let closes = [9.0, 11.0, 10.0, 13.0, 11.0, 12.0];
+
+    // %K brut: 60, 100, 400/6, puis fenêtre [10, 13] → 200/3
+    let points = calculate_slow_stochastic(&open_times, &highs, &lows, &closes, 3, 2, 2);
+    let k: Vec<Option<f64>> = points.iter().map(|p| p.k).collect();
+    assert_eq!(k[..3], [None, None, None]);
+    assert_eq!(k[3], Some(80.0));
+    assert!((k[4].unwrap() - (100.0 + 400.0 / 6.0) / 2.0).abs() < EPSILON);
+    assert!((k[5].unwrap() - (400.0 / 6.0 + 200.0 / 3.0) / 2.0).abs() < EPSILON);
+    assert_eq!(points[3].d, None);
+    assert!((points[4].d.unwrap() - (k[3].unwrap() + k[4].unwrap()) / 2.0).abs() < EPSILON);
+
+    // Lissage 1: stochastique rapide
+    assert_eq!(
+        calculate_slow_stochastic(&open_times, &highs, &lows, &closes, 3, 1, 2),
+        calculate_stochastic(&open_times, &highs, &lows, &closes, 3, 2)
+    );
+    // Prix figés: %K lissé reste au milieu de l'échelle
+    let flat = [5.0; 6];
+    let points = calculate_slow_stochastic(&open_times, &flat, &flat, &flat, 2, 3, 2);
+    assert_eq!(points[5].k, Some(FLAT_RANGE_K));
+    assert_eq!(points[5].d, Some(FLAT_RANGE_K));
+    assert!(Stochastic::new(14, 0, 3).is_err());
+}
+
+#[test]
+fn stochastic_indicator_stored_end_to_end() {
+    let mut db = memory_db();
+    let closes: Vec<(f64, f64)> = (0..60)
+        .map(|i| {
+            (
+                50_000.0 + (i as f64 / 5.0).sin() * 600.0 + (i % 3) as f64 * 40.0,
+                1.0,
+            )
+        })
+        .collect();
+    insert_hourly(&mut db, &closes);
+
+    // Seconde moitié: les 5 + 3 + 3 - 3 bougies qui précèdent complètent
+    // les fenêtres
+    let stochastic = Stochastic::new(5, 3, 3).unwrap();
+    let start = BASE_TIME + 30 * INTERVAL;
+    let stored = recalculate_stochastic_for_range(
+        db.connection_mut(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        &stochastic,
+        start,
+        i64::MAX,
+    )
+    .unwrap();
+    assert_eq!(stored, 60);
+
+    let expected = slow_stochastic_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        0..=i64::MAX,
+        5,
+        3,
+        3,
+    )
+    .unwrap();
+    for (component, pick) in [
+        (
+            "k",
+            (|p: &StochasticPoint| p.k) as fn(&StochasticPoint) -> Option<f64>,
+        ),
+        ("d", |p: &StochasticPoint| p.d),
+    ] {
+        let values = component_values(
+            db.connection(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            &stochastic,
+            component,
+            0..=i64::MAX,
+        )
+        .unwrap();
+        let reference: Vec<IndicatorValue> = expected
+            .iter()
+            .filter(|p| p.open_time >= start)
+            .map(|p| IndicatorValue {
+                open_time: p.open_time,
+                value: pick(p).unwrap(),
+            })
+            .collect();
+        assert_eq!(values, reference, "composante {}", component);
+    }
+}
+
+const DAY: i64 = 86_400_000;
+/// 2024-01-01 00:00 UTC, début de jour
+const DAY_START: i64 = 1704067200000;
+
+#[test]
+fn pivot_levels_are_ordered_around_pp() {
+    let days = [
+        DailyCandle {
+            date: DAY_START,
+            high: 110.0,
+            low: 90.0,
+            close: 105.0,
+        },
+        DailyCandle {
+            date: DAY_START + DAY,
+            high: 120.0,
+            low: 100.0,
+            close: 101.0,
+        },
+    ];
+
+    // Un point par jour sauf le premier, niveaux de la veille
+    let points = calculate_pivot_points(&days);
+    assert_eq!(points.len(), 1);
+    let point = points[0];
+    assert_eq!(point.date, DAY_START + DAY);
+    let pp = (110.0 + 90.0 + 105.0) / 3.0;
+    assert_eq!(point.pp, pp);
+    assert_eq!(point.r1, 2.0 * pp - 90.0);
+    assert_eq!(point.s1, 2.0 * pp - 110.0);
+    assert_eq!(point.r2, pp + 20.0);
+    assert_eq!(point.s2, pp - 20.0);
+    assert!(point.r3 > point.r2 && point.r2 > point.r1 && point.r1 > point.pp);
+    assert!(point.pp > point.s1 && point.s1 > point.s2 && point.s2 > point.s3);
+
+    assert!(calculate_pivot_points(&days[..1]).is_empty());
+}
+
+#[test]
+fn pivot_points_group_hourly_candles_by_day() {
+    let mut db = memory_db();
+    // 3 jours de bougies 1h, close = 50000 + position
+    let candles = testutil::series("BTCUSDT", "1h", DAY_START, 72, &[]);
+    db.insert_candles(&candles).unwrap();
+
+    let full = pivot_point_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        "1d",
+        0..=i64::MAX,
+    )
+    .unwrap();
+    // Jour 1: high = close + 300, low = close - 500 des bougies du jour 0
+    let first_day = DailyCandle {
+        date: DAY_START,
+        high: 50_023.0 + 300.0,
+        low: 50_000.0 - 500.0,
+        close: 50_023.0,
+    };
+    assert_eq!(full.len(), 2);
+    assert_eq!(
+        full[0],
+        PivotPoint::from_candle(DAY_START + DAY, &first_day)
+    );
+
+    // Plage limitée au jour 2: le jour 1 est lu pour ses niveaux
+    let tail = pivot_point_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        "1d",
+        DAY_START + 2 * DAY..=i64::MAX,
+    )
+    .unwrap();
+    assert_eq!(tail, full[1..]);
+
+    // Période pivot qui n'est pas un multiple strict du timeframe
+    assert!(
+        pivot_point_series(
+            db.connection(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            "1h",
+            0..=i64::MAX
+        )
+        .is_err()
+    );
+
+    let stored = store_pivot_points(
+        db.connection_mut(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        "1d",
+        0,
+        i64::MAX,
+    )
+    .unwrap();
+    assert_eq!(stored, 2);
+    let rows: i64 = db
+        .connection()
+        .query_row(
+            "SELECT COUNT(*) FROM pivot_points WHERE pivot_period = '1d'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(rows, 2);
+}
+
+#[test]
+fn rolling_statistics_by_hand() {
+    let values = [2.0, 4.0, 6.0, 3.0, 1.0];
+
+    assert_eq!(
+        rolling_mean(&values, 3),
+        vec![None, None, Some(4.0), Some(13.0 / 3.0), Some(10.0 / 3.0)]
+    );
+    // Écart-type échantillon de [2, 4, 6]: sqrt(8 / 2)
+    assert_eq!(rolling_std(&values, 3)[2], Some(2.0));
+    assert_eq!(
+        rolling_min(&values, 3),
+        vec![None, None, Some(2.0), Some(3.0), Some(1.0)]
+    );
+    assert_eq!(
+        rolling_max(&values, 3),
+        vec![None, None, Some(6.0), Some(6.0), Some(6.0)]
+    );
+
+    // Période 1: la valeur elle-même; période nulle ou trop courte: None
+    assert_eq!(rolling_max(&values, 1), values.map(Some).to_vec());
+    assert!(rolling_mean(&values, 0).iter().all(Option::is_none));
+    assert!(rolling_std(&values, 1).iter().all(Option::is_none));
+    assert!(rolling_min(&values, 6).iter().all(Option::is_none));
+}
+
+#[test]
+fn monotonic_deque_matches_naive_extremes() {
+    // Série en dents de scie avec égalités
+    let values: Vec<f64> = (0..200).map(|i| ((i * 37) % 23) as f64).collect();
+    for period in [1, 2, 5, 23, 50] {
+        let naive = |pick: fn(f64, f64) -> f64| -> Vec<Option<f64>> {
+            (0..values.len())
+                .map(|i| {
+                    (i + 1 >= period)
+                        .then(|| values[i + 1 - period..=i].iter().copied().reduce(pick))
+                        .flatten()
+                })
+                .collect()
+        };
+        assert_eq!(rolling_min(&values, period), naive(f64::min));
+        assert_eq!(rolling_max(&values, period), naive(f64::max));
+    }
+}
+
+#[test]
+fn rolling_series_reads_window_before_range() {
+    let mut db = memory_db();
+    let closes = [10.0, 12.0, 11.0, 15.0, 9.0, 14.0];
+    insert_hourly(&mut db, &closes.map(|close| (close, 1.0)));
+
+    let full = rolling_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        0..=i64::MAX,
+        RollingKind::Max,
+        3,
+    )
+    .unwrap();
+    let maxima: Vec<f64> = full.iter().map(|v| v.value).collect();
+    assert_eq!(maxima, vec![12.0, 15.0, 15.0, 15.0]);
+    assert_eq!(full[0].open_time, BASE_TIME + 2 * INTERVAL);
+
+    let tail = rolling_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        BASE_TIME + 4 * INTERVAL..=i64::MAX,
+        RollingKind::Max,
+        3,
+    )
+    .unwrap();
+    assert_eq!(tail, full[2..]);
+}
+
+#[test]
+fn adx_of_a_steady_uptrend_exceeds_25() {
+    let period = 3;
+    let open_times: Vec<i64> = (0..12).collect();
+    let closes: Vec<f64> = (0..12).map(|i| 100.0 + 2.0 * i as f64).collect();
+    let highs: Vec<f64> = closes.iter().map(|c| c + 1.0).collect();
+    let lows: Vec<f64> = closes.iter().map(|c| c - 1.0).collect();
+
+    let points = calculate_adx(&open_times, &highs, &lows, &closes, period);
+    assert_eq!(points.len(), 12);
+    // DI à partir de la bougie `period`, ADX à partir de 2 × period - 1
+    assert!(points[..period].iter().all(|p| p.plus_di.is_none()));
+    assert!(points[period].plus_di.is_some() && points[period].adx.is_none());
+    assert!(points[..2 * period - 1].iter().all(|p| p.adx.is_none()));
+
+    for point in &points[2 * period - 1..] {
+        let adx = point.adx.unwrap();
+        assert!(adx > 25.0, "ADX {} en {}", adx, point.open_time);
+        assert_eq!(point.minus_di, Some(0.0));
+        // TR = 3 (close préc. à 2 sous le plus haut), +DM = 2
+        assert!((point.plus_di.unwrap() - 200.0 / 3.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn adx_of_flat_prices_is_zero() {
+    let flat = [10.0; 6];
+    let points = calculate_adx(&[0, 1, 2, 3, 4, 5], &flat, &flat, &flat, 2);
+    assert_eq!(
+        points[5],
+        AdxPoint {
+            open_time: 5,
+            adx: Some(0.0),
+            plus_di: Some(0.0),
+            minus_di: Some(0.0),
+        }
+    );
+    assert!(
+        calculate_adx(&[0, 1], &flat[..2], &flat[..2], &flat[..2], 0)
+            .iter()
+            .all(|p| p.adx.is_none() && p.plus_di.is_none())
+    );
+}
+
+#[test]
+fn adx_range_and_storage() {
+    let mut db = memory_db();
+    // Série haussière avec un repli au milieu
+    let candles: Vec<CandleRecord> = (0..30)
+        .map(|i| {
+            let close = if (12..16).contains(&i) {
+                50_000.0 - 40.0 * i as f64
+            } else {
+                50_000.0 + 25.0 * i as f64
+            };
+            candle("BTCUSDT", "1h", BASE_TIME + i * INTERVAL, close)
+        })
+        .collect();
+    db.insert_candles(&candles).unwrap();
+
+    let full = adx_series(db.connection(), PROVIDER, "BTCUSDT", "1h", 0..=i64::MAX, 5).unwrap();
+    // ADX à partir de la bougie 2 × 5 - 1
+    assert_eq!(full.len(), 21);
+    assert_eq!(full[0].open_time, BASE_TIME + 9 * INTERVAL);
+
+    // Plage restreinte: mêmes valeurs que la série complète
+    let start = BASE_TIME + 20 * INTERVAL;
+    let tail = adx_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        start..=i64::MAX,
+        5,
+    )
+    .unwrap();
+    assert_eq!(tail, full[11..]);
+
+    let stored = store_adx(
+        db.connection_mut(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        5,
+        start,
+        BASE_TIME + 29 * INTERVAL,
+    )
+    .unwrap();
+    assert_eq!(stored, 10);
+    let adx: f64 = db
+        .connection()
+        .query_row(
+            "SELECT adx FROM adx_values WHERE period = 5 AND open_time = ?1",
+            params![start],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(Some(adx), tail[0].adx);
+}
+
+/// Écart du close au close `lag` bougies plus tôt
+struct Momentum {
+    lag: usize,
+}
+
+impl Indicator for Momentum {
+    fn name(&self) -> &str {
+        "momentum"
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::json!({ "lag": self.lag })
+    }
+
+    fn compute(&self, candles: &[CandleRecord]) -> Vec<Option<f64>> {
+        (0..candles.len())
+            .map(|i| (i >= self.lag).then(|| candles[i].close - candles[i - self.lag].close))
+            .collect()
+    }
+}
+
+#[test]
+fn generic_indicator_values_by_params() {
+    let mut db = memory_db();
+    insert_hourly(
+        &mut db,
+        &[(100.0, 1.0), (103.0, 1.0), (101.0, 1.0), (108.0, 1.0)],
+    );
+
+    let (one, two) = (Momentum { lag: 1 }, Momentum { lag: 2 });
+    let all = 0..=i64::MAX;
+    assert_eq!(
+        recalculate_indicator_for_range(
+            db.connection_mut(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            &one,
+            0,
+            i64::MAX
+        )
+        .unwrap(),
+        3
+    );
+    // Plage restreinte à la dernière bougie: calculée depuis la première
+    let last = BASE_TIME + 3 * INTERVAL;
+    assert_eq!(
+        recalculate_indicator_for_range(
+            db.connection_mut(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            &two,
+            last,
+            last
+        )
+        .unwrap(),
+        1
+    );
+
+    let lag_one = indicator_values(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        &one,
+        all.clone(),
+    )
+    .unwrap();
+    let closes_diff: Vec<f64> = lag_one.iter().map(|v| v.value).collect();
+    assert_eq!(closes_diff, vec![3.0, -2.0, 7.0]);
+    assert_eq!(lag_one[0].open_time, BASE_TIME + INTERVAL);
+    assert_eq!(
+        indicator_values(db.connection(), PROVIDER, "BTCUSDT", "1h", &two, all).unwrap(),
+        vec![IndicatorValue {
+            open_time: last,
+            value: 5.0
+        }]
+    );
+    let params: String = db
+        .connection()
+        .query_row(
+            "SELECT params FROM indicator_values WHERE open_time = ?1 AND params != '{\"lag\":1}'",
+            params![last],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(params, r#"{"lag":2}"#);
+}
+
+#[test]
+fn sar_stays_below_price_while_bullish_then_flips() {
+    // 15 bougies en hausse, puis 5 en forte baisse
+    let closes: Vec<f64> = (0..20)
+        .map(|i| {
+            if i < 15 {
+                100.0 + 3.0 * i as f64
+            } else {
+                142.0 - 10.0 * (i - 14) as f64
+            }
+        })
+        .collect();
+    let highs: Vec<f64> = closes.iter().map(|c| c + 1.0).collect();
+    let lows: Vec<f64> = closes.iter().map(|c| c - 1.0).collect();
+    let open_times: Vec<i64> = (0..20).collect();
+
+    let points = calculate_sar(
+        &open_times,
+        &highs,
+        &lows,
+        DEFAULT_INITIAL_AF,
+        DEFAULT_STEP_AF,
+        DEFAULT_MAX_AF,
+    );
+    assert_eq!(points.len(), 20);
+    assert_eq!(points[0], None);
+
+    for point in points[1..15].iter().map(|p| p.unwrap()) {
+        let i = point.open_time as usize;
+        assert!(point.is_bullish, "bougie {}", i);
+        assert!(point.sar < lows[i], "SAR {} en {}", point.sar, i);
+    }
+    // Le retournement fait basculer le SAR au-dessus du prix
+    let flip = points[15..]
+        .iter()
+        .map(|p| p.unwrap())
+        .find(|p| !p.is_bullish)
+        .expect("bascule baissière");
+    assert!(flip.sar > highs[flip.open_time as usize]);
+    // SAR de la bascule: plus haut de la tendance haussière
+    assert_eq!(flip.sar, highs[14]);
+
+    assert!(calculate_sar(&[0], &[1.0], &[0.5], 0.02, 0.02, 0.2)[0].is_none());
+}
+
+#[test]
+fn sar_range_validation_and_storage() {
+    let mut db = memory_db();
+    let candles: Vec<CandleRecord> = (0..10)
+        .map(|i| {
+            candle(
+                "BTCUSDT",
+                "1h",
+                BASE_TIME + i * INTERVAL,
+                50_000.0 + 600.0 * i as f64,
+            )
+        })
+        .collect();
+    db.insert_candles(&candles).unwrap();
+
+    let full = sar_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        0..=i64::MAX,
+        0.02,
+        0.02,
+        0.2,
+    )
+    .unwrap();
+    assert_eq!(full.len(), 9);
+    assert_eq!(full[0].open_time, BASE_TIME + INTERVAL);
+    let start = BASE_TIME + 6 * INTERVAL;
+    let tail = sar_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        start..=i64::MAX,
+        0.02,
+        0.02,
+        0.2,
+    )
+    .unwrap();
+    assert_eq!(tail, full[5..]);
+
+    for (initial, step, max) in [
+        (0.0, 0.02, 0.2),
+        (0.3, 0.02, 0.2),
+        (0.02, -0.01, 0.2),
+        (0.02, f64::NAN, 0.2),
+    ] {
+        assert!(
+            sar_series(
+                db.connection(),
+                PROVIDER,
+                "BTCUSDT",
+                "1h",
+                0..=i64::MAX,
+                initial,
+                step,
+                max
+            )
+            .is_err()
+        );
+    }
+
+    let stored = store_sar(
+        db.connection_mut(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        0.02,
+        0.02,
+        0.2,
+        start,
+        i64::MAX,
+    )
+    .unwrap();
+    assert_eq!(stored, 4);
+    let (sar, is_bullish): (f64, bool) = db
+        .connection()
+        .query_row(
+            "SELECT sar, is_bullish FROM sar_values WHERE open_time = ?1",
+            params![start],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!((sar, is_bullish), (tail[0].sar, true));
+}
+
+#[test]
+fn sma_and_ema_by_hand() {
+    let closes = [1.0, 2.0, 3.0, 4.0, 5.0];
+    assert_eq!(
+        sma(&closes, 3),
+        vec![None, None, Some(2.0), Some(3.0), Some(4.0)]
+    );
+    // k = 0.5: amorce 2, puis 2 + 0.5 × (4 - 2) = 3, 3 + 0.5 × (5 - 3) = 4
+    assert_eq!(
+        ema(&closes, 3),
+        vec![None, None, Some(2.0), Some(3.0), Some(4.0)]
+    );
+    let jump = ema(&[10.0, 10.0, 10.0, 22.0], 3);
+    assert_eq!(jump[3], Some(16.0));
+
+    // Série plus courte que la période, période nulle: que des None
+    assert_eq!(ema(&closes[..2], 3), vec![None, None]);
+    assert_eq!(sma(&closes[..2], 3), vec![None, None]);
+    assert!(ema(&closes, 0).iter().all(Option::is_none));
+}
+
+#[test]
+fn ema_matches_reference_series() {
+    // Exemple EMA 10 périodes de StockCharts (valeurs arrondies au centime)
+    let closes = [
+        22.27, 22.19, 22.08, 22.17, 22.18, 22.13, 22.23, 22.43, 22.24, 22.29, 22.15, 22.39, 22.38,
+        22.61, 23.36, 24.05, 23.75, 23.83, 23.95, 23.63,
+    ];
+    let expected = [
+        22.22, 22.21, 22.24, 22.27, 22.33, 22.52, 22.80, 22.97, 23.13, 23.28, 23.34,
+    ];
+
+    let values = ema(&closes, 10);
+    assert!(values[..9].iter().all(Option::is_none));
+    let rounded: Vec<f64> = values[9..]
+        .iter()
+        .map(|v| (v.unwrap() * 100.0).round() / 100.0)
+        .collect();
+    assert_eq!(rounded, expected);
+}
+
+#[test]
+fn moving_averages_are_stored_by_period() {
+    let mut db = memory_db();
+    insert_hourly(
+        &mut db,
+        &[
+            (10.0, 1.0),
+            (10.0, 1.0),
+            (10.0, 1.0),
+            (22.0, 1.0),
+            (22.0, 1.0),
+        ],
+    );
+    let last = BASE_TIME + 4 * INTERVAL;
+
+    // Plage restreinte à la dernière bougie: EMA amorcée sur la première
+    assert_eq!(
+        recalculate_ema_for_range(
+            db.connection_mut(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            3,
+            last,
+            last
+        )
+        .unwrap(),
+        1
+    );
+    assert_eq!(
+        recalculate_sma_for_range(
+            db.connection_mut(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            3,
+            0,
+            i64::MAX
+        )
+        .unwrap(),
+        3
+    );
+    assert_eq!(
+        indicator_values(
+            db.connection(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            &Ema { period: 3 },
+            0..=i64::MAX
+        )
+        .unwrap(),
+        vec![IndicatorValue {
+            open_time: last,
+            value: 19.0
+        }]
+    );
+
+    let by_name = indicator_by_name("sma", Some(3)).unwrap();
+    let stored = indicator_values(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        by_name.as_ref(),
+        0..=i64::MAX,
+    )
+    .unwrap();
+    let computed = indicator_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        by_name.as_ref(),
+        0..=i64::MAX,
+    )
+    .unwrap();
+    assert_eq!(stored, computed);
+    assert_eq!(
+        stored.iter().map(|v| v.value).collect::<Vec<_>>(),
+        vec![10.0, 14.0, 18.0]
+    );
+
+    assert!(indicator_by_name("unknown", None).is_err());
+    assert!(indicator_by_name("ema", Some(0)).is_err());
+    assert_eq!(
+        indicator_by_name("ema", None).unwrap().params(),
+        serde_json::json!({ "period": 20 })
+    );
+}
+
+#[test]
+fn rsi_by_hand() {
+    // Variations +1, +1, -1, +1 (période 2)
+    // - moyennes initiales: gain 1, perte 0 → 100
+    // - gain (1 + 0) / 2 = 0.5, perte (0 + 1) / 2 = 0.5 → 50
+    // - gain (0.5 + 1) / 2 = 0.75, perte 0.25 → 100 - 100 / 4 = 75
+    assert_eq!(
+        calculate_rsi(&[1.0, 2.0, 3.0, 2.0, 3.0], 2),
+        vec![None, None, Some(100.0), Some(50.0), Some(75.0)]
+    );
+    assert_eq!(calculate_rsi(&[5.0; 4], 2)[3], Some(FLAT_RSI));
+    // Série trop courte, période nulle: que des None
+    assert_eq!(calculate_rsi(&[1.0, 2.0], 2), vec![None, None]);
+    assert!(calculate_rsi(&[1.0, 2.0], 0).iter().all(Option::is_none));
+    assert_eq!(RsiState::from_history(&[1.0, 2.0], 2), None);
+    assert_eq!(
+        indicator_by_name("rsi", None).unwrap().params(),
+        serde_json::json!({ "period": 14 })
+    );
+}
+
+#[test]
+fn streaming_rsi_matches_full_calculation() {
+    let closes: Vec<f64> = (0..60)
+        .map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0 + i as f64 * 0.1)
+        .collect();
+    let full = calculate_rsi(&closes, 14);
+
+    // État initialisé sur les 30 premiers closes, puis une étape par close
+    let mut state = RsiState::from_history(&closes[..30], 14).unwrap();
+    assert!((state.value() - full[29].unwrap()).abs() < 1e-9);
+    for i in 30..closes.len() {
+        let rsi = state.update(closes[i], closes[i - 1]);
+        assert!((rsi - full[i].unwrap()).abs() < 1e-9, "RSI en {}", i);
+        assert!((0.0..=100.0).contains(&rsi));
+    }
+}
+
+#[test]
+fn macd_warmup_by_hand() {
+    // EMA 2: 1.5, 2.5, 3.5...; EMA 3: 2, 3, 4... → MACD 0.5 dès l'indice 2
+    let points = calculate_macd(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 2, 3, 2);
+    assert_eq!(points[1].macd, None);
+    assert_eq!(points[2].macd, Some(0.5));
+    assert_eq!(points[2].signal, None);
+    assert_eq!(
+        points[3],
+        MacdPoint {
+            macd: Some(0.5),
+            signal: Some(0.5),
+            histogram: Some(0.0),
+        }
+    );
+    assert!(
+        calculate_macd(&[1.0, 2.0], 12, 26, 9)
+            .iter()
+            .all(|p| p.macd.is_none())
+    );
+}
+
+/// MACD de référence: EMA écrites indépendamment de moving_average
+fn reference_macd(closes: &[f64], fast: usize, slow: usize, signal: usize) -> Vec<(f64, f64)> {
+    fn ema_from(values: &[f64], period: usize) -> Vec<f64> {
+        let k = 2.0 / (period as f64 + 1.0);
+        let mut out = vec![values[..period].iter().sum::<f64>() / period as f64];
+        for value in &values[period..] {
+            let last = *out.last().unwrap();
+            out.push(value * k + last * (1.0 - k));
+        }
+        out
+    }
+    // EMA alignées sur le dernier close
+    let fast_ema = ema_from(closes, fast);
+    let slow_ema = ema_from(closes, slow);
+    let macd: Vec<f64> = slow_ema
+        .iter()
+        .enumerate()
+        .map(|(i, slow)| fast_ema[i + slow_ema.len().abs_diff(fast_ema.len())] - slow)
+        .collect();
+    let signal_line = ema_from(&macd, signal);
+    let offset = macd.len() - signal_line.len();
+    signal_line
+        .iter()
+        .enumerate()
+        .map(|(i, signal)| (macd[i + offset], *signal))
+        .collect()
+}
+
+#[test]
+fn macd_matches_reference_implementation() {
+    let closes: Vec<f64> = (0..80)
+        .map(|i| 100.0 + (i as f64 / 5.0).sin() * 8.0 + (i % 7) as f64 * 0.3)
+        .collect();
+    let points = calculate_macd(&closes, 12, 26, 9);
+    let reference = reference_macd(&closes, 12, 26, 9);
+
+    // Signal à partir de l'indice 26 + 9 - 2 = 33
+    assert!(points[..33].iter().all(|p| p.signal.is_none()));
+    assert_eq!(reference.len(), 80 - 33);
+    for (point, (macd, signal)) in points[33..].iter().zip(reference) {
+        assert!((point.macd.unwrap() - macd).abs() < 1e-9);
+        assert!((point.signal.unwrap() - signal).abs() < 1e-9);
+        assert!((point.histogram.unwrap() - (macd - signal)).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn macd_components_are_stored_with_warmup() {
+    let mut db = memory_db();
+    let closes: Vec<f64> = (0..500)
+        .map(|i| 50_000.0 + (i as f64 / 9.0).sin() * 400.0 + i as f64)
+        .collect();
+    let candles: Vec<CandleRecord> = closes
+        .iter()
+        .enumerate()
+        .map(|(i, &close)| candle("BTCUSDT", "1h", BASE_TIME + i as i64 * INTERVAL, close))
+        .collect();
+    db.insert_candles(&candles).unwrap();
+    let full = calculate_macd(&closes, 12, 26, 9);
+
+    // Dix dernières bougies: 350 bougies de chauffe lues, pas toute la série
+    let macd = Macd::default();
+    let start = BASE_TIME + 490 * INTERVAL;
+    let stored = recalculate_macd_for_range(
+        db.connection_mut(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        &macd,
+        start,
+        i64::MAX,
+    )
+    .unwrap();
+    assert_eq!(stored, 30);
+
+    let series = component_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        &macd,
+        start..=i64::MAX,
+    )
+    .unwrap();
+    let names: Vec<&str> = series.iter().map(|c| c.component.as_str()).collect();
+    assert_eq!(names, vec!["macd", "signal", "histogram"]);
+    for (i, value) in series[1].values.iter().enumerate() {
+        assert!((value.value - full[490 + i].signal.unwrap()).abs() < 1e-6);
+    }
+    assert_eq!(
+        component_values(
+            db.connection(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            &macd,
+            "histogram",
+            0..=i64::MAX
+        )
+        .unwrap(),
+        series[2].values
+    );
+    assert_eq!(
+        indicator_values(
+            db.connection(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            &macd,
+            0..=i64::MAX
+        )
+        .unwrap(),
+        series[0].values
+    );
+
+    assert!(Macd::new(26, 12, 9).is_err());
+    assert!(Macd::new(0, 26, 9).is_err());
+    assert!(Macd::new(12, 26, 0).is_err());
+    assert_eq!(
+        Macd::new(5, 35, 5).unwrap().params(),
+        serde_json::json!({ "fast": 5, "slow": 35, "signal": 5 })
+    );
+}
+
+#[test]
+fn bollinger_computed_by_hand() {
+    let points = calculate_bollinger(&[1.0, 2.0, 3.0, 4.0, 6.0], 3, 2.0);
+
+    assert!(
+        points[..2]
+            .iter()
+            .all(|p| p.middle.is_none() && p.upper.is_none())
+    );
+    // [1, 2, 3]: moyenne 2, variance (1 + 0 + 1) / 3
+    let width = 2.0 * (2.0f64 / 3.0).sqrt();
+    assert_eq!(points[2].middle, Some(2.0));
+    assert!((points[2].upper.unwrap() - (2.0 + width)).abs() < 1e-12);
+    assert!((points[2].lower.unwrap() - (2.0 - width)).abs() < 1e-12);
+    // [2, 4, 6] décalé: [3, 4, 6], moyenne 13/3
+    let mean = 13.0 / 3.0;
+    let std = (([3.0, 4.0, 6.0]
+        .iter()
+        .map(|c| (c - mean) * (c - mean))
+        .sum::<f64>())
+        / 3.0)
+        .sqrt();
+    assert!((points[4].middle.unwrap() - mean).abs() < 1e-12);
+    assert!((points[4].upper.unwrap() - (mean + 2.0 * std)).abs() < 1e-12);
+
+    // Série plus courte que la période, période nulle: que des None
+    assert!(
+        calculate_bollinger(&[1.0, 2.0], 3, 2.0)
+            .iter()
+            .all(|p| p.middle.is_none())
+    );
+    assert!(
+        calculate_bollinger(&[1.0, 2.0], 0, 2.0)
+            .iter()
+            .all(|p| p.lower.is_none())
+    );
+}
+
+#[test]
+fn bollinger_matches_reference_on_high_prices() {
+    // Prix élevés et peu dispersés: la moindre perte de précision se voit
+    let closes: Vec<f64> = (0..60)
+        .map(|i| 1_000_000.0 + (i as f64 / 3.0).sin() * 0.05 + (i % 5) as f64 * 0.01)
+        .collect();
+    let points = calculate_bollinger(&closes, 20, 2.5);
+
+    for i in 19..closes.len() {
+        let window = &closes[i - 19..=i];
+        // Référence: écarts à la plus petite valeur de la fenêtre (décalage
+        // qui ne change pas l'écart-type)
+        let shift = window.iter().copied().fold(f64::INFINITY, f64::min);
+        let shifted: Vec<f64> = window.iter().map(|c| c - shift).collect();
+        let mean = shifted.iter().sum::<f64>() / 20.0;
+        let std = (shifted.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / 20.0).sqrt();
+
+        let point = points[i];
+        assert!((point.middle.unwrap() - (mean + shift)).abs() < 1e-6);
+        let width = point.upper.unwrap() - point.middle.unwrap();
+        assert!((width - 2.5 * std).abs() < 1e-7, "indice {}", i);
+        assert!((point.middle.unwrap() - point.lower.unwrap() - width).abs() < 1e-7);
+    }
+}
+
+#[test]
+fn bollinger_bands_collapse_on_flat_prices() {
+    let points = calculate_bollinger(&[42_000.5; 30], 20, 2.0);
+
+    for point in &points[19..] {
+        let middle = point.middle.unwrap();
+        assert!((middle - 42_000.5).abs() < 1e-9);
+        assert!((point.upper.unwrap() - middle).abs() < 1e-9);
+        assert!((point.lower.unwrap() - middle).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn bollinger_stores_restricted_range() {
+    let mut db = memory_db();
+    let closes: Vec<f64> = (0..40)
+        .map(|i| 50_000.0 + (i as f64 / 4.0).cos() * 250.0)
+        .collect();
+    let candles: Vec<CandleRecord> = closes
+        .iter()
+        .enumerate()
+        .map(|(i, &close)| candle("BTCUSDT", "1h", BASE_TIME + i as i64 * INTERVAL, close))
+        .collect();
+    db.insert_candles(&candles).unwrap();
+    let full = calculate_bollinger(&closes, 20, 2.0);
+
+    // Cinq dernières bougies: les 19 précédentes complètent la fenêtre
+    let bollinger = Bollinger::default();
+    let start = BASE_TIME + 35 * INTERVAL;
+    assert_eq!(
+        recalculate_bollinger_for_range(
+            db.connection_mut(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            &bollinger,
+            start,
+            i64::MAX,
+        )
+        .unwrap(),
+        15
+    );
+
+    let lower = component_values(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        &bollinger,
+        "lower",
+        0..=i64::MAX,
+    )
+    .unwrap();
+    assert_eq!(lower.len(), 5);
+    for (i, value) in lower.iter().enumerate() {
+        assert_eq!(value.open_time, start + i as i64 * INTERVAL);
+        assert!((value.value - full[35 + i].lower.unwrap()).abs() < 1e-9);
+    }
+
+    assert!(Bollinger::new(0, 2.0).is_err());
+    assert!(Bollinger::new(20, -1.0).is_err());
+    assert!(Bollinger::new(20, f64::NAN).is_err());
+    assert_eq!(
+        Bollinger::new(10, 1.5).unwrap().params(),
+        serde_json::json!({ "k": 1.5, "period": 10 })
+    );
+}
+
+/// Bougies 1h (high, low, close) à partir de BASE_TIME
+fn ohlc_candles(points: &[(f64, f64, f64)]) -> Vec<CandleRecord> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, &(high, low, close))| CandleRecord {
+            high,
+            low,
+            ..candle("BTCUSDT", "1h", BASE_TIME + i as i64 * INTERVAL, close)
+        })
+        .collect()
+}
+
+#[test]
+fn atr_computed_by_hand_with_gap_up() {
+    let highs = [10.0, 11.0, 16.0, 16.0, 15.0];
+    let lows = [8.0, 9.0, 15.0, 14.0, 13.0];
+    let closes = [9.0, 10.0, 15.5, 14.5, 14.0];
+
+    // Bougie 2: ouverture en gap, TR = high - close préc. = 16 - 10
+    assert_eq!(
+        calculate_true_range(&highs, &lows, &closes),
+        vec![None, Some(2.0), Some(6.0), Some(2.0), Some(2.0)]
+    );
+
+    let atr = calculate_atr(&highs, &lows, &closes, 3);
+    assert_eq!(&atr[..3], &[None, None, None]);
+    // Moyenne des 3 premiers TR, puis lissage de Wilder
+    assert!((atr[3].unwrap() - 10.0 / 3.0).abs() < 1e-12);
+    assert!((atr[4].unwrap() - 26.0 / 9.0).abs() < 1e-12);
+
+    assert!(
+        calculate_atr(&highs, &lows, &closes, 5)
+            .iter()
+            .all(Option::is_none)
+    );
+    assert!(
+        calculate_atr(&highs, &lows, &closes, 0)
+            .iter()
+            .all(Option::is_none)
+    );
+    assert!(Atr::new(0, false).is_err());
+}
+
+#[test]
+fn atr_excludes_interpolated_candles() {
+    let mut candles = ohlc_candles(&[
+        (10.0, 8.0, 9.0),
+        (11.0, 9.0, 10.0),
+        (10.0, 10.0, 10.0), // Interpolée (ffill): amplitude nulle
+        (16.0, 15.0, 15.5),
+        (16.0, 14.0, 14.5),
+    ]);
+    candles[2].interpolated = 1;
+
+    // Bougies comprises: le TR nul fait baisser l'ATR
+    let with = Atr::new(2, false).unwrap().compute(&candles);
+    let without = Atr::new(2, true).unwrap().compute(&candles);
+    assert_eq!(with[2], Some(1.0));
+    assert_eq!(without[2], None);
+
+    // Réelles seulement: même calcul que sur la série sans la bougie 2
+    let expected = calculate_atr(
+        &[10.0, 11.0, 16.0, 16.0],
+        &[8.0, 9.0, 15.0, 14.0],
+        &[9.0, 10.0, 15.5, 14.5],
+        2,
+    );
+    assert_eq!(without[3], expected[2]);
+    assert_eq!(without[4], expected[3]);
+    assert!(without[4].unwrap() > with[4].unwrap());
+    assert_eq!(
+        Atr::new(14, true).unwrap().params(),
+        serde_json::json!({ "exclude_interpolated": true, "period": 14 })
+    );
+}
+
+#[test]
+fn atr_stores_restricted_range_with_warmup() {
+    let mut db = memory_db();
+    let points: Vec<(f64, f64, f64)> = (0..600)
+        .map(|i| {
+            let close = 50_000.0 + (i as f64 / 7.0).sin() * 300.0;
+            (close + 50.0 + (i % 4) as f64 * 10.0, close - 60.0, close)
+        })
+        .collect();
+    db.insert_candles(&ohlc_candles(&points)).unwrap();
+    let highs: Vec<f64> = points.iter().map(|p| p.0).collect();
+    let lows: Vec<f64> = points.iter().map(|p| p.1).collect();
+    let closes: Vec<f64> = points.iter().map(|p| p.2).collect();
+    let full = calculate_atr(&highs, &lows, &closes, 14);
+
+    // Dix dernières bougies: 350 bougies de chauffe, pas toute la série
+    let atr = Atr::default();
+    let start = BASE_TIME + 590 * INTERVAL;
+    assert_eq!(
+        recalculate_atr_for_range(
+            db.connection_mut(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            &atr,
+            start,
+            i64::MAX,
+        )
+        .unwrap(),
+        10
+    );
+
+    let stored = indicator_values(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        &atr,
+        0..=i64::MAX,
+    )
+    .unwrap();
+    assert_eq!(stored.len(), 10);
+    for (i, value) in stored.iter().enumerate() {
+        assert!((value.value - full[590 + i].unwrap()).abs() < 1e-6);
+    }
+}
+
+/// Bougies 1h à prix unique (high = low = close: prix typique = prix)
+fn priced_candles(start: i64, points: &[(f64, f64)]) -> Vec<CandleRecord> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, &(price, volume))| CandleRecord {
+            high: price,
+            low: price,
+            volume,
+            ..candle("BTCUSDT", "1h", start + i as i64 * INTERVAL, price)
+        })
+        .collect()
+}
+
+#[test]
+fn vwap_resets_at_utc_midnight() {
+    // 22:00 et 23:00 la veille, puis 00:00, 01:00 et 02:00 (sans volume)
+    let candles = priced_candles(
+        DAY_START - 2 * INTERVAL,
+        &[
+            (10.0, 1.0),
+            (20.0, 3.0),
+            (30.0, 2.0),
+            (40.0, 2.0),
+            (50.0, 0.0),
+        ],
+    );
+    assert_eq!(
+        calculate_vwap(&candles, "1d").unwrap(),
+        vec![10.0, 17.5, 30.0, 35.0, 35.0]
+    );
+    // Session 4h (20:00 - 00:00 puis 00:00 - 04:00): mêmes remises à zéro
+    assert_eq!(
+        calculate_vwap(&candles, "4h").unwrap(),
+        calculate_vwap(&candles, "1d").unwrap()
+    );
+    // Session d'une bougie: prix typique, même sans volume
+    assert_eq!(
+        calculate_vwap(&candles, "1h").unwrap(),
+        vec![10.0, 20.0, 30.0, 40.0, 50.0]
+    );
+    assert!(calculate_vwap(&candles, "1day").is_err());
+    assert!(Vwap::new("1day").is_err());
+}
+
+#[test]
+fn vwap_range_reads_from_session_start() {
+    let mut db = memory_db();
+    db.insert_candles(&priced_candles(
+        DAY_START - 2 * INTERVAL,
+        &[
+            (10.0, 1.0),
+            (20.0, 3.0),
+            (30.0, 2.0),
+            (40.0, 2.0),
+            (50.0, 0.0),
+        ],
+    ))
+    .unwrap();
+    let vwap = Vwap::default();
+
+    // Plage à partir de 01:00: la bougie de minuit compte dans la session
+    let stored = recalculate_vwap_for_range(
+        db.connection_mut(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        &vwap,
+        DAY_START + INTERVAL,
+        i64::MAX,
+    )
+    .unwrap();
+    assert_eq!(stored, 2);
+    assert_eq!(
+        indicator_values(
+            db.connection(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            &vwap,
+            0..=i64::MAX
+        )
+        .unwrap(),
+        vec![
+            IndicatorValue {
+                open_time: DAY_START + INTERVAL,
+                value: 35.0
+            },
+            IndicatorValue {
+                open_time: DAY_START + 2 * INTERVAL,
+                value: 35.0
+            }
+        ]
+    );
+
+    // Plage qui commence à 23:00: session de la veille
+    let values = vwap_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        &vwap,
+        DAY_START - INTERVAL..=DAY_START,
+    )
+    .unwrap();
+    assert_eq!(
+        values.iter().map(|v| v.value).collect::<Vec<_>>(),
+        vec![17.5, 30.0]
+    );
+    // Même résultat que le calcul générique depuis la première bougie
+    assert_eq!(
+        values,
+        indicator_series(
+            db.connection(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            &vwap,
+            DAY_START - INTERVAL..=DAY_START
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn registry_recalculates_a_1000_candle_batch() {
+    let mut db = memory_db();
+    let points: Vec<(f64, f64)> = (0..10_000)
+        .map(|i| {
+            (
+                50_000.0 + (i as f64 / 7.0).sin() * 800.0 + (i % 5) as f64 * 30.0,
+                1.0,
+            )
+        })
+        .collect();
+    insert_hourly(&mut db, &points);
+    let registry = IndicatorRegistry::from_specs(&[
+        "rsi:14".parse().unwrap(),
+        IndicatorSpec {
+            name: "ema".to_string(),
+            period: Some(20),
+        },
+    ])
+    .unwrap();
+    assert_eq!(registry.len(), 2);
+
+    // Batch des bougies 5000 à 5999: seules leurs chauffes sont relues et
+    // seules les bougies qui en dépendent sont recalculées
+    let started = std::time::Instant::now();
+    let stored = registry
+        .recalculate(
+            db.connection_mut(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            BASE_TIME + 5000 * INTERVAL,
+            BASE_TIME + 5999 * INTERVAL,
+        )
+        .unwrap();
+    let elapsed = started.elapsed();
+    assert_eq!(
+        stored,
+        (1000 + RSI_WARMUP_FACTOR * 14 + 1000 + EMA_WARMUP_FACTOR * 20) as i64
+    );
+    assert!(elapsed < std::time::Duration::from_secs(2), "{:?}", elapsed);
+
+    let closes: Vec<f64> = points.iter().map(|p| p.0).collect();
+    for (indicator, expected) in [
+        (
+            Box::new(Rsi { period: 14 }) as Box<dyn Indicator>,
+            calculate_rsi(&closes, 14),
+        ),
+        (Box::new(Ema { period: 20 }), ema(&closes, 20)),
+    ] {
+        let values = indicator_values(
+            db.connection(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            indicator.as_ref(),
+            0..=i64::MAX,
+        )
+        .unwrap();
+        assert_eq!(values[0].open_time, BASE_TIME + 5000 * INTERVAL);
+        for value in values {
+            let position = ((value.open_time - BASE_TIME) / INTERVAL) as usize;
+            assert!((value.value - expected[position].unwrap()).abs() < 1e-9);
+        }
+    }
+
+    assert!("rsi:x".parse::<IndicatorSpec>().is_err());
+    assert!(IndicatorRegistry::from_specs(&["macd".parse().unwrap()]).is_err());
+    assert!(IndicatorRegistry::from_specs(&["sma:0".parse().unwrap()]).is_err());
+}
+
+#[test]
+fn registry_resumes_obv_for_appended_candles() {
+    let mut db = memory_db();
+    insert_hourly(
+        &mut db,
+        &[
+            (100.0, 10.0),
+            (101.0, 20.0),
+            (99.0, 5.0),
+            (99.0, 8.0),
+            (105.0, 1.0),
+        ],
+    );
+    let registry = IndicatorRegistry::from_specs(&["obv".parse().unwrap()]).unwrap();
+    let obv_at = |db: &rust_candles_retriever::database::DatabaseManager, position: i64| -> f64 {
+        db.connection()
+            .query_row(
+                "SELECT value FROM indicator_values WHERE open_time = ?1",
+                [BASE_TIME + position * INTERVAL],
+                |row| row.get(0),
+            )
+            .unwrap()
+    };
+
+    assert_eq!(
+        registry
+            .recalculate(
+                db.connection_mut(),
+                PROVIDER,
+                "BTCUSDT",
+                "1h",
+                BASE_TIME,
+                BASE_TIME + 2 * INTERVAL,
+            )
+            .unwrap(),
+        5
+    );
+    // Valeur enregistrée décalée: seule une reprise la prolonge
+    db.connection()
+        .execute(
+            "UPDATE indicator_values SET value = 115.0 WHERE open_time = ?1",
+            [BASE_TIME + 2 * INTERVAL],
+        )
+        .unwrap();
+
+    let appended = registry
+        .recalculate_appended(
+            db.connection_mut(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            BASE_TIME + 3 * INTERVAL,
+            BASE_TIME + 4 * INTERVAL,
+        )
+        .unwrap();
+    assert_eq!(appended, 2);
+    assert_eq!((obv_at(&db, 3), obv_at(&db, 4)), (115.0, 116.0));
+
+    // Batch du backfill: calcul complet depuis la première bougie
+    registry
+        .recalculate(
+            db.connection_mut(),
+            PROVIDER,
+            "BTCUSDT",
+            "1h",
+            BASE_TIME + 3 * INTERVAL,
+            BASE_TIME + 4 * INTERVAL,
+        )
+        .unwrap();
+    assert_eq!((obv_at(&db, 3), obv_at(&db, 4)), (15.0, 16.0));
+}
@@ -0,0 +1,162 @@
+/// Golden files du comblement linéaire (FillStrategy::Linear)
+///
+/// Valeurs de référence dans tests/fixtures/linear_fill_*.csv, calculées par
+/// une implémentation indépendante (tests/fixtures/generate_linear_fill.py):
+/// une erreur de position dans le ratio j / (n + 1) décale toutes les
+/// bougies d'un trou
+///
+/// - Marche aléatoire de 100 bougies, trous de 1 à 20 bougies
+/// - Cas limites: prix constants, croissants, décroissants
+///
+/// NOTE: la crate ne calcule pas de RSI, les golden files portent sur les
+/// bougies synthétiques que les indicateurs consomment
+mod testutil;
+
+use rust_candles_retriever::database::query_candle_records;
+use rust_candles_retriever::gap_filler::{FillStrategy, GapFiller};
+use rust_candles_retriever::models::CandleRecord;
+use std::path::PathBuf;
+use testutil::{BASE_TIME, PROVIDER, candle, memory_db};
+
+const SYMBOL: &str = "BTCUSDT";
+const INTERVAL: i64 = 300_000; // 5 minutes
+const TOLERANCE: f64 = 1e-6;
+
+/// Ligne d'un golden file
+struct GoldenRow {
+    position: i64,
+    /// true: bougie insérée, false: valeur attendue après comblement
+    real: bool,
+    /// open, high, low, close, volume
+    values: [f64; 5],
+}
+
+fn load_golden(name: &str) -> Vec<GoldenRow> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name);
+    let content =
+        std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+
+    content
+        .lines()
+        .skip(1)
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            let number = |i: usize| fields[i].parse::<f64>().expect(line);
+            GoldenRow {
+                position: fields[0].parse().expect(line),
+                real: fields[1] == "1",
+                values: [number(2), number(3), number(4), number(5), number(6)],
+            }
+        })
+        .collect()
+}
+
+/// Insère les bougies réelles du golden file, comble, et retourne la série
+fn fill_golden(rows: &[GoldenRow]) -> Vec<CandleRecord> {
+    let mut db = memory_db();
+    let real: Vec<CandleRecord> = rows
+        .iter()
+        .filter(|row| row.real)
+        .map(|row| {
+            let [open, high, low, close, volume] = row.values;
+            CandleRecord {
+                open,
+                high,
+                low,
+                close,
+                volume,
+                ..candle(SYMBOL, "5m", BASE_TIME + row.position * INTERVAL, close)
+            }
+        })
+        .collect();
+    db.insert_candles(&real).unwrap();
+
+    let end = BASE_TIME + rows.last().unwrap().position * INTERVAL;
+    GapFiller::fill_gaps_in_range(
+        db.connection_mut(),
+        PROVIDER,
+        SYMBOL,
+        "5m",
+        BASE_TIME,
+        end,
+        FillStrategy::Linear,
+    )
+    .unwrap();
+
+    query_candle_records(
+        db.connection(),
+        PROVIDER,
+        SYMBOL,
+        "5m",
+        BASE_TIME..=end,
+        None,
+    )
+    .unwrap()
+}
+
+/// Compare la série comblée au golden file, bougie par bougie
+fn assert_matches_golden(name: &str) -> Vec<CandleRecord> {
+    let rows = load_golden(name);
+    let filled = fill_golden(&rows);
+    assert_eq!(filled.len(), rows.len(), "{}: nombre de bougies", name);
+
+    for (row, candle) in rows.iter().zip(&filled) {
+        assert_eq!(candle.open_time, BASE_TIME + row.position * INTERVAL);
+        assert_eq!(
+            candle.interpolated == 0,
+            row.real,
+            "{} @ {}",
+            name,
+            row.position
+        );
+
+        let actual = [
+            candle.open,
+            candle.high,
+            candle.low,
+            candle.close,
+            candle.volume,
+        ];
+        for (field, (expected, actual)) in ["open", "high", "low", "close", "volume"]
+            .iter()
+            .zip(row.values.iter().zip(actual))
+        {
+            assert!(
+                (expected - actual).abs() <= TOLERANCE,
+                "{} @ {}: {} attendu {}, obtenu {}",
+                name,
+                row.position,
+                field,
+                expected,
+                actual
+            );
+        }
+    }
+    filled
+}
+
+#[test]
+fn random_walk_matches_golden_file() {
+    let filled = assert_matches_golden("linear_fill_random_walk.csv");
+    assert_eq!(filled.iter().filter(|c| c.interpolated != 0).count(), 34);
+}
+
+#[test]
+fn constant_prices_stay_constant() {
+    let filled = assert_matches_golden("linear_fill_flat.csv");
+    assert!(filled.iter().all(|c| c.close == 100.0));
+}
+
+#[test]
+fn increasing_prices_stay_strictly_increasing() {
+    let filled = assert_matches_golden("linear_fill_increasing.csv");
+    assert!(filled.windows(2).all(|pair| pair[0].close < pair[1].close));
+}
+
+#[test]
+fn decreasing_prices_stay_strictly_decreasing() {
+    let filled = assert_matches_golden("linear_fill_decreasing.csv");
+    assert!(filled.windows(2).all(|pair| pair[0].close > pair[1].close));
+}
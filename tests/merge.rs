@@ -0,0 +1,169 @@
+/// Tests de la fusion de bases (merge_databases) et de la synchronisation
+/// (sync_db)
+///
+/// - Deux bases générées, dont une paire en commun: totaux et conflits
+/// - Reprise: une seconde fusion saute les paires déjà copiées
+/// - Les fichiers sources ne sont jamais modifiés
+/// - Synchronisation: seules les bougies postérieures à la dernière bougie
+///   de la destination sont copiées, séries absentes copiées en entier,
+///   filtre --symbol, progression fusionnée pour les seules séries copiées
+mod testutil;
+
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::merge::{merge_database, sync_database};
+use rust_candles_retriever::timeframe_status::TimeframeStatus;
+use std::path::Path;
+use testutil::{BASE_TIME, PROVIDER, TempDir, insert_series};
+
+/// Base fichier avec une série 5m par symbole et sa progression
+fn source_db(path: &Path, series: &[(&str, i64, i64)]) {
+    let mut db = DatabaseManager::new(path.to_str().unwrap()).unwrap();
+    for &(symbol, start_position, count) in series {
+        let start = BASE_TIME + start_position * 300_000;
+        let candles = insert_series(&mut db, symbol, "5m", start, count, &[]);
+        TimeframeStatus::update_progress(
+            db.connection(),
+            PROVIDER,
+            symbol,
+            "5m",
+            candles[0].open_time,
+            candles[candles.len() - 1].open_time,
+        )
+        .unwrap();
+    }
+    // Fermeture: le WAL est reporté dans le fichier principal
+}
+
+fn count(db: &DatabaseManager, symbol: &str) -> i64 {
+    db.stats(PROVIDER, symbol, "5m").unwrap().count
+}
+
+#[test]
+fn merge_two_databases_counts_rows_and_conflicts() {
+    let dir = TempDir::new("candles_merge_totals");
+    let (a, b) = (dir.file("a.db"), dir.file("b.db"));
+    source_db(&a, &[("BTCUSDT", 0, 100)]);
+    // BTCUSDT chevauche a.db sur les positions 80..100
+    source_db(&b, &[("BTCUSDT", 80, 50), ("ETHUSDT", 0, 30)]);
+
+    let mut dest = DatabaseManager::new(dir.file("merged.db").to_str().unwrap()).unwrap();
+    let first = merge_database(dest.connection_mut(), &a).unwrap();
+    assert_eq!(first.len(), 1);
+    assert_eq!((first[0].inserted, first[0].conflicts), (100, 0));
+
+    let second = merge_database(dest.connection_mut(), &b).unwrap();
+    let summary: Vec<_> = second
+        .iter()
+        .map(|r| (r.symbol.as_str(), r.source_rows, r.inserted, r.conflicts))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![("BTCUSDT", 50, 30, 20), ("ETHUSDT", 30, 30, 0)]
+    );
+
+    assert_eq!(count(&dest, "BTCUSDT"), 130);
+    assert_eq!(count(&dest, "ETHUSDT"), 30);
+
+    // Bornes de progression élargies aux deux sources
+    let conn = dest.connection();
+    assert_eq!(
+        TimeframeStatus::get_last_candle_time(conn, PROVIDER, "BTCUSDT", "5m"),
+        Some(BASE_TIME)
+    );
+    assert_eq!(
+        TimeframeStatus::get_newest_candle_time(conn, PROVIDER, "BTCUSDT", "5m"),
+        Some(BASE_TIME + 129 * 300_000)
+    );
+}
+
+#[test]
+fn second_merge_skips_copied_pairs() {
+    let dir = TempDir::new("candles_merge_resume");
+    let a = dir.file("a.db");
+    source_db(&a, &[("BTCUSDT", 0, 40), ("ETHUSDT", 0, 10)]);
+
+    let mut dest = DatabaseManager::new(dir.file("merged.db").to_str().unwrap()).unwrap();
+    merge_database(dest.connection_mut(), &a).unwrap();
+    let again = merge_database(dest.connection_mut(), &a).unwrap();
+
+    assert!(again.iter().all(|r| r.skipped && r.inserted == 0));
+    assert_eq!(count(&dest, "BTCUSDT"), 40);
+    assert_eq!(count(&dest, "ETHUSDT"), 10);
+}
+
+#[test]
+fn sources_are_left_untouched() {
+    let dir = TempDir::new("candles_merge_readonly");
+    let a = dir.file("a.db");
+    source_db(&a, &[("BTCUSDT", 0, 60)]);
+    let before = std::fs::read(&a).unwrap();
+
+    let mut dest = DatabaseManager::new(dir.file("merged.db").to_str().unwrap()).unwrap();
+    merge_database(dest.connection_mut(), &a).unwrap();
+    drop(dest);
+
+    assert_eq!(std::fs::read(&a).unwrap(), before);
+}
+
+#[test]
+fn incompatible_source_is_rejected_and_detached() {
+    let dir = TempDir::new("candles_merge_schema");
+    let foreign = dir.file("foreign.db");
+    rusqlite::Connection::open(&foreign)
+        .unwrap()
+        .execute_batch("CREATE TABLE candlesticks (symbol TEXT, open_time INTEGER)")
+        .unwrap();
+
+    let mut dest = DatabaseManager::new(dir.file("merged.db").to_str().unwrap()).unwrap();
+    assert!(merge_database(dest.connection_mut(), &foreign).is_err());
+
+    // La source a été détachée: une fusion suivante peut réutiliser l'alias
+    let a = dir.file("a.db");
+    source_db(&a, &[("BTCUSDT", 0, 5)]);
+    assert_eq!(
+        merge_database(dest.connection_mut(), &a).unwrap()[0].inserted,
+        5
+    );
+}
+
+#[test]
+fn sync_copies_only_newer_candles() {
+    let dir = TempDir::new("candles_merge_sync");
+    let (source, dest_path) = (dir.file("fetcher.db"), dir.file("server.db"));
+    source_db(&source, &[("BTCUSDT", 0, 100), ("ETHUSDT", 0, 30)]);
+    // Progression 1h sans bougie: pas une série synchronisée
+    {
+        let db = DatabaseManager::new(source.to_str().unwrap()).unwrap();
+        TimeframeStatus::update_progress(db.connection(), PROVIDER, "BTCUSDT", "1h", 0, 0).unwrap();
+    }
+    // La destination a déjà les 60 premières bougies, sauf un trou (10..20)
+    source_db(&dest_path, &[("BTCUSDT", 0, 10), ("BTCUSDT", 20, 40)]);
+
+    let mut dest = DatabaseManager::new(dest_path.to_str().unwrap()).unwrap();
+    let only_btc = sync_database(dest.connection_mut(), &source, Some("BTCUSDT")).unwrap();
+    let summary: Vec<_> = only_btc
+        .iter()
+        .map(|r| (r.symbol.as_str(), r.timeframe.as_str(), r.inserted))
+        .collect();
+    assert_eq!(summary, vec![("BTCUSDT", "5m", 40)]);
+    // Le trou antérieur à la dernière bougie n'est pas comblé
+    assert_eq!(count(&dest, "BTCUSDT"), 90);
+    assert_eq!(count(&dest, "ETHUSDT"), 0);
+    assert_eq!(
+        TimeframeStatus::get_newest_candle_time(dest.connection(), PROVIDER, "BTCUSDT", "5m"),
+        Some(BASE_TIME + 99 * 300_000)
+    );
+    // Seule la ligne timeframe_status de la série synchronisée est fusionnée
+    assert_eq!(
+        TimeframeStatus::get_newest_candle_time(dest.connection(), PROVIDER, "BTCUSDT", "1h"),
+        None
+    );
+
+    let all = sync_database(dest.connection_mut(), &source, None).unwrap();
+    let summary: Vec<_> = all
+        .iter()
+        .map(|r| (r.symbol.as_str(), r.inserted))
+        .collect();
+    assert_eq!(summary, vec![("BTCUSDT", 0), ("ETHUSDT", 30)]);
+    assert_eq!(count(&dest, "ETHUSDT"), 30);
+}
@@ -0,0 +1,202 @@
+/// Tests de la reconnaissance des figures de chandeliers (patterns)
+///
+/// - Golden set tests/fixtures/patterns_golden.csv: une séquence par figure,
+///   plus les cas sans figure (corps ordinaire, même sens, corps égaux)
+/// - pattern_series: engulfing sur la première bougie de la plage
+/// - store_patterns: figures remplacées au recalcul
+mod testutil;
+
+use rusqlite::params;
+use rust_candles_retriever::models::CandleRecord;
+use rust_candles_retriever::patterns::{
+    Pattern, PatternCandle, PatternEvent, detect_patterns, pattern_series, store_patterns,
+};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use testutil::{BASE_TIME, PROVIDER, candle, memory_db};
+
+const INTERVAL: i64 = 3_600_000; // 1 heure
+
+fn parse_pattern(name: &str) -> Pattern {
+    match name {
+        "doji" => Pattern::Doji,
+        "hammer" => Pattern::Hammer,
+        "shooting_star" => Pattern::ShootingStar,
+        "bullish_engulfing" => Pattern::BullishEngulfing,
+        "bearish_engulfing" => Pattern::BearishEngulfing,
+        other => panic!("figure inconnue: {other}"),
+    }
+}
+
+/// Séquences du golden set: bougies et figures attendues, par nom
+fn golden_sequences() -> BTreeMap<String, (Vec<PatternCandle>, Vec<PatternEvent>)> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/patterns_golden.csv");
+    let content = std::fs::read_to_string(&path).unwrap();
+
+    let mut sequences: BTreeMap<String, (Vec<PatternCandle>, Vec<PatternEvent>)> = BTreeMap::new();
+    for line in content.lines().skip(1) {
+        let fields: Vec<&str> = line.split(',').collect();
+        let (candles, events) = sequences.entry(fields[0].to_string()).or_default();
+        let price = |i: usize| fields[i].parse::<f64>().unwrap();
+        let open_time = BASE_TIME + candles.len() as i64 * INTERVAL;
+
+        candles.push(PatternCandle {
+            open_time,
+            open: price(1),
+            high: price(2),
+            low: price(3),
+            close: price(4),
+        });
+        events.extend(
+            fields[5]
+                .split('|')
+                .filter(|name| !name.is_empty())
+                .map(|name| PatternEvent {
+                    open_time,
+                    pattern: parse_pattern(name),
+                }),
+        );
+    }
+    sequences
+}
+
+#[test]
+fn golden_sequences_match() {
+    let sequences = golden_sequences();
+    assert_eq!(sequences.len(), 10);
+
+    for (name, (candles, expected)) in &sequences {
+        assert_eq!(&detect_patterns(candles), expected, "séquence {name}");
+    }
+    assert!(detect_patterns(&[]).is_empty());
+}
+
+#[test]
+fn patterns_serialize_in_snake_case() {
+    let event = PatternEvent {
+        open_time: BASE_TIME,
+        pattern: Pattern::ShootingStar,
+    };
+    assert_eq!(
+        serde_json::to_value(event).unwrap(),
+        serde_json::json!({"open_time": BASE_TIME, "pattern": "shooting_star"})
+    );
+    assert_eq!(
+        serde_json::to_value(Pattern::BullishEngulfing).unwrap(),
+        Pattern::BullishEngulfing.as_str()
+    );
+}
+
+/// Séquence bullish_engulfing (baissière puis haussière englobante) suivie
+/// d'un doji, en 1h depuis BASE_TIME
+fn engulfing_then_doji() -> Vec<CandleRecord> {
+    [
+        (102.0, 103.0, 99.5, 100.0),
+        (99.5, 104.0, 99.0, 103.5),
+        (100.0, 105.0, 95.0, 100.5),
+    ]
+    .iter()
+    .enumerate()
+    .map(|(position, &(open, high, low, close))| CandleRecord {
+        open,
+        high,
+        low,
+        ..candle(
+            "BTCUSDT",
+            "1h",
+            BASE_TIME + position as i64 * INTERVAL,
+            close,
+        )
+    })
+    .collect()
+}
+
+#[test]
+fn range_start_sees_the_previous_candle() {
+    let mut db = memory_db();
+    db.insert_candles(&engulfing_then_doji()).unwrap();
+
+    let events = pattern_series(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        BASE_TIME + INTERVAL..=i64::MAX,
+    )
+    .unwrap();
+    assert_eq!(
+        events,
+        vec![
+            PatternEvent {
+                open_time: BASE_TIME + INTERVAL,
+                pattern: Pattern::BullishEngulfing,
+            },
+            PatternEvent {
+                open_time: BASE_TIME + 2 * INTERVAL,
+                pattern: Pattern::Doji,
+            },
+        ]
+    );
+}
+
+#[test]
+fn stored_patterns_are_replaced_on_recompute() {
+    let mut db = memory_db();
+    db.insert_candles(&engulfing_then_doji()).unwrap();
+
+    let stored = |db: &rust_candles_retriever::database::DatabaseManager| -> Vec<(i64, String)> {
+        let mut stmt = db
+            .connection()
+            .prepare(
+                "SELECT open_time, pattern FROM pattern_events
+                 WHERE provider = ?1 AND symbol = ?2 AND timeframe = ?3 ORDER BY open_time",
+            )
+            .unwrap();
+        stmt.query_map(params![PROVIDER, "BTCUSDT", "1h"], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap()
+        .collect::<rusqlite::Result<_>>()
+        .unwrap()
+    };
+
+    let count = store_patterns(
+        db.connection_mut(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        BASE_TIME,
+        BASE_TIME + 2 * INTERVAL,
+    )
+    .unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(
+        stored(&db),
+        vec![
+            (BASE_TIME + INTERVAL, "bullish_engulfing".to_string()),
+            (BASE_TIME + 2 * INTERVAL, "doji".to_string()),
+        ]
+    );
+
+    // Dernière bougie corrigée: plus de doji
+    db.connection()
+        .execute(
+            "UPDATE candlesticks SET close = 104.0 WHERE open_time = ?1",
+            params![BASE_TIME + 2 * INTERVAL],
+        )
+        .unwrap();
+    let count = store_patterns(
+        db.connection_mut(),
+        PROVIDER,
+        "BTCUSDT",
+        "1h",
+        BASE_TIME,
+        BASE_TIME + 2 * INTERVAL,
+    )
+    .unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(
+        stored(&db),
+        vec![(BASE_TIME + INTERVAL, "bullish_engulfing".to_string())]
+    );
+}
@@ -0,0 +1,106 @@
+/// Tests d'intégration du pool de connexions SQLite
+///
+/// 100 requêtes séquentielles (comme des requêtes /api/candles hors cache)
+/// doivent réutiliser une seule connexion au lieu d'en ouvrir 100
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::pool::ConnectionPool;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+const REQUESTS: usize = 100;
+
+/// Base fichier temporaire avec le schéma, supprimée en fin de test
+struct TempDb(PathBuf);
+
+impl TempDb {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("{}_{}.db", name, std::process::id()));
+        let db = TempDb(path);
+        db.cleanup();
+        DatabaseManager::new(db.path()).expect("schéma");
+        db
+    }
+
+    fn path(&self) -> &str {
+        self.0.to_str().expect("chemin UTF-8")
+    }
+
+    fn cleanup(&self) {
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", self.path(), suffix));
+        }
+    }
+}
+
+impl Drop for TempDb {
+    fn drop(&mut self) {
+        self.cleanup();
+    }
+}
+
+fn count_candles(conn: &rusqlite::Connection) -> i64 {
+    conn.query_row(
+        "SELECT COUNT(*) FROM candlesticks WHERE symbol = 'BTCUSDT' AND timeframe = '5m'",
+        [],
+        |row| row.get(0),
+    )
+    .unwrap()
+}
+
+#[test]
+fn sequential_requests_reuse_one_connection() {
+    let temp = TempDb::new("candles_pool_reuse");
+    let pool = ConnectionPool::new(temp.path(), 4, Duration::from_secs(60));
+
+    let started = Instant::now();
+    for _ in 0..REQUESTS {
+        let conn = pool.get().unwrap();
+        assert_eq!(count_candles(&conn), 0);
+    }
+    let pooled = started.elapsed();
+
+    assert_eq!(pool.opened_connections(), 1);
+    assert_eq!(pool.idle_connections(), 1);
+
+    // Référence: une connexion ouverte par requête (comportement précédent)
+    let started = Instant::now();
+    for _ in 0..REQUESTS {
+        let conn = rust_candles_retriever::database::open_configured(temp.path()).unwrap();
+        assert_eq!(count_candles(&conn), 0);
+    }
+    println!(
+        "{} requêtes: pool {:?}, ouverture par requête {:?}",
+        REQUESTS,
+        pooled,
+        started.elapsed()
+    );
+}
+
+#[test]
+fn concurrent_borrows_open_extra_connections_up_to_max_idle() {
+    let temp = TempDb::new("candles_pool_concurrent");
+    let pool = ConnectionPool::new(temp.path(), 2, Duration::from_secs(60));
+
+    let held: Vec<_> = (0..3).map(|_| pool.get().unwrap()).collect();
+    assert_eq!(pool.opened_connections(), 3);
+    drop(held);
+
+    // Une connexion au-delà de max_idle est fermée à sa restitution
+    assert_eq!(pool.idle_connections(), 2);
+
+    let _conn = pool.get().unwrap();
+    assert_eq!(pool.opened_connections(), 3);
+}
+
+#[test]
+fn idle_connections_are_evicted_after_timeout() {
+    let temp = TempDb::new("candles_pool_eviction");
+    let pool = ConnectionPool::new(temp.path(), 4, Duration::from_millis(50));
+
+    drop(pool.get().unwrap());
+    assert_eq!(pool.idle_connections(), 1);
+
+    std::thread::sleep(Duration::from_millis(100));
+    drop(pool.get().unwrap());
+    assert_eq!(pool.opened_connections(), 2);
+}
@@ -0,0 +1,343 @@
+/// Tests du RealtimeManager
+///
+/// - Bougies partielles: regroupement par symbole, retrait après stockage
+/// - Écrivains de bougies clôturées: écriture par lots, reprise après échec,
+///   progression (newest_candle_time) dans timeframe_status
+/// - RSI en continu: avancé par les bougies diffusées comme le calcul
+///   complet, bougie plus ancienne ignorée, historique trop court
+/// - RSI live: bougies partielles successives (close partiel compté comme
+///   dernier close, état inchangé), clôture diffusée avec le RSI complet,
+///   suivi initialisé depuis la base
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::indicators::rsi::calculate_rsi;
+use rust_candles_retriever::models::CandleRecord;
+use rust_candles_retriever::realtime::{CandleUpdate, RealtimeManager, WriterConfig};
+use rust_candles_retriever::timeframe_status::TimeframeStatus;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const BASE_TIME: i64 = 1704067200000; // 2024-01-01 00:00 UTC
+
+fn partial(symbol: &str, timeframe: &str, open_time: i64, close: f64) -> CandleUpdate {
+    CandleUpdate {
+        provider: "binance".to_string(),
+        symbol: symbol.to_string(),
+        timeframe: timeframe.to_string(),
+        time: open_time / 1000,
+        open: 1.0,
+        high: 2.0,
+        low: 0.5,
+        close,
+        volume: 3.0,
+        indicators: None,
+    }
+}
+
+fn stored(open_time: i64) -> CandleRecord {
+    CandleRecord {
+        provider: "binance".to_string(),
+        symbol: "BTCUSDT".to_string(),
+        timeframe: "5m".to_string(),
+        open_time,
+        open: 1.0,
+        high: 2.0,
+        low: 0.5,
+        close: 1.5,
+        volume: 3.0,
+        close_time: open_time + 299_999,
+        quote_asset_volume: 0.0,
+        number_of_trades: 0,
+        taker_buy_base_asset_volume: 0.0,
+        taker_buy_quote_asset_volume: 0.0,
+        interpolated: 0,
+        derived_from: None,
+    }
+}
+
+#[test]
+fn partials_are_grouped_by_symbol_and_replaced() {
+    let manager = RealtimeManager::new(16);
+    manager.set_partial(partial("BTCUSDT", "5m", BASE_TIME, 1.0));
+    manager.set_partial(partial("BTCUSDT", "5m", BASE_TIME, 1.2));
+    manager.set_partial(partial("BTCUSDT", "1h", BASE_TIME, 1.1));
+    manager.set_partial(partial("ETHUSDT", "5m", BASE_TIME, 9.0));
+
+    let partials = manager.partials_for("BTCUSDT");
+    assert_eq!(partials.len(), 2);
+    assert_eq!(partials["5m"].close, 1.2);
+    assert!(manager.partials_for("SOLUSDT").is_empty());
+}
+
+#[test]
+fn stored_candle_clears_older_partial() {
+    let mut db = DatabaseManager::new(":memory:").unwrap();
+    let manager = RealtimeManager::new(16);
+    manager.set_partial(partial("BTCUSDT", "5m", BASE_TIME + 300_000, 1.0));
+
+    // Bougie stockée plus ancienne: la partielle reste
+    db.insert_candles(&[stored(BASE_TIME)]).unwrap();
+    let mut last_rowid = 0;
+    manager
+        .poll_new_candles(db.connection(), &mut last_rowid)
+        .unwrap();
+    assert_eq!(manager.partials_for("BTCUSDT").len(), 1);
+
+    // La bougie clôturée correspondante est stockée: la partielle disparaît
+    db.insert_candles(&[stored(BASE_TIME + 300_000)]).unwrap();
+    manager
+        .poll_new_candles(db.connection(), &mut last_rowid)
+        .unwrap();
+    assert!(manager.partials_for("BTCUSDT").is_empty());
+}
+
+/// Base fichier temporaire (un écrivain ouvre sa propre connexion), supprimée
+/// à la fin du test avec ses fichiers -wal et -shm
+struct TempDb(PathBuf);
+
+impl TempDb {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("{}_{}.db", name, std::process::id()));
+        let db = TempDb(path);
+        db.remove_files();
+        db
+    }
+
+    fn path(&self) -> String {
+        self.0.to_str().unwrap().to_string()
+    }
+
+    fn remove_files(&self) {
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", self.path(), suffix));
+        }
+    }
+}
+
+impl Drop for TempDb {
+    fn drop(&mut self) {
+        self.remove_files();
+    }
+}
+
+#[test]
+fn completed_candles_are_written_in_few_transactions() {
+    let file = TempDb::new("candles_realtime_writer");
+    let db = DatabaseManager::new(&file.path()).unwrap();
+    let manager = RealtimeManager::new(16);
+    manager.enable_candle_writers(
+        file.path(),
+        WriterConfig {
+            flush_interval: Duration::from_secs(1),
+            max_batch: 50,
+            queue_capacity: 100,
+        },
+    );
+
+    for position in 0..500 {
+        manager
+            .save_completed_candle(stored(BASE_TIME + position * 300_000))
+            .unwrap();
+    }
+    let stats = manager.close_writers();
+
+    assert_eq!(stats.len(), 1);
+    let (symbol, stats) = &stats[0];
+    assert_eq!(symbol, "BTCUSDT");
+    assert_eq!(
+        (stats.received, stats.inserted, stats.dropped),
+        (500, 500, 0)
+    );
+    // Lots de 50 (plus d'éventuels lots partiels à l'échéance d'une seconde)
+    assert!(
+        (10..=15).contains(&stats.transactions),
+        "{} transactions",
+        stats.transactions
+    );
+    assert_eq!(db.stats("binance", "BTCUSDT", "5m").unwrap().count, 500);
+}
+
+#[test]
+fn failed_flush_keeps_candles_for_retry() {
+    let file = TempDb::new("candles_realtime_retry");
+    // Fichier sans schéma: les premières écritures échouent (table absente)
+    rusqlite::Connection::open(file.path()).unwrap();
+    let manager = RealtimeManager::new(16);
+    manager.enable_candle_writers(
+        file.path(),
+        WriterConfig {
+            flush_interval: Duration::from_millis(20),
+            ..WriterConfig::default()
+        },
+    );
+
+    for position in 0..10 {
+        manager
+            .save_completed_candle(stored(BASE_TIME + position * 300_000))
+            .unwrap();
+    }
+    std::thread::sleep(Duration::from_millis(200));
+    let db = DatabaseManager::new(&file.path()).unwrap();
+    let stats = manager.close_writers();
+
+    let stats = stats[0].1;
+    assert!(stats.failed_flushes >= 1);
+    assert_eq!((stats.inserted, stats.dropped), (10, 0));
+    assert_eq!(db.stats("binance", "BTCUSDT", "5m").unwrap().count, 10);
+}
+
+#[test]
+fn writer_advances_newest_candle_time_out_of_order() {
+    let file = TempDb::new("candles_realtime_status");
+    let db = DatabaseManager::new(&file.path()).unwrap();
+    let manager = RealtimeManager::new(16);
+    // Lots d'une bougie: une transaction (et une mise à jour du statut) chacune
+    manager.enable_candle_writers(
+        file.path(),
+        WriterConfig {
+            max_batch: 1,
+            ..WriterConfig::default()
+        },
+    );
+
+    manager
+        .save_completed_candle(stored(BASE_TIME + 300_000))
+        .unwrap();
+    manager.save_completed_candle(stored(BASE_TIME)).unwrap();
+    let stats = manager.close_writers();
+    assert_eq!(stats[0].1.transactions, 2);
+
+    let status = TimeframeStatus::get(db.connection(), "binance", "BTCUSDT", "5m")
+        .unwrap()
+        .expect("ligne de statut créée par l'écrivain");
+    assert_eq!(status.newest_candle_time, Some(BASE_TIME + 300_000));
+    // Reprise du backfill et complétion inchangées
+    assert_eq!(status.oldest_candle_time, None);
+    assert!(!status.is_complete);
+}
+
+#[test]
+fn save_completed_candle_requires_enabled_writers() {
+    let manager = RealtimeManager::new(16);
+    assert!(manager.save_completed_candle(stored(BASE_TIME)).is_err());
+}
+
+/// Bougie 5m stockée de close donné, `position` intervalles après BASE_TIME
+fn stored_close(position: i64, close: f64) -> CandleRecord {
+    CandleRecord {
+        close,
+        ..stored(BASE_TIME + position * 300_000)
+    }
+}
+
+#[test]
+fn tracked_rsi_follows_published_candles() {
+    let closes: Vec<f64> = (0..25).map(|i| 100.0 + ((i * 7) % 11) as f64).collect();
+    let history: Vec<CandleRecord> = closes[..20]
+        .iter()
+        .enumerate()
+        .map(|(i, &close)| stored_close(i as i64, close))
+        .collect();
+    let mut db = DatabaseManager::new_in_memory().unwrap();
+    db.insert_candles(&history).unwrap();
+
+    let manager = RealtimeManager::new(16);
+    assert!(manager.track_rsi("BTCUSDT", "5m", 14, &history));
+    assert!(!manager.track_rsi("BTCUSDT", "5m", 30, &history));
+    assert_eq!(manager.rsi("BTCUSDT", "5m", 30), None);
+    let full = calculate_rsi(&closes, 14);
+    assert!((manager.rsi("BTCUSDT", "5m", 14).unwrap() - full[19].unwrap()).abs() < 1e-9);
+
+    // Nouvelles bougies, plus une bougie ancienne rattrapée après coup
+    let mut last_rowid: i64 = db
+        .connection()
+        .query_row("SELECT MAX(rowid) FROM candlesticks", [], |row| row.get(0))
+        .unwrap();
+    let mut updates: Vec<CandleRecord> = (20..25)
+        .map(|i| stored_close(i, closes[i as usize]))
+        .collect();
+    updates.insert(2, stored_close(-1, 1.0));
+    db.insert_candles(&updates).unwrap();
+
+    assert_eq!(
+        manager
+            .poll_new_candles(db.connection(), &mut last_rowid)
+            .unwrap(),
+        6
+    );
+    assert!((manager.rsi("BTCUSDT", "5m", 14).unwrap() - full[24].unwrap()).abs() < 1e-9);
+    assert_eq!(manager.rsi("BTCUSDT", "1h", 14), None);
+}
+
+#[test]
+fn live_rsi_includes_the_partial_close() {
+    let closes: Vec<f64> = (0..22).map(|i| 100.0 + ((i * 5) % 13) as f64).collect();
+    let history: Vec<CandleRecord> = closes[..20]
+        .iter()
+        .enumerate()
+        .map(|(i, &close)| stored_close(i as i64, close))
+        .collect();
+    let mut db = DatabaseManager::new_in_memory().unwrap();
+    db.insert_candles(&history).unwrap();
+
+    let manager = RealtimeManager::new(16);
+    assert!(
+        manager
+            .track_rsi_from_db(db.connection(), "binance", "BTCUSDT", "5m", 14)
+            .unwrap()
+    );
+    assert!(
+        !manager
+            .track_rsi_from_db(db.connection(), "binance", "ETHUSDT", "5m", 14)
+            .unwrap()
+    );
+    let mut receiver = manager.subscribe_updates();
+
+    // Événements kline successifs de la bougie 20 en formation
+    let live_rsi = |close: f64| {
+        let mut series = closes[..20].to_vec();
+        series.push(close);
+        calculate_rsi(&series, 14)[20].unwrap()
+    };
+    for close in [95.0, 118.0, closes[20]] {
+        let partial_time = BASE_TIME + 20 * 300_000;
+        assert_eq!(
+            manager.publish_partial(partial("BTCUSDT", "5m", partial_time, close)),
+            1
+        );
+        let update = receiver.try_recv().unwrap();
+        let value = update.indicators.unwrap()["rsi_14"];
+        assert!((value - live_rsi(close)).abs() < 1e-9);
+    }
+    // Approximation d'affichage: l'état suivi n'a pas bougé
+    let full = calculate_rsi(&closes, 14);
+    assert!((manager.rsi("BTCUSDT", "5m", 14).unwrap() - full[19].unwrap()).abs() < 1e-9);
+    assert!(manager.partials_for("BTCUSDT")["5m"].indicators.is_some());
+    assert!(
+        manager
+            .set_partial(partial("BTCUSDT", "1h", BASE_TIME, 1.0))
+            .indicators
+            .is_none()
+    );
+
+    // Clôture: la bougie stockée est diffusée avec le RSI complet, puis la
+    // bougie suivante repart de l'état avancé
+    let mut last_rowid: i64 = db
+        .connection()
+        .query_row("SELECT MAX(rowid) FROM candlesticks", [], |row| row.get(0))
+        .unwrap();
+    db.insert_candles(&[stored_close(20, closes[20])]).unwrap();
+    manager
+        .poll_new_candles(db.connection(), &mut last_rowid)
+        .unwrap();
+    let closed = receiver.try_recv().unwrap();
+    assert!((closed.indicators.unwrap()["rsi_14"] - full[20].unwrap()).abs() < 1e-9);
+
+    manager.publish_partial(partial(
+        "BTCUSDT",
+        "5m",
+        BASE_TIME + 21 * 300_000,
+        closes[21],
+    ));
+    let next = receiver.try_recv().unwrap();
+    assert!((next.indicators.unwrap()["rsi_14"] - full[21].unwrap()).abs() < 1e-9);
+}
@@ -0,0 +1,211 @@
+/// Tests des timeframes (utils::parse_timeframe, round_to_timeframe) et du rééchantillonnage
+/// vers un timeframe arbitraire (utils::resample_candles)
+use rust_candles_retriever::error::CandleError;
+use rust_candles_retriever::models::CandleRecord;
+use rust_candles_retriever::utils::{
+    ALL_TIMEFRAMES, compare_timeframes, is_valid_timeframe, next_candle_time, parse_timeframe,
+    period_start, resample_candles, round_to_timeframe, timeframe_ordering,
+};
+use std::cmp::Ordering;
+
+const BASE_TIME: i64 = 1704067200000; // 2024-01-01 00:00 UTC (lundi)
+const FIVE_MINUTES: i64 = 300_000;
+const TWO_HOURS: i64 = 7_200_000;
+
+/// Bougie 5m n° index depuis `start`, close = index
+fn candle(start: i64, index: i64) -> CandleRecord {
+    let open_time = start + index * FIVE_MINUTES;
+    CandleRecord {
+        provider: "binance".to_string(),
+        symbol: "BTCUSDT".to_string(),
+        timeframe: "5m".to_string(),
+        open_time,
+        open: index as f64,
+        high: index as f64 + 10.0,
+        low: index as f64 - 10.0,
+        close: index as f64 + 0.5,
+        volume: 1.0,
+        close_time: open_time + FIVE_MINUTES - 1,
+        quote_asset_volume: 100.0,
+        number_of_trades: 2,
+        taker_buy_base_asset_volume: 0.5,
+        taker_buy_quote_asset_volume: 50.0,
+        interpolated: 0,
+        derived_from: None,
+    }
+}
+
+#[test]
+fn five_minutes_to_two_hours() {
+    let source: Vec<CandleRecord> = (0..48).map(|i| candle(BASE_TIME, i)).collect();
+    let resampled = resample_candles(&source, "5m", "2h").unwrap();
+
+    assert_eq!(resampled.len(), 2);
+    let first = &resampled[0];
+    assert_eq!(first.timeframe, "2h");
+    assert_eq!(first.open_time, BASE_TIME);
+    assert_eq!(first.close_time, BASE_TIME + TWO_HOURS - 1);
+    assert_eq!(first.open, 0.0);
+    assert_eq!(first.close, 23.5);
+    assert_eq!(first.high, 33.0);
+    assert_eq!(first.low, -10.0);
+    assert_eq!(first.volume, 24.0);
+    assert_eq!(first.number_of_trades, 48);
+    assert_eq!(first.derived_from.as_deref(), Some("5m"));
+    assert_eq!(resampled[1].open_time, BASE_TIME + TWO_HOURS);
+}
+
+#[test]
+fn periods_start_on_utc_boundaries() {
+    // Première bougie à 01:15: la période 2h commence à 00:00, pas à 01:15
+    let start = BASE_TIME + 75 * 60_000;
+    let source: Vec<CandleRecord> = (0..12).map(|i| candle(start, i)).collect();
+    let resampled = resample_candles(&source, "5m", "2h").unwrap();
+
+    assert_eq!(resampled.len(), 2);
+    assert_eq!(resampled[0].open_time, BASE_TIME);
+    assert_eq!(resampled[0].volume, 9.0); // 01:15 → 01:55
+    assert_eq!(resampled[1].open_time, BASE_TIME + TWO_HOURS);
+}
+
+#[test]
+fn weeks_start_on_monday() {
+    let thursday = BASE_TIME + 3 * 86_400_000;
+    assert_eq!(period_start(thursday, 604_800_000), BASE_TIME);
+    assert_eq!(period_start(BASE_TIME, 604_800_000), BASE_TIME);
+}
+
+#[test]
+fn synthetic_sources_mark_the_aggregate() {
+    let mut source: Vec<CandleRecord> = (0..24).map(|i| candle(BASE_TIME, i)).collect();
+    source[5].interpolated = 2;
+    let resampled = resample_candles(&source, "5m", "2h").unwrap();
+    assert_eq!(resampled[0].interpolated, 2);
+}
+
+#[test]
+fn target_must_be_an_integer_multiple() {
+    let source = vec![candle(BASE_TIME, 0)];
+
+    for (source_tf, target_tf) in [("2h", "3d"), ("8h", "1d"), ("1d", "1w")] {
+        assert!(resample_candles(&source, source_tf, target_tf).is_ok());
+    }
+    // Égal, inférieur, non multiple (3d → 1w), inconnu, mois de durée variable
+    for (source_tf, target_tf) in [
+        ("5m", "5m"),
+        ("1h", "5m"),
+        ("3d", "1w"),
+        ("5m", "7m"),
+        ("4h", "1M"),
+    ] {
+        assert!(matches!(
+            resample_candles(&source, source_tf, target_tf),
+            Err(CandleError::ValidationError(_))
+        ));
+    }
+}
+
+#[test]
+fn empty_source_gives_empty_result() {
+    assert!(resample_candles(&[], "5m", "1h").unwrap().is_empty());
+}
+
+#[test]
+fn known_timeframes_parse_in_increasing_order() {
+    assert_eq!(parse_timeframe("5m").unwrap(), FIVE_MINUTES);
+    assert_eq!(parse_timeframe("2h").unwrap(), TWO_HOURS);
+
+    let intervals: Vec<i64> = ALL_TIMEFRAMES
+        .iter()
+        .map(|tf| parse_timeframe(tf).unwrap())
+        .collect();
+    assert!(intervals.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn unknown_timeframes_are_rejected() {
+    for typo in ["25m", "1hour", "5M", "", " 5m"] {
+        assert!(
+            matches!(parse_timeframe(typo), Err(CandleError::ValidationError(_))),
+            "{:?}",
+            typo
+        );
+        assert!(!is_valid_timeframe(typo));
+    }
+    assert!(is_valid_timeframe("1M"));
+}
+
+#[test]
+fn timestamps_snap_to_the_containing_candle() {
+    let mid_candle = BASE_TIME + 2 * FIVE_MINUTES + 59_999;
+    assert_eq!(
+        round_to_timeframe(mid_candle, "5m").unwrap(),
+        BASE_TIME + 2 * FIVE_MINUTES
+    );
+    assert_eq!(round_to_timeframe(BASE_TIME, "5m").unwrap(), BASE_TIME);
+    assert_eq!(round_to_timeframe(mid_candle, "2h").unwrap(), BASE_TIME);
+    assert_eq!(
+        next_candle_time(mid_candle, "5m").unwrap(),
+        BASE_TIME + 3 * FIVE_MINUTES
+    );
+
+    // Semaine du lundi 2024-01-01, mois calendaires pour 1M
+    let wednesday = BASE_TIME + 2 * 86_400_000;
+    assert_eq!(round_to_timeframe(wednesday, "1w").unwrap(), BASE_TIME);
+    let february_15 = 1707955200000; // 2024-02-15 00:00 UTC
+    assert_eq!(
+        round_to_timeframe(february_15, "1M").unwrap(),
+        1706745600000
+    );
+    assert_eq!(next_candle_time(february_15, "1M").unwrap(), 1709251200000);
+
+    assert!(matches!(
+        round_to_timeframe(BASE_TIME, "25m"),
+        Err(CandleError::ValidationError(_))
+    ));
+}
+
+#[test]
+fn extreme_timestamps_are_rejected_without_overflow() {
+    // Cas du fuzzing (fuzz/fuzz_targets/fuzz_timeframe.rs): 1w décale le
+    // timestamp, next_candle_time ajoute un intervalle
+    for timestamp in [i64::MIN, i64::MIN + 1, i64::MAX - 1, i64::MAX] {
+        for timeframe in ["1m", "1w", "1M"] {
+            assert!(matches!(
+                round_to_timeframe(timestamp, timeframe),
+                Err(CandleError::ParseError(_))
+            ));
+            assert!(matches!(
+                next_candle_time(timestamp, timeframe),
+                Err(CandleError::ParseError(_))
+            ));
+        }
+    }
+}
+
+#[test]
+fn timeframe_ordering_is_strictly_increasing() {
+    let ordering = timeframe_ordering();
+    assert_eq!(ordering.first(), Some(&"1m"));
+    assert_eq!(ordering.last(), Some(&"1M"));
+
+    for pair in ordering.windows(2) {
+        assert!(
+            parse_timeframe(pair[0]).unwrap() < parse_timeframe(pair[1]).unwrap(),
+            "{} < {}",
+            pair[0],
+            pair[1]
+        );
+        assert_eq!(compare_timeframes(pair[0], pair[1]), Ordering::Less);
+    }
+}
+
+#[test]
+fn timeframes_sort_by_duration_unknown_last() {
+    let mut timeframes = vec!["1d", "12h", "bogus", "15m", "1M", "1h", "1w"];
+    timeframes.sort_by(|a, b| compare_timeframes(a, b));
+    assert_eq!(
+        timeframes,
+        vec!["15m", "1h", "12h", "1d", "1w", "1M", "bogus"]
+    );
+}
@@ -0,0 +1,128 @@
+/// Tests du mode de reprise (anciens binaires test_resume_mode et test_isolation)
+///
+/// - Première exécution: aucune bougie, aucun point de reprise
+/// - Reprise: bornes stockées et point de reprise de chaque série
+/// - Isolation: chaque (provider, symbol, timeframe) a ses propres bornes
+mod testutil;
+
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::timeframe_status::TimeframeStatus;
+use testutil::{BASE_TIME, PROVIDER, candle, insert_series, memory_db};
+
+/// Insère une série complète et enregistre la progression comme le retriever
+fn backfill(db: &mut DatabaseManager, provider: &str, symbol: &str, timeframe: &str, count: i64) {
+    let mut candles = testutil::series(symbol, timeframe, BASE_TIME, count, &[]);
+    for c in &mut candles {
+        c.provider = provider.to_string();
+    }
+    db.insert_candles(&candles).unwrap();
+    TimeframeStatus::update_progress(
+        db.connection(),
+        provider,
+        symbol,
+        timeframe,
+        candles[0].open_time,
+        candles[candles.len() - 1].open_time,
+    )
+    .unwrap();
+}
+
+/// (dernière bougie stockée, point de reprise du backfill)
+fn bounds(
+    db: &DatabaseManager,
+    provider: &str,
+    symbol: &str,
+    timeframe: &str,
+) -> (Option<i64>, Option<i64>) {
+    (
+        db.stats(provider, symbol, timeframe)
+            .unwrap()
+            .last_open_time,
+        TimeframeStatus::get_last_candle_time(db.connection(), provider, symbol, timeframe),
+    )
+}
+
+#[test]
+fn first_run_has_nothing_to_resume() {
+    let db = memory_db();
+    assert_eq!(bounds(&db, PROVIDER, "BTCUSDT", "5m"), (None, None));
+}
+
+#[test]
+fn resume_uses_stored_bounds_of_each_timeframe() {
+    let mut db = memory_db();
+    insert_series(&mut db, "BTCUSDT", "5m", BASE_TIME, 2, &[]);
+    db.insert_candles(&[candle("BTCUSDT", "15m", BASE_TIME, 50_500.0)])
+        .unwrap();
+
+    assert_eq!(
+        db.stats(PROVIDER, "BTCUSDT", "5m").unwrap().last_open_time,
+        Some(BASE_TIME + 300_000)
+    );
+    assert_eq!(
+        db.stats(PROVIDER, "BTCUSDT", "15m").unwrap().last_open_time,
+        Some(BASE_TIME)
+    );
+    assert_eq!(
+        db.stats(PROVIDER, "BTCUSDT", "1h").unwrap().last_open_time,
+        None
+    );
+
+    // Le backfill remonte dans le temps: il reprend à la plus ancienne bougie
+    TimeframeStatus::update_progress(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "5m",
+        BASE_TIME,
+        BASE_TIME + 300_000,
+    )
+    .unwrap();
+    assert_eq!(
+        bounds(&db, PROVIDER, "BTCUSDT", "5m"),
+        (Some(BASE_TIME + 300_000), Some(BASE_TIME))
+    );
+}
+
+#[test]
+fn each_series_is_isolated() {
+    let mut db = memory_db();
+    backfill(&mut db, PROVIDER, "BTCUSDT", "5m", 11);
+    backfill(&mut db, PROVIDER, "BTCUSDT", "15m", 6);
+    backfill(&mut db, PROVIDER, "ETHUSDT", "5m", 8);
+    backfill(&mut db, PROVIDER, "ETHUSDT", "15m", 4);
+    backfill(&mut db, "other", "BTCUSDT", "5m", 3);
+
+    let expected = [
+        (PROVIDER, "BTCUSDT", "5m", Some(BASE_TIME + 10 * 300_000)),
+        (PROVIDER, "BTCUSDT", "15m", Some(BASE_TIME + 5 * 900_000)),
+        (PROVIDER, "ETHUSDT", "5m", Some(BASE_TIME + 7 * 300_000)),
+        (PROVIDER, "ETHUSDT", "15m", Some(BASE_TIME + 3 * 900_000)),
+        ("other", "BTCUSDT", "5m", Some(BASE_TIME + 2 * 300_000)),
+        (PROVIDER, "BTCUSDT", "1h", None),
+        (PROVIDER, "SOLUSDT", "5m", None),
+    ];
+    for (provider, symbol, timeframe, last) in expected {
+        let resume_point = last.map(|_| BASE_TIME);
+        assert_eq!(
+            bounds(&db, provider, symbol, timeframe),
+            (last, resume_point),
+            "{}/{}/{}",
+            provider,
+            symbol,
+            timeframe
+        );
+    }
+}
+
+#[test]
+fn gaps_do_not_move_stored_bounds() {
+    let mut db = memory_db();
+    let candles = insert_series(&mut db, "BTCUSDT", "5m", BASE_TIME, 10, &[0, 4, 5]);
+    assert_eq!(candles.len(), 7);
+
+    let stats = db.stats(PROVIDER, "BTCUSDT", "5m").unwrap();
+    assert_eq!(stats.first_open_time, Some(BASE_TIME + 300_000));
+    assert_eq!(stats.last_open_time, Some(BASE_TIME + 9 * 300_000));
+    assert_eq!(stats.expected_count - stats.count, 2);
+}
@@ -0,0 +1,151 @@
+/// Tests d'intégration de la purge de rétention sur une base en mémoire
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::error::CandleError;
+use rust_candles_retriever::models::CandleRecord;
+use rust_candles_retriever::retention::{RetentionPolicy, parse_retention};
+use rust_candles_retriever::timeframe_status::TimeframeStatus;
+use std::collections::BTreeMap;
+
+const BASE_TIME: i64 = 1704067200000; // 2024-01-01 00:00 UTC
+const DAY: i64 = 86_400_000;
+
+fn candle(symbol: &str, timeframe: &str, open_time: i64) -> CandleRecord {
+    CandleRecord {
+        provider: "binance".to_string(),
+        symbol: symbol.to_string(),
+        timeframe: timeframe.to_string(),
+        open_time,
+        open: 1.0,
+        high: 1.0,
+        low: 1.0,
+        close: 1.0,
+        volume: 1.0,
+        close_time: open_time + 59_999,
+        quote_asset_volume: 0.0,
+        number_of_trades: 0,
+        taker_buy_base_asset_volume: 0.0,
+        taker_buy_quote_asset_volume: 0.0,
+        interpolated: 0,
+        derived_from: None,
+    }
+}
+
+/// 10 jours de bougies (une par jour) pour BTCUSDT 1m, BTCUSDT 1d et ETHUSDT 1m
+fn setup() -> DatabaseManager {
+    let mut db = DatabaseManager::new(":memory:").unwrap();
+    for (symbol, timeframe) in [("BTCUSDT", "1m"), ("BTCUSDT", "1d"), ("ETHUSDT", "1m")] {
+        let candles: Vec<CandleRecord> = (0..10)
+            .map(|day| candle(symbol, timeframe, BASE_TIME + day * DAY))
+            .collect();
+        db.insert_candles(&candles).unwrap();
+        TimeframeStatus::update_progress(
+            db.connection(),
+            "binance",
+            symbol,
+            timeframe,
+            BASE_TIME,
+            BASE_TIME + 9 * DAY,
+        )
+        .unwrap();
+    }
+    db
+}
+
+fn count(db: &DatabaseManager, symbol: &str, timeframe: &str) -> usize {
+    db.query_candles("binance", symbol, timeframe, 0..=i64::MAX, None)
+        .unwrap()
+        .len()
+}
+
+#[test]
+fn prune_deletes_only_older_candles_of_the_series() {
+    let mut db = setup();
+    assert_eq!(count(&db, "BTCUSDT", "1m"), 10);
+
+    // Cutoff sur une bougie existante: elle est conservée
+    let deleted = db
+        .prune("binance", "BTCUSDT", "1m", BASE_TIME + 4 * DAY)
+        .unwrap();
+
+    assert_eq!(deleted, 4);
+    assert_eq!(count(&db, "BTCUSDT", "1m"), 6);
+    assert_eq!(count(&db, "BTCUSDT", "1d"), 10);
+    assert_eq!(count(&db, "ETHUSDT", "1m"), 10);
+
+    let remaining = db
+        .query_candles("binance", "BTCUSDT", "1m", 0..=i64::MAX, Some(1))
+        .unwrap();
+    assert_eq!(remaining[0].open_time, BASE_TIME + 4 * DAY);
+}
+
+#[test]
+fn prune_moves_oldest_candle_time() {
+    let mut db = setup();
+    db.prune("binance", "BTCUSDT", "1m", BASE_TIME + 4 * DAY)
+        .unwrap();
+
+    let progress = |symbol, timeframe| {
+        TimeframeStatus::get(db.connection(), "binance", symbol, timeframe)
+            .unwrap()
+            .unwrap()
+    };
+    assert_eq!(
+        progress("BTCUSDT", "1m").oldest_candle_time,
+        Some(BASE_TIME + 4 * DAY)
+    );
+    assert_eq!(
+        progress("BTCUSDT", "1m").newest_candle_time,
+        Some(BASE_TIME + 9 * DAY)
+    );
+    assert_eq!(
+        progress("BTCUSDT", "1d").oldest_candle_time,
+        Some(BASE_TIME)
+    );
+}
+
+#[test]
+fn policy_prunes_configured_timeframes_for_all_symbols() {
+    let mut db = setup();
+    let rules = BTreeMap::from([("1m".to_string(), "3d".to_string())]);
+    let policy = RetentionPolicy::from_config(&rules).unwrap();
+
+    let now = BASE_TIME + 10 * DAY;
+    let reports = policy
+        .apply(db.connection_mut(), "binance", None, now)
+        .unwrap();
+
+    assert_eq!(reports.len(), 2);
+    assert!(
+        reports
+            .iter()
+            .all(|r| r.deleted == 7 && r.cutoff == now - 3 * DAY)
+    );
+    assert_eq!(count(&db, "BTCUSDT", "1m"), 3);
+    assert_eq!(count(&db, "ETHUSDT", "1m"), 3);
+    assert_eq!(count(&db, "BTCUSDT", "1d"), 10);
+
+    // Deuxième passage: plus rien à purger
+    let reports = policy
+        .apply(db.connection_mut(), "binance", Some("BTCUSDT"), now)
+        .unwrap();
+    assert_eq!(reports[0].deleted, 0);
+}
+
+#[test]
+fn retention_durations_are_parsed() {
+    assert_eq!(parse_retention("90d").unwrap(), 90 * DAY);
+    assert_eq!(parse_retention("2w").unwrap(), 14 * DAY);
+    assert_eq!(parse_retention("48h").unwrap(), 2 * DAY);
+    for invalid in ["", "d", "90", "-1d", "0d", "10y", "1é"] {
+        assert!(matches!(
+            parse_retention(invalid),
+            Err(CandleError::ParseError(_))
+        ));
+    }
+
+    let unknown = BTreeMap::from([("7m".to_string(), "1d".to_string())]);
+    assert!(matches!(
+        RetentionPolicy::from_config(&unknown),
+        Err(CandleError::ValidationError(_))
+    ));
+}
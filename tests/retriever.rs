@@ -0,0 +1,143 @@
+/// Tests du backfill de CandleRetriever sur une source de klines simulée
+///
+/// - Deux batches de 100 bougies (MockKlineProvider): 200 lignes insérées
+/// - Source épuisée: timeframe épuisé, aucune insertion
+/// - IndicatorRegistry (RSI + EMA): valeurs de chaque batch enregistrées,
+///   bougies qui suivent un batch plus ancien recalculées, identiques au
+///   calcul sur toute la série
+mod testutil;
+
+use binance::model::KlineSummary;
+use rust_candles_retriever::circuit_breaker::CircuitBreaker;
+use rust_candles_retriever::gap_filler::FillStrategy;
+use rust_candles_retriever::indicators::moving_average::{Ema, ema};
+use rust_candles_retriever::indicators::registry::IndicatorRegistry;
+use rust_candles_retriever::indicators::rsi::Rsi;
+use rust_candles_retriever::indicators::values::indicator_values;
+use rust_candles_retriever::kline_provider::MockKlineProvider;
+use rust_candles_retriever::rate_limiter::RateLimiter;
+use rust_candles_retriever::retriever::CandleRetriever;
+use rust_candles_retriever::timeframe_status::TimeframeStatus;
+use std::sync::Arc;
+use std::time::Duration;
+use testutil::{BASE_TIME, PROVIDER, memory_db};
+
+const INTERVAL: i64 = 300_000; // 5 minutes
+
+/// `count` klines 5m consécutives depuis la position `start` (prix en texte)
+fn klines(start: i64, count: i64) -> Vec<KlineSummary> {
+    (start..start + count)
+        .map(|position| {
+            let open_time = BASE_TIME + position * INTERVAL;
+            let price = (50_000 + position).to_string();
+            KlineSummary {
+                open_time,
+                open: price.clone(),
+                high: price.clone(),
+                low: price.clone(),
+                close: price,
+                volume: "1.5".to_string(),
+                close_time: open_time + INTERVAL - 1,
+                quote_asset_volume: "75000".to_string(),
+                number_of_trades: 42,
+                taker_buy_base_asset_volume: "0.7".to_string(),
+                taker_buy_quote_asset_volume: "35000".to_string(),
+            }
+        })
+        .collect()
+}
+
+fn mocked_retriever<'a>(
+    provider: &'a MockKlineProvider,
+    conn: &'a mut rusqlite::Connection,
+) -> CandleRetriever<'a, MockKlineProvider> {
+    CandleRetriever::new(
+        provider,
+        conn,
+        "BTCUSDT",
+        "5m",
+        None,
+        Arc::new(RateLimiter::new(6000)),
+        Arc::new(CircuitBreaker::new(5, Duration::from_secs(60))),
+        FillStrategy::Linear,
+    )
+}
+
+#[test]
+fn two_mocked_batches_insert_200_candles() {
+    let mut db = memory_db();
+    // Backfill en arrière: le batch le plus récent d'abord
+    let provider = MockKlineProvider::new([klines(100, 100), klines(0, 100)]);
+
+    let mut retriever = mocked_retriever(&provider, db.connection_mut());
+    assert_eq!(retriever.fetch_one_batch().unwrap(), (100, false));
+    assert_eq!(retriever.fetch_one_batch().unwrap(), (100, false));
+    assert_eq!(provider.remaining(), 0);
+
+    assert_eq!(db.stats(PROVIDER, "BTCUSDT", "5m").unwrap().count, 200);
+    assert_eq!(
+        TimeframeStatus::get_last_candle_time(db.connection(), PROVIDER, "BTCUSDT", "5m"),
+        Some(BASE_TIME)
+    );
+}
+
+#[test]
+fn drained_provider_exhausts_the_timeframe() {
+    let mut db = memory_db();
+    let provider = MockKlineProvider::new([klines(0, 10)]);
+
+    let mut retriever = mocked_retriever(&provider, db.connection_mut());
+    assert_eq!(retriever.fetch_one_batch().unwrap(), (10, false));
+    assert_eq!(retriever.fetch_one_batch().unwrap(), (0, true));
+
+    // Batch déjà stocké: aucune insertion, épuisé
+    provider.push_batch(klines(0, 10));
+    let mut retriever = mocked_retriever(&provider, db.connection_mut());
+    assert_eq!(retriever.fetch_one_batch().unwrap(), (0, true));
+}
+
+#[test]
+fn indicators_follow_each_batch() {
+    let mut db = memory_db();
+    let registry =
+        IndicatorRegistry::from_specs(&["rsi:14".parse().unwrap(), "ema:20".parse().unwrap()])
+            .unwrap();
+    let provider = MockKlineProvider::new([klines(1000, 1000), klines(0, 1000)]);
+
+    let mut retriever = mocked_retriever(&provider, db.connection_mut()).indicators(&registry);
+    assert_eq!(retriever.fetch_one_batch().unwrap(), (1000, false));
+    assert_eq!(retriever.fetch_one_batch().unwrap(), (1000, false));
+
+    // Le second batch (plus ancien) complète la chauffe des bougies 1000+
+    let closes: Vec<f64> = (0..2000)
+        .map(|position| (50_000 + position) as f64)
+        .collect();
+    let stored_ema = indicator_values(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "5m",
+        &Ema { period: 20 },
+        0..=i64::MAX,
+    )
+    .unwrap();
+    let expected: Vec<f64> = ema(&closes, 20).into_iter().flatten().collect();
+    assert_eq!(stored_ema.len(), expected.len());
+    for (value, expected) in stored_ema.iter().zip(&expected) {
+        assert!((value.value - expected).abs() < 1e-6);
+    }
+    assert_eq!(stored_ema[0].open_time, BASE_TIME + 19 * INTERVAL);
+
+    // Hausse continue: RSI à 100 dès la 15e bougie
+    let stored_rsi = indicator_values(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "5m",
+        &Rsi { period: 14 },
+        0..=i64::MAX,
+    )
+    .unwrap();
+    assert_eq!(stored_rsi.len(), 2000 - 14);
+    assert!(stored_rsi.iter().all(|v| v.value == 100.0));
+}
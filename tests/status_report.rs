@@ -0,0 +1,114 @@
+/// Tests de l'état des bases (status_report, binaire status)
+///
+/// - Instantané du tableau sur un répertoire de fixtures généré
+/// - Filtre --symbol et sortie JSON
+/// - Dernier passage de vérification enregistré (verify_data --record)
+mod testutil;
+
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::status_report::{directory_status, render_table};
+use rust_candles_retriever::timeframe_status::TimeframeStatus;
+use rust_candles_retriever::verification_history::VerificationHistory;
+use rust_candles_retriever::verify::verify_spacing;
+use std::path::Path;
+use testutil::{BASE_TIME, PROVIDER, TempDir, insert_series};
+
+/// 2024-01-02 03:04:05 UTC, last_updated figé pour l'instantané
+const LAST_UPDATED: i64 = 1704164645000;
+
+/// Fixtures: BTCUSDT.db (5m troué, complet et vérifié, 1h sans statut),
+/// ETHUSDT.db (5m), broken.db (pas une base SQLite), notes.txt (ignoré)
+fn fixture_dir(dir: &Path) {
+    let mut btc = DatabaseManager::new(dir.join("BTCUSDT.db").to_str().unwrap()).unwrap();
+    let mut candles = testutil::series("BTCUSDT", "5m", BASE_TIME, 12, &[4, 5, 9]);
+    candles[2].interpolated = 1;
+    btc.insert_candles(&candles).unwrap();
+    TimeframeStatus::update_progress(
+        btc.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "5m",
+        BASE_TIME,
+        BASE_TIME + 11 * 300_000,
+    )
+    .unwrap();
+    TimeframeStatus::mark_complete(btc.connection(), PROVIDER, "BTCUSDT", "5m").unwrap();
+    insert_series(&mut btc, "BTCUSDT", "1h", BASE_TIME, 3, &[]);
+    let report = verify_spacing(btc.connection(), PROVIDER, "BTCUSDT", "5m").unwrap();
+    VerificationHistory::record(btc.connection(), PROVIDER, "BTCUSDT", &report, LAST_UPDATED)
+        .unwrap();
+
+    let mut eth = DatabaseManager::new(dir.join("ETHUSDT.db").to_str().unwrap()).unwrap();
+    let candles = insert_series(&mut eth, "ETHUSDT", "5m", BASE_TIME, 5, &[]);
+    TimeframeStatus::update_progress(
+        eth.connection(),
+        PROVIDER,
+        "ETHUSDT",
+        "5m",
+        candles[0].open_time,
+        candles[4].open_time,
+    )
+    .unwrap();
+
+    for db in [&btc, &eth] {
+        db.connection()
+            .execute(
+                "UPDATE timeframe_status SET last_updated = ?1",
+                [LAST_UPDATED],
+            )
+            .unwrap();
+    }
+
+    std::fs::write(dir.join("broken.db"), "pas une base").unwrap();
+    std::fs::write(dir.join("notes.txt"), "ignoré").unwrap();
+}
+
+#[test]
+fn table_snapshot_of_fixture_directory() {
+    let dir = TempDir::new("candles_status_snapshot");
+    fixture_dir(&dir.0);
+
+    let files = directory_status(&dir.0, None).unwrap();
+    let table = render_table(&files).replace(&dir.0.display().to_string(), "<dir>");
+
+    let expected = "\
+<dir>/BTCUSDT.db — BTCUSDT (binance)
+TF   | Début               | Fin                 |   Bougies |  Interp. | Manquantes | Complet | Mis à jour          | Vérification
+---------------------------------------------------------------------------------------------------------------------------------------------
+5m   | 2023-11-14 22:13:20 | 2023-11-14 23:08:20 |         9 |        1 |          3 | oui     | 2024-01-02 03:04:05 | 2024-01-02 03:04:05 2 gaps
+1h   | 2023-11-14 22:13:20 | 2023-11-15 00:13:20 |         3 |        0 |          0 | non     | -                   | -
+
+<dir>/ETHUSDT.db — ETHUSDT (binance)
+TF   | Début               | Fin                 |   Bougies |  Interp. | Manquantes | Complet | Mis à jour          | Vérification
+---------------------------------------------------------------------------------------------------------------------------------------------
+5m   | 2023-11-14 22:13:20 | 2023-11-14 22:33:20 |         5 |        0 |          0 | non     | 2024-01-02 03:04:05 | -
+
+✗ <dir>/broken.db: Erreur base de données: file is not a database
+";
+    assert_eq!(table, expected);
+}
+
+#[test]
+fn symbol_filter_and_json_output() {
+    let dir = TempDir::new("candles_status_filter");
+    fixture_dir(&dir.0);
+
+    let files = directory_status(&dir.0, Some("ETHUSDT")).unwrap();
+    let counts: Vec<_> = files.iter().map(|f| f.series.len()).collect();
+    assert_eq!(counts, vec![0, 1, 0]);
+
+    let json = serde_json::to_value(&files[1]).unwrap();
+    let series = &json["series"][0];
+    assert_eq!(series["symbol"], "ETHUSDT");
+    assert_eq!(series["count"], 5);
+    assert_eq!(series["missing_count"], 0);
+    assert_eq!(series["is_complete"], false);
+    assert_eq!(series["last_updated"], LAST_UPDATED);
+    assert!(series["last_verification"].is_null());
+
+    let files = directory_status(&dir.0, Some("BTCUSDT")).unwrap();
+    let run = &serde_json::to_value(&files[0]).unwrap()["series"][0]["last_verification"];
+    assert_eq!(run["run_at"], LAST_UPDATED);
+    assert_eq!(run["gaps"], 2);
+    assert_eq!(run["interpolated_count"], 1);
+}
@@ -0,0 +1,105 @@
+#![allow(dead_code)]
+/// Fixtures partagées des tests d'intégration
+///
+/// - memory_db: base en mémoire avec le schéma complet (migrations)
+/// - series: série de bougies réelles régulières, avec trous optionnels
+/// - TempDir: répertoire temporaire de fichiers .db
+///
+/// NOTE: chaque fichier de tests/ compile ce module séparément
+/// (mod testutil;), d'où le allow(dead_code)
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::models::CandleRecord;
+use rust_candles_retriever::utils::parse_timeframe;
+use std::path::PathBuf;
+
+pub const PROVIDER: &str = "binance";
+pub const BASE_TIME: i64 = 1700000000000; // 2023-11-14 22:13:20 UTC
+
+/// Base en mémoire prête à l'emploi
+pub fn memory_db() -> DatabaseManager {
+    DatabaseManager::new_in_memory().expect("base en mémoire")
+}
+
+/// Bougie réelle du provider PROVIDER, prix dérivés de `close`
+pub fn candle(symbol: &str, timeframe: &str, open_time: i64, close: f64) -> CandleRecord {
+    let interval = parse_timeframe(timeframe).unwrap();
+    CandleRecord {
+        provider: PROVIDER.to_string(),
+        symbol: symbol.to_string(),
+        timeframe: timeframe.to_string(),
+        open_time,
+        open: close - 100.0,
+        high: close + 300.0,
+        low: close - 500.0,
+        close,
+        volume: 100.0,
+        close_time: open_time + interval - 1,
+        quote_asset_volume: close * 100.0,
+        number_of_trades: 1000,
+        taker_buy_base_asset_volume: 50.0,
+        taker_buy_quote_asset_volume: close * 50.0,
+        interpolated: 0,
+        derived_from: None,
+    }
+}
+
+/// `count` positions régulières depuis `start`, sans les positions de `gaps`
+///
+/// EXEMPLE: series("BTCUSDT", "5m", BASE_TIME, 5, &[2]) → positions 0, 1, 3, 4
+/// (close = 50000 + position)
+pub fn series(
+    symbol: &str,
+    timeframe: &str,
+    start: i64,
+    count: i64,
+    gaps: &[i64],
+) -> Vec<CandleRecord> {
+    let interval = parse_timeframe(timeframe).unwrap();
+    (0..count)
+        .filter(|position| !gaps.contains(position))
+        .map(|position| {
+            candle(
+                symbol,
+                timeframe,
+                start + position * interval,
+                50_000.0 + position as f64,
+            )
+        })
+        .collect()
+}
+
+/// Insère une série (voir series) et retourne les bougies insérées
+pub fn insert_series(
+    db: &mut DatabaseManager,
+    symbol: &str,
+    timeframe: &str,
+    start: i64,
+    count: i64,
+    gaps: &[i64],
+) -> Vec<CandleRecord> {
+    let candles = series(symbol, timeframe, start, count, gaps);
+    db.insert_candles(&candles).expect("insertion");
+    candles
+}
+
+/// Répertoire temporaire supprimé à la fin du test
+pub struct TempDir(pub PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+
+    pub fn file(&self, name: &str) -> PathBuf {
+        self.0.join(name)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
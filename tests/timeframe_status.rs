@@ -0,0 +1,575 @@
+/// Tests d'intégration de TimeframeStatus sur le schéma canonique
+///
+/// Toutes les méthodes s'exécutent sur une base créée par DatabaseManager
+/// (migrations), pour détecter toute requête visant une colonne absente.
+/// Reprend aussi l'ancien binaire test_timeframe_completion; list_all et
+/// list_incomplete (ordre de priorité) sur plusieurs symboles
+mod testutil;
+
+use rust_candles_retriever::database::DatabaseManager;
+use rust_candles_retriever::gap_filler::GapFiller;
+use rust_candles_retriever::timeframe_status::{Coverage, TimeframeStatus};
+use std::time::Duration;
+use testutil::insert_series;
+
+const PROVIDER: &str = "binance";
+const SYMBOL: &str = "BTCUSDT";
+const BASE_TIME: i64 = 1704067200000; // 2024-01-01 00:00 UTC
+const INTERVAL: i64 = 300_000; // 5 minutes
+
+fn setup() -> DatabaseManager {
+    testutil::memory_db()
+}
+
+#[test]
+fn unknown_timeframe_has_no_status() {
+    let db = setup();
+    let conn = db.connection();
+
+    assert_eq!(
+        TimeframeStatus::get(conn, PROVIDER, SYMBOL, "5m").unwrap(),
+        None
+    );
+    assert!(!TimeframeStatus::is_complete(conn, PROVIDER, SYMBOL, "5m").unwrap());
+    assert_eq!(
+        TimeframeStatus::get_last_candle_time(conn, PROVIDER, SYMBOL, "5m"),
+        None
+    );
+    assert_eq!(
+        TimeframeStatus::expected_bounds(conn, PROVIDER, SYMBOL, "5m", BASE_TIME),
+        None
+    );
+}
+
+#[test]
+fn progress_tracks_both_bounds() {
+    let db = setup();
+    let conn = db.connection();
+
+    // Backfill: les batches remontent dans le temps
+    let newest = BASE_TIME + 999 * INTERVAL;
+    TimeframeStatus::update_progress(
+        conn,
+        PROVIDER,
+        SYMBOL,
+        "5m",
+        BASE_TIME + 500 * INTERVAL,
+        newest,
+    )
+    .unwrap();
+    TimeframeStatus::update_progress(
+        conn,
+        PROVIDER,
+        SYMBOL,
+        "5m",
+        BASE_TIME,
+        BASE_TIME + 499 * INTERVAL,
+    )
+    .unwrap();
+
+    let progress = TimeframeStatus::get(conn, PROVIDER, SYMBOL, "5m")
+        .unwrap()
+        .expect("statut enregistré");
+    assert_eq!(progress.oldest_candle_time, Some(BASE_TIME));
+    assert_eq!(progress.newest_candle_time, Some(newest));
+    assert!(!progress.is_complete);
+    assert!(progress.last_updated > 0);
+
+    assert_eq!(
+        TimeframeStatus::get_last_candle_time(conn, PROVIDER, SYMBOL, "5m"),
+        Some(BASE_TIME)
+    );
+    let now = BASE_TIME + 2000 * INTERVAL;
+    assert_eq!(
+        TimeframeStatus::expected_bounds(conn, PROVIDER, SYMBOL, "5m", now),
+        Some((BASE_TIME, now - INTERVAL))
+    );
+}
+
+#[test]
+fn completion_survives_progress_updates() {
+    let db = setup();
+    let conn = db.connection();
+
+    TimeframeStatus::update_progress(conn, PROVIDER, SYMBOL, "5m", BASE_TIME, BASE_TIME).unwrap();
+    TimeframeStatus::mark_complete(conn, PROVIDER, SYMBOL, "5m").unwrap();
+    assert!(TimeframeStatus::is_complete(conn, PROVIDER, SYMBOL, "5m").unwrap());
+
+    // Mise à jour incrémentale suivante: is_complete ne doit pas être réinitialisé
+    TimeframeStatus::update_progress(
+        conn,
+        PROVIDER,
+        SYMBOL,
+        "5m",
+        BASE_TIME,
+        BASE_TIME + INTERVAL,
+    )
+    .unwrap();
+    assert!(TimeframeStatus::is_complete(conn, PROVIDER, SYMBOL, "5m").unwrap());
+
+    // Les autres timeframes ne sont pas concernés
+    assert!(!TimeframeStatus::is_complete(conn, PROVIDER, SYMBOL, "15m").unwrap());
+}
+
+#[test]
+fn mark_complete_without_progress_creates_status() {
+    let db = setup();
+    let conn = db.connection();
+
+    TimeframeStatus::mark_complete(conn, PROVIDER, SYMBOL, "1h").unwrap();
+
+    let progress = TimeframeStatus::get(conn, PROVIDER, SYMBOL, "1h")
+        .unwrap()
+        .expect("statut créé");
+    assert!(progress.is_complete);
+    assert_eq!(progress.oldest_candle_time, None);
+    assert_eq!(progress.newest_candle_time, None);
+}
+
+#[test]
+fn only_marked_timeframes_are_complete() {
+    let mut db = setup();
+    let candles = insert_series(&mut db, SYMBOL, "5m", BASE_TIME, 10, &[]);
+    insert_series(&mut db, SYMBOL, "15m", BASE_TIME, 5, &[]);
+
+    // 5m: limite historique atteinte; 15m: backfill en cours
+    let conn = db.connection();
+    TimeframeStatus::update_progress(
+        conn,
+        PROVIDER,
+        SYMBOL,
+        "5m",
+        candles[0].open_time,
+        candles[9].open_time,
+    )
+    .unwrap();
+    TimeframeStatus::mark_complete(conn, PROVIDER, SYMBOL, "5m").unwrap();
+    TimeframeStatus::update_progress(conn, PROVIDER, SYMBOL, "15m", BASE_TIME, BASE_TIME).unwrap();
+
+    let complete: Vec<bool> = ["5m", "15m", "30m", "1h"]
+        .iter()
+        .map(|tf| TimeframeStatus::is_complete(conn, PROVIDER, SYMBOL, tf).unwrap())
+        .collect();
+    assert_eq!(complete, vec![true, false, false, false]);
+
+    let progress = TimeframeStatus::get(conn, PROVIDER, SYMBOL, "5m")
+        .unwrap()
+        .unwrap();
+    assert_eq!(progress.oldest_candle_time, Some(BASE_TIME));
+    assert_eq!(progress.newest_candle_time, Some(BASE_TIME + 9 * INTERVAL));
+    assert!(progress.is_complete);
+
+    let rows: i64 = conn
+        .query_row("SELECT COUNT(*) FROM timeframe_status", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(rows, 2);
+}
+
+#[test]
+fn coverage_counts_real_and_interpolated_candles() {
+    let mut db = setup();
+    // 10 positions, 2 manquantes, la dernière remplacée par une interpolée
+    let mut candles = testutil::series(SYMBOL, "5m", BASE_TIME, 10, &[3, 4]);
+    candles.last_mut().unwrap().interpolated = 1;
+    db.insert_candles(&candles).unwrap();
+    let conn = db.connection();
+
+    let end = BASE_TIME + 9 * INTERVAL;
+    let report =
+        TimeframeStatus::get_coverage(conn, PROVIDER, SYMBOL, "5m", BASE_TIME, end).unwrap();
+    assert_eq!((report.actual, report.expected), (8, 10));
+    assert_eq!((report.real_count, report.interpolated_count), (7, 1));
+    assert_eq!(report.percentage, 80.0);
+    assert_eq!(
+        TimeframeStatus::get_coverage_percentage(conn, PROVIDER, SYMBOL, "5m", BASE_TIME, end)
+            .unwrap(),
+        80.0
+    );
+
+    // Plage au-delà des données: attendues mais absentes
+    let wider =
+        TimeframeStatus::get_coverage(conn, PROVIDER, SYMBOL, "5m", BASE_TIME, end + 10 * INTERVAL)
+            .unwrap();
+    assert_eq!((wider.actual, wider.expected), (8, 20));
+    assert_eq!(wider.percentage, 40.0);
+
+    // Plage inversée: rien n'est attendu
+    let empty =
+        TimeframeStatus::get_coverage(conn, PROVIDER, SYMBOL, "5m", end, BASE_TIME).unwrap();
+    assert_eq!(
+        (empty.actual, empty.expected, empty.percentage),
+        (0, 0, 0.0)
+    );
+}
+
+#[test]
+fn reset_forces_a_new_backfill() {
+    let db = setup();
+    let conn = db.connection();
+    for timeframe in ["5m", "1h"] {
+        TimeframeStatus::update_progress(
+            conn,
+            PROVIDER,
+            SYMBOL,
+            timeframe,
+            BASE_TIME,
+            BASE_TIME + INTERVAL,
+        )
+        .unwrap();
+        TimeframeStatus::mark_complete(conn, PROVIDER, SYMBOL, timeframe).unwrap();
+    }
+    TimeframeStatus::mark_complete(conn, PROVIDER, "ETHUSDT", "5m").unwrap();
+
+    TimeframeStatus::reset(conn, PROVIDER, SYMBOL, "5m").unwrap();
+    let progress = TimeframeStatus::get(conn, PROVIDER, SYMBOL, "5m")
+        .unwrap()
+        .unwrap();
+    assert!(!progress.is_complete);
+    assert_eq!(progress.oldest_candle_time, None);
+    assert_eq!(progress.newest_candle_time, Some(BASE_TIME + INTERVAL));
+    assert_eq!(
+        TimeframeStatus::get_last_candle_time(conn, PROVIDER, SYMBOL, "5m"),
+        None
+    );
+    assert!(TimeframeStatus::is_complete(conn, PROVIDER, SYMBOL, "1h").unwrap());
+
+    // Timeframe sans statut: aucun effet, aucune ligne créée
+    TimeframeStatus::reset(conn, PROVIDER, SYMBOL, "1d").unwrap();
+    assert_eq!(
+        TimeframeStatus::get(conn, PROVIDER, SYMBOL, "1d").unwrap(),
+        None
+    );
+
+    assert_eq!(
+        TimeframeStatus::reset_all(conn, PROVIDER, SYMBOL).unwrap(),
+        2
+    );
+    assert!(!TimeframeStatus::is_complete(conn, PROVIDER, SYMBOL, "1h").unwrap());
+    assert!(TimeframeStatus::is_complete(conn, PROVIDER, "ETHUSDT", "5m").unwrap());
+    assert_eq!(
+        TimeframeStatus::reset_all(conn, PROVIDER, "SOLUSDT").unwrap(),
+        0
+    );
+}
+
+#[test]
+fn newest_candle_time_follows_progress() {
+    let db = setup();
+    let conn = db.connection();
+    assert_eq!(
+        TimeframeStatus::get_newest_candle_time(conn, PROVIDER, SYMBOL, "5m"),
+        None
+    );
+
+    TimeframeStatus::update_progress(
+        conn,
+        PROVIDER,
+        SYMBOL,
+        "5m",
+        BASE_TIME,
+        BASE_TIME + 10 * INTERVAL,
+    )
+    .unwrap();
+    // Batch plus ancien (backfill): le maximum connu est conservé
+    TimeframeStatus::update_progress(
+        conn,
+        PROVIDER,
+        SYMBOL,
+        "5m",
+        BASE_TIME - 10 * INTERVAL,
+        BASE_TIME,
+    )
+    .unwrap();
+    assert_eq!(
+        TimeframeStatus::get_newest_candle_time(conn, PROVIDER, SYMBOL, "5m"),
+        Some(BASE_TIME + 10 * INTERVAL)
+    );
+    assert_eq!(
+        TimeframeStatus::get_newest_candle_time(conn, PROVIDER, SYMBOL, "1h"),
+        None
+    );
+}
+
+#[test]
+fn list_is_empty_without_status_rows() {
+    let mut db = setup();
+    // Bougies importées sans backfill: aucune ligne de statut
+    insert_series(&mut db, SYMBOL, "5m", BASE_TIME, 10, &[]);
+
+    let conn = db.connection();
+    assert!(
+        TimeframeStatus::list(conn, PROVIDER, SYMBOL)
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        TimeframeStatus::list(conn, PROVIDER, "ETHUSDT")
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn list_reports_mixed_timeframes_with_counts() {
+    let mut db = setup();
+    insert_series(&mut db, SYMBOL, "1h", BASE_TIME, 4, &[]);
+    let mut candles = testutil::series(SYMBOL, "5m", BASE_TIME, 10, &[]);
+    candles[3].interpolated = 1;
+    candles[4].interpolated = 1;
+    db.insert_candles(&candles).unwrap();
+    // Autre symbole: non compté
+    insert_series(&mut db, "ETHUSDT", "5m", BASE_TIME, 7, &[]);
+
+    let conn = db.connection();
+    TimeframeStatus::update_progress(conn, PROVIDER, SYMBOL, "1h", BASE_TIME, BASE_TIME).unwrap();
+    TimeframeStatus::update_progress(
+        conn,
+        PROVIDER,
+        SYMBOL,
+        "5m",
+        BASE_TIME,
+        BASE_TIME + 9 * INTERVAL,
+    )
+    .unwrap();
+    TimeframeStatus::mark_complete(conn, PROVIDER, SYMBOL, "5m").unwrap();
+    // Statut sans bougie stockée
+    TimeframeStatus::mark_complete(conn, PROVIDER, SYMBOL, "1d").unwrap();
+
+    let rows = TimeframeStatus::list(conn, PROVIDER, SYMBOL).unwrap();
+    let summary: Vec<_> = rows
+        .iter()
+        .map(|r| {
+            (
+                r.timeframe.as_str(),
+                r.is_complete,
+                r.total_rows,
+                r.interpolated_rows,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![("5m", true, 10, 2), ("1h", false, 4, 0), ("1d", true, 0, 0)]
+    );
+    assert_eq!(rows[0].newest_candle_time, Some(BASE_TIME + 9 * INTERVAL));
+    assert_eq!(rows[2].oldest_candle_time, None);
+}
+
+#[test]
+fn list_all_covers_every_symbol() {
+    let mut db = setup();
+    insert_series(&mut db, SYMBOL, "5m", BASE_TIME, 3, &[]);
+    insert_series(&mut db, "ETHUSDT", "5m", BASE_TIME, 5, &[]);
+
+    let conn = db.connection();
+    for (symbol, timeframe) in [("ETHUSDT", "1h"), (SYMBOL, "5m"), ("ETHUSDT", "5m")] {
+        TimeframeStatus::update_progress(conn, PROVIDER, symbol, timeframe, BASE_TIME, BASE_TIME)
+            .unwrap();
+    }
+
+    let rows = TimeframeStatus::list_all(conn).unwrap();
+    let summary: Vec<_> = rows
+        .iter()
+        .map(|r| (r.symbol.as_str(), r.timeframe.as_str(), r.total_rows))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("BTCUSDT", "5m", 3),
+            ("ETHUSDT", "5m", 5),
+            ("ETHUSDT", "1h", 0)
+        ]
+    );
+    assert!(rows.iter().all(|r| r.provider == PROVIDER));
+    // list: mêmes lignes, restreintes au symbole
+    assert_eq!(
+        TimeframeStatus::list(conn, PROVIDER, "ETHUSDT").unwrap(),
+        rows[1..].to_vec()
+    );
+}
+
+#[test]
+fn list_incomplete_orders_by_priority() {
+    let db = setup();
+    let conn = db.connection();
+    // Complète: jamais listée
+    TimeframeStatus::update_progress(conn, PROVIDER, SYMBOL, "1d", BASE_TIME, BASE_TIME).unwrap();
+    TimeframeStatus::mark_complete(conn, PROVIDER, SYMBOL, "1d").unwrap();
+    // Longue histoire, puis courte
+    TimeframeStatus::update_progress(conn, PROVIDER, SYMBOL, "1h", BASE_TIME, BASE_TIME).unwrap();
+    TimeframeStatus::update_progress(
+        conn,
+        PROVIDER,
+        "ETHUSDT",
+        "5m",
+        BASE_TIME + 100 * INTERVAL,
+        BASE_TIME + 100 * INTERVAL,
+    )
+    .unwrap();
+    // Remise à zéro: aucune bougie récupérée
+    TimeframeStatus::update_progress(conn, PROVIDER, SYMBOL, "4h", BASE_TIME, BASE_TIME).unwrap();
+    TimeframeStatus::reset(conn, PROVIDER, SYMBOL, "4h").unwrap();
+    // Autre provider: ignoré
+    TimeframeStatus::update_progress(conn, "kraken", SYMBOL, "1h", BASE_TIME, BASE_TIME).unwrap();
+
+    assert_eq!(
+        TimeframeStatus::list_incomplete(conn, PROVIDER).unwrap(),
+        vec![
+            (SYMBOL.to_string(), "4h".to_string(), None),
+            (
+                "ETHUSDT".to_string(),
+                "5m".to_string(),
+                Some(BASE_TIME + 100 * INTERVAL)
+            ),
+            (SYMBOL.to_string(), "1h".to_string(), Some(BASE_TIME)),
+        ]
+    );
+}
+
+#[test]
+fn coverage_of_empty_and_single_candle_series() {
+    let mut db = setup();
+    assert_eq!(
+        TimeframeStatus::coverage(db.connection(), PROVIDER, SYMBOL, "5m").unwrap(),
+        Coverage::Empty
+    );
+
+    insert_series(&mut db, SYMBOL, "5m", BASE_TIME, 1, &[]);
+    assert_eq!(
+        TimeframeStatus::coverage(db.connection(), PROVIDER, SYMBOL, "5m").unwrap(),
+        Coverage::Series {
+            first: BASE_TIME,
+            last: BASE_TIME,
+            stored: 1,
+            expected: 1,
+            missing: 0,
+            gaps: 0,
+            interpolated: 0,
+            pct_complete: 100.0,
+        }
+    );
+
+    assert!(TimeframeStatus::coverage(db.connection(), PROVIDER, SYMBOL, "7m").is_err());
+}
+
+#[test]
+fn coverage_counts_known_gaps_and_interpolated_candles() {
+    let mut db = setup();
+    // 20 positions: trous de 2 (5, 6) et de 3 (11..14), bornes présentes
+    let mut candles = testutil::series(SYMBOL, "5m", BASE_TIME, 20, &[5, 6, 11, 12, 13]);
+    candles[1].interpolated = 1;
+    db.insert_candles(&candles).unwrap();
+
+    let Coverage::Series {
+        first,
+        last,
+        stored,
+        expected,
+        missing,
+        gaps,
+        interpolated,
+        pct_complete,
+    } = TimeframeStatus::coverage(db.connection(), PROVIDER, SYMBOL, "5m").unwrap()
+    else {
+        panic!("série non vide");
+    };
+    assert_eq!((first, last), (BASE_TIME, BASE_TIME + 19 * INTERVAL));
+    assert_eq!((stored, expected, missing, interpolated), (15, 20, 5, 1));
+    assert_eq!(gaps, 2);
+    assert!((pct_complete - 70.0).abs() < 1e-9);
+
+    // stats() (/api/stats, binaire status) lit la même couverture
+    let stats = db.stats(PROVIDER, SYMBOL, "5m").unwrap();
+    assert_eq!(
+        (stats.expected_count, stats.coverage_pct),
+        (20, pct_complete)
+    );
+}
+
+#[test]
+fn coverage_of_monthly_series_follows_calendar() {
+    let mut db = setup();
+    // 2024-01 à 2024-06 sans 2024-03 et 2024-04 (mois de 29 à 31 jours)
+    let months = [1704067200000, 1706745600000, 1714521600000, 1717200000000];
+    let candles: Vec<_> = months
+        .iter()
+        .map(|&open_time| testutil::candle(SYMBOL, "1M", open_time, 50_000.0))
+        .collect();
+    db.insert_candles(&candles).unwrap();
+
+    match TimeframeStatus::coverage(db.connection(), PROVIDER, SYMBOL, "1M").unwrap() {
+        Coverage::Series {
+            stored,
+            expected,
+            missing,
+            ..
+        } => assert_eq!((stored, expected, missing), (4, 6, 2)),
+        Coverage::Empty => panic!("série non vide"),
+    }
+}
+
+#[test]
+fn staleness_follows_last_update() {
+    let db = setup();
+    let conn = db.connection();
+    let hour = Duration::from_secs(3600);
+    // Jamais récupéré: périmé
+    assert!(TimeframeStatus::is_stale(conn, PROVIDER, SYMBOL, "5m", hour).unwrap());
+
+    TimeframeStatus::update_progress(conn, PROVIDER, SYMBOL, "5m", BASE_TIME, BASE_TIME).unwrap();
+    assert!(!TimeframeStatus::is_stale(conn, PROVIDER, SYMBOL, "5m", hour).unwrap());
+
+    conn.execute(
+        "UPDATE timeframe_status SET last_updated = last_updated - 2 * 3600000",
+        [],
+    )
+    .unwrap();
+    assert!(TimeframeStatus::is_stale(conn, PROVIDER, SYMBOL, "5m", hour).unwrap());
+}
+
+#[test]
+fn purging_every_candle_clears_completion() {
+    let mut db = setup();
+    let mut candles = testutil::series(SYMBOL, "5m", BASE_TIME, 10, &[]);
+    candles
+        .iter_mut()
+        .skip(1)
+        .take(8)
+        .for_each(|c| c.interpolated = 1);
+    db.insert_candles(&candles).unwrap();
+    let conn = db.connection();
+    TimeframeStatus::update_progress(
+        conn,
+        PROVIDER,
+        SYMBOL,
+        "5m",
+        BASE_TIME,
+        BASE_TIME + 9 * INTERVAL,
+    )
+    .unwrap();
+    TimeframeStatus::mark_complete(conn, PROVIDER, SYMBOL, "5m").unwrap();
+
+    // Purge des bougies synthétiques: bornes réelles inchangées, toujours complet
+    GapFiller::purge_interpolated(conn, PROVIDER, SYMBOL, "5m", 0, i64::MAX).unwrap();
+    let progress = TimeframeStatus::get(conn, PROVIDER, SYMBOL, "5m")
+        .unwrap()
+        .unwrap();
+    assert!(progress.is_complete);
+    assert_eq!(
+        (progress.oldest_candle_time, progress.newest_candle_time),
+        (Some(BASE_TIME), Some(BASE_TIME + 9 * INTERVAL))
+    );
+
+    // Purge de toute la série: plus rien ne peut être complet
+    db.prune(PROVIDER, SYMBOL, "5m", i64::MAX).unwrap();
+    let progress = TimeframeStatus::get(db.connection(), PROVIDER, SYMBOL, "5m")
+        .unwrap()
+        .unwrap();
+    assert!(!progress.is_complete);
+    assert_eq!(
+        (progress.oldest_candle_time, progress.newest_candle_time),
+        (None, None)
+    );
+}
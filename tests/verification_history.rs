@@ -0,0 +1,98 @@
+/// Tests de l'historique des vérifications (verification_history)
+///
+/// - Deux passages: du plus récent au plus ancien, compteurs du rapport
+/// - prune: seuls les passages antérieurs à la borne sont supprimés
+/// - Base sans table verification_runs: historique vide
+mod testutil;
+
+use rusqlite::Connection;
+use rust_candles_retriever::verification_history::VerificationHistory;
+use rust_candles_retriever::verify::{DEFAULT_FLATLINE_RUN, verify_spacing, verify_values};
+use testutil::{BASE_TIME, PROVIDER, insert_series, memory_db};
+
+const DAY_MS: i64 = 86_400_000;
+
+#[test]
+fn recent_runs_are_newest_first() {
+    let mut db = memory_db();
+    let mut candles = testutil::series("BTCUSDT", "5m", BASE_TIME, 10, &[4, 5]);
+    candles[1].interpolated = 1;
+    db.insert_candles(&candles).unwrap();
+    let conn = db.connection();
+
+    // Premier passage: un gap de 2 bougies
+    let mut report = verify_spacing(conn, PROVIDER, "BTCUSDT", "5m").unwrap();
+    report.values =
+        Some(verify_values(conn, PROVIDER, "BTCUSDT", "5m", DEFAULT_FLATLINE_RUN).unwrap());
+    VerificationHistory::record(conn, PROVIDER, "BTCUSDT", &report, BASE_TIME).unwrap();
+
+    // Second passage après comblement du gap
+    insert_series(&mut db, "BTCUSDT", "5m", BASE_TIME, 10, &[]);
+    let conn = db.connection();
+    let report = verify_spacing(conn, PROVIDER, "BTCUSDT", "5m").unwrap();
+    VerificationHistory::record(conn, PROVIDER, "BTCUSDT", &report, BASE_TIME + DAY_MS).unwrap();
+
+    let runs = VerificationHistory::recent(conn, "BTCUSDT", "5m", 10).unwrap();
+    let summary: Vec<_> = runs
+        .iter()
+        .map(|run| (run.run_at, run.gaps, run.interpolated_count, run.total))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![(BASE_TIME + DAY_MS, 0, 1, 10), (BASE_TIME, 1, 1, 8)]
+    );
+    assert!(runs[0].passed());
+    assert!(!runs[1].passed());
+
+    assert_eq!(
+        VerificationHistory::recent(conn, "BTCUSDT", "5m", 1).unwrap(),
+        runs[..1]
+    );
+    assert_eq!(
+        VerificationHistory::latest(conn, PROVIDER, "BTCUSDT", "5m").unwrap(),
+        Some(runs[0].clone())
+    );
+    assert!(
+        VerificationHistory::recent(conn, "BTCUSDT", "1h", 10)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn prune_removes_only_older_runs() {
+    let mut db = memory_db();
+    insert_series(&mut db, "BTCUSDT", "5m", BASE_TIME, 3, &[]);
+    let conn = db.connection();
+    let report = verify_spacing(conn, PROVIDER, "BTCUSDT", "5m").unwrap();
+    for day in 0..3 {
+        VerificationHistory::record(conn, PROVIDER, "BTCUSDT", &report, BASE_TIME + day * DAY_MS)
+            .unwrap();
+    }
+
+    // Borne exclusive: le passage du jour 1 est conservé
+    assert_eq!(
+        VerificationHistory::prune(conn, BASE_TIME + DAY_MS).unwrap(),
+        1
+    );
+    let kept: Vec<_> = VerificationHistory::recent(conn, "BTCUSDT", "5m", 10)
+        .unwrap()
+        .iter()
+        .map(|run| run.run_at)
+        .collect();
+    assert_eq!(kept, vec![BASE_TIME + 2 * DAY_MS, BASE_TIME + DAY_MS]);
+}
+
+#[test]
+fn database_without_history_table_has_no_runs() {
+    let conn = Connection::open_in_memory().unwrap();
+    assert!(
+        VerificationHistory::recent(&conn, "BTCUSDT", "5m", 10)
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        VerificationHistory::latest(&conn, PROVIDER, "BTCUSDT", "5m").unwrap(),
+        None
+    );
+}
@@ -0,0 +1,766 @@
+/// Tests de l'audit d'une base (verify::audit_database)
+///
+/// - Base saine: aucune anomalie, bougies interpolées seulement comptées
+/// - close_time corrompu volontairement sur une ligne: détecté
+/// - Bornes de timeframe_status différentes des bougies stockées: détectées
+/// - Rapport JSON de verify_data (verify_spacing): golden file
+///   tests/fixtures/verify_report.json
+/// - repair_overlaps: doublons alignés (base sans contrainte UNIQUE), bougie
+///   hors grille, bougie interpolée en concurrence avec une réelle
+/// - verify_values: une bougie par type d'anomalie, séries de bougies
+///   identiques, bougies interpolées comptées à part
+/// - verify_directory: toutes les séries d'un répertoire, fichier illisible
+///   signalé, même résultat quel que soit le nombre de threads
+/// - detect_price_outliers: pic et chute signalés, retour au prix normal,
+///   fenêtre incomplète et prix constants non signalés
+/// - verify_cross_timeframe: bougie 1h corrompue signalée, périodes
+///   concordantes, interpolées ou incomplètes non signalées
+/// - verify_interpolation: séries interpolées en début et en fin de série,
+///   série vide
+/// - compare_providers / audit_providers: deux providers, l'un décalé sur
+///   une bougie, divergence signalée et audit en échec
+/// - spot_check: champ modifié par l'API signalé, bougies plus servies et
+///   bougies interpolées ignorées
+mod testutil;
+
+use binance::model::KlineSummary;
+use rusqlite::{Connection, params};
+use rust_candles_retriever::database::{
+    DatabaseManager, SQL_CREATE_TABLE_CANDLESTICKS, SQL_CREATE_TABLE_TIMEFRAME_STATUS,
+    insert_candle_records,
+};
+use rust_candles_retriever::downtime::KnownDowntime;
+use rust_candles_retriever::kline_provider::MockKlineProvider;
+use rust_candles_retriever::models::CandleRecord;
+use rust_candles_retriever::rate_limiter::RateLimiter;
+use rust_candles_retriever::timeframe_status::TimeframeStatus;
+use rust_candles_retriever::utils::resample_candles;
+use rust_candles_retriever::verify::{
+    AnomalyCounts, AnomalyKind, CROSS_CHECK_TOLERANCE, CrossTimeframeMismatch,
+    DEFAULT_FLATLINE_RUN, Divergence, FileVerification, InterpolatedRun, OUTLIER_WINDOW,
+    PROVIDER_TOLERANCE, ValueAnomaly, VerificationSummary, audit_cross_timeframe, audit_database,
+    audit_providers, compare_providers, detect_price_outliers, parse_cross_check, repair_overlaps,
+    spot_check, verify_cross_timeframe, verify_directory, verify_interpolation, verify_spacing,
+    verify_values,
+};
+use std::path::PathBuf;
+use testutil::{BASE_TIME, PROVIDER, TempDir, candle, insert_series, memory_db, series};
+
+/// Série 5m de `count` bougies avec sa progression enregistrée
+fn backfilled_db(count: i64) -> DatabaseManager {
+    let mut db = memory_db();
+    let candles = insert_series(&mut db, "BTCUSDT", "5m", BASE_TIME, count, &[]);
+    TimeframeStatus::update_progress(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "5m",
+        candles[0].open_time,
+        candles[candles.len() - 1].open_time,
+    )
+    .unwrap();
+    db
+}
+
+#[test]
+fn healthy_database_passes_every_check() {
+    let mut db = backfilled_db(10);
+    db.insert_candles(&[CandleRecord {
+        interpolated: 1,
+        ..candle("BTCUSDT", "1h", BASE_TIME, 50_000.0)
+    }])
+    .unwrap();
+    // 1M: close_time à la fin du mois calendaire (2024-02), comme Binance
+    db.insert_candles(&[CandleRecord {
+        close_time: 1709251199999,
+        ..candle("BTCUSDT", "1M", 1706745600000, 50_000.0)
+    }])
+    .unwrap();
+
+    let report = audit_database(db.connection(), DEFAULT_FLATLINE_RUN).unwrap();
+
+    assert!(report.is_healthy(), "{:?}", report);
+    assert_eq!((report.total_rows, report.interpolated_rows), (12, 1));
+}
+
+#[test]
+fn corrupted_close_time_is_detected() {
+    let db = backfilled_db(10);
+    let corrupted_open_time = BASE_TIME + 4 * 300_000;
+    db.connection()
+        .execute(
+            "UPDATE candlesticks SET close_time = close_time + 1 WHERE open_time = ?1",
+            params![corrupted_open_time],
+        )
+        .unwrap();
+
+    let report = audit_database(db.connection(), DEFAULT_FLATLINE_RUN).unwrap();
+
+    assert!(!report.is_healthy());
+    assert_eq!(report.close_time_mismatches, 1);
+    let sample = &report.close_time_samples[0];
+    assert_eq!(sample.open_time, corrupted_open_time);
+    assert_eq!(sample.close_time, corrupted_open_time + 300_000);
+    assert_eq!(sample.expected_close_time, corrupted_open_time + 299_999);
+}
+
+#[test]
+fn status_bounds_must_match_stored_candles() {
+    let db = backfilled_db(10);
+    db.connection()
+        .execute(
+            "DELETE FROM candlesticks WHERE open_time = ?1",
+            params![BASE_TIME],
+        )
+        .unwrap();
+
+    let report = audit_database(db.connection(), DEFAULT_FLATLINE_RUN).unwrap();
+
+    assert_eq!(report.status_mismatches.len(), 1);
+    let status = &report.status_mismatches[0];
+    assert_eq!(status.status_oldest, Some(BASE_TIME));
+    assert_eq!(status.actual_oldest, Some(BASE_TIME + 300_000));
+
+    // Après un reset, oldest_candle_time est NULL: plus comparé
+    TimeframeStatus::reset(db.connection(), PROVIDER, "BTCUSDT", "5m").unwrap();
+    let report = audit_database(db.connection(), DEFAULT_FLATLINE_RUN).unwrap();
+    assert!(report.is_healthy(), "{:?}", report);
+}
+
+#[test]
+fn verification_json_matches_golden_file() {
+    let mut db = memory_db();
+    // Trous aux positions 3-4 et 10 (maintenance connue), bougie décalée
+    // d'une minute après la position 15 (overlap)
+    insert_series(&mut db, "BTCUSDT", "5m", BASE_TIME, 20, &[3, 4, 10]);
+    db.insert_candles(&[candle(
+        "BTCUSDT",
+        "5m",
+        BASE_TIME + 15 * 300_000 + 60_000,
+        50_000.0,
+    )])
+    .unwrap();
+    KnownDowntime::add(
+        db.connection(),
+        BASE_TIME + 10 * 300_000,
+        BASE_TIME + 11 * 300_000 - 1,
+        Some("maintenance"),
+    )
+    .unwrap();
+
+    let summary = VerificationSummary {
+        provider: PROVIDER.to_string(),
+        symbol: "BTCUSDT".to_string(),
+        db_file: "BTCUSDT.db".to_string(),
+        timeframes: ["5m", "1h"]
+            .iter()
+            .map(|tf| verify_spacing(db.connection(), PROVIDER, "BTCUSDT", tf).unwrap())
+            .collect(),
+        cross_checks: Vec::new(),
+    };
+    let json = serde_json::to_string_pretty(&summary).unwrap();
+
+    let golden =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/verify_report.json");
+    let expected = std::fs::read_to_string(&golden).unwrap();
+    assert_eq!(json, expected.trim_end(), "{}", golden.display());
+}
+
+/// 2024-01-01 00:00 UTC: aligné sur la grille de tous les timeframes < 1w
+const GRID_TIME: i64 = 1704067200000;
+
+/// open_time des bougies 5m restantes
+fn open_times(conn: &Connection) -> Vec<i64> {
+    conn.prepare("SELECT open_time FROM candlesticks WHERE timeframe = '5m' ORDER BY open_time")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<rusqlite::Result<_>>()
+        .unwrap()
+}
+
+#[test]
+fn repair_removes_duplicate_aligned_rows() {
+    // Base antérieure à la contrainte UNIQUE: doublons exacts possibles
+    let mut conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(&format!(
+        "{}; {};",
+        SQL_CREATE_TABLE_CANDLESTICKS
+            .replace(",\n    UNIQUE(provider, symbol, timeframe, open_time)", ""),
+        SQL_CREATE_TABLE_TIMEFRAME_STATUS
+    ))
+    .unwrap();
+    let candles: Vec<CandleRecord> = (0..5)
+        .map(|position| candle("BTCUSDT", "5m", GRID_TIME + position * 300_000, 50_000.0))
+        .collect();
+    let tx = conn.transaction().unwrap();
+    insert_candle_records(&tx, &candles).unwrap();
+    insert_candle_records(&tx, &candles).unwrap();
+    tx.commit().unwrap();
+    assert_eq!(open_times(&conn).len(), 10);
+
+    let report = repair_overlaps(&mut conn, PROVIDER, "BTCUSDT", "5m", false).unwrap();
+
+    assert_eq!((report.overlapping_periods, report.removed.len()), (5, 5));
+    let expected: Vec<i64> = candles.iter().map(|c| c.open_time).collect();
+    assert_eq!(open_times(&conn), expected);
+}
+
+#[test]
+fn repair_keeps_aligned_row_and_lone_off_grid_row() {
+    let mut db = memory_db();
+    let stray = GRID_TIME + 2 * 300_000 + 60_000;
+    let lone = GRID_TIME + 4 * 300_000 + 60_000;
+    let candles: Vec<CandleRecord> = [0, 1, 2, 3]
+        .iter()
+        .map(|position| GRID_TIME + position * 300_000)
+        .chain([stray, lone])
+        .map(|open_time| candle("BTCUSDT", "5m", open_time, 50_000.0))
+        .collect();
+    db.insert_candles(&candles).unwrap();
+
+    // Dry-run: rapport seul
+    let report = repair_overlaps(db.connection_mut(), PROVIDER, "BTCUSDT", "5m", true).unwrap();
+    assert_eq!(report.removed.len(), 1);
+    assert_eq!(open_times(db.connection()).len(), 6);
+
+    let report = repair_overlaps(db.connection_mut(), PROVIDER, "BTCUSDT", "5m", false).unwrap();
+    assert_eq!(report.overlapping_periods, 1);
+    assert_eq!(report.removed[0].open_time, stray);
+    assert_eq!(report.removed[0].kept_open_time, GRID_TIME + 2 * 300_000);
+    // Seule ligne de sa période: conservée bien que hors grille
+    assert!(open_times(db.connection()).contains(&lone));
+    assert_eq!(open_times(db.connection()).len(), 5);
+}
+
+#[test]
+fn repair_prefers_real_candle_over_interpolated_one() {
+    let mut db = memory_db();
+    let real_off_grid = GRID_TIME + 60_000;
+    db.insert_candles(&[
+        CandleRecord {
+            interpolated: 1,
+            ..candle("BTCUSDT", "5m", GRID_TIME, 50_000.0)
+        },
+        candle("BTCUSDT", "5m", real_off_grid, 50_100.0),
+    ])
+    .unwrap();
+
+    let report = repair_overlaps(db.connection_mut(), PROVIDER, "BTCUSDT", "5m", false).unwrap();
+
+    assert_eq!(report.removed.len(), 1);
+    assert!(report.removed[0].interpolated);
+    assert_eq!(open_times(db.connection()), vec![real_off_grid]);
+}
+
+/// 4 heures de bougies 5m depuis GRID_TIME, et les bougies 1h agrégées
+/// correspondantes, après `corrupt` (appliqué aux deux séries avant insertion)
+fn cross_timeframe_db(
+    corrupt: impl FnOnce(&mut Vec<CandleRecord>, &mut Vec<CandleRecord>),
+) -> DatabaseManager {
+    let mut db = memory_db();
+    let mut lower = series("BTCUSDT", "5m", GRID_TIME, 48, &[]);
+    let mut higher: Vec<CandleRecord> = resample_candles(&lower, "5m", "1h")
+        .unwrap()
+        .into_iter()
+        .map(|candle| CandleRecord {
+            derived_from: None,
+            ..candle
+        })
+        .collect();
+    corrupt(&mut lower, &mut higher);
+    db.insert_candles(&lower).unwrap();
+    db.insert_candles(&higher).unwrap();
+    db
+}
+
+#[test]
+fn cross_timeframe_mismatch_is_flagged() {
+    let hour = 3_600_000;
+    let db = cross_timeframe_db(|lower, higher| {
+        // Heure 1: close divergent (récupéré à un autre moment)
+        higher[1].close *= 1.01;
+        // Heure 2: divergente mais issue d'une bougie 5m interpolée
+        lower[30].interpolated = 1;
+        higher[2].volume *= 2.0;
+        // Heure 3: divergente mais période 5m incomplète
+        lower.remove(40);
+        higher[3].high += 100.0;
+    });
+
+    let mismatches = verify_cross_timeframe(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "5m",
+        "1h",
+        i64::MIN..=i64::MAX,
+        CROSS_CHECK_TOLERANCE,
+    )
+    .unwrap();
+    assert_eq!(
+        mismatches,
+        vec![CrossTimeframeMismatch {
+            provider: PROVIDER.to_string(),
+            symbol: "BTCUSDT".to_string(),
+            open_time: GRID_TIME + hour,
+            field: "close".to_string(),
+            resampled: 50_023.0,
+            stored: 50_023.0 * 1.01,
+        }]
+    );
+
+    // Plage limitée à l'heure 0: rien à signaler
+    let mismatches = verify_cross_timeframe(
+        db.connection(),
+        PROVIDER,
+        "BTCUSDT",
+        "5m",
+        "1h",
+        GRID_TIME..=GRID_TIME,
+        CROSS_CHECK_TOLERANCE,
+    )
+    .unwrap();
+    assert!(mismatches.is_empty());
+
+    // Tolérance au-delà de l'écart de 1 %: rien à signaler
+    let report = audit_cross_timeframe(db.connection(), "5m", "1h", 0.02).unwrap();
+    assert!(report.mismatches.is_empty());
+
+    let mut audit = audit_database(db.connection(), DEFAULT_FLATLINE_RUN).unwrap();
+    audit
+        .cross_checks
+        .push(audit_cross_timeframe(db.connection(), "5m", "1h", CROSS_CHECK_TOLERANCE).unwrap());
+    assert!(!audit.is_healthy());
+}
+
+#[test]
+fn consistent_timeframes_pass_cross_check() {
+    let db = cross_timeframe_db(|_, _| {});
+
+    let report = audit_cross_timeframe(db.connection(), "5m", "1h", CROSS_CHECK_TOLERANCE).unwrap();
+    assert!(report.mismatches.is_empty());
+
+    assert_eq!(
+        parse_cross_check("5m:1h").unwrap(),
+        ("5m".to_string(), "1h".to_string())
+    );
+    assert!(parse_cross_check("5m-1h").is_err());
+    assert!(parse_cross_check("5m:7x").is_err());
+    // 1h n'est pas un multiple de 45m
+    assert!(
+        verify_cross_timeframe(
+            db.connection(),
+            PROVIDER,
+            "BTCUSDT",
+            "45m",
+            "1h",
+            i64::MIN..=i64::MAX,
+            CROSS_CHECK_TOLERANCE,
+        )
+        .is_err()
+    );
+}
+
+/// Série 5m de 30 bougies valides, modifiée par `seed` avant insertion
+fn seeded_db(seed: impl FnOnce(&mut Vec<CandleRecord>)) -> DatabaseManager {
+    let mut db = memory_db();
+    let mut candles = series("BTCUSDT", "5m", BASE_TIME, 30, &[]);
+    seed(&mut candles);
+    db.insert_candles(&candles).unwrap();
+    db
+}
+
+/// Copie open, high, low, close et volume de `from` sur `positions`
+fn flatline(candles: &mut [CandleRecord], from: usize, positions: std::ops::RangeInclusive<usize>) {
+    let source = candles[from].clone();
+    for position in positions {
+        candles[position] = CandleRecord {
+            open_time: candles[position].open_time,
+            close_time: candles[position].close_time,
+            interpolated: candles[position].interpolated,
+            ..source.clone()
+        };
+    }
+}
+
+#[test]
+fn every_value_anomaly_class_is_detected() {
+    let db = seeded_db(|candles| {
+        // high < low (donc aussi sous le corps)
+        candles[1].high = candles[1].low - 1.0;
+        // high sous le close seulement
+        candles[3].high = candles[3].close - 50.0;
+        // low au-dessus de l'open
+        candles[5].low = candles[5].open + 10.0;
+        candles[7].volume = -1.0;
+        candles[9].low = 0.0;
+        // 4 bougies réelles identiques
+        flatline(candles, 12, 13..=15);
+        // 4 bougies interpolées identiques, puis une interpolée impossible
+        (20..=23).for_each(|position| candles[position].interpolated = 1);
+        flatline(candles, 20, 21..=23);
+        candles[25].interpolated = 1;
+        candles[25].volume = -5.0;
+        // 2 bougies identiques: sous le seuil
+        flatline(candles, 27, 28..=28);
+    });
+
+    let anomalies = verify_values(db.connection(), PROVIDER, "BTCUSDT", "5m", 3).unwrap();
+
+    assert_eq!(anomalies.checked_candles, 30);
+    assert_eq!(
+        anomalies.real,
+        AnomalyCounts {
+            high_below_low: 1,
+            high_below_body: 2,
+            low_above_body: 1,
+            negative_volume: 1,
+            zero_price: 1,
+            flatline_runs: 1,
+            flatline_candles: 4,
+        }
+    );
+    assert_eq!(
+        anomalies.interpolated,
+        AnomalyCounts {
+            negative_volume: 1,
+            flatline_runs: 1,
+            flatline_candles: 4,
+            ..AnomalyCounts::default()
+        }
+    );
+    assert!(anomalies.samples.contains(&ValueAnomaly {
+        open_time: BASE_TIME + 12 * 300_000,
+        kind: AnomalyKind::Flatline,
+        interpolated: false,
+        run_length: Some(4),
+    }));
+
+    // Seuil < 2: séries identiques non vérifiées
+    let anomalies = verify_values(db.connection(), PROVIDER, "BTCUSDT", "5m", 0).unwrap();
+    assert_eq!(
+        anomalies.real.flatline_runs + anomalies.interpolated.flatline_runs,
+        0
+    );
+
+    let report = audit_database(db.connection(), 3).unwrap();
+    assert!(!report.is_healthy());
+    assert_eq!(report.value_anomalies.len(), 1);
+    assert_eq!(report.value_anomalies[0].real.total(), 7);
+}
+
+#[test]
+fn interpolated_anomalies_do_not_fail_the_audit() {
+    let db = seeded_db(|candles| {
+        (10..=19).for_each(|position| candles[position].interpolated = 1);
+        flatline(candles, 10, 11..=19);
+    });
+
+    let report = audit_database(db.connection(), DEFAULT_FLATLINE_RUN).unwrap();
+
+    assert!(report.is_healthy(), "{:?}", report);
+    assert_eq!(report.value_anomalies.len(), 1);
+    assert_eq!(report.value_anomalies[0].interpolated.flatline_runs, 1);
+    assert_eq!(report.value_anomalies[0].real.total(), 0);
+
+    // Série saine: omise du rapport d'audit
+    let db = seeded_db(|_| {});
+    let report = audit_database(db.connection(), DEFAULT_FLATLINE_RUN).unwrap();
+    assert!(report.value_anomalies.is_empty());
+}
+
+#[test]
+fn price_spike_and_crash_are_outliers() {
+    let mut db = memory_db();
+    // Tendance régulière (close = 50000 + position): z ≈ 1.8 à chaque bougie
+    let mut candles = series("BTCUSDT", "5m", BASE_TIME, 60, &[]);
+    // Pic dans la fenêtre incomplète: non évalué
+    candles[5].close = 60_000.0;
+    candles[30].close = 55_000.0;
+    candles[52].close = 40_000.0;
+    db.insert_candles(&candles).unwrap();
+
+    let outliers = detect_price_outliers(db.connection(), PROVIDER, "BTCUSDT", "5m", 3.0).unwrap();
+
+    // Bougie 31: le pic de la bougie 30 est dans sa fenêtre, pas signalée
+    assert_eq!(
+        outliers
+            .iter()
+            .map(|outlier| outlier.open_time)
+            .collect::<Vec<_>>(),
+        vec![BASE_TIME + 30 * 300_000, BASE_TIME + 52 * 300_000]
+    );
+    assert!(outliers[0].z_score > 3.0);
+    assert_eq!(outliers[0].close, 55_000.0);
+    // Moyenne des closes 10 à 29
+    assert_eq!(outliers[0].rolling_mean, 50_019.5);
+    assert!(outliers[1].z_score < -3.0);
+    assert_eq!(OUTLIER_WINDOW, 20);
+
+    // Seuil au-dessus des z des pics: rien
+    let z_max = outliers
+        .iter()
+        .map(|outlier| outlier.z_score.abs())
+        .fold(0.0, f64::max);
+    assert!(
+        detect_price_outliers(db.connection(), PROVIDER, "BTCUSDT", "5m", z_max)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn constant_prices_have_no_outliers() {
+    let db = seeded_db(|candles| flatline(candles, 0, 1..=29));
+
+    assert!(
+        detect_price_outliers(db.connection(), PROVIDER, "BTCUSDT", "5m", 3.0)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn directory_verification_covers_every_series() {
+    let dir = TempDir::new("candles_verify_all");
+    let mut btc = DatabaseManager::new(dir.file("BTCUSDT.db").to_str().unwrap()).unwrap();
+    insert_series(&mut btc, "BTCUSDT", "1h", BASE_TIME, 3, &[]);
+    insert_series(&mut btc, "BTCUSDT", "5m", BASE_TIME, 12, &[]);
+    let mut eth = DatabaseManager::new(dir.file("ETHUSDT.db").to_str().unwrap()).unwrap();
+    let mut candles = series("ETHUSDT", "5m", BASE_TIME, 12, &[4, 5]);
+    candles[0].high = candles[0].low - 1.0;
+    eth.insert_candles(&candles).unwrap();
+    std::fs::write(dir.file("broken.db"), "pas une base").unwrap();
+    std::fs::write(dir.file("notes.txt"), "ignoré").unwrap();
+
+    let files = verify_directory(&dir.0, 2, DEFAULT_FLATLINE_RUN).unwrap();
+
+    let names: Vec<String> = files
+        .iter()
+        .map(|file| file.file.rsplit('/').next().unwrap().to_string())
+        .collect();
+    assert_eq!(names, ["BTCUSDT.db", "ETHUSDT.db", "broken.db"]);
+
+    // BTCUSDT: 5m puis 1h (ordre des durées), tout passe
+    assert!(files[0].passed(), "{:?}", files[0]);
+    let timeframes: Vec<&str> = files[0]
+        .series
+        .iter()
+        .map(|series| series.report.timeframe.as_str())
+        .collect();
+    assert_eq!(timeframes, ["5m", "1h"]);
+
+    // ETHUSDT: un gap de 2 bougies et une bougie impossible
+    assert!(!files[1].passed());
+    let report = &files[1].series[0].report;
+    assert_eq!(report.gaps.len(), 1);
+    assert_eq!(report.gaps[0].missing_candles, 2);
+    assert_eq!(report.values.as_ref().unwrap().real.high_below_low, 1);
+
+    assert!(files[2].error.is_some());
+    assert!(!files[2].passed());
+
+    // Un seul thread: même résultat
+    let sequential: Vec<FileVerification> =
+        verify_directory(&dir.0, 1, DEFAULT_FLATLINE_RUN).unwrap();
+    assert_eq!(sequential, files);
+}
+
+/// Kline de l'API (prix en texte) correspondant à une bougie stockée
+fn kline(candle: &CandleRecord) -> KlineSummary {
+    KlineSummary {
+        open_time: candle.open_time,
+        open: candle.open.to_string(),
+        high: candle.high.to_string(),
+        low: candle.low.to_string(),
+        close: candle.close.to_string(),
+        volume: candle.volume.to_string(),
+        close_time: candle.close_time,
+        quote_asset_volume: candle.quote_asset_volume.to_string(),
+        number_of_trades: candle.number_of_trades,
+        taker_buy_base_asset_volume: candle.taker_buy_base_asset_volume.to_string(),
+        taker_buy_quote_asset_volume: candle.taker_buy_quote_asset_volume.to_string(),
+    }
+}
+
+#[test]
+fn spot_check_reports_fields_changed_by_the_api() {
+    let mut db = memory_db();
+    let mut candles = series("BTCUSDT", "5m", BASE_TIME, 4, &[]);
+    candles[3].interpolated = 1;
+    db.insert_candles(&candles).unwrap();
+
+    // Une réponse par bougie réelle, dans l'ordre des open_time: la bougie
+    // interpolée n'est pas tirée (sinon les réponses seraient décalées)
+    let mut revised = kline(&candles[1]);
+    revised.close = "50100.5".to_string();
+    revised.number_of_trades = 999;
+    let source = MockKlineProvider::new([
+        vec![kline(&candles[0])],
+        vec![revised],
+        // Bougie plus servie: l'API renvoie la suivante
+        vec![kline(&candles[3])],
+    ]);
+
+    let divergences = spot_check(
+        db.connection(),
+        &source,
+        &RateLimiter::new(6000),
+        PROVIDER,
+        "BTCUSDT",
+        "5m",
+        10,
+    )
+    .unwrap();
+    assert_eq!(source.remaining(), 0);
+
+    let divergence = |field: &str, stored: f64, fresh: f64| Divergence {
+        provider: PROVIDER.to_string(),
+        symbol: "BTCUSDT".to_string(),
+        timeframe: "5m".to_string(),
+        open_time: candles[1].open_time,
+        field: field.to_string(),
+        stored,
+        fresh,
+    };
+    assert_eq!(
+        divergences,
+        vec![
+            divergence("close", 50_001.0, 50_100.5),
+            divergence("number_of_trades", 1000.0, 999.0),
+        ]
+    );
+}
+
+#[test]
+fn spot_check_skips_candles_no_longer_served() {
+    let mut db = memory_db();
+    insert_series(&mut db, "BTCUSDT", "5m", BASE_TIME, 3, &[]);
+    // Source vide: batch vide pour chaque bougie
+    let source = MockKlineProvider::default();
+
+    let divergences = spot_check(
+        db.connection(),
+        &source,
+        &RateLimiter::new(6000),
+        PROVIDER,
+        "BTCUSDT",
+        "5m",
+        2,
+    )
+    .unwrap();
+    assert!(divergences.is_empty());
+}
+
+/// Série 5m de 10 bougies, interpolées aux positions données
+fn partly_interpolated_db(interpolated: &[usize]) -> DatabaseManager {
+    let mut db = memory_db();
+    let mut candles = series("BTCUSDT", "5m", BASE_TIME, 10, &[]);
+    for &position in interpolated {
+        candles[position].interpolated = 1;
+    }
+    db.insert_candles(&candles).unwrap();
+    db
+}
+
+#[test]
+fn interpolated_runs_at_both_ends_of_the_series() {
+    let run = |first: i64, last: i64| InterpolatedRun {
+        first_open_time: BASE_TIME + first * 300_000,
+        last_open_time: BASE_TIME + last * 300_000,
+        length: last - first + 1,
+    };
+
+    // Série de fin la plus longue
+    let db = partly_interpolated_db(&[0, 1, 4, 7, 8, 9]);
+    let report = verify_interpolation(db.connection(), PROVIDER, "BTCUSDT", "5m").unwrap();
+    assert_eq!((report.total, report.interpolated), (10, 6));
+    assert_eq!(report.percentage, 60.0);
+    assert_eq!(report.longest_run, Some(run(7, 9)));
+    assert!(report.exceeds(50.0));
+    assert!(!report.exceeds(60.0));
+
+    // Série de début la plus longue, retenue aussi à longueur égale
+    let db = partly_interpolated_db(&[0, 1, 2, 5, 7, 8, 9]);
+    let report = verify_interpolation(db.connection(), PROVIDER, "BTCUSDT", "5m").unwrap();
+    assert_eq!(report.longest_run, Some(run(0, 2)));
+
+    // Aucune bougie interpolée, puis série vide
+    let db = partly_interpolated_db(&[]);
+    let report = verify_interpolation(db.connection(), PROVIDER, "BTCUSDT", "5m").unwrap();
+    assert_eq!((report.interpolated, report.longest_run), (0, None));
+    let report = verify_interpolation(db.connection(), PROVIDER, "BTCUSDT", "1h").unwrap();
+    assert_eq!((report.total, report.percentage), (0, 0.0));
+}
+
+#[test]
+fn shifted_provider_diverges() {
+    let mut db = memory_db();
+    let binance = series("BTCUSDT", "1h", BASE_TIME, 10, &[]);
+    // Même série chez kraken avec une bougie de plus, close décalé de 1 %
+    // en position 6 et bougie interpolée (ignorée) en position 2
+    let mut kraken: Vec<CandleRecord> = series("BTCUSDT", "1h", BASE_TIME, 11, &[])
+        .into_iter()
+        .map(|candle| CandleRecord {
+            provider: "kraken".to_string(),
+            ..candle
+        })
+        .collect();
+    kraken[6].close *= 1.01;
+    kraken[2].interpolated = 1;
+    kraken[2].close = 1.0;
+    db.insert_candles(&binance).unwrap();
+    db.insert_candles(&kraken).unwrap();
+
+    let comparison =
+        compare_providers(db.connection(), "BTCUSDT", "1h", &[], PROVIDER_TOLERANCE).unwrap();
+    let counts: Vec<_> = comparison
+        .providers
+        .iter()
+        .map(|count| (count.provider.as_str(), count.count))
+        .collect();
+    assert_eq!(counts, vec![("binance", 10), ("kraken", 11)]);
+
+    let pair = &comparison.pairs[0];
+    assert_eq!(
+        (pair.reference.as_str(), pair.other.as_str()),
+        ("binance", "kraken")
+    );
+    assert_eq!((pair.common_candles, pair.divergent_candles), (9, 1));
+    let shifted = &binance[6];
+    let diff = shifted.close * 0.01;
+    assert!((pair.mean_abs_close_diff - diff / 9.0).abs() < 1e-9);
+    let max = pair.max_divergence.unwrap();
+    assert_eq!(max.open_time, shifted.open_time);
+    assert_eq!(max.reference_close, shifted.close);
+    assert!(comparison.has_divergences());
+
+    // Tolérance au-delà du décalage: aucune divergence
+    let loose = compare_providers(
+        db.connection(),
+        "BTCUSDT",
+        "1h",
+        &["kraken".to_string(), "binance".to_string()],
+        0.02,
+    )
+    .unwrap();
+    assert_eq!(loose.pairs[0].reference, "kraken");
+    assert!(!loose.has_divergences());
+
+    let mut report = audit_database(db.connection(), DEFAULT_FLATLINE_RUN).unwrap();
+    report.provider_comparisons = audit_providers(db.connection(), PROVIDER_TOLERANCE).unwrap();
+    assert_eq!(report.provider_comparisons, vec![comparison]);
+    assert!(!report.is_healthy());
+}
+
+#[test]
+fn single_provider_series_are_not_compared() {
+    let mut db = memory_db();
+    insert_series(&mut db, "BTCUSDT", "1h", BASE_TIME, 5, &[]);
+    assert!(
+        audit_providers(db.connection(), PROVIDER_TOLERANCE)
+            .unwrap()
+            .is_empty()
+    );
+}
@@ -81,6 +81,7 @@ async function loadPairs() {
                     : app.availableTimeframes[app.availableTimeframes.length - 1];
 
                 updateTimeframeDisplay();
+                loadBackfillStatus(app.currentPair);
                 await loadCandles();
             }
         });
@@ -96,6 +97,7 @@ async function loadPairs() {
                 ? '1d'
                 : app.availableTimeframes[app.availableTimeframes.length - 1];
 
+            loadBackfillStatus(app.currentPair);
             await loadCandles();
         }
 
@@ -106,6 +108,27 @@ async function loadPairs() {
     }
 }
 
+// Couverture du backfill (GET /api/status) en infobulle du sélecteur de paire
+async function loadBackfillStatus(symbol) {
+    try {
+        const response = await fetch(`${API_BASE}/status?symbol=${encodeURIComponent(symbol)}`);
+        if (!response.ok) return;
+        const status = await response.json();
+
+        const lines = status.timeframes.map(tf => {
+            const since = tf.oldest_candle_date ? tf.oldest_candle_date.slice(0, 10) : '?';
+            const complete = tf.is_complete ? 'complete' : 'in progress';
+            return `${tf.timeframe}: ${tf.total_rows} candles since ${since} (${complete})`;
+        });
+        const option = document.querySelector(`#pairSelector option[value="${symbol}"]`);
+        if (option) {
+            option.title = lines.length > 0 ? lines.join('\n') : 'No backfill status';
+        }
+    } catch (error) {
+        console.error('Error loading backfill status:', error);
+    }
+}
+
 async function loadCandles(savedRange = null) {
     if (!app.currentPair) return;
 